@@ -44,14 +44,16 @@ export async function fetchGraphData(
   // never transferred when the client would only hide them (mirrors the server
   // gate, shrinking the dominant payload from ~26.6MB to the rendered set).
   const params = new URLSearchParams();
+  // Node metadata is read by its stored snake_case string keys throughout
+  // the renderer, so keep requesting the pre-typed metadata format.
+  params.set('string_metadata', 'true');
   if (graphTypeFilter && graphTypeFilter !== 'all') {
     params.set('graph_type', graphTypeFilter);
   }
   if (excludeLinkedPages) {
     params.set('exclude_linked_pages', 'true');
   }
-  const qs = params.toString();
-  const requestUrl = qs ? `/graph/data?${qs}` : '/graph/data';
+  const requestUrl = `/graph/data?${params.toString()}`;
 
   for (let attempt = 1; attempt <= maxRetries; attempt++) {
     try {
@@ -181,8 +183,8 @@ export function scheduleEmptyDataRetry(
   const INTERVAL_MS  = 15_000;
   const retryUrl =
     graphTypeFilter && graphTypeFilter !== 'all'
-      ? `/graph/data?graph_type=${encodeURIComponent(graphTypeFilter)}`
-      : '/graph/data';
+      ? `/graph/data?string_metadata=true&graph_type=${encodeURIComponent(graphTypeFilter)}`
+      : '/graph/data?string_metadata=true';
 
   if (attempt > MAX_ATTEMPTS) {
     logger.warn(`T6 empty-data retry: reached ${MAX_ATTEMPTS} attempts (${MAX_ATTEMPTS * INTERVAL_MS / 1000}s). Giving up.`);
@@ -591,6 +591,9 @@ export interface OpenAISettings {
   baseUrl?: string;
   timeout?: number;
   rateLimit?: number;
+  chatModel?: string;
+  embeddingModel?: string;
+  embedOnStartup?: boolean;
 }
 
 // Kokoro TTS settings
@@ -28,6 +28,32 @@ pub struct BroadcastNodePositions {
     pub positions: Vec<u8>,
 }
 
+/// Withhold `node_ids` from `client_id`'s position broadcasts for
+/// `window_ms`, after that client moved them itself.
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct SuppressPositionEcho {
+    pub client_id: usize,
+    pub node_ids: Vec<u32>,
+    pub window_ms: u64,
+}
+
+/// Ask for physics authority on `workspace`. The coordinator answers the
+/// client directly with `authority_granted` or `authority_denied`, and tells
+/// any preempted holder `authority_revoked`.
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct RequestPhysicsAuthority {
+    pub client_id: usize,
+    pub workspace: String,
+}
+
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct ReleasePhysicsAuthority {
+    pub client_id: usize,
+}
+
 #[derive(Message)]
 #[rtype(result = "Result<(), String>")]
 pub struct BroadcastMessage {
@@ -93,6 +119,25 @@ pub struct UpdateClientFilter {
     pub include_linked_pages: bool,
 }
 
+/// Per-session node budget: stream at most `max_nodes` nodes, keeping the
+/// neighbourhood of `focus_node` first. `None` clears either.
+#[derive(Message, Debug, Clone, Serialize, Deserialize)]
+#[rtype(result = "Result<(), String>")]
+pub struct SetNodeBudget {
+    pub client_id: usize,
+    pub max_nodes: Option<usize>,
+    pub focus_node: Option<u32>,
+}
+
+/// Per-session content language filter: only stream pages detected as one of
+/// `languages` (ISO 639-3 codes, `und` for undetected). `None` clears it.
+#[derive(Message, Debug, Clone, Serialize, Deserialize)]
+#[rtype(result = "Result<(), String>")]
+pub struct SetLanguageFilter {
+    pub client_id: usize,
+    pub languages: Option<Vec<String>>,
+}
+
 // ---------------------------------------------------------------------------
 // Client broadcast acknowledgement (end-to-end flow control)
 // ---------------------------------------------------------------------------
@@ -94,6 +94,16 @@ pub struct RemoveNode {
     pub node_id: u32,
 }
 
+/// Add a temporary visual aid (search-result marker, chat-answer ghost) that
+/// the graph removes again after `ttl_secs`. Ephemeral nodes are never
+/// persisted. Returns the assigned node id.
+#[derive(Message)]
+#[rtype(result = "Result<u32, String>")]
+pub struct AddEphemeralNode {
+    pub node: Node,
+    pub ttl_secs: u64,
+}
+
 #[derive(Message)]
 #[rtype(result = "Result<(), String>")]
 pub struct UpdateNodePosition {
@@ -189,6 +199,18 @@ pub struct RemoveNodeByMetadata {
     pub metadata_id: String,
 }
 
+/// Set `node.metadata[key]` on every node whose `metadata_id` is in `values`
+/// and clear `key` from all other nodes. Used for derived per-node flags
+/// (e.g. `broken_links`) that are recomputed wholesale by a background pass.
+/// Nodes whose value changed reach clients in a `graphDiff`. Returns the
+/// number of nodes carrying the flag.
+#[derive(Message)]
+#[rtype(result = "Result<usize, String>")]
+pub struct SetNodeMetadataFlag {
+    pub key: String,
+    pub values: HashMap<String, String>,
+}
+
 // ---------------------------------------------------------------------------
 // Graph update / reload
 // ---------------------------------------------------------------------------
@@ -268,6 +290,14 @@ pub struct UpdateWorkspace {
     pub request: UpdateWorkspaceRequest,
 }
 
+/// Merge-patch a workspace's settings overlay (`null` clears a key).
+#[derive(Message)]
+#[rtype(result = "Result<Workspace, String>")]
+pub struct PatchWorkspaceSettings {
+    pub workspace_id: String,
+    pub patch: serde_json::Value,
+}
+
 #[derive(Message)]
 #[rtype(result = "Result<(), String>")]
 pub struct DeleteWorkspace {
@@ -23,7 +23,8 @@ pub use graph_messages::{
     GetNodeTypeArrays, GetPositionFrameSnapshot, GetWorkspace, GetWorkspaceCount,
     GetWorkspaces, InitializeActor, LoadWorkspaces, NodeIdMapping, NodeTypeArrays,
     PositionFrameSnapshot, PositionRow, RefreshMetadata, ReloadGraphFromDatabase, RemoveEdge,
-    RemoveNode, RemoveNodeByMetadata, SaveWorkspaces, ToggleFavoriteWorkspace,
+    PatchWorkspaceSettings, RemoveNode, RemoveNodeByMetadata, SaveWorkspaces,
+    ToggleFavoriteWorkspace,
     UpdateGraphData, UpdateMetadata, UpdateNodeFromMetadata, UpdateNodePosition,
     UpdateNodePositions, UpdateNodeTypeArrays, UpdateWorkspace, WorkspaceChangeType,
     WorkspaceStateChanged,
@@ -79,8 +80,8 @@ pub use broadcast_messages::{
 pub use client_messages::{
     AuthenticateClient, BroadcastMessage, BroadcastNodePositions,
     ClientBroadcastAck, ForcePositionBroadcast, GetClientCount, InitialClientSync,
-    SendToClientBinary,
-    SendToClientText, UnregisterClient, UpdateClientFilter,
+    ReleasePhysicsAuthority, RequestPhysicsAuthority, SendToClientBinary,
+    SendToClientText, SetLanguageFilter, SetNodeBudget, SuppressPositionEcho, UnregisterClient, UpdateClientFilter,
 };
 
 // ---------------------------------------------------------------------------
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Play `cueId` from the `xr.audioCues` catalog at `position`.
+ */
+export type AudioCue = { cueId: string, nodeId: number, position: [number, number, number], };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A broker's decision on an enrichment proposal, as recorded for audit.
+ */
+export type EnrichmentDecision = { case_id: string, outcome: string, attributed: boolean, broker_pubkey: string | null, reasoning: string | null, writeback_triggered: boolean, activity_urn: string, proposal_urn: string | null, owner_did: string | null, decided_at_ms: number, };
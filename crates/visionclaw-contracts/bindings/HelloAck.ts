@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * `hello_ack` body: what was chosen from the client's lists.
+ */
+export type HelloAck = { protocolVersion: number, encoding: string, compression: string, capabilities: Array<string>, jsonSchema: number, server: Record<string, unknown>, xrAnchor?: Record<string, unknown>, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { HighlightReason } from "./HighlightReason";
+
+/**
+ * Nodes highlighted for the user, and conversation, that produced them.
+ */
+export type HighlightLayer = { id: number, owner: string, conversationId?: string, reason: HighlightReason, nodeIds: Array<number>, focusNode?: number, shared: boolean, 
+/**
+ * Unix ms.
+ */
+createdAt: number, expiresAt: number, };
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A fresh layout computed in the background, scored by normalised stress
+ * (0 is a perfect fit to graph distances).
+ */
+export type LayoutProposal = { id: string, 
+/**
+ * RFC 3339.
+ */
+createdAt: string, 
+/**
+ * RFC 3339; the proposal cannot be applied after this.
+ */
+expiresAt: string, nodeCount: number, currentStress: number, proposedStress: number, 
+/**
+ * `1 - proposedStress / currentStress`.
+ */
+improvement: number, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type PositionSubscription = { 
+/**
+ * Milliseconds (default 60), clamped to the rate limit.
+ */
+interval?: number, binary?: boolean, 
+/**
+ * Only nodes of these types are streamed.
+ */
+nodeTypes?: Array<string>, };
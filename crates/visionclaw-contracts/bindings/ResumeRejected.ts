@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Why `connection_established` fell back to a full load.
+ */
+export type ResumeRejected = "unknownToken" | "expired" | "gap";
@@ -0,0 +1,95 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AudioCue } from "./AudioCue";
+import type { EnrichmentDecision } from "./EnrichmentDecision";
+import type { GestureActionKind } from "./GestureActionKind";
+import type { GestureKind } from "./GestureKind";
+import type { GraphDiff } from "./GraphDiff";
+import type { HelloAck } from "./HelloAck";
+import type { HighlightAction } from "./HighlightAction";
+import type { HighlightLayer } from "./HighlightLayer";
+import type { LayoutProposal } from "./LayoutProposal";
+import type { MemoryFlash } from "./MemoryFlash";
+import type { NodeRef } from "./NodeRef";
+import type { OverviewNode } from "./OverviewNode";
+import type { QualityLevel } from "./QualityLevel";
+import type { ResumeRejected } from "./ResumeRejected";
+import type { SettingsSection } from "./SettingsSection";
+import type { SimulationAnomaly } from "./SimulationAnomaly";
+import type { SimulationMode } from "./SimulationMode";
+import type { StallIncident } from "./StallIncident";
+import type { VisitSummary } from "./VisitSummary";
+import type { WorldPoint } from "./WorldPoint";
+
+/**
+ * A text frame the server sends.
+ */
+export type ServerMessage = { "type": "connection_established", timestamp: number, is_reconnection: boolean, state_sync_sent: boolean, 
+/**
+ * Protocol versions, encodings and capabilities on offer.
+ */
+protocol: Record<string, unknown>, 
+/**
+ * Keep, with the latest `graphDiff` revision, to resume after a
+ * drop: `/wss?resume=<token>&revision=<n>`.
+ */
+resumeToken: string, revision: number, resumed: boolean, resumeRejected?: ResumeRejected, 
+/**
+ * Saved room anchor of the XR device named in the URL.
+ */
+xrAnchor?: Record<string, unknown>, } | { "type": "hello_ack", data: HelloAck, } | { "type": "hello_error", message: string, server?: Record<string, unknown>, } | { "type": "pong", timestamp: number, } | { "type": "state_sync", data: Record<string, unknown>, } | { "type": "loading", message: string, } | { "type": "initialDataInfo", message: string, flow: string, timestamp: number, } | { "type": "error", message: string, 
+/**
+ * Machine-readable reason, e.g. `gestureRejected`.
+ */
+code?: string, 
+/**
+ * The client message being handled when the error occurred.
+ */
+messageType?: string, 
+/**
+ * False when the session is about to close.
+ */
+recoverable?: boolean, details?: Record<string, unknown>, } | { "type": "rate_limit_warning", message: string, 
+/**
+ * Seconds.
+ */
+retry_after: number, } | { "type": "authenticate_success", pubkey: string, is_power_user: boolean, 
+/**
+ * Present when the client asked for an ephemeral session.
+ */
+ephemeral?: boolean, timestamp: number, } | { "type": "filter_update_success", enabled: boolean, timestamp: number, } | { "type": "subscription_confirmed", subscription: string, 
+/**
+ * Milliseconds, after clamping to the rate limit.
+ */
+interval: number, binary: boolean, timestamp: number, rate_limit: Record<string, unknown>, } | { "type": "botsGraphUpdate", data?: Record<string, unknown>, meta?: Record<string, unknown>, error?: string, timestamp: number, } | { "type": "botsUpdatesStarted", timestamp: number, } | { "type": "swarmTelemetry", timestamp: number, data_source: string, metrics: Record<string, unknown>, node_count: number, } | { "type": "nodeDragStartAck", data: NodeRef, timestamp: number, } | { "type": "nodeDragEndAck", data: NodeRef, timestamp: number, } | { "type": "authority_granted", workspace: string, policy: Record<string, unknown>, } | { "type": "authority_denied", message?: string, workspace?: string, policy?: Record<string, unknown>, holder?: number, } | { "type": "authority_revoked", workspace: string, reason: string, by?: number, } | { "type": "positionsRejected", rejected: number, accepted: number, } | { "type": "positions", nodes: Array<[number, number, number, number, number, number, number]>, } | { "type": "voice_ack", bytes: number, message: string, } | { "type": "ontology_validation_update", status: string, ontologyId?: string, 
+/**
+ * Set on broadcasts from a background validation job.
+ */
+jobId?: string, message?: string, violations?: number, inferredTriples?: number, constraints?: number, durationMs?: number, timestamp?: number, } | { "type": "ontology_constraint_update", status: string, message: string, timestamp: number, } | { "type": "ontology_reasoning_started", jobId: string, timestamp: number, } | { "type": "ontology_reasoning_error", message: string, timestamp: number, } | { "type": "graphDiff" } & GraphDiff | { "type": "sessionResumed", fromRevision: number, toRevision: number, 
+/**
+ * Each also carries `"type": "graphDiff"`, as first broadcast.
+ */
+diffs: Array<GraphDiff>, } | { "type": "hibernating", idleMs: number, } | { "type": "resumed", hibernatedMs: number, } | { "type": "visitSummary" } & VisitSummary | { "type": "layoutPlayback", enabled: boolean, intervalMs?: number, 
+/**
+ * Recorded frame times, oldest first.
+ */
+frames?: Array<number>, } | { "type": "layoutHistoryFrame", t: number, 
+/**
+ * `[id, x, y, z]` rows.
+ */
+nodes: Array<[number, number, number, number]>, } | { "type": "layoutSeeded", applied: number, unknown: number, rejected: number, totalNodes: number, } | { "type": "layoutProposal", proposal: LayoutProposal, } | { "type": "layoutProposalApplied", id: string, applied: number, transitionMs: number, } | { "type": "layoutProposalDismissed", id: string, } | { "type": "simulationControl", mode: SimulationMode, 
+/**
+ * Steps still to run while `stepping`.
+ */
+remaining?: number, singleSteps: number, } | { "type": "simulationQuality", level: QualityLevel, tickHz: number, collision: boolean, readbackEvery: number, } | { "type": "gestureAction", gesture: GestureKind, action: GestureActionKind, nodeId: number | null, position: WorldPoint | null, } | { "type": "overview", enabled: boolean, nodeCount?: number, nodes?: Array<OverviewNode>, 
+/**
+ * `[a, b, weight]` between coarse ids.
+ */
+edges?: Array<[number, number, number]>, } | { "type": "overviewFrame", 
+/**
+ * `[coarseId, x, y, z]` rows, each the centroid of its members.
+ */
+nodes: Array<[number, number, number, number]>, } | { "type": "nodeBudget", maxNodes: number | null, focusNodeId: number | null, } | { "type": "settingsPatch", section: SettingsSection, values: Record<string, unknown>, } | { "type": "set_language_filter_success", languages: Array<string> | null, } | { "type": "cameraRecordingStarted", recordingId: string, 
+/**
+ * RFC 3339.
+ */
+startedAt: string, } | { "type": "cameraRecordingStopped", recordingId: string, recording: Record<string, unknown>, } | { "type": "simulationPaused", reason: string, anomaly: SimulationAnomaly, } | { "type": "simulationStalled", recovering: boolean, incident: StallIncident, } | { "type": "settingsUpdated", category: string, settings?: Record<string, unknown>, updatedBy: string, timestamp: number, } | { "type": "audioCues", cues: Array<AudioCue>, timestamp: number, } | { "type": "highlightLayer", action: HighlightAction, layer: HighlightLayer, } | { "type": "memory_flash", data: MemoryFlash, } | { "type": "enrichment_decision", data: EnrichmentDecision, };
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { StallKind } from "./StallKind";
+
+export type StallIncident = { kind: StallKind, stalledMs: number, 
+/**
+ * Last iteration that completed before the stall.
+ */
+iteration: number, 
+/**
+ * Restart attempt this incident triggered, starting at 1.
+ */
+attempt: number, 
+/**
+ * RFC 3339.
+ */
+detectedAt: string, };
@@ -19,6 +19,8 @@
 //! - [`github_adapter`] — `ParsedMarkdown` boundary value-object between the
 //!   GitHub transport (Section 10) and the ontology domain (Section 8).
 //!   Canonical: ADR-10 §D11 + DDD-08.
+//! - [`socket_protocol`] — `ClientMessage` / `ServerMessage`, the JSON text
+//!   frames on the `/wss` graph socket (server ⇄ browser / XR client).
 //! - [`version`] — schema-version constants. Canonical: ADR-10 §D8.
 //!
 //! ## Versioning
@@ -45,6 +47,7 @@
 pub mod agent_action;
 pub mod enterprise;
 pub mod github_adapter;
+pub mod socket_protocol;
 pub mod telemetry;
 pub mod version;
 
@@ -75,3 +78,5 @@ pub use crate::enterprise::{
 };
 
 pub use crate::github_adapter::{ParseErrorKind, ParseErrorReport, ParsedMarkdown};
+
+pub use crate::socket_protocol::{ClientMessage, GraphDiff, ServerMessage};
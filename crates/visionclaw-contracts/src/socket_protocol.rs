@@ -0,0 +1,1394 @@
+//! `/wss` text-frame protocol — VisionClaw server ⇄ browser / XR client.
+//!
+//! Every JSON text frame on the graph socket is an object discriminated by
+//! its `type` field. [`ClientMessage`] is what the server routes (see
+//! `src/handlers/socket_flow_handler/message_routing.rs`); [`ServerMessage`]
+//! is everything the socket handlers, the client coordinator and the graph
+//! actor send back. Binary position frames are specified separately (the
+//! V3/V5 wire format in `visionclaw-protocol`) and are not covered here.
+//!
+//! The frontend imports these shapes from `@visionclaw/contracts`, together
+//! with the `CLIENT_MESSAGE_TYPES` / `SERVER_MESSAGE_TYPES` lists and the
+//! `encodeClientMessage` / `parseServerMessage` helpers. The
+//! `tests/socket_protocol_drift.rs` integration test fails when the server
+//! routes or emits a `type` missing here, or when the npm package lists fall
+//! out of step with [`ClientMessage::TYPES`] and [`ServerMessage::TYPES`].
+//!
+//! ## Conventions
+//!
+//! - Wire names are whatever the server has always used, so casing is mixed
+//!   (`request_full_snapshot`, `nodeDragStart`); fields likewise.
+//! - Optional fields are omitted rather than sent as `null`, and are `?:` in
+//!   TypeScript. Where `null` itself means something (clearing a budget or a
+//!   filter) the field says so.
+//! - Millisecond timestamps, revisions and counters are `number` in
+//!   TypeScript: they stay well below 2^53 and `JSON.parse` never produces a
+//!   `bigint`.
+//! - Open-ended payloads (settings sections, telemetry, recordings) are
+//!   `serde_json::Value` and `Record<string, unknown>` respectively; their
+//!   own schemas live with the REST API that serves the same data.
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "typescript-export")]
+use ts_rs::TS;
+
+/// A text frame the server accepts. A bare `"ping"` string (not JSON) is
+/// also accepted and answered with a bare `"pong"`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(
+    feature = "typescript-export",
+    derive(TS),
+    ts(export, export_to = "ClientMessage.ts")
+)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ClientMessage {
+    /// JSON heartbeat; answered with `pong`.
+    Ping {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional, type = "number"))]
+        timestamp: Option<u64>,
+    },
+    /// Per-session protocol negotiation; answered with `hello_ack` or
+    /// `hello_error`.
+    Hello {
+        data: HelloData,
+    },
+    /// Retired; physics parameters go through `POST /api/analytics/params`.
+    /// Always answered with `error`.
+    #[serde(rename = "update_physics_params")]
+    UpdatePhysicsParams {},
+    /// One binary position frame for `graphs` ("knowledge", "agent"), or
+    /// for every graph when absent.
+    #[serde(rename = "request_full_snapshot")]
+    RequestFullSnapshot {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        graphs: Option<Vec<String>>,
+    },
+    /// Legacy; answered with `initialDataInfo` pointing at the REST load.
+    RequestInitialData {},
+    EnableRandomization {
+        #[serde(default)]
+        enabled: bool,
+    },
+    RequestBotsGraph {},
+    RequestBotsPositions {},
+    #[serde(rename = "subscribe_position_updates")]
+    SubscribePositionUpdates {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        data: Option<PositionSubscription>,
+    },
+    /// Legacy alias for `subscribe_position_updates` at 60 ms, binary.
+    RequestPositionUpdates {},
+    /// Either a base64 NIP-98 `event`, or the legacy `token` + `pubkey`
+    /// pair. Answered with `authenticate_success` or `error`.
+    Authenticate {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        event: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        token: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        pubkey: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        ephemeral: Option<bool>,
+        /// Highlight pages created since the last visit (default true).
+        #[serde(rename = "highlightChanges")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        highlight_changes: Option<bool>,
+    },
+    #[serde(rename = "filter_update")]
+    FilterUpdate {
+        filter: GraphFilterUpdate,
+    },
+    RequestSwarmTelemetry {},
+    /// Latest validation report for `ontologyId` (default "default").
+    #[serde(rename = "ontology_validation")]
+    OntologyValidation {
+        #[serde(rename = "ontologyId")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        ontology_id: Option<String>,
+    },
+    /// Alias for `ontology_validation`.
+    #[serde(rename = "ontology_validation_report")]
+    OntologyValidationReport {
+        #[serde(rename = "ontologyId")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        ontology_id: Option<String>,
+    },
+    /// Acknowledged only; constraints are managed over REST.
+    #[serde(rename = "ontology_constraint_update")]
+    OntologyConstraintUpdate {},
+    /// Acknowledged only; constraints are managed over REST.
+    #[serde(rename = "ontology_constraint_toggle")]
+    OntologyConstraintToggle {},
+    #[serde(rename = "ontology_reasoning")]
+    OntologyReasoning {
+        #[serde(rename = "ontologyId")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional, type = "number"))]
+        ontology_id: Option<i64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        source: Option<String>,
+    },
+    NodeDragStart {
+        data: NodeDrag,
+    },
+    NodeDragUpdate {
+        data: NodeDrag,
+    },
+    NodeDragEnd {
+        data: NodeDrag,
+    },
+    CameraRecordingStart {},
+    CameraPose {
+        position: [f32; 3],
+        /// Quaternion `[x, y, z, w]`.
+        rotation: [f32; 4],
+        /// Degrees (default 60).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        fov: Option<f32>,
+    },
+    CameraRecordingStop {},
+    /// Local-physics clients only; answered with `authority_granted` or
+    /// `authority_denied`.
+    RequestAuthority {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        data: Option<AuthorityRequest>,
+    },
+    ReleaseAuthority {},
+    LayoutPlayback {
+        enabled: bool,
+    },
+    LayoutHistorySeek {
+        #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+        t: u64,
+    },
+    /// `[id, x, y, z]` rows; see `layoutSeeded`.
+    SeedLayout {
+        positions: Vec<[f64; 4]>,
+    },
+    /// Power users, or the sole connected client; see `layoutProposal`.
+    #[serde(rename_all = "camelCase")]
+    ApplyLayoutProposal {
+        id: String,
+        /// How long clients animate to the new layout (default 1500, at
+        /// most 10000).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional, type = "number"))]
+        transition_ms: Option<u64>,
+    },
+    /// Power users, or the sole connected client.
+    DismissLayoutProposal {
+        id: String,
+    },
+    /// Power users only.
+    SimulationControl {
+        action: SimulationAction,
+        /// For `step` (default 1).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        steps: Option<u32>,
+    },
+    /// Power users only.
+    SimulationQuality {
+        level: QualityLevel,
+    },
+    Gesture {
+        gesture: GestureKind,
+        phase: GesturePhase,
+        /// Graph-space ray; required for `start` and `move`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        ray: Option<Ray>,
+        /// Target the client already resolved; skips the server-side pick.
+        #[serde(rename = "nodeId")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        node_id: Option<u32>,
+    },
+    Overview {
+        enabled: bool,
+    },
+    RequestOverviewFrame {},
+    /// An absent field keeps its current value; `null` clears it.
+    NodeBudget {
+        #[serde(rename = "maxNodes")]
+        #[serde(default, skip_serializing_if = "Option::is_none", with = "nullable")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        max_nodes: Option<Option<u32>>,
+        #[serde(rename = "focusNodeId")]
+        #[serde(default, skip_serializing_if = "Option::is_none", with = "nullable")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        focus_node_id: Option<Option<u32>>,
+    },
+    /// Authenticated sessions only; `values` is a partial update exactly as
+    /// `PUT /api/settings/{section}` takes it.
+    SettingsPatch {
+        section: SettingsSection,
+        #[cfg_attr(feature = "typescript-export", ts(type = "Record<string, unknown>"))]
+        values: serde_json::Value,
+    },
+    /// ISO 639-3 codes (`und` for undetected); empty or `null` shows every
+    /// language.
+    #[serde(rename = "set_language_filter")]
+    SetLanguageFilter {
+        #[serde(default)]
+        languages: Option<Vec<String>>,
+    },
+}
+
+impl ClientMessage {
+    /// Every `type` the server routes, in routing order.
+    pub const TYPES: &'static [&'static str] = &[
+        "ping",
+        "hello",
+        "update_physics_params",
+        "request_full_snapshot",
+        "requestInitialData",
+        "enableRandomization",
+        "requestBotsGraph",
+        "requestBotsPositions",
+        "subscribe_position_updates",
+        "requestPositionUpdates",
+        "authenticate",
+        "filter_update",
+        "requestSwarmTelemetry",
+        "ontology_validation",
+        "ontology_validation_report",
+        "ontology_constraint_update",
+        "ontology_constraint_toggle",
+        "ontology_reasoning",
+        "nodeDragStart",
+        "nodeDragEnd",
+        "nodeDragUpdate",
+        "cameraRecordingStart",
+        "cameraPose",
+        "cameraRecordingStop",
+        "requestAuthority",
+        "releaseAuthority",
+        "layoutPlayback",
+        "layoutHistorySeek",
+        "seedLayout",
+        "applyLayoutProposal",
+        "dismissLayoutProposal",
+        "simulationControl",
+        "simulationQuality",
+        "gesture",
+        "overview",
+        "requestOverviewFrame",
+        "nodeBudget",
+        "settingsPatch",
+        "set_language_filter",
+    ];
+}
+
+/// A text frame the server sends.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(
+    feature = "typescript-export",
+    derive(TS),
+    ts(export, export_to = "ServerMessage.ts")
+)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ServerMessage {
+    /// First frame of every session.
+    #[serde(rename = "connection_established")]
+    ConnectionEstablished {
+        #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+        timestamp: i64,
+        is_reconnection: bool,
+        state_sync_sent: bool,
+        /// Protocol versions, encodings and capabilities on offer.
+        #[cfg_attr(feature = "typescript-export", ts(type = "Record<string, unknown>"))]
+        protocol: serde_json::Value,
+        /// Keep, with the latest `graphDiff` revision, to resume after a
+        /// drop: `/wss?resume=<token>&revision=<n>`.
+        #[serde(rename = "resumeToken")]
+        resume_token: String,
+        #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+        revision: u64,
+        resumed: bool,
+        #[serde(rename = "resumeRejected")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        resume_rejected: Option<ResumeRejected>,
+        /// Saved room anchor of the XR device named in the URL.
+        #[serde(rename = "xrAnchor")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(
+            feature = "typescript-export",
+            ts(optional, type = "Record<string, unknown>")
+        )]
+        xr_anchor: Option<serde_json::Value>,
+    },
+    #[serde(rename = "hello_ack")]
+    HelloAck {
+        data: HelloAck,
+    },
+    #[serde(rename = "hello_error")]
+    HelloError {
+        message: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(
+            feature = "typescript-export",
+            ts(optional, type = "Record<string, unknown>")
+        )]
+        server: Option<serde_json::Value>,
+    },
+    Pong {
+        #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+        timestamp: u64,
+    },
+    #[serde(rename = "state_sync")]
+    StateSync {
+        #[cfg_attr(feature = "typescript-export", ts(type = "Record<string, unknown>"))]
+        data: serde_json::Value,
+    },
+    Loading {
+        message: String,
+    },
+    InitialDataInfo {
+        message: String,
+        flow: String,
+        #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+        timestamp: i64,
+    },
+    Error {
+        message: String,
+        /// Machine-readable reason, e.g. `gestureRejected`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        code: Option<String>,
+        /// The client message being handled when the error occurred.
+        #[serde(rename = "messageType")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        message_type: Option<String>,
+        /// False when the session is about to close.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        recoverable: Option<bool>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(
+            feature = "typescript-export",
+            ts(optional, type = "Record<string, unknown>")
+        )]
+        details: Option<serde_json::Value>,
+    },
+    #[serde(rename = "rate_limit_warning")]
+    RateLimitWarning {
+        message: String,
+        /// Seconds.
+        #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+        retry_after: u64,
+    },
+    #[serde(rename = "authenticate_success")]
+    AuthenticateSuccess {
+        pubkey: String,
+        is_power_user: bool,
+        /// Present when the client asked for an ephemeral session.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        ephemeral: Option<bool>,
+        #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+        timestamp: i64,
+    },
+    #[serde(rename = "filter_update_success")]
+    FilterUpdateSuccess {
+        enabled: bool,
+        #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+        timestamp: i64,
+    },
+    #[serde(rename = "subscription_confirmed")]
+    SubscriptionConfirmed {
+        subscription: String,
+        /// Milliseconds, after clamping to the rate limit.
+        #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+        interval: u64,
+        binary: bool,
+        #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+        timestamp: i64,
+        #[cfg_attr(feature = "typescript-export", ts(type = "Record<string, unknown>"))]
+        rate_limit: serde_json::Value,
+    },
+    BotsGraphUpdate {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(
+            feature = "typescript-export",
+            ts(optional, type = "Record<string, unknown>")
+        )]
+        data: Option<serde_json::Value>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(
+            feature = "typescript-export",
+            ts(optional, type = "Record<string, unknown>")
+        )]
+        meta: Option<serde_json::Value>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        error: Option<String>,
+        #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+        timestamp: i64,
+    },
+    BotsUpdatesStarted {
+        #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+        timestamp: i64,
+    },
+    SwarmTelemetry {
+        #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+        timestamp: i64,
+        data_source: String,
+        #[cfg_attr(feature = "typescript-export", ts(type = "Record<string, unknown>"))]
+        metrics: serde_json::Value,
+        node_count: u32,
+    },
+    NodeDragStartAck {
+        data: NodeRef,
+        #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+        timestamp: i64,
+    },
+    NodeDragEndAck {
+        data: NodeRef,
+        #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+        timestamp: i64,
+    },
+    #[serde(rename = "authority_granted")]
+    AuthorityGranted {
+        workspace: String,
+        #[cfg_attr(feature = "typescript-export", ts(type = "Record<string, unknown>"))]
+        policy: serde_json::Value,
+    },
+    /// Either `message` (the session cannot hold authority at all) or
+    /// `workspace`, `policy` and the current `holder`.
+    #[serde(rename = "authority_denied")]
+    AuthorityDenied {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        message: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        workspace: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(
+            feature = "typescript-export",
+            ts(optional, type = "Record<string, unknown>")
+        )]
+        policy: Option<serde_json::Value>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional, type = "number"))]
+        holder: Option<u64>,
+    },
+    /// `reason` is "released" or "preempted"; `by` names the preempting
+    /// client.
+    #[serde(rename = "authority_revoked")]
+    AuthorityRevoked {
+        workspace: String,
+        reason: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional, type = "number"))]
+        by: Option<u64>,
+    },
+    /// Some pushed local-physics positions were out of bounds or unknown.
+    PositionsRejected {
+        rejected: u32,
+        accepted: u32,
+    },
+    /// JSON position frame for sessions that negotiated the `json`
+    /// encoding: `[id, x, y, z, vx, vy, vz]` rows.
+    Positions {
+        nodes: Vec<[f64; 7]>,
+    },
+    #[serde(rename = "voice_ack")]
+    VoiceAck {
+        bytes: u32,
+        message: String,
+    },
+    /// `status` is "completed", "not_found", "error" or "unavailable"; the
+    /// counts accompany "completed" only.
+    #[serde(rename = "ontology_validation_update")]
+    OntologyValidationUpdate {
+        status: String,
+        #[serde(rename = "ontologyId")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        ontology_id: Option<String>,
+        /// Set on broadcasts from a background validation job.
+        #[serde(rename = "jobId")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        job_id: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        message: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        violations: Option<u32>,
+        #[serde(rename = "inferredTriples")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        inferred_triples: Option<u32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        constraints: Option<u32>,
+        #[serde(rename = "durationMs")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional, type = "number"))]
+        duration_ms: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional, type = "number"))]
+        timestamp: Option<i64>,
+    },
+    #[serde(rename = "ontology_constraint_update")]
+    OntologyConstraintUpdate {
+        status: String,
+        message: String,
+        #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+        timestamp: i64,
+    },
+    #[serde(rename = "ontology_reasoning_started")]
+    OntologyReasoningStarted {
+        #[serde(rename = "jobId")]
+        job_id: String,
+        #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+        timestamp: i64,
+    },
+    #[serde(rename = "ontology_reasoning_error")]
+    OntologyReasoningError {
+        message: String,
+        #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+        timestamp: i64,
+    },
+    GraphDiff(GraphDiff),
+    /// Diffs missed while disconnected, oldest first, followed by a binary
+    /// position snapshot.
+    #[serde(rename_all = "camelCase")]
+    SessionResumed {
+        #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+        from_revision: u64,
+        #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+        to_revision: u64,
+        /// Each also carries `"type": "graphDiff"`, as first broadcast.
+        diffs: Vec<GraphDiff>,
+    },
+    /// Idle session; position frames stop until the client sends a text or
+    /// binary frame.
+    #[serde(rename_all = "camelCase")]
+    Hibernating {
+        #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+        idle_ms: u64,
+    },
+    /// Woken from hibernation; `state_sync` and a snapshot follow.
+    #[serde(rename_all = "camelCase")]
+    Resumed {
+        #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+        hibernated_ms: u64,
+    },
+    VisitSummary(VisitSummary),
+    #[serde(rename_all = "camelCase")]
+    LayoutPlayback {
+        enabled: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional, type = "number"))]
+        interval_ms: Option<u64>,
+        /// Recorded frame times, oldest first.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional, type = "Array<number>"))]
+        frames: Option<Vec<u64>>,
+    },
+    LayoutHistoryFrame {
+        #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+        t: u64,
+        /// `[id, x, y, z]` rows.
+        nodes: Vec<[f64; 4]>,
+    },
+    #[serde(rename_all = "camelCase")]
+    LayoutSeeded {
+        applied: u32,
+        unknown: u32,
+        rejected: u32,
+        total_nodes: u32,
+    },
+    /// Broadcast when a background re-layout beats the current layout.
+    LayoutProposal {
+        proposal: LayoutProposal,
+    },
+    /// Broadcast; animate to the new positions over `transitionMs` rather
+    /// than snapping to them.
+    #[serde(rename_all = "camelCase")]
+    LayoutProposalApplied {
+        id: String,
+        applied: u32,
+        #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+        transition_ms: u64,
+    },
+    /// Broadcast when a proposal is dismissed or replaced by a newer one.
+    LayoutProposalDismissed {
+        id: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    SimulationControl {
+        mode: SimulationMode,
+        /// Steps still to run while `stepping`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        remaining: Option<u32>,
+        #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+        single_steps: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    SimulationQuality {
+        level: QualityLevel,
+        tick_hz: u32,
+        collision: bool,
+        readback_every: u32,
+    },
+    /// Answer to a gesture `start`; `nodeId` and `position` are `null` when
+    /// nothing was picked.
+    #[serde(rename_all = "camelCase")]
+    GestureAction {
+        gesture: GestureKind,
+        action: GestureActionKind,
+        node_id: Option<u32>,
+        position: Option<WorldPoint>,
+    },
+    /// The coarse graph when `enabled`; just `enabled: false` otherwise.
+    #[serde(rename_all = "camelCase")]
+    Overview {
+        enabled: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        node_count: Option<u32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        nodes: Option<Vec<OverviewNode>>,
+        /// `[a, b, weight]` between coarse ids.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "typescript-export", ts(optional))]
+        edges: Option<Vec<[f64; 3]>>,
+    },
+    /// Answer to `requestOverviewFrame`. Ids are coarse ids from `overview`,
+    /// never graph node ids, so this is not a position frame.
+    OverviewFrame {
+        /// `[coarseId, x, y, z]` rows, each the centroid of its members.
+        nodes: Vec<[f64; 4]>,
+    },
+    #[serde(rename_all = "camelCase")]
+    NodeBudget {
+        max_nodes: Option<u32>,
+        focus_node_id: Option<u32>,
+    },
+    /// The section as it now stands.
+    SettingsPatch {
+        section: SettingsSection,
+        #[cfg_attr(feature = "typescript-export", ts(type = "Record<string, unknown>"))]
+        values: serde_json::Value,
+    },
+    #[serde(rename = "set_language_filter_success")]
+    SetLanguageFilterSuccess {
+        languages: Option<Vec<String>>,
+    },
+    #[serde(rename_all = "camelCase")]
+    CameraRecordingStarted {
+        recording_id: String,
+        /// RFC 3339.
+        started_at: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    CameraRecordingStopped {
+        recording_id: String,
+        #[cfg_attr(feature = "typescript-export", ts(type = "Record<string, unknown>"))]
+        recording: serde_json::Value,
+    },
+    /// Broadcast when the simulation pauses itself on an unstable frame.
+    SimulationPaused {
+        reason: String,
+        anomaly: SimulationAnomaly,
+    },
+    /// Broadcast when no physics step has completed for a while;
+    /// `recovering` is false once the server has stopped restarting the
+    /// pipeline.
+    SimulationStalled {
+        recovering: bool,
+        incident: StallIncident,
+    },
+    /// Broadcast after a settings write over REST. `settings` accompanies
+    /// the `nodeFilter` category; other categories are re-fetched.
+    #[serde(rename_all = "camelCase")]
+    SettingsUpdated {
+        category: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(
+            feature = "typescript-export",
+            ts(optional, type = "Record<string, unknown>")
+        )]
+        settings: Option<serde_json::Value>,
+        updated_by: String,
+        #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+        timestamp: i64,
+    },
+    /// Sent only to users who turned audio cues on.
+    AudioCues {
+        cues: Vec<AudioCue>,
+        #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+        timestamp: i64,
+    },
+    /// An `add` for a layer id the client already has replaces it.
+    HighlightLayer {
+        action: HighlightAction,
+        layer: HighlightLayer,
+    },
+    #[serde(rename = "memory_flash")]
+    MemoryFlash {
+        data: MemoryFlash,
+    },
+    #[serde(rename = "enrichment_decision")]
+    EnrichmentDecision {
+        data: EnrichmentDecision,
+    },
+}
+
+impl ServerMessage {
+    /// Every `type` the server sends.
+    pub const TYPES: &'static [&'static str] = &[
+        "connection_established",
+        "hello_ack",
+        "hello_error",
+        "pong",
+        "state_sync",
+        "loading",
+        "initialDataInfo",
+        "error",
+        "rate_limit_warning",
+        "authenticate_success",
+        "filter_update_success",
+        "subscription_confirmed",
+        "botsGraphUpdate",
+        "botsUpdatesStarted",
+        "swarmTelemetry",
+        "nodeDragStartAck",
+        "nodeDragEndAck",
+        "authority_granted",
+        "authority_denied",
+        "authority_revoked",
+        "positionsRejected",
+        "positions",
+        "voice_ack",
+        "ontology_validation_update",
+        "ontology_constraint_update",
+        "ontology_reasoning_started",
+        "ontology_reasoning_error",
+        "graphDiff",
+        "sessionResumed",
+        "hibernating",
+        "resumed",
+        "visitSummary",
+        "layoutPlayback",
+        "layoutHistoryFrame",
+        "layoutSeeded",
+        "layoutProposal",
+        "layoutProposalApplied",
+        "layoutProposalDismissed",
+        "simulationControl",
+        "simulationQuality",
+        "gestureAction",
+        "overview",
+        "overviewFrame",
+        "nodeBudget",
+        "settingsPatch",
+        "set_language_filter_success",
+        "cameraRecordingStarted",
+        "cameraRecordingStopped",
+        "simulationPaused",
+        "simulationStalled",
+        "settingsUpdated",
+        "audioCues",
+        "highlightLayer",
+        "memory_flash",
+        "enrichment_decision",
+    ];
+}
+
+// ---------------------------------------------------------------------------
+// Payload types
+// ---------------------------------------------------------------------------
+
+/// `hello` body. Lists are in client preference order.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+#[serde(rename_all = "camelCase")]
+pub struct HelloData {
+    #[serde(default)]
+    pub protocol_versions: Vec<u32>,
+    #[serde(default)]
+    pub encodings: Vec<String>,
+    #[serde(default)]
+    pub compression: Vec<String>,
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript-export", ts(optional))]
+    pub json_schema: Option<u32>,
+    /// "camel" or "snake".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript-export", ts(optional))]
+    pub field_casing: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript-export", ts(optional))]
+    pub string_metadata: Option<bool>,
+    /// BCP 47; picks native or romanized node labels.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript-export", ts(optional))]
+    pub locale: Option<String>,
+    /// XR device id; the ack then carries its saved room anchor.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript-export", ts(optional))]
+    pub device_id: Option<String>,
+}
+
+/// `hello_ack` body: what was chosen from the client's lists.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+#[serde(rename_all = "camelCase")]
+pub struct HelloAck {
+    pub protocol_version: u32,
+    pub encoding: String,
+    pub compression: String,
+    pub capabilities: Vec<String>,
+    pub json_schema: u32,
+    #[cfg_attr(feature = "typescript-export", ts(type = "Record<string, unknown>"))]
+    pub server: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(
+        feature = "typescript-export",
+        ts(optional, type = "Record<string, unknown>")
+    )]
+    pub xr_anchor: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+#[serde(rename_all = "camelCase")]
+pub struct PositionSubscription {
+    /// Milliseconds (default 60), clamped to the rate limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript-export", ts(optional))]
+    pub interval: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript-export", ts(optional))]
+    pub binary: Option<bool>,
+    /// Only nodes of these types are streamed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript-export", ts(optional))]
+    pub node_types: Option<Vec<String>>,
+}
+
+/// Partial graph filter; absent fields keep their current value.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+pub struct GraphFilterUpdate {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript-export", ts(optional))]
+    pub enabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript-export", ts(optional))]
+    pub quality_threshold: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript-export", ts(optional))]
+    pub authority_threshold: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript-export", ts(optional))]
+    pub filter_by_quality: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript-export", ts(optional))]
+    pub filter_by_authority: Option<bool>,
+    /// "or" or "and".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript-export", ts(optional))]
+    pub filter_mode: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript-export", ts(optional))]
+    pub max_nodes: Option<u32>,
+    #[serde(rename = "includeLinkedPages")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript-export", ts(optional))]
+    pub include_linked_pages: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+pub struct WorldPoint {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+#[serde(rename_all = "camelCase")]
+pub struct NodeDrag {
+    pub node_id: u32,
+    /// Omitted on `nodeDragEnd`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript-export", ts(optional))]
+    pub position: Option<WorldPoint>,
+    /// Client clock, milliseconds; lets the server drop stale updates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript-export", ts(optional, type = "number"))]
+    pub timestamp: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+#[serde(rename_all = "camelCase")]
+pub struct NodeRef {
+    pub node_id: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+pub struct AuthorityRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript-export", ts(optional))]
+    pub workspace: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+pub struct Ray {
+    pub origin: [f32; 3],
+    pub direction: [f32; 3],
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+#[serde(rename_all = "camelCase")]
+pub enum SimulationAction {
+    Pause,
+    Resume,
+    Step,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+#[serde(rename_all = "camelCase")]
+pub enum SimulationMode {
+    Running,
+    Paused,
+    Stepping,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+#[serde(rename_all = "camelCase")]
+pub enum QualityLevel {
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+#[serde(rename_all = "camelCase")]
+pub enum GestureKind {
+    Pinch,
+    Grab,
+    Point,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+#[serde(rename_all = "camelCase")]
+pub enum GesturePhase {
+    Start,
+    Move,
+    End,
+}
+
+/// What `xr.gestures` maps a gesture to.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+#[serde(rename_all = "camelCase")]
+pub enum GestureActionKind {
+    Select,
+    Drag,
+    Focus,
+    None,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+#[serde(rename_all = "camelCase")]
+pub enum SettingsSection {
+    Physics,
+    Rendering,
+    Visual,
+}
+
+/// Why `connection_established` fell back to a full load.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+#[serde(rename_all = "camelCase")]
+pub enum ResumeRejected {
+    UnknownToken,
+    Expired,
+    Gap,
+}
+
+/// Incremental graph change, stamped with the graph revision it produces.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+pub struct GraphDiff {
+    /// e.g. "ephemeral_added", "ephemeral_expired", "metadata_updated".
+    pub reason: String,
+    /// Nodes in the session's JSON node schema.
+    #[cfg_attr(
+        feature = "typescript-export",
+        ts(type = "Array<Record<string, unknown>>")
+    )]
+    pub added: Vec<serde_json::Value>,
+    pub removed: Vec<u32>,
+    /// Existing nodes whose metadata changed, in the same schema as
+    /// `added`; replace them by id.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(
+        feature = "typescript-export",
+        ts(optional, type = "Array<Record<string, unknown>>")
+    )]
+    pub updated: Option<Vec<serde_json::Value>>,
+    #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+    pub revision: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+#[serde(rename_all = "camelCase")]
+pub struct VisitSummary {
+    #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+    pub revision: u64,
+    #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+    pub since_revision: u64,
+    /// RFC 3339.
+    pub since: Option<String>,
+    pub created: Vec<String>,
+    pub edited: Vec<String>,
+    pub removed: Vec<String>,
+    pub new_clusters: Vec<Vec<String>>,
+    pub link_changes: u32,
+    pub counts: VisitCounts,
+    pub new_node_ids: Vec<u32>,
+    pub truncated: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript-export", ts(optional, type = "number"))]
+    pub highlight_id: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+pub struct VisitCounts {
+    pub created: u32,
+    pub edited: u32,
+    pub removed: u32,
+}
+
+/// A fresh layout computed in the background, scored by normalised stress
+/// (0 is a perfect fit to graph distances).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+#[serde(rename_all = "camelCase")]
+pub struct LayoutProposal {
+    pub id: String,
+    /// RFC 3339.
+    pub created_at: String,
+    /// RFC 3339; the proposal cannot be applied after this.
+    pub expires_at: String,
+    pub node_count: u32,
+    pub current_stress: f32,
+    pub proposed_stress: f32,
+    /// `1 - proposedStress / currentStress`.
+    pub improvement: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+pub struct OverviewNode {
+    pub id: u32,
+    pub members: u32,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+#[serde(rename_all = "camelCase")]
+pub enum AnomalyKind {
+    NonFinitePosition,
+    RunawayVelocity,
+    EnergySpike,
+}
+
+/// The frame that made the simulation pause itself.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationAnomaly {
+    pub kind: AnomalyKind,
+    /// "nan_positions", "runaway_velocity" or "energy_spike".
+    pub reason: String,
+    pub detail: String,
+    /// The first few offending nodes; the snapshot has them all.
+    pub node_ids: Vec<u32>,
+    /// Unix ms.
+    #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+    pub detected_at: i64,
+    /// Snapshot file id, once written.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript-export", ts(optional))]
+    pub snapshot: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+#[serde(rename_all = "camelCase")]
+pub enum StallKind {
+    /// No step reply at all.
+    NoReply,
+    /// Replies keep arriving but every step was skipped.
+    SkippedSteps,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+#[serde(rename_all = "camelCase")]
+pub struct StallIncident {
+    pub kind: StallKind,
+    #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+    pub stalled_ms: u64,
+    /// Last iteration that completed before the stall.
+    #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+    pub iteration: u64,
+    /// Restart attempt this incident triggered, starting at 1.
+    pub attempt: u32,
+    /// RFC 3339.
+    pub detected_at: String,
+}
+
+/// Play `cueId` from the `xr.audioCues` catalog at `position`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+#[serde(rename_all = "camelCase")]
+pub struct AudioCue {
+    pub cue_id: String,
+    pub node_id: u32,
+    pub position: [f32; 3],
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+#[serde(rename_all = "camelCase")]
+pub enum HighlightAction {
+    Add,
+    Remove,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+#[serde(rename_all = "camelCase")]
+pub enum HighlightReason {
+    Citation,
+    Focus,
+    Manual,
+    SinceLastVisit,
+}
+
+/// Nodes highlighted for the user, and conversation, that produced them.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+#[serde(rename_all = "camelCase")]
+pub struct HighlightLayer {
+    #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+    pub id: u64,
+    pub owner: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript-export", ts(optional))]
+    pub conversation_id: Option<String>,
+    pub reason: HighlightReason,
+    pub node_ids: Vec<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript-export", ts(optional))]
+    pub focus_node: Option<u32>,
+    pub shared: bool,
+    /// Unix ms.
+    #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+    pub created_at: i64,
+    #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+    pub expires_at: i64,
+}
+
+/// An agent touched a memory entry.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+pub struct MemoryFlash {
+    pub key: String,
+    pub namespace: String,
+    /// "store", "search", "retrieve", "delete", "update" or "access".
+    pub action: String,
+    #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+    pub timestamp: u64,
+}
+
+/// A broker's decision on an enrichment proposal, as recorded for audit.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "typescript-export", derive(TS), ts(export))]
+pub struct EnrichmentDecision {
+    pub case_id: String,
+    pub outcome: String,
+    pub attributed: bool,
+    pub broker_pubkey: Option<String>,
+    pub reasoning: Option<String>,
+    pub writeback_triggered: bool,
+    pub activity_urn: String,
+    pub proposal_urn: Option<String>,
+    pub owner_did: Option<String>,
+    #[cfg_attr(feature = "typescript-export", ts(type = "number"))]
+    pub decided_at_ms: u64,
+}
+
+/// `Some(None)` for an explicit `null`, `None` for an absent field.
+mod nullable {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer, T: Serialize>(
+        value: &Option<Option<T>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(inner) => inner.serialize(serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: Deserialize<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Option<T>>, D::Error> {
+        Option::<T>::deserialize(deserializer).map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn type_of<T: Serialize>(message: &T) -> String {
+        serde_json::to_value(message).unwrap()["type"]
+            .as_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn parses_frames_as_clients_send_them() {
+        let drag: ClientMessage = serde_json::from_value(json!({
+            "type": "nodeDragUpdate",
+            "data": { "nodeId": 7, "position": { "x": 1.0, "y": 2.0, "z": 3.0 } }
+        }))
+        .unwrap();
+        assert_eq!(
+            drag,
+            ClientMessage::NodeDragUpdate {
+                data: NodeDrag {
+                    node_id: 7,
+                    position: Some(WorldPoint {
+                        x: 1.0,
+                        y: 2.0,
+                        z: 3.0
+                    }),
+                    timestamp: None,
+                }
+            }
+        );
+
+        // Unit-like messages tolerate stray fields.
+        let snapshot: ClientMessage =
+            serde_json::from_value(json!({ "type": "requestBotsGraph", "extra": 1 })).unwrap();
+        assert_eq!(snapshot, ClientMessage::RequestBotsGraph {});
+
+        let clear: ClientMessage =
+            serde_json::from_value(json!({ "type": "nodeBudget", "maxNodes": null })).unwrap();
+        assert_eq!(
+            clear,
+            ClientMessage::NodeBudget {
+                max_nodes: Some(None),
+                focus_node_id: None,
+            }
+        );
+        assert_eq!(
+            serde_json::to_value(&clear).unwrap(),
+            json!({ "type": "nodeBudget", "maxNodes": null })
+        );
+
+        assert!(serde_json::from_value::<ClientMessage>(json!({
+            "type": "simulationControl",
+            "action": "rewind"
+        }))
+        .is_err());
+    }
+
+    #[test]
+    fn wire_names_match_the_type_lists() {
+        let client = [
+            ClientMessage::UpdatePhysicsParams {},
+            ClientMessage::RequestFullSnapshot { graphs: None },
+            ClientMessage::OntologyConstraintToggle {},
+            ClientMessage::SetLanguageFilter { languages: None },
+            ClientMessage::RequestOverviewFrame {},
+            ClientMessage::ApplyLayoutProposal {
+                id: "p".into(),
+                transition_ms: None,
+            },
+        ];
+        for message in &client {
+            assert!(ClientMessage::TYPES.contains(&type_of(message).as_str()));
+        }
+
+        let diff = GraphDiff {
+            reason: "ephemeral_added".into(),
+            added: vec![json!({ "id": 1 })],
+            removed: vec![],
+            updated: None,
+            revision: 4,
+        };
+        let server = [
+            ServerMessage::GraphDiff(diff.clone()),
+            ServerMessage::SessionResumed {
+                from_revision: 3,
+                to_revision: 4,
+                diffs: vec![diff],
+            },
+            ServerMessage::SetLanguageFilterSuccess { languages: None },
+            ServerMessage::Hibernating { idle_ms: 1 },
+            ServerMessage::OverviewFrame {
+                nodes: vec![[0.0, 1.0, 2.0, 3.0]],
+            },
+        ];
+        for message in &server {
+            assert!(ServerMessage::TYPES.contains(&type_of(message).as_str()));
+        }
+
+        let resumed = serde_json::to_value(&server[1]).unwrap();
+        assert_eq!(resumed["fromRevision"], 3);
+        assert_eq!(resumed["diffs"][0]["revision"], 4);
+        let back: ServerMessage = serde_json::from_value(resumed).unwrap();
+        assert_eq!(back, server[1]);
+    }
+}
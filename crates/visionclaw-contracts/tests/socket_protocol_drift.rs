@@ -0,0 +1,370 @@
+//! Socket protocol drift guard.
+//!
+//! The server still dispatches and builds `/wss` text frames as loose JSON,
+//! so nothing ties `src/handlers/socket_flow_handler/` to
+//! `socket_protocol` at compile time. This test reads the server sources and
+//! the `@visionclaw/contracts` runtime lists and fails when:
+//!
+//! - `message_routing.rs` routes a `type` that `ClientMessage` lacks, or
+//!   `ClientMessage` lists one that is no longer routed;
+//! - a file that sends `/wss` text frames emits a `type` that is in neither
+//!   enum, or a file that builds a `BroadcastMessage` is not scanned;
+//! - a `json!` frame the server builds has a field its `ServerMessage`
+//!   variant lacks, judged against `tests/fixtures/server_messages.json`,
+//!   whose frames must each round-trip through `ServerMessage` unchanged and
+//!   cover every `type`;
+//! - `sdk/visionflow-contracts/index.js` lists differ from
+//!   `ClientMessage::TYPES` / `ServerMessage::TYPES`.
+//!
+//! Frames serialised from server structs are checked for their `type` only.
+//!
+//! After changing the protocol, update `src/socket_protocol.rs` and the
+//! fixtures, regenerate the bindings in both directories
+//! (`UPDATE_BINDINGS=1 cargo test --features typescript-export ts_export`),
+//! then update the lists in `sdk/visionflow-contracts/index.js`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use visionclaw_contracts::{ClientMessage, ServerMessage};
+
+fn repo_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../..")
+}
+
+fn read(path: &Path) -> String {
+    fs::read_to_string(path).unwrap_or_else(|e| panic!("reading {}: {}", path.display(), e))
+}
+
+/// Everything that sends `/wss` text frames: the socket handlers plus the
+/// actors and services that reach clients through the client coordinator.
+fn server_sources() -> Vec<PathBuf> {
+    let root = repo_root().join("src");
+    let mut sources: Vec<PathBuf> = fs::read_dir(root.join("handlers/socket_flow_handler"))
+        .expect("socket_flow_handler sources")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .collect();
+    for file in [
+        "actors/client_coordinator_actor.rs",
+        "actors/graph_state_actor.rs",
+        "actors/ontology_actor.rs",
+        "actors/physics_orchestrator_actor.rs",
+        "handlers/enrichment_proposals_handler.rs",
+        "handlers/memory_flash_handler.rs",
+        "services/audio_cue_service.rs",
+        "services/highlight_service.rs",
+        "services/layout_refresh_service.rs",
+        "services/visit_service.rs",
+        "settings/api/settings_routes.rs",
+    ] {
+        sources.push(root.join(file));
+    }
+    sources
+}
+
+/// `.rs` files under `dir`, recursively.
+fn rust_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).unwrap_or_else(|e| panic!("reading {}: {}", dir.display(), e)) {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            rust_files(&path, out);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            out.push(path);
+        }
+    }
+}
+
+/// The source without its `#[cfg(test)]` module, whose literals are test
+/// input rather than frames the server sends.
+fn without_tests(source: &str) -> &str {
+    source
+        .find("#[cfg(test)]")
+        .map_or(source, |end| &source[..end])
+}
+
+/// Every `Some("...")` arm in the text-message dispatcher.
+fn routed_types(source: &str) -> BTreeSet<String> {
+    source
+        .split("Some(\"")
+        .skip(1)
+        .filter_map(|rest| rest.split_once('"').map(|(name, _)| name.to_string()))
+        .collect()
+}
+
+/// String literals that follow a `"type"` key or a `type_` field on the
+/// same line: `"type": "x"`, `"type":"x"` inside raw strings,
+/// `reply["type"] = ...("x")` and `type_: "x"`. Escaped JSON inside
+/// ordinary strings is skipped; it only ever re-dispatches client messages.
+fn emitted_types(source: &str) -> BTreeSet<String> {
+    let mut types = BTreeSet::new();
+    for line in source.lines().filter(|l| !l.contains("\\\"")) {
+        for (_, rest) in line
+            .match_indices("\"type\"")
+            .chain(line.match_indices("type_:"))
+            .map(|(i, m)| line.split_at(i + m.len()))
+        {
+            let Some((gap, literal)) = rest.split_once('"') else {
+                continue;
+            };
+            if gap.contains(',') || gap.contains(';') {
+                continue;
+            }
+            if let Some((name, _)) = literal.split_once('"') {
+                types.insert(name.to_string());
+            }
+        }
+    }
+    types
+}
+
+/// `(type, top-level keys)` of every `json!({ ... })` object whose `"type"`
+/// is a string literal.
+fn json_frames(source: &str) -> Vec<(String, BTreeSet<String>)> {
+    let bytes = source.as_bytes();
+    let mut frames = Vec::new();
+    for (start, _) in source.match_indices("json!(") {
+        let Some(open) = source[start + 6..].find(|c: char| !c.is_whitespace()) else {
+            continue;
+        };
+        let mut i = start + 6 + open;
+        if bytes[i] != b'{' {
+            continue;
+        }
+        let (mut depth, mut frame_type, mut keys) = (0usize, None, BTreeSet::new());
+        while i < bytes.len() {
+            match bytes[i] {
+                b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                    i = source[i..].find('\n').map_or(bytes.len(), |n| i + n);
+                    continue;
+                }
+                b'"' => {
+                    let (text, end) = string_literal(source, i);
+                    i = end;
+                    if depth != 1 {
+                        continue;
+                    }
+                    let after = source[i..].trim_start();
+                    if after.starts_with(':') && !after.starts_with("::") {
+                        if text == "type" {
+                            let value = after[1..].trim_start();
+                            if value.starts_with('"') {
+                                frame_type = Some(string_literal(value, 0).0.to_string());
+                            }
+                        }
+                        keys.insert(text.to_string());
+                    }
+                    continue;
+                }
+                b'(' | b'[' | b'{' => depth += 1,
+                b')' | b']' | b'}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+        if let Some(frame_type) = frame_type {
+            frames.push((frame_type, keys));
+        }
+    }
+    frames
+}
+
+/// The contents of the string literal opening at `start`, and the index just
+/// past its closing quote.
+fn string_literal(source: &str, start: usize) -> (&str, usize) {
+    let bytes = source.as_bytes();
+    let mut end = start + 1;
+    while end < bytes.len() && bytes[end] != b'"' {
+        end += if bytes[end] == b'\\' { 2 } else { 1 };
+    }
+    (&source[start + 1..end.min(bytes.len())], end + 1)
+}
+
+fn fixtures() -> Vec<serde_json::Value> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/server_messages.json");
+    serde_json::from_str(&read(&path)).expect("server message fixtures are JSON")
+}
+
+/// The string array assigned to `exports.<name>` in the sdk's index.js.
+fn sdk_list(index_js: &str, name: &str) -> Vec<String> {
+    let start = index_js
+        .find(&format!("exports.{name} = "))
+        .unwrap_or_else(|| panic!("index.js has no {name}"));
+    let body = &index_js[start..];
+    let body = &body[body.find('[').unwrap() + 1..body.find(']').unwrap()];
+    body.split(',')
+        .map(|item| item.trim().trim_matches('"').to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+fn as_set(types: &[&str]) -> BTreeSet<String> {
+    types.iter().map(|t| t.to_string()).collect()
+}
+
+#[test]
+fn client_messages_match_the_dispatcher() {
+    let routing = read(&repo_root().join("src/handlers/socket_flow_handler/message_routing.rs"));
+    let routed = routed_types(&routing);
+    let declared = as_set(ClientMessage::TYPES);
+    assert_eq!(
+        routed.difference(&declared).collect::<Vec<_>>(),
+        Vec::<&String>::new(),
+        "routed but missing from ClientMessage"
+    );
+    assert_eq!(
+        declared.difference(&routed).collect::<Vec<_>>(),
+        Vec::<&String>::new(),
+        "in ClientMessage but no longer routed"
+    );
+}
+
+#[test]
+fn emitted_messages_are_declared() {
+    let known: BTreeSet<String> = as_set(ServerMessage::TYPES)
+        .union(&as_set(ClientMessage::TYPES))
+        .cloned()
+        .collect();
+    for path in server_sources() {
+        let undeclared: Vec<String> = emitted_types(&read(&path))
+            .into_iter()
+            .filter(|t| !known.contains(t))
+            .collect();
+        assert!(
+            undeclared.is_empty(),
+            "{} sends {:?}, missing from ServerMessage",
+            path.display(),
+            undeclared
+        );
+    }
+}
+
+#[test]
+fn broadcasters_are_scanned() {
+    let sources: BTreeSet<PathBuf> = server_sources().into_iter().collect();
+    let mut files = Vec::new();
+    rust_files(&repo_root().join("src"), &mut files);
+    let unscanned: Vec<PathBuf> = files
+        .into_iter()
+        .filter(|path| without_tests(&read(path)).contains("BroadcastMessage {"))
+        .filter(|path| !sources.contains(path))
+        .collect();
+    assert!(
+        unscanned.is_empty(),
+        "{:?} broadcast to /wss clients; add them to server_sources()",
+        unscanned
+    );
+}
+
+#[test]
+fn fixtures_round_trip_and_cover_every_server_message() {
+    let mut covered = BTreeSet::new();
+    for fixture in fixtures() {
+        let message: ServerMessage = serde_json::from_value(fixture.clone())
+            .unwrap_or_else(|e| panic!("{} does not parse: {}", fixture, e));
+        assert_eq!(
+            serde_json::to_value(&message).unwrap(),
+            fixture,
+            "fixture does not round-trip"
+        );
+        covered.insert(fixture["type"].as_str().unwrap().to_string());
+    }
+    assert_eq!(
+        as_set(ServerMessage::TYPES)
+            .difference(&covered)
+            .collect::<Vec<_>>(),
+        Vec::<&String>::new(),
+        "ServerMessage types without a fixture"
+    );
+}
+
+#[test]
+fn emitted_fields_are_declared() {
+    let mut declared: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for fixture in fixtures() {
+        let object = fixture.as_object().unwrap();
+        declared
+            .entry(fixture["type"].as_str().unwrap().to_string())
+            .or_default()
+            .extend(object.keys().cloned());
+    }
+    for path in server_sources() {
+        let source = read(&path);
+        for (frame_type, keys) in json_frames(without_tests(&source)) {
+            // Client messages re-dispatched internally.
+            let Some(fields) = declared.get(&frame_type) else {
+                continue;
+            };
+            let undeclared: Vec<&String> = keys.difference(fields).collect();
+            assert!(
+                undeclared.is_empty(),
+                "{} sends {} with {:?}, missing from ServerMessage",
+                path.display(),
+                frame_type,
+                undeclared
+            );
+        }
+    }
+}
+
+#[test]
+fn sdk_lists_match() {
+    let index_js = read(&repo_root().join("sdk/visionflow-contracts/index.js"));
+    assert_eq!(
+        sdk_list(&index_js, "CLIENT_MESSAGE_TYPES"),
+        ClientMessage::TYPES
+    );
+    assert_eq!(
+        sdk_list(&index_js, "SERVER_MESSAGE_TYPES"),
+        ServerMessage::TYPES
+    );
+}
+
+#[test]
+fn extracts_types_from_source_shapes() {
+    let source = r##"
+        Some("hello") => handle(),
+        Some("ontology_validation") | Some("ontology_validation_report") => {}
+        let m = serde_json::json!({ "type": "layoutSeeded", "applied": 1 });
+        ctx.text(r#"{"type":"error","message":"no"}"#);
+        reply["type"] = serde_json::Value::from("simulationQuality");
+        let t = msg.get("type").and_then(|t| t.as_str());
+        let s = "{\"type\":\"subscribe_position_updates\"}";
+    "##;
+    assert_eq!(
+        routed_types(source),
+        as_set(&["hello", "ontology_validation", "ontology_validation_report"])
+    );
+    assert_eq!(
+        emitted_types(source),
+        as_set(&["error", "layoutSeeded", "simulationQuality"])
+    );
+
+    let frames = r##"
+        let m = serde_json::json!({
+            "type": "layoutSeeded",
+            // "not": "a key"
+            "applied": count,
+            "nested": { "inner": 1 },
+            "path": Some::<u32>(1),
+        });
+        let other = json!({ "kind": kind, "type": "x" });
+        let dynamic = json!({ "type": name });
+    "##;
+    assert_eq!(
+        json_frames(frames),
+        vec![
+            (
+                "layoutSeeded".to_string(),
+                as_set(&["applied", "nested", "path", "type"])
+            ),
+            ("x".to_string(), as_set(&["kind", "type"])),
+        ]
+    );
+}
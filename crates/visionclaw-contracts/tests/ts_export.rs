@@ -7,8 +7,11 @@
 //! cargo test -p visionclaw-contracts --features typescript-export ts_export
 //! ```
 //!
-//! and then asserts the resulting files in `crates/visionclaw-contracts/bindings/`
-//! match `client/src/types/contracts/` byte-for-byte (regenerated on every
+//! which regenerates every binding into a scratch directory and fails unless
+//! `crates/visionclaw-contracts/bindings/` and the npm package's
+//! `sdk/visionflow-contracts/bindings/` hold exactly that output, byte for
+//! byte. After changing a contract, run it with `UPDATE_BINDINGS=1` to
+//! rewrite both directories from the generator (regenerated on every
 //! version bump per ADR-10 §D8).
 //!
 //! When the feature is disabled the file compiles to a no-op stub, so it
@@ -16,53 +19,139 @@
 
 #![cfg(feature = "typescript-export")]
 
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
 use ts_rs::TS;
 use visionclaw_contracts::{
-    agent_action::{ActionKind, AgentAction, AgentActionEnvelope, ModifierKeys, NodeClass, WorldPosition},
+    agent_action::{
+        ActionKind, AgentAction, AgentActionEnvelope, AgentActionTargetOrigin, ModifierKeys,
+        NodeClass, WorldPosition,
+    },
     enterprise::{
         EnterpriseEventEnvelope, EnterpriseEventKind, EnterpriseRole, MembershipAction,
         MembershipChangePayload, RoleChangePayload, SessionRevokedPayload,
     },
     github_adapter::{ParseErrorKind, ParseErrorReport, ParsedMarkdown},
+    socket_protocol::{ClientMessage, ServerMessage},
     telemetry::{
-        AgentRecord, AgentRemovedPayload, AgentStatus, AgentTelemetryEnvelope,
-        AgentTelemetryEvent, CommunicationPayload, DeltaPayload, HeartbeatPayload,
-        SnapshotPayload,
+        AgentRecord, AgentRemovedPayload, AgentStatus, AgentTelemetryEnvelope, AgentTelemetryEvent,
+        CommunicationPayload, DeltaPayload, DepartReason, HeartbeatPayload, SnapshotPayload,
     },
 };
 
-/// Drives the ts-rs `export!` machinery for every public type and asserts
-/// that each .d.ts file lands at the expected path.
-#[test]
-fn ts_export_writes_all_top_level_types() {
-    // ts-rs derives a per-type `export()` that writes to the path supplied
-    // by `#[ts(export_to = ...)]`, defaulting to `bindings/<TypeName>.ts`.
-    AgentActionEnvelope::export_all().expect("AgentActionEnvelope export");
-    AgentAction::export_all().expect("AgentAction export");
-    ActionKind::export_all().expect("ActionKind export");
-    NodeClass::export_all().expect("NodeClass export");
-    ModifierKeys::export_all().expect("ModifierKeys export");
-    WorldPosition::export_all().expect("WorldPosition export");
+/// Writes every public type into the empty directory `dir`. ts-rs merges
+/// into files that already exist, so `dir` is cleared first.
+fn export_bindings(dir: &Path) {
+    let _ = fs::remove_dir_all(dir);
+    fs::create_dir_all(dir).expect("create scratch bindings dir");
 
-    AgentTelemetryEnvelope::export_all().expect("AgentTelemetryEnvelope export");
-    AgentTelemetryEvent::export_all().expect("AgentTelemetryEvent export");
-    SnapshotPayload::export_all().expect("SnapshotPayload export");
-    DeltaPayload::export_all().expect("DeltaPayload export");
-    AgentRemovedPayload::export_all().expect("AgentRemovedPayload export");
-    HeartbeatPayload::export_all().expect("HeartbeatPayload export");
-    CommunicationPayload::export_all().expect("CommunicationPayload export");
-    AgentRecord::export_all().expect("AgentRecord export");
-    AgentStatus::export_all().expect("AgentStatus export");
+    // ts-rs derives a per-type `export_all_to()` that writes the type and its
+    // dependencies to the path supplied by `#[ts(export_to = ...)]`,
+    // defaulting to `<dir>/<TypeName>.ts`.
+    AgentActionEnvelope::export_all_to(dir).expect("AgentActionEnvelope export");
+    AgentAction::export_all_to(dir).expect("AgentAction export");
+    AgentActionTargetOrigin::export_all_to(dir).expect("AgentActionTargetOrigin export");
+    ActionKind::export_all_to(dir).expect("ActionKind export");
+    NodeClass::export_all_to(dir).expect("NodeClass export");
+    ModifierKeys::export_all_to(dir).expect("ModifierKeys export");
+    WorldPosition::export_all_to(dir).expect("WorldPosition export");
 
-    EnterpriseEventEnvelope::export_all().expect("EnterpriseEventEnvelope export");
-    EnterpriseEventKind::export_all().expect("EnterpriseEventKind export");
-    MembershipChangePayload::export_all().expect("MembershipChangePayload export");
-    MembershipAction::export_all().expect("MembershipAction export");
-    RoleChangePayload::export_all().expect("RoleChangePayload export");
-    EnterpriseRole::export_all().expect("EnterpriseRole export");
-    SessionRevokedPayload::export_all().expect("SessionRevokedPayload export");
+    AgentTelemetryEnvelope::export_all_to(dir).expect("AgentTelemetryEnvelope export");
+    AgentTelemetryEvent::export_all_to(dir).expect("AgentTelemetryEvent export");
+    SnapshotPayload::export_all_to(dir).expect("SnapshotPayload export");
+    DeltaPayload::export_all_to(dir).expect("DeltaPayload export");
+    AgentRemovedPayload::export_all_to(dir).expect("AgentRemovedPayload export");
+    HeartbeatPayload::export_all_to(dir).expect("HeartbeatPayload export");
+    CommunicationPayload::export_all_to(dir).expect("CommunicationPayload export");
+    AgentRecord::export_all_to(dir).expect("AgentRecord export");
+    AgentStatus::export_all_to(dir).expect("AgentStatus export");
+    DepartReason::export_all_to(dir).expect("DepartReason export");
+
+    EnterpriseEventEnvelope::export_all_to(dir).expect("EnterpriseEventEnvelope export");
+    EnterpriseEventKind::export_all_to(dir).expect("EnterpriseEventKind export");
+    MembershipChangePayload::export_all_to(dir).expect("MembershipChangePayload export");
+    MembershipAction::export_all_to(dir).expect("MembershipAction export");
+    RoleChangePayload::export_all_to(dir).expect("RoleChangePayload export");
+    EnterpriseRole::export_all_to(dir).expect("EnterpriseRole export");
+    SessionRevokedPayload::export_all_to(dir).expect("SessionRevokedPayload export");
+
+    ParsedMarkdown::export_all_to(dir).expect("ParsedMarkdown export");
+    ParseErrorReport::export_all_to(dir).expect("ParseErrorReport export");
+    ParseErrorKind::export_all_to(dir).expect("ParseErrorKind export");
+
+    // Payload types are written alongside as dependencies.
+    ClientMessage::export_all_to(dir).expect("ClientMessage export");
+    ServerMessage::export_all_to(dir).expect("ServerMessage export");
+}
+
+/// `.ts` file name to contents.
+fn ts_files(dir: &Path) -> BTreeMap<String, Vec<u8>> {
+    fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("reading {}: {}", dir.display(), e))
+        .map(|entry| entry.expect("dir entry").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "ts"))
+        .map(|path| {
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+            (name, fs::read(&path).expect("read binding"))
+        })
+        .collect()
+}
+
+/// Names that are missing from, extra in or different in `committed`.
+fn drift(
+    generated: &BTreeMap<String, Vec<u8>>,
+    committed: &BTreeMap<String, Vec<u8>>,
+) -> Vec<String> {
+    let names: BTreeSet<&String> = generated.keys().chain(committed.keys()).collect();
+    names
+        .into_iter()
+        .filter_map(|name| match (generated.get(name), committed.get(name)) {
+            (Some(_), None) => Some(format!("{name} (missing)")),
+            (None, Some(_)) => Some(format!("{name} (not generated)")),
+            (Some(a), Some(b)) if a != b => Some(format!("{name} (differs)")),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn ts_export_writes_all_top_level_types() {
+    let scratch = Path::new(env!("CARGO_TARGET_TMPDIR")).join("bindings");
+    export_bindings(&scratch);
+    let generated = ts_files(&scratch);
+    for name in [
+        "ClientMessage.ts",
+        "ServerMessage.ts",
+        "AgentActionEnvelope.ts",
+    ] {
+        assert!(generated.contains_key(name), "{name} was not exported");
+    }
 
-    ParsedMarkdown::export_all().expect("ParsedMarkdown export");
-    ParseErrorReport::export_all().expect("ParseErrorReport export");
-    ParseErrorKind::export_all().expect("ParseErrorKind export");
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let committed_dirs: [PathBuf; 2] = [
+        manifest.join("bindings"),
+        manifest.join("../../sdk/visionflow-contracts/bindings"),
+    ];
+    let update = std::env::var_os("UPDATE_BINDINGS").is_some();
+    for dir in &committed_dirs {
+        let committed = ts_files(dir);
+        if update {
+            for name in committed.keys() {
+                fs::remove_file(dir.join(name)).expect("remove stale binding");
+            }
+            for (name, contents) in &generated {
+                fs::write(dir.join(name), contents).expect("write binding");
+            }
+            continue;
+        }
+        let drifted = drift(&generated, &committed);
+        assert!(
+            drifted.is_empty(),
+            "{} is not the generator's output: {}. Rerun with UPDATE_BINDINGS=1.",
+            dir.display(),
+            drifted.join(", ")
+        );
+    }
 }
@@ -6,8 +6,10 @@ use validator::{Validate, ValidationError};
 
 use super::field_mappings::{convert_empty_strings_to_null, merge_json_values, normalize_field_names_to_camel_case};
 use super::services::{
-    AuthSettings, KokoroSettings, OntologyAgentSettings, OpenAISettings, PerplexitySettings,
-    RagFlowSettings, VoiceRoutingSettings, WhisperSettings,
+    ApiCostSettings, AuthSettings, ComputeBudgetSettings, DigestSettings, GitHubSettings, KokoroSettings,
+    LayoutRefreshSettings, LinkCheckSettings, LintSettings, OntologyAgentSettings, OpenAISettings,
+    PerplexitySettings, PublicApiSettings, RagFlowSettings, VoiceRoutingSettings, WebhookSettings,
+    WhisperSettings,
 };
 use super::system::SystemSettings;
 use super::validation::{to_camel_case, validate_bloom_glow_settings};
@@ -101,6 +103,24 @@ pub struct AppFullSettings {
     pub voice_routing: Option<VoiceRoutingSettings>,
     #[serde(skip_serializing_if = "Option::is_none", alias = "ontology_agent")]
     pub ontology_agent: Option<OntologyAgentSettings>,
+    #[serde(skip_serializing_if = "Option::is_none", alias = "digest")]
+    pub digest: Option<DigestSettings>,
+    #[serde(skip_serializing_if = "Option::is_none", alias = "link_check")]
+    pub link_check: Option<LinkCheckSettings>,
+    #[serde(skip_serializing_if = "Option::is_none", alias = "webhooks")]
+    pub webhooks: Option<WebhookSettings>,
+    #[serde(skip_serializing_if = "Option::is_none", alias = "public_api")]
+    pub public_api: Option<PublicApiSettings>,
+    #[serde(skip_serializing_if = "Option::is_none", alias = "compute_budget")]
+    pub compute_budget: Option<ComputeBudgetSettings>,
+    #[serde(skip_serializing_if = "Option::is_none", alias = "api_costs")]
+    pub api_costs: Option<ApiCostSettings>,
+    #[serde(skip_serializing_if = "Option::is_none", alias = "github")]
+    pub github: Option<GitHubSettings>,
+    #[serde(skip_serializing_if = "Option::is_none", alias = "lint")]
+    pub lint: Option<LintSettings>,
+    #[serde(skip_serializing_if = "Option::is_none", alias = "layout_refresh")]
+    pub layout_refresh: Option<LayoutRefreshSettings>,
     #[serde(default = "default_version", alias = "version")]
     pub version: String,
     #[serde(default, alias = "user_preferences")]
@@ -129,6 +149,15 @@ impl Default for AppFullSettings {
             whisper: None,
             voice_routing: None,
             ontology_agent: None,
+            digest: None,
+            link_check: None,
+            webhooks: None,
+            public_api: None,
+            compute_budget: None,
+            api_costs: None,
+            github: None,
+            lint: None,
+            layout_refresh: None,
             version: default_version(),
             user_preferences: UserPreferences::default(),
             physics: PhysicsSettings::default(),
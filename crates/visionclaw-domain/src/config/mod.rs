@@ -29,10 +29,15 @@ pub use system::{
     DebugSettings, NetworkSettings, SecuritySettings, SystemSettings, WebSocketSettings,
 };
 
-pub use xr::{MovementAxes, XRSettings};
+pub use xr::{
+    AudioCueDefinition, GestureAction, MovementAxes, XRAudioCueSettings, XRGestureSettings,
+    XRSettings,
+};
 
 pub use services::{
-    AgentVoicePreset, AuthSettings, KokoroSettings, LiveKitSettings, OntologyAgentSettings,
-    OpenAISettings, PerplexitySettings, RagFlowSettings, TurboWhisperSettings,
-    VoiceRoutingSettings, WhisperSettings,
+    AgentVoicePreset, ApiCostSettings, AuthSettings, ChatNotifierSettings, ComputeBudgetSettings,
+    DigestSettings, GitHubSettings, KokoroSettings, LayoutRefreshSettings, LinkCheckSettings,
+    LintSettings, LintSeverity, LiveKitSettings, OntologyAgentSettings, OpenAISettings,
+    PerplexitySettings, PublicApiSettings, RagFlowSettings, TurboWhisperSettings,
+    VoiceRoutingSettings, WebhookEndpoint, WebhookSettings, WhisperSettings, WriteMode,
 };
@@ -69,6 +69,15 @@ pub struct OpenAISettings {
     pub timeout: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none", alias = "rate_limit")]
     pub rate_limit: Option<u32>,
+    /// Model the graph chat agent asks for.
+    #[serde(skip_serializing_if = "Option::is_none", alias = "chat_model")]
+    pub chat_model: Option<String>,
+    /// Model page embeddings are made with.
+    #[serde(skip_serializing_if = "Option::is_none", alias = "embedding_model")]
+    pub embedding_model: Option<String>,
+    /// Embed changed pages when the server starts, not only after a sync.
+    #[serde(skip_serializing_if = "Option::is_none", alias = "embed_on_startup")]
+    pub embed_on_startup: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default, Type, Validate)]
@@ -250,3 +259,382 @@ fn default_notes_path_prefix() -> String { "pages/".to_string() }
 fn default_pr_labels() -> Vec<String> {
     vec!["ontology".to_string(), "agent-proposed".to_string()]
 }
+
+// ---------- Digest Settings ----------
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct DigestSettings {
+    /// Generate a daily digest page (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Hour of day (UTC, 0-23) at which the digest is generated
+    #[validate(range(min = 0, max = 23))]
+    #[serde(default = "default_digest_hour_utc", alias = "hour_utc")]
+    pub hour_utc: u32,
+    /// Vault sub-directory the digest pages are written to
+    #[serde(default = "default_digest_output_dir", alias = "output_dir")]
+    pub output_dir: String,
+    /// Ask the configured LLM (Perplexity) for a prose summary of the day
+    #[serde(default, alias = "llm_summary")]
+    pub llm_summary: bool,
+    /// Maximum entries listed per digest section
+    #[serde(default = "default_digest_max_items", alias = "max_items_per_section")]
+    pub max_items_per_section: usize,
+}
+
+impl Default for DigestSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            hour_utc: default_digest_hour_utc(),
+            output_dir: default_digest_output_dir(),
+            llm_summary: false,
+            max_items_per_section: default_digest_max_items(),
+        }
+    }
+}
+
+fn default_digest_hour_utc() -> u32 { 6 }
+fn default_digest_output_dir() -> String { "digests".to_string() }
+fn default_digest_max_items() -> usize { 25 }
+
+// ---------- Link Check Settings ----------
+
+/// Periodic link-rot checks of the external URLs pages reference, served by
+/// `GET /api/maintenance/broken_links`. URLs that resolve to private,
+/// loopback or link-local addresses are never fetched.
+#[derive(Debug, Serialize, Deserialize, Clone, Type, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkCheckSettings {
+    /// Check links on a schedule (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Seconds between passes (default: 21600)
+    #[validate(range(min = 60))]
+    #[serde(default = "default_link_check_interval", alias = "interval_secs")]
+    pub interval_secs: u64,
+    /// Minimum spacing between requests to one domain, in ms (default: 2000)
+    #[serde(default = "default_link_check_domain_delay", alias = "domain_delay_ms")]
+    pub domain_delay_ms: u64,
+    /// Set a `broken_links` count in the metadata of pages that cite broken
+    /// URLs, so clients can badge them (default: false)
+    #[serde(default, alias = "flag_nodes")]
+    pub flag_nodes: bool,
+}
+
+impl Default for LinkCheckSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_secs: default_link_check_interval(),
+            domain_delay_ms: default_link_check_domain_delay(),
+            flag_nodes: false,
+        }
+    }
+}
+
+fn default_link_check_interval() -> u64 { 6 * 3600 }
+fn default_link_check_domain_delay() -> u64 { 2000 }
+
+// ---------- Webhook Settings ----------
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookSettings {
+    /// Master switch for outgoing webhooks (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Receivers of graph event payloads
+    #[serde(default)]
+    #[validate(nested)]
+    pub endpoints: Vec<WebhookEndpoint>,
+    /// Delivery attempts after the first failure (exponential backoff)
+    #[validate(range(max = 10))]
+    #[serde(default = "default_webhook_max_retries", alias = "max_retries")]
+    pub max_retries: u32,
+    /// Per-request timeout in milliseconds
+    #[validate(range(min = 100, max = 60000))]
+    #[serde(default = "default_webhook_timeout_ms", alias = "timeout_ms")]
+    pub timeout_ms: u64,
+    /// Slack incoming-webhook notifier
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(nested)]
+    pub slack: Option<ChatNotifierSettings>,
+    /// Discord channel-webhook notifier
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(nested)]
+    pub discord: Option<ChatNotifierSettings>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatNotifierSettings {
+    #[validate(length(min = 1))]
+    #[serde(alias = "webhook_url")]
+    pub webhook_url: String,
+    /// Events posted to the channel (default: `error`, `gpu.fallback`,
+    /// `digest.published`)
+    #[serde(default = "default_chat_notifier_events")]
+    pub events: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Type, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookEndpoint {
+    #[validate(length(min = 1))]
+    pub url: String,
+    /// Shared secret for the `X-VisionClaw-Signature` HMAC-SHA256 header
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    /// Event names to deliver (`sync.completed`, `node.created`,
+    /// `cluster.changed`, `error`); empty means all events
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+impl Default for WebhookSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoints: Vec::new(),
+            max_retries: default_webhook_max_retries(),
+            timeout_ms: default_webhook_timeout_ms(),
+            slack: None,
+            discord: None,
+        }
+    }
+}
+
+fn default_webhook_max_retries() -> u32 { 3 }
+fn default_webhook_timeout_ms() -> u64 { 10_000 }
+fn default_chat_notifier_events() -> Vec<String> {
+    vec!["error".to_string(), "gpu.fallback".to_string(), "digest.published".to_string()]
+}
+
+// ---------- Public API Settings ----------
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicApiSettings {
+    /// Serve the read-only `/public/graph/*` mirror (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Requests per minute allowed per client
+    #[validate(range(min = 1, max = 600))]
+    #[serde(default = "default_public_requests_per_minute", alias = "requests_per_minute")]
+    pub requests_per_minute: u32,
+    /// How often the cached snapshot is rebuilt from the live graph
+    #[validate(range(min = 5, max = 86400))]
+    #[serde(default = "default_public_cache_ttl_secs", alias = "cache_ttl_secs")]
+    pub cache_ttl_secs: u64,
+    /// Neighbours listed per node
+    #[validate(range(max = 500))]
+    #[serde(default = "default_public_max_neighbours", alias = "max_neighbours")]
+    pub max_neighbours: usize,
+}
+
+impl Default for PublicApiSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            requests_per_minute: default_public_requests_per_minute(),
+            cache_ttl_secs: default_public_cache_ttl_secs(),
+            max_neighbours: default_public_max_neighbours(),
+        }
+    }
+}
+
+fn default_public_requests_per_minute() -> u32 { 30 }
+fn default_public_cache_ttl_secs() -> u64 { 300 }
+fn default_public_max_neighbours() -> usize { 50 }
+
+// ---------- Compute Budget Settings ----------
+
+/// Caps on how much of the GPU/CPU the layout simulation may use, for hosts
+/// that share the device with other workloads. Steps over budget are skipped
+/// (the next step is delayed) rather than cut short.
+#[derive(Debug, Serialize, Deserialize, Clone, Type, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ComputeBudgetSettings {
+    /// Enforce the caps below (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Kernel milliseconds allowed per wall-clock second on the GPU path
+    #[validate(range(min = 1.0, max = 1000.0))]
+    #[serde(default = "default_max_gpu_ms_per_second", alias = "max_gpu_ms_per_second")]
+    pub max_gpu_ms_per_second: f32,
+    /// Share of one core the CPU fallback may keep busy, in percent
+    #[validate(range(min = 1.0, max = 100.0))]
+    #[serde(default = "default_max_cpu_percent", alias = "max_cpu_percent")]
+    pub max_cpu_percent: f32,
+}
+
+impl Default for ComputeBudgetSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_gpu_ms_per_second: default_max_gpu_ms_per_second(),
+            max_cpu_percent: default_max_cpu_percent(),
+        }
+    }
+}
+
+fn default_max_gpu_ms_per_second() -> f32 { 1000.0 }
+fn default_max_cpu_percent() -> f32 { 100.0 }
+
+// ---------- API Cost Settings ----------
+
+/// Spending caps on paid external APIs (OpenAI, Perplexity). Spend is
+/// estimated from list prices, so the caps are approximate. Reaching one
+/// pauses background enrichment (embeddings, digest summaries) until the
+/// day or month rolls over; requests users make themselves are not blocked.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Type, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiCostSettings {
+    /// Enforce the caps below (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Estimated USD per UTC day; unset means no daily cap
+    #[validate(range(min = 0.0))]
+    #[serde(default, alias = "daily_budget_usd", skip_serializing_if = "Option::is_none")]
+    pub daily_budget_usd: Option<f64>,
+    /// Estimated USD per calendar month (UTC); unset means no monthly cap
+    #[validate(range(min = 0.0))]
+    #[serde(default, alias = "monthly_budget_usd", skip_serializing_if = "Option::is_none")]
+    pub monthly_budget_usd: Option<f64>,
+}
+
+// ---------- Layout Refresh Settings ----------
+
+/// Scheduled layout re-initialisation. A fresh layout is simulated in the
+/// background and offered to clients as a proposal when it lowers layout
+/// stress enough; positions only change once a client applies it.
+#[derive(Debug, Serialize, Deserialize, Clone, Type, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct LayoutRefreshSettings {
+    /// Compute proposals on a schedule (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Minutes between proposals (default: 360)
+    #[validate(range(min = 5, max = 10080))]
+    #[serde(default = "default_layout_refresh_interval", alias = "interval_minutes")]
+    pub interval_minutes: u32,
+    /// Smallest relative stress reduction worth proposing (default: 0.15)
+    #[validate(range(min = 0.0, max = 1.0))]
+    #[serde(default = "default_layout_refresh_min_improvement", alias = "min_improvement")]
+    pub min_improvement: f32,
+    /// Steps the background simulation runs (default: 600)
+    #[validate(range(min = 50, max = 20000))]
+    #[serde(default = "default_layout_refresh_iterations", alias = "iterations")]
+    pub iterations: u32,
+    /// Graphs with more nodes are not re-laid out (default: 20000)
+    #[validate(range(min = 2))]
+    #[serde(default = "default_layout_refresh_max_nodes", alias = "max_nodes")]
+    pub max_nodes: u32,
+}
+
+impl Default for LayoutRefreshSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_minutes: default_layout_refresh_interval(),
+            min_improvement: default_layout_refresh_min_improvement(),
+            iterations: default_layout_refresh_iterations(),
+            max_nodes: default_layout_refresh_max_nodes(),
+        }
+    }
+}
+
+fn default_layout_refresh_interval() -> u32 { 360 }
+fn default_layout_refresh_min_improvement() -> f32 { 0.15 }
+fn default_layout_refresh_iterations() -> u32 { 600 }
+fn default_layout_refresh_max_nodes() -> u32 { 20_000 }
+
+// ---------- Lint Settings ----------
+
+/// Graph lint rules served by `GET /api/lint`. Each rule has its own
+/// severity; `off` skips it.
+#[derive(Debug, Serialize, Deserialize, Clone, Type, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct LintSettings {
+    /// Pages with no links in or out (default: warning)
+    #[serde(default = "default_lint_warning", alias = "orphan_page")]
+    pub orphan_page: LintSeverity,
+    /// Pages with more than `maxLinks` links (default: info)
+    #[serde(default = "default_lint_info", alias = "too_many_links")]
+    pub too_many_links: LintSeverity,
+    /// Threshold for `tooManyLinks` (default: 200)
+    #[validate(range(min = 1))]
+    #[serde(default = "default_lint_max_links", alias = "max_links")]
+    pub max_links: u32,
+    /// `((uuid))` block references that match no `id::` block (default: error)
+    #[serde(default = "default_lint_error", alias = "broken_block_ref")]
+    pub broken_block_ref: LintSeverity,
+    /// Tags carried by exactly one node (default: info)
+    #[serde(default = "default_lint_info", alias = "tag_used_once")]
+    pub tag_used_once: LintSeverity,
+    /// Let `POST /api/lint/flags` set a `lint` entry in each offending
+    /// node's metadata to its worst severity, so clients can badge it; with
+    /// this off it clears them (default: false)
+    #[serde(default, alias = "flag_nodes")]
+    pub flag_nodes: bool,
+}
+
+impl Default for LintSettings {
+    fn default() -> Self {
+        Self {
+            orphan_page: default_lint_warning(),
+            too_many_links: default_lint_info(),
+            max_links: default_lint_max_links(),
+            broken_block_ref: default_lint_error(),
+            tag_used_once: default_lint_info(),
+            flag_nodes: false,
+        }
+    }
+}
+
+fn default_lint_info() -> LintSeverity { LintSeverity::Info }
+fn default_lint_warning() -> LintSeverity { LintSeverity::Warning }
+fn default_lint_error() -> LintSeverity { LintSeverity::Error }
+fn default_lint_max_links() -> u32 { 200 }
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum LintSeverity {
+    /// Rule is not run
+    Off,
+    Info,
+    Warning,
+    Error,
+}
+
+// ---------- GitHub Content Settings ----------
+
+/// Which revision and paths of the content repository are ingested. Owner
+/// and repo still come from the environment.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Type, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct GitHubSettings {
+    /// Branch, tag or commit SHA to sync from; unset means `GITHUB_BRANCH`
+    #[validate(length(min = 1, max = 255))]
+    #[serde(default, rename = "ref", alias = "git_ref", skip_serializing_if = "Option::is_none")]
+    pub git_ref: Option<String>,
+    /// Repository directories to ingest; nothing outside them is fetched.
+    /// Empty means `GITHUB_BASE_PATHS`. Applied on restart.
+    #[serde(default, alias = "base_paths", skip_serializing_if = "Vec::is_empty")]
+    pub base_paths: Vec<String>,
+    /// How page edits made through the server reach the repository
+    #[serde(default, alias = "write_mode")]
+    pub write_mode: WriteMode,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum WriteMode {
+    /// Commit straight to the content branch
+    Direct,
+    /// Batch edits on a branch and open a pull request for review
+    #[default]
+    Pr,
+}
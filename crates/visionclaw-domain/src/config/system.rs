@@ -100,6 +100,30 @@ pub struct WebSocketSettings {
     pub reconnect_delay: u64,
     #[serde(alias = "update_rate")]
     pub update_rate: u32,
+    /// Window (ms) during which a node a client just moved is left out of
+    /// that client's position broadcasts. 0 disables suppression.
+    #[serde(default = "default_echo_suppression_ms", alias = "echo_suppression_ms")]
+    pub echo_suppression_ms: u64,
+    /// Who may drive positions when several clients run physics locally:
+    /// "firstCome" or "roleBased" (power users preempt regular users).
+    #[serde(default = "default_physics_authority_policy", alias = "physics_authority_policy")]
+    pub physics_authority_policy: String,
+    /// A session that sends nothing, not even a pong, for this long (ms)
+    /// stops receiving broadcasts until it next speaks. 0 disables.
+    #[serde(default = "default_idle_hibernation_ms", alias = "idle_hibernation_ms")]
+    pub idle_hibernation_ms: u64,
+}
+
+fn default_echo_suppression_ms() -> u64 {
+    250
+}
+
+fn default_physics_authority_policy() -> String {
+    "firstCome".to_string()
+}
+
+fn default_idle_hibernation_ms() -> u64 {
+    30 * 60 * 1000
 }
 
 impl Default for WebSocketSettings {
@@ -121,6 +145,9 @@ impl Default for WebSocketSettings {
             reconnect_attempts: 5,
             reconnect_delay: 1000,
             update_rate: 60,
+            echo_suppression_ms: default_echo_suppression_ms(),
+            physics_authority_policy: default_physics_authority_policy(),
+            idle_hibernation_ms: default_idle_hibernation_ms(),
         }
     }
 }
@@ -154,11 +181,28 @@ pub struct SecuritySettings {
 pub struct DebugSettings {
     #[serde(default, alias = "enabled")]
     pub enabled: bool,
+    /// Minimum seconds between log lines from one per-frame call site.
+    /// 0 logs every occurrence.
+    #[serde(
+        default = "default_hot_path_log_interval_secs",
+        alias = "hot_path_log_interval_secs"
+    )]
+    pub hot_path_log_interval_secs: u32,
+}
+
+/// Default for [`DebugSettings::hot_path_log_interval_secs`].
+pub const DEFAULT_HOT_PATH_LOG_INTERVAL_SECS: u32 = 5;
+
+fn default_hot_path_log_interval_secs() -> u32 {
+    DEFAULT_HOT_PATH_LOG_INTERVAL_SECS
 }
 
 impl Default for DebugSettings {
     fn default() -> Self {
-        Self { enabled: false }
+        Self {
+            enabled: false,
+            hot_path_log_interval_secs: default_hot_path_log_interval_secs(),
+        }
     }
 }
 
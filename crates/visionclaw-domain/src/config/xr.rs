@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::collections::BTreeMap;
 use validator::Validate;
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default, Type)]
@@ -11,6 +12,86 @@ pub struct MovementAxes {
     pub vertical: i32,
 }
 
+/// What the server does when a hand-tracking gesture starts on a node.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum GestureAction {
+    #[default]
+    None,
+    Select,
+    Drag,
+    Focus,
+}
+
+/// Gesture to action mapping for the `gesture` WebSocket message.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+#[serde(default, rename_all = "camelCase")]
+pub struct XRGestureSettings {
+    pub pinch: GestureAction,
+    pub grab: GestureAction,
+    pub point: GestureAction,
+    /// Largest distance, in graph units, a node may sit from a gesture ray
+    /// and still be picked.
+    #[serde(alias = "pick_radius")]
+    pub pick_radius: f32,
+}
+
+impl Default for XRGestureSettings {
+    fn default() -> Self {
+        Self {
+            pinch: GestureAction::Select,
+            grab: GestureAction::Drag,
+            point: GestureAction::Focus,
+            pick_radius: 5.0,
+        }
+    }
+}
+
+/// One entry in the positional audio cue catalog.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioCueDefinition {
+    /// Sound asset URL, relative to the client origin.
+    pub sound: String,
+    #[serde(default = "default_cue_volume")]
+    pub volume: f32,
+    /// Distance, in graph units, within which the cue plays at full volume.
+    #[serde(default = "default_cue_ref_distance", alias = "ref_distance")]
+    pub ref_distance: f32,
+}
+
+fn default_cue_volume() -> f32 {
+    0.7
+}
+
+fn default_cue_ref_distance() -> f32 {
+    10.0
+}
+
+/// Cue id to sound, served to XR clients as the audio cue catalog.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+#[serde(default, rename_all = "camelCase")]
+pub struct XRAudioCueSettings {
+    pub cues: BTreeMap<String, AudioCueDefinition>,
+}
+
+impl Default for XRAudioCueSettings {
+    fn default() -> Self {
+        let cue = |sound: &str, volume: f32| AudioCueDefinition {
+            sound: sound.to_string(),
+            volume,
+            ref_distance: default_cue_ref_distance(),
+        };
+        Self {
+            cues: BTreeMap::from([
+                ("nodeAppear".to_string(), cue("/audio/cues/node-appear.ogg", 0.5)),
+                ("searchHit".to_string(), cue("/audio/cues/search-hit.ogg", 0.8)),
+                ("chatCitation".to_string(), cue("/audio/cues/chat-citation.ogg", 0.7)),
+            ]),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default, Type, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct XRSettings {
@@ -60,6 +141,10 @@ pub struct XRSettings {
     pub hand_ray_width: f32,
     #[serde(alias = "gesture_smoothing")]
     pub gesture_smoothing: f32,
+    #[serde(default)]
+    pub gestures: XRGestureSettings,
+    #[serde(default, alias = "audio_cues")]
+    pub audio_cues: XRAudioCueSettings,
 
     #[serde(alias = "enable_haptics")]
     pub enable_haptics: bool,
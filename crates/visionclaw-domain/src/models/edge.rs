@@ -152,6 +152,126 @@ impl std::fmt::Display for SemanticEdgeType {
     }
 }
 
+/// Signal that caused (part of) an edge to exist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProvenanceSource {
+    /// `[[wikilink]]` written in the source page
+    Wikilink,
+    /// pages sharing tags
+    TagCooccurrence,
+    /// pages that both linked a folded stub page
+    CoCitation,
+    /// created by a user through the API
+    Manual,
+    /// embedding / semantic similarity score
+    Semantic,
+    /// typed ontology relation from a JSON-LD / OWL block
+    Ontology,
+    /// whelk reasoner output
+    Inferred,
+    /// synthetic domain-root membership
+    Hierarchy,
+}
+
+/// One source's share of an edge's weight.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvenanceContribution {
+    pub source: ProvenanceSource,
+    pub weight: f32,
+    /// e.g. number of wikilink occurrences or shared tags
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+    /// free-form detail such as the relation type or similarity model
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// How an edge was derived, as a list of weighted contributions.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EdgeProvenance {
+    pub contributions: Vec<ProvenanceContribution>,
+    /// True when reconstructed from the edge's type and id (e.g. after a
+    /// reload from Oxigraph, which does not persist provenance) rather than
+    /// recorded when the edge was created.
+    #[serde(default)]
+    pub reconstructed: bool,
+}
+
+impl EdgeProvenance {
+    pub fn from_source(source: ProvenanceSource, weight: f32) -> Self {
+        let mut provenance = Self::default();
+        provenance.add(source, weight, None, None);
+        provenance
+    }
+
+    /// Add a contribution, folding it into an existing entry for the same
+    /// source (weights and counts are summed).
+    pub fn add(
+        &mut self,
+        source: ProvenanceSource,
+        weight: f32,
+        count: Option<u32>,
+        detail: Option<String>,
+    ) -> &mut Self {
+        if let Some(existing) = self.contributions.iter_mut().find(|c| c.source == source) {
+            existing.weight += weight;
+            existing.count = match (existing.count, count) {
+                (Some(a), Some(b)) => Some(a + b),
+                (a, b) => a.or(b),
+            };
+            if existing.detail.is_none() {
+                existing.detail = detail;
+            }
+        } else {
+            self.contributions.push(ProvenanceContribution {
+                source,
+                weight,
+                count,
+                detail,
+            });
+        }
+        self
+    }
+
+    pub fn total_weight(&self) -> f32 {
+        self.contributions.iter().map(|c| c.weight).sum()
+    }
+
+    /// The contribution with the largest weight.
+    pub fn dominant(&self) -> Option<&ProvenanceContribution> {
+        self.contributions
+            .iter()
+            .max_by(|a, b| a.weight.partial_cmp(&b.weight).unwrap_or(std::cmp::Ordering::Equal))
+    }
+
+    /// Best-effort provenance from the fields that survive persistence: the
+    /// edge id conventions used by the sync pipeline and the edge type.
+    pub fn reconstruct(id: &str, edge_type: Option<&str>, weight: f32) -> Self {
+        let (source, detail) = match edge_type {
+            _ if id.ends_with("_cocite") => (ProvenanceSource::CoCitation, None),
+            _ if id.starts_with("domain_") => (ProvenanceSource::Hierarchy, None),
+            Some("co_citation") => (ProvenanceSource::CoCitation, None),
+            Some("inferred") => (ProvenanceSource::Inferred, None),
+            Some("manual") => (ProvenanceSource::Manual, None),
+            Some("tag" | "tag_cooccurrence" | "shared_tag") => {
+                (ProvenanceSource::TagCooccurrence, None)
+            }
+            Some("semantic" | "similarity" | "similar_to") => {
+                (ProvenanceSource::Semantic, edge_type.map(str::to_string))
+            }
+            None | Some("explicit_link" | "link" | "wikilink") => (ProvenanceSource::Wikilink, None),
+            Some(other) => (ProvenanceSource::Ontology, Some(other.to_string())),
+        };
+        let mut provenance = Self::default();
+        provenance.add(source, weight, None, detail);
+        provenance.reconstructed = true;
+        provenance
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Edge {
@@ -167,6 +287,9 @@ pub struct Edge {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, String>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<EdgeProvenance>,
 }
 
 impl Edge {
@@ -180,6 +303,7 @@ impl Edge {
             edge_type: None,
             owl_property_iri: None,
             metadata: None,
+            provenance: None,
         }
     }
 
@@ -198,6 +322,18 @@ impl Edge {
         self
     }
 
+    pub fn with_provenance(mut self, provenance: EdgeProvenance) -> Self {
+        self.provenance = Some(provenance);
+        self
+    }
+
+    /// Recorded provenance, or one reconstructed from the edge's id and type.
+    pub fn provenance_or_reconstructed(&self) -> EdgeProvenance {
+        self.provenance.clone().unwrap_or_else(|| {
+            EdgeProvenance::reconstruct(&self.id, self.edge_type.as_deref(), self.weight)
+        })
+    }
+
     pub fn add_metadata(mut self, key: String, value: String) -> Self {
         if let Some(ref mut map) = self.metadata {
             map.insert(key, value);
@@ -382,5 +518,48 @@ mod tests {
         assert!(!json.contains("edgeType"));
         assert!(!json.contains("owlPropertyIri"));
         assert!(!json.contains("metadata"));
+        assert!(!json.contains("provenance"));
+    }
+
+    // --- EdgeProvenance ---
+
+    #[test]
+    fn provenance_add_merges_same_source() {
+        let mut p = EdgeProvenance::default();
+        p.add(ProvenanceSource::Wikilink, 1.0, Some(2), None)
+            .add(ProvenanceSource::Wikilink, 0.5, Some(1), None)
+            .add(ProvenanceSource::Semantic, 0.3, None, Some("cosine".into()));
+        assert_eq!(p.contributions.len(), 2);
+        assert_eq!(p.contributions[0].count, Some(3));
+        assert!((p.total_weight() - 1.8).abs() < 1e-6);
+        assert_eq!(p.dominant().unwrap().source, ProvenanceSource::Wikilink);
+    }
+
+    #[test]
+    fn provenance_reconstruct_from_id_and_type() {
+        let p = EdgeProvenance::reconstruct("1_2_cocite", Some("co_citation"), 0.2);
+        assert_eq!(p.contributions[0].source, ProvenanceSource::CoCitation);
+        assert!(p.reconstructed);
+
+        let p = EdgeProvenance::reconstruct("domain_1_2", Some("hierarchical"), 1.5);
+        assert_eq!(p.contributions[0].source, ProvenanceSource::Hierarchy);
+
+        let p = EdgeProvenance::reconstruct("1_2_wikilink", Some("explicit_link"), 1.0);
+        assert_eq!(p.contributions[0].source, ProvenanceSource::Wikilink);
+
+        let p = EdgeProvenance::reconstruct("1_2_has_part", Some("has_part"), 1.0);
+        assert_eq!(p.contributions[0].source, ProvenanceSource::Ontology);
+        assert_eq!(p.contributions[0].detail.as_deref(), Some("has_part"));
+    }
+
+    #[test]
+    fn edge_provenance_serde_roundtrip() {
+        let e = Edge::new(1, 2, 1.0)
+            .with_provenance(EdgeProvenance::from_source(ProvenanceSource::Manual, 1.0));
+        let json = serde_json::to_string(&e).unwrap();
+        assert!(json.contains("\"provenance\""));
+        let back: Edge = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.provenance, e.provenance);
+        assert!(!back.provenance_or_reconstructed().reconstructed);
     }
 }
@@ -33,6 +33,12 @@ pub struct Metadata {
     pub last_perplexity_process: Option<DateTime<Utc>>,
     #[serde(default)]
     pub topic_counts: HashMap<String, usize>,
+    /// External (http/https) URLs referenced by the page, for link-rot checks.
+    #[serde(default)]
+    pub external_links: Vec<String>,
+    /// Detected content language, ISO 639-3 (`eng`, `deu`); `None` when undetermined.
+    #[serde(default)]
+    pub language: Option<String>,
     // Ontology fields from new header format
     #[serde(default)]
     pub term_id: Option<String>,
@@ -13,7 +13,9 @@ pub mod simulation_params;
 pub mod workspace;
 
 pub use canonical_entity::{CanonicalEntity, EntityKind, OutboundLink};
-pub use edge::{Edge, SemanticEdgeType};
+pub use edge::{
+    Edge, EdgeProvenance, ProvenanceContribution, ProvenanceSource, SemanticEdgeType,
+};
 pub use graph::GraphData;
 pub use metadata::MetadataStore;
 pub use node::{Node, Population};
@@ -10,7 +10,9 @@
 use serde::{Deserialize, Serialize};
 
 use crate::types::layout::LayoutMode;
-use crate::types::physics_config::{AutoBalanceConfig, AutoPauseConfig, PhysicsSettings};
+use crate::types::physics_config::{
+    AutoBalanceConfig, AutoPauseConfig, DragReleaseConfig, PhysicsSettings,
+};
 
 fn default_lin_log_mode() -> bool { true }
 fn default_scaling_ratio() -> f32 { 10.0 }
@@ -100,6 +102,9 @@ pub struct SimulationParams {
     pub auto_balance_config: AutoBalanceConfig,
 
     pub auto_pause_config: AutoPauseConfig,
+    /// How a node behaves when a drag ends.
+    #[serde(default)]
+    pub drag_release: DragReleaseConfig,
     pub is_physics_paused: bool,
     pub equilibrium_stability_counter: u32,
 
@@ -332,6 +337,7 @@ impl From<&PhysicsSettings> for SimulationParams {
             auto_balance_interval_ms: physics.auto_balance_interval_ms,
             auto_balance_config: physics.auto_balance_config.clone(),
             auto_pause_config: physics.auto_pause.clone(),
+            drag_release: physics.drag_release.clone(),
             is_physics_paused: false,
             equilibrium_stability_counter: 0,
             iterations: physics.iterations,
@@ -181,6 +181,75 @@ impl Workspace {
         self.owner_id = Some(owner_id);
         self.updated_at = time::now();
     }
+
+    /// Settings overriding the global ones for this workspace, as a sparse
+    /// camelCase tree (e.g. `{"visualisation":{"graphs":{"logseq":{"physics":{...}}}}}`).
+    pub fn settings_overlay(&self) -> Option<&serde_json::Value> {
+        self.metadata.get(SETTINGS_OVERLAY_KEY)
+    }
+
+    /// Apply a JSON merge patch (RFC 7386) to the settings overlay: objects
+    /// merge recursively, `null` removes a key and falls back to the global value.
+    pub fn patch_settings_overlay(&mut self, patch: serde_json::Value) {
+        let mut overlay = self
+            .metadata
+            .remove(SETTINGS_OVERLAY_KEY)
+            .unwrap_or_else(|| serde_json::Value::Object(Default::default()));
+        merge_patch(&mut overlay, patch);
+        if overlay.as_object().is_some_and(|o| !o.is_empty()) {
+            self.metadata.insert(SETTINGS_OVERLAY_KEY.to_string(), overlay);
+        }
+        self.updated_at = time::now();
+    }
+}
+
+/// Metadata key holding a workspace's settings overlay.
+pub const SETTINGS_OVERLAY_KEY: &str = "settingsOverlay";
+
+/// Top-level settings sections a workspace may override. Physics lives under
+/// `visualisation.graphs.<graph>.physics`.
+pub const OVERLAYABLE_SETTINGS: &[&str] = &["visualisation"];
+
+/// RFC 7386 merge patch; empty objects left behind by removals are pruned so
+/// the stored overlay stays minimal.
+fn merge_patch(target: &mut serde_json::Value, patch: serde_json::Value) {
+    let serde_json::Value::Object(patch) = patch else {
+        *target = patch;
+        return;
+    };
+    if !target.is_object() {
+        *target = serde_json::Value::Object(Default::default());
+    }
+    let map = target.as_object_mut().expect("target is an object");
+    for (key, value) in patch {
+        if value.is_null() {
+            map.remove(&key);
+            continue;
+        }
+        let entry = map.entry(key.clone()).or_insert(serde_json::Value::Null);
+        merge_patch(entry, value);
+        if entry.as_object().is_some_and(|o| o.is_empty()) {
+            map.remove(&key);
+        }
+    }
+}
+
+/// Layer a workspace overlay over the serialized global settings. Objects
+/// merge key by key; any other overlay value replaces the global one.
+pub fn overlay_settings(global: &mut serde_json::Value, overlay: &serde_json::Value) {
+    match (global, overlay) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(over)) => {
+            for (key, value) in over {
+                match base.get_mut(key) {
+                    Some(existing) => overlay_settings(existing, value),
+                    None => {
+                        base.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (base, over) => *base = over.clone(),
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type, Validate)]
@@ -367,6 +436,40 @@ impl Default for WorkspaceQuery {
 mod tests {
     use super::*;
 
+    #[test]
+    fn settings_overlay_patches_merge_and_null_removes() {
+        let mut ws = Workspace::default();
+        ws.patch_settings_overlay(serde_json::json!({
+            "visualisation": {"graphs": {"logseq": {"physics": {"springK": 2.0, "damping": 0.9}}}}
+        }));
+        ws.patch_settings_overlay(serde_json::json!({
+            "visualisation": {"graphs": {"logseq": {"physics": {"damping": null}}}}
+        }));
+        assert_eq!(
+            ws.settings_overlay().unwrap(),
+            &serde_json::json!({"visualisation": {"graphs": {"logseq": {"physics": {"springK": 2.0}}}}})
+        );
+
+        ws.patch_settings_overlay(serde_json::json!({"visualisation": null}));
+        assert!(ws.settings_overlay().is_none(), "empty overlay is dropped");
+    }
+
+    #[test]
+    fn overlay_settings_deep_merges_over_global() {
+        let mut global = serde_json::json!({
+            "visualisation": {"glow": {"enabled": true, "intensity": 1.0}, "bloom": {"enabled": true}},
+            "system": {"debug": false}
+        });
+        overlay_settings(
+            &mut global,
+            &serde_json::json!({"visualisation": {"glow": {"intensity": 0.2}}}),
+        );
+        assert_eq!(global["visualisation"]["glow"]["enabled"], true);
+        assert_eq!(global["visualisation"]["glow"]["intensity"], 0.2);
+        assert_eq!(global["visualisation"]["bloom"]["enabled"], true);
+        assert_eq!(global["system"]["debug"], false);
+    }
+
     #[test]
     fn workspace_new_sets_expected_fields() {
         let ws = Workspace::new(
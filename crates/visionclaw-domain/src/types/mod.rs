@@ -14,7 +14,7 @@ pub use actor_types::{AutoBalanceNotification, PhysicsState};
 pub use layout::{ConstraintZone, LayoutMode, LayoutModeConfig, LayoutStatus};
 pub use physics_config::{
     AutoBalanceConfig, AutoPauseConfig, ClusteringConfiguration, ConstraintSystem,
-    LegacyConstraintData, PhysicsSettings, PhysicsUpdate, CANONICAL_MAX_FORCE,
-    CANONICAL_MAX_VELOCITY,
+    DragReleaseConfig, DragReleaseMode, LegacyConstraintData, PhysicsSettings, PhysicsUpdate,
+    CANONICAL_MAX_FORCE, CANONICAL_MAX_VELOCITY,
 };
 pub use vec3::{BinaryNodeData, Vec3Data};
@@ -86,6 +86,63 @@ impl AutoPauseConfig {
     }
 }
 
+/// What a node does when the user lets go of it after a drag.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum DragReleaseMode {
+    /// Rejoin the simulation at rest where it was dropped.
+    #[default]
+    Free,
+    /// Keep the velocity of the last drag movement and glide to a stop.
+    Inertia,
+    /// Ease back to where the drag started.
+    SpringBack,
+    /// Stay where it was dropped until dragged again.
+    Pin,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type, Validate)]
+#[serde(rename_all = "camelCase")]
+pub struct DragReleaseConfig {
+    #[serde(default, alias = "mode")]
+    pub mode: DragReleaseMode,
+    /// Multiplier on the release velocity in `inertia` mode.
+    #[validate(range(min = 0.0, max = 5.0))]
+    #[serde(default = "default_inertia_scale", alias = "inertia_scale")]
+    pub inertia_scale: f32,
+    /// Fraction of glide speed kept each frame in `inertia` mode.
+    #[validate(range(min = 0.0, max = 0.99))]
+    #[serde(default = "default_inertia_decay", alias = "inertia_decay")]
+    pub inertia_decay: f32,
+    /// Frames the return takes in `springBack` mode.
+    #[validate(range(min = 1, max = 600))]
+    #[serde(default = "default_spring_back_frames", alias = "spring_back_frames")]
+    pub spring_back_frames: u32,
+}
+
+fn default_inertia_scale() -> f32 {
+    1.0
+}
+
+fn default_inertia_decay() -> f32 {
+    0.92
+}
+
+fn default_spring_back_frames() -> u32 {
+    30
+}
+
+impl Default for DragReleaseConfig {
+    fn default() -> Self {
+        Self {
+            mode: DragReleaseMode::default(),
+            inertia_scale: default_inertia_scale(),
+            inertia_decay: default_inertia_decay(),
+            spring_back_frames: default_spring_back_frames(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default, Type, Validate)]
 #[serde(rename_all = "camelCase")]
 pub struct AutoBalanceConfig {
@@ -216,6 +273,9 @@ pub struct PhysicsSettings {
     #[serde(default, alias = "auto_pause")]
     #[validate(nested)]
     pub auto_pause: AutoPauseConfig,
+    #[serde(default, alias = "drag_release")]
+    #[validate(nested)]
+    pub drag_release: DragReleaseConfig,
     #[serde(default = "default_bounds_size", alias = "bounds_size")]
     pub bounds_size: f32,
     #[serde(alias = "separation_radius")]
@@ -332,6 +392,7 @@ impl Default for PhysicsSettings {
             auto_balance_interval_ms: 500,
             auto_balance_config: AutoBalanceConfig::default(),
             auto_pause: AutoPauseConfig::default(),
+            drag_release: DragReleaseConfig::default(),
             // Canonical compact profile (single source of truth). The YAML
             // visualisation.graphs.logseq.physics block is NOT applied to the
             // running simulation — boot uses these defaults whenever the sqlite
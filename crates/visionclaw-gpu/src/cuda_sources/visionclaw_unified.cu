@@ -9,6 +9,7 @@
 #include <thrust/execution_policy.h>
 #include <cub/cub.cuh>
 #include <curand_kernel.h>
+#include <cuda_fp16.h>
 #include <cfloat>
 
 extern "C" {
@@ -126,6 +127,21 @@ enum ConstraintKind {
 // =============================================================================
 
 __device__ inline float3 make_vec3(float x, float y, float z) { return make_float3(x, y, z); }
+
+// Velocity of node idx: from the f16 planes when given (f16 state storage),
+// otherwise from the f32 ones.
+__device__ __forceinline__ float3 load_velocity(
+    const float* __restrict__ vel_x, const float* __restrict__ vel_y,
+    const float* __restrict__ vel_z, const __half* __restrict__ vel_half_x,
+    const __half* __restrict__ vel_half_y, const __half* __restrict__ vel_half_z,
+    const int idx)
+{
+    if (vel_half_x != nullptr) {
+        return make_vec3(__half2float(vel_half_x[idx]), __half2float(vel_half_y[idx]),
+                         __half2float(vel_half_z[idx]));
+    }
+    return make_vec3(vel_x[idx], vel_y[idx], vel_z[idx]);
+}
 __device__ inline float3 vec3_add(float3 a, float3 b) { return make_float3(a.x + b.x, a.y + b.y, a.z + b.z); }
 __device__ inline float3 vec3_sub(float3 a, float3 b) { return make_float3(a.x - b.x, a.y - b.y, a.z - b.z); }
 __device__ inline float3 vec3_scale(float3 v, float s) { return make_float3(v.x * s, v.y * s, v.z * s); }
@@ -713,38 +729,21 @@ extern "C" __global__ void cluster_cohesion_kernel(
 // Integration Pass Kernel
 // =============================================================================
 
-__global__ void integrate_pass_kernel(
-    const float* __restrict__ pos_in_x,
-    const float* __restrict__ pos_in_y,
-    const float* __restrict__ pos_in_z,
-    const float* __restrict__ vel_in_x,
-    const float* __restrict__ vel_in_y,
-    const float* __restrict__ vel_in_z,
+// Integrates one node in f32, updating pos and vel in place. Shared by the
+// f32 and f16-storage kernels, which differ only in how state is stored.
+__device__ __forceinline__ void integrate_node(
+    const int idx,
     const float* __restrict__ force_x,
     const float* __restrict__ force_y,
     const float* __restrict__ force_z,
     const float* __restrict__ mass,
-    float* __restrict__ pos_out_x,
-    float* __restrict__ pos_out_y,
-    float* __restrict__ pos_out_z,
-    float* __restrict__ vel_out_x,
-    float* __restrict__ vel_out_y,
-    float* __restrict__ vel_out_z,
-    const int num_nodes,
-    // Ontology class metadata
-    const int* __restrict__ class_id,       // [num_nodes] OWL class IDs
-    const float* __restrict__ class_charge, // [num_nodes] class-specific charge modifiers
-    const float* __restrict__ class_mass,   // [num_nodes] class-specific mass modifiers
-    // FA2 adaptive speed: previous-step forces (read this step, will be updated)
-    float* __restrict__ prev_force_x,       // [num_nodes] force from prior step (in/out)
+    const float* __restrict__ class_mass,
+    float* __restrict__ prev_force_x,
     float* __restrict__ prev_force_y,
-    float* __restrict__ prev_force_z)
+    float* __restrict__ prev_force_z,
+    float3& pos,
+    float3& vel)
 {
-    const int idx = blockIdx.x * blockDim.x + threadIdx.x;
-    if (idx >= num_nodes) return;
-
-    float3 pos = make_vec3(pos_in_x[idx], pos_in_y[idx], pos_in_z[idx]);
-    float3 vel = make_vec3(vel_in_x[idx], vel_in_y[idx], vel_in_z[idx]);
     float3 force = make_vec3(force_x[idx], force_y[idx], force_z[idx]);
 
     // Apply class-based mass modifier (default 1.0 if nullptr)
@@ -872,6 +871,42 @@ __global__ void integrate_pass_kernel(
             vel.z *= c_params.boundary_damping;
         }
     }
+}
+
+__global__ void integrate_pass_kernel(
+    const float* __restrict__ pos_in_x,
+    const float* __restrict__ pos_in_y,
+    const float* __restrict__ pos_in_z,
+    const float* __restrict__ vel_in_x,
+    const float* __restrict__ vel_in_y,
+    const float* __restrict__ vel_in_z,
+    const float* __restrict__ force_x,
+    const float* __restrict__ force_y,
+    const float* __restrict__ force_z,
+    const float* __restrict__ mass,
+    float* __restrict__ pos_out_x,
+    float* __restrict__ pos_out_y,
+    float* __restrict__ pos_out_z,
+    float* __restrict__ vel_out_x,
+    float* __restrict__ vel_out_y,
+    float* __restrict__ vel_out_z,
+    const int num_nodes,
+    // Ontology class metadata
+    const int* __restrict__ class_id,       // [num_nodes] OWL class IDs
+    const float* __restrict__ class_charge, // [num_nodes] class-specific charge modifiers
+    const float* __restrict__ class_mass,   // [num_nodes] class-specific mass modifiers
+    // FA2 adaptive speed: previous-step forces (read this step, will be updated)
+    float* __restrict__ prev_force_x,       // [num_nodes] force from prior step (in/out)
+    float* __restrict__ prev_force_y,
+    float* __restrict__ prev_force_z)
+{
+    const int idx = blockIdx.x * blockDim.x + threadIdx.x;
+    if (idx >= num_nodes) return;
+
+    float3 pos = make_vec3(pos_in_x[idx], pos_in_y[idx], pos_in_z[idx]);
+    float3 vel = make_vec3(vel_in_x[idx], vel_in_y[idx], vel_in_z[idx]);
+    integrate_node(idx, force_x, force_y, force_z, mass, class_mass,
+                   prev_force_x, prev_force_y, prev_force_z, pos, vel);
 
     pos_out_x[idx] = pos.x;
     pos_out_y[idx] = pos.y;
@@ -881,6 +916,45 @@ __global__ void integrate_pass_kernel(
     vel_out_z[idx] = vel.z;
 }
 
+// F16 state storage: velocities live only as halves and positions in a
+// single f32 buffer, so there is no ping-pong set. Each thread reads and
+// writes only its own node, which makes the in-place update safe.
+__global__ void integrate_pass_f16_kernel(
+    float* __restrict__ pos_x,
+    float* __restrict__ pos_y,
+    float* __restrict__ pos_z,
+    __half* __restrict__ vel_x,
+    __half* __restrict__ vel_y,
+    __half* __restrict__ vel_z,
+    const float* __restrict__ force_x,
+    const float* __restrict__ force_y,
+    const float* __restrict__ force_z,
+    const float* __restrict__ mass,
+    const int num_nodes,
+    const int* __restrict__ class_id,
+    const float* __restrict__ class_charge,
+    const float* __restrict__ class_mass,
+    float* __restrict__ prev_force_x,
+    float* __restrict__ prev_force_y,
+    float* __restrict__ prev_force_z)
+{
+    const int idx = blockIdx.x * blockDim.x + threadIdx.x;
+    if (idx >= num_nodes) return;
+
+    float3 pos = make_vec3(pos_x[idx], pos_y[idx], pos_z[idx]);
+    float3 vel = make_vec3(__half2float(vel_x[idx]), __half2float(vel_y[idx]),
+                           __half2float(vel_z[idx]));
+    integrate_node(idx, force_x, force_y, force_z, mass, class_mass,
+                   prev_force_x, prev_force_y, prev_force_z, pos, vel);
+
+    pos_x[idx] = pos.x;
+    pos_y[idx] = pos.y;
+    pos_z[idx] = pos.z;
+    vel_x[idx] = __float2half_rn(vel.x);
+    vel_y[idx] = __float2half_rn(vel.y);
+    vel_z[idx] = __float2half_rn(vel.z);
+}
+
 // =============================================================================
 // Device-side Frontier Compaction for SSSP
 // =============================================================================
@@ -1796,7 +1870,11 @@ __global__ void calculate_kinetic_energy_kernel(
     float* __restrict__ partial_kinetic_energy,
     int* __restrict__ active_node_count,
     const int num_nodes,
-    const float min_velocity_threshold)
+    const float min_velocity_threshold,
+    // f16 velocity planes (nullptr unless state is stored as f16)
+    const __half* __restrict__ vel_half_x,
+    const __half* __restrict__ vel_half_y,
+    const __half* __restrict__ vel_half_z)
 {
     const int tid = threadIdx.x;
     const int idx = blockIdx.x * blockDim.x + tid;
@@ -1812,10 +1890,8 @@ __global__ void calculate_kinetic_energy_kernel(
     
     // Calculate kinetic energy for this thread's node
     if (idx < num_nodes) {
-        float vx = vel_x[idx];
-        float vy = vel_y[idx];
-        float vz = vel_z[idx];
-        float vel_sq = vx * vx + vy * vy + vz * vz;
+        float3 v = load_velocity(vel_x, vel_y, vel_z, vel_half_x, vel_half_y, vel_half_z, idx);
+        float vel_sq = v.x * v.x + v.y * v.y + v.z * v.z;
         
         // Use stability threshold from parameter
         float min_vel_sq = min_velocity_threshold * min_velocity_threshold;
@@ -1938,7 +2014,11 @@ __global__ void force_pass_with_stability_kernel(
     const float* __restrict__ node_degrees,      // [num_nodes] sum of incident edge weights
     // Per-population spring strength multiplier (nullptr = uniform 1.0); mirrors
     // force_pass_kernel so the spring sliders behave identically on both paths.
-    const float* __restrict__ spring_scale)      // [num_nodes] per-node spring multiplier
+    const float* __restrict__ spring_scale,      // [num_nodes] per-node spring multiplier
+    // f16 velocity planes (nullptr unless state is stored as f16)
+    const __half* __restrict__ vel_half_x,
+    const __half* __restrict__ vel_half_y,
+    const __half* __restrict__ vel_half_z)
 {
     const int idx = blockIdx.x * blockDim.x + threadIdx.x;
     if (idx >= num_nodes) return;
@@ -1952,10 +2032,8 @@ __global__ void force_pass_with_stability_kernel(
     }
     
     // Per-node stability check
-    float vx = vel_in_x[idx];
-    float vy = vel_in_y[idx];
-    float vz = vel_in_z[idx];
-    float vel_sq = vx * vx + vy * vy + vz * vz;
+    float3 v = load_velocity(vel_in_x, vel_in_y, vel_in_z, vel_half_x, vel_half_y, vel_half_z, idx);
+    float vel_sq = v.x * v.x + v.y * v.y + v.z * v.z;
     float min_vel_sq = c_params.min_velocity_threshold * c_params.min_velocity_threshold;
     
     // Skip force calculation for nearly stationary nodes
@@ -22,6 +22,21 @@ static RUNTIME_CUDA_ARCH: OnceLock<String> = OnceLock::new();
 /// Cached max PTX ISA version the installed driver supports.
 static RUNTIME_MAX_PTX_ISA: OnceLock<(u32, u32)> = OnceLock::new();
 
+/// Configured directory of precompiled PTX modules; see [`set_ptx_dir`].
+static CONFIGURED_PTX_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Search `dir` for precompiled PTX ahead of the source tree copies. Set
+/// once at startup from the server configuration; later calls are ignored.
+pub fn set_ptx_dir(dir: PathBuf) {
+    if CONFIGURED_PTX_DIR.set(dir.clone()).is_err() {
+        warn!("PTX directory already configured, ignoring {}", dir.display());
+    }
+}
+
+pub fn configured_ptx_dir() -> Option<&'static Path> {
+    CONFIGURED_PTX_DIR.get().map(PathBuf::as_path)
+}
+
 /// Detects the GPU compute capability at runtime by querying `nvidia-smi`.
 /// The result is cached for the lifetime of the process.
 /// Falls back to `DEFAULT_CUDA_ARCH` ("75") if detection fails.
@@ -427,6 +442,11 @@ fn load_precompiled_ptx(module: PTXModule) -> Result<String, String> {
         ptx_paths.push(PathBuf::from(env_path));
     }
 
+    // 1c. Configured PTX directory (`gpu.ptx_path`).
+    if let Some(dir) = configured_ptx_dir() {
+        ptx_paths.push(dir.join(&ptx_file));
+    }
+
     // 2. Build output directory (recompiled by build.rs from current .cu source)
     //    OUT_DIR is only available at build time, so scan target/*/build/*/out/.
     //    Incremental builds leave stale build-hash dirs alongside the current one
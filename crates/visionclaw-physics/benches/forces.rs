@@ -0,0 +1,114 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use visionclaw_physics::{
+    CenterGravity, Collision, Constraint, Constraints, Edge, ForceModel, ForcePipeline, Graph,
+    Integrator, Layout, Repulsion, Spring,
+};
+
+const SIZES: [usize; 3] = [500, 2_000, 8_000];
+
+/// A ring with a few pseudo-random chords per node, spread at roughly the
+/// density the server's default settings settle to.
+fn fixture(n: usize) -> (Graph, Layout) {
+    let mut seed = 0x9E37_79B9u32;
+    let mut next = move || {
+        seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        seed as usize
+    };
+    let mut edges: Vec<Edge> = (0..n).map(|i| Edge::new(i, (i + 1) % n)).collect();
+    edges.extend((0..n * 2).map(|_| Edge::new(next() % n, next() % n)));
+    let radius = 12.0 * (n as f32).cbrt();
+    (Graph::new(n, edges), Layout::spiral(n, radius))
+}
+
+fn constraints(n: usize) -> Constraints {
+    Constraints::new(
+        (0..n / 10)
+            .map(|i| match i % 3 {
+                0 => Constraint::Distance {
+                    a: i,
+                    b: n - 1 - i,
+                    distance: 30.0,
+                    weight: 0.5,
+                },
+                1 => Constraint::Position {
+                    node: i,
+                    target: [0.0; 3],
+                    weight: 0.1,
+                },
+                _ => Constraint::Separation {
+                    a: i,
+                    b: (i * 7) % n,
+                    min_distance: 20.0,
+                    weight: 1.0,
+                },
+            })
+            .collect(),
+    )
+}
+
+fn bench_model<M: ForceModel>(c: &mut Criterion, make: impl Fn(usize) -> M) {
+    let mut group = c.benchmark_group(make(0).name());
+    // Repulsion at 8k nodes takes ~0.5 s an iteration; keep a full run short.
+    group.sample_size(20);
+    for n in SIZES {
+        let (graph, layout) = fixture(n);
+        let model = make(n);
+        let mut forces = vec![[0.0f32; 3]; n];
+        group.bench_with_input(BenchmarkId::from_parameter(n), &n, |b, _| {
+            b.iter(|| {
+                forces.iter_mut().for_each(|f| *f = [0.0; 3]);
+                model.accumulate(black_box(&graph), black_box(&layout.positions), &mut forces);
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_spring(c: &mut Criterion) {
+    bench_model(c, |_| Spring::default());
+}
+
+fn bench_repulsion(c: &mut Criterion) {
+    bench_model(c, |_| Repulsion::default());
+}
+
+fn bench_gravity(c: &mut Criterion) {
+    bench_model(c, |_| CenterGravity::default());
+}
+
+fn bench_collision(c: &mut Criterion) {
+    bench_model(c, |_| Collision::default());
+}
+
+fn bench_constraints(c: &mut Criterion) {
+    bench_model(c, constraints);
+}
+
+fn bench_pipeline_step(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pipeline_step");
+    group.sample_size(20);
+    for n in SIZES {
+        let (graph, mut layout) = fixture(n);
+        let mut pipeline = ForcePipeline::new(Integrator::default())
+            .with(Spring::default())
+            .with(Repulsion::default())
+            .with(Collision::default())
+            .with(CenterGravity::default())
+            .with(constraints(n));
+        group.bench_with_input(BenchmarkId::from_parameter(n), &n, |b, _| {
+            b.iter(|| pipeline.step(black_box(&graph), &mut layout))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_spring,
+    bench_repulsion,
+    bench_gravity,
+    bench_collision,
+    bench_constraints,
+    bench_pipeline_step
+);
+criterion_main!(benches);
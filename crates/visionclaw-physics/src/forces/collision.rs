@@ -0,0 +1,74 @@
+//! Short-range overlap prevention.
+
+use super::ForceModel;
+use crate::graph::{Graph, SpatialGrid};
+use crate::vec3::{self, Vec3};
+
+/// Pushes apart nodes closer than `radius` with a force that grows linearly
+/// from zero at `radius` to `max_force` at contact, so nodes cannot overlap
+/// however weak repulsion is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Collision {
+    pub radius: f32,
+    pub max_force: f32,
+}
+
+impl Default for Collision {
+    fn default() -> Self {
+        Self {
+            radius: 2.1155233,
+            max_force: 150.0,
+        }
+    }
+}
+
+impl ForceModel for Collision {
+    fn name(&self) -> &'static str {
+        "collision"
+    }
+
+    fn accumulate(&self, _graph: &Graph, positions: &[Vec3], forces: &mut [Vec3]) {
+        if self.radius <= 0.0 {
+            return;
+        }
+        let grid = SpatialGrid::build(positions, self.radius);
+        for (i, &p) in positions.iter().enumerate() {
+            let mut total = [0.0; 3];
+            grid.for_each_near(p, |j| {
+                if i == j {
+                    return;
+                }
+                let diff = vec3::sub(p, positions[j]);
+                let dist = vec3::length(diff);
+                if dist >= self.radius || dist <= 1e-6 {
+                    return;
+                }
+                let penetration = (self.radius - dist) / self.radius;
+                vec3::add_assign(
+                    &mut total,
+                    vec3::scale(diff, self.max_force * penetration / dist),
+                );
+            });
+            vec3::add_assign(&mut forces[i], total);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn separates_only_overlapping_nodes() {
+        let collision = Collision {
+            radius: 2.0,
+            max_force: 10.0,
+        };
+        let positions = [[0.0; 3], [1.0, 0.0, 0.0], [10.0, 0.0, 0.0]];
+        let mut forces = vec![[0.0; 3]; 3];
+        collision.accumulate(&Graph::default(), &positions, &mut forces);
+        assert_eq!(forces[0], [-5.0, 0.0, 0.0]);
+        assert_eq!(forces[1], [5.0, 0.0, 0.0]);
+        assert_eq!(forces[2], [0.0; 3]);
+    }
+}
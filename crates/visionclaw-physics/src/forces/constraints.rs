@@ -0,0 +1,153 @@
+//! Layout constraints: target distances, anchor positions and minimum
+//! separations between particular nodes.
+
+use super::ForceModel;
+use crate::graph::Graph;
+use crate::vec3::{self, Vec3};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Constraint {
+    /// Hold `a` and `b` `distance` apart.
+    Distance {
+        a: usize,
+        b: usize,
+        distance: f32,
+        weight: f32,
+    },
+    /// Pull `node` towards `target`.
+    Position {
+        node: usize,
+        target: Vec3,
+        weight: f32,
+    },
+    /// Push `a` and `b` apart while closer than `min_distance`; no force
+    /// beyond it.
+    Separation {
+        a: usize,
+        b: usize,
+        min_distance: f32,
+        weight: f32,
+    },
+}
+
+/// A set of constraints applied as forces. Each constraint's force on a node
+/// is capped at `max_force_per_node`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constraints {
+    pub constraints: Vec<Constraint>,
+    pub max_force_per_node: f32,
+}
+
+impl Constraints {
+    pub fn new(constraints: Vec<Constraint>) -> Self {
+        Self {
+            constraints,
+            max_force_per_node: 50.0,
+        }
+    }
+}
+
+impl ForceModel for Constraints {
+    fn name(&self) -> &'static str {
+        "constraints"
+    }
+
+    fn accumulate(&self, _graph: &Graph, positions: &[Vec3], forces: &mut [Vec3]) {
+        let cap = self.max_force_per_node;
+        let n = positions.len();
+        for constraint in &self.constraints {
+            match *constraint {
+                Constraint::Distance {
+                    a,
+                    b,
+                    distance,
+                    weight,
+                } if a < n && b < n && distance > 0.0 => {
+                    let diff = vec3::sub(positions[a], positions[b]);
+                    let dist = vec3::length(diff);
+                    if dist <= 1e-6 {
+                        continue;
+                    }
+                    let magnitude = (-weight * (dist - distance)).clamp(-cap, cap);
+                    let force = vec3::scale(diff, magnitude / dist);
+                    vec3::add_assign(&mut forces[a], force);
+                    vec3::sub_assign(&mut forces[b], force);
+                }
+                Constraint::Position {
+                    node,
+                    target,
+                    weight,
+                } if node < n => {
+                    let diff = vec3::sub(target, positions[node]);
+                    let dist = vec3::length(diff);
+                    if dist <= 1e-6 {
+                        continue;
+                    }
+                    let magnitude = (weight * dist).min(cap);
+                    vec3::add_assign(&mut forces[node], vec3::scale(diff, magnitude / dist));
+                }
+                Constraint::Separation {
+                    a,
+                    b,
+                    min_distance,
+                    weight,
+                } if a < n && b < n => {
+                    let diff = vec3::sub(positions[a], positions[b]);
+                    let dist = vec3::length(diff);
+                    if dist <= 1e-6 || dist >= min_distance {
+                        continue;
+                    }
+                    let magnitude = (weight * (min_distance - dist)).min(cap);
+                    let force = vec3::scale(diff, magnitude / dist);
+                    vec3::add_assign(&mut forces[a], force);
+                    vec3::sub_assign(&mut forces[b], force);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn applies_each_kind_and_caps_the_force() {
+        let positions = [[0.0; 3], [10.0, 0.0, 0.0], [0.0, 3.0, 0.0]];
+        let mut constraints = Constraints::new(vec![
+            Constraint::Distance {
+                a: 0,
+                b: 1,
+                distance: 4.0,
+                weight: 1.0,
+            },
+            Constraint::Position {
+                node: 2,
+                target: [0.0, 5.0, 0.0],
+                weight: 1.0,
+            },
+            Constraint::Separation {
+                a: 0,
+                b: 2,
+                min_distance: 2.0,
+                weight: 1.0,
+            },
+            Constraint::Position {
+                node: 9,
+                target: [0.0; 3],
+                weight: 1.0,
+            },
+        ]);
+        let mut forces = vec![[0.0; 3]; 3];
+        constraints.accumulate(&Graph::default(), &positions, &mut forces);
+        assert_eq!(forces[0], [6.0, 0.0, 0.0]);
+        assert_eq!(forces[1], [-6.0, 0.0, 0.0]);
+        assert_eq!(forces[2], [0.0, 2.0, 0.0]);
+
+        constraints.max_force_per_node = 1.0;
+        let mut forces = vec![[0.0; 3]; 3];
+        constraints.accumulate(&Graph::default(), &positions, &mut forces);
+        assert_eq!(forces[0], [1.0, 0.0, 0.0]);
+    }
+}
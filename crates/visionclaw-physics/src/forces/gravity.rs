@@ -0,0 +1,43 @@
+//! Pull towards the origin.
+
+use super::ForceModel;
+use crate::graph::Graph;
+use crate::vec3::{self, Vec3};
+
+/// Pulls each node towards the origin in proportion to its distance. This
+/// sets the layout's overall radius against repulsion and keeps nodes that
+/// no edge reaches from drifting off.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CenterGravity {
+    pub k: f32,
+}
+
+impl Default for CenterGravity {
+    fn default() -> Self {
+        Self { k: 0.2 }
+    }
+}
+
+impl ForceModel for CenterGravity {
+    fn name(&self) -> &'static str {
+        "gravity"
+    }
+
+    fn accumulate(&self, _graph: &Graph, positions: &[Vec3], forces: &mut [Vec3]) {
+        for (force, &p) in forces.iter_mut().zip(positions) {
+            vec3::sub_assign(force, vec3::scale(p, self.k));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pulls_towards_the_origin() {
+        let mut forces = vec![[1.0, 0.0, 0.0]];
+        CenterGravity { k: 0.5 }.accumulate(&Graph::default(), &[[4.0, -2.0, 0.0]], &mut forces);
+        assert_eq!(forces, vec![[-1.0, 1.0, 0.0]]);
+    }
+}
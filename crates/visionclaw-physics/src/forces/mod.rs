@@ -0,0 +1,29 @@
+//! Force models. Each adds its contribution to a shared force buffer; a
+//! [`ForcePipeline`](crate::ForcePipeline) runs them in order and then
+//! integrates.
+
+pub mod collision;
+pub mod constraints;
+pub mod gravity;
+pub mod repulsion;
+pub mod spring;
+
+pub use collision::Collision;
+pub use constraints::{Constraint, Constraints};
+pub use gravity::CenterGravity;
+pub use repulsion::Repulsion;
+pub use spring::Spring;
+
+use crate::graph::Graph;
+use crate::vec3::Vec3;
+
+/// One force acting on the layout.
+pub trait ForceModel: Send + Sync {
+    /// Name used in benchmarks and logs.
+    fn name(&self) -> &'static str;
+
+    /// Add this model's force on every node to `forces`, which has one entry
+    /// per entry of `positions`. Models must only add, never overwrite, so
+    /// they compose in any order.
+    fn accumulate(&self, graph: &Graph, positions: &[Vec3], forces: &mut [Vec3]);
+}
@@ -0,0 +1,95 @@
+//! Node-node repulsion.
+
+use super::ForceModel;
+use crate::graph::{Graph, SpatialGrid};
+use crate::vec3::{self, Vec3};
+
+/// Pushes every pair of nodes closer than `cutoff` apart with
+/// `repel_k / (distance² + softening)`, capped at `max_force`. Pairs are
+/// found through a spatial grid with `cutoff`-sized cells, so a step costs
+/// about `n × neighbours` rather than `n²`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Repulsion {
+    pub repel_k: f32,
+    pub softening: f32,
+    pub cutoff: f32,
+    pub max_force: f32,
+}
+
+impl Default for Repulsion {
+    fn default() -> Self {
+        Self {
+            repel_k: 120.0,
+            softening: 0.0001,
+            cutoff: 400.0,
+            max_force: 150.0,
+        }
+    }
+}
+
+impl ForceModel for Repulsion {
+    fn name(&self) -> &'static str {
+        "repulsion"
+    }
+
+    fn accumulate(&self, _graph: &Graph, positions: &[Vec3], forces: &mut [Vec3]) {
+        let grid = SpatialGrid::build(positions, self.cutoff);
+        let cutoff_sq = self.cutoff * self.cutoff;
+        for (i, &p) in positions.iter().enumerate() {
+            let mut total = [0.0; 3];
+            grid.for_each_near(p, |j| {
+                if i == j {
+                    return;
+                }
+                let diff = vec3::sub(p, positions[j]);
+                let dist_sq = vec3::length_sq(diff);
+                if dist_sq >= cutoff_sq || dist_sq <= 1e-6 {
+                    return;
+                }
+                let repulsion = (self.repel_k / (dist_sq + self.softening)).min(self.max_force);
+                vec3::add_assign(&mut total, vec3::scale(diff, repulsion / dist_sq.sqrt()));
+            });
+            vec3::add_assign(&mut forces[i], total);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_matches_all_pairs_within_the_cutoff() {
+        let repulsion = Repulsion {
+            cutoff: 8.0,
+            ..Repulsion::default()
+        };
+        let mut seed = 7u32;
+        let positions: Vec<Vec3> = (0..200)
+            .map(|_| {
+                let mut next = || {
+                    seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                    (seed >> 8) as f32 / (1u32 << 24) as f32 * 40.0 - 20.0
+                };
+                [next(), next(), next()]
+            })
+            .collect();
+        let mut forces = vec![[0.0; 3]; positions.len()];
+        repulsion.accumulate(&Graph::default(), &positions, &mut forces);
+
+        for (i, &p) in positions.iter().enumerate() {
+            let mut expected = [0.0f32; 3];
+            for (j, &q) in positions.iter().enumerate() {
+                let diff = vec3::sub(p, q);
+                let dist_sq = vec3::length_sq(diff);
+                if i != j && dist_sq < 64.0 && dist_sq > 1e-6 {
+                    let f = (repulsion.repel_k / (dist_sq + repulsion.softening))
+                        .min(repulsion.max_force);
+                    vec3::add_assign(&mut expected, vec3::scale(diff, f / dist_sq.sqrt()));
+                }
+            }
+            assert!(vec3::length(vec3::sub(forces[i], expected)) < 1e-3);
+        }
+        assert!(forces.iter().any(|f| vec3::length(*f) > 0.0));
+    }
+}
@@ -0,0 +1,84 @@
+//! Edge attraction.
+
+use super::ForceModel;
+use crate::graph::Graph;
+use crate::vec3::{self, Vec3};
+
+/// Pulls the ends of each edge together. In Hooke mode the pull is
+/// `spring_k * (distance - rest_length) * weight`, pushing apart when the
+/// edge is shorter than its rest length; in LinLog mode it is
+/// `ln(1 + distance) * weight` with no rest length, as in ForceAtlas2.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spring {
+    pub spring_k: f32,
+    pub rest_length: f32,
+    pub lin_log: bool,
+}
+
+impl Default for Spring {
+    fn default() -> Self {
+        Self {
+            spring_k: 12.0,
+            rest_length: 50.0,
+            lin_log: false,
+        }
+    }
+}
+
+impl ForceModel for Spring {
+    fn name(&self) -> &'static str {
+        "spring"
+    }
+
+    fn accumulate(&self, graph: &Graph, positions: &[Vec3], forces: &mut [Vec3]) {
+        for edge in &graph.edges {
+            if edge.source == edge.target {
+                continue;
+            }
+            let diff = vec3::sub(positions[edge.target], positions[edge.source]);
+            let dist = vec3::length(diff);
+            if dist <= 1e-6 {
+                continue;
+            }
+            let magnitude = if self.lin_log {
+                dist.ln_1p() * edge.weight
+            } else {
+                self.spring_k * (dist - self.rest_length) * edge.weight
+            };
+            let force = vec3::scale(diff, magnitude / dist);
+            vec3::add_assign(&mut forces[edge.source], force);
+            vec3::sub_assign(&mut forces[edge.target], force);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Edge;
+
+    #[test]
+    fn pulls_long_edges_in_and_pushes_short_ones_out() {
+        let graph = Graph::new(2, vec![Edge::new(0, 1)]);
+        let spring = Spring {
+            spring_k: 1.0,
+            rest_length: 10.0,
+            lin_log: false,
+        };
+        let mut forces = vec![[0.0; 3]; 2];
+        spring.accumulate(&graph, &[[0.0; 3], [20.0, 0.0, 0.0]], &mut forces);
+        assert_eq!(forces, vec![[10.0, 0.0, 0.0], [-10.0, 0.0, 0.0]]);
+
+        let mut forces = vec![[0.0; 3]; 2];
+        spring.accumulate(&graph, &[[0.0; 3], [5.0, 0.0, 0.0]], &mut forces);
+        assert_eq!(forces[0], [-5.0, 0.0, 0.0]);
+
+        let lin_log = Spring {
+            lin_log: true,
+            ..spring
+        };
+        let mut forces = vec![[0.0; 3]; 2];
+        lin_log.accumulate(&graph, &[[0.0; 3], [5.0, 0.0, 0.0]], &mut forces);
+        assert!((forces[0][0] - 6f32.ln()).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,88 @@
+//! The graph a layout runs on: node indices `0..node_count` and weighted
+//! edges between them.
+
+use std::collections::HashMap;
+
+use crate::vec3::Vec3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Edge {
+    pub source: usize,
+    pub target: usize,
+    pub weight: f32,
+}
+
+impl Edge {
+    pub fn new(source: usize, target: usize) -> Self {
+        Self::weighted(source, target, 1.0)
+    }
+
+    pub fn weighted(source: usize, target: usize, weight: f32) -> Self {
+        Self {
+            source,
+            target,
+            weight,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    pub node_count: usize,
+    pub edges: Vec<Edge>,
+}
+
+impl Graph {
+    /// Edges naming a node outside `0..node_count` are dropped.
+    pub fn new(node_count: usize, edges: Vec<Edge>) -> Self {
+        let edges = edges
+            .into_iter()
+            .filter(|e| e.source < node_count && e.target < node_count)
+            .collect();
+        Self { node_count, edges }
+    }
+}
+
+/// Uniform grid bucketing positions by cell, used to find the neighbours
+/// within `cell_size` of a node without visiting every pair.
+pub(crate) struct SpatialGrid {
+    cell_size: f32,
+    cells: HashMap<[i32; 3], Vec<usize>>,
+}
+
+impl SpatialGrid {
+    pub(crate) fn build(positions: &[Vec3], cell_size: f32) -> Self {
+        let mut grid = Self {
+            cell_size: cell_size.max(f32::EPSILON),
+            cells: HashMap::new(),
+        };
+        for (i, &p) in positions.iter().enumerate() {
+            let cell = grid.cell(p);
+            grid.cells.entry(cell).or_default().push(i);
+        }
+        grid
+    }
+
+    fn cell(&self, p: Vec3) -> [i32; 3] {
+        [
+            (p[0] / self.cell_size).floor() as i32,
+            (p[1] / self.cell_size).floor() as i32,
+            (p[2] / self.cell_size).floor() as i32,
+        ]
+    }
+
+    /// Calls `visit` with every node in the 27 cells around `p`; those are
+    /// all the nodes within `cell_size` of it, plus some further away.
+    pub(crate) fn for_each_near(&self, p: Vec3, mut visit: impl FnMut(usize)) {
+        let [cx, cy, cz] = self.cell(p);
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    if let Some(nodes) = self.cells.get(&[cx + dx, cy + dy, cz + dz]) {
+                        nodes.iter().for_each(|&j| visit(j));
+                    }
+                }
+            }
+        }
+    }
+}
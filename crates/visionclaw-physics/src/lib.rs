@@ -0,0 +1,46 @@
+//! Force-directed layout on the CPU for VisionClaw.
+//!
+//! The server's production layout runs in the CUDA kernels of
+//! `visionclaw-gpu`. This crate models the same kinds of force as plain
+//! Rust, each behind the [`ForceModel`] trait, so they can be composed,
+//! tested and benchmarked one at a time and reused by anything that has
+//! nodes and edges — no Logseq pages, actors or GPU are assumed.
+//!
+//! ```
+//! use visionclaw_physics::{
+//!     CenterGravity, Edge, ForcePipeline, Graph, Integrator, Layout, Repulsion, Spring,
+//! };
+//!
+//! let graph = Graph::new(3, vec![Edge::new(0, 1), Edge::new(1, 2)]);
+//! let mut layout = Layout::spiral(graph.node_count, 10.0);
+//! let mut pipeline = ForcePipeline::new(Integrator::default())
+//!     .with(Spring::default())
+//!     .with(Repulsion::default())
+//!     .with(CenterGravity::default());
+//! for _ in 0..100 {
+//!     pipeline.step(&graph, &mut layout);
+//! }
+//! ```
+//!
+//! Formulas and defaults were written from the unified force kernel and the
+//! server's default physics settings, but they are a separate copy: neither
+//! the kernel nor the server's CPU fallback (`simd_forces`) runs through
+//! this crate, and nothing checks the two against each other. Use it to
+//! compare layouts with each other, not to predict what the GPU will do.
+//! Run `cargo bench -p visionclaw-physics` for per-model timings.
+//! [`stress`] scores how well a layout reflects graph distances, for
+//! comparing two layouts of the same graph.
+
+pub mod forces;
+pub mod graph;
+pub mod pipeline;
+pub mod stress;
+pub mod vec3;
+
+pub use forces::{
+    CenterGravity, Collision, Constraint, Constraints, ForceModel, Repulsion, Spring,
+};
+pub use graph::{Edge, Graph};
+pub use pipeline::{ForcePipeline, Integrator, Layout, StepStats};
+pub use stress::{sample_sources, stress};
+pub use vec3::Vec3;
@@ -0,0 +1,187 @@
+//! Composing force models and integrating their result.
+
+use crate::forces::ForceModel;
+use crate::graph::Graph;
+use crate::vec3::{self, Vec3};
+
+/// Positions and velocities of every node. Pinned nodes feel forces but do
+/// not move.
+#[derive(Debug, Clone, Default)]
+pub struct Layout {
+    pub positions: Vec<Vec3>,
+    pub velocities: Vec<Vec3>,
+    pub pinned: Vec<bool>,
+}
+
+impl Layout {
+    pub fn new(positions: Vec<Vec3>) -> Self {
+        let n = positions.len();
+        Self {
+            positions,
+            velocities: vec![[0.0; 3]; n],
+            pinned: vec![false; n],
+        }
+    }
+
+    /// `n` nodes on a golden-angle spiral filling a sphere of `radius`;
+    /// deterministic, with no two nodes in the same place.
+    pub fn spiral(n: usize, radius: f32) -> Self {
+        let golden_angle = std::f32::consts::PI * (3.0 - 5f32.sqrt());
+        let positions = (0..n)
+            .map(|i| {
+                let t = (i as f32 + 0.5) / n as f32;
+                let y = 1.0 - 2.0 * t;
+                let ring = (1.0 - y * y).sqrt();
+                let theta = golden_angle * i as f32;
+                let r = radius * t.cbrt();
+                [r * ring * theta.cos(), r * y, r * ring * theta.sin()]
+            })
+            .collect();
+        Self::new(positions)
+    }
+}
+
+/// Turns forces into motion, modelled on the GPU integrate pass: forces are
+/// capped at `max_force`, velocity is `(v + f·dt) · damping` capped at
+/// `max_velocity`, and positions advance by `v·dt`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Integrator {
+    pub dt: f32,
+    pub damping: f32,
+    pub max_force: f32,
+    pub max_velocity: f32,
+}
+
+impl Default for Integrator {
+    fn default() -> Self {
+        Self {
+            dt: 0.016,
+            damping: 0.9,
+            max_force: 150.0,
+            max_velocity: 100.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StepStats {
+    /// Mean `½|v|²` after the step; falls towards zero as the layout
+    /// settles.
+    pub kinetic_energy: f32,
+    /// Largest force on any node before capping.
+    pub max_force: f32,
+}
+
+/// Force models run in order each step, followed by integration.
+pub struct ForcePipeline {
+    models: Vec<Box<dyn ForceModel>>,
+    pub integrator: Integrator,
+    forces: Vec<Vec3>,
+}
+
+impl ForcePipeline {
+    pub fn new(integrator: Integrator) -> Self {
+        Self {
+            models: Vec::new(),
+            integrator,
+            forces: Vec::new(),
+        }
+    }
+
+    pub fn with(mut self, model: impl ForceModel + 'static) -> Self {
+        self.push(Box::new(model));
+        self
+    }
+
+    pub fn push(&mut self, model: Box<dyn ForceModel>) {
+        self.models.push(model);
+    }
+
+    pub fn models(&self) -> impl Iterator<Item = &dyn ForceModel> {
+        self.models.iter().map(|m| m.as_ref())
+    }
+
+    /// The total force on each node at `positions`.
+    pub fn compute_forces(&mut self, graph: &Graph, positions: &[Vec3]) -> &[Vec3] {
+        self.forces.clear();
+        self.forces.resize(positions.len(), [0.0; 3]);
+        for model in &self.models {
+            model.accumulate(graph, positions, &mut self.forces);
+        }
+        &self.forces
+    }
+
+    /// Advance `layout` by one step.
+    pub fn step(&mut self, graph: &Graph, layout: &mut Layout) -> StepStats {
+        let Integrator {
+            dt,
+            damping,
+            max_force,
+            max_velocity,
+        } = self.integrator;
+        self.compute_forces(graph, &layout.positions);
+
+        let mut stats = StepStats::default();
+        let mut energy = 0.0f64;
+        for (i, &force) in self.forces.iter().enumerate() {
+            stats.max_force = stats.max_force.max(vec3::length(force));
+            if layout.pinned.get(i).copied().unwrap_or(false) {
+                layout.velocities[i] = [0.0; 3];
+                continue;
+            }
+            let force = vec3::clamp_length(force, max_force);
+            let velocity = vec3::scale(
+                vec3::add(layout.velocities[i], vec3::scale(force, dt)),
+                damping,
+            );
+            let velocity = vec3::clamp_length(velocity, max_velocity);
+            layout.velocities[i] = velocity;
+            vec3::add_assign(&mut layout.positions[i], vec3::scale(velocity, dt));
+            energy += 0.5 * f64::from(vec3::length_sq(velocity));
+        }
+        if !self.forces.is_empty() {
+            stats.kinetic_energy = (energy / self.forces.len() as f64) as f32;
+        }
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forces::{CenterGravity, Collision, Repulsion, Spring};
+    use crate::graph::Edge;
+
+    #[test]
+    fn settles_a_small_graph_and_holds_pinned_nodes() {
+        let edges = (0..20).map(|i| Edge::new(i, (i + 1) % 20)).collect();
+        let graph = Graph::new(20, edges);
+        let mut layout = Layout::spiral(graph.node_count, 30.0);
+        layout.pinned[0] = true;
+        let anchor = layout.positions[0];
+        let mut pipeline = ForcePipeline::new(Integrator::default())
+            .with(Spring::default())
+            .with(Repulsion::default())
+            .with(Collision::default())
+            .with(CenterGravity::default());
+        let names: Vec<_> = pipeline.models().map(|m| m.name()).collect();
+        assert_eq!(names, ["spring", "repulsion", "collision", "gravity"]);
+
+        let early = (0..20)
+            .map(|_| pipeline.step(&graph, &mut layout))
+            .last()
+            .unwrap();
+        let late = (0..2000)
+            .map(|_| pipeline.step(&graph, &mut layout))
+            .last()
+            .unwrap();
+        assert!(
+            late.kinetic_energy < early.kinetic_energy * 1e-3,
+            "still moving: {:?} -> {:?}",
+            early,
+            late
+        );
+        assert_eq!(layout.positions[0], anchor);
+        assert!(layout.positions.iter().flatten().all(|c| c.is_finite()));
+    }
+}
@@ -0,0 +1,120 @@
+//! Layout quality as normalised stress.
+//!
+//! Stress compares each pair's distance in the layout with its distance in
+//! the graph (hops along edges): a layout where linked nodes sit close and
+//! far-apart nodes sit far scores low. This is the scale-free form, so two
+//! layouts of different size compare fairly: the layout is first scaled by
+//! the factor that fits the graph distances best, then
+//!
+//! ```text
+//! stress = mean over pairs (s·|p_i − p_j| / d_ij − 1)²
+//! ```
+//!
+//! which is 0 for a perfect embedding. All pairs would cost `O(n²)`, so
+//! distances are taken from a sample of BFS sources; see [`sample_sources`].
+//! Pairs in different components are skipped.
+
+use std::collections::VecDeque;
+
+use crate::graph::Graph;
+use crate::vec3::{self, Vec3};
+
+/// Up to `count` source nodes spread evenly over `0..node_count`.
+/// Deterministic, so two layouts of one graph are measured on the same
+/// pairs.
+pub fn sample_sources(node_count: usize, count: usize) -> Vec<usize> {
+    if node_count == 0 || count == 0 {
+        return Vec::new();
+    }
+    let count = count.min(node_count);
+    (0..count).map(|i| i * node_count / count).collect()
+}
+
+/// Hop distance from `source` to every node; `u32::MAX` when unreachable.
+fn hops_from(adjacency: &[Vec<usize>], source: usize) -> Vec<u32> {
+    let mut hops = vec![u32::MAX; adjacency.len()];
+    let mut queue = VecDeque::from([source]);
+    hops[source] = 0;
+    while let Some(node) = queue.pop_front() {
+        for &next in &adjacency[node] {
+            if hops[next] == u32::MAX {
+                hops[next] = hops[node] + 1;
+                queue.push_back(next);
+            }
+        }
+    }
+    hops
+}
+
+/// Normalised stress of `positions` over the pairs reachable from
+/// `sources`. Returns 0 when there are no such pairs.
+pub fn stress(graph: &Graph, positions: &[Vec3], sources: &[usize]) -> f32 {
+    let n = graph.node_count.min(positions.len());
+    let mut adjacency = vec![Vec::new(); n];
+    for edge in graph.edges.iter().filter(|e| e.source < n && e.target < n) {
+        if edge.source != edge.target {
+            adjacency[edge.source].push(edge.target);
+            adjacency[edge.target].push(edge.source);
+        }
+    }
+
+    // (layout distance, graph distance) for every sampled pair.
+    let mut pairs: Vec<(f64, f64)> = Vec::new();
+    for &source in sources.iter().filter(|&&s| s < n) {
+        let hops = hops_from(&adjacency, source);
+        for (target, &h) in hops.iter().enumerate() {
+            if h == 0 || h == u32::MAX {
+                continue;
+            }
+            let e = vec3::length(vec3::sub(positions[source], positions[target]));
+            pairs.push((f64::from(e), f64::from(h)));
+        }
+    }
+    if pairs.is_empty() {
+        return 0.0;
+    }
+
+    // Weights 1/d² make the best scale Σ(e/d) / Σ(e/d)².
+    let (sum_ratio, sum_ratio_sq) = pairs.iter().fold((0.0, 0.0), |(a, b), &(e, d)| {
+        let r = e / d;
+        (a + r, b + r * r)
+    });
+    if sum_ratio_sq == 0.0 {
+        return 1.0;
+    }
+    let scale = sum_ratio / sum_ratio_sq;
+    let total: f64 = pairs
+        .iter()
+        .map(|&(e, d)| (scale * e / d - 1.0).powi(2))
+        .sum();
+    (total / pairs.len() as f64) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Edge;
+
+    #[test]
+    fn rewards_layouts_that_follow_the_graph() {
+        let path = Graph::new(5, (0..4).map(|i| Edge::new(i, i + 1)).collect());
+        let sources = sample_sources(path.node_count, 8);
+        assert_eq!(sources, [0, 1, 2, 3, 4]);
+
+        let line: Vec<Vec3> = (0..5).map(|i| [i as f32 * 10.0, 0.0, 0.0]).collect();
+        assert!(stress(&path, &line, &sources) < 1e-6);
+
+        let bigger: Vec<Vec3> = line.iter().map(|&p| vec3::scale(p, 7.0)).collect();
+        assert!(stress(&path, &bigger, &sources) < 1e-6, "scale-free");
+
+        let folded: Vec<Vec3> = [0.0, 40.0, 10.0, 30.0, 20.0]
+            .iter()
+            .map(|&x| [x, 0.0, 0.0])
+            .collect();
+        assert!(stress(&path, &folded, &sources) > 0.1);
+
+        let collapsed = vec![[0.0; 3]; 5];
+        assert_eq!(stress(&path, &collapsed, &sources), 1.0);
+        assert_eq!(stress(&Graph::new(3, Vec::new()), &line, &[0, 1]), 0.0);
+    }
+}
@@ -0,0 +1,53 @@
+//! Minimal 3-vector arithmetic on `[f32; 3]`.
+
+pub type Vec3 = [f32; 3];
+
+#[inline]
+pub fn add(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+#[inline]
+pub fn sub(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+#[inline]
+pub fn scale(a: Vec3, s: f32) -> Vec3 {
+    [a[0] * s, a[1] * s, a[2] * s]
+}
+
+#[inline]
+pub fn length_sq(a: Vec3) -> f32 {
+    a[0] * a[0] + a[1] * a[1] + a[2] * a[2]
+}
+
+#[inline]
+pub fn length(a: Vec3) -> f32 {
+    length_sq(a).sqrt()
+}
+
+/// `a` scaled down to at most `max` long.
+#[inline]
+pub fn clamp_length(a: Vec3, max: f32) -> Vec3 {
+    let len = length(a);
+    if len > max && len > 0.0 {
+        scale(a, max / len)
+    } else {
+        a
+    }
+}
+
+#[inline]
+pub fn add_assign(a: &mut Vec3, b: Vec3) {
+    a[0] += b[0];
+    a[1] += b[1];
+    a[2] += b[2];
+}
+
+#[inline]
+pub fn sub_assign(a: &mut Vec3, b: Vec3) {
+    a[0] -= b[0];
+    a[1] -= b[1];
+    a[2] -= b[2];
+}
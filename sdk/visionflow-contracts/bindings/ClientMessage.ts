@@ -0,0 +1,47 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AuthorityRequest } from "./AuthorityRequest";
+import type { GestureKind } from "./GestureKind";
+import type { GesturePhase } from "./GesturePhase";
+import type { GraphFilterUpdate } from "./GraphFilterUpdate";
+import type { HelloData } from "./HelloData";
+import type { NodeDrag } from "./NodeDrag";
+import type { PositionSubscription } from "./PositionSubscription";
+import type { QualityLevel } from "./QualityLevel";
+import type { Ray } from "./Ray";
+import type { SettingsSection } from "./SettingsSection";
+import type { SimulationAction } from "./SimulationAction";
+
+/**
+ * A text frame the server accepts. A bare `"ping"` string (not JSON) is
+ * also accepted and answered with a bare `"pong"`.
+ */
+export type ClientMessage = { "type": "ping", timestamp?: number, } | { "type": "hello", data: HelloData, } | { "type": "update_physics_params", } | { "type": "request_full_snapshot", graphs?: Array<string>, } | { "type": "requestInitialData", } | { "type": "enableRandomization", enabled: boolean, } | { "type": "requestBotsGraph", } | { "type": "requestBotsPositions", } | { "type": "subscribe_position_updates", data?: PositionSubscription, } | { "type": "requestPositionUpdates", } | { "type": "authenticate", event?: string, token?: string, pubkey?: string, ephemeral?: boolean, 
+/**
+ * Highlight pages created since the last visit (default true).
+ */
+highlightChanges?: boolean, } | { "type": "filter_update", filter: GraphFilterUpdate, } | { "type": "requestSwarmTelemetry", } | { "type": "ontology_validation", ontologyId?: string, } | { "type": "ontology_validation_report", ontologyId?: string, } | { "type": "ontology_constraint_update", } | { "type": "ontology_constraint_toggle", } | { "type": "ontology_reasoning", ontologyId?: number, source?: string, } | { "type": "nodeDragStart", data: NodeDrag, } | { "type": "nodeDragUpdate", data: NodeDrag, } | { "type": "nodeDragEnd", data: NodeDrag, } | { "type": "cameraRecordingStart", } | { "type": "cameraPose", position: [number, number, number], 
+/**
+ * Quaternion `[x, y, z, w]`.
+ */
+rotation: [number, number, number, number], 
+/**
+ * Degrees (default 60).
+ */
+fov?: number, } | { "type": "cameraRecordingStop", } | { "type": "requestAuthority", data?: AuthorityRequest, } | { "type": "releaseAuthority", } | { "type": "layoutPlayback", enabled: boolean, } | { "type": "layoutHistorySeek", t: number, } | { "type": "seedLayout", positions: Array<[number, number, number, number]>, } | { "type": "applyLayoutProposal", id: string, 
+/**
+ * How long clients animate to the new layout (default 1500, at
+ * most 10000).
+ */
+transitionMs?: number, } | { "type": "dismissLayoutProposal", id: string, } | { "type": "simulationControl", action: SimulationAction, 
+/**
+ * For `step` (default 1).
+ */
+steps?: number, } | { "type": "simulationQuality", level: QualityLevel, } | { "type": "gesture", gesture: GestureKind, phase: GesturePhase, 
+/**
+ * Graph-space ray; required for `start` and `move`.
+ */
+ray?: Ray, 
+/**
+ * Target the client already resolved; skips the server-side pick.
+ */
+nodeId?: number, } | { "type": "overview", enabled: boolean, } | { "type": "requestOverviewFrame", } | { "type": "nodeBudget", maxNodes?: number | null, focusNodeId?: number | null, } | { "type": "settingsPatch", section: SettingsSection, values: Record<string, unknown>, } | { "type": "set_language_filter", languages: Array<string> | null, };
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Incremental graph change, stamped with the graph revision it produces.
+ */
+export type GraphDiff = { 
+/**
+ * e.g. "ephemeral_added", "ephemeral_expired", "metadata_updated".
+ */
+reason: string, 
+/**
+ * Nodes in the session's JSON node schema.
+ */
+added: Array<Record<string, unknown>>, removed: Array<number>, 
+/**
+ * Existing nodes whose metadata changed, in the same schema as
+ * `added`; replace them by id.
+ */
+updated?: Array<Record<string, unknown>>, revision: number, };
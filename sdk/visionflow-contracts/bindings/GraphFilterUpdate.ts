@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Partial graph filter; absent fields keep their current value.
+ */
+export type GraphFilterUpdate = { enabled?: boolean, quality_threshold?: number, authority_threshold?: number, filter_by_quality?: boolean, filter_by_authority?: boolean, 
+/**
+ * "or" or "and".
+ */
+filter_mode?: string, max_nodes?: number, includeLinkedPages?: boolean, };
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * `hello` body. Lists are in client preference order.
+ */
+export type HelloData = { protocolVersions: Array<number>, encodings: Array<string>, compression: Array<string>, capabilities: Array<string>, jsonSchema?: number, 
+/**
+ * "camel" or "snake".
+ */
+fieldCasing?: string, stringMetadata?: boolean, 
+/**
+ * BCP 47; picks native or romanized node labels.
+ */
+locale?: string, 
+/**
+ * XR device id; the ack then carries its saved room anchor.
+ */
+deviceId?: string, };
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * An agent touched a memory entry.
+ */
+export type MemoryFlash = { key: string, namespace: string, 
+/**
+ * "store", "search", "retrieve", "delete", "update" or "access".
+ */
+action: string, timestamp: number, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { WorldPoint } from "./WorldPoint";
+
+export type NodeDrag = { nodeId: number, 
+/**
+ * Omitted on `nodeDragEnd`.
+ */
+position?: WorldPoint, 
+/**
+ * Client clock, milliseconds; lets the server drop stale updates.
+ */
+timestamp?: number, };
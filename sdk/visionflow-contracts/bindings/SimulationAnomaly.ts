@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AnomalyKind } from "./AnomalyKind";
+
+/**
+ * The frame that made the simulation pause itself.
+ */
+export type SimulationAnomaly = { kind: AnomalyKind, 
+/**
+ * "nan_positions", "runaway_velocity" or "energy_spike".
+ */
+reason: string, detail: string, 
+/**
+ * The first few offending nodes; the snapshot has them all.
+ */
+nodeIds: Array<number>, 
+/**
+ * Unix ms.
+ */
+detectedAt: number, 
+/**
+ * Snapshot file id, once written.
+ */
+snapshot?: string, };
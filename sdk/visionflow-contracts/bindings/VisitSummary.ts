@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { VisitCounts } from "./VisitCounts";
+
+export type VisitSummary = { revision: number, sinceRevision: number, 
+/**
+ * RFC 3339.
+ */
+since: string | null, created: Array<string>, edited: Array<string>, removed: Array<string>, newClusters: Array<Array<string>>, linkChanges: number, counts: VisitCounts, newNodeIds: Array<number>, truncated: boolean, highlightId?: number, };
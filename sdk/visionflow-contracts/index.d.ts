@@ -43,8 +43,59 @@ export type { ParsedMarkdown } from "./bindings/ParsedMarkdown";
 export type { ParseErrorReport } from "./bindings/ParseErrorReport";
 export type { ParseErrorKind } from "./bindings/ParseErrorKind";
 
+// `/wss` text frames (server ⇄ browser / XR client)
+export type { ClientMessage } from "./bindings/ClientMessage";
+export type { ServerMessage } from "./bindings/ServerMessage";
+export type { HelloData } from "./bindings/HelloData";
+export type { HelloAck } from "./bindings/HelloAck";
+export type { PositionSubscription } from "./bindings/PositionSubscription";
+export type { GraphFilterUpdate } from "./bindings/GraphFilterUpdate";
+export type { WorldPoint } from "./bindings/WorldPoint";
+export type { NodeDrag } from "./bindings/NodeDrag";
+export type { NodeRef } from "./bindings/NodeRef";
+export type { AuthorityRequest } from "./bindings/AuthorityRequest";
+export type { Ray } from "./bindings/Ray";
+export type { SimulationAction } from "./bindings/SimulationAction";
+export type { SimulationMode } from "./bindings/SimulationMode";
+export type { QualityLevel } from "./bindings/QualityLevel";
+export type { GestureKind } from "./bindings/GestureKind";
+export type { GesturePhase } from "./bindings/GesturePhase";
+export type { GestureActionKind } from "./bindings/GestureActionKind";
+export type { SettingsSection } from "./bindings/SettingsSection";
+export type { ResumeRejected } from "./bindings/ResumeRejected";
+export type { GraphDiff } from "./bindings/GraphDiff";
+export type { VisitSummary } from "./bindings/VisitSummary";
+export type { VisitCounts } from "./bindings/VisitCounts";
+export type { OverviewNode } from "./bindings/OverviewNode";
+export type { LayoutProposal } from "./bindings/LayoutProposal";
+export type { AnomalyKind } from "./bindings/AnomalyKind";
+export type { SimulationAnomaly } from "./bindings/SimulationAnomaly";
+export type { StallKind } from "./bindings/StallKind";
+export type { StallIncident } from "./bindings/StallIncident";
+export type { AudioCue } from "./bindings/AudioCue";
+export type { HighlightAction } from "./bindings/HighlightAction";
+export type { HighlightReason } from "./bindings/HighlightReason";
+export type { HighlightLayer } from "./bindings/HighlightLayer";
+export type { MemoryFlash } from "./bindings/MemoryFlash";
+export type { EnrichmentDecision } from "./bindings/EnrichmentDecision";
+
 // Runtime constants (mirrored from Rust — see ./index.js).
 export const AGENT_ACTION_CHANNEL: "visionclaw:agent-actions";
 export const AGENT_ACTION_TYPE: "visionclaw:agent-action";
 export const SCHEMA_VERSION: 1;
 export const SCHEMA_VERSION_STRING: "v1";
+
+import type { ClientMessage } from "./bindings/ClientMessage";
+import type { ServerMessage } from "./bindings/ServerMessage";
+
+export const CLIENT_MESSAGE_TYPES: ReadonlyArray<ClientMessage["type"]>;
+export const SERVER_MESSAGE_TYPES: ReadonlyArray<ServerMessage["type"]>;
+
+/** Serialise a message for `WebSocket.send`. */
+export function encodeClientMessage(message: ClientMessage): string;
+
+/**
+ * Parse a `/wss` text frame; `null` for the bare "pong" heartbeat,
+ * malformed JSON or an unknown `type`.
+ */
+export function parseServerMessage(text: string): ServerMessage | null;
@@ -2,9 +2,13 @@
 //
 // The type-only re-exports live in index.d.ts. This file provides the small
 // set of literals that consumers need at runtime (BroadcastChannel name,
-// envelope type discriminator, schema version).
+// envelope type discriminator, schema version) and the `/wss` text-frame
+// helpers.
 //
-// Bump in lockstep with `crates/visionclaw-contracts/src/version.rs`.
+// Bump in lockstep with `crates/visionclaw-contracts/src/version.rs`. The
+// message type lists mirror `ClientMessage::TYPES` / `ServerMessage::TYPES`
+// in `src/socket_protocol.rs`; `tests/socket_protocol_drift.rs` fails when
+// they differ.
 
 "use strict";
 
@@ -14,3 +18,126 @@ exports.AGENT_ACTION_CHANNEL = "visionclaw:agent-actions";
 exports.AGENT_ACTION_TYPE = "visionclaw:agent-action";
 exports.SCHEMA_VERSION = 1;
 exports.SCHEMA_VERSION_STRING = "v1";
+
+exports.CLIENT_MESSAGE_TYPES = [
+  "ping",
+  "hello",
+  "update_physics_params",
+  "request_full_snapshot",
+  "requestInitialData",
+  "enableRandomization",
+  "requestBotsGraph",
+  "requestBotsPositions",
+  "subscribe_position_updates",
+  "requestPositionUpdates",
+  "authenticate",
+  "filter_update",
+  "requestSwarmTelemetry",
+  "ontology_validation",
+  "ontology_validation_report",
+  "ontology_constraint_update",
+  "ontology_constraint_toggle",
+  "ontology_reasoning",
+  "nodeDragStart",
+  "nodeDragEnd",
+  "nodeDragUpdate",
+  "cameraRecordingStart",
+  "cameraPose",
+  "cameraRecordingStop",
+  "requestAuthority",
+  "releaseAuthority",
+  "layoutPlayback",
+  "layoutHistorySeek",
+  "seedLayout",
+  "applyLayoutProposal",
+  "dismissLayoutProposal",
+  "simulationControl",
+  "simulationQuality",
+  "gesture",
+  "overview",
+  "requestOverviewFrame",
+  "nodeBudget",
+  "settingsPatch",
+  "set_language_filter",
+];
+
+exports.SERVER_MESSAGE_TYPES = [
+  "connection_established",
+  "hello_ack",
+  "hello_error",
+  "pong",
+  "state_sync",
+  "loading",
+  "initialDataInfo",
+  "error",
+  "rate_limit_warning",
+  "authenticate_success",
+  "filter_update_success",
+  "subscription_confirmed",
+  "botsGraphUpdate",
+  "botsUpdatesStarted",
+  "swarmTelemetry",
+  "nodeDragStartAck",
+  "nodeDragEndAck",
+  "authority_granted",
+  "authority_denied",
+  "authority_revoked",
+  "positionsRejected",
+  "positions",
+  "voice_ack",
+  "ontology_validation_update",
+  "ontology_constraint_update",
+  "ontology_reasoning_started",
+  "ontology_reasoning_error",
+  "graphDiff",
+  "sessionResumed",
+  "hibernating",
+  "resumed",
+  "visitSummary",
+  "layoutPlayback",
+  "layoutHistoryFrame",
+  "layoutSeeded",
+  "layoutProposal",
+  "layoutProposalApplied",
+  "layoutProposalDismissed",
+  "simulationControl",
+  "simulationQuality",
+  "gestureAction",
+  "overview",
+  "overviewFrame",
+  "nodeBudget",
+  "settingsPatch",
+  "set_language_filter_success",
+  "cameraRecordingStarted",
+  "cameraRecordingStopped",
+  "simulationPaused",
+  "simulationStalled",
+  "settingsUpdated",
+  "audioCues",
+  "highlightLayer",
+  "memory_flash",
+  "enrichment_decision",
+];
+
+const serverMessageTypes = new Set(exports.SERVER_MESSAGE_TYPES);
+
+// Serialise a ClientMessage for `socket.send`.
+exports.encodeClientMessage = function encodeClientMessage(message) {
+  return JSON.stringify(message);
+};
+
+// Parse a `/wss` text frame. Returns null for the bare "pong" heartbeat,
+// malformed JSON and unknown message types, so callers can switch
+// on `message.type` without a catch-all.
+exports.parseServerMessage = function parseServerMessage(text) {
+  let message;
+  try {
+    message = JSON.parse(text);
+  } catch (_) {
+    return null;
+  }
+  if (message === null || typeof message !== "object") {
+    return null;
+  }
+  return serverMessageTypes.has(message.type) ? message : null;
+};
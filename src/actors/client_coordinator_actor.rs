@@ -21,15 +21,19 @@
 //! - **Binary Protocol**: 28-byte optimized node data for network efficiency
 
 use actix::prelude::*;
-use log::{debug, error, info, warn};
+use log::{debug, error, info, trace, warn};
 use serde::{Deserialize, Serialize};
 use serde_json;
 use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
 
 // Import required types and messages
 use crate::actors::messages::*;
+use crate::actors::physics_authority::{
+    AuthorityOutcome, AuthorityPolicy, PhysicsAuthority, DEFAULT_WORKSPACE,
+};
 use crate::telemetry::agent_telemetry::{get_telemetry_logger, CorrelationId, Position3D};
 use crate::utils::socket_flow_messages::BinaryNodeDataClient;
 
@@ -53,6 +57,70 @@ pub struct ClientState {
     pub settings_override: Option<crate::config::AppFullSettings>,
     /// Whether this client authenticated with an ephemeral (dev-mode) identity
     pub ephemeral_session: bool,
+    /// Nodes this client recently moved itself, with the instant until which
+    /// they are left out of its position broadcasts (echo suppression).
+    pub echo_suppressed: HashMap<u32, Instant>,
+    /// Position frames handed to this client's mailbox.
+    pub traffic: Arc<SessionTraffic>,
+}
+
+impl ClientState {
+    fn is_echo(&self, node_id: u32, now: Instant) -> bool {
+        self.echo_suppressed
+            .get(&node_id)
+            .is_some_and(|until| *until > now)
+    }
+
+    pub fn session_summary(&self, now: Instant) -> ClientSessionSummary {
+        let connected = now.saturating_duration_since(self.connected_at);
+        let frames_sent = self.traffic.frames.load(Ordering::Relaxed);
+        let bytes_sent = self.traffic.bytes.load(Ordering::Relaxed);
+        let secs = connected.as_secs_f64().max(1.0);
+        ClientSessionSummary {
+            client_id: self.client_id,
+            pubkey: self.pubkey.clone(),
+            is_power_user: self.is_power_user,
+            connected_secs: connected.as_secs(),
+            idle_secs: now.saturating_duration_since(self.last_update).as_secs(),
+            filtered: self.filter.is_active(),
+            frames_sent,
+            bytes_sent,
+            frames_per_sec: frames_sent as f64 / secs,
+            bytes_per_sec: bytes_sent as f64 / secs,
+        }
+    }
+}
+
+/// Binary frames delivered to one client. Counted on the broadcast path,
+/// which only holds the manager's read lock, hence atomics.
+#[derive(Debug, Default)]
+pub struct SessionTraffic {
+    frames: AtomicU64,
+    bytes: AtomicU64,
+}
+
+impl SessionTraffic {
+    fn record(&self, bytes: usize) {
+        self.frames.fetch_add(1, Ordering::Relaxed);
+        self.bytes.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+}
+
+/// A connected session as reported by `/api/admin/overview`. Rates are
+/// averaged over the life of the connection.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientSessionSummary {
+    pub client_id: usize,
+    pub pubkey: Option<String>,
+    pub is_power_user: bool,
+    pub connected_secs: u64,
+    pub idle_secs: u64,
+    pub filtered: bool,
+    pub frames_sent: u64,
+    pub bytes_sent: u64,
+    pub frames_per_sec: f64,
+    pub bytes_per_sec: f64,
 }
 
 /// Per-client filter settings for graph visibility
@@ -67,6 +135,12 @@ pub struct ClientFilter {
     pub max_nodes: Option<usize>,
     pub filtered_node_ids: std::collections::HashSet<u32>,
     pub include_linked_pages: bool,
+    /// Session node budget, applied on top of the filter (see `node_budget`).
+    pub node_budget: Option<usize>,
+    /// Node whose neighbourhood the budget keeps first.
+    pub focus_node: Option<u32>,
+    /// Content languages to show (ISO 639-3, `und` for undetected); `None` shows all.
+    pub languages: Option<std::collections::HashSet<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -88,10 +162,20 @@ impl Default for ClientFilter {
             max_nodes: Some(10000),
             filtered_node_ids: std::collections::HashSet::new(),
             include_linked_pages: false,
+            node_budget: None,
+            focus_node: None,
+            languages: None,
         }
     }
 }
 
+impl ClientFilter {
+    /// Whether broadcasts to this client must be narrowed to `filtered_node_ids`.
+    pub fn is_active(&self) -> bool {
+        self.enabled || self.node_budget.is_some() || self.languages.is_some()
+    }
+}
+
 impl std::str::FromStr for FilterMode {
     type Err = String;
 
@@ -252,6 +336,8 @@ impl ClientManager {
             filter: ClientFilter::default(),
             settings_override: None,
             ephemeral_session: false,
+            echo_suppressed: HashMap::new(),
+            traffic: Arc::default(),
         };
 
         self.clients.insert(client_id, client_state);
@@ -294,6 +380,19 @@ impl ClientManager {
         }
     }
 
+    /// Leave `node_ids` out of `client_id`'s position broadcasts until
+    /// `until`. Expired entries are pruned here rather than on the hot
+    /// broadcast path, which only holds a read lock.
+    pub fn suppress_echo(&mut self, client_id: usize, node_ids: &[u32], until: Instant) {
+        if let Some(client) = self.clients.get_mut(&client_id) {
+            let now = Instant::now();
+            client.echo_suppressed.retain(|_, deadline| *deadline > now);
+            for &node_id in node_ids {
+                client.echo_suppressed.insert(node_id, until);
+            }
+        }
+    }
+
     pub fn update_client_timestamp(&mut self, client_id: usize) {
         if let Some(client) = self.clients.get_mut(&client_id) {
             client.last_update = Instant::now();
@@ -309,8 +408,12 @@ impl ClientManager {
         let mut sent = 0;
         let mut slow_clients = Vec::new();
         for (&client_id, client_state) in &self.clients {
+            let len = data.len();
             match client_state.addr.binary.try_send(SendToClientBinary(data.clone())) {
-                Ok(()) => sent += 1,
+                Ok(()) => {
+                    client_state.traffic.record(len);
+                    sent += 1;
+                }
                 Err(actix::prelude::SendError::Full(_)) => {
                     warn!(
                         "[ClientCoordinator] Client {} mailbox full — marking for eviction",
@@ -348,37 +451,54 @@ impl ClientManager {
             return BroadcastResult::default();
         }
 
-        // Pre-serialize the full unfiltered payload ONCE
+        // Pre-serialize the full unfiltered payload ONCE. Encode and dispatch
+        // time feed the physics step profile.
+        let broadcast_start = Instant::now();
         let unfiltered_binary = self.serialize_positions(positions, node_type_arrays, broadcast_sequence, analytics_data);
+        let mut encode_time = broadcast_start.elapsed();
 
+        let now = Instant::now();
         let mut sent = 0;
         let mut slow_clients = Vec::new();
         for (&client_id, client_state) in &self.clients {
-            let payload = if !client_state.filter.enabled {
+            let has_echo = client_state.echo_suppressed.values().any(|until| *until > now);
+            let payload = if !client_state.filter.is_active() && !has_echo {
                 // Send pre-serialized payload — no re-encoding needed
                 Some(unfiltered_binary.clone())
             } else {
-                // Only re-serialize for clients with active filters
+                // Only re-serialize for clients with active filters or
+                // nodes they are moving themselves
                 let filtered_positions: Vec<_> = positions
                     .iter()
-                    .filter(|pos| client_state.filter.filtered_node_ids.contains(&pos.node_id))
+                    .filter(|pos| {
+                        !client_state.filter.is_active()
+                            || client_state.filter.filtered_node_ids.contains(&pos.node_id)
+                    })
+                    .filter(|pos| !client_state.is_echo(pos.node_id, now))
                     .copied()
                     .collect();
                 if filtered_positions.is_empty() {
                     None
                 } else {
-                    Some(self.serialize_positions(
+                    let encode_start = Instant::now();
+                    let data = self.serialize_positions(
                         &filtered_positions,
                         node_type_arrays,
                         broadcast_sequence,
                         analytics_data,
-                    ))
+                    );
+                    encode_time += encode_start.elapsed();
+                    Some(data)
                 }
             };
 
             if let Some(data) = payload {
+                let len = data.len();
                 match client_state.addr.binary.try_send(SendToClientBinary(data)) {
-                    Ok(()) => sent += 1,
+                    Ok(()) => {
+                        client_state.traffic.record(len);
+                        sent += 1;
+                    }
                     Err(actix::prelude::SendError::Full(_)) => {
                         warn!(
                             "[ClientCoordinator] Client {} mailbox full — marking for eviction",
@@ -392,6 +512,11 @@ impl ClientManager {
                 }
             }
         }
+        let dispatch_time = broadcast_start.elapsed().saturating_sub(encode_time);
+        crate::physics::step_profile::step_profiler().record_broadcast(
+            encode_time.as_secs_f32() * 1000.0,
+            dispatch_time.as_secs_f32() * 1000.0,
+        );
         BroadcastResult { sent, slow_clients }
     }
 
@@ -434,6 +559,21 @@ impl ClientManager {
         broadcast_count
     }
 
+    /// Send `message` to authenticated clients whose pubkey passes `accept`.
+    pub fn send_to_users(&self, message: &str, accept: impl Fn(&str) -> bool) -> usize {
+        let mut sent = 0;
+        for client_state in self.clients.values() {
+            if client_state.pubkey.as_deref().is_some_and(&accept) {
+                let _ = client_state
+                    .addr
+                    .text
+                    .do_send(SendToClientText(message.to_string()));
+                sent += 1;
+            }
+        }
+        sent
+    }
+
     pub fn get_client_count(&self) -> usize {
         self.clients.len()
     }
@@ -507,6 +647,9 @@ pub struct ClientCoordinatorActor {
 
     /// ADR-031 gap 3b: Per-client reconnect message queue.
     disconnected_queue: DisconnectedClientQueue,
+
+    /// Which local-physics client, if any, drives positions per workspace.
+    physics_authority: PhysicsAuthority,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -547,6 +690,7 @@ impl ClientCoordinatorActor {
                 64,                          // max 64 messages buffered per client
                 Duration::from_secs(30),     // 30-second TTL
             ),
+            physics_authority: PhysicsAuthority::new(AuthorityPolicy::FirstCome),
         }
     }
 
@@ -592,6 +736,21 @@ impl ClientCoordinatorActor {
         info!("Bandwidth limit set to {} bytes/sec", bytes_per_sec);
     }
 
+    /// Set the arbitration policy for local-physics authority
+    pub fn set_authority_policy(&mut self, policy: AuthorityPolicy) {
+        self.physics_authority.set_policy(policy);
+        info!("Physics authority policy set to {:?}", policy);
+    }
+
+    /// Send a JSON text message to a single client, if it is still connected.
+    fn send_text_to(&self, client_id: usize, message: serde_json::Value) {
+        if let Ok(manager) = handle_rwlock_error(self.client_manager.read()) {
+            if let Some(client) = manager.get_client(client_id) {
+                let _ = client.addr.text.do_send(SendToClientText(message.to_string()));
+            }
+        }
+    }
+
     /// Set the SQLite settings repository for loading user filters (ADR-11)
     pub fn set_settings_repository(&mut self, repo: Arc<crate::adapters::SqliteSettingsRepository>) {
         self.settings_repository = Some(repo);
@@ -1306,6 +1465,7 @@ impl Handler<UnregisterClient> for ClientCoordinatorActor {
             };
             manager.unregister_client(msg.client_id)
         };
+        self.physics_authority.release(msg.client_id);
 
         if success {
             // ADR-031 gap 3b: Start buffering for the disconnected client so
@@ -1353,6 +1513,162 @@ impl Handler<UnregisterClient> for ClientCoordinatorActor {
     }
 }
 
+impl Handler<SuppressPositionEcho> for ClientCoordinatorActor {
+    type Result = ();
+
+    fn handle(&mut self, msg: SuppressPositionEcho, _ctx: &mut Self::Context) -> Self::Result {
+        if msg.window_ms == 0 || msg.node_ids.is_empty() {
+            return;
+        }
+        let until = Instant::now() + Duration::from_millis(msg.window_ms);
+        match handle_rwlock_error(self.client_manager.write()) {
+            Ok(mut manager) => manager.suppress_echo(msg.client_id, &msg.node_ids, until),
+            Err(e) => error!("RwLock error: {}", e),
+        }
+    }
+}
+
+impl Handler<RequestPhysicsAuthority> for ClientCoordinatorActor {
+    type Result = ();
+
+    fn handle(&mut self, msg: RequestPhysicsAuthority, _ctx: &mut Self::Context) -> Self::Result {
+        let workspace = if msg.workspace.is_empty() {
+            DEFAULT_WORKSPACE.to_string()
+        } else {
+            msg.workspace
+        };
+        let (is_power_user, stale_holder) = match handle_rwlock_error(self.client_manager.read()) {
+            Ok(manager) => {
+                let is_power_user = manager
+                    .get_client(msg.client_id)
+                    .is_some_and(|c| c.is_power_user);
+                // Slow-client eviction bypasses UnregisterClient, so a holder
+                // may already be gone; it must not block the workspace.
+                let stale_holder = self
+                    .physics_authority
+                    .holder(&workspace)
+                    .map(|h| h.client_id)
+                    .filter(|id| manager.get_client(*id).is_none());
+                (is_power_user, stale_holder)
+            }
+            Err(e) => {
+                error!("RwLock error: {}", e);
+                return;
+            }
+        };
+        if let Some(stale) = stale_holder {
+            self.physics_authority.release(stale);
+        }
+
+        let policy = self.physics_authority.policy();
+        match self
+            .physics_authority
+            .request(&workspace, msg.client_id, is_power_user)
+        {
+            AuthorityOutcome::Granted { revoked } => {
+                info!(
+                    "[Authority] Client {} granted physics authority on '{}'",
+                    msg.client_id, workspace
+                );
+                if let Some(revoked) = revoked {
+                    self.send_text_to(
+                        revoked,
+                        serde_json::json!({
+                            "type": "authority_revoked",
+                            "workspace": workspace,
+                            "reason": "preempted",
+                            "by": msg.client_id,
+                        }),
+                    );
+                }
+                self.send_text_to(
+                    msg.client_id,
+                    serde_json::json!({
+                        "type": "authority_granted",
+                        "workspace": workspace,
+                        "policy": policy,
+                    }),
+                );
+            }
+            AuthorityOutcome::AlreadyHeld => {
+                self.send_text_to(
+                    msg.client_id,
+                    serde_json::json!({
+                        "type": "authority_granted",
+                        "workspace": workspace,
+                        "policy": policy,
+                    }),
+                );
+            }
+            AuthorityOutcome::Denied { holder } => {
+                self.send_text_to(
+                    msg.client_id,
+                    serde_json::json!({
+                        "type": "authority_denied",
+                        "workspace": workspace,
+                        "policy": policy,
+                        "holder": holder,
+                    }),
+                );
+            }
+        }
+    }
+}
+
+impl Handler<ReleasePhysicsAuthority> for ClientCoordinatorActor {
+    type Result = ();
+
+    fn handle(&mut self, msg: ReleasePhysicsAuthority, _ctx: &mut Self::Context) -> Self::Result {
+        for workspace in self.physics_authority.release(msg.client_id) {
+            self.send_text_to(
+                msg.client_id,
+                serde_json::json!({
+                    "type": "authority_revoked",
+                    "workspace": workspace,
+                    "reason": "released",
+                }),
+            );
+        }
+    }
+}
+
+impl Handler<SubmitClientPositions> for ClientCoordinatorActor {
+    type Result = Result<usize, String>;
+
+    fn handle(&mut self, msg: SubmitClientPositions, _ctx: &mut Self::Context) -> Self::Result {
+        let Some(workspace) = self.physics_authority.workspace_of(msg.client_id) else {
+            return Err("Client does not hold physics authority".to_string());
+        };
+        // The graph actor holds the default workspace's layout; authority over
+        // any other workspace must not overwrite it.
+        if workspace != DEFAULT_WORKSPACE {
+            return Err(format!(
+                "Positions for workspace '{}' are not merged into the shared layout",
+                workspace
+            ));
+        }
+        let graph_addr = self
+            .graph_service_addr
+            .clone()
+            .ok_or_else(|| "Graph service not available".to_string())?;
+
+        if msg.echo_window_ms > 0 {
+            let node_ids: Vec<u32> = msg.positions.iter().map(|(id, _)| *id).collect();
+            let until = Instant::now() + Duration::from_millis(msg.echo_window_ms);
+            if let Ok(mut manager) = handle_rwlock_error(self.client_manager.write()) {
+                manager.suppress_echo(msg.client_id, &node_ids, until);
+            }
+        }
+
+        let count = msg.positions.len();
+        graph_addr.do_send(UpdateNodePositions {
+            positions: msg.positions,
+            correlation_id: None,
+        });
+        Ok(count)
+    }
+}
+
 impl Handler<BroadcastNodePositions> for ClientCoordinatorActor {
     type Result = Result<(), String>;
 
@@ -1478,6 +1794,76 @@ impl Handler<BroadcastAgentActionFrame> for ClientCoordinatorActor {
     }
 }
 
+impl Handler<BroadcastAudioCues> for ClientCoordinatorActor {
+    type Result = ();
+
+    fn handle(&mut self, msg: BroadcastAudioCues, _ctx: &mut Self::Context) -> Self::Result {
+        use crate::services::audio_cue_service::{audio_cues, cue_message};
+
+        if msg.cues.is_empty() {
+            return;
+        }
+        let message = cue_message(&msg.cues, chrono::Utc::now().timestamp_millis());
+        let manager = match handle_rwlock_error(self.client_manager.read()) {
+            Ok(manager) => manager,
+            Err(e) => {
+                error!("RwLock error in BroadcastAudioCues: {}", e);
+                return;
+            }
+        };
+        let sent = manager.send_to_users(&message, |pubkey| {
+            msg.recipient.as_deref().map_or(true, |r| r == pubkey)
+                && audio_cues().is_enabled(pubkey)
+        });
+        trace!("Sent {} audio cue(s) to {} clients", msg.cues.len(), sent);
+    }
+}
+
+impl Handler<SendHighlightLayer> for ClientCoordinatorActor {
+    type Result = ();
+
+    fn handle(&mut self, msg: SendHighlightLayer, ctx: &mut Self::Context) -> Self::Result {
+        use crate::services::highlight_service::{
+            highlights, layer_message, HighlightAction, HighlightAudience,
+        };
+
+        let message = layer_message(msg.action, &msg.layer);
+        let sent = match handle_rwlock_error(self.client_manager.read()) {
+            Ok(manager) => {
+                manager.send_to_users(&message, |pubkey| msg.audience.accepts(&msg.layer, pubkey))
+            }
+            Err(e) => {
+                error!("RwLock error in SendHighlightLayer: {}", e);
+                0
+            }
+        };
+        trace!(
+            "Sent highlight layer {} {:?} to {} clients",
+            msg.layer.id,
+            msg.action,
+            sent
+        );
+
+        // Sharing re-sends an add; expiring twice is a no-op.
+        if msg.action == HighlightAction::Add {
+            let remaining = msg.layer.expires_at - chrono::Utc::now().timestamp_millis();
+            let id = msg.layer.id;
+            ctx.run_later(
+                Duration::from_millis(remaining.max(0) as u64),
+                move |_act, ctx| {
+                    if let Some(layer) = highlights().expire(id) {
+                        ctx.notify(SendHighlightLayer {
+                            layer,
+                            action: HighlightAction::Remove,
+                            audience: HighlightAudience::Viewers,
+                        });
+                    }
+                },
+            );
+        }
+    }
+}
+
 /// Handler for BroadcastPositions - modern position broadcasting with backpressure ack
 impl Handler<BroadcastPositions> for ClientCoordinatorActor {
     type Result = ();
@@ -1523,7 +1909,7 @@ impl Handler<BroadcastPositions> for ClientCoordinatorActor {
             // (queue-enqueue, not client receipt). Real acks come from ClientBroadcastAck
             // handler when clients confirm receipt, providing true end-to-end backpressure.
 
-            debug!(
+            trace!(
                 "Broadcasted {} node positions to {} clients (~{} bytes), seq: {}",
                 msg.positions.len(),
                 client_count,
@@ -1586,6 +1972,28 @@ impl Handler<GetClientCount> for ClientCoordinatorActor {
     }
 }
 
+#[derive(Message)]
+#[rtype(result = "Vec<ClientSessionSummary>")]
+pub struct GetClientSessions;
+
+impl Handler<GetClientSessions> for ClientCoordinatorActor {
+    type Result = MessageResult<GetClientSessions>;
+
+    fn handle(&mut self, _msg: GetClientSessions, _ctx: &mut Self::Context) -> Self::Result {
+        let now = Instant::now();
+        let mut sessions: Vec<ClientSessionSummary> =
+            match handle_rwlock_error(self.client_manager.read()) {
+                Ok(manager) => manager.clients.values().map(|c| c.session_summary(now)).collect(),
+                Err(e) => {
+                    error!("RwLock error: {}", e);
+                    Vec::new()
+                }
+            };
+        sessions.sort_by_key(|s| s.client_id);
+        MessageResult(sessions)
+    }
+}
+
 impl Handler<ForcePositionBroadcast> for ClientCoordinatorActor {
     type Result = Result<(), String>;
 
@@ -1701,7 +2109,7 @@ impl Handler<UpdateNodePositions> for ClientCoordinatorActor {
             }
         }
 
-        debug!(
+        trace!(
             "Updated position cache with {} nodes for {} clients",
             self.position_cache.len(),
             client_count
@@ -1867,17 +2275,24 @@ impl Handler<AuthenticateClient> for ClientCoordinatorActor {
                 if let Some(graph_addr) = self.graph_service_addr.clone() {
                     let client_id = msg.client_id;
                     let manager_arc = self.client_manager.clone();
+                    let analytics = self.node_analytics.clone();
 
                     ctx.spawn(actix::fut::wrap_future::<_, Self>(async move {
                         use crate::actors::messages::GetGraphData;
                         match graph_addr.send(GetGraphData).await {
                             Ok(Ok(graph_data)) => {
+                                let analytics = analytics.read().map(|a| a.clone()).ok();
                                 if let Ok(mut manager) = manager_arc.write() {
                                     if let Some(client) = manager.get_client_mut(client_id) {
                                         crate::actors::client_filter::recompute_filtered_nodes(
                                             &mut client.filter,
                                             &graph_data
                                         );
+                                        crate::actors::node_budget::apply_node_budget(
+                                            &mut client.filter,
+                                            &graph_data,
+                                            analytics.as_ref(),
+                                        );
                                         info!("Recomputed filter for authenticated client {}: {} nodes visible",
                                               client_id, client.filter.filtered_node_ids.len());
                                     }
@@ -1932,13 +2347,15 @@ impl Handler<UpdateClientFilter> for ClientCoordinatorActor {
             if let Some(graph_addr) = self.graph_service_addr.clone() {
                 let client_id = msg.client_id;
                 let manager_arc = self.client_manager.clone();
+                let analytics = self.node_analytics.clone();
 
                 ctx.spawn(actix::fut::wrap_future::<_, Self>(async move {
                     use crate::actors::messages::GetGraphData;
-                    use crate::utils::socket_flow_messages::{InitialNodeData, InitialEdgeData};
 
                     match graph_addr.send(GetGraphData).await {
                         Ok(Ok(graph_data)) => {
+                            // Snapshot analytics before taking the manager lock.
+                            let analytics = analytics.read().map(|a| a.clone()).ok();
                             if let Ok(mut manager) = manager_arc.write() {
                                 if let Some(client) = manager.get_client_mut(client_id) {
                                     // Recompute which nodes pass the filter
@@ -1946,57 +2363,21 @@ impl Handler<UpdateClientFilter> for ClientCoordinatorActor {
                                         &mut client.filter,
                                         &graph_data
                                     );
+                                    crate::actors::node_budget::apply_node_budget(
+                                        &mut client.filter,
+                                        &graph_data,
+                                        analytics.as_ref(),
+                                    );
 
                                     let filtered_count = client.filter.filtered_node_ids.len();
                                     info!("Recomputed filter for client {}: {} nodes visible",
                                           client_id, filtered_count);
 
-                                    // Build filtered node data
-                                    let filtered_nodes: Vec<InitialNodeData> = graph_data
-                                        .nodes
-                                        .iter()
-                                        .filter(|n| client.filter.filtered_node_ids.contains(&n.id))
-                                        .map(|node| InitialNodeData {
-                                            id: node.id,
-                                            metadata_id: node.metadata_id.clone(),
-                                            label: node.label.clone(),
-                                            x: node.data.x,
-                                            y: node.data.y,
-                                            z: node.data.z,
-                                            vx: node.data.vx,
-                                            vy: node.data.vy,
-                                            vz: node.data.vz,
-                                            owl_class_iri: node.owl_class_iri.clone(),
-                                            node_type: node.node_type.clone(),
-                                            metadata: node.metadata.clone(),
-                                        })
-                                        .collect();
-
-                                    // Build filtered edge data (only edges where both endpoints pass filter)
-                                    let filtered_edges: Vec<InitialEdgeData> = graph_data
-                                        .edges
-                                        .iter()
-                                        .filter(|e| {
-                                            client.filter.filtered_node_ids.contains(&e.source) &&
-                                            client.filter.filtered_node_ids.contains(&e.target)
-                                        })
-                                        .map(|edge| InitialEdgeData {
-                                            id: edge.id.clone(),
-                                            source_id: edge.source,
-                                            target_id: edge.target,
-                                            weight: Some(edge.weight),
-                                            edge_type: edge.edge_type.clone(),
-                                        })
-                                        .collect();
-
-                                    info!("Sending filtered graph to client {}: {} nodes, {} edges",
-                                          client_id, filtered_nodes.len(), filtered_edges.len());
-
                                     // Send filtered graph data to this specific client
-                                    let _ = client.addr.initial_load.do_send(SendInitialGraphLoad {
-                                        nodes: filtered_nodes,
-                                        edges: filtered_edges,
-                                    });
+                                    let _ = client
+                                        .addr
+                                        .initial_load
+                                        .do_send(filtered_graph_load(&client.filter, &graph_data));
                                 }
                             }
                         }
@@ -2016,6 +2397,162 @@ impl Handler<UpdateClientFilter> for ClientCoordinatorActor {
     }
 }
 
+/// The nodes in `filter.filtered_node_ids` and the edges between them.
+fn filtered_graph_load(
+    filter: &ClientFilter,
+    graph_data: &crate::models::graph::GraphData,
+) -> SendInitialGraphLoad {
+    use crate::utils::socket_flow_messages::{InitialEdgeData, InitialNodeData};
+
+    let nodes: Vec<InitialNodeData> = graph_data
+        .nodes
+        .iter()
+        .filter(|n| filter.filtered_node_ids.contains(&n.id))
+        .map(|node| InitialNodeData {
+            id: node.id,
+            metadata_id: node.metadata_id.clone(),
+            label: node.label.clone(),
+            x: node.data.x,
+            y: node.data.y,
+            z: node.data.z,
+            vx: node.data.vx,
+            vy: node.data.vy,
+            vz: node.data.vz,
+            owl_class_iri: node.owl_class_iri.clone(),
+            node_type: node.node_type.clone(),
+            metadata: node.metadata.clone(),
+        })
+        .collect();
+
+    // Only edges where both endpoints pass the filter
+    let edges: Vec<InitialEdgeData> = graph_data
+        .edges
+        .iter()
+        .filter(|e| {
+            filter.filtered_node_ids.contains(&e.source)
+                && filter.filtered_node_ids.contains(&e.target)
+        })
+        .map(|edge| InitialEdgeData {
+            id: edge.id.clone(),
+            source_id: edge.source,
+            target_id: edge.target,
+            weight: Some(edge.weight),
+            edge_type: edge.edge_type.clone(),
+        })
+        .collect();
+
+    info!(
+        "Filtered graph: {} nodes, {} edges",
+        nodes.len(),
+        edges.len()
+    );
+    SendInitialGraphLoad { nodes, edges }
+}
+
+impl ClientCoordinatorActor {
+    /// Recompute a client's visible nodes (filter, then budget) against the
+    /// current graph and resend its initial load if the subset changed.
+    fn refilter_client(&self, ctx: &mut Context<Self>, client_id: usize, reason: &'static str) {
+        let Some(graph_addr) = self.graph_service_addr.clone() else {
+            return;
+        };
+        let manager_arc = self.client_manager.clone();
+        let analytics = self.node_analytics.clone();
+
+        ctx.spawn(actix::fut::wrap_future::<_, Self>(async move {
+            use crate::actors::messages::GetGraphData;
+
+            let graph_data = match graph_addr.send(GetGraphData).await {
+                Ok(Ok(graph_data)) => graph_data,
+                Err(e) => {
+                    warn!("Failed to fetch graph data for {}: {}", reason, e);
+                    return;
+                }
+                Ok(Err(e)) => {
+                    warn!("Graph data fetch error: {}", e);
+                    return;
+                }
+            };
+            let analytics = analytics.read().map(|a| a.clone()).ok();
+            if let Ok(mut manager) = manager_arc.write() {
+                if let Some(client) = manager.get_client_mut(client_id) {
+                    let previous = client.filter.filtered_node_ids.clone();
+                    crate::actors::client_filter::recompute_filtered_nodes(
+                        &mut client.filter,
+                        &graph_data,
+                    );
+                    crate::actors::node_budget::apply_node_budget(
+                        &mut client.filter,
+                        &graph_data,
+                        analytics.as_ref(),
+                    );
+                    // Focus moves often; only resend when the subset moved with it.
+                    if client.filter.filtered_node_ids != previous {
+                        info!(
+                            "{} for client {}: {} nodes visible",
+                            reason,
+                            client_id,
+                            client.filter.filtered_node_ids.len()
+                        );
+                        let _ = client
+                            .addr
+                            .initial_load
+                            .do_send(filtered_graph_load(&client.filter, &graph_data));
+                    }
+                }
+            }
+        }));
+    }
+}
+
+/// Set a client's node budget / focus and resend its subset if that changed.
+impl Handler<SetNodeBudget> for ClientCoordinatorActor {
+    type Result = Result<(), String>;
+
+    fn handle(&mut self, msg: SetNodeBudget, ctx: &mut Self::Context) -> Self::Result {
+        {
+            let mut manager = handle_rwlock_error(self.client_manager.write())
+                .map_err(|e| format!("Failed to acquire client manager lock: {}", e))?;
+            let client = manager
+                .get_client_mut(msg.client_id)
+                .ok_or_else(|| format!("Client {} not found", msg.client_id))?;
+            client.filter.node_budget = msg.max_nodes;
+            client.filter.focus_node = msg.focus_node;
+        }
+        debug!(
+            "Node budget for client {}: max_nodes={:?}, focus={:?}",
+            msg.client_id, msg.max_nodes, msg.focus_node
+        );
+        self.refilter_client(ctx, msg.client_id, "Node budget");
+        Ok(())
+    }
+}
+
+/// Set a client's content language filter and resend its subset if that changed.
+impl Handler<SetLanguageFilter> for ClientCoordinatorActor {
+    type Result = Result<(), String>;
+
+    fn handle(&mut self, msg: SetLanguageFilter, ctx: &mut Self::Context) -> Self::Result {
+        let languages = crate::services::language_detection::parse_languages(
+            msg.languages.iter().flatten().map(String::as_str),
+        )?;
+        {
+            let mut manager = handle_rwlock_error(self.client_manager.write())
+                .map_err(|e| format!("Failed to acquire client manager lock: {}", e))?;
+            let client = manager
+                .get_client_mut(msg.client_id)
+                .ok_or_else(|| format!("Client {} not found", msg.client_id))?;
+            client.filter.languages = languages;
+        }
+        debug!(
+            "Language filter for client {}: {:?}",
+            msg.client_id, msg.languages
+        );
+        self.refilter_client(ctx, msg.client_id, "Language filter");
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -4,7 +4,9 @@
 //! are visible to each client based on their filter criteria.
 
 use crate::actors::client_coordinator_actor::{ClientFilter, FilterMode};
+use crate::services::language_detection::node_language;
 use visionclaw_domain::models::graph::GraphData;
+use visionclaw_domain::models::node::Node;
 use log::{debug, trace};
 
 /// Recomputes which node IDs pass the client's filter criteria
@@ -19,8 +21,12 @@ pub fn recompute_filtered_nodes(filter: &mut ClientFilter, graph_data: &GraphDat
     filter.filtered_node_ids.clear();
 
     if !filter.enabled {
-        // Filter disabled = all nodes visible (still respect include_linked_pages)
+        // Filter disabled = all nodes visible (still respect include_linked_pages
+        // and the language filter)
         for node in &graph_data.nodes {
+            if !passes_language(filter, node, graph_data) {
+                continue;
+            }
             // SINGLE SOURCE OF TRUTH: gate linked_page stubs on the authoritative
             // origin via Node::population_type() (metadata["type"] first, node_type
             // only as legacy fallback), matching the client filter and the GPU.
@@ -41,6 +47,10 @@ pub fn recompute_filtered_nodes(filter: &mut ClientFilter, graph_data: &GraphDat
     let mut candidates = Vec::new();
 
     for node in &graph_data.nodes {
+        if !passes_language(filter, node, graph_data) {
+            continue;
+        }
+
         // Gate linked_page stub nodes (wikilink targets with no authored content).
         // SINGLE SOURCE OF TRUTH: read the authoritative origin via
         // Node::population_type() (metadata["type"] first, node_type only as legacy
@@ -130,6 +140,14 @@ pub fn recompute_filtered_nodes(filter: &mut ClientFilter, graph_data: &GraphDat
     );
 }
 
+/// Whether the node's detected content language is one the client asked for.
+fn passes_language(filter: &ClientFilter, node: &Node, graph_data: &GraphData) -> bool {
+    filter
+        .languages
+        .as_ref()
+        .is_none_or(|languages| languages.contains(node_language(node, &graph_data.metadata)))
+}
+
 /// Helper to check if a node passes the filter criteria without modifying state
 pub fn node_passes_filter(
     filter: &ClientFilter,
@@ -351,4 +369,27 @@ mod tests {
         assert!(filter.filtered_node_ids.contains(&1));
         assert!(filter.filtered_node_ids.contains(&2));
     }
+
+    #[test]
+    fn test_language_filter_applies_with_and_without_quality_filter() {
+        let mut graph = create_test_graph();
+        graph.metadata.get_mut("node1.md").unwrap().language = Some("deu".to_string());
+        graph.nodes[2]
+            .metadata
+            .insert("content_language".to_string(), "eng".to_string());
+        let mut filter = ClientFilter::default();
+        filter.enabled = false;
+        filter.languages = Some(["deu".to_string(), "und".to_string()].into_iter().collect());
+        assert!(filter.is_active());
+
+        recompute_filtered_nodes(&mut filter, &graph);
+
+        // Node 3 is English; nodes 2 and 4 have no detected language.
+        assert_eq!(filter.filtered_node_ids, [1, 2, 4].into_iter().collect());
+
+        filter.enabled = true;
+        filter.filter_mode = FilterMode::And;
+        recompute_filtered_nodes(&mut filter, &graph);
+        assert_eq!(filter.filtered_node_ids, [1].into_iter().collect());
+    }
 }
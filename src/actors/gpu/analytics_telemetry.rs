@@ -150,7 +150,17 @@ pub fn record_execution(kernel: AnalyticsKernel, path: ExecutionPath) -> Executi
             );
         }
         ExecutionPath::CpuFallback => {
-            CPU_FALLBACK_RUNS[i].fetch_add(1, Ordering::Relaxed);
+            let previous = CPU_FALLBACK_RUNS[i].fetch_add(1, Ordering::Relaxed);
+            // Notify webhook / chat subscribers on the first fallback per kernel
+            // only; every later run is still counted and logged below.
+            if previous == 0 {
+                crate::services::webhook_service::emit(
+                    crate::services::webhook_service::GraphEvent::GpuFallback {
+                        kernel: kernel.as_str().to_string(),
+                        reason: "GPU path failed; CPU implementation ran instead".to_string(),
+                    },
+                );
+            }
             // Zero-fallback gate: a silent CPU fallback is a gated regression. Surface
             // it at warn so it is never missed in logs or the metrics snapshot.
             log::warn!(
@@ -132,16 +132,24 @@ impl ClusteringActor {
     /// happens here so it always matches the V3 encoder's masked lookup. A
     /// `cluster_id_1based` of 0 contributes nothing beyond the reset (the node
     /// stays unclustered). Returns the number of nodes assigned a non-zero id.
+    /// Emits a `cluster.changed` webhook event when any node's id moved.
     fn write_cluster_id_from_assignments(
         &self,
         map: &mut std::collections::HashMap<u32, crate::utils::binary_protocol::NodeAnalytics>,
         assignments: &[(u32, u32)],
     ) -> usize {
+        let previous: std::collections::HashMap<u32, u32> = map
+            .iter()
+            .filter(|(_, e)| e.cluster_id != 0)
+            .map(|(&k, e)| (k, e.cluster_id))
+            .collect();
+
         // Single-writer reset: clear stale cluster_id from any prior run.
         for entry in map.values_mut() {
             entry.cluster_id = 0;
         }
         let mut assigned = 0usize;
+        let mut clusters = std::collections::HashSet::new();
         for &(graph_node_id, cluster_id_1based) in assignments {
             if cluster_id_1based == 0 {
                 continue;
@@ -151,8 +159,23 @@ impl ClusteringActor {
             let node_id = graph_node_id & crate::utils::binary_protocol::NODE_ID_MASK;
             let entry = map.entry(node_id).or_default();
             entry.cluster_id = cluster_id_1based;
+            clusters.insert(cluster_id_1based);
             assigned += 1;
         }
+
+        let changed_nodes = map
+            .iter()
+            .filter(|(k, e)| previous.get(k).copied().unwrap_or(0) != e.cluster_id)
+            .count();
+        if changed_nodes > 0 {
+            crate::services::webhook_service::emit(
+                crate::services::webhook_service::GraphEvent::ClusterChanged {
+                    clusters: clusters.len(),
+                    assigned_nodes: assigned,
+                    changed_nodes,
+                },
+            );
+        }
         assigned
     }
 
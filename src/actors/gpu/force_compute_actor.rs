@@ -6,7 +6,10 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::time::Instant;
 
-use super::shared::{GPUOperation, GPUState, SharedGPUContext};
+use super::shared::{GPUOperation, GPUState, SharedGPUContext, UnifiedGPUCompute};
+use super::workspace_contexts::{
+    ScheduledStep, WorkspaceGpuContexts, WorkspaceGraphUpload, DEFAULT_WORKSPACE_ID,
+};
 use crate::actors::messages::*;
 use crate::models::simulation_params::{SimulationParams, ToSimParams};
 use crate::telemetry::agent_telemetry::{
@@ -17,6 +20,9 @@ use crate::utils::unified_gpu_compute::ComputeMode;
 use crate::utils::unified_gpu_compute::SimParams;
 use crate::gpu::broadcast_optimizer::{BroadcastConfig, BroadcastOptimizer};
 use crate::gpu::backpressure::{BackpressureConfig, NetworkBackpressure};
+use crate::physics::stability_monitor::{
+    record_rejected_frame, record_scrubbed_frame, scrub_non_finite, AnomalyKind, SimulationAnomaly,
+};
 use glam::Vec3;
 
 use cudarc::driver::CudaDevice;
@@ -277,6 +283,21 @@ pub struct ForceComputeActor {
     /// Indexed by GPU buffer index. Populated during graph upload from node_type field.
     node_population: Vec<GraphPopulation>,
 
+    /// Host copy of the uploaded CSR (node id → row, offsets, columns, weights)
+    /// so `EdgeWeightsUpdated` can patch spring weights without a rebuild.
+    csr_node_index: std::collections::HashMap<u32, usize>,
+    csr_row_offsets: Vec<u32>,
+    csr_col_indices: Vec<u32>,
+    csr_weights: Vec<f32>,
+
+    /// Weights set by `EdgeWeightsUpdated` since `pending_graph_data` arrived,
+    /// reapplied if that graph is uploaded again (e.g. after a context reset).
+    edge_weight_overrides: std::collections::HashMap<(u32, u32), f32>,
+
+    /// Nodes pinned by `SetNodeMotion`, by node id, with their held
+    /// position. Rewritten after every step while any are pinned.
+    pinned_nodes: std::collections::HashMap<u32, [f32; 3]>,
+
     /// Graph data waiting to be uploaded to GPU (set by InitializeGPU/UpdateGPUGraphData,
     /// consumed when shared_context becomes available)
     pending_graph_data: Option<Arc<visionclaw_domain::models::graph::GraphData>>,
@@ -308,6 +329,10 @@ pub struct ForceComputeActor {
     /// broadcast payload when the current frame is bad, so clients never see
     /// infinity. Stored as (node_id, position, velocity-zeroed-on-recovery).
     last_good_positions: Vec<(u32, Vec3, Vec3)>,
+
+    /// Per-workspace params, GPU buffers and metrics; stepped round-robin
+    /// with the primary (default workspace) layout.
+    workspace_contexts: WorkspaceGpuContexts,
 }
 
 impl ForceComputeActor {
@@ -339,6 +364,8 @@ impl ForceComputeActor {
             initial_params.max_force, initial_params.max_velocity
         );
 
+        let workspace_contexts = WorkspaceGpuContexts::new(initial_params.clone());
+
         Self {
             gpu_state: GPUState::default(),
             shared_context: None,
@@ -372,6 +399,12 @@ impl ForceComputeActor {
             node_id_buffer: Vec::with_capacity(10000),
             gpu_index_to_node_id: Vec::new(),
             node_population: Vec::new(),
+            csr_node_index: std::collections::HashMap::new(),
+            csr_row_offsets: Vec::new(),
+            csr_col_indices: Vec::new(),
+            csr_weights: Vec::new(),
+            edge_weight_overrides: std::collections::HashMap::new(),
+            pinned_nodes: std::collections::HashMap::new(),
             pending_graph_data: None,
             physics_orchestrator_addr: None,
             gpu_self_init_attempts: 0,
@@ -380,6 +413,7 @@ impl ForceComputeActor {
             consecutive_bad_frames: 0,
             simulation_halted: false,
             last_good_positions: Vec::new(),
+            workspace_contexts,
         }
     }
 
@@ -646,9 +680,11 @@ impl ForceComputeActor {
         let mut adjacency_lists: Vec<Vec<(u32, f32)>> = vec![Vec::new(); num_nodes];
         for edge in &graph_data.edges {
             if let (Some(&src), Some(&tgt)) = (node_indices.get(&edge.source), node_indices.get(&edge.target)) {
-                adjacency_lists[src].push((tgt as u32, edge.weight));
+                let key = (edge.source.min(edge.target), edge.source.max(edge.target));
+                let weight = self.edge_weight_overrides.get(&key).copied().unwrap_or(edge.weight);
+                adjacency_lists[src].push((tgt as u32, weight));
                 if src != tgt {
-                    adjacency_lists[tgt].push((src as u32, edge.weight));
+                    adjacency_lists[tgt].push((src as u32, weight));
                 }
             }
         }
@@ -666,6 +702,10 @@ impl ForceComputeActor {
             }
         }
         row_offsets[num_nodes] = edge_count;
+        self.csr_node_index = node_indices;
+        self.csr_row_offsets = row_offsets.clone();
+        self.csr_col_indices = col_indices.clone();
+        self.csr_weights = edge_weights.clone();
 
         // Place isolated nodes (degree 0) on a spherical shell so they don't
         // clump in the center and obscure community structure of connected nodes.
@@ -883,6 +923,7 @@ impl ForceComputeActor {
                 let was_uninitialized = self.gpu_state.num_nodes == 0;
                 self.gpu_state.num_nodes = num_nodes as u32;
                 self.gpu_state.num_edges = edge_count;
+                self.workspace_contexts.set_default_size(num_nodes, edge_count as usize);
                 self.pending_graph_data = None;
 
                 // Fresh graph data needs a full warmup window so the layout can
@@ -1011,6 +1052,8 @@ impl ForceComputeActor {
         );
 
         self.simulation_params = params;
+        self.workspace_contexts
+            .set_params(DEFAULT_WORKSPACE_ID, self.simulation_params.clone());
 
         // Sync ALL GPU-relevant fields to unified_params
         {
@@ -1041,6 +1084,55 @@ impl ForceComputeActor {
         }
     }
 
+    /// Run one physics step for a non-default workspace on its own buffers.
+    /// The orchestrator is told the primary layout skipped this tick so the
+    /// sequential pipeline keeps moving.
+    fn step_workspace(
+        &mut self,
+        workspace_id: String,
+        compute: Arc<std::sync::Mutex<UnifiedGPUCompute>>,
+        params: SimulationParams,
+    ) -> ResponseActFuture<Self, Result<(), String>> {
+        self.is_computing = true;
+        let step_start = Instant::now();
+
+        let fut = async move {
+            tokio::task::spawn_blocking(move || {
+                let mut compute = match compute.lock() {
+                    Ok(guard) => guard,
+                    Err(poisoned) => poisoned.into_inner(),
+                };
+                compute
+                    .execute_physics_step(&params)
+                    .map_err(|e| e.to_string())
+            })
+            .await
+            .map_err(|e| format!("Workspace GPU step panicked: {}", e))?
+        };
+
+        Box::pin(fut.into_actor(self).map(move |result, actor, _ctx| {
+            let elapsed_ms = step_start.elapsed().as_secs_f32() * 1000.0;
+            actor.is_computing = false;
+            actor
+                .workspace_contexts
+                .record_step(&workspace_id, elapsed_ms, result.is_ok());
+            if let Err(ref e) = result {
+                warn!("ForceComputeActor: workspace '{}' step failed: {}", workspace_id, e);
+            }
+            if let Some(ref orch_addr) = actor.physics_orchestrator_addr {
+                orch_addr.do_send(crate::actors::messages::PhysicsStepCompleted {
+                    step_duration_ms: 0.0,
+                    nodes_broadcast: 0,
+                    iteration: actor.gpu_state.iteration_count,
+                    kinetic_energy: f64::MAX,
+                    skipped: true,
+                    read_back: false,
+                });
+            }
+            result
+        }))
+    }
+
     
     fn get_physics_stats(&self) -> PhysicsStats {
         
@@ -1233,6 +1325,70 @@ impl ForceComputeActor {
         }
     }
 
+    /// Upload the (scrubbed) readback buffer back to the GPU so repaired
+    /// values replace the non-finite ones in simulation state.
+    fn write_back_scrubbed_frame(&mut self) {
+        let Some(shared_context) = self.shared_context.clone() else {
+            return;
+        };
+        let Ok(mut unified_compute) = shared_context.unified_compute.try_lock() else {
+            warn!("ForceComputeActor: GPU mutex busy, scrubbed frame not written back");
+            return;
+        };
+        let n = self.position_velocity_buffer.len();
+        let mut columns: [Vec<f32>; 6] = Default::default();
+        for column in columns.iter_mut() {
+            column.reserve(n);
+        }
+        for (pos, vel) in &self.position_velocity_buffer {
+            for (column, value) in columns.iter_mut().zip([pos.x, pos.y, pos.z, vel.x, vel.y, vel.z]) {
+                column.push(value);
+            }
+        }
+        let [px, py, pz, vx, vy, vz] = columns;
+        if let Err(e) = unified_compute
+            .upload_positions(&px, &py, &pz)
+            .and_then(|()| unified_compute.upload_velocities(&vx, &vy, &vz))
+        {
+            warn!("ForceComputeActor: failed to write back scrubbed frame: {}", e);
+        }
+    }
+
+    /// Hand an unrepairable readback to the orchestrator's anomaly path.
+    fn report_readback_anomaly(&self, non_finite: usize) {
+        error!(
+            "[ForceComputeActor] {} of {} nodes non-finite at iter {} — too many to scrub",
+            non_finite,
+            self.position_velocity_buffer.len(),
+            self.gpu_state.iteration_count
+        );
+        let Some(ref orch_addr) = self.physics_orchestrator_addr else {
+            return;
+        };
+        let mut node_ids = Vec::new();
+        let mut positions = Vec::with_capacity(self.position_velocity_buffer.len());
+        for (idx, (pos, vel)) in self.position_velocity_buffer.iter().enumerate() {
+            let node_id = self.node_id_buffer.get(idx).copied().unwrap_or(idx as u32);
+            if !(pos.is_finite() && vel.is_finite()) {
+                node_ids.push(node_id);
+            }
+            positions.push((
+                node_id,
+                BinaryNodeDataClient::new(node_id, glam_to_vec3data(*pos), glam_to_vec3data(*vel)),
+            ));
+        }
+        let anomaly = SimulationAnomaly::new(
+            AnomalyKind::NonFinitePosition,
+            format!(
+                "{} of {} nodes non-finite in GPU readback",
+                non_finite,
+                positions.len()
+            ),
+            node_ids,
+        );
+        orch_addr.do_send(crate::actors::messages::ReadbackAnomaly { anomaly, positions });
+    }
+
     /// Clear divergence/circuit-breaker state and re-arm the simulation.
     /// Called on recovery triggers (e.g. a parameter change) so a previously
     /// halted layout resumes stepping from its restored good positions.
@@ -1358,7 +1514,7 @@ impl Actor for ForceComputeActor {
 impl Handler<ComputeForces> for ForceComputeActor {
     type Result = ResponseActFuture<Self, Result<(), String>>;
 
-    fn handle(&mut self, _msg: ComputeForces, _ctx: &mut Self::Context) -> Self::Result {
+    fn handle(&mut self, msg: ComputeForces, _ctx: &mut Self::Context) -> Self::Result {
         // Helper: notify orchestrator on early exit so the pipeline doesn't stall.
         macro_rules! notify_skip {
             ($self:ident) => {
@@ -1369,6 +1525,7 @@ impl Handler<ComputeForces> for ForceComputeActor {
                         iteration: $self.gpu_state.iteration_count,
                         kinetic_energy: f64::MAX, // Unknown — don't trigger false convergence
                         skipped: true, // benign skip, NOT a GPU failure — must not trip the breaker
+                        read_back: false,
                     });
                 }
             };
@@ -1380,13 +1537,12 @@ impl Handler<ComputeForces> for ForceComputeActor {
         // or an explicit reset) re-arms it.
         if self.simulation_halted {
             self.skipped_frames += 1;
-            if self.skipped_frames % 300 == 0 {
-                error!(
-                    "ForceComputeActor: simulation HALTED (divergence circuit breaker tripped) — \
-                     stepping suspended, last-known-good positions retained. \
-                     Apply a parameter change to recover."
-                );
-            }
+            crate::log_throttled!(
+                log::Level::Error,
+                "ForceComputeActor: simulation HALTED (divergence circuit breaker tripped) — \
+                 stepping suspended, last-known-good positions retained. \
+                 Apply a parameter change to recover."
+            );
             notify_skip!(self);
             return Box::pin(futures::future::ready(Ok(())).into_actor(self));
         }
@@ -1404,16 +1560,25 @@ impl Handler<ComputeForces> for ForceComputeActor {
 
         if self.is_computing {
             self.skipped_frames += 1;
-            if self.skipped_frames % 60 == 0 {
-                info!(
-                    "ForceComputeActor: Skipped {} frames due to ongoing GPU computation",
-                    self.skipped_frames
-                );
-            }
+            crate::log_throttled!(
+                log::Level::Info,
+                "ForceComputeActor: Skipped {} frames due to ongoing GPU computation",
+                self.skipped_frames
+            );
             notify_skip!(self);
             return Box::pin(futures::future::ready(Ok(())).into_actor(self));
         }
 
+        // Fair round-robin across workspace contexts: on another workspace's
+        // turn the primary layout sits this tick out.
+        if self.workspace_contexts.len() > 1 {
+            if let ScheduledStep::Workspace { workspace_id, compute, params } =
+                self.workspace_contexts.next_turn()
+            {
+                return self.step_workspace(workspace_id, compute, params);
+            }
+        }
+
         // Check for shared context; attempt self-init if missing
         if self.shared_context.is_none() {
             self.initialize_own_gpu_context();
@@ -1453,12 +1618,12 @@ impl Handler<ComputeForces> for ForceComputeActor {
         let correlation_id = CorrelationId::new();
         let iteration = self.iteration_count();
 
-        if iteration % 60 == 0 {
-            info!(
-                "ForceComputeActor: Computing forces (iteration {}), nodes: {}",
-                iteration, self.gpu_state.num_nodes
-            );
-        }
+        crate::log_throttled!(
+            log::Level::Info,
+            "ForceComputeActor: Computing forces (iteration {}), nodes: {}",
+            iteration,
+            self.gpu_state.num_nodes
+        );
 
         // Log telemetry event
         if let Some(logger) = get_telemetry_logger() {
@@ -1492,6 +1657,16 @@ impl Handler<ComputeForces> for ForceComputeActor {
         }
         let reheat_factor = self.reheat_factor;
         let current_iteration = self.gpu_state.iteration_count;
+        let read_back = msg.read_back;
+        let pinned: Vec<(usize, [f32; 3], [f32; 3])> = self
+            .pinned_nodes
+            .iter()
+            .filter_map(|(node_id, &position)| {
+                self.csr_node_index
+                    .get(node_id)
+                    .map(|&index| (index, position, [0.0; 3]))
+            })
+            .collect();
 
         // Log GPU params on first iteration to verify non-zero values
         if current_iteration == 0 {
@@ -1506,6 +1681,7 @@ impl Handler<ComputeForces> for ForceComputeActor {
         // GPU operations are inherently blocking (waiting for GPU kernels), so we move them
         // to the blocking thread pool to keep async executor threads responsive
         let fut = async move {
+            let wait_start = Instant::now();
             // Acquire GPU access asynchronously (this uses tokio::sync::RwLock - non-blocking)
             let _gpu_guard = match shared_context.acquire_gpu_access().await {
                 Ok(guard) => guard,
@@ -1528,7 +1704,9 @@ impl Handler<ComputeForces> for ForceComputeActor {
                         poisoned.into_inner()
                     }
                 };
+                let lock_wait_ms = wait_start.elapsed().as_secs_f32() * 1000.0;
 
+                let perturb_start = Instant::now();
                 if reheat_factor > 0.0 {
                     info!(
                         "Reheating physics with factor {:.2} to break equilibrium after parameter change",
@@ -1539,12 +1717,42 @@ impl Handler<ComputeForces> for ForceComputeActor {
                     }
                 }
 
+                let perturb_ms = perturb_start.elapsed().as_secs_f32() * 1000.0;
+
                 let gpu_result = unified_compute.execute_physics_step_with_bypass(&sim_params, stability_bypass);
+                if gpu_result.is_ok() {
+                    // Put pinned nodes back before anything reads the step.
+                    if let Err(e) = unified_compute.set_node_states(&pinned) {
+                        warn!("Failed to hold {} pinned node(s): {}", pinned.len(), e);
+                    }
+                }
                 let execution_duration = step_start.elapsed().as_secs_f64() * 1000.0;
 
-                // Get positions and velocities for broadcast
-                let positions_result = unified_compute.get_node_positions();
-                let velocities_result = unified_compute.get_node_velocities();
+                // Get positions and velocities for broadcast. Lower quality
+                // levels skip the download on some steps; those steps leave
+                // the previous frame in place and broadcast nothing.
+                let readback_start = Instant::now();
+                let (positions_result, velocities_result) = if read_back {
+                    (unified_compute.get_node_positions(), unified_compute.get_node_velocities())
+                } else {
+                    (Err(anyhow::anyhow!("readback skipped")), Err(anyhow::anyhow!("readback skipped")))
+                };
+
+                if gpu_result.is_ok() {
+                    let gpu_metrics = unified_compute.get_performance_metrics();
+                    crate::physics::step_profile::step_profiler().record_step(
+                        crate::physics::step_profile::StepTimings {
+                            iteration: u64::from(iteration),
+                            recorded_at: chrono::Utc::now(),
+                            lock_wait_ms,
+                            upload_ms: perturb_ms + gpu_metrics.last_step_upload_ms,
+                            kernel_ms: gpu_metrics.last_step_kernel_ms,
+                            readback_ms: readback_start.elapsed().as_secs_f32() * 1000.0,
+                            encode_ms: None,
+                            broadcast_ms: None,
+                        },
+                    );
+                }
 
                 Ok((gpu_result, execution_duration, positions_result, velocities_result))
             }).await;
@@ -1660,9 +1868,9 @@ impl Handler<ComputeForces> for ForceComputeActor {
                                 let flatten = actor.simulation_params.axis_compression_z.clamp(0.0, 1.0);
                                 let face_scale = 1.0 - flatten;
                                 let project = (sep > 0.0 || flatten > 0.0) && !actor.node_population.is_empty();
-                                // Once-per-300-iter diagnostic to verify the params reach this site.
-                                if actor.gpu_state.iteration_count % 300 == 0 && project {
-                                    info!(
+                                if project {
+                                    crate::log_throttled!(
+                                        log::Level::Info,
                                         "ForceComputeActor: facing-disc projection iter={} sep_z={:.1} flatten={:.2} populations={} (k+o+a)",
                                         actor.gpu_state.iteration_count, sep, flatten, actor.node_population.len()
                                     );
@@ -1687,6 +1895,32 @@ impl Handler<ComputeForces> for ForceComputeActor {
                                 // breaker counter advances. After MAX_CONSECUTIVE_BAD_FRAMES
                                 // the simulation halts and recovery resets velocities.
                                 // ---------------------------------------------------------
+
+                                // Isolated NaN/Inf nodes are repaired from the last-known-good
+                                // frame (and the repair written back to the GPU so it cannot
+                                // spread through the force kernels) before the guard runs.
+                                // A frame with too many to repair still fails the guard
+                                // below, and the orchestrator pauses via its anomaly path.
+                                match scrub_non_finite(
+                                    &mut actor.position_velocity_buffer,
+                                    &actor.node_id_buffer,
+                                    &actor.last_good_positions,
+                                ) {
+                                    Ok(0) => {}
+                                    Ok(scrubbed) => {
+                                        record_scrubbed_frame(scrubbed);
+                                        warn!(
+                                            "[ForceComputeActor] Scrubbed {} non-finite node(s) at iter {} from last-known-good",
+                                            scrubbed, actor.gpu_state.iteration_count
+                                        );
+                                        actor.write_back_scrubbed_frame();
+                                    }
+                                    Err(non_finite) => {
+                                        record_rejected_frame();
+                                        actor.report_readback_anomaly(non_finite);
+                                    }
+                                }
+
                                 let mut nan_count = 0usize;
                                 let mut oob_count = 0usize;
                                 for (p, v) in actor.position_velocity_buffer.iter() {
@@ -1919,12 +2153,11 @@ impl Handler<ComputeForces> for ForceComputeActor {
                                                 )));
                                             }
                                             if let Some(ref graph_addr) = actor.graph_service_addr {
-                                                if actor.gpu_state.iteration_count % 300 == 0 {
-                                                    info!(
-                                                        "ForceComputeActor: Full snapshot — {} nodes (iter {})",
-                                                        node_updates.len(), actor.gpu_state.iteration_count
-                                                    );
-                                                }
+                                                crate::log_throttled!(
+                                                    log::Level::Info,
+                                                    "ForceComputeActor: Full snapshot — {} nodes (iter {})",
+                                                    node_updates.len(), actor.gpu_state.iteration_count
+                                                );
                                                 graph_addr.do_send(crate::actors::messages::UpdateNodePositions {
                                                     positions: node_updates,
                                                     correlation_id: Some(crate::actors::messaging::MessageId::new()),
@@ -1990,6 +2223,11 @@ impl Handler<ComputeForces> for ForceComputeActor {
 
                             actor.gpu_state.iteration_count += 1;
                             actor.last_step_duration_ms = step_start.elapsed().as_millis() as f32;
+                            actor.workspace_contexts.record_step(
+                                DEFAULT_WORKSPACE_ID,
+                                actor.last_step_duration_ms,
+                                true,
+                            );
 
                             if actor.iteration_count() % 300 == 0 {
                                 debug!("ForceComputeActor: {} iterations completed, {} GPU failures, {} skipped frames, last step: {:.2}ms",
@@ -1998,7 +2236,9 @@ impl Handler<ComputeForces> for ForceComputeActor {
 
                             // Compute kinetic energy from velocity buffer for convergence detection.
                             // KE = 0.5 * sum(vx^2 + vy^2 + vz^2), averaged over node count.
-                            let step_kinetic_energy = if actor.position_velocity_buffer.is_empty() {
+                            // Without a readback the buffer still holds an older frame, so
+                            // the step reports no energy and no nodes.
+                            let step_kinetic_energy = if !read_back || actor.position_velocity_buffer.is_empty() {
                                 0.0_f64
                             } else {
                                 let total_ke: f64 = actor.position_velocity_buffer.iter()
@@ -2016,10 +2256,15 @@ impl Handler<ComputeForces> for ForceComputeActor {
                             if let Some(ref orch_addr) = actor.physics_orchestrator_addr {
                                 orch_addr.do_send(crate::actors::messages::PhysicsStepCompleted {
                                     step_duration_ms: actor.last_step_duration_ms,
-                                    nodes_broadcast: actor.position_velocity_buffer.len() as u32,
+                                    nodes_broadcast: if read_back {
+                                        actor.position_velocity_buffer.len() as u32
+                                    } else {
+                                        0
+                                    },
                                     iteration: actor.gpu_state.iteration_count,
                                     kinetic_energy: step_kinetic_energy,
                                     skipped: false,
+                                    read_back,
                                 });
                             }
 
@@ -2031,6 +2276,11 @@ impl Handler<ComputeForces> for ForceComputeActor {
                             let error_msg = format!("GPU force computation failed: {}", e);
                             error!("{}", error_msg);
                             actor.gpu_state.gpu_failure_count += 1;
+                            actor.workspace_contexts.record_step(
+                                DEFAULT_WORKSPACE_ID,
+                                step_start.elapsed().as_millis() as f32,
+                                false,
+                            );
 
                             // Sequential pipeline: notify orchestrator even on failure
                             // so the pipeline doesn't stall.
@@ -2041,6 +2291,7 @@ impl Handler<ComputeForces> for ForceComputeActor {
                                     iteration: actor.gpu_state.iteration_count,
                                     kinetic_energy: f64::MAX,
                                     skipped: false, // real GPU compute failure
+                                    read_back: false,
                                 });
                             }
 
@@ -2063,6 +2314,7 @@ impl Handler<ComputeForces> for ForceComputeActor {
                             iteration: actor.gpu_state.iteration_count,
                             kinetic_energy: f64::MAX,
                             skipped: false, // real GPU access failure
+                            read_back: false,
                         });
                     }
 
@@ -2262,6 +2514,26 @@ impl Handler<UpdateClusteringParams> for ForceComputeActor {
 #[rtype(result = "()")]
 pub struct ForceFullBroadcast;
 
+/// Sent by the orchestrator's watchdog when the pipeline stopped producing
+/// steps. Clears a step left marked in flight so the next `ComputeForces`
+/// runs, then re-broadcasts the last positions the GPU holds.
+#[derive(actix::Message)]
+#[rtype(result = "()")]
+pub struct RecoverStalledStep;
+
+impl Handler<RecoverStalledStep> for ForceComputeActor {
+    type Result = ();
+
+    fn handle(&mut self, _msg: RecoverStalledStep, ctx: &mut Self::Context) -> Self::Result {
+        if self.is_computing {
+            warn!("ForceComputeActor: clearing stuck in-flight step after pipeline stall");
+            self.is_computing = false;
+            self.gpu_state.complete_operation(&GPUOperation::ForceComputation);
+        }
+        ctx.notify(ForceFullBroadcast);
+    }
+}
+
 impl Handler<ForceFullBroadcast> for ForceComputeActor {
     type Result = ResponseActFuture<Self, ()>;
 
@@ -2345,24 +2617,41 @@ impl Handler<ForceFullBroadcast> for ForceComputeActor {
                     let r_max = DISC_RIM_RADIUS;
 
                     let mut node_updates = Vec::with_capacity(pos_x.len());
+                    let mut scrubbed = 0usize;
                     for i in 0..pos_x.len() {
+                        let node_id = actor.gpu_index_to_node_id.get(i).copied().unwrap_or(i as u32);
                         let mut position = Vec3::new(pos_x[i], pos_y[i], pos_z[i]);
-                        let velocity = Vec3::new(vel_x[i], vel_y[i], vel_z[i]);
-                        if !position.x.is_finite() || !position.y.is_finite() || !position.z.is_finite() {
-                            continue;
+                        let mut velocity = Vec3::new(vel_x[i], vel_y[i], vel_z[i]);
+                        if !(position.is_finite() && velocity.is_finite()) {
+                            // Same repair as the main loop; nodes with no
+                            // last-known-good entry are left out of the snapshot.
+                            match actor.last_good_positions.get(i) {
+                                Some(&(id, good_pos, good_vel)) if id == node_id => {
+                                    if !position.is_finite() {
+                                        position = good_pos;
+                                    }
+                                    if !velocity.is_finite() {
+                                        velocity = good_vel;
+                                    }
+                                    scrubbed += 1;
+                                }
+                                _ => continue,
+                            }
                         }
                         if project {
                             if let Some(&pop) = actor.node_population.get(i) {
                                 project_node_xy(&mut position, pop, &centroids, sep, face_scale, r_max);
                             }
                         }
-                        let node_id = actor.gpu_index_to_node_id.get(i).copied().unwrap_or(i as u32);
                         node_updates.push((node_id, BinaryNodeDataClient::new(
                             node_id,
                             glam_to_vec3data(position),
                             glam_to_vec3data(velocity),
                         )));
                     }
+                    if scrubbed > 0 {
+                        record_scrubbed_frame(scrubbed);
+                    }
 
                     if let Some(ref graph_addr) = actor.graph_service_addr {
                         info!(
@@ -2533,6 +2822,7 @@ impl Handler<InitializeGPU> for ForceComputeActor {
 
         // Store graph data for GPU upload
         self.pending_graph_data = Some(msg.graph);
+        self.edge_weight_overrides.clear();
 
         // Ensure GPU context is available before attempting upload
         if self.shared_context.is_none() {
@@ -2593,8 +2883,10 @@ impl Handler<UpdateGPUGraphData> for ForceComputeActor {
         info!("ForceComputeActor: UpdateGPUGraphData received with {} nodes, {} edges",
             msg.graph.nodes.len(), msg.graph.edges.len());
 
-        // Store graph data and attempt upload (num_nodes set only after successful upload)
+        // Store graph data and attempt upload (num_nodes set only after successful upload).
+        // The new graph carries current edge weights, so earlier overrides are dropped.
         self.pending_graph_data = Some(msg.graph);
+        self.edge_weight_overrides.clear();
         if self.shared_context.is_none() {
             self.initialize_own_gpu_context();
         }
@@ -2703,6 +2995,132 @@ impl Handler<GetGPUMetrics> for ForceComputeActor {
     }
 }
 
+impl Handler<UpdateWorkspaceSimulationParams> for ForceComputeActor {
+    type Result = Result<(), String>;
+
+    fn handle(&mut self, msg: UpdateWorkspaceSimulationParams, ctx: &mut Self::Context) -> Self::Result {
+        if msg.workspace_id == DEFAULT_WORKSPACE_ID {
+            return <Self as Handler<UpdateSimulationParams>>::handle(
+                self,
+                UpdateSimulationParams { params: msg.params },
+                ctx,
+            );
+        }
+        msg.params
+            .validate()
+            .map_err(|e| format!("Parameter validation failed: {}", e))?;
+        info!("ForceComputeActor: updating simulation params for workspace '{}'", msg.workspace_id);
+        self.workspace_contexts.set_params(&msg.workspace_id, msg.params);
+        Ok(())
+    }
+}
+
+impl Handler<UploadWorkspaceGraph> for ForceComputeActor {
+    type Result = Result<(), String>;
+
+    fn handle(&mut self, msg: UploadWorkspaceGraph, _ctx: &mut Self::Context) -> Self::Result {
+        if msg.workspace_id == DEFAULT_WORKSPACE_ID {
+            return Err("The default workspace is uploaded via UpdateGPUGraphData".to_string());
+        }
+        let upload = WorkspaceGraphUpload::from_graph(&msg.graph);
+        let num_nodes = upload.node_ids.len();
+        let num_edges = upload.num_edges();
+        if num_nodes == 0 {
+            return Err(format!("Workspace '{}' graph has no nodes", msg.workspace_id));
+        }
+
+        let existing = self
+            .workspace_contexts
+            .get(&msg.workspace_id)
+            .and_then(|c| c.compute.clone());
+        let compute = match existing {
+            Some(compute) => compute,
+            None => {
+                let ptx = visionclaw_gpu::ptx_loader::load_ptx_module_sync(
+                    visionclaw_gpu::ptx_loader::PTXModule::VisionflowUnified,
+                )
+                .map_err(|e| format!("Failed to load main PTX: {}", e))?;
+                let engine = UnifiedGPUCompute::new(num_nodes, num_edges, &ptx)
+                    .map_err(|e| format!("Failed to create workspace GPU context: {}", e))?;
+                Arc::new(std::sync::Mutex::new(engine))
+            }
+        };
+
+        {
+            let mut guard = match compute.lock() {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            guard
+                .initialize_graph(
+                    upload.row_offsets.clone(),
+                    upload.col_indices.clone(),
+                    upload.edge_weights.clone(),
+                    upload.positions_x.clone(),
+                    upload.positions_y.clone(),
+                    upload.positions_z.clone(),
+                    num_nodes,
+                    num_edges,
+                )
+                .map_err(|e| format!("Workspace graph upload failed: {}", e))?;
+        }
+
+        info!(
+            "ForceComputeActor: workspace '{}' uploaded ({} nodes, {} CSR edges)",
+            msg.workspace_id, num_nodes, num_edges
+        );
+        self.workspace_contexts
+            .attach_compute(&msg.workspace_id, compute, &upload);
+        Ok(())
+    }
+}
+
+impl Handler<RemoveWorkspaceGpuContext> for ForceComputeActor {
+    type Result = bool;
+
+    fn handle(&mut self, msg: RemoveWorkspaceGpuContext, _ctx: &mut Self::Context) -> Self::Result {
+        let removed = self.workspace_contexts.remove(&msg.workspace_id);
+        if removed {
+            info!("ForceComputeActor: released GPU context for workspace '{}'", msg.workspace_id);
+        }
+        removed
+    }
+}
+
+impl Handler<GetWorkspaceGpuMetrics> for ForceComputeActor {
+    type Result = MessageResult<GetWorkspaceGpuMetrics>;
+
+    fn handle(&mut self, _msg: GetWorkspaceGpuMetrics, _ctx: &mut Self::Context) -> Self::Result {
+        MessageResult(self.workspace_contexts.metrics())
+    }
+}
+
+impl Handler<GetWorkspacePositions> for ForceComputeActor {
+    type Result = Result<Vec<(u32, [f32; 3])>, String>;
+
+    fn handle(&mut self, msg: GetWorkspacePositions, _ctx: &mut Self::Context) -> Self::Result {
+        let ctx = self
+            .workspace_contexts
+            .get(&msg.workspace_id)
+            .ok_or_else(|| format!("Unknown workspace '{}'", msg.workspace_id))?;
+        let compute = ctx
+            .compute
+            .clone()
+            .ok_or_else(|| format!("Workspace '{}' has no GPU context", msg.workspace_id))?;
+        let mut guard = compute
+            .try_lock()
+            .map_err(|_| "Workspace GPU context is busy".to_string())?;
+        let (x, y, z) = guard.get_node_positions().map_err(|e| e.to_string())?;
+        Ok(ctx
+            .node_ids
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i < x.len())
+            .map(|(i, &id)| (id, [x[i], y[i], z[i]]))
+            .collect())
+    }
+}
+
 impl Handler<RunCommunityDetection> for ForceComputeActor {
     type Result = Result<CommunityDetectionResult, String>;
 
@@ -3020,6 +3438,166 @@ impl Handler<crate::actors::messages::ResetPositions> for ForceComputeActor {
     }
 }
 
+/// Handler for SeedPositions — overwrites the positions of known node ids with
+/// a layout the client saved earlier; see `physics::layout_seed`.
+impl Handler<crate::actors::messages::SeedPositions> for ForceComputeActor {
+    type Result = Result<crate::physics::layout_seed::SeedReport, String>;
+
+    fn handle(&mut self, msg: crate::actors::messages::SeedPositions, _ctx: &mut Self::Context) -> Self::Result {
+        use crate::physics::layout_seed::{apply_seed, check_match, SeedReport};
+
+        let ctx = self
+            .shared_context
+            .clone()
+            .ok_or_else(|| "GPU context not available".to_string())?;
+        let num_nodes = self.gpu_state.num_nodes as usize;
+        if num_nodes == 0 {
+            return Err("No nodes loaded in GPU".to_string());
+        }
+
+        // Same synchronous upload as ResetPositions: the actor handles this
+        // before any later ComputeForces, so the next step starts from the
+        // seeded layout.
+        let mut compute = match ctx.unified_compute.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => {
+                error!("ForceComputeActor: SeedPositions — GPU mutex poisoned, recovering");
+                poisoned.into_inner()
+            }
+        };
+        let (mut x, mut y, mut z) = compute
+            .get_node_positions()
+            .map_err(|e| format!("Failed to read current positions: {}", e))?;
+
+        let applied = apply_seed(
+            &self.gpu_index_to_node_id,
+            &msg.positions,
+            &mut x,
+            &mut y,
+            &mut z,
+        );
+        check_match(applied, msg.positions.len())?;
+
+        compute
+            .upload_positions(&x, &y, &z)
+            .and_then(|_| compute.reset_velocities())
+            .map_err(|e| format!("Failed to upload seeded positions to GPU: {}", e))?;
+        drop(compute);
+
+        // Let the seeded layout settle gently rather than reheating it, and
+        // make sure every client gets it in full.
+        self.reheat_factor = 0.0;
+        self.stability_iterations = 0;
+        self.force_full_broadcast = true;
+
+        info!(
+            "ForceComputeActor: Seeded {} of {} node positions from a client layout",
+            applied, num_nodes
+        );
+        Ok(SeedReport {
+            applied,
+            unknown: msg.positions.len() - applied,
+            total_nodes: num_nodes,
+        })
+    }
+}
+
+impl Handler<SetNodeMotion> for ForceComputeActor {
+    type Result = Result<(), String>;
+
+    fn handle(&mut self, msg: SetNodeMotion, _ctx: &mut Self::Context) -> Self::Result {
+        let ctx = self
+            .shared_context
+            .clone()
+            .ok_or_else(|| "GPU context not available".to_string())?;
+        let index = *self
+            .csr_node_index
+            .get(&msg.node_id)
+            .ok_or_else(|| format!("Node {} is not on the GPU", msg.node_id))?;
+
+        if msg.pinned {
+            self.pinned_nodes.insert(msg.node_id, msg.position);
+        } else {
+            self.pinned_nodes.remove(&msg.node_id);
+        }
+
+        // Synchronous like SeedPositions, so the next step starts from it.
+        let mut compute = match ctx.unified_compute.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => {
+                error!("ForceComputeActor: SetNodeMotion — GPU mutex poisoned, recovering");
+                poisoned.into_inner()
+            }
+        };
+        let velocity = if msg.pinned { [0.0; 3] } else { msg.velocity };
+        compute
+            .set_node_states(&[(index, msg.position, velocity)])
+            .map_err(|e| format!("Failed to set state of node {}: {}", msg.node_id, e))
+    }
+}
+
+impl Handler<UnpinNode> for ForceComputeActor {
+    type Result = ();
+
+    fn handle(&mut self, msg: UnpinNode, _ctx: &mut Self::Context) -> Self::Result {
+        self.pinned_nodes.remove(&msg.node_id);
+    }
+}
+
+impl Handler<EdgeWeightsUpdated> for ForceComputeActor {
+    type Result = Result<crate::physics::edge_weights::EdgeWeightReport, String>;
+
+    fn handle(&mut self, msg: EdgeWeightsUpdated, _ctx: &mut Self::Context) -> Self::Result {
+        use crate::physics::edge_weights::{apply_to_csr, collect_updates};
+
+        let ctx = self
+            .shared_context
+            .clone()
+            .ok_or_else(|| "GPU context not available".to_string())?;
+        if self.csr_weights.is_empty() {
+            return Err("No edges loaded in GPU".to_string());
+        }
+
+        let weights = collect_updates(&msg.updates)?;
+        let report = apply_to_csr(
+            &self.csr_row_offsets,
+            &self.csr_col_indices,
+            &mut self.csr_weights,
+            &self.csr_node_index,
+            &weights,
+        );
+        if report.updated == 0 {
+            return Ok(report);
+        }
+
+        // Only the weight array is re-uploaded; positions, velocities and the
+        // CSR structure stay as they are.
+        let mut compute = match ctx.unified_compute.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => {
+                error!("ForceComputeActor: EdgeWeightsUpdated — GPU mutex poisoned, recovering");
+                poisoned.into_inner()
+            }
+        };
+        compute
+            .upload_edge_weights(&self.csr_weights)
+            .map_err(|e| format!("Failed to upload edge weights to GPU: {}", e))?;
+        drop(compute);
+        self.edge_weight_overrides.extend(weights);
+
+        // Let the changed springs act even if the layout had settled: keep the
+        // stability gate open for a while, without reheating the whole graph.
+        self.stability_warmup_remaining = self.stability_warmup_remaining.max(600);
+        self.stability_iterations = 0;
+
+        info!(
+            "ForceComputeActor: Updated spring weights for {} node pairs ({} unknown)",
+            report.updated, report.unknown
+        );
+        Ok(report)
+    }
+}
+
 /// Handler for ConfigureStressMajorization message
 impl Handler<ConfigureStressMajorization> for ForceComputeActor {
     type Result = Result<(), String>;
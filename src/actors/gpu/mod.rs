@@ -53,6 +53,7 @@ pub mod connected_components_actor;
 pub mod shared;
 pub mod stress_majorization_actor;
 pub mod semantic_forces_actor;
+pub mod workspace_contexts;
 
 // Supervisor actors
 pub mod supervisor_messages;
@@ -535,7 +535,9 @@ impl SemanticForcesActor {
                 min_distance: self.config.collision.min_distance,
                 collision_strength: self.config.collision.collision_strength,
                 node_radius: self.config.collision.node_radius,
-                enabled: self.config.collision.enabled,
+                // A quality level may switch collision off, never on.
+                enabled: self.config.collision.enabled
+                    && crate::physics::quality::simulation_quality().collision,
                 _pad: [0; 3],
             },
             attribute_spring: AttributeSpringConfigGPU {
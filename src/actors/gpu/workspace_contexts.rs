@@ -0,0 +1,369 @@
+//! Per-workspace GPU simulation contexts.
+//!
+//! Each workspace gets its own `SimulationParams`, its own `UnifiedGPUCompute`
+//! buffers and its own step metrics, while all of them run on the same CUDA
+//! device (ordinal 0). The default workspace keeps using the actor's
+//! `SharedGPUContext`; additional workspaces get a dedicated compute engine
+//! created on first graph upload.
+//!
+//! `ForceComputeActor` asks [`WorkspaceGpuContexts::next_turn`] on every
+//! `ComputeForces` tick. Ready workspaces are stepped round-robin, one per
+//! tick, so a large graph in one workspace cannot starve the others.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use super::shared::UnifiedGPUCompute;
+use crate::models::simulation_params::SimulationParams;
+use visionclaw_domain::models::graph::GraphData;
+
+/// Workspace whose layout is driven by the actor's primary shared context.
+pub const DEFAULT_WORKSPACE_ID: &str = "default";
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceGpuMetrics {
+    pub workspace_id: String,
+    pub num_nodes: usize,
+    pub num_edges: usize,
+    pub steps: u64,
+    pub failed_steps: u64,
+    pub last_step_ms: f32,
+    pub average_step_ms: f32,
+    pub last_step_at: Option<DateTime<Utc>>,
+    /// False for a context that has no graph uploaded and is never stepped.
+    pub active: bool,
+    #[serde(skip)]
+    total_step_ms: f64,
+}
+
+impl WorkspaceGpuMetrics {
+    fn new(workspace_id: &str) -> Self {
+        Self {
+            workspace_id: workspace_id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn record(&mut self, duration_ms: f32, ok: bool) {
+        if ok {
+            self.steps += 1;
+            self.total_step_ms += duration_ms as f64;
+            self.average_step_ms = (self.total_step_ms / self.steps as f64) as f32;
+        } else {
+            self.failed_steps += 1;
+        }
+        self.last_step_ms = duration_ms;
+        self.last_step_at = Some(Utc::now());
+    }
+}
+
+/// CSR upload prepared from a workspace graph.
+#[derive(Debug, Clone, Default)]
+pub struct WorkspaceGraphUpload {
+    pub node_ids: Vec<u32>,
+    pub row_offsets: Vec<i32>,
+    pub col_indices: Vec<i32>,
+    pub edge_weights: Vec<f32>,
+    pub positions_x: Vec<f32>,
+    pub positions_y: Vec<f32>,
+    pub positions_z: Vec<f32>,
+}
+
+impl WorkspaceGraphUpload {
+    /// Undirected CSR over the graph's nodes; edges to unknown nodes are dropped.
+    pub fn from_graph(graph: &GraphData) -> Self {
+        let index: HashMap<u32, usize> = graph
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (n.id, i))
+            .collect();
+
+        let mut adjacency: Vec<Vec<(i32, f32)>> = vec![Vec::new(); graph.nodes.len()];
+        for edge in &graph.edges {
+            if let (Some(&s), Some(&t)) = (index.get(&edge.source), index.get(&edge.target)) {
+                adjacency[s].push((t as i32, edge.weight));
+                if s != t {
+                    adjacency[t].push((s as i32, edge.weight));
+                }
+            }
+        }
+
+        let mut upload = Self {
+            node_ids: graph.nodes.iter().map(|n| n.id).collect(),
+            positions_x: graph.nodes.iter().map(|n| n.data.x).collect(),
+            positions_y: graph.nodes.iter().map(|n| n.data.y).collect(),
+            positions_z: graph.nodes.iter().map(|n| n.data.z).collect(),
+            row_offsets: Vec::with_capacity(graph.nodes.len() + 1),
+            ..Default::default()
+        };
+        for adj in &adjacency {
+            upload.row_offsets.push(upload.col_indices.len() as i32);
+            for &(target, weight) in adj {
+                upload.col_indices.push(target);
+                upload.edge_weights.push(weight);
+            }
+        }
+        upload.row_offsets.push(upload.col_indices.len() as i32);
+        upload
+    }
+
+    pub fn num_edges(&self) -> usize {
+        self.col_indices.len()
+    }
+}
+
+pub struct WorkspaceGpuContext {
+    pub simulation_params: SimulationParams,
+    /// `None` for the default workspace (it uses the shared context) and for
+    /// workspaces whose graph has not been uploaded yet.
+    pub compute: Option<Arc<Mutex<UnifiedGPUCompute>>>,
+    /// GPU index → graph node id for position readback.
+    pub node_ids: Vec<u32>,
+    pub metrics: WorkspaceGpuMetrics,
+}
+
+impl WorkspaceGpuContext {
+    fn new(workspace_id: &str, simulation_params: SimulationParams) -> Self {
+        Self {
+            simulation_params,
+            compute: None,
+            node_ids: Vec::new(),
+            metrics: WorkspaceGpuMetrics::new(workspace_id),
+        }
+    }
+}
+
+/// What the next `ComputeForces` tick should step.
+pub enum ScheduledStep {
+    /// The primary layout on the shared context.
+    Default,
+    Workspace {
+        workspace_id: String,
+        compute: Arc<Mutex<UnifiedGPUCompute>>,
+        params: SimulationParams,
+    },
+}
+
+pub struct WorkspaceGpuContexts {
+    contexts: HashMap<String, WorkspaceGpuContext>,
+    /// Round-robin order; the front is the next candidate.
+    schedule: VecDeque<String>,
+}
+
+impl WorkspaceGpuContexts {
+    pub fn new(default_params: SimulationParams) -> Self {
+        let mut contexts = HashMap::new();
+        contexts.insert(
+            DEFAULT_WORKSPACE_ID.to_string(),
+            WorkspaceGpuContext::new(DEFAULT_WORKSPACE_ID, default_params),
+        );
+        Self {
+            contexts,
+            schedule: VecDeque::from([DEFAULT_WORKSPACE_ID.to_string()]),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.contexts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.contexts.is_empty()
+    }
+
+    pub fn contains(&self, workspace_id: &str) -> bool {
+        self.contexts.contains_key(workspace_id)
+    }
+
+    /// Get or create the context for a workspace. New contexts start from
+    /// the default workspace's parameters.
+    pub fn ensure(&mut self, workspace_id: &str) -> &mut WorkspaceGpuContext {
+        if !self.contexts.contains_key(workspace_id) {
+            let params = self.default_params().clone();
+            self.contexts.insert(
+                workspace_id.to_string(),
+                WorkspaceGpuContext::new(workspace_id, params),
+            );
+            self.schedule.push_back(workspace_id.to_string());
+        }
+        self.contexts
+            .get_mut(workspace_id)
+            .expect("workspace context inserted above")
+    }
+
+    /// Drop a workspace's context and its GPU buffers. The default workspace
+    /// cannot be removed.
+    pub fn remove(&mut self, workspace_id: &str) -> bool {
+        if workspace_id == DEFAULT_WORKSPACE_ID {
+            return false;
+        }
+        self.schedule.retain(|id| id != workspace_id);
+        self.contexts.remove(workspace_id).is_some()
+    }
+
+    pub fn default_params(&self) -> &SimulationParams {
+        &self.contexts[DEFAULT_WORKSPACE_ID].simulation_params
+    }
+
+    pub fn params(&self, workspace_id: &str) -> Option<&SimulationParams> {
+        self.contexts.get(workspace_id).map(|c| &c.simulation_params)
+    }
+
+    pub fn set_params(&mut self, workspace_id: &str, params: SimulationParams) {
+        self.ensure(workspace_id).simulation_params = params;
+    }
+
+    pub fn attach_compute(
+        &mut self,
+        workspace_id: &str,
+        compute: Arc<Mutex<UnifiedGPUCompute>>,
+        upload: &WorkspaceGraphUpload,
+    ) {
+        let ctx = self.ensure(workspace_id);
+        ctx.compute = Some(compute);
+        ctx.node_ids = upload.node_ids.clone();
+        ctx.metrics.num_nodes = upload.node_ids.len();
+        ctx.metrics.num_edges = upload.num_edges();
+    }
+
+    pub fn get(&self, workspace_id: &str) -> Option<&WorkspaceGpuContext> {
+        self.contexts.get(workspace_id)
+    }
+
+    pub fn set_default_size(&mut self, num_nodes: usize, num_edges: usize) {
+        let ctx = self.ensure(DEFAULT_WORKSPACE_ID);
+        ctx.metrics.num_nodes = num_nodes;
+        ctx.metrics.num_edges = num_edges;
+    }
+
+    fn is_ready(&self, workspace_id: &str) -> bool {
+        if workspace_id == DEFAULT_WORKSPACE_ID {
+            return true;
+        }
+        self.contexts
+            .get(workspace_id)
+            .is_some_and(|c| c.compute.is_some() && !c.node_ids.is_empty())
+    }
+
+    /// Pick the next ready workspace and rotate it to the back of the queue.
+    /// Workspaces without an uploaded graph are passed over.
+    pub fn next_turn(&mut self) -> ScheduledStep {
+        for _ in 0..self.schedule.len() {
+            let Some(id) = self.schedule.pop_front() else {
+                break;
+            };
+            self.schedule.push_back(id.clone());
+            if !self.is_ready(&id) {
+                continue;
+            }
+            if id == DEFAULT_WORKSPACE_ID {
+                return ScheduledStep::Default;
+            }
+            let ctx = &self.contexts[&id];
+            if let Some(compute) = ctx.compute.clone() {
+                return ScheduledStep::Workspace {
+                    params: ctx.simulation_params.clone(),
+                    workspace_id: id,
+                    compute,
+                };
+            }
+        }
+        ScheduledStep::Default
+    }
+
+    pub fn record_step(&mut self, workspace_id: &str, duration_ms: f32, ok: bool) {
+        if let Some(ctx) = self.contexts.get_mut(workspace_id) {
+            ctx.metrics.record(duration_ms, ok);
+        }
+    }
+
+    /// Metrics for every workspace, default first then by id.
+    pub fn metrics(&self) -> Vec<WorkspaceGpuMetrics> {
+        let mut out: Vec<WorkspaceGpuMetrics> = self
+            .contexts
+            .iter()
+            .map(|(id, c)| WorkspaceGpuMetrics {
+                active: self.is_ready(id),
+                ..c.metrics.clone()
+            })
+            .collect();
+        out.sort_by(|a, b| {
+            (a.workspace_id != DEFAULT_WORKSPACE_ID, &a.workspace_id)
+                .cmp(&(b.workspace_id != DEFAULT_WORKSPACE_ID, &b.workspace_id))
+        });
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use visionclaw_domain::models::edge::Edge;
+    use visionclaw_domain::models::node::Node;
+
+    #[test]
+    fn params_are_isolated_per_workspace() {
+        let mut contexts = WorkspaceGpuContexts::new(SimulationParams::default());
+        let mut tuned = SimulationParams::default();
+        tuned.repel_k = 123.0;
+        contexts.set_params("research", tuned);
+
+        assert_eq!(contexts.params("research").unwrap().repel_k, 123.0);
+        assert_ne!(contexts.default_params().repel_k, 123.0);
+        assert!(!contexts.remove(DEFAULT_WORKSPACE_ID));
+        assert!(contexts.remove("research"));
+        assert_eq!(contexts.len(), 1);
+    }
+
+    #[test]
+    fn workspaces_without_graph_are_not_scheduled() {
+        let mut contexts = WorkspaceGpuContexts::new(SimulationParams::default());
+        contexts.ensure("empty");
+        for _ in 0..4 {
+            assert!(matches!(contexts.next_turn(), ScheduledStep::Default));
+        }
+        let active: Vec<(String, bool)> = contexts
+            .metrics()
+            .into_iter()
+            .map(|m| (m.workspace_id, m.active))
+            .collect();
+        assert_eq!(
+            active,
+            vec![
+                (DEFAULT_WORKSPACE_ID.to_string(), true),
+                ("empty".to_string(), false)
+            ]
+        );
+    }
+
+    #[test]
+    fn csr_upload_is_undirected_and_drops_dangling_edges() {
+        let mut graph = GraphData::new();
+        graph.nodes = vec![
+            Node::new_with_id("a".into(), Some(10)),
+            Node::new_with_id("b".into(), Some(20)),
+        ];
+        graph.edges = vec![Edge::new(10, 20, 2.0), Edge::new(10, 99, 1.0)];
+
+        let upload = WorkspaceGraphUpload::from_graph(&graph);
+        assert_eq!(upload.node_ids, vec![10, 20]);
+        assert_eq!(upload.row_offsets, vec![0, 1, 2]);
+        assert_eq!(upload.col_indices, vec![1, 0]);
+        assert_eq!(upload.edge_weights, vec![2.0, 2.0]);
+    }
+
+    #[test]
+    fn metrics_average_successful_steps() {
+        let mut m = WorkspaceGpuMetrics::new("w");
+        m.record(10.0, true);
+        m.record(20.0, true);
+        m.record(5.0, false);
+        assert_eq!(m.steps, 2);
+        assert_eq!(m.failed_steps, 1);
+        assert!((m.average_step_ms - 15.0).abs() < f32::EPSILON);
+    }
+}
@@ -0,0 +1,352 @@
+//! Invariant checks for the graph state held by `GraphStateActor`.
+//!
+//! `graph_data.nodes` is the source of truth (it carries live positions);
+//! `node_map`, `graph_data.id_to_metadata` and the node-type classification
+//! sets are derived indexes that every mutation has to keep in step. This
+//! module reports where they have drifted apart and can repair the drift.
+//! Debug builds run the check after every reload; `/api/admin/graph/consistency`
+//! exposes it on demand.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use visionclaw_domain::models::graph::GraphData;
+use visionclaw_domain::models::node::Node;
+
+/// Offending ids listed per check; counts are always exact.
+const MAX_SAMPLES: usize = 50;
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Finding<T> {
+    pub count: usize,
+    pub samples: Vec<T>,
+}
+
+impl<T> Finding<T> {
+    fn push(&mut self, item: T) {
+        self.count += 1;
+        if self.samples.len() < MAX_SAMPLES {
+            self.samples.push(item);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsistencyReport {
+    pub node_count: usize,
+    pub edge_count: usize,
+    pub node_map_count: usize,
+    /// Ids appearing more than once in `graph.nodes`.
+    pub duplicate_node_ids: Finding<u32>,
+    /// Nodes with no `node_map` entry.
+    pub missing_from_node_map: Finding<u32>,
+    /// `node_map` entries with no node.
+    pub stale_node_map_entries: Finding<u32>,
+    /// `node_map` entries whose `metadata_id` differs from the node's.
+    pub mismatched_node_map_entries: Finding<u32>,
+    /// Edge ids whose source or target is not a node.
+    pub orphan_edges: Finding<String>,
+    pub duplicate_edge_ids: Finding<String>,
+    /// `id_to_metadata` keys that are not a node id or disagree with it.
+    pub dangling_metadata_index: Finding<String>,
+    /// Ids in the node-type classification sets that are not nodes.
+    pub stale_type_ids: Finding<u32>,
+}
+
+impl ConsistencyReport {
+    pub fn is_consistent(&self) -> bool {
+        self.duplicate_node_ids.is_empty()
+            && self.missing_from_node_map.is_empty()
+            && self.stale_node_map_entries.is_empty()
+            && self.mismatched_node_map_entries.is_empty()
+            && self.orphan_edges.is_empty()
+            && self.duplicate_edge_ids.is_empty()
+            && self.dangling_metadata_index.is_empty()
+            && self.stale_type_ids.is_empty()
+    }
+
+    /// One-line summary of the failing checks, for logs.
+    pub fn summary(&self) -> String {
+        let checks = [
+            ("duplicate nodes", self.duplicate_node_ids.count),
+            ("missing from node_map", self.missing_from_node_map.count),
+            ("stale node_map", self.stale_node_map_entries.count),
+            (
+                "mismatched node_map",
+                self.mismatched_node_map_entries.count,
+            ),
+            ("orphan edges", self.orphan_edges.count),
+            ("duplicate edges", self.duplicate_edge_ids.count),
+            (
+                "dangling metadata index",
+                self.dangling_metadata_index.count,
+            ),
+            ("stale type ids", self.stale_type_ids.count),
+        ];
+        let failing: Vec<String> = checks
+            .iter()
+            .filter(|(_, n)| *n > 0)
+            .map(|(name, n)| format!("{} {}", n, name))
+            .collect();
+        if failing.is_empty() {
+            "consistent".to_string()
+        } else {
+            failing.join(", ")
+        }
+    }
+}
+
+/// Which classes of drift [`repair`] should fix. All default to off so a
+/// bare request only reports.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RepairOptions {
+    /// Keep the first node for each duplicated id.
+    pub dedupe_nodes: bool,
+    /// Rebuild `node_map` from `graph.nodes`.
+    pub rebuild_node_map: bool,
+    /// Drop orphan edges and all but the first edge per id.
+    pub drop_orphan_edges: bool,
+    /// Drop dangling `id_to_metadata` entries.
+    pub prune_metadata_index: bool,
+    /// Recompute the node-type classification sets.
+    pub reclassify: bool,
+}
+
+impl RepairOptions {
+    pub fn all() -> Self {
+        Self {
+            dedupe_nodes: true,
+            rebuild_node_map: true,
+            drop_orphan_edges: true,
+            prune_metadata_index: true,
+            reclassify: true,
+        }
+    }
+
+    pub fn any(&self) -> bool {
+        self.dedupe_nodes
+            || self.rebuild_node_map
+            || self.drop_orphan_edges
+            || self.prune_metadata_index
+            || self.reclassify
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepairSummary {
+    pub nodes_removed: usize,
+    pub node_map_rebuilt: bool,
+    pub edges_removed: usize,
+    pub metadata_entries_removed: usize,
+}
+
+pub fn check(
+    graph: &GraphData,
+    node_map: &HashMap<u32, Node>,
+    type_sets: &[&HashSet<u32>],
+) -> ConsistencyReport {
+    let mut report = ConsistencyReport {
+        node_count: graph.nodes.len(),
+        edge_count: graph.edges.len(),
+        node_map_count: node_map.len(),
+        ..Default::default()
+    };
+
+    let mut nodes: HashMap<u32, &Node> = HashMap::with_capacity(graph.nodes.len());
+    for node in &graph.nodes {
+        if nodes.insert(node.id, node).is_some() {
+            report.duplicate_node_ids.push(node.id);
+        }
+    }
+
+    let mut ids: Vec<u32> = nodes.keys().copied().collect();
+    ids.sort_unstable();
+    for id in &ids {
+        match node_map.get(id) {
+            None => report.missing_from_node_map.push(*id),
+            Some(entry) if entry.metadata_id != nodes[id].metadata_id => {
+                report.mismatched_node_map_entries.push(*id)
+            }
+            Some(_) => {}
+        }
+    }
+    let mut stale: Vec<u32> = node_map
+        .keys()
+        .filter(|id| !nodes.contains_key(id))
+        .copied()
+        .collect();
+    stale.sort_unstable();
+    stale
+        .into_iter()
+        .for_each(|id| report.stale_node_map_entries.push(id));
+
+    let mut edge_ids = HashSet::with_capacity(graph.edges.len());
+    for edge in &graph.edges {
+        if !nodes.contains_key(&edge.source) || !nodes.contains_key(&edge.target) {
+            report.orphan_edges.push(edge.id.clone());
+        }
+        if !edge_ids.insert(edge.id.as_str()) {
+            report.duplicate_edge_ids.push(edge.id.clone());
+        }
+    }
+
+    let mut keys: Vec<&String> = graph.id_to_metadata.keys().collect();
+    keys.sort_unstable();
+    for key in keys {
+        let matches = key
+            .parse::<u32>()
+            .ok()
+            .and_then(|id| nodes.get(&id))
+            .is_some_and(|node| node.metadata_id == graph.id_to_metadata[key]);
+        if !matches {
+            report.dangling_metadata_index.push(key.clone());
+        }
+    }
+
+    let mut stale_types: Vec<u32> = type_sets
+        .iter()
+        .flat_map(|set| set.iter())
+        .filter(|id| !nodes.contains_key(id))
+        .copied()
+        .collect();
+    stale_types.sort_unstable();
+    stale_types.dedup();
+    stale_types
+        .into_iter()
+        .for_each(|id| report.stale_type_ids.push(id));
+
+    report
+}
+
+/// Apply the requested fixes. `graph.nodes` wins every disagreement.
+/// Reclassification needs actor state and is left to the caller.
+pub fn repair(
+    graph: &mut GraphData,
+    node_map: &mut HashMap<u32, Node>,
+    options: RepairOptions,
+) -> RepairSummary {
+    let mut summary = RepairSummary::default();
+
+    if options.dedupe_nodes {
+        let before = graph.nodes.len();
+        let mut seen = HashSet::with_capacity(before);
+        graph.nodes.retain(|n| seen.insert(n.id));
+        summary.nodes_removed = before - graph.nodes.len();
+    }
+
+    if options.rebuild_node_map {
+        *node_map = graph.nodes.iter().map(|n| (n.id, n.clone())).collect();
+        summary.node_map_rebuilt = true;
+    }
+
+    let node_ids: HashSet<u32> = graph.nodes.iter().map(|n| n.id).collect();
+
+    if options.drop_orphan_edges {
+        let before = graph.edges.len();
+        let mut seen = HashSet::with_capacity(before);
+        graph.edges.retain(|e| {
+            node_ids.contains(&e.source)
+                && node_ids.contains(&e.target)
+                && seen.insert(e.id.clone())
+        });
+        summary.edges_removed = before - graph.edges.len();
+    }
+
+    if options.prune_metadata_index {
+        let metadata_ids: HashMap<u32, &str> = graph
+            .nodes
+            .iter()
+            .map(|n| (n.id, n.metadata_id.as_str()))
+            .collect();
+        let before = graph.id_to_metadata.len();
+        graph.id_to_metadata.retain(|key, value| {
+            key.parse::<u32>()
+                .ok()
+                .and_then(|id| metadata_ids.get(&id))
+                .is_some_and(|m| *m == value.as_str())
+        });
+        summary.metadata_entries_removed = before - graph.id_to_metadata.len();
+    }
+
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use visionclaw_domain::models::edge::Edge;
+
+    fn node(id: u32, metadata_id: &str) -> Node {
+        Node::new_with_id(metadata_id.to_string(), Some(id))
+    }
+
+    fn edge(id: &str, source: u32, target: u32) -> Edge {
+        let mut e = Edge::new(source, target, 1.0);
+        e.id = id.to_string();
+        e
+    }
+
+    fn drifted() -> (GraphData, HashMap<u32, Node>) {
+        let mut graph = GraphData::new();
+        graph.nodes = vec![node(1, "a"), node(2, "b"), node(2, "b-dup"), node(3, "c")];
+        graph.edges = vec![edge("e1", 1, 2), edge("e1", 1, 3), edge("e2", 3, 9)];
+        graph
+            .id_to_metadata
+            .insert("1".to_string(), "a".to_string());
+        graph
+            .id_to_metadata
+            .insert("7".to_string(), "gone".to_string());
+        let node_map = [
+            (1, node(1, "a")),
+            (3, node(3, "renamed")),
+            (8, node(8, "x")),
+        ]
+        .into_iter()
+        .collect();
+        (graph, node_map)
+    }
+
+    #[test]
+    fn reports_every_kind_of_drift() {
+        let (graph, node_map) = drifted();
+        let types: HashSet<u32> = [1, 5].into_iter().collect();
+        let report = check(&graph, &node_map, &[&types]);
+
+        assert!(!report.is_consistent());
+        assert_eq!(report.duplicate_node_ids.samples, vec![2]);
+        assert_eq!(report.missing_from_node_map.samples, vec![2]);
+        assert_eq!(report.mismatched_node_map_entries.samples, vec![3]);
+        assert_eq!(report.stale_node_map_entries.samples, vec![8]);
+        assert_eq!(report.orphan_edges.samples, vec!["e2".to_string()]);
+        assert_eq!(report.duplicate_edge_ids.samples, vec!["e1".to_string()]);
+        assert_eq!(
+            report.dangling_metadata_index.samples,
+            vec!["7".to_string()]
+        );
+        assert_eq!(report.stale_type_ids.samples, vec![5]);
+    }
+
+    #[test]
+    fn repair_restores_consistency() {
+        let (mut graph, mut node_map) = drifted();
+        let summary = repair(&mut graph, &mut node_map, RepairOptions::all());
+
+        assert_eq!(summary.nodes_removed, 1);
+        assert_eq!(summary.edges_removed, 2);
+        assert_eq!(summary.metadata_entries_removed, 1);
+        assert_eq!(node_map[&2].metadata_id, "b", "first duplicate wins");
+        assert!(check(&graph, &node_map, &[]).is_consistent());
+
+        let (mut graph, mut node_map) = drifted();
+        let untouched = repair(&mut graph, &mut node_map, RepairOptions::default());
+        assert!(!untouched.node_map_rebuilt);
+        assert_eq!(graph.nodes.len(), 4);
+    }
+}
@@ -538,6 +538,17 @@ impl GraphServiceSupervisor {
         }
     }
 
+    /// Wire graph state and client coordinator together for graph diff broadcasts
+    fn wire_graph_state_and_client(&mut self) {
+        if let (Some(ref graph_state_addr), Some(ref client_addr)) = (&self.graph_state, &self.client) {
+            use crate::actors::SetClientCoordinator;
+            graph_state_addr.do_send(SetClientCoordinator {
+                addr: client_addr.clone(),
+            });
+            info!("Wired GraphState and ClientCoordinator for graph diff broadcasting");
+        }
+    }
+
 
     fn initialize_actors(&mut self, ctx: &mut Context<Self>) {
         info!("Initializing supervised actors");
@@ -683,6 +694,9 @@ impl GraphServiceSupervisor {
         if actor_type == ActorType::ClientCoordinator || actor_type == ActorType::PhysicsOrchestrator {
             self.wire_physics_and_client();
         }
+        if actor_type == ActorType::ClientCoordinator || actor_type == ActorType::GraphState {
+            self.wire_graph_state_and_client();
+        }
 
 
         if let Some(info) = self.actor_info.get_mut(&actor_type) {
@@ -1558,6 +1572,25 @@ impl Handler<msgs::ComputeShortestPaths> for GraphServiceSupervisor {
     }
 }
 
+/// Handler for CheckGraphConsistency - delegates to GraphStateActor
+impl Handler<msgs::CheckGraphConsistency> for GraphServiceSupervisor {
+    type Result = ResponseFuture<Result<msgs::CheckGraphConsistencyResult, String>>;
+
+    fn handle(&mut self, msg: msgs::CheckGraphConsistency, _ctx: &mut Self::Context) -> Self::Result {
+        if let Some(ref graph_state_addr) = self.graph_state {
+            let addr = graph_state_addr.clone();
+            Box::pin(async move {
+                addr.send(msg).await.unwrap_or_else(|e| {
+                    error!("Failed to forward CheckGraphConsistency to GraphStateActor: {}", e);
+                    Err(format!("Message forwarding failed: {}", e))
+                })
+            })
+        } else {
+            Box::pin(async { Err("GraphStateActor not initialized".to_string()) })
+        }
+    }
+}
+
 impl Handler<msgs::UpdateGraphData> for GraphServiceSupervisor {
     type Result = ResponseActFuture<Self, Result<(), String>>;
 
@@ -1602,6 +1635,50 @@ impl Handler<msgs::AddNodesFromMetadata> for GraphServiceSupervisor {
     }
 }
 
+impl Handler<msgs::UpdateNodeFromMetadata> for GraphServiceSupervisor {
+    type Result = ResponseFuture<Result<(), String>>;
+
+    fn handle(
+        &mut self,
+        msg: msgs::UpdateNodeFromMetadata,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        if let Some(ref graph_state_addr) = self.graph_state {
+            let addr = graph_state_addr.clone();
+            Box::pin(async move {
+                addr.send(msg).await.unwrap_or_else(|e| {
+                    error!("Failed to forward UpdateNodeFromMetadata to GraphStateActor: {}", e);
+                    Err(format!("Message forwarding failed: {}", e))
+                })
+            })
+        } else {
+            Box::pin(async { Err("GraphStateActor not initialized".to_string()) })
+        }
+    }
+}
+
+impl Handler<msgs::SetNodeMetadataFlag> for GraphServiceSupervisor {
+    type Result = ResponseFuture<Result<usize, String>>;
+
+    fn handle(
+        &mut self,
+        msg: msgs::SetNodeMetadataFlag,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        if let Some(ref graph_state_addr) = self.graph_state {
+            let addr = graph_state_addr.clone();
+            Box::pin(async move {
+                addr.send(msg).await.unwrap_or_else(|e| {
+                    error!("Failed to forward SetNodeMetadataFlag to GraphStateActor: {}", e);
+                    Err(format!("Message forwarding failed: {}", e))
+                })
+            })
+        } else {
+            Box::pin(async { Err("GraphStateActor not initialized".to_string()) })
+        }
+    }
+}
+
 // Removed UpdateNodePosition handler from graph_messages - GraphServiceActor doesn't implement it
 
 // Additional commonly used messages
@@ -1724,6 +1801,54 @@ impl Handler<msgs::ForceResumePhysics> for GraphServiceSupervisor {
     }
 }
 
+impl Handler<msgs::ControlSimulation> for GraphServiceSupervisor {
+    type Result = ResponseActFuture<
+        Self,
+        Result<crate::physics::simulation_control::SimulationControlStatus, String>,
+    >;
+
+    fn handle(&mut self, msg: msgs::ControlSimulation, _ctx: &mut Self::Context) -> Self::Result {
+        if let Some(ref physics_addr) = self.physics {
+            let addr = physics_addr.clone();
+            Box::pin(
+                async move {
+                    addr.send(msg).await.unwrap_or_else(|e| {
+                        error!("Failed to forward ControlSimulation to PhysicsOrchestratorActor: {}", e);
+                        Err(format!("Message forwarding failed: {}", e))
+                    })
+                }
+                .into_actor(self),
+            )
+        } else {
+            warn!("ControlSimulation: PhysicsOrchestratorActor not initialized");
+            Box::pin(actix::fut::ready(Err("Physics actor not initialized".to_string())))
+        }
+    }
+}
+
+impl Handler<msgs::SetSimulationQuality> for GraphServiceSupervisor {
+    type Result =
+        ResponseActFuture<Self, Result<crate::physics::quality::QualityPreset, String>>;
+
+    fn handle(&mut self, msg: msgs::SetSimulationQuality, _ctx: &mut Self::Context) -> Self::Result {
+        if let Some(ref physics_addr) = self.physics {
+            let addr = physics_addr.clone();
+            Box::pin(
+                async move {
+                    addr.send(msg).await.unwrap_or_else(|e| {
+                        error!("Failed to forward SetSimulationQuality to PhysicsOrchestratorActor: {}", e);
+                        Err(format!("Message forwarding failed: {}", e))
+                    })
+                }
+                .into_actor(self),
+            )
+        } else {
+            warn!("SetSimulationQuality: PhysicsOrchestratorActor not initialized");
+            Box::pin(actix::fut::ready(Err("Physics actor not initialized".to_string())))
+        }
+    }
+}
+
 impl Handler<msgs::InitializeGPUConnection> for GraphServiceSupervisor {
     type Result = ();
 
@@ -2009,3 +2134,39 @@ impl Handler<msgs::AddEdge> for GraphServiceSupervisor {
         }
     }
 }
+
+impl Handler<msgs::EdgeWeightsUpdated> for GraphServiceSupervisor {
+    type Result = ResponseFuture<Result<crate::physics::edge_weights::EdgeWeightReport, String>>;
+
+    fn handle(&mut self, msg: msgs::EdgeWeightsUpdated, _ctx: &mut Self::Context) -> Self::Result {
+        if let Some(ref graph_state_addr) = self.graph_state {
+            let addr = graph_state_addr.clone();
+            Box::pin(async move {
+                addr.send(msg).await.unwrap_or_else(|e| {
+                    error!("Failed to forward EdgeWeightsUpdated to GraphStateActor: {}", e);
+                    Err(format!("Message forwarding failed: {}", e))
+                })
+            })
+        } else {
+            Box::pin(async { Err("GraphStateActor not initialized".to_string()) })
+        }
+    }
+}
+
+impl Handler<msgs::AddEphemeralNode> for GraphServiceSupervisor {
+    type Result = ResponseFuture<Result<u32, String>>;
+
+    fn handle(&mut self, msg: msgs::AddEphemeralNode, _ctx: &mut Self::Context) -> Self::Result {
+        if let Some(ref graph_state_addr) = self.graph_state {
+            let addr = graph_state_addr.clone();
+            Box::pin(async move {
+                addr.send(msg).await.unwrap_or_else(|e| {
+                    error!("Failed to forward AddEphemeralNode to GraphStateActor: {}", e);
+                    Err(format!("Message forwarding failed: {}", e))
+                })
+            })
+        } else {
+            Box::pin(async { Err("GraphStateActor not initialized".to_string()) })
+        }
+    }
+}
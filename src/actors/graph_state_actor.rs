@@ -21,6 +21,7 @@
 //! - **AddNode**: Add new nodes to the graph with proper ID management
 //! - **RemoveNode**: Remove nodes and clean up associated edges
 //! - **UpdateNodeFromMetadata**: Update existing nodes based on metadata changes
+//! - **AddEphemeralNode**: Add an unpersisted node that expires after a TTL
 //!
 //! ### 3. Edge Operations (via Repository)
 //! - **AddEdge**: Create connections between nodes
@@ -52,13 +53,30 @@
 use actix::prelude::*;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use log::{debug, info, warn, error};
+use std::time::{Duration, Instant};
+use log::{debug, info, trace, warn, error};
 
 use crate::actors::messages::*;
 use visionclaw_domain::models::node::Node;
-use visionclaw_domain::models::edge::Edge;
+use visionclaw_domain::models::edge::{Edge, EdgeProvenance};
 use visionclaw_domain::models::metadata::{MetadataStore, FileMetadata};
 use visionclaw_domain::models::graph::GraphData;
+use crate::services::webhook_service::{self, GraphEvent};
+use crate::services::audio_cue_service::{AudioCue, MAX_CUES_PER_MESSAGE, NODE_APPEAR};
+use crate::services::camera_recording_service::FramePosition;
+use crate::services::layout_history_service::{layout_history, KEYFRAME_INTERVAL};
+use crate::services::diff_journal_service::diff_journal;
+use crate::services::language_detection::NODE_LANGUAGE_KEY;
+use crate::actors::client_coordinator_actor::ClientCoordinatorActor;
+use crate::actors::graph_consistency::{self, ConsistencyReport, RepairOptions, RepairSummary};
+use crate::actors::SetClientCoordinator;
+use crate::utils::expiry_wheel::ExpiryWheel;
+
+/// Resolution of the ephemeral-node expiry wheel.
+const EPHEMERAL_TICK: Duration = Duration::from_secs(1);
+/// One rotation covers ten minutes; longer TTLs wrap round the wheel.
+const EPHEMERAL_SLOTS: usize = 600;
+const MAX_EPHEMERAL_TTL_SECS: u64 = 24 * 60 * 60;
 
 // Ports (hexagonal architecture)
 use crate::ports::knowledge_graph_repository::KnowledgeGraphRepository;
@@ -99,6 +117,12 @@ pub struct GraphStateActor {
     /// Monotonic epoch incremented on every `UpdateNodePositions` apply.
     /// Broadcast actor uses this to short-circuit redundant encodes.
     position_epoch: u64,
+
+    /// TTL schedule for ephemeral nodes (search markers, chat-answer ghosts).
+    ephemeral: ExpiryWheel<u32>,
+
+    /// Used to push graph diffs (ephemeral add/expire) to connected clients.
+    client_coordinator: Option<Addr<ClientCoordinatorActor>>,
 }
 
 impl GraphStateActor {
@@ -120,6 +144,8 @@ impl GraphStateActor {
             compact_to_persistent: Vec::new(),
             position_snapshot: Arc::new(crate::actors::messages::PositionFrameSnapshot::default()),
             position_epoch: 0,
+            ephemeral: ExpiryWheel::new(EPHEMERAL_TICK, EPHEMERAL_SLOTS, Instant::now()),
+            client_coordinator: None,
         }
     }
 
@@ -221,6 +247,8 @@ impl GraphStateActor {
             new_node_map.insert(node.id, node.clone());
         }
         self.node_map = Arc::new(new_node_map);
+        // Every id a client holds may now name another node.
+        diff_journal().reset();
 
         info!(
             "Remapped {} nodes to compact IDs 0..{} (edges: {})",
@@ -342,6 +370,14 @@ impl GraphStateActor {
             self.compact_to_persistent.resize(node_id as usize + 1, 0);
         }
         self.compact_to_persistent[node_id as usize] = node_id;
+        // Only ephemeral changes go out as diffs; resuming sessions reload.
+        diff_journal().reset();
+
+        webhook_service::emit(GraphEvent::NodeCreated {
+            node_id,
+            metadata_id: node.metadata_id.clone(),
+            label: node.label.clone(),
+        });
 
         // Persist to Oxigraph store (fire-and-forget)
         let repository = Arc::clone(&self.repository);
@@ -368,6 +404,13 @@ impl GraphStateActor {
             self.ontology_individual_ids.remove(&node_id);
             self.ontology_property_ids.remove(&node_id);
             self.agent_node_ids.remove(&node_id);
+            diff_journal().reset();
+
+            // Ephemeral nodes were never persisted
+            if self.ephemeral.cancel(node_id) {
+                debug!("Removed ephemeral node {} before expiry", node_id);
+                return;
+            }
 
             // Persist removal to Oxigraph (fire-and-forget)
             let repository = Arc::clone(&self.repository);
@@ -383,8 +426,194 @@ impl GraphStateActor {
         }
     }
 
+    /// Add an unpersisted node and schedule its removal after `ttl_secs`.
+    fn add_ephemeral_node(&mut self, mut node: Node, ttl_secs: u64) -> Result<u32, String> {
+        if ttl_secs == 0 || ttl_secs > MAX_EPHEMERAL_TTL_SECS {
+            return Err(format!(
+                "ttl_secs must be between 1 and {}",
+                MAX_EPHEMERAL_TTL_SECS
+            ));
+        }
+        // Take ids from the shared counter so an expired ephemeral id is never
+        // handed out again while clients may still hold positions for it.
+        let node_id = loop {
+            let id = self
+                .next_node_id
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if !self.node_map.contains_key(&id) {
+                break id;
+            }
+        };
+        node.id = node_id;
+        node.data.node_id = node_id;
+        let expires_at = chrono::Utc::now() + chrono::Duration::seconds(ttl_secs as i64);
+        node.metadata.insert("ephemeral".to_string(), "true".to_string());
+        node.metadata.insert("expiresAt".to_string(), expires_at.to_rfc3339());
+
+        self.classify_node(&node);
+        Arc::make_mut(&mut self.node_map).insert(node_id, node.clone());
+        Arc::make_mut(&mut self.graph_data).nodes.push(node.clone());
+        if self.compact_to_persistent.len() <= node_id as usize {
+            self.compact_to_persistent.resize(node_id as usize + 1, 0);
+        }
+        self.compact_to_persistent[node_id as usize] = node_id;
+
+        self.ephemeral
+            .insert(node_id, Instant::now() + Duration::from_secs(ttl_secs));
+        self.cue_node_appear(&[&node]);
+        self.broadcast_diff(serde_json::json!({
+            "type": "graphDiff",
+            "reason": "ephemeral_added",
+            "added": [node],
+            "removed": [],
+        }));
+        debug!("Added ephemeral node {} (ttl {}s)", node_id, ttl_secs);
+        Ok(node_id)
+    }
+
+    /// Drop ephemeral nodes whose TTL has elapsed and tell clients.
+    fn expire_ephemeral_nodes(&mut self) {
+        let due = self.ephemeral.advance(Instant::now());
+        if due.is_empty() {
+            return;
+        }
+        // A graph reload can reuse an expired id for a real node; only
+        // remove nodes that are still flagged ephemeral.
+        let removed: HashSet<u32> = due
+            .into_iter()
+            .filter(|id| {
+                self.node_map
+                    .get(id)
+                    .is_some_and(|n| n.metadata.get("ephemeral").map(String::as_str) == Some("true"))
+            })
+            .collect();
+        if removed.is_empty() {
+            return;
+        }
+
+        Arc::make_mut(&mut self.node_map).retain(|id, _| !removed.contains(id));
+        let graph_data = Arc::make_mut(&mut self.graph_data);
+        graph_data.nodes.retain(|n| !removed.contains(&n.id));
+        graph_data
+            .edges
+            .retain(|e| !removed.contains(&e.source) && !removed.contains(&e.target));
+        for id in &removed {
+            self.knowledge_node_ids.remove(id);
+            self.ontology_class_ids.remove(id);
+            self.ontology_individual_ids.remove(id);
+            self.ontology_property_ids.remove(id);
+            self.agent_node_ids.remove(id);
+        }
+
+        let mut removed: Vec<u32> = removed.into_iter().collect();
+        removed.sort_unstable();
+        debug!("Expired {} ephemeral node(s)", removed.len());
+        self.broadcast_diff(serde_json::json!({
+            "type": "graphDiff",
+            "reason": "ephemeral_expired",
+            "added": [],
+            "removed": removed,
+        }));
+    }
+
+    /// Sample current positions into the layout history ring.
+    fn record_layout_keyframe(&self) {
+        let history = layout_history();
+        let now = chrono::Utc::now().timestamp_millis();
+        if self.graph_data.nodes.is_empty() || !history.is_due(now) {
+            return;
+        }
+        let nodes = self
+            .graph_data
+            .nodes
+            .iter()
+            .map(|n| FramePosition {
+                id: n.id,
+                x: n.data.x,
+                y: n.data.y,
+                z: n.data.z,
+            })
+            .collect();
+        history.record(now, nodes);
+    }
+
+    fn consistency_report(&self) -> ConsistencyReport {
+        graph_consistency::check(
+            &self.graph_data,
+            &self.node_map,
+            &[
+                &self.knowledge_node_ids,
+                &self.ontology_class_ids,
+                &self.ontology_individual_ids,
+                &self.ontology_property_ids,
+                &self.agent_node_ids,
+            ],
+        )
+    }
+
+    /// Debug builds verify the node indexes after bulk changes so drift is
+    /// caught where it is introduced rather than when a client renders it.
+    #[cfg(debug_assertions)]
+    fn debug_check_consistency(&self, context: &str) {
+        let report = self.consistency_report();
+        if !report.is_consistent() {
+            warn!(
+                "GraphStateActor: graph indexes inconsistent after {}: {}",
+                context,
+                report.summary()
+            );
+        }
+    }
+
+    #[cfg(not(debug_assertions))]
+    fn debug_check_consistency(&self, _context: &str) {}
+
+    fn repair_consistency(&mut self, options: RepairOptions) -> RepairSummary {
+        let summary = graph_consistency::repair(
+            Arc::make_mut(&mut self.graph_data),
+            Arc::make_mut(&mut self.node_map),
+            options,
+        );
+        if options.reclassify || summary.nodes_removed > 0 {
+            self.reclassify_all_nodes();
+        }
+        if summary.nodes_removed > 0 {
+            self.rebuild_position_snapshot();
+        }
+        summary
+    }
+
+    /// Play the `nodeAppear` cue at each node for users who want audio cues.
+    fn cue_node_appear(&self, nodes: &[&Node]) {
+        if nodes.is_empty() {
+            return;
+        }
+        if let Some(ref addr) = self.client_coordinator {
+            let cues = nodes
+                .iter()
+                .take(MAX_CUES_PER_MESSAGE)
+                .map(|n| AudioCue::new(NODE_APPEAR, n.id, [n.data.x, n.data.y, n.data.z]))
+                .collect();
+            addr.do_send(BroadcastAudioCues {
+                cues,
+                recipient: None,
+            });
+        }
+    }
+
+    /// Stamp `diff` with the next graph revision, journal it for resuming
+    /// sessions and send it to every client.
+    fn broadcast_diff(&self, diff: serde_json::Value) {
+        let diff = diff_journal().record(diff);
+        if let Some(ref addr) = self.client_coordinator {
+            addr.do_send(BroadcastMessage {
+                message: diff.to_string(),
+            });
+        }
+    }
+
     
-    fn add_edge(&mut self, edge: Edge) {
+    fn add_edge(&mut self, mut edge: Edge) {
 
         if !self.node_map.contains_key(&edge.source) {
             warn!("Cannot add edge: source node {} does not exist", edge.source);
@@ -396,7 +625,17 @@ impl GraphStateActor {
         }
 
 
+        // Callers that don't say where an edge came from get a best guess
+        // from its id and type, so every live edge carries provenance.
+        if edge.provenance.is_none() {
+            edge.provenance = Some(EdgeProvenance::reconstruct(
+                &edge.id,
+                edge.edge_type.as_deref(),
+                edge.weight,
+            ));
+        }
         Arc::make_mut(&mut self.graph_data).edges.push(edge.clone());
+        diff_journal().reset();
 
         // Persist to Oxigraph (fire-and-forget)
         let repository = Arc::clone(&self.repository);
@@ -419,6 +658,7 @@ impl GraphStateActor {
 
         let removed_count = initial_count - graph_data_mut.edges.len();
         if removed_count > 0 {
+            diff_journal().reset();
             // Persist to Oxigraph (fire-and-forget)
             let repository = Arc::clone(&self.repository);
             let edge_id_owned = edge_id.to_string();
@@ -434,6 +674,51 @@ impl GraphStateActor {
         }
     }
 
+    /// Patch edge weights in place; nodes and edge topology are untouched.
+    fn update_edge_weights(
+        &mut self,
+        updates: &[crate::physics::edge_weights::EdgeWeightUpdate],
+    ) -> Result<crate::physics::edge_weights::EdgeWeightReport, String> {
+        use crate::physics::edge_weights::{apply_to_edges, collect_updates};
+
+        let weights = collect_updates(updates)?;
+        let graph_data_mut = Arc::make_mut(&mut self.graph_data);
+        let (changed, report) = apply_to_edges(&mut graph_data_mut.edges, &weights);
+
+        if !changed.is_empty() {
+            // Persist to Oxigraph (fire-and-forget), translating compact ids
+            // back to persistent ones like other write-backs.
+            let persistent = |id: u32| {
+                self.compact_to_persistent.get(id as usize).copied().unwrap_or(id)
+            };
+            let changed_edges: Vec<Edge> = changed
+                .iter()
+                .map(|&i| {
+                    let mut edge = graph_data_mut.edges[i].clone();
+                    edge.source = persistent(edge.source);
+                    edge.target = persistent(edge.target);
+                    edge
+                })
+                .collect();
+            let repository = Arc::clone(&self.repository);
+            actix::spawn(async move {
+                for edge in &changed_edges {
+                    if let Err(e) = repository.update_edge(edge).await {
+                        error!("Failed to persist weight of edge {} to Oxigraph: {}", edge.id, e);
+                    }
+                }
+            });
+        }
+
+        debug!(
+            "Updated weights of {} node pair(s) ({} edges changed, {} unknown pairs)",
+            report.updated,
+            changed.len(),
+            report.unknown
+        );
+        Ok(report)
+    }
+
     
     fn build_from_metadata(&mut self, metadata: MetadataStore) -> Result<(), String> {
         let mut new_graph_data = GraphData::new();
@@ -475,6 +760,7 @@ impl GraphStateActor {
         // OxigraphOntologyRepository). No client-side edge generation.
 
         self.graph_data = Arc::new(new_graph_data);
+        diff_journal().reset();
         self.next_node_id.store(compact_id, std::sync::atomic::Ordering::SeqCst);
         self.metadata_store = metadata.clone();
 
@@ -537,6 +823,7 @@ impl GraphStateActor {
     fn configure_node_from_metadata(&self, node: &mut Node, metadata: &FileMetadata) {
 
         node.label = metadata.file_name.clone();
+        crate::utils::transliteration::prepare_label(node);
 
 
         let path = std::path::Path::new(&metadata.file_name);
@@ -566,6 +853,20 @@ impl GraphStateActor {
         if let Some(authority) = metadata.authority_score {
             node.metadata.insert("authority_score".to_string(), authority.to_string());
         }
+        Self::set_node_language(node, metadata);
+    }
+
+    /// Copy the page's detected content language to node metadata for
+    /// per-language filters, dropping a stale one.
+    fn set_node_language(node: &mut Node, metadata: &FileMetadata) {
+        match metadata.language {
+            Some(ref language) => {
+                node.metadata.insert(NODE_LANGUAGE_KEY.to_string(), language.clone());
+            }
+            None => {
+                node.metadata.remove(NODE_LANGUAGE_KEY);
+            }
+        }
     }
 
     
@@ -588,6 +889,7 @@ impl GraphStateActor {
 
     fn add_nodes_from_metadata(&mut self, metadata: MetadataStore) -> Result<(), String> {
         let mut added_count = 0;
+        let mut added = Vec::new();
         let mut current_id = self.next_node_id.load(std::sync::atomic::Ordering::SeqCst);
 
         for (metadata_id, file_metadata) in metadata.iter() {
@@ -600,6 +902,9 @@ impl GraphStateActor {
             self.generate_random_position(&mut node);
             self.configure_node_from_metadata(&mut node, file_metadata);
 
+            if added.len() < MAX_CUES_PER_MESSAGE {
+                added.push(node.clone());
+            }
             self.add_node(node);
             current_id += 1;
             added_count += 1;
@@ -607,6 +912,7 @@ impl GraphStateActor {
 
         self.next_node_id.store(current_id, std::sync::atomic::Ordering::SeqCst);
         info!("Added {} new nodes from metadata", added_count);
+        self.cue_node_appear(&added.iter().collect::<Vec<_>>());
 
         // Merge new metadata into stored metadata for node configuration.
         for (id, meta) in metadata {
@@ -629,6 +935,7 @@ impl GraphStateActor {
                 if node.metadata_id == metadata_id {
                     // Inline configuration to avoid borrowing self
                     node.label = metadata.file_name.clone();
+                    crate::utils::transliteration::prepare_label(node);
                     let path = std::path::Path::new(&metadata.file_name);
                     node.color = Some(Self::color_for_extension(path));
                     let size = metadata.file_size;
@@ -636,6 +943,7 @@ impl GraphStateActor {
                     node.metadata.insert("file_name".to_string(), metadata.file_name.clone());
                     node.metadata.insert("file_size".to_string(), size.to_string());
                     node.metadata.insert("last_modified".to_string(), metadata.last_modified.to_string());
+                    Self::set_node_language(node, &metadata);
                     node_found = true;
                     break;
                 }
@@ -651,6 +959,7 @@ impl GraphStateActor {
                     if node.metadata_id == metadata_id {
                         // Inline configuration to avoid borrowing self
                         node.label = metadata.file_name.clone();
+                        crate::utils::transliteration::prepare_label(node);
                         let path = std::path::Path::new(&metadata.file_name);
                         node.color = Some(Self::color_for_extension(path));
                         let size = metadata.file_size;
@@ -658,10 +967,12 @@ impl GraphStateActor {
                         node.metadata.insert("file_name".to_string(), metadata.file_name.clone());
                         node.metadata.insert("file_size".to_string(), size.to_string());
                         node.metadata.insert("last_modified".to_string(), metadata.last_modified.to_string());
+                        Self::set_node_language(node, &metadata);
                         break;
                     }
                 }
             } // Release mutable borrow
+            diff_journal().reset();
             debug!("Updated node with metadata_id: {}", metadata_id);
             Ok(())
         } else {
@@ -771,7 +1082,7 @@ impl GraphStateActor {
 impl Actor for GraphStateActor {
     type Context = Context<Self>;
 
-    fn started(&mut self, _ctx: &mut Self::Context) {
+    fn started(&mut self, ctx: &mut Self::Context) {
         // Start with empty state. main.rs runs the Data Orchestration Sequence
         // (Step 2: load_graph_from_files → save_graph into Oxigraph, then
         // Step 3: send ReloadGraphFromDatabase to this actor). The eager
@@ -781,6 +1092,9 @@ impl Actor for GraphStateActor {
         // had loaded fresh state, overwriting it with stale/empty data.
         // Letting Step 3 do the single canonical load eliminates the race.
         info!("GraphStateActor started with empty state - waiting for ReloadGraphFromDatabase");
+
+        ctx.run_interval(EPHEMERAL_TICK, |act, _ctx| act.expire_ephemeral_nodes());
+        ctx.run_interval(KEYFRAME_INTERVAL, |act, _ctx| act.record_layout_keyframe());
     }
 
     fn stopped(&mut self, _ctx: &mut Self::Context) {
@@ -849,7 +1163,7 @@ impl Handler<UpdateNodePositions> for GraphStateActor {
         // from this single source.
         self.rebuild_position_snapshot();
 
-        debug!("GraphStateActor: Updated {} node positions from GPU", updated);
+        trace!("GraphStateActor: Updated {} node positions from GPU", updated);
         Ok(())
     }
 }
@@ -880,11 +1194,29 @@ impl Handler<AddNode> for GraphStateActor {
     type Result = Result<(), String>;
 
     fn handle(&mut self, msg: AddNode, _ctx: &mut Self::Context) -> Self::Result {
+        self.cue_node_appear(&[&msg.node]);
         self.add_node(msg.node);
         Ok(())
     }
 }
 
+impl Handler<AddEphemeralNode> for GraphStateActor {
+    type Result = Result<u32, String>;
+
+    fn handle(&mut self, msg: AddEphemeralNode, _ctx: &mut Self::Context) -> Self::Result {
+        self.add_ephemeral_node(msg.node, msg.ttl_secs)
+    }
+}
+
+impl Handler<SetClientCoordinator> for GraphStateActor {
+    type Result = ();
+
+    fn handle(&mut self, msg: SetClientCoordinator, _ctx: &mut Self::Context) -> Self::Result {
+        self.client_coordinator = Some(msg.addr);
+        debug!("Client coordinator address set for graph state actor");
+    }
+}
+
 impl Handler<RemoveNode> for GraphStateActor {
     type Result = Result<(), String>;
 
@@ -912,6 +1244,14 @@ impl Handler<RemoveEdge> for GraphStateActor {
     }
 }
 
+impl Handler<EdgeWeightsUpdated> for GraphStateActor {
+    type Result = Result<crate::physics::edge_weights::EdgeWeightReport, String>;
+
+    fn handle(&mut self, msg: EdgeWeightsUpdated, _ctx: &mut Self::Context) -> Self::Result {
+        self.update_edge_weights(&msg.updates)
+    }
+}
+
 impl Handler<GetNodeMap> for GraphStateActor {
     type Result = Result<Arc<HashMap<u32, Node>>, String>;
 
@@ -954,6 +1294,54 @@ impl Handler<RemoveNodeByMetadata> for GraphStateActor {
     }
 }
 
+impl Handler<SetNodeMetadataFlag> for GraphStateActor {
+    type Result = Result<usize, String>;
+
+    fn handle(&mut self, msg: SetNodeMetadataFlag, _ctx: &mut Self::Context) -> Self::Result {
+        // Whether the node's value changed.
+        let apply = |node: &mut Node| match msg.values.get(&node.metadata_id) {
+            Some(value) => {
+                node.metadata.insert(msg.key.clone(), value.clone()).as_ref() != Some(value)
+            }
+            None => node.metadata.remove(&msg.key).is_some(),
+        };
+
+        let mut flagged = 0;
+        let mut changed = Vec::new();
+        for node in Arc::make_mut(&mut self.node_map).values_mut() {
+            if apply(node) {
+                changed.push(node.id);
+            }
+            if msg.values.contains_key(&node.metadata_id) {
+                flagged += 1;
+            }
+        }
+        for node in &mut Arc::make_mut(&mut self.graph_data).nodes {
+            apply(node);
+        }
+        debug!(
+            "Set node metadata flag '{}' on {} node(s), {} changed",
+            msg.key,
+            flagged,
+            changed.len()
+        );
+
+        if !changed.is_empty() {
+            changed.sort_unstable();
+            let updated: Vec<&Node> =
+                changed.iter().filter_map(|id| self.node_map.get(id)).collect();
+            self.broadcast_diff(serde_json::json!({
+                "type": "graphDiff",
+                "reason": "metadata_updated",
+                "added": [],
+                "removed": [],
+                "updated": updated,
+            }));
+        }
+        Ok(flagged)
+    }
+}
+
 impl Handler<UpdateGraphData> for GraphStateActor {
     type Result = Result<(), String>;
 
@@ -962,6 +1350,7 @@ impl Handler<UpdateGraphData> for GraphStateActor {
               msg.graph_data.nodes.len(), msg.graph_data.edges.len());
 
         self.graph_data = msg.graph_data;
+        diff_journal().reset();
 
         Arc::make_mut(&mut self.node_map).clear();
         for node in &self.graph_data.nodes {
@@ -1130,6 +1519,7 @@ impl Handler<ReloadGraphFromDatabase> for GraphStateActor {
 
                         // Reclassify all nodes after reload (using compact IDs)
                         act.reclassify_all_nodes();
+                        act.debug_check_consistency("reload");
 
                         // No edge re-persistence here. Edges arrived FROM Oxigraph;
                         // writing them back triggers the per-edge bridge-integrity
@@ -1154,6 +1544,38 @@ impl Handler<ReloadGraphFromDatabase> for GraphStateActor {
     }
 }
 
+impl Handler<CheckGraphConsistency> for GraphStateActor {
+    type Result = Result<CheckGraphConsistencyResult, String>;
+
+    fn handle(&mut self, msg: CheckGraphConsistency, _ctx: &mut Self::Context) -> Self::Result {
+        let report = self.consistency_report();
+        let options = match msg.repair {
+            Some(options) if options.any() => options,
+            _ => {
+                return Ok(CheckGraphConsistencyResult {
+                    report,
+                    repaired: None,
+                    after: None,
+                })
+            }
+        };
+
+        let repaired = self.repair_consistency(options);
+        let after = self.consistency_report();
+        info!(
+            "GraphStateActor: consistency repair ({:?}): before [{}], after [{}]",
+            repaired,
+            report.summary(),
+            after.summary()
+        );
+        Ok(CheckGraphConsistencyResult {
+            report,
+            repaired: Some(repaired),
+            after: Some(after),
+        })
+    }
+}
+
 impl Handler<GetNodeTypeArrays> for GraphStateActor {
     type Result = NodeTypeArrays;
 
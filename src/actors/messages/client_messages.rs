@@ -11,7 +11,8 @@
 pub use visionclaw_actors::messages::client_messages::{
     AuthenticateClient, BroadcastMessage, BroadcastNodePositions,
     ClientBroadcastAck, ForcePositionBroadcast, GetClientCount, InitialClientSync,
-    SendToClientBinary, SendToClientText, UnregisterClient, UpdateClientFilter,
+    ReleasePhysicsAuthority, RequestPhysicsAuthority, SendToClientBinary, SendToClientText,
+    SetLanguageFilter, SetNodeBudget, SuppressPositionEcho, UnregisterClient, UpdateClientFilter,
 };
 
 // ---------------------------------------------------------------------------
@@ -65,6 +66,18 @@ pub struct BroadcastPositions {
     pub positions: Vec<crate::utils::socket_flow_messages::BinaryNodeDataClient>,
 }
 
+/// Positions computed by a local-physics client. Merged into the graph only
+/// if the client currently holds physics authority; returns the number of
+/// positions forwarded.
+/// Blocked: references `utils::socket_flow_messages::BinaryNodeData`.
+#[derive(Message)]
+#[rtype(result = "Result<usize, String>")]
+pub struct SubmitClientPositions {
+    pub client_id: usize,
+    pub positions: Vec<(u32, crate::utils::socket_flow_messages::BinaryNodeData)>,
+    pub echo_window_ms: u64,
+}
+
 /// Set the graph service supervisor address in client manager.
 /// Blocked: references `Addr<actors::GraphServiceSupervisor>`.
 #[derive(Message)]
@@ -112,3 +125,22 @@ pub struct SendPositionUpdate {
 #[derive(Message)]
 #[rtype(result = "()")]
 pub struct BroadcastAgentActionFrame(pub Vec<u8>);
+
+/// Send positional audio cues to users who have turned them on, or only to
+/// `recipient`'s sessions when set (see `services::audio_cue_service`).
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct BroadcastAudioCues {
+    pub cues: Vec<crate::services::audio_cue_service::AudioCue>,
+    pub recipient: Option<String>,
+}
+
+/// Add or remove a chat highlight layer in the sessions of `audience` (see
+/// `services::highlight_service`). Adding also schedules the layer's expiry.
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct SendHighlightLayer {
+    pub layer: crate::services::highlight_service::HighlightLayer,
+    pub action: crate::services::highlight_service::HighlightAction,
+    pub audience: crate::services::highlight_service::HighlightAudience,
+}
@@ -26,14 +26,15 @@ use crate::utils::socket_flow_messages::BinaryNodeData;
 // ---------------------------------------------------------------------------
 
 pub use visionclaw_actors::messages::graph_messages::{
-    AddEdge, AddNode, AddNodesFromMetadata, ArchiveWorkspace, AutoBalanceNotification,
-    BuildGraphFromMetadata, CreateWorkspace, DeleteWorkspace, GetAutoBalanceNotifications,
+    AddEdge, AddEphemeralNode, AddNode, AddNodesFromMetadata, ArchiveWorkspace,
+    AutoBalanceNotification, BuildGraphFromMetadata, CreateWorkspace, DeleteWorkspace, GetAutoBalanceNotifications,
     GetGraphData, GetMetadata, GetNodeIdMapping, GetNodeMap, GetNodePositions,
     GetNodeTypeArrays, GetPositionFrameSnapshot, GetWorkspace, GetWorkspaceCount,
     GetWorkspaces, InitializeActor, LoadWorkspaces, NodeIdMapping, NodeTypeArrays,
-    PositionFrameSnapshot, PositionRow, RefreshMetadata, ReloadGraphFromDatabase, RemoveEdge,
-    RemoveNode, RemoveNodeByMetadata, SaveWorkspaces, ToggleFavoriteWorkspace,
-    UpdateGraphData, UpdateMetadata, UpdateNodeFromMetadata,
+    PatchWorkspaceSettings, PositionFrameSnapshot, PositionRow, RefreshMetadata,
+    ReloadGraphFromDatabase, RemoveEdge,
+    RemoveNode, RemoveNodeByMetadata, SaveWorkspaces, SetNodeMetadataFlag,
+    ToggleFavoriteWorkspace, UpdateGraphData, UpdateMetadata, UpdateNodeFromMetadata,
     UpdateNodeTypeArrays, UpdateWorkspace, WorkspaceChangeType,
     WorkspaceStateChanged,
 };
@@ -81,3 +82,32 @@ pub struct RequestGraphUpdate {
     pub graph_type: crate::models::graph_types::GraphType,
     pub force_refresh: bool,
 }
+
+/// Check (and optionally repair) the consistency of `GraphStateActor`'s
+/// parallel node indexes. The reply describes the state before any repair.
+/// Stays in webxr because the report types live in `crate::actors::graph_consistency`.
+#[derive(Message)]
+#[rtype(result = "Result<CheckGraphConsistencyResult, String>")]
+pub struct CheckGraphConsistency {
+    pub repair: Option<crate::actors::graph_consistency::RepairOptions>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckGraphConsistencyResult {
+    pub report: crate::actors::graph_consistency::ConsistencyReport,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repaired: Option<crate::actors::graph_consistency::RepairSummary>,
+    /// Re-check after repair; `None` when nothing was repaired.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<crate::actors::graph_consistency::ConsistencyReport>,
+}
+
+/// Weight-only edge changes, applied in place by `GraphStateActor` (edge
+/// list) and `ForceComputeActor` (CSR spring weights) without a rebuild.
+/// Stays in webxr because the report type lives in `crate::physics::edge_weights`.
+#[derive(Message, Debug, Clone)]
+#[rtype(result = "Result<crate::physics::edge_weights::EdgeWeightReport, String>")]
+pub struct EdgeWeightsUpdated {
+    pub updates: Vec<crate::physics::edge_weights::EdgeWeightUpdate>,
+}
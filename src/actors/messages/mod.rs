@@ -21,23 +21,28 @@ pub use visionclaw_domain::ports::gpu_semantic_analyzer::PathfindingResult;
 
 // --- graph_messages ---
 pub use graph_messages::{
-    AddEdge, AddNode, AddNodesFromMetadata, ArchiveWorkspace, AutoBalanceNotification,
-    BuildGraphFromMetadata, CreateWorkspace, DeleteWorkspace, GetAutoBalanceNotifications,
+    AddEdge, AddEphemeralNode, AddNode, AddNodesFromMetadata, ArchiveWorkspace,
+    AutoBalanceNotification, BuildGraphFromMetadata, CheckGraphConsistency,
+    CheckGraphConsistencyResult, CreateWorkspace, DeleteWorkspace, EdgeWeightsUpdated,
+    GetAutoBalanceNotifications,
     GetGraphData, GetGraphStateActor, GetMetadata, GetNodeIdMapping, GetNodeMap, GetNodePositions,
     GetNodeTypeArrays, GetPositionFrameSnapshot, NodeIdMapping, GetWorkspace, GetWorkspaceCount,
-    GetWorkspaces, InitializeActor, LoadWorkspaces, NodeTypeArrays, PositionFrameSnapshot,
+    GetWorkspaces, InitializeActor, LoadWorkspaces, NodeTypeArrays, PatchWorkspaceSettings,
+    PositionFrameSnapshot,
     PositionRow, RefreshMetadata, ReloadGraphFromDatabase, RemoveEdge, RemoveNode,
-    RemoveNodeByMetadata, RequestGraphUpdate, SaveWorkspaces, ToggleFavoriteWorkspace,
-    UpdateGraphData, UpdateMetadata, UpdateNodeFromMetadata, UpdateNodePosition,
-    UpdateNodePositions, UpdateNodeTypeArrays, UpdateWorkspace, WorkspaceChangeType,
-    WorkspaceStateChanged,
+    RemoveNodeByMetadata, RequestGraphUpdate, SaveWorkspaces, SetNodeMetadataFlag,
+    ToggleFavoriteWorkspace, UpdateGraphData, UpdateMetadata, UpdateNodeFromMetadata,
+    UpdateNodePosition, UpdateNodePositions, UpdateNodeTypeArrays, UpdateWorkspace,
+    WorkspaceChangeType, WorkspaceStateChanged,
 };
 
 // --- physics_messages ---
 pub use physics_messages::{
     AddIsolationLayer, AdjustConstraintWeights, ApplyConstraintsToNodes,
     BroadcastPerformanceStats, ComputeForces, ConfigureBroadcastOptimization, ConfigureCollision,
-    ConfigureDAG, ConfigureStressMajorization, ConfigureTypeClustering, ForceResumePhysics,
+    ConfigureDAG, ConfigureStressMajorization, ConfigureTypeClustering, ControlSimulation,
+    SetSimulationQuality,
+    ForceResumePhysics,
     GPUInitFailed, GPUInitialized, GPUStatus, GetActiveConstraints, GetBroadcastStats, GetConstraintBuffer,
     GetConstraints, GetEquilibriumStatus, GetForceComputeActor, GetPhysicsOrchestratorActor, GetGPUMetrics, GetGPUStatus,
     GetHierarchyLevels, GetKernelMode, GetNodeData, GetPhysicsStats, GetSemanticConfig,
@@ -55,11 +60,16 @@ pub use physics_messages::{
     UpdateStressMajorizationParams, UpdateVisualAnalyticsParams, UploadConstraintsToGPU,
     UploadPositions,
     // Sequential pipeline (Step 5)
-    PhysicsStepCompleted, SetPhysicsOrchestratorAddr,
+    PhysicsStepCompleted, ReadbackAnomaly, SetPhysicsOrchestratorAddr,
     // GPU position snapshot (REST API)
     BoundingBox, CurrentPositionsSnapshot, GetCurrentPositions,
-    // Layout reset
-    ResetPositions,
+    // Per-workspace simulation contexts
+    GetWorkspaceGpuMetrics, GetWorkspacePositions, RemoveWorkspaceGpuContext,
+    UpdateWorkspaceSimulationParams, UploadWorkspaceGraph,
+    // Layout reset and client seeding
+    ResetPositions, SeedPositions,
+    // Drag release
+    SetNodeMotion, UnpinNode,
     // Phase 5 (ADR-01 D9): event emission only
     ClampKind, EmitPhysicsEvent, PhysicsEvent, SetLayoutMode,
 };
@@ -83,11 +93,12 @@ pub use ontology_messages::{
 
 // --- client_messages ---
 pub use client_messages::{
-    AuthenticateClient, BroadcastAgentActionFrame, BroadcastMessage, BroadcastNodePositions,
-    BroadcastPositions, ClientBroadcastAck, ClientRecipients, ForcePositionBroadcast,
-    GetClientCount, InitialClientSync, RegisterClient, SendInitialGraphLoad, SendPositionUpdate,
-    SendToClientBinary, SendToClientText, SetGraphServiceAddress, UnregisterClient,
-    UpdateClientFilter,
+    AuthenticateClient, BroadcastAgentActionFrame, BroadcastAudioCues, BroadcastMessage,
+    BroadcastNodePositions, BroadcastPositions, ClientBroadcastAck, ClientRecipients, ForcePositionBroadcast,
+    GetClientCount, InitialClientSync, RegisterClient, ReleasePhysicsAuthority,
+    RequestPhysicsAuthority, SendHighlightLayer, SendInitialGraphLoad, SendPositionUpdate, SendToClientBinary,
+    SendToClientText, SetGraphServiceAddress, SetLanguageFilter, SetNodeBudget, SubmitClientPositions,
+    SuppressPositionEcho, UnregisterClient, UpdateClientFilter,
 };
 
 // --- analytics_messages ---
@@ -12,6 +12,8 @@ use crate::gpu::visual_analytics::{IsolationLayer, VisualAnalyticsParams};
 use visionclaw_domain::models::constraints::{AdvancedParams, ConstraintSet};
 use visionclaw_domain::models::graph::GraphData as ModelsGraphData;
 use crate::models::simulation_params::SimulationParams;
+use crate::physics::quality::{QualityLevel, QualityPreset};
+use crate::physics::simulation_control::{SimulationCommand, SimulationControlStatus};
 use crate::utils::socket_flow_messages::BinaryNodeData;
 use crate::utils::unified_gpu_compute::ComputeMode;
 
@@ -182,6 +184,8 @@ pub struct UpdateSimulationParams {
 pub struct ComputeForces {
     /// Optional correlation ID for message tracking (H4)
     pub correlation_id: Option<MessageId>,
+    /// Download positions after the step for broadcast and convergence.
+    pub read_back: bool,
 }
 
 #[derive(Message)]
@@ -490,6 +494,20 @@ pub struct ForceResumePhysics {
     pub reason: String,
 }
 
+/// Operator pause, resume or single-step; see `physics::simulation_control`.
+#[derive(Message, Debug, Clone, Serialize, Deserialize)]
+#[rtype(result = "Result<SimulationControlStatus, String>")]
+pub struct ControlSimulation {
+    pub command: SimulationCommand,
+}
+
+/// Switch the simulation quality level; see `physics::quality`.
+#[derive(Message, Debug, Clone, Serialize, Deserialize)]
+#[rtype(result = "Result<QualityPreset, String>")]
+pub struct SetSimulationQuality {
+    pub level: QualityLevel,
+}
+
 #[derive(Message, Debug, Clone, Serialize, Deserialize)]
 #[rtype(result = "Result<bool, VisionClawError>")]
 pub struct GetEquilibriumStatus;
@@ -611,6 +629,20 @@ pub struct PhysicsStepCompleted {
     /// startup skips trip the consecutive-failure circuit breaker and latch a
     /// permanent DEGRADED halt before the first real step ever completes.
     pub skipped: bool,
+    /// False when positions were not downloaded after the step (quality
+    /// level readback interval, skips and failures); `kinetic_energy` then
+    /// says nothing about the layout and must not feed convergence.
+    pub read_back: bool,
+}
+
+/// Sent by ForceComputeActor when a GPU readback holds too many non-finite
+/// nodes to scrub. The orchestrator pauses the simulation through its
+/// anomaly path; `positions` is the raw frame for the debug snapshot.
+#[derive(Message, Debug, Clone)]
+#[rtype(result = "()")]
+pub struct ReadbackAnomaly {
+    pub anomaly: crate::physics::stability_monitor::SimulationAnomaly,
+    pub positions: Vec<(u32, BinaryNodeData)>,
 }
 
 /// Sent by PhysicsOrchestratorActor to ForceComputeActor to wire up the
@@ -635,6 +667,32 @@ pub struct SetGpuComputeAddress {
 #[rtype(result = "Result<(), String>")]
 pub struct ResetPositions;
 
+/// Overwrite positions of the primary layout with a client's saved layout,
+/// keyed by node id, before the next step. Refused if too few ids match.
+#[derive(Message, Debug, Clone)]
+#[rtype(result = "Result<crate::physics::layout_seed::SeedReport, String>")]
+pub struct SeedPositions {
+    pub positions: std::collections::HashMap<u32, [f32; 3]>,
+}
+
+/// Place a node in the simulation with the given velocity, e.g. where a drag
+/// ended. A pinned node is held at `position` until [`UnpinNode`].
+#[derive(Message, Debug, Clone, Copy)]
+#[rtype(result = "Result<(), String>")]
+pub struct SetNodeMotion {
+    pub node_id: u32,
+    pub position: [f32; 3],
+    pub velocity: [f32; 3],
+    pub pinned: bool,
+}
+
+/// Let a node pinned by [`SetNodeMotion`] move with the simulation again.
+#[derive(Message, Debug, Clone, Copy)]
+#[rtype(result = "()")]
+pub struct UnpinNode {
+    pub node_id: u32,
+}
+
 // ---------------------------------------------------------------------------
 // Phase 5 (ADR-01 D9): event emission only
 // ---------------------------------------------------------------------------
@@ -704,3 +762,44 @@ pub struct EmitPhysicsEvent {
 pub struct SetLayoutMode {
     pub mode: crate::layout::types::LayoutMode,
 }
+
+// ---------------------------------------------------------------------------
+// Per-workspace simulation contexts
+// ---------------------------------------------------------------------------
+
+/// Replace the simulation parameters of one workspace without touching the
+/// others. `"default"` targets the primary layout.
+#[derive(Message, Clone)]
+#[rtype(result = "Result<(), String>")]
+pub struct UpdateWorkspaceSimulationParams {
+    pub workspace_id: String,
+    pub params: SimulationParams,
+}
+
+/// Upload a workspace's graph into its own GPU buffers, creating the
+/// workspace context on first use.
+#[derive(Message, Clone)]
+#[rtype(result = "Result<(), String>")]
+pub struct UploadWorkspaceGraph {
+    pub workspace_id: String,
+    pub graph: std::sync::Arc<ModelsGraphData>,
+}
+
+/// Release a workspace's GPU buffers. Returns false for unknown workspaces
+/// and for the default workspace.
+#[derive(Message)]
+#[rtype(result = "bool")]
+pub struct RemoveWorkspaceGpuContext {
+    pub workspace_id: String,
+}
+
+#[derive(Message)]
+#[rtype(result = "Vec<crate::actors::gpu::workspace_contexts::WorkspaceGpuMetrics>")]
+pub struct GetWorkspaceGpuMetrics;
+
+/// Current positions of a non-default workspace as `(node_id, [x, y, z])`.
+#[derive(Message)]
+#[rtype(result = "Result<Vec<(u32, [f32; 3])>, String>")]
+pub struct GetWorkspacePositions {
+    pub workspace_id: String,
+}
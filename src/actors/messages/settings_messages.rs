@@ -7,6 +7,7 @@ use std::collections::HashMap;
 
 use crate::config::AppFullSettings;
 use crate::errors::VisionClawError;
+use crate::services::settings_history_service::ChangeOrigin;
 
 // ---------------------------------------------------------------------------
 // Settings Actor Messages
@@ -20,6 +21,8 @@ pub struct GetSettings;
 #[rtype(result = "Result<(), VisionClawError>")]
 pub struct UpdateSettings {
     pub settings: AppFullSettings,
+    /// Who made the change, for the settings history.
+    pub origin: ChangeOrigin,
 }
 
 #[derive(Message)]
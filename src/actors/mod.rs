@@ -5,6 +5,7 @@ pub mod agent_monitor_actor;
 pub mod client_coordinator_actor;
 pub mod client_filter;
 pub mod gpu;
+pub mod graph_consistency;
 pub mod graph_state_actor;
 pub mod graph_actor {
     // Re-export graph_state_actor types for backward compatibility
@@ -29,7 +30,9 @@ pub mod graph_actor {
     }
 }
 pub mod metadata_actor;
+pub mod node_budget;
 pub mod optimized_settings_actor;
+pub mod physics_authority;
 pub mod physics_orchestrator_actor;
 pub mod protected_settings_actor;
 pub mod supervisor;
@@ -0,0 +1,418 @@
+//! Per-session node budget.
+//!
+//! A client can cap how many nodes it is sent (`nodeBudget` over the
+//! WebSocket). When the nodes that pass its filter exceed the budget, the
+//! server keeps:
+//!
+//! 1. the focus neighbourhood: the client's focus node and its neighbours out
+//!    to [`FOCUS_HOPS`] hops, nearest first, up to [`FOCUS_SHARE`] of the
+//!    budget;
+//! 2. the most important of the rest, filling the budget.
+//!
+//! Importance blends degree, PageRank centrality from the shared analytics
+//! map and how recently the page changed, each scaled to 0..1. Recomputed
+//! whenever the budget, the focus or the filter changes; position broadcasts
+//! then skip everything outside the subset.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use chrono::{DateTime, Utc};
+use log::debug;
+
+use crate::actors::client_coordinator_actor::ClientFilter;
+use crate::utils::binary_protocol::NodeAnalytics;
+use visionclaw_domain::models::graph::GraphData;
+
+/// How far from the focus node the neighbourhood reaches.
+pub const FOCUS_HOPS: usize = 2;
+
+/// Most of the budget the focus neighbourhood may take.
+pub const FOCUS_SHARE: f32 = 0.5;
+
+const DEGREE_WEIGHT: f32 = 0.4;
+const CENTRALITY_WEIGHT: f32 = 0.4;
+const RECENCY_WEIGHT: f32 = 0.2;
+
+/// A page edited this many days ago scores half a fresh one on recency.
+const RECENCY_HALF_LIFE_DAYS: f32 = 7.0;
+
+/// Importance of each candidate in 0..=1.
+pub fn importance_scores(
+    candidates: &HashSet<u32>,
+    graph: &GraphData,
+    analytics: Option<&HashMap<u32, NodeAnalytics>>,
+    now: DateTime<Utc>,
+) -> HashMap<u32, f32> {
+    let mut degree: HashMap<u32, u32> = HashMap::new();
+    for edge in &graph.edges {
+        if candidates.contains(&edge.source) && candidates.contains(&edge.target) {
+            *degree.entry(edge.source).or_default() += 1;
+            *degree.entry(edge.target).or_default() += 1;
+        }
+    }
+    let max_degree = degree.values().copied().max().unwrap_or(0).max(1) as f32;
+
+    let centrality = |id: u32| {
+        analytics
+            .and_then(|a| a.get(&id))
+            .map(|a| a.centrality)
+            .filter(|c| c.is_finite() && *c > 0.0)
+            .unwrap_or(0.0)
+    };
+    let max_centrality = candidates
+        .iter()
+        .map(|id| centrality(*id))
+        .fold(0.0f32, f32::max);
+
+    graph
+        .nodes
+        .iter()
+        .filter(|node| candidates.contains(&node.id))
+        .map(|node| {
+            let degree = degree.get(&node.id).copied().unwrap_or(0) as f32 / max_degree;
+            let centrality = if max_centrality > 0.0 {
+                centrality(node.id) / max_centrality
+            } else {
+                0.0
+            };
+            let recency = graph
+                .metadata
+                .get(&node.metadata_id)
+                .map(|m| {
+                    let changed = m.last_content_change.unwrap_or(m.last_modified);
+                    let age_days = (now - changed).num_seconds().max(0) as f32 / 86_400.0;
+                    0.5f32.powf(age_days / RECENCY_HALF_LIFE_DAYS)
+                })
+                .unwrap_or(0.0);
+            (
+                node.id,
+                DEGREE_WEIGHT * degree + CENTRALITY_WEIGHT * centrality + RECENCY_WEIGHT * recency,
+            )
+        })
+        .collect()
+}
+
+/// `focus` and its neighbours among `candidates`, breadth first, at most
+/// `limit` of them.
+pub fn focus_neighbourhood(
+    focus: u32,
+    candidates: &HashSet<u32>,
+    graph: &GraphData,
+    limit: usize,
+) -> Vec<u32> {
+    if !candidates.contains(&focus) || limit == 0 {
+        return Vec::new();
+    }
+    let mut adjacency: HashMap<u32, Vec<u32>> = HashMap::new();
+    for edge in &graph.edges {
+        if candidates.contains(&edge.source) && candidates.contains(&edge.target) {
+            adjacency.entry(edge.source).or_default().push(edge.target);
+            adjacency.entry(edge.target).or_default().push(edge.source);
+        }
+    }
+
+    let mut seen = HashSet::from([focus]);
+    let mut order = vec![focus];
+    let mut queue = VecDeque::from([(focus, 0)]);
+    while let Some((node, hops)) = queue.pop_front() {
+        if hops == FOCUS_HOPS {
+            continue;
+        }
+        let mut neighbours = adjacency.get(&node).cloned().unwrap_or_default();
+        neighbours.sort_unstable();
+        for next in neighbours {
+            if order.len() == limit {
+                return order;
+            }
+            if seen.insert(next) {
+                order.push(next);
+                queue.push_back((next, hops + 1));
+            }
+        }
+    }
+    order
+}
+
+/// At most `budget` of `candidates`: the focus neighbourhood first, then by
+/// importance, ties to the lower id.
+pub fn select_within_budget(
+    candidates: &HashSet<u32>,
+    graph: &GraphData,
+    analytics: Option<&HashMap<u32, NodeAnalytics>>,
+    budget: usize,
+    focus: Option<u32>,
+    now: DateTime<Utc>,
+) -> HashSet<u32> {
+    if candidates.len() <= budget {
+        return candidates.clone();
+    }
+    let focus_limit = ((budget as f32 * FOCUS_SHARE) as usize).max(1).min(budget);
+    let mut selected: HashSet<u32> = focus
+        .map(|focus| focus_neighbourhood(focus, candidates, graph, focus_limit))
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
+
+    let scores = importance_scores(candidates, graph, analytics, now);
+    let mut ranked: Vec<(u32, f32)> = scores
+        .into_iter()
+        .filter(|(id, _)| !selected.contains(id))
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    let room = budget - selected.len();
+    selected.extend(ranked.into_iter().take(room).map(|(id, _)| id));
+    selected
+}
+
+/// Trim `filter.filtered_node_ids` to the client's node budget. Call after
+/// `client_filter::recompute_filtered_nodes`.
+pub fn apply_node_budget(
+    filter: &mut ClientFilter,
+    graph: &GraphData,
+    analytics: Option<&HashMap<u32, NodeAnalytics>>,
+) {
+    let Some(budget) = filter.node_budget else {
+        return;
+    };
+    let before = filter.filtered_node_ids.len();
+    filter.filtered_node_ids = select_within_budget(
+        &filter.filtered_node_ids,
+        graph,
+        analytics,
+        budget,
+        filter.focus_node,
+        Utc::now(),
+    );
+    debug!(
+        "Node budget {} applied: {} -> {} nodes (focus={:?})",
+        budget,
+        before,
+        filter.filtered_node_ids.len(),
+        filter.focus_node
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use visionclaw_domain::models::edge::Edge;
+    use visionclaw_domain::models::metadata::Metadata;
+    use visionclaw_domain::models::node::Node;
+
+    /// A hub (1) with spokes 2..=6, of which 6 was edited at `now`, and a
+    /// chain 10-11-12-13 off to the side.
+    fn sample_graph(now: DateTime<Utc>) -> GraphData {
+        let mut graph = GraphData::new();
+        for id in [1, 2, 3, 4, 5, 6, 10, 11, 12, 13] {
+            graph
+                .nodes
+                .push(Node::new_with_id(format!("{}.md", id), Some(id)));
+        }
+        for spoke in 2..=6 {
+            graph.edges.push(Edge::new(1, spoke, 1.0));
+        }
+        for (a, b) in [(10, 11), (11, 12), (12, 13)] {
+            graph.edges.push(Edge::new(a, b, 1.0));
+        }
+        edited(&mut graph, "6.md", now);
+        graph
+    }
+
+    fn edited(graph: &mut GraphData, page: &str, at: DateTime<Utc>) {
+        graph.metadata.insert(
+            page.to_string(),
+            Metadata {
+                last_modified: at,
+                ..Default::default()
+            },
+        );
+    }
+
+    fn all_nodes(graph: &GraphData) -> HashSet<u32> {
+        graph.nodes.iter().map(|n| n.id).collect()
+    }
+
+    fn centrality(id: u32, value: f32) -> HashMap<u32, NodeAnalytics> {
+        HashMap::from([(
+            id,
+            NodeAnalytics {
+                centrality: value,
+                ..Default::default()
+            },
+        )])
+    }
+
+    #[test]
+    fn budget_at_or_above_the_node_count_keeps_everything() {
+        let now = Utc::now();
+        let graph = sample_graph(now);
+        let candidates = all_nodes(&graph);
+        for budget in [candidates.len(), candidates.len() + 1, usize::MAX] {
+            assert_eq!(
+                select_within_budget(&candidates, &graph, None, budget, Some(10), now),
+                candidates
+            );
+        }
+    }
+
+    #[test]
+    fn zero_budget_keeps_nothing() {
+        let now = Utc::now();
+        let graph = sample_graph(now);
+        let candidates = all_nodes(&graph);
+        assert!(select_within_budget(&candidates, &graph, None, 0, Some(10), now).is_empty());
+        assert!(select_within_budget(&candidates, &graph, None, 0, None, now).is_empty());
+    }
+
+    #[test]
+    fn budget_of_one_keeps_only_the_focus() {
+        let now = Utc::now();
+        let graph = sample_graph(now);
+        let candidates = all_nodes(&graph);
+        assert_eq!(
+            select_within_budget(&candidates, &graph, None, 1, Some(13), now),
+            HashSet::from([13])
+        );
+    }
+
+    #[test]
+    fn no_candidates_select_nothing() {
+        let now = Utc::now();
+        let graph = sample_graph(now);
+        assert!(select_within_budget(&HashSet::new(), &graph, None, 5, Some(10), now).is_empty());
+    }
+
+    #[test]
+    fn focus_takes_half_the_budget_and_importance_the_rest() {
+        // The hub, then the recently edited spoke, then the lowest id among
+        // equals.
+        let now = Utc::now();
+        let graph = sample_graph(now);
+        let selected = select_within_budget(&all_nodes(&graph), &graph, None, 6, Some(10), now);
+        assert_eq!(selected, HashSet::from([10, 11, 12, 1, 6, 2]));
+    }
+
+    #[test]
+    fn unknown_focus_falls_back_to_importance() {
+        let now = Utc::now();
+        let graph = sample_graph(now);
+        // With no neighbourhood to keep, the hub and the recently edited
+        // spoke lead, then a mid-chain node: degree 2 beats a spoke's 1.
+        let selected = select_within_budget(&all_nodes(&graph), &graph, None, 3, Some(99), now);
+        assert_eq!(selected, HashSet::from([1, 6, 11]));
+    }
+
+    #[test]
+    fn focus_neighbourhood_reaches_two_hops() {
+        let graph = sample_graph(Utc::now());
+        assert_eq!(
+            focus_neighbourhood(10, &all_nodes(&graph), &graph, 10),
+            vec![10, 11, 12]
+        );
+    }
+
+    #[test]
+    fn focus_neighbourhood_stops_at_its_limit() {
+        let graph = sample_graph(Utc::now());
+        let candidates = all_nodes(&graph);
+        assert_eq!(
+            focus_neighbourhood(10, &candidates, &graph, 2),
+            vec![10, 11]
+        );
+        assert_eq!(
+            focus_neighbourhood(1, &candidates, &graph, 3),
+            vec![1, 2, 3]
+        );
+        assert!(focus_neighbourhood(10, &candidates, &graph, 0).is_empty());
+    }
+
+    #[test]
+    fn focus_outside_the_candidates_has_no_neighbourhood() {
+        let graph = sample_graph(Utc::now());
+        let mut candidates = all_nodes(&graph);
+        assert!(focus_neighbourhood(99, &candidates, &graph, 10).is_empty());
+        candidates.remove(&10);
+        assert!(focus_neighbourhood(10, &candidates, &graph, 10).is_empty());
+    }
+
+    #[test]
+    fn focus_neighbourhood_does_not_cross_filtered_nodes() {
+        let graph = sample_graph(Utc::now());
+        let mut candidates = all_nodes(&graph);
+        candidates.remove(&11);
+        assert_eq!(focus_neighbourhood(10, &candidates, &graph, 10), vec![10]);
+    }
+
+    #[test]
+    fn importance_is_scored_in_unit_range_for_candidates_only() {
+        let now = Utc::now();
+        let graph = sample_graph(now);
+        let candidates: HashSet<u32> = HashSet::from([1, 2, 6, 10]);
+        let scores = importance_scores(&candidates, &graph, Some(&centrality(1, 0.5)), now);
+        assert_eq!(scores.keys().copied().collect::<HashSet<_>>(), candidates);
+        assert!(scores.values().all(|s| (0.0..=1.0).contains(s)));
+        assert_eq!(scores[&10], 0.0);
+        assert!(scores[&1] > scores[&6] && scores[&6] > scores[&2]);
+    }
+
+    #[test]
+    fn recency_halves_every_half_life() {
+        let now = Utc::now();
+        let mut graph = sample_graph(now);
+        let week_ago = now - chrono::Duration::days(RECENCY_HALF_LIFE_DAYS as i64);
+        edited(&mut graph, "5.md", week_ago);
+        let scores = importance_scores(&all_nodes(&graph), &graph, None, now);
+        let fresh = scores[&6] - scores[&2];
+        let stale = scores[&5] - scores[&2];
+        assert!((fresh - RECENCY_WEIGHT).abs() < 1e-4);
+        assert!((stale - RECENCY_WEIGHT / 2.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn centrality_lifts_an_unremarkable_node() {
+        let now = Utc::now();
+        let graph = sample_graph(now);
+        let analytics = centrality(13, 0.9);
+        let selected =
+            select_within_budget(&all_nodes(&graph), &graph, Some(&analytics), 2, None, now);
+        assert_eq!(selected, HashSet::from([1, 13]));
+    }
+
+    #[test]
+    fn non_finite_centrality_is_ignored() {
+        let now = Utc::now();
+        let graph = sample_graph(now);
+        for value in [f32::NAN, f32::INFINITY, -1.0] {
+            let analytics = centrality(13, value);
+            let selected =
+                select_within_budget(&all_nodes(&graph), &graph, Some(&analytics), 2, None, now);
+            assert_eq!(selected, HashSet::from([1, 6]), "centrality {}", value);
+        }
+    }
+
+    #[test]
+    fn apply_without_a_budget_leaves_the_filter_alone() {
+        let graph = sample_graph(Utc::now());
+        let mut filter = ClientFilter {
+            filtered_node_ids: all_nodes(&graph),
+            node_budget: None,
+            focus_node: Some(10),
+            ..Default::default()
+        };
+        apply_node_budget(&mut filter, &graph, None);
+        assert_eq!(filter.filtered_node_ids, all_nodes(&graph));
+    }
+
+    #[test]
+    fn apply_trims_the_filter_around_its_focus() {
+        let graph = sample_graph(Utc::now());
+        let mut filter = ClientFilter {
+            filtered_node_ids: all_nodes(&graph),
+            node_budget: Some(4),
+            focus_node: Some(13),
+            ..Default::default()
+        };
+        apply_node_budget(&mut filter, &graph, None);
+        assert_eq!(filter.filtered_node_ids, HashSet::from([13, 12, 1, 6]));
+    }
+}
@@ -10,6 +10,7 @@ use crate::actors::messages::{
 };
 use crate::config::AppFullSettings;
 use crate::errors::{SettingsError, VisionClawError, VisionClawResult};
+use crate::services::settings_history_service::{self, settings_history, ChangeOrigin};
 use actix::prelude::*;
 use blake3::Hasher;
 use flate2::Status;
@@ -593,8 +594,18 @@ impl OptimizedSettingsActor {
         }))
     }
 
-    pub async fn update_settings(&self, new_settings: AppFullSettings) -> VisionClawResult<()> {
+    /// Replace the settings and save them. Changes to the sections the
+    /// settings history tracks are recorded against `origin`.
+    pub async fn update_settings(
+        &self,
+        new_settings: AppFullSettings,
+        origin: &ChangeOrigin,
+    ) -> VisionClawResult<()> {
         let mut settings = self.settings.write().await;
+        crate::utils::log_throttle::set_interval_secs(
+            new_settings.system.debug.hot_path_log_interval_secs,
+        );
+        let previous = settings_history_service::typed_sections(&settings);
         *settings = new_settings;
 
         
@@ -613,6 +624,10 @@ impl OptimizedSettingsActor {
         })?;
 
         info!("Settings updated, caches cleared, and saved successfully");
+        let current = settings_history_service::typed_sections(&settings);
+        for ((section, old), (_, new)) in previous.iter().zip(&current) {
+            settings_history().record(origin, section, old, new);
+        }
         Ok(())
     }
 
@@ -848,7 +863,7 @@ impl Handler<UpdateSettings> for OptimizedSettingsActor {
     fn handle(&mut self, msg: UpdateSettings, _ctx: &mut Self::Context) -> Self::Result {
         let actor = self.clone();
 
-        Box::pin(async move { actor.update_settings(msg.settings).await })
+        Box::pin(async move { actor.update_settings(msg.settings, &msg.origin).await })
     }
 }
 
@@ -0,0 +1,203 @@
+//! Physics authority arbitration.
+//!
+//! A client running physics locally pushes positions that overwrite the
+//! server's layout. Two such clients on the same workspace would fight, so at
+//! most one session per workspace holds authority at a time. Under
+//! `firstCome` the holder keeps it until it releases or disconnects; under
+//! `roleBased` a power user may take it over from a regular user, and ties
+//! fall back to first-come. Remote-mode clients never need authority.
+//!
+//! Only the holder of [`DEFAULT_WORKSPACE`] merges positions into the shared
+//! graph; holders of other workspaces have their frames refused rather than
+//! overwriting a layout they do not own.
+
+use serde::Serialize;
+use std::collections::HashMap;
+
+pub const DEFAULT_WORKSPACE: &str = "default";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AuthorityPolicy {
+    FirstCome,
+    RoleBased,
+}
+
+impl AuthorityPolicy {
+    /// Unknown values fall back to `FirstCome`, the least surprising policy.
+    pub fn parse(name: &str) -> Self {
+        match name {
+            "roleBased" | "role_based" | "role" => AuthorityPolicy::RoleBased,
+            _ => AuthorityPolicy::FirstCome,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorityHolder {
+    pub client_id: usize,
+    pub is_power_user: bool,
+    pub granted_at: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthorityOutcome {
+    /// Granted; `revoked` is the client that lost authority to the requester.
+    Granted {
+        revoked: Option<usize>,
+    },
+    AlreadyHeld,
+    Denied {
+        holder: AuthorityHolder,
+    },
+}
+
+#[derive(Debug)]
+pub struct PhysicsAuthority {
+    policy: AuthorityPolicy,
+    holders: HashMap<String, AuthorityHolder>,
+}
+
+impl PhysicsAuthority {
+    pub fn new(policy: AuthorityPolicy) -> Self {
+        Self {
+            policy,
+            holders: HashMap::new(),
+        }
+    }
+
+    pub fn policy(&self) -> AuthorityPolicy {
+        self.policy
+    }
+
+    pub fn set_policy(&mut self, policy: AuthorityPolicy) {
+        self.policy = policy;
+    }
+
+    pub fn holder(&self, workspace: &str) -> Option<&AuthorityHolder> {
+        self.holders.get(workspace)
+    }
+
+    pub fn holds(&self, client_id: usize, workspace: &str) -> bool {
+        self.holder(workspace)
+            .is_some_and(|h| h.client_id == client_id)
+    }
+
+    /// A client holds authority on at most one workspace; asking for another
+    /// gives up the first.
+    pub fn request(
+        &mut self,
+        workspace: &str,
+        client_id: usize,
+        is_power_user: bool,
+    ) -> AuthorityOutcome {
+        let revoked = match self.holders.get(workspace) {
+            Some(h) if h.client_id == client_id => return AuthorityOutcome::AlreadyHeld,
+            Some(h) => {
+                let preempts =
+                    self.policy == AuthorityPolicy::RoleBased && is_power_user && !h.is_power_user;
+                if !preempts {
+                    return AuthorityOutcome::Denied { holder: h.clone() };
+                }
+                Some(h.client_id)
+            }
+            None => None,
+        };
+        self.release(client_id);
+        self.holders.insert(
+            workspace.to_string(),
+            AuthorityHolder {
+                client_id,
+                is_power_user,
+                granted_at: chrono::Utc::now().timestamp_millis(),
+            },
+        );
+        AuthorityOutcome::Granted { revoked }
+    }
+
+    /// Drop every grant held by `client_id`, returning the workspaces freed.
+    pub fn release(&mut self, client_id: usize) -> Vec<String> {
+        let freed: Vec<String> = self
+            .holders
+            .iter()
+            .filter(|(_, h)| h.client_id == client_id)
+            .map(|(ws, _)| ws.clone())
+            .collect();
+        for ws in &freed {
+            self.holders.remove(ws);
+        }
+        freed
+    }
+
+    /// Workspace currently held by `client_id`, if any.
+    pub fn workspace_of(&self, client_id: usize) -> Option<&str> {
+        self.holders
+            .iter()
+            .find(|(_, h)| h.client_id == client_id)
+            .map(|(ws, _)| ws.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_come_keeps_authority_until_release() {
+        let mut auth = PhysicsAuthority::new(AuthorityPolicy::FirstCome);
+        assert_eq!(
+            auth.request("ws", 1, false),
+            AuthorityOutcome::Granted { revoked: None }
+        );
+        assert_eq!(auth.request("ws", 1, false), AuthorityOutcome::AlreadyHeld);
+        assert!(matches!(
+            auth.request("ws", 2, true),
+            AuthorityOutcome::Denied { holder } if holder.client_id == 1
+        ));
+        // Other workspaces are independent.
+        assert_eq!(
+            auth.request("other", 2, false),
+            AuthorityOutcome::Granted { revoked: None }
+        );
+
+        assert_eq!(auth.release(1), vec!["ws".to_string()]);
+        assert_eq!(
+            auth.request("ws", 3, false),
+            AuthorityOutcome::Granted { revoked: None }
+        );
+        assert!(auth.holds(3, "ws"));
+    }
+
+    #[test]
+    fn role_based_lets_power_users_preempt_regular_users_only() {
+        let mut auth = PhysicsAuthority::new(AuthorityPolicy::RoleBased);
+        auth.request("ws", 1, false);
+        assert_eq!(
+            auth.request("ws", 2, true),
+            AuthorityOutcome::Granted { revoked: Some(1) }
+        );
+        assert!(matches!(
+            auth.request("ws", 3, true),
+            AuthorityOutcome::Denied { .. }
+        ));
+        assert!(auth.holds(2, "ws"));
+    }
+
+    #[test]
+    fn switching_workspace_releases_the_previous_grant() {
+        let mut auth = PhysicsAuthority::new(AuthorityPolicy::FirstCome);
+        auth.request("a", 1, false);
+        auth.request("b", 1, false);
+        assert!(auth.holder("a").is_none());
+        assert_eq!(auth.workspace_of(1), Some("b"));
+        assert_eq!(
+            AuthorityPolicy::parse("roleBased"),
+            AuthorityPolicy::RoleBased
+        );
+        assert_eq!(
+            AuthorityPolicy::parse("nonsense"),
+            AuthorityPolicy::FirstCome
+        );
+    }
+}
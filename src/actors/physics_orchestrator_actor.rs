@@ -5,7 +5,7 @@
 
 use actix::prelude::*;
 use actix::MessageResult;
-use log::{debug, error, info, warn};
+use log::{debug, error, info, trace, warn};
 use std::collections::HashMap;
 use std::sync::{
     atomic::{AtomicBool, Ordering},
@@ -19,18 +19,32 @@ use crate::errors::VisionClawError;
 
 use crate::actors::gpu::force_compute_actor::ForceComputeActor;
 use crate::actors::gpu::force_compute_actor::PhysicsStats;
-use crate::actors::messages::{InitializeGPU, UpdateGPUGraphData};
+use crate::actors::gpu::force_compute_actor::RecoverStalledStep;
+use crate::actors::messages::{EdgeWeightsUpdated, InitializeGPU, UpdateGPUGraphData};
 // GraphStateActor will be implemented separately - using direct graph data access
 use crate::actors::messages::{
-    ApplyOntologyConstraints, ConstraintMergeMode, ConstraintStats, ForceResumePhysics,
-    GetConstraintStats, NodeInteractionMessage, PhysicsPauseMessage, RequestPositionSnapshot,
-    SetConstraintGroupActive, SimulationStep, StartSimulation, StopSimulation,
+    ApplyOntologyConstraints, ConstraintMergeMode, ConstraintStats, ControlSimulation,
+    ForceResumePhysics,
+    GetConstraintStats, NodeInteractionMessage, NodeInteractionType, PhysicsPauseMessage,
+    RequestPositionSnapshot,
+    SetConstraintGroupActive, SetNodeMotion, SetSimulationQuality, SimulationStep, StartSimulation,
+    StopSimulation, UnpinNode,
     StoreGPUComputeAddress, UpdateNodePosition, UpdateNodePositions, UpdateSimulationParams,
 };
 use visionclaw_domain::models::constraints::ConstraintSet;
 use crate::models::constraints::ConstraintGpuExt;
 use visionclaw_domain::models::graph::GraphData;
 use crate::models::simulation_params::{SettleMode, SimulationParams};
+use crate::physics::compute_budget::{BudgetResource, ComputeBudget, ComputeBudgetUsage};
+use crate::physics::drag_release::{DragTrack, ReleaseMotion, ReleasePlan};
+use crate::physics::stability_monitor::{
+    snapshot_dir, snapshot_id, write_snapshot, SimulationAnomaly, StabilityMonitor,
+};
+use crate::physics::quality::{set_simulation_quality, simulation_quality, QualityPreset};
+use crate::physics::simulation_control::{
+    simulation_control, SimulationCommand, SimulationControlStatus, StepGate,
+};
+use crate::physics::simulation_watchdog::{SimulationWatchdog, StallAction, MAX_RESTARTS};
 use crate::utils::socket_flow_messages::BinaryNodeData;
 use crate::utils::socket_flow_messages::BinaryNodeDataClient;
 
@@ -78,6 +92,12 @@ pub struct PhysicsOrchestratorActor {
 
     user_pinned_nodes: HashMap<u32, (f32, f32, f32)>,
 
+    /// Nodes being dragged, for the release behaviour in `drag_release`.
+    drag_tracks: HashMap<u32, DragTrack>,
+
+    /// Released nodes still gliding or springing back.
+    release_motions: HashMap<u32, ReleaseMotion>,
+
     last_broadcast_time: Instant,
 
     /// Tracks how many iterations have been run in the current fast-settle phase.
@@ -112,6 +132,11 @@ pub struct PhysicsOrchestratorActor {
     /// a PhysicsStepCompleted reply.  Prevents duplicate pipeline starts.
     pipeline_step_pending: bool,
 
+    /// A gated single step was sent to the GPU and is not yet counted; it is
+    /// counted against the operator's step budget only once
+    /// `PhysicsStepCompleted` reports that it actually ran.
+    single_step_in_flight: bool,
+
     /// Timestamp when `pipeline_step_pending` was set to `true`.
     /// Used by the heartbeat watchdog to detect stuck pipeline steps (>2s).
     pipeline_step_pending_since: Option<Instant>,
@@ -137,6 +162,21 @@ pub struct PhysicsOrchestratorActor {
     /// no longer loops forever broadcasting `f64::MAX`.  Cleared when physics
     /// is resumed/re-triggered.
     gpu_degraded: bool,
+
+    /// Operator cap on GPU kernel time / CPU share; enforced by delaying the
+    /// next pipeline step.
+    compute_budget: ComputeBudget,
+
+    /// Screens every position batch for NaNs, runaway velocities and energy
+    /// spikes before it reaches clients.
+    stability_monitor: StabilityMonitor,
+
+    /// Set when the monitor halted the simulation; positions are withheld
+    /// until physics is resumed. Cleared on resume.
+    anomaly: Option<SimulationAnomaly>,
+
+    /// Tracks frame progress and restarts the pipeline when it stalls.
+    watchdog: SimulationWatchdog,
 }
 
 /// Consecutive GPU-failure threshold after which the physics pipeline stops
@@ -229,6 +269,8 @@ impl PhysicsOrchestratorActor {
             message_tracker: tracker,
             client_coordinator_addr: None,
             user_pinned_nodes: HashMap::new(),
+            drag_tracks: HashMap::new(),
+            release_motions: HashMap::new(),
             last_broadcast_time: Instant::now(),
             fast_settle_iteration_count: 0,
             fast_settle_complete: false,
@@ -236,12 +278,17 @@ impl PhysicsOrchestratorActor {
             settle_ref_per_node_energy: f64::MAX,
             pre_settle_damping: None,
             pipeline_step_pending: false,
+            single_step_in_flight: false,
             pipeline_step_pending_since: None,
-            pipeline_target_interval: Duration::from_millis(16),
+            pipeline_target_interval: simulation_quality().tick_interval(),
             cpu_fallback_warned: false,
             gpu_init_started_at: None,
             consecutive_gpu_failures: 0,
             gpu_degraded: false,
+            compute_budget: ComputeBudget::default(),
+            stability_monitor: StabilityMonitor::default(),
+            anomaly: None,
+            watchdog: SimulationWatchdog::default(),
         }
     }
 
@@ -269,7 +316,7 @@ impl PhysicsOrchestratorActor {
         // Choose target interval based on settle mode.
         // FastSettle: 0ms — fire as fast as the GPU can compute, each step
         //   triggers a broadcast, until convergence then stop entirely.
-        // Continuous: 16ms (~60 fps) target cadence.
+        // Continuous: the quality level's tick rate (16ms, ~60 fps, at high).
         match &self.simulation_params.settle_mode {
             SettleMode::FastSettle { .. } => {
                 self.fast_settle_iteration_count = 0;
@@ -279,8 +326,11 @@ impl PhysicsOrchestratorActor {
                 info!("Starting physics simulation loop (FastSettle mode, sequential pipeline, 0ms sleep)");
             }
             SettleMode::Continuous => {
-                self.pipeline_target_interval = Duration::from_millis(16);
-                info!("Starting physics simulation loop (Continuous mode, sequential pipeline, 16ms target)");
+                self.pipeline_target_interval = simulation_quality().tick_interval();
+                info!(
+                    "Starting physics simulation loop (Continuous mode, sequential pipeline, {:?} target)",
+                    self.pipeline_target_interval
+                );
             }
         };
 
@@ -319,15 +369,22 @@ impl PhysicsOrchestratorActor {
     fn physics_step(&mut self, ctx: &mut Context<Self>) {
         let start_time = Instant::now();
 
+        // Operator pause holds the pipeline until ControlSimulation resumes or
+        // steps it; a requested step runs even if the layout has settled.
+        let gate = simulation_control().gate();
+        if gate == StepGate::Hold {
+            return;
+        }
+
         // In FastSettle mode, skip ticks once settling is complete.
-        if self.fast_settle_complete {
+        if self.fast_settle_complete && gate == StepGate::Run {
             if self.simulation_params.is_physics_paused {
                 self.handle_physics_paused_state(ctx);
             }
             return;
         }
 
-        if self.simulation_params.is_physics_paused {
+        if self.simulation_params.is_physics_paused && gate == StepGate::Run {
             self.handle_physics_paused_state(ctx);
             return;
         }
@@ -373,13 +430,21 @@ impl PhysicsOrchestratorActor {
 
         if let Some(gpu_addr) = self.gpu_compute_addr.clone() {
             // GPU path: ComputeForces is sent, and PhysicsStepCompleted will
-            // come back to drive the next step.
+            // come back to drive the next step. The GPU may skip it, so a
+            // single step is counted when the completion arrives.
+            self.single_step_in_flight = gate == StepGate::Single;
             self.execute_gpu_physics_step(&gpu_addr, ctx);
         } else {
             // CPU fallback: no PhysicsStepCompleted will come back, so
             // re-schedule the next step directly.
+            if gate == StepGate::Single {
+                simulation_control().step_taken();
+            }
+            let cpu_start = Instant::now();
             self.execute_cpu_physics_step(ctx);
-            self.schedule_next_pipeline_step(ctx, self.pipeline_target_interval);
+            let busy_ms = cpu_start.elapsed().as_secs_f32() * 1000.0;
+            let delay = self.budgeted_delay(BudgetResource::Cpu, busy_ms, self.pipeline_target_interval);
+            self.schedule_next_pipeline_step(ctx, delay);
         }
 
         let step_time = start_time.elapsed();
@@ -527,18 +592,41 @@ impl PhysicsOrchestratorActor {
     fn update_graph_data(&mut self, graph_data: Arc<GraphData>) {
         let prev_count = self.last_node_count;
         let new_count = graph_data.nodes.len();
-        self.graph_data_ref = Some(graph_data.clone());
+        let prev_graph = self.graph_data_ref.replace(graph_data.clone());
         self.last_node_count = new_count;
+        // A different graph has a different energy profile.
+        self.stability_monitor.reset();
 
         // If GPU is already initialised and the graph just grew/changed, forward
         // the new graph to ForceComputeActor — otherwise the GPU keeps computing
         // on the previous (stale) graph forever. This is the documented
         // "UpdateGPUGraphData propagation gap" from the ADR-090 sprint.
-        if self.gpu_initialized
-            && self.gpu_compute_addr.is_some()
-            && new_count != prev_count
-        {
-            if let Some(ref gpu_addr) = self.gpu_compute_addr {
+        if !self.gpu_initialized {
+            return;
+        }
+        let Some(ref gpu_addr) = self.gpu_compute_addr else {
+            return;
+        };
+
+        // A sync that only re-weights existing links (a new mention between
+        // pages already connected) keeps the node and edge structure, so the
+        // springs are patched in place instead of re-uploading the graph.
+        let weight_only = match prev_graph {
+            Some(ref prev) if new_count == prev_count => {
+                crate::physics::edge_weights::weight_changes(&prev.edges, &graph_data.edges)
+            }
+            _ => None,
+        };
+        match weight_only {
+            Some(updates) if updates.is_empty() => {}
+            Some(updates) => {
+                info!(
+                    "PhysicsOrchestratorActor: {} edge weight(s) changed — forwarding EdgeWeightsUpdated to GPU",
+                    updates.len()
+                );
+                gpu_addr.do_send(EdgeWeightsUpdated { updates });
+            }
+            None => {
                 let msg_id = crate::actors::messaging::MessageId::new();
                 let tracker = self.message_tracker.clone();
                 actix::spawn(async move {
@@ -550,7 +638,7 @@ impl PhysicsOrchestratorActor {
                         .await;
                 });
                 info!(
-                    "PhysicsOrchestratorActor: graph grew {} → {} nodes — forwarding UpdateGPUGraphData to GPU",
+                    "PhysicsOrchestratorActor: graph changed ({} → {} nodes) — forwarding UpdateGPUGraphData to GPU",
                     prev_count, new_count
                 );
                 gpu_addr.do_send(UpdateGPUGraphData {
@@ -583,22 +671,33 @@ impl PhysicsOrchestratorActor {
         // immutable initial graph data (never updated with GPU positions).
         // Broadcasting stale positions would also steal the throttle window
         // from the GPU-computed path, causing 0 real broadcasts.
+        //
+        // FastSettle reads back every step: its plateau detection needs the
+        // energy of each one.
         use crate::actors::messages::ComputeForces;
+        let read_back = matches!(
+            self.simulation_params.settle_mode,
+            SettleMode::FastSettle { .. }
+        ) || simulation_quality().reads_back(self.current_iteration as u32);
         gpu_addr.do_send(ComputeForces {
             correlation_id: None,
+            read_back,
         });
 
-        if self.current_iteration % 300 == 0 {
-            info!(
-                "PhysicsOrchestratorActor: step {} dispatched ComputeForces to GPU",
-                self.current_iteration
-            );
-        }
+        trace!(
+            "PhysicsOrchestratorActor: step {} dispatched ComputeForces to GPU",
+            self.current_iteration
+        );
+        crate::log_throttled!(
+            log::Level::Info,
+            "PhysicsOrchestratorActor: {} steps dispatched to GPU",
+            self.current_iteration
+        );
     }
 
     #[allow(dead_code)]
     fn handle_physics_step_completion(&mut self) {
-        debug!("Physics step {} completed", self.current_iteration);
+        trace!("Physics step {} completed", self.current_iteration);
     }
 
     fn execute_cpu_physics_step(&mut self, _ctx: &mut Context<Self>) {
@@ -608,6 +707,75 @@ impl PhysicsOrchestratorActor {
         }
     }
 
+    /// Step every release motion by the time since the last broadcast and
+    /// return the positions and velocities to show this frame. A motion's
+    /// final frame is still returned before it is dropped, and written into
+    /// the simulation so the node carries on from there.
+    fn advance_release_motions(&mut self, now: Instant) -> HashMap<u32, ([f32; 3], [f32; 3])> {
+        if self.release_motions.is_empty() {
+            return HashMap::new();
+        }
+        let dt = now
+            .duration_since(self.last_broadcast_time)
+            .as_secs_f32()
+            .min(0.1);
+        let mut frame = HashMap::with_capacity(self.release_motions.len());
+        let mut finished = Vec::new();
+        self.release_motions.retain(|node_id, motion| {
+            let moving = motion.advance(dt);
+            frame.insert(*node_id, motion.current());
+            if !moving {
+                finished.push(*node_id);
+            }
+            moving
+        });
+        for node_id in finished {
+            let (position, velocity) = frame[&node_id];
+            self.set_simulated_motion(node_id, position, velocity, false);
+        }
+        frame
+    }
+
+    /// Write a released node's position and velocity into the GPU
+    /// simulation; broadcast overrides alone are lost when they end.
+    fn set_simulated_motion(&self, node_id: u32, position: [f32; 3], velocity: [f32; 3], pinned: bool) {
+        if let Some(ref gpu_addr) = self.gpu_compute_addr {
+            gpu_addr.do_send(SetNodeMotion {
+                node_id,
+                position,
+                velocity,
+                pinned,
+            });
+        }
+    }
+
+    /// Apply the configured release behaviour to a node whose drag just ended.
+    fn release_dragged_node(&mut self, node_id: u32) {
+        let Some(track) = self.drag_tracks.remove(&node_id) else {
+            return;
+        };
+        let plan = ReleasePlan::for_release(
+            &self.simulation_params.drag_release,
+            &track,
+            self.simulation_params.max_velocity,
+        );
+        debug!("Node {} released: {:?}", node_id, plan);
+        match plan {
+            ReleasePlan::Free => {
+                self.set_simulated_motion(node_id, track.position(), [0.0; 3], false);
+            }
+            ReleasePlan::Pin([x, y, z]) => {
+                self.user_pinned_nodes.insert(node_id, (x, y, z));
+                self.set_simulated_motion(node_id, [x, y, z], [0.0; 3], true);
+            }
+            ReleasePlan::Move(motion) => {
+                let (position, velocity) = motion.current();
+                self.set_simulated_motion(node_id, position, velocity, false);
+                self.release_motions.insert(node_id, motion);
+            }
+        }
+    }
+
     #[allow(dead_code)]
     fn broadcast_position_updates(
         &mut self,
@@ -620,6 +788,7 @@ impl PhysicsOrchestratorActor {
         if now.duration_since(self.last_broadcast_time) < broadcast_interval {
             return;
         }
+        let released = self.advance_release_motions(now);
         self.last_broadcast_time = now;
 
         // Check if client coordinator is available
@@ -636,6 +805,9 @@ impl PhysicsOrchestratorActor {
                     node_data.vx = 0.0;
                     node_data.vy = 0.0;
                     node_data.vz = 0.0;
+                } else if let Some(&(position, velocity)) = released.get(&node_id) {
+                    [node_data.x, node_data.y, node_data.z] = position;
+                    [node_data.vx, node_data.vy, node_data.vz] = velocity;
                 }
                 final_positions.push((node_id, node_data));
             }
@@ -794,6 +966,7 @@ impl PhysicsOrchestratorActor {
             // chance once physics is re-triggered.
             self.gpu_degraded = false;
             self.consecutive_gpu_failures = 0;
+            self.clear_anomaly();
 
             // Reset fast-settle state so a new settle cycle begins if in FastSettle mode.
             self.fast_settle_iteration_count = 0;
@@ -815,6 +988,113 @@ impl PhysicsOrchestratorActor {
         // Clients detect settled state when position updates stop arriving.
     }
 
+    /// Pause on an unstable frame: stop the pipeline, snapshot the offending
+    /// state for debugging and tell clients why positions stopped.
+    fn halt_for_anomaly(
+        &mut self,
+        mut anomaly: SimulationAnomaly,
+        positions: &[(u32, BinaryNodeData)],
+    ) {
+        let id = snapshot_id(&anomaly);
+        anomaly.snapshot = Some(id.clone());
+        error!(
+            "PhysicsOrchestratorActor: unstable simulation at step {} ({}: {}); pausing, snapshot {}",
+            self.current_iteration, anomaly.reason, anomaly.detail, id
+        );
+
+        self.simulation_params.is_physics_paused = true;
+        self.pipeline_step_pending = false;
+        self.pipeline_step_pending_since = None;
+
+        let snapshot = anomaly.clone();
+        let iteration = self.current_iteration;
+        let params = self.simulation_params.clone();
+        let positions = positions.to_vec();
+        tokio::task::spawn_blocking(move || {
+            let dir = snapshot_dir();
+            if let Err(e) = write_snapshot(&dir, &snapshot, iteration, &params, &positions) {
+                warn!("PhysicsOrchestratorActor: failed to write simulation snapshot: {}", e);
+            }
+        });
+
+        if let Some(ref client_coord_addr) = self.client_coordinator_addr {
+            use crate::actors::messages::BroadcastMessage;
+            client_coord_addr.do_send(BroadcastMessage {
+                message: serde_json::json!({
+                    "type": "simulationPaused",
+                    "reason": anomaly.reason,
+                    "anomaly": anomaly,
+                })
+                .to_string(),
+            });
+        }
+        self.anomaly = Some(anomaly);
+    }
+
+    fn clear_anomaly(&mut self) {
+        if let Some(anomaly) = self.anomaly.take() {
+            info!(
+                "PhysicsOrchestratorActor: resuming after {} anomaly",
+                anomaly.reason
+            );
+        }
+        self.stability_monitor.reset();
+    }
+
+    /// Whether GPU steps should currently be completing. Paused, settled,
+    /// halted and not-yet-initialised states are all legitimately quiet.
+    fn expects_progress(&self) -> bool {
+        self.simulation_running.load(Ordering::SeqCst)
+            && self.gpu_compute_addr.is_some()
+            && self.gpu_initialized
+            && !self.gpu_degraded
+            && !self.simulation_params.is_physics_paused
+            && !self.fast_settle_complete
+            && self.anomaly.is_none()
+            && self.last_node_count > 0
+            && simulation_control().gate() != StepGate::Hold
+    }
+
+    /// Restart the pipeline after the watchdog saw no progress. The GPU keeps
+    /// its current positions; clients are told why updates paused.
+    fn handle_stall(&mut self, action: StallAction, ctx: &mut Context<Self>) {
+        let (incident, recovering) = match action {
+            StallAction::Restart(incident) => (incident, true),
+            StallAction::GiveUp(incident) => (incident, false),
+        };
+
+        if recovering {
+            warn!(
+                "PhysicsOrchestratorActor: no progress for {}ms after step {} ({:?}); restarting pipeline (attempt {}/{})",
+                incident.stalled_ms, incident.iteration, incident.kind, incident.attempt, MAX_RESTARTS
+            );
+            if let Some(ref gpu_addr) = self.gpu_compute_addr {
+                gpu_addr.do_send(RecoverStalledStep);
+            }
+            self.pipeline_step_pending = false;
+            self.pipeline_step_pending_since = None;
+            self.schedule_next_pipeline_step(ctx, Duration::ZERO);
+        } else {
+            error!(
+                "PhysicsOrchestratorActor: pipeline still stalled after {} restarts; \
+                 leaving it until a step completes",
+                MAX_RESTARTS
+            );
+        }
+
+        if let Some(ref client_coord_addr) = self.client_coordinator_addr {
+            use crate::actors::messages::BroadcastMessage;
+            client_coord_addr.do_send(BroadcastMessage {
+                message: serde_json::json!({
+                    "type": "simulationStalled",
+                    "recovering": recovering,
+                    "incident": incident,
+                })
+                .to_string(),
+            });
+        }
+    }
+
     fn broadcast_physics_resumed(&self) {
         info!("PhysicsOrchestratorActor: Physics resumed — new settle cycle started");
     }
@@ -844,6 +1124,18 @@ impl PhysicsOrchestratorActor {
         }
     }
 
+    /// Apply the compute budget to the pipeline's natural `delay` after a step
+    /// that kept `resource` busy for `busy_ms`.
+    fn budgeted_delay(&mut self, resource: BudgetResource, busy_ms: f32, delay: Duration) -> Duration {
+        let required = self.compute_budget.record(resource, busy_ms, Instant::now());
+        if required > delay {
+            self.compute_budget.note_throttled(required - delay);
+            required
+        } else {
+            delay
+        }
+    }
+
     pub fn get_physics_status(&self) -> PhysicsStatus {
         PhysicsStatus {
             simulation_running: self.simulation_running.load(Ordering::SeqCst),
@@ -853,6 +1145,8 @@ impl PhysicsOrchestratorActor {
             node_count: self.last_node_count,
             performance: self.performance_metrics.clone(),
             current_params: self.simulation_params.clone(),
+            compute_budget: self.compute_budget.usage(Instant::now()),
+            anomaly: self.anomaly.clone(),
         }
     }
 
@@ -1067,6 +1361,9 @@ pub struct PhysicsStatus {
     pub node_count: usize,
     pub performance: PhysicsPerformanceMetrics,
     pub current_params: SimulationParams,
+    pub compute_budget: ComputeBudgetUsage,
+    /// Why the stability monitor last halted the simulation, if it did.
+    pub anomaly: Option<SimulationAnomaly>,
 }
 
 impl Actor for PhysicsOrchestratorActor {
@@ -1105,6 +1402,13 @@ impl Actor for PhysicsOrchestratorActor {
                 }
             }
 
+            // Frame-progress watchdog: catches a GPU step that never replies or
+            // is skipped every time, which the pending flag above cannot see.
+            let expecting = act.expects_progress();
+            if let Some(action) = act.watchdog.check(expecting, Instant::now()) {
+                act.handle_stall(action, ctx);
+            }
+
             // GPU recovery: if we have graph data but no working GPU, retry initialization.
             // This handles the race condition where GPU actor's mailbox closes before
             // graph data arrives, leaving physics permanently dead.
@@ -1173,11 +1477,22 @@ impl Handler<UpdateNodePositions> for PhysicsOrchestratorActor {
 
         let node_count = msg.positions.len();
 
-        if let Some(ref client_coord_addr) = self.client_coordinator_addr {
+        // Steps already in flight when the monitor halted physics still land
+        // here; never broadcast them.
+        if self.anomaly.is_some() {
+            return Ok(());
+        }
+        if let Some(anomaly) = self.stability_monitor.check(&msg.positions) {
+            self.halt_for_anomaly(anomaly, &msg.positions);
+            return Ok(());
+        }
+
+        if let Some(client_coord_addr) = self.client_coordinator_addr.clone() {
             // Throttle broadcasts to 60 FPS max
             let now = std::time::Instant::now();
             let broadcast_interval = std::time::Duration::from_millis(16); // 60 FPS
             if now.duration_since(self.last_broadcast_time) >= broadcast_interval {
+                let released = self.advance_release_motions(now);
                 self.last_broadcast_time = now;
 
                 // Apply user pinning — override GPU positions for nodes being dragged
@@ -1195,6 +1510,16 @@ impl Handler<UpdateNodePositions> for PhysicsOrchestratorActor {
                                 vy: 0.0,
                                 vz: 0.0,
                             }
+                        } else if let Some(&(position, velocity)) = released.get(node_id) {
+                            BinaryNodeDataClient {
+                                node_id: *node_id,
+                                x: position[0],
+                                y: position[1],
+                                z: position[2],
+                                vx: velocity[0],
+                                vy: velocity[1],
+                                vz: velocity[2],
+                            }
                         } else {
                             BinaryNodeDataClient {
                                 node_id: *node_id,
@@ -1294,7 +1619,28 @@ impl Handler<NodeInteractionMessage> for PhysicsOrchestratorActor {
     type Result = Result<(), VisionClawError>;
 
     fn handle(&mut self, msg: NodeInteractionMessage, ctx: &mut Self::Context) -> Self::Result {
-        info!("Node interaction detected: {:?}", msg.interaction_type);
+        debug!("Node interaction detected: {:?}", msg.interaction_type);
+
+        match (&msg.interaction_type, msg.position) {
+            (NodeInteractionType::Dragged, Some(position)) => {
+                // Picking a node up cancels whatever its last release left behind.
+                self.release_motions.remove(&msg.node_id);
+                if self.user_pinned_nodes.remove(&msg.node_id).is_some() {
+                    if let Some(ref gpu_addr) = self.gpu_compute_addr {
+                        gpu_addr.do_send(UnpinNode {
+                            node_id: msg.node_id,
+                        });
+                    }
+                }
+                let now = Instant::now();
+                self.drag_tracks
+                    .entry(msg.node_id)
+                    .and_modify(|track| track.moved(position, now))
+                    .or_insert_with(|| DragTrack::start(position, now));
+            }
+            (NodeInteractionType::Released, _) => self.release_dragged_node(msg.node_id),
+            _ => {}
+        }
 
         if self
             .simulation_params
@@ -1312,6 +1658,53 @@ impl Handler<NodeInteractionMessage> for PhysicsOrchestratorActor {
     }
 }
 
+impl Handler<ControlSimulation> for PhysicsOrchestratorActor {
+    type Result = Result<SimulationControlStatus, String>;
+
+    fn handle(&mut self, msg: ControlSimulation, ctx: &mut Self::Context) -> Self::Result {
+        let status = simulation_control().apply(msg.command)?;
+        info!("Simulation control: {:?} -> {:?}", msg.command, status.mode);
+
+        match msg.command {
+            SimulationCommand::Pause => {}
+            // Clears an equilibrium pause too, then re-kicks the pipeline.
+            SimulationCommand::Resume => {
+                self.resume_physics(ctx);
+                if self.simulation_running.load(Ordering::SeqCst) {
+                    self.schedule_next_pipeline_step(ctx, Duration::ZERO);
+                }
+            }
+            SimulationCommand::Step { .. } => {
+                if self.simulation_running.load(Ordering::SeqCst) {
+                    self.schedule_next_pipeline_step(ctx, Duration::ZERO);
+                }
+            }
+        }
+        Ok(status)
+    }
+}
+
+impl Handler<SetSimulationQuality> for PhysicsOrchestratorActor {
+    type Result = Result<QualityPreset, String>;
+
+    fn handle(&mut self, msg: SetSimulationQuality, _ctx: &mut Self::Context) -> Self::Result {
+        let preset = set_simulation_quality(msg.level);
+        // FastSettle keeps running flat out; the new cadence applies once it
+        // hands over to Continuous.
+        if matches!(self.simulation_params.settle_mode, SettleMode::Continuous) {
+            self.pipeline_target_interval = preset.tick_interval();
+        }
+        info!(
+            "Simulation quality -> {:?} ({} Hz, collision {}, readback every {})",
+            preset.level,
+            preset.tick_hz,
+            preset.collision,
+            preset.readback_every
+        );
+        Ok(preset)
+    }
+}
+
 impl Handler<ForceResumePhysics> for PhysicsOrchestratorActor {
     type Result = Result<(), VisionClawError>;
 
@@ -1447,6 +1840,7 @@ impl Handler<UpdateSimulationParams> for PhysicsOrchestratorActor {
             // and restart the pipeline so the GPU can re-converge under new params.
             if self.simulation_params.is_physics_paused {
                 self.simulation_params.is_physics_paused = false;
+                self.clear_anomaly();
                 self.broadcast_physics_resumed();
                 info!("PhysicsOrchestratorActor: Unpausing physics for new settle cycle");
             }
@@ -1457,7 +1851,7 @@ impl Handler<UpdateSimulationParams> for PhysicsOrchestratorActor {
                     self.pipeline_target_interval = Duration::ZERO;
                 }
                 SettleMode::Continuous => {
-                    self.pipeline_target_interval = Duration::from_millis(16);
+                    self.pipeline_target_interval = simulation_quality().tick_interval();
                 }
             }
 
@@ -1702,6 +2096,21 @@ impl Handler<crate::actors::messages::GPUInitFailed> for PhysicsOrchestratorActo
     }
 }
 
+/// A GPU readback too corrupt to scrub; pause through the anomaly path.
+impl Handler<crate::actors::messages::ReadbackAnomaly> for PhysicsOrchestratorActor {
+    type Result = ();
+
+    fn handle(
+        &mut self,
+        msg: crate::actors::messages::ReadbackAnomaly,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        if self.anomaly.is_none() {
+            self.halt_for_anomaly(msg.anomaly, &msg.positions);
+        }
+    }
+}
+
 /// Handler for PhysicsStepCompleted — closes the sequential pipeline loop.
 ///
 /// When ForceComputeActor finishes a GPU physics step (including position readback
@@ -1721,6 +2130,14 @@ impl Handler<crate::actors::messages::PhysicsStepCompleted> for PhysicsOrchestra
         msg: crate::actors::messages::PhysicsStepCompleted,
         ctx: &mut Self::Context,
     ) -> Self::Result {
+        self.watchdog
+            .record_step(msg.iteration, msg.skipped, Instant::now());
+
+        // A skipped single step stays queued and is retried on the next tick.
+        if std::mem::take(&mut self.single_step_in_flight) && !msg.skipped {
+            simulation_control().step_taken();
+        }
+
         // Update performance metrics with the actual step duration
         let step_duration = Duration::from_secs_f32(msg.step_duration_ms / 1000.0);
         self.update_performance_metrics(step_duration);
@@ -1802,7 +2219,8 @@ impl Handler<crate::actors::messages::PhysicsStepCompleted> for PhysicsOrchestra
             // but casting it to f32 produces INFINITY, which would poison
             // convergence/equilibrium checks and mask a GPU error as a stable
             // state.  Treat it as "not a valid energy" and keep the previous value.
-            if !energy_invalid {
+            // A step without readback has no energy of its own either.
+            if !energy_invalid && msg.read_back {
                 stats.kinetic_energy = msg.kinetic_energy as f32;
             }
             stats.iteration_count = msg.iteration;
@@ -1916,12 +2334,12 @@ impl Handler<crate::actors::messages::PhysicsStepCompleted> for PhysicsOrchestra
                 }
 
                 self.simulation_params.settle_mode = SettleMode::Continuous;
-                self.pipeline_target_interval = Duration::from_millis(16);
+                self.pipeline_target_interval = simulation_quality().tick_interval();
 
                 info!(
                     "PhysicsOrchestratorActor: FastSettle cap {} reached (energy={:.6} > threshold={:.6}); \
-                     transitioning to Continuous mode @ 16ms cadence",
-                    max_settle_iterations, energy, energy_threshold
+                     transitioning to Continuous mode @ {:?} cadence",
+                    max_settle_iterations, energy, energy_threshold, self.pipeline_target_interval
                 );
 
                 if let Some(ref gpu_addr) = self.gpu_compute_addr {
@@ -1951,12 +2369,48 @@ impl Handler<crate::actors::messages::PhysicsStepCompleted> for PhysicsOrchestra
 
         // Compute remaining time in the target interval.
         // If the step took longer than the target, proceed immediately (Duration::ZERO).
-        let delay = self.pipeline_target_interval.saturating_sub(step_duration);
+        let mut delay = self.pipeline_target_interval.saturating_sub(step_duration);
+        if !msg.skipped {
+            delay = self.budgeted_delay(BudgetResource::Gpu, msg.step_duration_ms, delay);
+        }
 
         self.schedule_next_pipeline_step(ctx, delay);
     }
 }
 
+/// Replace the compute budget caps (from `computeBudget` settings).
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct SetComputeBudget {
+    pub settings: crate::config::ComputeBudgetSettings,
+}
+
+impl Handler<SetComputeBudget> for PhysicsOrchestratorActor {
+    type Result = ();
+
+    fn handle(&mut self, msg: SetComputeBudget, _ctx: &mut Self::Context) -> Self::Result {
+        info!(
+            "PhysicsOrchestratorActor: compute budget {} (gpu {:.0} ms/s, cpu {:.0}%)",
+            if msg.settings.enabled { "enabled" } else { "disabled" },
+            msg.settings.max_gpu_ms_per_second,
+            msg.settings.max_cpu_percent
+        );
+        self.compute_budget.set_settings(msg.settings);
+    }
+}
+
+#[derive(Message)]
+#[rtype(result = "ComputeBudgetUsage")]
+pub struct GetComputeBudgetUsage;
+
+impl Handler<GetComputeBudgetUsage> for PhysicsOrchestratorActor {
+    type Result = MessageResult<GetComputeBudgetUsage>;
+
+    fn handle(&mut self, _msg: GetComputeBudgetUsage, _ctx: &mut Self::Context) -> Self::Result {
+        MessageResult(self.compute_budget.usage(Instant::now()))
+    }
+}
+
 /// Set client coordinator address for broadcasting
 #[derive(Message)]
 #[rtype(result = "()")]
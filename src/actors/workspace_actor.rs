@@ -17,8 +17,8 @@ use validator::Validate;
 
 use crate::actors::messages::{
     ArchiveWorkspace, CreateWorkspace, DeleteWorkspace, GetWorkspace, GetWorkspaceCount,
-    GetWorkspaces, LoadWorkspaces, SaveWorkspaces, ToggleFavoriteWorkspace, UpdateWorkspace,
-    WorkspaceChangeType, WorkspaceStateChanged,
+    GetWorkspaces, LoadWorkspaces, PatchWorkspaceSettings, SaveWorkspaces,
+    ToggleFavoriteWorkspace, UpdateWorkspace, WorkspaceChangeType, WorkspaceStateChanged,
 };
 use crate::models::workspace::{
     SortDirection, Workspace, WorkspaceFilter, WorkspaceListResponse, WorkspaceSortBy,
@@ -59,7 +59,12 @@ pub struct WorkspaceActor {
 impl WorkspaceActor {
     
     pub fn new() -> Self {
-        Self::with_storage_path("data/workspaces.json".to_string())
+        Self::with_storage_path(
+            crate::config::dev_config::storage()
+                .path("workspaces.json")
+                .to_string_lossy()
+                .into_owned(),
+        )
     }
 
     
@@ -450,6 +455,33 @@ impl Handler<UpdateWorkspace> for WorkspaceActor {
     }
 }
 
+impl Handler<PatchWorkspaceSettings> for WorkspaceActor {
+    type Result = Result<Workspace, String>;
+
+    fn handle(&mut self, msg: PatchWorkspaceSettings, _ctx: &mut Self::Context) -> Self::Result {
+        self.ensure_initialized()
+            .map_err(|e| format!("Failed to initialize: {}", e))?;
+
+        let workspace = self
+            .workspaces
+            .get_mut(&msg.workspace_id)
+            .ok_or_else(|| format!("Workspace with ID '{}' not found", msg.workspace_id))?;
+
+        workspace.patch_settings_overlay(msg.patch);
+        let updated_workspace = workspace.clone();
+
+        if let Err(e) = self.save_to_storage() {
+            error!("Failed to save workspaces after settings patch: {}", e);
+            return Err(format!("Failed to persist workspace settings: {}", e));
+        }
+
+        self.notify_change(&updated_workspace, WorkspaceChangeType::Updated);
+
+        info!("Updated settings overlay for workspace {}", updated_workspace.id);
+        Ok(updated_workspace)
+    }
+}
+
 impl Handler<DeleteWorkspace> for WorkspaceActor {
     type Result = Result<(), String>;
 
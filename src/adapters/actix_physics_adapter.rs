@@ -6,7 +6,7 @@
 
 use actix::prelude::*;
 use async_trait::async_trait;
-use log::{debug, info, warn};
+use log::{debug, info, trace, warn};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -162,7 +162,7 @@ impl GpuPhysicsAdapter for ActixPhysicsAdapter {
 
     
     async fn compute_forces(&mut self) -> PortResult<Vec<NodeForce>> {
-        debug!("Computing forces via actor");
+        trace!("Computing forces via actor");
 
         let addr = self.actor_addr.as_ref().ok_or_else(|| {
             crate::ports::gpu_physics_adapter::GpuPhysicsAdapterError::GraphNotLoaded
@@ -193,7 +193,7 @@ impl GpuPhysicsAdapter for ActixPhysicsAdapter {
         &mut self,
         forces: &[NodeForce],
     ) -> PortResult<Vec<(u32, f32, f32, f32)>> {
-        debug!("Updating positions for {} nodes via actor", forces.len());
+        trace!("Updating positions for {} nodes via actor", forces.len());
         let msg = UpdatePositionsMessage::new(forces.to_vec());
         let result = self.send_message(msg).await?;
         result.map_err(|e| {
@@ -203,7 +203,7 @@ impl GpuPhysicsAdapter for ActixPhysicsAdapter {
 
     
     async fn step(&mut self) -> PortResult<PhysicsStepResult> {
-        debug!("Executing physics step via actor");
+        trace!("Executing physics step via actor");
         let msg = PhysicsStepMessage;
         let result = self.send_message(msg).await?;
         result.map_err(|e| {
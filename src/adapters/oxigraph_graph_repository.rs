@@ -43,7 +43,7 @@ use oxigraph::store::{StorageError, Store};
 
 use crate::actors::graph_actor::{AutoBalanceNotification, PhysicsState};
 use visionclaw_domain::models::constraints::ConstraintSet;
-use visionclaw_domain::models::edge::Edge;
+use visionclaw_domain::models::edge::{Edge, EdgeProvenance};
 use visionclaw_domain::models::graph::GraphData;
 use visionclaw_domain::models::node::Node;
 use crate::ports::graph_repository::{
@@ -121,6 +121,35 @@ fn escape_literal(s: &str) -> String {
     out
 }
 
+/// `vc:provenance` triple for an edge, as an escaped JSON literal.
+fn provenance_triple(indent: &str, iri: &str, edge: &Edge) -> String {
+    match edge
+        .provenance
+        .as_ref()
+        .and_then(|p| serde_json::to_string(p).ok())
+    {
+        Some(json) => format!(
+            "{indent}<{iri}> vc:provenance \"{json}\" .\n",
+            json = escape_literal(&json)
+        ),
+        None => String::new(),
+    }
+}
+
+/// Stored provenance, or one rebuilt from id/type for edges written before
+/// provenance was persisted.
+fn load_provenance(
+    sol: &oxigraph::sparql::QuerySolution,
+    edge_iri: &str,
+    etype: Option<&str>,
+    weight: f32,
+) -> EdgeProvenance {
+    sol.get("prov")
+        .and_then(term_to_string)
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_else(|| EdgeProvenance::reconstruct(edge_iri, etype, weight))
+}
+
 /// Convert an Oxigraph storage/eval error into the port's error variant.
 fn access<E: std::fmt::Display>(e: E) -> GraphRepositoryError {
     GraphRepositoryError::AccessError(e.to_string())
@@ -522,6 +551,7 @@ impl GraphRepository for OxigraphGraphRepository {
                     owl = owl
                 ));
             }
+            update.push_str(&provenance_triple("    ", &iri, edge));
             update.push_str("  }\n");
         }
 
@@ -544,6 +574,7 @@ impl GraphRepository for OxigraphGraphRepository {
                 "  <{iri}> vc:relationshipType \"{etype}\" .\n",
                 etype = etype
             ));
+            update.push_str(&provenance_triple("  ", &iri, edge));
         }
 
         update.push_str("}\n");
@@ -1374,13 +1405,14 @@ fn load_nodes_in_graph(store: &Store, graph_iri: &str) -> RepoResult<Vec<Node>>
 fn load_edges_in_graph(store: &Store, graph_iri: &str) -> RepoResult<Vec<Edge>> {
     let prologue = OxigraphGraphRepository::PROLOGUE;
     let q = format!(
-        "{p}SELECT ?edge ?src ?tgt ?weight ?etype WHERE {{\n  \
+        "{p}SELECT ?edge ?src ?tgt ?weight ?etype ?prov WHERE {{\n  \
          GRAPH <{graph}> {{\n    \
          ?edge a vc:KGEdge .\n    \
          ?edge vc:source ?src .\n    \
          ?edge vc:target ?tgt .\n    \
          OPTIONAL {{ ?edge vc:weight ?weight }} .\n    \
-         OPTIONAL {{ ?edge vc:relationshipType ?etype }} .\n  \
+         OPTIONAL {{ ?edge vc:relationshipType ?etype }} .\n    \
+         OPTIONAL {{ ?edge vc:provenance ?prov }} .\n  \
          }}\n}}",
         p = prologue,
         graph = graph_iri,
@@ -1419,6 +1451,7 @@ fn load_edges_in_graph(store: &Store, graph_iri: &str) -> RepoResult<Vec<Edge>>
             .unwrap_or(1.0);
         let etype = sol.get("etype").and_then(term_to_string);
 
+        let provenance = load_provenance(&sol, &edge_iri, etype.as_deref(), weight);
         out.push(Edge {
             id: edge_iri,
             source,
@@ -1427,6 +1460,7 @@ fn load_edges_in_graph(store: &Store, graph_iri: &str) -> RepoResult<Vec<Edge>>
             edge_type: etype,
             owl_property_iri: None,
             metadata: None,
+            provenance: Some(provenance),
         });
     }
 
@@ -1439,12 +1473,13 @@ fn load_edges_in_graph(store: &Store, graph_iri: &str) -> RepoResult<Vec<Edge>>
 fn load_bridge_edges(store: &Store) -> RepoResult<Vec<Edge>> {
     let prologue = OxigraphGraphRepository::PROLOGUE;
     let q = format!(
-        "{p}SELECT ?edge ?src ?tgt ?weight ?etype WHERE {{\n  \
+        "{p}SELECT ?edge ?src ?tgt ?weight ?etype ?prov WHERE {{\n  \
          ?edge a vc:BridgeEdge .\n  \
          ?edge vc:source ?src .\n  \
          ?edge vc:target ?tgt .\n  \
          OPTIONAL {{ ?edge vc:weight ?weight }} .\n  \
-         OPTIONAL {{ ?edge vc:relationshipType ?etype }} .\n}}",
+         OPTIONAL {{ ?edge vc:relationshipType ?etype }} .\n  \
+         OPTIONAL {{ ?edge vc:provenance ?prov }} .\n}}",
         p = prologue,
     );
 
@@ -1481,6 +1516,7 @@ fn load_bridge_edges(store: &Store) -> RepoResult<Vec<Edge>> {
             .and_then(term_to_string)
             .or_else(|| Some("bridge_to".to_string()));
 
+        let provenance = load_provenance(&sol, &edge_iri, etype.as_deref(), weight);
         out.push(Edge {
             id: edge_iri,
             source,
@@ -1489,6 +1525,7 @@ fn load_bridge_edges(store: &Store) -> RepoResult<Vec<Edge>> {
             edge_type: etype,
             owl_property_iri: None,
             metadata: None,
+            provenance: Some(provenance),
         });
     }
 
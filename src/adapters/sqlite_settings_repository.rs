@@ -312,6 +312,29 @@ impl SqliteSettingsRepository {
             .map_err(map_db_err)
     }
 
+    /// Delete the SHA1 rows for files that are no longer part of the source.
+    pub async fn delete_file_sha1s(&self, names: &[String]) -> Result<(), SettingsRepositoryError> {
+        if names.is_empty() {
+            return Ok(());
+        }
+        let names_owned: Vec<String> = names.to_vec();
+        self.conn
+            .call(move |c| {
+                let tx = c.transaction()?;
+                {
+                    let mut stmt =
+                        tx.prepare_cached("DELETE FROM sync_file_metadata WHERE file_name = ?1")?;
+                    for name in &names_owned {
+                        stmt.execute(rusqlite::params![name])?;
+                    }
+                }
+                tx.commit()?;
+                Ok(())
+            })
+            .await
+            .map_err(map_db_err)
+    }
+
     /// Read a sync config value by key.
     pub async fn get_sync_config(
         &self,
@@ -35,6 +35,7 @@ use crate::services::bots_client::BotsClient;
 use crate::services::github::content_enhanced::EnhancedContentAPI;
 use crate::services::github::{ContentAPI, GitHubClient};
 use crate::services::github_sync_service::GitHubSyncService;
+use crate::services::webhook_service::{self, GraphEvent};
 use crate::services::management_api_client::ManagementApiClient;
 use crate::services::nostr_service::NostrService;
 use crate::services::perplexity_service::PerplexityService;
@@ -342,6 +343,8 @@ pub struct AppState {
     /// Health degradation reason. `None` means healthy; `Some(reason)` means degraded.
     /// Uses `std::sync::RwLock` (not tokio) so it can be read synchronously in health checks.
     pub degraded_reason: Arc<std::sync::RwLock<Option<String>>>,
+    /// Whether the knowledge graph has been built; reported by `/readyz`.
+    pub graph_readiness: Arc<crate::services::graph_readiness::GraphReadinessTracker>,
 
     /// Shared per-node analytics data populated by GPU analytics actors.
     /// Maps node_id -> NodeAnalytics{cluster_id, community_id, anomaly, centrality}.
@@ -372,7 +375,7 @@ impl AppState {
         info!("[AppState::new] Creating repository adapters for hexagonal architecture (ADR-11 Oxigraph)");
 
         // Open Oxigraph store — shared across ontology + graph repositories (ADR-11 §D1)
-        let data_dir = std::env::var("DATA_DIR").unwrap_or_else(|_| "./data".to_string());
+        let data_dir = crate::config::dev_config::storage().data_dir();
         let oxigraph_path = std::path::Path::new(&data_dir).join("oxigraph");
         let onto_repo = Arc::new(
             OxigraphOntologyRepository::open(&oxigraph_path)
@@ -467,6 +470,12 @@ impl AppState {
                             warn!("    ... and {} more errors", stats.errors.len() - 5);
                         }
                     }
+                    webhook_service::emit(GraphEvent::SyncCompleted {
+                        files_scanned: stats.total_files,
+                        files_processed: stats.kg_files_processed + stats.ontology_files_processed,
+                        duration_ms: stats.duration.as_millis() as u64,
+                        error_count: stats.errors.len(),
+                    });
 
                     // Load synced data into graph actor (if it's ready)
                     if let Some(graph_addr) = &*graph_service_addr_clone_for_sync.lock().await {
@@ -483,6 +492,10 @@ impl AppState {
                     log::error!("❌ Background GitHub sync failed after {:?}: {}", elapsed, e);
                     log::error!("❌ Error details: {:?}", e);
                     log::error!("⚠️  Databases may have partial data - use manual import API if needed");
+                    webhook_service::emit(GraphEvent::Error {
+                        source: "github_sync".to_string(),
+                        message: e.to_string(),
+                    });
                 }
             }
         });
@@ -542,6 +555,11 @@ impl AppState {
         let mut client_coordinator = ClientCoordinatorActor::new();
         client_coordinator.set_settings_repository(sqlite_settings_repository.clone());
         client_coordinator.set_node_analytics(node_analytics.clone());
+        client_coordinator.set_authority_policy(
+            crate::actors::physics_authority::AuthorityPolicy::parse(
+                &settings.system.websocket.physics_authority_policy,
+            ),
+        );
         let client_manager_addr = client_coordinator.start();
 
         // ADR-059 Phase 2b: spawn the agent-embodiment beam actor once at startup.
@@ -1138,6 +1156,7 @@ impl AppState {
             client_message_rx: Arc::new(tokio::sync::Mutex::new(client_message_rx)),
             ontology_pipeline_service,
             degraded_reason: Arc::new(std::sync::RwLock::new(None)),
+            graph_readiness: Arc::new(Default::default()),
             node_analytics,
             node_sssp,
         };
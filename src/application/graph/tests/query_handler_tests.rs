@@ -102,6 +102,7 @@ impl MockGraphRepository {
                 edge_type: Some("default".to_string()),
                 owl_property_iri: None,
                 metadata: None,
+                provenance: None,
             });
         }
 
@@ -323,6 +323,9 @@ export interface WebSocketSettings {
   reconnect_attempts: number;
   reconnect_delay: number;
   update_rate: number;
+  echo_suppression_ms: number;
+  physics_authority_policy: string;
+  idle_hibernation_ms: number;
 }
 
 // Security settings
@@ -341,6 +344,7 @@ export interface SecuritySettings {
 // Debug settings
 export interface DebugSettings {
   enabled: boolean;
+  hotPathLogIntervalSecs: number;
 }
 
 // System settings
@@ -353,6 +357,27 @@ export interface SystemSettings {
   custom_backend_url?: string;
 }
 
+// XR gesture mapping
+export type GestureAction = 'none' | 'select' | 'drag' | 'focus';
+
+export interface XRGestureSettings {
+  pinch: GestureAction;
+  grab: GestureAction;
+  point: GestureAction;
+  pick_radius: number;
+}
+
+// Positional audio cue catalog
+export interface AudioCueDefinition {
+  sound: string;
+  volume: number;
+  ref_distance: number;
+}
+
+export interface XRAudioCueSettings {
+  cues: Record<string, AudioCueDefinition>;
+}
+
 // XR settings
 export interface XRSettings {
   enabled?: boolean;
@@ -376,6 +401,8 @@ export interface XRSettings {
   hand_ray_color: string;
   hand_ray_width: number;
   gesture_smoothing: number;
+  gestures: XRGestureSettings;
+  audio_cues: XRAudioCueSettings;
   enable_haptics: boolean;
   haptic_intensity: number;
   drag_threshold: number;
@@ -437,6 +464,9 @@ export interface OpenAISettings {
   base_url?: string;
   timeout?: number;
   rate_limit?: number;
+  chat_model?: string;
+  embedding_model?: string;
+  embed_on_startup?: boolean;
 }
 
 export interface KokoroSettings {
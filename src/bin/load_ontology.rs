@@ -20,9 +20,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     info!("Starting ontology loader (Oxigraph backend, ADR-11)...");
 
     // 1. Initialize Oxigraph store
-    use std::env;
-
-    let data_dir = env::var("DATA_DIR").unwrap_or_else(|_| "./data".to_string());
+    let data_dir = visionclaw_server::config::dev_config::storage().data_dir();
     let oxigraph_path = std::path::Path::new(&data_dir).join("oxigraph");
     info!("Opening Oxigraph store at: {}", oxigraph_path.display());
 
@@ -24,7 +24,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     dotenvy::dotenv().ok();
 
     // Open Oxigraph store
-    let data_dir = std::env::var("DATA_DIR").unwrap_or_else(|_| "./data".to_string());
+    let data_dir = visionclaw_server::config::dev_config::storage().data_dir();
     let oxigraph_path = std::path::Path::new(&data_dir).join("oxigraph");
     log::info!("Opening Oxigraph store at: {}", oxigraph_path.display());
 
@@ -3,6 +3,7 @@
 // These settings control internal behavior, performance tuning, and debug features
 
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
 static DEV_CONFIG: OnceLock<DevConfig> = OnceLock::new();
@@ -15,6 +16,10 @@ pub struct DevConfig {
     pub rendering: RenderingInternals,
     pub performance: PerformanceInternals,
     pub debug: DebugInternals,
+    #[serde(default)]
+    pub storage: StorageInternals,
+    #[serde(default)]
+    pub gpu: GpuInternals,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -94,6 +99,10 @@ pub struct CudaInternals {
     
     pub max_nodes: u32,
     pub max_edges: u32,
+
+    /// Storage precision for integrated positions and velocities.
+    #[serde(default)]
+    pub state_precision: crate::utils::unified_gpu_compute::StatePrecision,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -146,6 +155,18 @@ pub struct RenderingInternals {
     pub lod_distance_high: f32,
     pub lod_distance_medium: f32,
     pub lod_distance_low: f32,
+
+    /// Store a Latin rendering of non-Latin labels in node metadata.
+    #[serde(default = "default_romanize_labels")]
+    pub romanize_labels: bool,
+
+    /// Hex colours tags are hashed into; empty uses the built-in palette.
+    #[serde(default)]
+    pub tag_palette: Vec<String>,
+}
+
+fn default_romanize_labels() -> bool {
+    true
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -198,6 +219,91 @@ pub struct DebugInternals {
     pub profile_sample_rate: f32,
 }
 
+/// Where persisted server data lives. Every file path under the data
+/// directory is resolved through here so the server runs outside the
+/// container layout too.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageInternals {
+    /// Root for server data (databases, object store, snapshots,
+    /// recordings). The `DATA_DIR` environment variable overrides it.
+    pub root: PathBuf,
+    /// Directory holding the vault's `markdown/` and `metadata/`. Unset
+    /// means the container mount at `/workspace/ext/data` when it exists,
+    /// otherwise `root`.
+    #[serde(default)]
+    pub vault_root: Option<PathBuf>,
+    /// Directory for server and client log files. Unset means the container
+    /// mount at `/app/logs` when it exists, otherwise `logs/` under `root`.
+    #[serde(default)]
+    pub logs_root: Option<PathBuf>,
+}
+
+/// Mount point of the vault in the container images.
+const CONTAINER_VAULT_ROOT: &str = "/workspace/ext/data";
+
+/// Mount point of the log volume in the container images.
+const CONTAINER_LOGS_ROOT: &str = "/app/logs";
+
+impl Default for StorageInternals {
+    fn default() -> Self {
+        Self {
+            root: PathBuf::from("./data"),
+            vault_root: None,
+            logs_root: None,
+        }
+    }
+}
+
+impl StorageInternals {
+    pub fn data_dir(&self) -> PathBuf {
+        std::env::var_os("DATA_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| self.root.clone())
+    }
+
+    /// `relative` under the data directory.
+    pub fn path(&self, relative: impl AsRef<Path>) -> PathBuf {
+        self.data_dir().join(relative)
+    }
+
+    pub fn vault_dir(&self) -> PathBuf {
+        if let Some(ref dir) = self.vault_root {
+            return dir.clone();
+        }
+        if Path::new(CONTAINER_VAULT_ROOT).is_dir() {
+            return PathBuf::from(CONTAINER_VAULT_ROOT);
+        }
+        self.data_dir()
+    }
+
+    pub fn markdown_dir(&self) -> PathBuf {
+        self.vault_dir().join("markdown")
+    }
+
+    pub fn metadata_dir(&self) -> PathBuf {
+        self.vault_dir().join("metadata")
+    }
+
+    pub fn logs_dir(&self) -> PathBuf {
+        if let Some(ref dir) = self.logs_root {
+            return dir.clone();
+        }
+        if Path::new(CONTAINER_LOGS_ROOT).is_dir() {
+            return PathBuf::from(CONTAINER_LOGS_ROOT);
+        }
+        self.path("logs")
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GpuInternals {
+    /// Directory of precompiled `.ptx` modules, tried after the build-time
+    /// PTX and before the source tree copies. Unset uses the built-in
+    /// search order only.
+    #[serde(default)]
+    pub ptx_path: Option<PathBuf>,
+}
+
 impl Default for DevConfig {
     fn default() -> Self {
         Self {
@@ -260,6 +366,7 @@ impl Default for DevConfig {
                 debug_node_count: 3,
                 max_nodes: 1_000_000,
                 max_edges: 10_000_000,
+                state_precision: Default::default(),
             },
             network: NetworkInternals {
                 pool_max_idle_per_host: 32,
@@ -303,6 +410,8 @@ impl Default for DevConfig {
                 lod_distance_high: 100.0,
                 lod_distance_medium: 500.0,
                 lod_distance_low: 1000.0,
+                romanize_labels: true,
+                tag_palette: Vec::new(),
             },
             performance: PerformanceInternals {
                 batch_size_nodes: 1000,
@@ -328,6 +437,8 @@ impl Default for DevConfig {
                 log_memory_usage_interval_secs: 60,
                 profile_sample_rate: 0.01,
             },
+            storage: StorageInternals::default(),
+            gpu: GpuInternals::default(),
         }
     }
 }
@@ -393,3 +504,11 @@ pub fn performance() -> &'static PerformanceInternals {
 pub fn debug() -> &'static DebugInternals {
     &DevConfig::get().debug
 }
+
+pub fn storage() -> &'static StorageInternals {
+    &DevConfig::get().storage
+}
+
+pub fn gpu() -> &'static GpuInternals {
+    &DevConfig::get().gpu
+}
@@ -32,12 +32,17 @@ pub use visionclaw_domain::config::system::{
     DebugSettings, NetworkSettings, SecuritySettings, SystemSettings, WebSocketSettings,
 };
 
-pub use visionclaw_domain::config::xr::{MovementAxes, XRSettings};
+pub use visionclaw_domain::config::xr::{
+    AudioCueDefinition, GestureAction, MovementAxes, XRAudioCueSettings, XRGestureSettings,
+    XRSettings,
+};
 
 pub use visionclaw_domain::config::services::{
-    AgentVoicePreset, AuthSettings, KokoroSettings, LiveKitSettings, OntologyAgentSettings,
-    OpenAISettings, PerplexitySettings, RagFlowSettings, TurboWhisperSettings,
-    VoiceRoutingSettings, WhisperSettings,
+    AgentVoicePreset, ApiCostSettings, AuthSettings, ChatNotifierSettings, ComputeBudgetSettings,
+    DigestSettings, GitHubSettings, KokoroSettings, LayoutRefreshSettings, LinkCheckSettings,
+    LintSettings, LintSeverity, LiveKitSettings, OntologyAgentSettings, OpenAISettings,
+    PerplexitySettings, PublicApiSettings, RagFlowSettings, TurboWhisperSettings,
+    VoiceRoutingSettings, WebhookEndpoint, WebhookSettings, WhisperSettings, WriteMode,
 };
 
 pub use visionclaw_domain::config::{
@@ -49,13 +54,13 @@ pub use visionclaw_domain::config::{
 pub mod physics {
     pub use visionclaw_domain::types::physics_config::{
         AutoBalanceConfig, AutoPauseConfig, ClusteringConfiguration, ConstraintSystem,
-        LegacyConstraintData, PhysicsSettings, PhysicsUpdate,
+        DragReleaseConfig, DragReleaseMode, LegacyConstraintData, PhysicsSettings, PhysicsUpdate,
     };
 }
 
 pub use physics::{
     AutoBalanceConfig, AutoPauseConfig, ClusteringConfiguration, ConstraintSystem,
-    LegacyConstraintData, PhysicsSettings, PhysicsUpdate,
+    DragReleaseConfig, DragReleaseMode, LegacyConstraintData, PhysicsSettings, PhysicsUpdate,
 };
 
 // Re-export path_access traits
@@ -7,5 +7,5 @@
 
 pub use visionclaw_domain::types::physics_config::{
     AutoBalanceConfig, AutoPauseConfig, ClusteringConfiguration, ConstraintSystem,
-    LegacyConstraintData, PhysicsSettings, PhysicsUpdate,
+    DragReleaseConfig, DragReleaseMode, LegacyConstraintData, PhysicsSettings, PhysicsUpdate,
 };
@@ -0,0 +1,379 @@
+// src/handlers/admin_overview_handler.rs
+//! Aggregated system state for ops dashboards (`/api/admin/overview`).
+//!
+//! Routed from the `/admin` scope in `admin_sync_handler::configure_routes`.
+//! Every section is gathered independently under [`SECTION_TIMEOUT`]; one
+//! whose source is missing or slow comes back `null` instead of failing the
+//! whole document.
+
+use actix_web::{web, HttpResponse, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::future::Future;
+use std::process::Command;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::actors::client_coordinator_actor::{ClientSessionSummary, GetClientSessions};
+use crate::actors::gpu::workspace_contexts::WorkspaceGpuMetrics;
+use crate::actors::messages::{
+    GetGraphData, GetOntologyHealth, GetPhysicsOrchestratorActor, GetSettings,
+    GetWorkspaceGpuMetrics, OntologyHealth,
+};
+use crate::actors::physics_orchestrator_actor::GetPhysicsStatus;
+use crate::handlers::consolidated_health_handler::check_mcp_metrics;
+use crate::handlers::metrics_handler::ProcessStartTime;
+use crate::handlers::socket_flow_handler::hibernation::hibernation_stats;
+use crate::ok_json;
+use crate::physics::quality::{simulation_quality, QualityPreset};
+use crate::physics::stability_monitor::SimulationAnomaly;
+use crate::services::cost_service::{costs, BudgetStatus};
+use crate::services::diff_journal_service::diff_journal;
+use crate::services::embedding_sync_service::embeddings;
+use crate::services::link_checker_service::LinkCheckerService;
+use crate::services::write_back_service::{WriteBackQueue, WriteBackService};
+use crate::settings::auth_extractor::AuthenticatedUser;
+use crate::AppState;
+
+/// Longest any one section may take.
+const SECTION_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminOverview {
+    pub generated_at: DateTime<Utc>,
+    pub uptime_secs: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub degraded_reason: Option<String>,
+    pub graph: Option<GraphOverview>,
+    /// Per-workspace simulation contexts; empty without a GPU. Contexts
+    /// with no graph uploaded are listed with `active: false`.
+    pub workspaces: Vec<WorkspaceGpuMetrics>,
+    pub simulation: Option<SimulationOverview>,
+    pub sessions: Option<SessionsOverview>,
+    pub jobs: JobsOverview,
+    pub services: Vec<ExternalService>,
+    pub api_budget: Option<BudgetStatus>,
+    pub resources: ResourceUsage,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphOverview {
+    pub nodes: usize,
+    pub edges: usize,
+    pub pages: usize,
+    /// Latest graph diff revision; see `services::diff_journal_service`.
+    pub revision: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationOverview {
+    /// "gpu", "gpuInitializing" or "unavailable".
+    pub backend: &'static str,
+    pub running: bool,
+    pub paused: bool,
+    pub fps: f32,
+    pub average_step_ms: f32,
+    pub total_steps: u64,
+    pub node_count: usize,
+    pub quality: QualityPreset,
+    pub anomaly: Option<SimulationAnomaly>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionsOverview {
+    pub connected: usize,
+    pub hibernating: u64,
+    pub sessions: Vec<ClientSessionSummary>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobsOverview {
+    pub embedding_sync_running: bool,
+    pub embedded_pages: usize,
+    pub ontology_validation: Option<OntologyHealth>,
+    /// `null` when write-back is not configured or a flush holds the queue.
+    pub write_back: Option<WriteBackQueue>,
+    pub link_check: Option<LinkCheckOverview>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkCheckOverview {
+    pub checked_urls: usize,
+    pub broken_urls: usize,
+    pub last_pass: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalService {
+    pub name: &'static str,
+    /// "up", "down", "configured" (present but not probed) or
+    /// "notConfigured".
+    pub status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl ExternalService {
+    fn configured(name: &'static str, present: bool) -> Self {
+        Self {
+            name,
+            status: if present {
+                "configured"
+            } else {
+                "notConfigured"
+            },
+            detail: None,
+        }
+    }
+}
+
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceUsage {
+    pub process_memory_mb: f64,
+    pub system_memory_used_percent: f64,
+    pub gpu: Option<GpuUsage>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GpuUsage {
+    pub name: String,
+    pub memory_used_mb: f64,
+    pub memory_total_mb: f64,
+    pub utilization_percent: f64,
+}
+
+async fn within<F: Future>(fut: F) -> Option<F::Output> {
+    tokio::time::timeout(SECTION_TIMEOUT, fut).await.ok()
+}
+
+/// GET /api/admin/overview
+///
+/// Graph size, per-workspace simulation contexts, the physics backend and
+/// frame rate, connected sessions with their send rates, background jobs
+/// (embeddings, ontology validation, the write-back queue, link checks),
+/// external services, API spend, and process and GPU memory, in one
+/// document.
+pub async fn get_admin_overview(
+    auth: AuthenticatedUser,
+    app_state: web::Data<AppState>,
+    start_time: web::Data<ProcessStartTime>,
+    write_back: Option<web::Data<Arc<WriteBackService>>>,
+    link_checker: Option<web::Data<Arc<LinkCheckerService>>>,
+) -> Result<HttpResponse> {
+    auth.require_power_user()?;
+
+    let (graph, workspaces, simulation, sessions) = tokio::join!(
+        graph_overview(&app_state),
+        workspace_metrics(&app_state),
+        simulation_overview(&app_state),
+        sessions_overview(&app_state),
+    );
+    let (ontology, services, api_budget, resources) = tokio::join!(
+        ontology_health(&app_state),
+        external_services(&app_state),
+        api_spend(&app_state),
+        resource_usage(),
+    );
+    let (write_back, link_check) = tokio::join!(
+        write_back_queue(write_back.as_deref()),
+        link_check_overview(link_checker.as_deref()),
+    );
+
+    let embeddings = embeddings();
+    ok_json!(AdminOverview {
+        generated_at: Utc::now(),
+        uptime_secs: start_time.0.elapsed().as_secs(),
+        degraded_reason: app_state.get_degraded_reason(),
+        graph,
+        workspaces,
+        simulation,
+        sessions,
+        jobs: JobsOverview {
+            embedding_sync_running: embeddings.is_running(),
+            embedded_pages: embeddings.page_count(),
+            ontology_validation: ontology,
+            write_back,
+            link_check,
+        },
+        services,
+        api_budget,
+        resources,
+    })
+}
+
+async fn graph_overview(app_state: &AppState) -> Option<GraphOverview> {
+    let graph = within(app_state.graph_service_addr.send(GetGraphData))
+        .await?
+        .ok()?
+        .ok()?;
+    Some(GraphOverview {
+        nodes: graph.nodes.len(),
+        edges: graph.edges.len(),
+        pages: graph.metadata.len(),
+        revision: diff_journal().revision(),
+    })
+}
+
+async fn workspace_metrics(app_state: &AppState) -> Vec<WorkspaceGpuMetrics> {
+    let Some(gpu) = app_state.get_gpu_compute_addr().await else {
+        return Vec::new();
+    };
+    within(gpu.send(GetWorkspaceGpuMetrics))
+        .await
+        .and_then(|r| r.ok())
+        .unwrap_or_default()
+}
+
+async fn simulation_overview(app_state: &AppState) -> Option<SimulationOverview> {
+    let physics = within(
+        app_state
+            .graph_service_addr
+            .send(GetPhysicsOrchestratorActor),
+    )
+    .await?
+    .ok()?
+    .ok()?;
+    let status = within(physics.send(GetPhysicsStatus)).await?.ok()?;
+    Some(SimulationOverview {
+        backend: match (status.gpu_enabled, status.gpu_initialized) {
+            (_, true) => "gpu",
+            (true, false) => "gpuInitializing",
+            (false, false) => "unavailable",
+        },
+        running: status.simulation_running,
+        paused: status.is_paused,
+        fps: status.performance.last_fps,
+        average_step_ms: status.performance.average_step_time_ms,
+        total_steps: status.performance.total_steps,
+        node_count: status.node_count,
+        quality: simulation_quality(),
+        anomaly: status.anomaly,
+    })
+}
+
+async fn sessions_overview(app_state: &AppState) -> Option<SessionsOverview> {
+    let sessions = within(app_state.client_manager_addr.send(GetClientSessions))
+        .await?
+        .ok()?;
+    Some(SessionsOverview {
+        connected: app_state.active_connections.load(Ordering::Relaxed),
+        hibernating: hibernation_stats().hibernating,
+        sessions,
+    })
+}
+
+async fn ontology_health(app_state: &AppState) -> Option<OntologyHealth> {
+    let ontology = app_state.get_ontology_actor_addr()?;
+    within(ontology.send(GetOntologyHealth)).await?.ok()?.ok()
+}
+
+async fn write_back_queue(service: Option<&Arc<WriteBackService>>) -> Option<WriteBackQueue> {
+    within(service?.queue()).await
+}
+
+async fn link_check_overview(
+    checker: Option<&Arc<LinkCheckerService>>,
+) -> Option<LinkCheckOverview> {
+    let checker = checker?;
+    within(async {
+        LinkCheckOverview {
+            checked_urls: checker.checked_count().await,
+            broken_urls: checker.broken_links().await.len(),
+            last_pass: checker.last_pass().await,
+        }
+    })
+    .await
+}
+
+async fn external_services(app_state: &AppState) -> Vec<ExternalService> {
+    // The check shells out to docker; run it on the blocking pool so the
+    // timeout can give up on it.
+    let mcp = within(web::block(|| {
+        tokio::runtime::Handle::current().block_on(check_mcp_metrics())
+    }))
+    .await
+    .and_then(|r| r.ok());
+    let mcp = match mcp {
+        Some(mcp) => ExternalService {
+            name: "mcp",
+            status: if mcp.mcp_relay_running { "up" } else { "down" },
+            detail: Some(mcp.message),
+        },
+        None => ExternalService {
+            name: "mcp",
+            status: "down",
+            detail: Some("Health check timed out".to_string()),
+        },
+    };
+    vec![
+        mcp,
+        ExternalService::configured("perplexity", app_state.perplexity_service.is_some()),
+        ExternalService::configured("ragflow", app_state.ragflow_service.is_some()),
+        ExternalService::configured("speech", app_state.speech_service.is_some()),
+    ]
+}
+
+async fn api_spend(app_state: &AppState) -> Option<BudgetStatus> {
+    let settings = within(app_state.settings_addr.send(GetSettings))
+        .await?
+        .ok()?
+        .ok()?;
+    Some(costs().budget(&settings.api_costs.unwrap_or_default()))
+}
+
+async fn resource_usage() -> ResourceUsage {
+    within(web::block(|| {
+        use sysinfo::{ProcessesToUpdate, System};
+        let mut sys = System::new();
+        sys.refresh_memory();
+        let process_memory = sysinfo::get_current_pid().ok().and_then(|pid| {
+            sys.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+            sys.process(pid).map(|p| p.memory())
+        });
+        ResourceUsage {
+            process_memory_mb: process_memory.unwrap_or(0) as f64 / (1024.0 * 1024.0),
+            system_memory_used_percent: if sys.total_memory() > 0 {
+                sys.used_memory() as f64 / sys.total_memory() as f64 * 100.0
+            } else {
+                0.0
+            },
+            gpu: gpu_usage(),
+        }
+    }))
+    .await
+    .and_then(|r| r.ok())
+    .unwrap_or_default()
+}
+
+/// First GPU as reported by `nvidia-smi`, if there is one.
+fn gpu_usage() -> Option<GpuUsage> {
+    let output = Command::new("nvidia-smi")
+        .args([
+            "--query-gpu=name,memory.used,memory.total,utilization.gpu",
+            "--format=csv,noheader,nounits",
+        ])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let fields: Vec<&str> = stdout.lines().next()?.split(',').map(str::trim).collect();
+    match fields.as_slice() {
+        [name, used, total, utilization] => Some(GpuUsage {
+            name: name.to_string(),
+            memory_used_mb: used.parse().ok()?,
+            memory_total_mb: total.parse().ok()?,
+            utilization_percent: utilization.parse().unwrap_or(0.0),
+        }),
+        _ => None,
+    }
+}
@@ -2,12 +2,22 @@
 //! Admin endpoint for triggering GitHub synchronization
 
 use std::sync::Arc;
-use actix_web::{web, Responder, Result};
-use log::{error, info};
-use serde::Serialize;
-use crate::{ok_json, error_json};
+use actix_web::{web, HttpResponse, Responder, Result};
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use crate::{bad_request, ok_json, error_json, service_unavailable};
 
+use crate::actors::messages::{GetSettings, UpdateSettings};
+use crate::config::GitHubSettings;
+use crate::handlers::{
+    admin_overview_handler, cost_handler, embedding_sync_handler, graph_consistency_handler,
+    vault_upload_handler,
+};
+use crate::services::embedding_sync_service::{self, UpdateTrigger};
+use crate::services::github::{ContentAPI, GitHubClient};
 use crate::services::github_sync_service::{GitHubSyncService, SyncStatistics};
+use crate::services::settings_history_service::{ChangeOrigin, ChangeSource};
+use crate::services::vault_upload_service::MAX_ARCHIVE_BYTES;
 use crate::AppState;
 
 #[derive(Serialize)]
@@ -71,7 +81,11 @@ pub async fn trigger_sync(
 
             // Notify graph actor to reload from database
             info!("Notifying GraphServiceActor to reload data from database...");
-            app_state.graph_service_addr.do_send(crate::actors::messages::ReloadGraphFromDatabase);
+            embedding_sync_service::spawn_reload_and_update(
+                app_state.graph_service_addr.clone(),
+                app_state.settings_addr.clone(),
+                UpdateTrigger::Requested,
+            );
             info!("Reload notification sent to GraphServiceActor");
 
             ok_json!(SyncResponse {
@@ -94,11 +108,142 @@ pub async fn trigger_sync(
     }
 }
 
+#[derive(Debug, Deserialize)]
+pub struct SetRefRequest {
+    /// Branch, tag or commit SHA; `null` reverts to `GITHUB_BRANCH`.
+    #[serde(rename = "ref")]
+    pub git_ref: Option<String>,
+}
+
+/// GET /api/admin/github/ref
+pub async fn get_github_ref(
+    _auth: crate::settings::auth_extractor::AuthenticatedUser,
+    github_client: web::Data<Arc<GitHubClient>>,
+) -> Result<HttpResponse> {
+    _auth.require_power_user()?;
+    ok_json!(serde_json::json!({
+        "ref": github_client.branch(),
+        "defaultRef": github_client.default_ref(),
+    }))
+}
+
+/// PUT /api/admin/github/ref
+///
+/// Switches the branch/tag/commit content is ingested from, persists it as
+/// `github.ref`, and resyncs in the background. Lets users browse a historical
+/// state of the vault or preview a PR branch.
+pub async fn set_github_ref(
+    auth: crate::settings::auth_extractor::AuthenticatedUser,
+    github_client: web::Data<Arc<GitHubClient>>,
+    content_api: web::Data<Arc<ContentAPI>>,
+    sync_service: web::Data<Arc<GitHubSyncService>>,
+    app_state: web::Data<AppState>,
+    body: web::Json<SetRefRequest>,
+) -> Result<HttpResponse> {
+    auth.require_power_user()?;
+    let requested = body
+        .into_inner()
+        .git_ref
+        .map(|r| r.trim().to_string())
+        .filter(|r| !r.is_empty());
+    let target = requested
+        .clone()
+        .unwrap_or_else(|| github_client.default_ref().to_string());
+
+    // Reject unknown refs before anything is persisted.
+    let tree_sha = match content_api.resolve_tree_sha(&target).await {
+        Ok(sha) => sha,
+        Err(e) => return bad_request!(format!("Cannot resolve ref '{}': {}", target, e)),
+    };
+
+    let mut settings = match app_state.settings_addr.send(GetSettings).await {
+        Ok(Ok(settings)) => settings,
+        Ok(Err(e)) => return error_json!("Failed to read settings", e),
+        Err(e) => {
+            error!("Settings actor error: {}", e);
+            return service_unavailable!("Settings service unavailable");
+        }
+    };
+    settings.github.get_or_insert_with(GitHubSettings::default).git_ref = requested.clone();
+    let update = UpdateSettings {
+        settings,
+        origin: ChangeOrigin::new(auth.pubkey.clone(), ChangeSource::Rest),
+    };
+    match app_state.settings_addr.send(update).await {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => return error_json!("Failed to save github.ref", e),
+        Err(e) => {
+            error!("Settings actor error: {}", e);
+            return service_unavailable!("Settings service unavailable");
+        }
+    }
+
+    let previous = github_client.branch();
+    let active = github_client.set_ref(requested);
+    info!("GitHub content ref switched '{}' -> '{}' (tree {})", previous, active, tree_sha);
+
+    let sync_service = sync_service.get_ref().clone();
+    let graph_service_addr = app_state.graph_service_addr.clone();
+    let settings_addr = app_state.settings_addr.clone();
+    actix_web::rt::spawn(async move {
+        match sync_service.sync_graphs().await {
+            Ok(stats) => {
+                info!(
+                    "Resync after ref switch completed: {} nodes, {} edges",
+                    stats.total_nodes, stats.total_edges
+                );
+                embedding_sync_service::spawn_reload_and_update(
+                    graph_service_addr,
+                    settings_addr,
+                    UpdateTrigger::Requested,
+                );
+            }
+            Err(e) => warn!("Resync after ref switch failed: {}", e),
+        }
+    });
+
+    Ok(HttpResponse::Accepted().json(serde_json::json!({
+        "success": true,
+        "ref": active,
+        "previousRef": previous,
+        "treeSha": tree_sha,
+        "message": "Ref switched; resync started",
+    })))
+}
+
 /// SECURITY: Admin sync endpoints require power user authentication
 /// Auth is enforced by the AuthenticatedUser extractor + require_power_user() in the handler.
 pub fn configure_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/admin")
             .route("/sync", web::post().to(trigger_sync))
+            .route("/github/ref", web::get().to(get_github_ref))
+            .route("/github/ref", web::put().to(set_github_ref))
+            .route(
+                "/graph/consistency",
+                web::get().to(graph_consistency_handler::get_graph_consistency),
+            )
+            .route(
+                "/graph/consistency/repair",
+                web::post().to(graph_consistency_handler::repair_graph_consistency),
+            )
+            .route(
+                "/embeddings",
+                web::get().to(embedding_sync_handler::get_embedding_status),
+            )
+            .route(
+                "/embeddings/sync",
+                web::post().to(embedding_sync_handler::trigger_embedding_sync),
+            )
+            .route("/costs", web::get().to(cost_handler::get_costs))
+            .route(
+                "/overview",
+                web::get().to(admin_overview_handler::get_admin_overview),
+            )
+            .service(
+                web::resource("/upload_vault")
+                    .app_data(web::PayloadConfig::new(MAX_ARCHIVE_BYTES))
+                    .route(web::post().to(vault_upload_handler::upload_vault)),
+            )
     );
 }
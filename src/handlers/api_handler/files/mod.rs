@@ -7,7 +7,7 @@ use log::{debug, error, info};
 use serde_json::json;
 use std::sync::Arc;
 
-use crate::services::file_service::{FileService, MARKDOWN_DIR};
+use crate::services::file_service::{FileService, markdown_dir};
 use crate::AppState;
 
 pub async fn fetch_and_process_files(state: web::Data<AppState>) -> Result<impl Responder> {
@@ -162,7 +162,7 @@ pub async fn get_file_content(
         }));
     }
 
-    let base_dir = match std::path::Path::new(MARKDOWN_DIR).canonicalize() {
+    let base_dir = match std::path::Path::new(markdown_dir()).canonicalize() {
         Ok(p) => p,
         Err(e) => {
             error!("Failed to canonicalize MARKDOWN_DIR: {}", e);
@@ -173,7 +173,7 @@ pub async fn get_file_content(
         }
     };
 
-    let requested_path = std::path::Path::new(MARKDOWN_DIR).join(&*file_name);
+    let requested_path = std::path::Path::new(markdown_dir()).join(&*file_name);
     let canonical_path = match requested_path.canonicalize() {
         Ok(p) => p,
         Err(e) => {
@@ -185,7 +185,7 @@ pub async fn get_file_content(
         }
     };
 
-    // SECURITY: Verify the resolved path is within MARKDOWN_DIR
+    // SECURITY: Verify the resolved path is within markdown_dir()
     if !canonical_path.starts_with(&base_dir) {
         error!("Path traversal attempt blocked: resolved path escapes MARKDOWN_DIR for file_name: {}", file_name);
         return HttpResponse::BadRequest().json(json!({
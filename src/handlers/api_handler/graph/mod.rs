@@ -18,6 +18,10 @@ use crate::application::graph::queries::{
 use crate::actors::graph_actor::PhysicsState;
 use visionclaw_domain::models::graph::GraphData;
 use crate::handlers::utils::execute_in_thread;
+use crate::protocol::graph_json::{EdgeJson, FieldCasing, JsonSchema};
+use crate::protocol::label_hints::compute_label_hints;
+use crate::services::tag_color_service::tag_colors;
+use crate::services::language_detection::{node_language, parse_languages};
 use hexser::{Hexserror, QueryHandler};
 
 #[derive(Serialize, Debug, Clone)]
@@ -28,34 +32,7 @@ pub struct SettlementState {
     pub kinetic_energy: f32,
 }
 
-#[derive(Serialize, Debug, Clone)]
-#[serde(rename_all = "camelCase")]
-pub struct NodeWithPosition {
-
-    pub id: u32,
-    pub metadata_id: String,
-    pub label: String,
-
-    pub position: Vec3Data,
-    pub velocity: Vec3Data,
-
-
-    #[serde(skip_serializing_if = "HashMap::is_empty")]
-    pub metadata: HashMap<String, String>,
-
-
-    #[serde(rename = "type")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub node_type: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub size: Option<f32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub color: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub weight: Option<f32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub group: Option<String>,
-}
+pub use crate::protocol::graph_json::NodeWithPosition;
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -68,8 +45,10 @@ pub struct GraphResponse {
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GraphResponseWithPositions {
-    pub nodes: Vec<NodeWithPosition>,
-    pub edges: Vec<visionclaw_domain::models::edge::Edge>,
+    /// `NodeWithPosition` rows encoded under the requested schema.
+    pub nodes: Vec<serde_json::Value>,
+    /// `EdgeJson` rows encoded under the requested schema.
+    pub edges: Vec<serde_json::Value>,
     pub metadata: HashMap<String, Metadata>,
     pub settlement_state: SettlementState,
 }
@@ -100,6 +79,20 @@ pub struct GraphQuery {
     /// 17.1k nodes) is never transferred when it will only be hidden anyway.
     /// Absent ⇒ no stub filtering (back-compat default).
     pub exclude_linked_pages: Option<bool>,
+    /// JSON schema version for nodes and edges; see `protocol::graph_json`.
+    pub schema: Option<u32>,
+    /// Overrides the schema's key casing (`camel` or `snake`).
+    pub casing: Option<FieldCasing>,
+    /// Send node metadata as the stored string map (pre-typed format).
+    pub string_metadata: Option<bool>,
+    /// Add `labelPriority`/`labelLod` decluttering hints to each node; see
+    /// `protocol::label_hints`.
+    pub label_hints: Option<bool>,
+    /// Reader locale (BCP 47); selects native or romanized node labels.
+    pub locale: Option<String>,
+    /// Comma-separated content languages to keep (ISO 639-3, `und` for
+    /// undetected), e.g. `eng,deu`; see `services::language_detection`.
+    pub language: Option<String>,
 }
 
 /// The three node populations, mirroring the wire flag bits in
@@ -151,6 +144,16 @@ pub async fn get_graph_data(
     _req: HttpRequest,
 ) -> impl Responder {
     info!("Received request for graph data (CQRS Phase 1D), graph_type={:?}", query.graph_type);
+    let schema = match JsonSchema::resolve(query.schema, query.casing) {
+        Ok(schema) => schema
+            .with_string_metadata(query.string_metadata)
+            .with_locale(query.locale.as_deref()),
+        Err(e) => return bad_request!(e),
+    };
+    let languages = match parse_languages(query.language.as_deref().unwrap_or("").split(',')) {
+        Ok(languages) => languages,
+        Err(e) => return bad_request!(e),
+    };
 
     
     let graph_handler = state.graph_query_handlers.get_graph_data.clone();
@@ -181,26 +184,9 @@ pub async fn get_graph_data(
             let nodes_with_positions: Vec<NodeWithPosition> = graph_data
                 .nodes
                 .iter()
-                .map(|node| {
-                    // Use node's own data for position and velocity
-                    // node_map contains HashMap<i32, Vec<i32>>, not physics nodes
-                    let position: Vec3Data = node.data.position().into();
-                    let velocity: Vec3Data = node.data.velocity().into();
-
-                    NodeWithPosition {
-                        id: node.id,
-                        metadata_id: node.metadata_id.clone(),
-                        label: node.label.clone(),
-                        position,
-                        velocity,
-                        metadata: node.metadata.clone(),
-                        node_type: node.node_type.clone(),
-                        size: node.size,
-                        color: node.color.clone(),
-                        weight: node.weight,
-                        group: node.group.clone(),
-                    }
-                })
+                // Use node's own data for position and velocity
+                // node_map contains HashMap<i32, Vec<i32>>, not physics nodes
+                .map(NodeWithPosition::from)
                 .collect();
 
             // Server-side population filtering (PRD-018 WS-4). `graph_type`
@@ -212,7 +198,16 @@ pub async fn get_graph_data(
             // Authoritative origin is metadata["type"] (matches the client
             // `nodePopulationType` precedence), with node_type as the fallback.
             let exclude_linked_pages = query.exclude_linked_pages.unwrap_or(false);
-            let filtered_nodes: Vec<NodeWithPosition> = nodes_with_positions
+            // Per-language view: ids of nodes in one of the requested languages.
+            let language_ids: Option<std::collections::HashSet<u32>> = languages.map(|languages| {
+                graph_data
+                    .nodes
+                    .iter()
+                    .filter(|node| languages.contains(node_language(node, &graph_data.metadata)))
+                    .map(|node| node.id)
+                    .collect()
+            });
+            let mut filtered_nodes: Vec<NodeWithPosition> = nodes_with_positions
                 .into_iter()
                 .filter(|node| match population {
                     Some(p) => p.matches(node.node_type.as_deref(), &node.metadata),
@@ -230,23 +225,56 @@ pub async fn get_graph_data(
                         .unwrap_or("");
                     origin != "linked_page"
                 })
+                .filter(|node| language_ids.as_ref().is_none_or(|ids| ids.contains(&node.id)))
                 .collect();
 
             // Filter edges to only include those connecting filtered nodes
             let filtered_node_ids: std::collections::HashSet<u32> =
                 filtered_nodes.iter().map(|n| n.id).collect();
-            let filtered_edges: Vec<_> = graph_data
+            let kept_edges: Vec<&visionclaw_domain::models::edge::Edge> = graph_data
                 .edges
                 .iter()
                 .filter(|e| {
-                    filtered_node_ids.contains(&e.source)
-                        && filtered_node_ids.contains(&e.target)
+                    filtered_node_ids.contains(&e.source) && filtered_node_ids.contains(&e.target)
                 })
-                .cloned()
                 .collect();
 
+            // Hints are computed over exactly what is sent, so clients that
+            // filtered the same way agree on them.
+            if query.label_hints.unwrap_or(false) {
+                let positions: Vec<(u32, [f32; 3])> = filtered_nodes
+                    .iter()
+                    .map(|n| (n.id, [n.position.x, n.position.y, n.position.z]))
+                    .collect();
+                let pairs: Vec<(u32, u32)> =
+                    kept_edges.iter().map(|e| (e.source, e.target)).collect();
+                let hints = compute_label_hints(&positions, &pairs);
+                for node in &mut filtered_nodes {
+                    if let Some(hint) = hints.get(&node.id) {
+                        node.label_priority = Some(hint.priority);
+                        node.label_lod = Some(hint.lod);
+                    }
+                }
+            }
+
+            let colors = tag_colors().snapshot();
+            for node in &mut filtered_nodes {
+                node.tag_color = colors.node_color(&node.metadata);
+            }
+            let node_metadata: HashMap<u32, &HashMap<String, String>> =
+                filtered_nodes.iter().map(|n| (n.id, &n.metadata)).collect();
+            let filtered_edges = schema.encode_all(kept_edges.into_iter().map(|edge| {
+                let mut json = EdgeJson::from(edge);
+                if let (Some(source), Some(target)) =
+                    (node_metadata.get(&edge.source), node_metadata.get(&edge.target))
+                {
+                    json.tag_color = colors.edge_color(source, target);
+                }
+                json
+            }));
+
             let response = GraphResponseWithPositions {
-                nodes: filtered_nodes,
+                nodes: schema.encode_all(filtered_nodes),
                 edges: filtered_edges,
                 metadata: graph_data.metadata.clone(),
                 settlement_state: SettlementState {
@@ -622,6 +650,174 @@ pub async fn get_graph_positions(
     }
 }
 
+#[derive(Deserialize, Debug)]
+pub struct GraphQueryRequest {
+    pub query: String,
+}
+
+/// Run a declarative graph query (see `services::graph_query_service`).
+///
+/// `POST /api/graph/query` with `{"query": "MATCH (a)-[*1..2]->(b) RETURN b"}`
+pub async fn run_graph_query(
+    state: web::Data<AppState>,
+    request: web::Json<GraphQueryRequest>,
+) -> impl Responder {
+    use crate::services::graph_query_service::{execute, parse_query, CsrGraph};
+
+    let src = request.into_inner().query;
+    let query = match web::block(move || parse_query(&src)).await {
+        Ok(Ok(q)) => q,
+        Ok(Err(e)) => return bad_request!(e.to_string()),
+        Err(e) => {
+            error!("Graph query parse task failed: {}", e);
+            return error_json!("Query parsing failed");
+        }
+    };
+
+    let graph_handler = state.graph_query_handlers.get_graph_data.clone();
+    let graph_data = match execute_in_thread(move || graph_handler.handle(GetGraphData)).await {
+        Ok(Ok(data)) => data,
+        Ok(Err(e)) => {
+            error!("Failed to get graph data for query: {}", e);
+            return error_json!("Failed to retrieve graph data");
+        }
+        Err(e) => {
+            error!("Thread execution error: {}", e);
+            return error_json!("Internal server error");
+        }
+    };
+
+    let result = web::block(move || execute(&query, &CsrGraph::build(&graph_data))).await;
+    match result {
+        Ok(result) => {
+            debug!(
+                "Graph query returned {} rows (truncated: {})",
+                result.rows.len(),
+                result.truncated
+            );
+            ok_json!(result)
+        }
+        Err(e) => {
+            error!("Graph query execution failed: {}", e);
+            error_json!("Query execution failed")
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct EphemeralNodeRequest {
+    pub label: String,
+    #[serde(default = "default_ephemeral_ttl")]
+    pub ttl_secs: u64,
+    pub node_type: Option<String>,
+    pub color: Option<String>,
+    pub position: Option<Vec3Data>,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+}
+
+fn default_ephemeral_ttl() -> u64 {
+    60
+}
+
+/// Add a temporary visual aid that the server removes after its TTL.
+///
+/// `POST /api/graph/ephemeral` with `{"label": "Answer", "ttlSecs": 30}`.
+/// Clients receive `graphDiff` messages when the node appears and expires.
+pub async fn add_ephemeral_node(
+    state: web::Data<AppState>,
+    request: web::Json<EphemeralNodeRequest>,
+) -> impl Responder {
+    use crate::actors::messages::AddEphemeralNode;
+
+    let request = request.into_inner();
+    if request.label.trim().is_empty() {
+        return bad_request!("label must not be empty");
+    }
+
+    let mut node = Node::new_with_id(format!("ephemeral-{}", uuid::Uuid::new_v4()), None);
+    node.label = request.label;
+    node.node_type = Some(request.node_type.unwrap_or_else(|| "ephemeral".to_string()));
+    node.color = request.color;
+    node.metadata = request.metadata;
+    if let Some(pos) = request.position {
+        node.data.x = pos.x;
+        node.data.y = pos.y;
+        node.data.z = pos.z;
+    }
+
+    match state
+        .graph_service_addr
+        .send(AddEphemeralNode {
+            node,
+            ttl_secs: request.ttl_secs,
+        })
+        .await
+    {
+        Ok(Ok(node_id)) => ok_json!(serde_json::json!({
+            "nodeId": node_id,
+            "ttlSecs": request.ttl_secs,
+        })),
+        Ok(Err(e)) => bad_request!(e),
+        Err(e) => {
+            error!("Mailbox error sending AddEphemeralNode: {}", e);
+            error_json!("Graph service unavailable")
+        }
+    }
+}
+
+/// Palette and pinned colours behind `tagColor`.
+///
+/// `GET /api/graph/tag-colors` returns `{"palette": [...], "pins": {"project": "#E15759"}}`.
+pub async fn get_tag_colors() -> impl Responder {
+    ok_json!(tag_colors().snapshot())
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TagColorRequest {
+    pub color: String,
+}
+
+/// Pin a tag to a colour for every client.
+///
+/// `PUT /api/graph/tag-colors/{tag}` with `{"color": "#E15759"}`.
+pub async fn pin_tag_color(
+    tag: web::Path<String>,
+    request: web::Json<TagColorRequest>,
+) -> impl Responder {
+    let color = request.into_inner().color;
+    let pinned = color.clone();
+    match web::block(move || tag_colors().pin(&tag, &pinned)).await {
+        Ok(Ok(tag)) => {
+            info!("Pinned tag '{}' to {}", tag, color);
+            ok_json!(serde_json::json!({ "tag": tag, "color": color.to_uppercase() }))
+        }
+        Ok(Err(e)) => bad_request!(e),
+        Err(e) => {
+            error!("Failed to pin tag colour: {}", e);
+            error_json!("Failed to save tag colours")
+        }
+    }
+}
+
+/// Return a tag to its hashed colour.
+///
+/// `DELETE /api/graph/tag-colors/{tag}`.
+pub async fn unpin_tag_color(tag: web::Path<String>) -> impl Responder {
+    match web::block(move || tag_colors().unpin(&tag)).await {
+        Ok(Ok(removed)) => ok_json!(serde_json::json!({ "removed": removed })),
+        Ok(Err(e)) => {
+            error!("Failed to unpin tag colour: {}", e);
+            error_json!("Failed to save tag colours")
+        }
+        Err(e) => {
+            error!("Failed to unpin tag colour: {}", e);
+            error_json!("Failed to save tag colours")
+        }
+    }
+}
+
 // Configure routes using snake_case
 /// SECURITY: Graph mutation operations require authentication
 pub fn config(cfg: &mut web::ServiceConfig) {
@@ -641,6 +837,14 @@ pub fn config(cfg: &mut web::ServiceConfig) {
                 "/auto-balance-notifications",
                 web::get().to(get_auto_balance_notifications),
             )
+            .route("/tag-colors", web::get().to(get_tag_colors))
+            // Pins change what every client sees, so they need a login.
+            .service(
+                web::resource("/tag-colors/{tag}")
+                    .wrap(RequireAuth::authenticated())
+                    .route(web::put().to(pin_tag_color))
+                    .route(web::delete().to(unpin_tag_color)),
+            )
             // S2: `/update` triggers a full bulk reload — it re-fetches and
             // re-processes the entire upstream content source and rebuilds the graph
             // from metadata (AddNodesFromMetadata). That is a destructive/expensive
@@ -652,12 +856,26 @@ pub fn config(cfg: &mut web::ServiceConfig) {
                     .wrap(RequireAuth::power_user())  // Bulk reload requires power-user
                     .route(web::post().to(update_graph)),
             )
+            // `/query` is read-only but can walk large neighbourhoods, so it is
+            // limited to authenticated users.
+            .service(
+                web::resource("/query")
+                    .wrap(RequireAuth::authenticated())
+                    .route(web::post().to(run_graph_query)),
+            )
             // `/refresh` only reads the current graph state (GetGraphData) and returns
             // it; it mutates nothing, so any authenticated user may call it.
             .service(
                 web::resource("/refresh")
                     .wrap(RequireAuth::authenticated())  // Read-back, any authed user
                     .route(web::post().to(refresh_graph)),
+            )
+            // Ephemeral nodes are never persisted and expire on their own,
+            // but they are still visible to every client.
+            .service(
+                web::resource("/ephemeral")
+                    .wrap(RequireAuth::authenticated())
+                    .route(web::post().to(add_ephemeral_node)),
             ),
     );
 }
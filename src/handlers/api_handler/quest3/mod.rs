@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use crate::ok_json;
 
 use crate::actors::messages::{GetSettings, UpdateSettings};
+use crate::services::settings_history_service::{ChangeOrigin, ChangeSource};
 use crate::AppState;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -220,7 +221,7 @@ pub async fn get_quest3_defaults(_app_state: web::Data<AppState>) -> Result<Http
 }
 
 pub async fn calibrate_quest3(
-    _auth: crate::settings::auth_extractor::AuthenticatedUser,
+    auth: crate::settings::auth_extractor::AuthenticatedUser,
     app_state: web::Data<AppState>,
     request: web::Json<Quest3CalibrationRequest>,
 ) -> Result<HttpResponse> {
@@ -271,21 +272,22 @@ pub async fn calibrate_quest3(
     let performance_estimate = estimate_performance(&calibrated_settings);
 
     
-    let settings_applied =
-        match apply_quest3_settings_to_system(app_state, &calibrated_settings).await {
-            Ok(()) => {
-                info!("Quest 3 calibrated settings applied successfully");
-                true
-            }
-            Err(e) => {
-                warn!("Failed to apply Quest 3 settings to system: {}", e);
-                recommendations.push(
-                    "Settings generated but not applied - manual configuration may be required"
-                        .to_string(),
-                );
-                false
-            }
-        };
+    let applied =
+        apply_quest3_settings_to_system(app_state, &calibrated_settings, &auth.pubkey).await;
+    let settings_applied = match applied {
+        Ok(()) => {
+            info!("Quest 3 calibrated settings applied successfully");
+            true
+        }
+        Err(e) => {
+            warn!("Failed to apply Quest 3 settings to system: {}", e);
+            recommendations.push(
+                "Settings generated but not applied - manual configuration may be required"
+                    .to_string(),
+            );
+            false
+        }
+    };
 
     if !settings_applied {
         recommendations
@@ -678,6 +680,7 @@ fn estimate_performance(settings: &Quest3Settings) -> PerformanceEstimate {
 async fn apply_quest3_settings_to_system(
     app_state: web::Data<AppState>,
     quest3_settings: &Quest3Settings,
+    author: &str,
 ) -> Result<(), String> {
     
     let mut settings = app_state
@@ -725,7 +728,10 @@ async fn apply_quest3_settings_to_system(
     
     app_state
         .settings_addr
-        .send(UpdateSettings { settings })
+        .send(UpdateSettings {
+            settings,
+            origin: ChangeOrigin::new(author, ChangeSource::Rest),
+        })
         .await
         .map_err(|e| format!("Failed to update settings: {}", e))?
         .map_err(|e| format!("Settings update error: {}", e))?;
@@ -0,0 +1,154 @@
+// src/handlers/audio_cue_handler.rs
+//! Positional audio cues (`/api/audio-cues`).
+//!
+//! The catalog comes from `xr.audioCues` in settings. Node appearances are
+//! cued by the graph state actor. Chat answers cue their citations and the
+//! nodes the chat agent's searches found through [`cue_nodes`]; clients that
+//! search locally report their hits on `/emit`. Cues play only in the asking
+//! user's XR sessions. See `services::audio_cue_service`.
+
+use actix_web::{web, HttpResponse, Result};
+use log::{debug, warn};
+use serde::Deserialize;
+
+use crate::actors::messages::{BroadcastAudioCues, GetSettings};
+use crate::adapters::actor_graph_repository::ActorGraphRepository;
+use crate::ports::graph_repository::GraphRepository;
+use crate::services::audio_cue_service::{
+    audio_cues, AudioCue, CHAT_CITATION, MAX_CUES_PER_MESSAGE, SEARCH_HIT,
+};
+use crate::settings::auth_extractor::AuthenticatedUser;
+use crate::AppState;
+use crate::{bad_request, error_json, ok_json};
+
+/// GET /api/audio-cues
+///
+/// `{"cues": {"nodeAppear": {"sound": "...", "volume": 0.5, "refDistance": 10}}}`
+pub async fn get_catalog(state: web::Data<AppState>) -> Result<HttpResponse> {
+    match state.settings_addr.send(GetSettings).await {
+        Ok(Ok(settings)) => ok_json!(settings.xr.audio_cues),
+        Ok(Err(e)) => error_json!("Failed to read settings", e),
+        Err(e) => error_json!("Failed to read settings", e),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CuePreference {
+    pub enabled: bool,
+}
+
+/// GET /api/audio-cues/preference
+pub async fn get_preference(auth: AuthenticatedUser) -> Result<HttpResponse> {
+    ok_json!(serde_json::json!({ "enabled": audio_cues().is_enabled(&auth.pubkey) }))
+}
+
+/// PUT /api/audio-cues/preference
+///
+/// `{"enabled": true}`
+pub async fn put_preference(
+    auth: AuthenticatedUser,
+    body: web::Json<CuePreference>,
+) -> Result<HttpResponse> {
+    let enabled = body.enabled;
+    let pubkey = auth.pubkey.clone();
+    match web::block(move || audio_cues().set_enabled(&pubkey, enabled)).await {
+        Ok(Ok(())) => ok_json!(serde_json::json!({ "enabled": enabled })),
+        Ok(Err(e)) => error_json!("Failed to save preference", e),
+        Err(e) => error_json!("Failed to save preference", e),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CueRequest {
+    pub cue_id: String,
+    pub node_ids: Vec<u32>,
+}
+
+/// POST /api/audio-cues/emit
+///
+/// `{"cueId": "searchHit", "nodeIds": [3, 17]}`, played only in the caller's
+/// own sessions. Unknown node ids are skipped.
+pub async fn emit_cues(
+    auth: AuthenticatedUser,
+    state: web::Data<AppState>,
+    body: web::Json<CueRequest>,
+) -> Result<HttpResponse> {
+    let request = body.into_inner();
+    if request.cue_id != SEARCH_HIT && request.cue_id != CHAT_CITATION {
+        return bad_request!(format!("cueId must be {} or {}", SEARCH_HIT, CHAT_CITATION));
+    }
+    if request.node_ids.is_empty() || request.node_ids.len() > MAX_CUES_PER_MESSAGE {
+        return bad_request!(format!(
+            "nodeIds must list 1 to {} nodes",
+            MAX_CUES_PER_MESSAGE
+        ));
+    }
+
+    let cues = match cues_at(&state.graph_repository, &request.cue_id, &request.node_ids).await {
+        Ok(cues) => cues,
+        Err(e) => return error_json!("Failed to read graph", e),
+    };
+    let count = cues.len();
+    debug!(
+        "[AudioCues] {} {} cue(s) for {}",
+        count, request.cue_id, auth.pubkey
+    );
+    state.client_manager_addr.do_send(BroadcastAudioCues {
+        cues,
+        recipient: Some(auth.pubkey),
+    });
+    ok_json!(serde_json::json!({ "cued": count }))
+}
+
+/// Cue `node_ids` at their current positions. Unknown ids are skipped.
+async fn cues_at(
+    graph: &ActorGraphRepository,
+    cue_id: &str,
+    node_ids: &[u32],
+) -> Result<Vec<AudioCue>, String> {
+    let nodes = graph.get_node_map().await.map_err(|e| e.to_string())?;
+    Ok(node_ids
+        .iter()
+        .filter_map(|id| nodes.get(id))
+        .map(|n| AudioCue::new(cue_id, n.id, [n.data.x, n.data.y, n.data.z]))
+        .collect())
+}
+
+/// Play `cue_id` on up to [`MAX_CUES_PER_MESSAGE`] of `node_ids` in
+/// `owner`'s sessions, without holding up the caller's response.
+pub(crate) fn cue_nodes(state: &AppState, cue_id: &'static str, owner: &str, node_ids: &[u32]) {
+    if node_ids.is_empty() {
+        return;
+    }
+    let graph = state.graph_repository.clone();
+    let client_manager = state.client_manager_addr.clone();
+    let owner = owner.to_string();
+    let node_ids: Vec<u32> = node_ids.iter().copied().take(MAX_CUES_PER_MESSAGE).collect();
+    tokio::spawn(async move {
+        match cues_at(&graph, cue_id, &node_ids).await {
+            Ok(cues) if !cues.is_empty() => {
+                debug!("[AudioCues] {} {} cue(s) for {}", cues.len(), cue_id, owner);
+                client_manager.do_send(BroadcastAudioCues {
+                    cues,
+                    recipient: Some(owner),
+                });
+            }
+            Ok(_) => {}
+            Err(e) => warn!("[AudioCues] Could not cue {} nodes: {}", cue_id, e),
+        }
+    });
+}
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/audio-cues")
+            .route("", web::get().to(get_catalog))
+            .service(
+                web::resource("/preference")
+                    .route(web::get().to(get_preference))
+                    .route(web::put().to(put_preference)),
+            )
+            .route("/emit", web::post().to(emit_cues)),
+    );
+}
@@ -53,7 +53,9 @@ pub async fn handle_client_logs(
         })));
     }
 
-    let log_file_path = "/app/logs/client.log";
+    let log_file_path = crate::config::dev_config::storage()
+        .logs_dir()
+        .join("client.log");
 
     
     let header_session_id = req
@@ -103,10 +105,15 @@ pub async fn handle_client_logs(
     }
 
     
+    if let Some(dir) = log_file_path.parent() {
+        if let Err(e) = std::fs::create_dir_all(dir) {
+            log::warn!("Failed to create client log directory {}: {}", dir.display(), e);
+        }
+    }
     let mut file = OpenOptions::new()
         .create(true)
         .append(true)
-        .open(log_file_path)
+        .open(&log_file_path)
         .map_err(|e| {
             error!("Failed to open client.log: {}", e);
             actix_web::error::ErrorInternalServerError(format!("Failed to open log file: {}", e))
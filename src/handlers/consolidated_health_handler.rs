@@ -191,7 +191,7 @@ async fn check_service_metrics(
     }
 }
 
-async fn check_mcp_metrics() -> McpMetrics {
+pub(crate) async fn check_mcp_metrics() -> McpMetrics {
     let container_running = McpRelayManager::check_mcp_container();
     let mcp_relay_running = if container_running {
         let manager = McpRelayManager::new();
@@ -449,15 +449,21 @@ pub async fn liveness_probe() -> HttpResponse {
 }
 
 /// Readiness probe — returns 200 if the application is ready to serve traffic,
-/// 503 if critical subsystems (e.g. Oxigraph store) are unavailable.
+/// 503 if critical subsystems (e.g. Oxigraph store) are unavailable or the
+/// knowledge graph has not been built yet (e.g. still waiting for metadata).
 pub async fn readiness_probe(app_state: web::Data<AppState>) -> HttpResponse {
-    if let Some(reason) = app_state.get_degraded_reason() {
+    let graph = app_state.graph_readiness.get();
+    let reason = app_state
+        .get_degraded_reason()
+        .or_else(|| graph.not_ready_reason());
+    if let Some(reason) = reason {
         HttpResponse::ServiceUnavailable().json(serde_json::json!({
             "status": "not_ready",
             "reason": reason,
+            "graph": graph,
         }))
     } else {
-        HttpResponse::Ok().json(serde_json::json!({"status": "ready"}))
+        HttpResponse::Ok().json(serde_json::json!({"status": "ready", "graph": graph}))
     }
 }
 
@@ -1,6 +1,7 @@
 use crate::actors::messages::{GetSettings, UpdateSettings};
 use crate::app_state::AppState;
 use crate::config::{ConstraintSystem, LegacyConstraintData};
+use crate::services::settings_history_service::{ChangeOrigin, ChangeSource};
 use crate::{ok_json, error_json, bad_request, service_unavailable};
 use actix_web::{web, HttpRequest, HttpResponse};
 use log::{debug, error, info, warn};
@@ -20,7 +21,7 @@ pub fn config(cfg: &mut web::ServiceConfig) {
 }
 
 async fn define_constraints(
-    _auth: crate::settings::auth_extractor::AuthenticatedUser,
+    auth: crate::settings::auth_extractor::AuthenticatedUser,
     _req: HttpRequest,
     state: web::Data<AppState>,
     payload: web::Json<ConstraintSystem>,
@@ -76,6 +77,7 @@ async fn define_constraints(
         .settings_addr
         .send(UpdateSettings {
             settings: app_settings,
+            origin: ChangeOrigin::new(auth.pubkey, ChangeSource::Rest),
         })
         .await
     {
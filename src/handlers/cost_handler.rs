@@ -0,0 +1,48 @@
+// src/handlers/cost_handler.rs
+//! External API usage and estimated cost (`/api/admin/costs`).
+//!
+//! Routed from the `/admin` scope in `admin_sync_handler::configure_routes`.
+//! Budget caps are the `apiCosts` settings; see `services::cost_service`.
+
+use actix_web::{web, HttpResponse, Result};
+use log::error;
+use serde::Deserialize;
+
+use crate::actors::messages::GetSettings;
+use crate::services::cost_service::{costs, PRICES, RETENTION_DAYS};
+use crate::settings::auth_extractor::AuthenticatedUser;
+use crate::AppState;
+use crate::{error_json, ok_json, service_unavailable};
+
+#[derive(Debug, Deserialize)]
+pub struct CostQuery {
+    pub days: Option<i64>,
+}
+
+/// GET /api/admin/costs?days=30
+///
+/// Requests, tokens and estimated USD per provider for each of the last
+/// `days` days (default 30), totals for the range, spend against the budget
+/// caps, and the prices the estimates use.
+pub async fn get_costs(
+    auth: AuthenticatedUser,
+    app_state: web::Data<AppState>,
+    query: web::Query<CostQuery>,
+) -> Result<HttpResponse> {
+    auth.require_power_user()?;
+    let settings = match app_state.settings_addr.send(GetSettings).await {
+        Ok(Ok(settings)) => settings,
+        Ok(Err(e)) => return error_json!("Failed to read settings", e),
+        Err(e) => {
+            error!("Settings actor error: {}", e);
+            return service_unavailable!("Settings service unavailable");
+        }
+    };
+    let days = query.days.unwrap_or(30).clamp(1, RETENTION_DAYS);
+    let service = costs();
+    ok_json!(serde_json::json!({
+        "summary": service.summary(days),
+        "budget": service.budget(&settings.api_costs.unwrap_or_default()),
+        "prices": PRICES,
+    }))
+}
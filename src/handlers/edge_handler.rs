@@ -0,0 +1,113 @@
+// src/handlers/edge_handler.rs
+//! Edge inspection endpoints (`/api/edges/*`).
+
+use actix_web::{web, HttpResponse, Result};
+use log::error;
+use serde::Serialize;
+use visionclaw_domain::models::edge::{Edge, EdgeProvenance};
+
+use crate::actors::messages::GetGraphData;
+use crate::AppState;
+use crate::{error_json, not_found, ok_json};
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EdgeProvenanceResponse {
+    pub source: u32,
+    pub target: u32,
+    pub edges: Vec<Edge>,
+    /// All contributions across both directions, merged per source.
+    pub combined: EdgeProvenance,
+}
+
+/// Collect the edges joining `a` and `b` (either direction), filling in
+/// reconstructed provenance where none was recorded.
+fn edges_between(edges: &[Edge], a: u32, b: u32) -> Vec<Edge> {
+    edges
+        .iter()
+        .filter(|e| (e.source == a && e.target == b) || (e.source == b && e.target == a))
+        .map(|e| {
+            let mut edge = e.clone();
+            edge.provenance = Some(e.provenance_or_reconstructed());
+            edge
+        })
+        .collect()
+}
+
+fn combine(edges: &[Edge]) -> EdgeProvenance {
+    let mut combined = EdgeProvenance::default();
+    for edge in edges {
+        if let Some(p) = &edge.provenance {
+            combined.reconstructed |= p.reconstructed;
+            for c in &p.contributions {
+                combined.add(c.source, c.weight, c.count, c.detail.clone());
+            }
+        }
+    }
+    combined
+}
+
+/// GET /api/edges/{source}/{target}
+///
+/// Explains why two nodes are connected: every edge between them with its
+/// per-source provenance, plus the merged breakdown.
+pub async fn get_edge_provenance(
+    app_state: web::Data<AppState>,
+    path: web::Path<(u32, u32)>,
+) -> Result<HttpResponse> {
+    let (source, target) = path.into_inner();
+
+    let graph_data = match app_state.graph_service_addr.send(GetGraphData).await {
+        Ok(Ok(data)) => data,
+        Ok(Err(e)) => return error_json!("Failed to read graph", e),
+        Err(e) => {
+            error!("[Edges] Graph service unreachable: {}", e);
+            return error_json!("Graph service unavailable", e.to_string());
+        }
+    };
+
+    let edges = edges_between(&graph_data.edges, source, target);
+    if edges.is_empty() {
+        return not_found!(format!("No edge between {} and {}", source, target));
+    }
+
+    let combined = combine(&edges);
+    ok_json!(EdgeProvenanceResponse {
+        source,
+        target,
+        edges,
+        combined,
+    })
+}
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/edges").route("/{source}/{target}", web::get().to(get_edge_provenance)),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use visionclaw_domain::models::edge::ProvenanceSource;
+
+    #[test]
+    fn edges_between_matches_both_directions_and_fills_provenance() {
+        let edges = vec![
+            Edge::new(1, 2, 1.0).with_edge_type("explicit_link".into()),
+            Edge::new(2, 1, 0.5).with_provenance(EdgeProvenance::from_source(
+                ProvenanceSource::Semantic,
+                0.5,
+            )),
+            Edge::new(1, 3, 1.0),
+        ];
+        let found = edges_between(&edges, 1, 2);
+        assert_eq!(found.len(), 2);
+        assert!(found.iter().all(|e| e.provenance.is_some()));
+
+        let combined = combine(&found);
+        assert_eq!(combined.contributions.len(), 2);
+        assert!(combined.reconstructed);
+        assert!((combined.total_weight() - 1.5).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,46 @@
+// src/handlers/embedding_sync_handler.rs
+//! Page embedding status and updates (`/api/admin/embeddings`).
+//!
+//! Routed from the `/admin` scope in `admin_sync_handler::configure_routes`.
+//! Updates also run by themselves after every sync; see
+//! `services::embedding_sync_service`.
+
+use actix_web::{web, HttpResponse, Result};
+use log::info;
+
+use crate::services::embedding_sync_service::{embeddings, spawn_update, UpdateTrigger};
+use crate::settings::auth_extractor::AuthenticatedUser;
+use crate::AppState;
+use crate::ok_json;
+
+/// GET /api/admin/embeddings
+///
+/// Pages embedded, whether an update is running, and the report of the last
+/// one (pages re-embedded, tokens, estimated cost, edges changed).
+pub async fn get_embedding_status(auth: AuthenticatedUser) -> Result<HttpResponse> {
+    auth.require_power_user()?;
+    let service = embeddings();
+    ok_json!(serde_json::json!({
+        "pages": service.page_count(),
+        "running": service.is_running(),
+        "lastReport": service.last_report(),
+    }))
+}
+
+/// POST /api/admin/embeddings/sync
+///
+/// Bring embeddings up to date with the graph now. Returns at once; poll
+/// `GET /api/admin/embeddings` for the report.
+pub async fn trigger_embedding_sync(
+    auth: AuthenticatedUser,
+    app_state: web::Data<AppState>,
+) -> Result<HttpResponse> {
+    auth.require_power_user()?;
+    info!("[Embeddings] Update requested by {}", auth.pubkey);
+    spawn_update(
+        app_state.graph_service_addr.clone(),
+        app_state.settings_addr.clone(),
+        UpdateTrigger::Requested,
+    );
+    Ok(HttpResponse::Accepted().json(serde_json::json!({ "success": true })))
+}
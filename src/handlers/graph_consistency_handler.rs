@@ -0,0 +1,53 @@
+// src/handlers/graph_consistency_handler.rs
+//! Graph index consistency checks (`/api/admin/graph/consistency`).
+//!
+//! Routed from the `/admin` scope in `admin_sync_handler::configure_routes`.
+
+use actix_web::{web, HttpResponse, Result};
+use log::info;
+
+use crate::actors::graph_consistency::RepairOptions;
+use crate::actors::messages::CheckGraphConsistency;
+use crate::settings::auth_extractor::AuthenticatedUser;
+use crate::AppState;
+use crate::{error_json, ok_json};
+
+async fn run_check(app_state: &AppState, repair: Option<RepairOptions>) -> Result<HttpResponse> {
+    match app_state
+        .graph_service_addr
+        .send(CheckGraphConsistency { repair })
+        .await
+    {
+        Ok(Ok(result)) => ok_json!(result),
+        Ok(Err(e)) => error_json!("Consistency check failed", e),
+        Err(e) => error_json!("Graph service unavailable", e),
+    }
+}
+
+/// GET /api/admin/graph/consistency
+///
+/// Reports drift between `graph.nodes`, the node map, the metadata index and
+/// the node-type sets: duplicate ids, orphan edges, stale index entries.
+pub async fn get_graph_consistency(
+    auth: AuthenticatedUser,
+    app_state: web::Data<AppState>,
+) -> Result<HttpResponse> {
+    auth.require_power_user()?;
+    run_check(&app_state, None).await
+}
+
+/// POST /api/admin/graph/consistency/repair
+///
+/// Body selects the fixes, e.g. `{"dropOrphanEdges": true}`; an empty body
+/// applies all of them. The response carries the report from before and
+/// after the repair.
+pub async fn repair_graph_consistency(
+    auth: AuthenticatedUser,
+    app_state: web::Data<AppState>,
+    body: Option<web::Json<RepairOptions>>,
+) -> Result<HttpResponse> {
+    auth.require_power_user()?;
+    let options = body.map_or_else(RepairOptions::all, |b| b.into_inner());
+    info!("[GraphConsistency] Repair requested: {:?}", options);
+    run_check(&app_state, Some(options)).await
+}
@@ -2,10 +2,10 @@
 // Uses Knowledge Graph application layer for all graph operations
 
 use crate::handlers::utils::execute_in_thread;
-use crate::{ok_json, error_json, not_found};
+use crate::{ok_json, error_json, not_found, bad_request};
 use crate::AppState;
 use actix_web::{web, Responder};
-use log::{debug, error, info};
+use log::{debug, error, info, warn};
 use serde::{Deserialize, Serialize};
 
 // Import CQRS handlers
@@ -31,7 +31,7 @@ use crate::application::knowledge_graph::{
     UpdateNode,
     UpdateNodeHandler,
 };
-use visionclaw_domain::models::edge::Edge;
+use visionclaw_domain::models::edge::{Edge, EdgeProvenance, ProvenanceSource};
 use visionclaw_domain::models::node::Node;
 use hexser::{DirectiveHandler, QueryHandler};
 
@@ -73,6 +73,13 @@ pub struct AddEdgeRequest {
     pub edge: Edge,
 }
 
+/// `{"updates": [[source, target, weight], ...]}` with compact node ids.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EdgeWeightsRequest {
+    pub updates: Vec<crate::physics::edge_weights::EdgeWeightUpdate>,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BatchPositionsRequest {
@@ -321,7 +328,13 @@ pub async fn add_edge(
     state: web::Data<AppState>,
     request: web::Json<AddEdgeRequest>,
 ) -> impl Responder {
-    let edge = request.into_inner().edge;
+    let mut edge = request.into_inner().edge;
+    if edge.provenance.is_none() {
+        edge.provenance = Some(EdgeProvenance::from_source(
+            ProvenanceSource::Manual,
+            edge.weight,
+        ));
+    }
     let edge_id = edge.id.clone();
     let edge_source = edge.source;
     let edge_target = edge.target;
@@ -383,6 +396,71 @@ pub async fn update_edge(_auth: crate::settings::auth_extractor::AuthenticatedUs
     }
 }
 
+/// Change the weights of existing edges without a structural diff or a
+/// rebuild: the graph state is patched in place and, when the GPU is up, so
+/// are its spring weights.
+pub async fn update_edge_weights(
+    _auth: crate::settings::auth_extractor::AuthenticatedUser,
+    state: web::Data<AppState>,
+    request: web::Json<EdgeWeightsRequest>,
+) -> impl Responder {
+    use crate::actors::messages::{EdgeWeightsUpdated, ForceResumePhysics};
+
+    let updates = request.into_inner().updates;
+    info!("Updating weights of {} edge(s) in place", updates.len());
+
+    let report = match state
+        .graph_service_addr
+        .send(EdgeWeightsUpdated { updates: updates.clone() })
+        .await
+    {
+        Ok(Ok(report)) => report,
+        Ok(Err(e)) => {
+            warn!("Edge weight update rejected: {}", e);
+            return bad_request!(e);
+        }
+        Err(e) => {
+            error!("Graph service unavailable for edge weight update: {}", e);
+            return error_json!("Internal server error");
+        }
+    };
+
+    // The GPU copy is patched independently; its failure leaves the graph
+    // state updated and is reported rather than failing the request.
+    let gpu = match state.get_gpu_compute_addr().await {
+        Some(gpu_addr) => match gpu_addr.send(EdgeWeightsUpdated { updates }).await {
+            Ok(Ok(gpu_report)) => Some(gpu_report),
+            Ok(Err(e)) => {
+                warn!("GPU edge weight update failed: {}", e);
+                None
+            }
+            Err(e) => {
+                warn!("GPU actor unavailable for edge weight update: {}", e);
+                None
+            }
+        },
+        None => None,
+    };
+
+    if gpu.is_some_and(|r| r.updated > 0) {
+        if let Err(e) = state
+            .graph_service_addr
+            .send(ForceResumePhysics {
+                reason: "Edge weights updated".to_string(),
+            })
+            .await
+        {
+            warn!("Failed to resume physics after edge weight update: {}", e);
+        }
+    }
+
+    ok_json!(serde_json::json!({
+        "updated": report.updated,
+        "unknown": report.unknown,
+        "gpu": gpu,
+    }))
+}
+
 pub async fn batch_update_positions(
     _auth: crate::settings::auth_extractor::AuthenticatedUser,
     state: web::Data<AppState>,
@@ -429,6 +507,7 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             .route("/nodes/{id}", web::put().to(update_node))
             .route("/nodes/{id}", web::delete().to(remove_node))
             .route("/edges", web::post().to(add_edge))
+            .route("/edges/weights", web::post().to(update_edge_weights))
             .route("/edges/{id}", web::put().to(update_edge))
             .route("/positions/batch", web::post().to(batch_update_positions)),
     );
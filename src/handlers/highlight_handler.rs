@@ -0,0 +1,207 @@
+// src/handlers/highlight_handler.rs
+//! Chat highlight layers (`/api/highlights`).
+//!
+//! Chat answers and the chat agent publish layers here as they cite and
+//! focus nodes; clients can add their own, share them, and clear a
+//! conversation's layers when it ends. See `services::highlight_service`.
+
+use actix_web::{web, HttpResponse, Result};
+use log::debug;
+use serde::Deserialize;
+
+use crate::actors::messages::{GetGraphData, SendHighlightLayer};
+use crate::services::highlight_service::{
+    highlights, HighlightAction, HighlightAudience, HighlightLayer, HighlightReason, NewHighlight,
+};
+use crate::settings::auth_extractor::AuthenticatedUser;
+use crate::AppState;
+use crate::{bad_request, error_json, not_found, ok_json};
+
+fn send(
+    state: &AppState,
+    layer: HighlightLayer,
+    action: HighlightAction,
+    audience: HighlightAudience,
+) {
+    state.client_manager_addr.do_send(SendHighlightLayer {
+        layer,
+        action,
+        audience,
+    });
+}
+
+/// Add a layer and tell the sessions that can see it, removing any of the
+/// owner's layers it pushed out.
+pub(crate) fn publish(state: &AppState, new: NewHighlight) -> Result<HighlightLayer, String> {
+    let (layer, evicted) = highlights().add(new)?;
+    for old in evicted {
+        send(
+            state,
+            old,
+            HighlightAction::Remove,
+            HighlightAudience::Viewers,
+        );
+    }
+    debug!(
+        "[Highlights] {:?} layer {} of {} node(s) for {}",
+        layer.reason,
+        layer.id,
+        layer.node_ids.len(),
+        layer.owner
+    );
+    send(
+        state,
+        layer.clone(),
+        HighlightAction::Add,
+        HighlightAudience::Viewers,
+    );
+    Ok(layer)
+}
+
+/// GET /api/highlights
+///
+/// The caller's layers and those shared with them.
+pub async fn list_highlights(auth: AuthenticatedUser) -> Result<HttpResponse> {
+    ok_json!(serde_json::json!({ "layers": highlights().visible_to(&auth.pubkey) }))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HighlightRequest {
+    pub node_ids: Vec<u32>,
+    pub conversation_id: Option<String>,
+    pub focus_node: Option<u32>,
+    pub ttl_secs: Option<u64>,
+    #[serde(default)]
+    pub shared: bool,
+}
+
+/// POST /api/highlights
+///
+/// `{"nodeIds": [3, 17], "conversationId": "abc", "ttlSecs": 120, "shared": false}`.
+/// Unknown node ids are skipped.
+pub async fn add_highlight(
+    auth: AuthenticatedUser,
+    state: web::Data<AppState>,
+    body: web::Json<HighlightRequest>,
+) -> Result<HttpResponse> {
+    let request = body.into_inner();
+    let graph = match state.graph_service_addr.send(GetGraphData).await {
+        Ok(Ok(graph)) => graph,
+        Ok(Err(e)) => return error_json!("Failed to read graph", e),
+        Err(e) => return error_json!("Failed to read graph", e),
+    };
+    let node_ids: Vec<u32> = request
+        .node_ids
+        .into_iter()
+        .filter(|id| graph.nodes.iter().any(|n| n.id == *id))
+        .collect();
+    let new = NewHighlight {
+        conversation_id: request.conversation_id,
+        focus_node: request.focus_node,
+        ttl_secs: request.ttl_secs,
+        shared: request.shared,
+        ..NewHighlight::new(auth.pubkey, HighlightReason::Manual, node_ids)
+    };
+    match publish(&state, new) {
+        Ok(layer) => ok_json!(layer),
+        Err(e) => bad_request!(e),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClearQuery {
+    pub conversation_id: String,
+}
+
+/// DELETE /api/highlights?conversationId=abc
+///
+/// Clear the caller's layers from a conversation.
+pub async fn clear_conversation(
+    auth: AuthenticatedUser,
+    state: web::Data<AppState>,
+    query: web::Query<ClearQuery>,
+) -> Result<HttpResponse> {
+    let removed = highlights().remove_conversation(&auth.pubkey, &query.conversation_id);
+    let count = removed.len();
+    for layer in removed {
+        send(
+            &state,
+            layer,
+            HighlightAction::Remove,
+            HighlightAudience::Viewers,
+        );
+    }
+    ok_json!(serde_json::json!({ "removed": count }))
+}
+
+/// DELETE /api/highlights/{id}
+pub async fn remove_highlight(
+    auth: AuthenticatedUser,
+    state: web::Data<AppState>,
+    id: web::Path<u64>,
+) -> Result<HttpResponse> {
+    match highlights().remove(&auth.pubkey, *id) {
+        Some(layer) => {
+            send(
+                &state,
+                layer,
+                HighlightAction::Remove,
+                HighlightAudience::Viewers,
+            );
+            ok_json!(serde_json::json!({ "removed": *id }))
+        }
+        None => not_found!("No such highlight layer"),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ShareRequest {
+    pub shared: bool,
+}
+
+/// PUT /api/highlights/{id}/shared
+///
+/// `{"shared": true}` shows the layer in every signed-in session;
+/// `false` takes it back to the owner's own.
+pub async fn share_highlight(
+    auth: AuthenticatedUser,
+    state: web::Data<AppState>,
+    id: web::Path<u64>,
+    body: web::Json<ShareRequest>,
+) -> Result<HttpResponse> {
+    let Some(layer) = highlights().set_shared(&auth.pubkey, *id, body.shared) else {
+        return not_found!("No such highlight layer");
+    };
+    if layer.shared {
+        send(
+            &state,
+            layer.clone(),
+            HighlightAction::Add,
+            HighlightAudience::Viewers,
+        );
+    } else {
+        send(
+            &state,
+            layer.clone(),
+            HighlightAction::Remove,
+            HighlightAudience::Others,
+        );
+    }
+    ok_json!(layer)
+}
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/highlights")
+            .service(
+                web::resource("")
+                    .route(web::get().to(list_highlights))
+                    .route(web::post().to(add_highlight))
+                    .route(web::delete().to(clear_conversation)),
+            )
+            .route("/{id}", web::delete().to(remove_highlight))
+            .route("/{id}/shared", web::put().to(share_highlight)),
+    );
+}
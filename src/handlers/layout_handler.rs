@@ -1,14 +1,24 @@
 use actix_web::{web, HttpResponse, Result};
 use crate::layout::types::*;
 use crate::layout::engines::compute_layout;
+use crate::layout::group_by::{compute_group_layout, GroupBy, GroupLayoutConfig};
+use crate::physics::layout_seed::SeedReport;
+use crate::services::layout_history_service::layout_history;
+use crate::services::layout_refresh_service::{
+    announce_applied, announce_dismissed, compute_proposal, layout_proposals, offer,
+    worth_offering, DEFAULT_TRANSITION_MS, MAX_TRANSITION_MS,
+};
+use crate::settings::auth_extractor::AuthenticatedUser;
 use crate::AppState;
-use crate::ok_json;
+use crate::{bad_request, error_json, not_found, ok_json};
+use serde::Deserialize;
 
 pub async fn get_layout_modes(_data: web::Data<AppState>) -> Result<HttpResponse> {
     ok_json!(serde_json::json!({
         "current": "forceDirected",
         "available": ["forceDirected", "hierarchical", "radial", "spectral", "temporal", "clustered"],
-        "transitioning": false
+        "transitioning": false,
+        "groupBy": GroupBy::ALL
     }))
 }
 
@@ -93,6 +103,99 @@ pub async fn set_layout_mode(
     }))
 }
 
+/// POST /api/layout/group
+///
+/// `{"groupBy": "tag", "transitionMs": 500, "groupGap": 120, "iterations": 60}`
+///
+/// Clusters nodes around invisible per-group anchors. Computed fresh on each
+/// call, so re-posting after tags or types change regroups the graph.
+pub async fn set_group_layout(
+    data: web::Data<AppState>,
+    body: web::Json<serde_json::Value>,
+) -> Result<HttpResponse> {
+    let group_by_str = body.get("groupBy").and_then(|g| g.as_str()).unwrap_or("tag");
+    let transition_ms = body.get("transitionMs").and_then(|t| t.as_u64()).unwrap_or(500);
+    let Some(group_by) = GroupBy::parse(group_by_str) else {
+        return bad_request!(format!(
+            "Unknown groupBy '{}', expected one of {:?}",
+            group_by_str,
+            GroupBy::ALL
+        ));
+    };
+
+    let defaults = GroupLayoutConfig::default();
+    let number = |key: &str, default: f32| {
+        body.get(key)
+            .and_then(|v| v.as_f64())
+            .map(|v| v as f32)
+            .filter(|v| v.is_finite() && *v >= 0.0)
+            .unwrap_or(default)
+    };
+    let config = GroupLayoutConfig {
+        group_gap: number("groupGap", defaults.group_gap),
+        node_spacing: number("nodeSpacing", defaults.node_spacing),
+        attraction: number("attraction", defaults.attraction).min(1.0),
+        repulsion: number("repulsion", defaults.repulsion).min(1.0),
+        edge_strength: number("edgeStrength", defaults.edge_strength).min(0.1),
+        iterations: body
+            .get("iterations")
+            .and_then(|v| v.as_u64())
+            .map_or(defaults.iterations, |v| v.min(500) as usize),
+    };
+
+    use crate::actors::messages::GetGraphData;
+    let graph_data = match data.graph_service_addr.send(GetGraphData).await {
+        Ok(Ok(gd)) => gd,
+        Ok(Err(e)) => {
+            log::error!("set_group_layout: failed to get graph data: {}", e);
+            return error_json!("Failed to retrieve graph data", e);
+        }
+        Err(e) => {
+            log::error!("set_group_layout: actor mailbox error: {}", e);
+            return error_json!("Graph service unavailable", e.to_string());
+        }
+    };
+
+    // The layout iterates over every node and edge; keep it off the async worker.
+    let computed = web::block(move || {
+        let now = chrono::Utc::now();
+        let nodes: Vec<(u32, String)> = graph_data
+            .nodes
+            .iter()
+            .map(|n| (n.id, group_by.key_for(n, now)))
+            .collect();
+        let edges: Vec<(u32, u32, f32)> = graph_data
+            .edges
+            .iter()
+            .map(|e| (e.source, e.target, e.weight))
+            .collect();
+
+        let layout = compute_group_layout(&nodes, &edges, &config);
+
+        let positions: Vec<serde_json::Value> = nodes
+            .iter()
+            .zip(layout.positions.iter().zip(&layout.group_of))
+            .map(|((id, _key), (&(x, y, z), &group))| {
+                serde_json::json!({ "id": id, "x": x, "y": y, "z": z, "group": group })
+            })
+            .collect();
+        (layout.anchors, positions)
+    })
+    .await;
+    let (anchors, positions) = match computed {
+        Ok(computed) => computed,
+        Err(e) => return error_json!("Group layout failed", e.to_string()),
+    };
+
+    ok_json!(serde_json::json!({
+        "success": true,
+        "groupBy": group_by,
+        "transitionMs": transition_ms,
+        "groups": anchors,
+        "positions": positions
+    }))
+}
+
 pub async fn get_layout_status(_data: web::Data<AppState>) -> Result<HttpResponse> {
     ok_json!(LayoutStatus {
         current_mode: LayoutMode::ForceDirected,
@@ -161,14 +264,180 @@ pub async fn reset_layout(data: web::Data<AppState>) -> Result<HttpResponse> {
     }
 }
 
+/// GET /api/layout/history — retained keyframe times, oldest first.
+pub async fn get_layout_history(_data: web::Data<AppState>) -> Result<HttpResponse> {
+    let history = layout_history();
+    ok_json!(serde_json::json!({
+        "intervalMs": history.interval_ms(),
+        "retentionMs": history.retention_ms(),
+        "frames": history.timeline()
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct HistoryFrameQuery {
+    /// Unix ms; defaults to the newest keyframe.
+    pub t: Option<i64>,
+}
+
+/// GET /api/layout/history/frame?t=<unix ms> — the keyframe in effect at `t`.
+pub async fn get_layout_history_frame(
+    _data: web::Data<AppState>,
+    query: web::Query<HistoryFrameQuery>,
+) -> Result<HttpResponse> {
+    let t = query.t.unwrap_or(i64::MAX);
+    match layout_history().at(t) {
+        Some(frame) => ok_json!(frame),
+        None => not_found!("No layout history recorded yet"),
+    }
+}
+
+/// GET /api/layout/proposal — the open layout refresh proposal, if any.
+pub async fn get_layout_proposal(_data: web::Data<AppState>) -> Result<HttpResponse> {
+    ok_json!(serde_json::json!({
+        "proposal": layout_proposals().current(chrono::Utc::now())
+    }))
+}
+
+/// POST /api/layout/proposal — run the background re-layout now rather than
+/// waiting for the schedule. The result is offered to clients only if it
+/// clears `layoutRefresh.minImprovement`.
+pub async fn create_layout_proposal(
+    auth: AuthenticatedUser,
+    data: web::Data<AppState>,
+) -> Result<HttpResponse> {
+    use crate::actors::messages::GetSettings;
+
+    auth.require_power_user()?;
+    let (settings, physics) = match data.settings_addr.send(GetSettings).await {
+        Ok(Ok(settings)) => (
+            settings.layout_refresh.clone().unwrap_or_default(),
+            settings.get_physics("logseq").clone(),
+        ),
+        Ok(Err(e)) => return error_json!("Failed to read settings", e),
+        Err(e) => return error_json!("Settings service unavailable", e),
+    };
+    let proposal = match compute_proposal(&data.graph_service_addr, &settings, &physics).await {
+        Ok(proposal) => proposal,
+        Err(e) => return bad_request!(e),
+    };
+    let offered = worth_offering(&proposal, &settings);
+    if offered {
+        offer(&data.client_manager_addr, proposal.clone());
+    }
+    ok_json!(serde_json::json!({
+        "proposal": proposal,
+        "offered": offered,
+    }))
+}
+
+/// Seed proposal `id` into the live layout and tell clients to animate to
+/// it. Shared with the `applyLayoutProposal` socket message.
+pub(crate) async fn apply_proposal(
+    state: &AppState,
+    id: &str,
+    transition_ms: Option<u64>,
+) -> Result<SeedReport, String> {
+    use crate::actors::messages::{ForceResumePhysics, GetGraphData, SeedPositions};
+    use crate::physics::layout_seed::check_match;
+
+    let gpu_addr = state
+        .get_gpu_compute_addr()
+        .await
+        .ok_or_else(|| "GPU compute is not available".to_string())?;
+    let proposal = layout_proposals().take(id, chrono::Utc::now())?;
+    let graph = state
+        .graph_service_addr
+        .send(GetGraphData)
+        .await
+        .map_err(|e| format!("Graph service unavailable: {}", e))??;
+    let positions = proposal.seed_for(&graph);
+    if let Err(e) = check_match(positions.len(), proposal.positions.len()) {
+        // The graph has moved on; nobody can apply this proposal any more.
+        announce_dismissed(&state.client_manager_addr, id);
+        return Err(e);
+    }
+    let report = gpu_addr
+        .send(SeedPositions { positions })
+        .await
+        .map_err(|e| format!("GPU actor unavailable: {}", e))??;
+    if report.applied > 0 {
+        if let Err(e) = state
+            .graph_service_addr
+            .send(ForceResumePhysics {
+                reason: format!("Layout proposal {} applied", id),
+            })
+            .await
+        {
+            log::warn!("Failed to resume physics after applying proposal: {}", e);
+        }
+    }
+    let transition_ms = transition_ms
+        .unwrap_or(DEFAULT_TRANSITION_MS)
+        .min(MAX_TRANSITION_MS);
+    announce_applied(&state.client_manager_addr, id, report.applied, transition_ms);
+    Ok(report)
+}
+
+/// Close proposal `id` for everyone. Shared with the
+/// `dismissLayoutProposal` socket message.
+pub(crate) fn dismiss_proposal(state: &AppState, id: &str) -> bool {
+    let dismissed = layout_proposals().dismiss(id).is_some();
+    if dismissed {
+        announce_dismissed(&state.client_manager_addr, id);
+    }
+    dismissed
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProposalRequest {
+    pub id: String,
+    pub transition_ms: Option<u64>,
+}
+
+/// POST /api/layout/proposal/apply — `{ id, transitionMs? }`.
+pub async fn apply_layout_proposal(
+    auth: AuthenticatedUser,
+    data: web::Data<AppState>,
+    body: web::Json<ProposalRequest>,
+) -> Result<HttpResponse> {
+    auth.require_power_user()?;
+    match apply_proposal(&data, &body.id, body.transition_ms).await {
+        Ok(report) => ok_json!(report),
+        Err(e) => bad_request!(e),
+    }
+}
+
+/// POST /api/layout/proposal/dismiss — `{ id }`.
+pub async fn dismiss_layout_proposal(
+    auth: AuthenticatedUser,
+    data: web::Data<AppState>,
+    body: web::Json<ProposalRequest>,
+) -> Result<HttpResponse> {
+    auth.require_power_user()?;
+    if dismiss_proposal(&data, &body.id) {
+        ok_json!(serde_json::json!({ "dismissed": body.id }))
+    } else {
+        not_found!(format!("No open layout proposal '{}'", body.id))
+    }
+}
+
 pub fn configure_layout_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/layout")
             .route("/modes", web::get().to(get_layout_modes))
             .route("/mode", web::post().to(set_layout_mode))
+            .route("/group", web::post().to(set_group_layout))
             .route("/status", web::get().to(get_layout_status))
             .route("/zones", web::post().to(set_zones))
             .route("/zones", web::get().to(get_zones))
             .route("/reset", web::post().to(reset_layout))
+            .route("/history", web::get().to(get_layout_history))
+            .route("/history/frame", web::get().to(get_layout_history_frame))
+            .route("/proposal", web::get().to(get_layout_proposal))
+            .route("/proposal", web::post().to(create_layout_proposal))
+            .route("/proposal/apply", web::post().to(apply_layout_proposal))
+            .route("/proposal/dismiss", web::post().to(dismiss_layout_proposal))
     );
 }
@@ -0,0 +1,146 @@
+// src/handlers/lint_handler.rs
+//! Graph lint report (`/api/lint`).
+//!
+//! Rules and their severities are the `lint` settings; see
+//! `services::lint_service`. Reading the report never touches the graph;
+//! node badges are refreshed only on `POST /api/lint/flags`.
+
+use actix_web::{web, HttpResponse, Result};
+use log::{debug, error};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::actors::messages::{GetGraphData, GetSettings, SetNodeMetadataFlag};
+use crate::config::{LintSettings, LintSeverity};
+use crate::services::duplicate_detection_service::load_pages;
+use crate::services::lint_service::{lint, LintReport, LintRule, LINT_FLAG};
+use crate::settings::auth_extractor::AuthenticatedUser;
+use crate::AppState;
+use crate::{bad_request, error_json, ok_json, service_unavailable};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintQuery {
+    pub rule: Option<String>,
+    pub min_severity: Option<LintSeverity>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlagResponse {
+    pub flag_nodes: bool,
+    pub flagged: usize,
+}
+
+async fn lint_settings(
+    app_state: &AppState,
+) -> std::result::Result<LintSettings, Result<HttpResponse>> {
+    match app_state.settings_addr.send(GetSettings).await {
+        Ok(Ok(settings)) => Ok(settings.lint.unwrap_or_default()),
+        Ok(Err(e)) => Err(error_json!("Failed to read settings", e)),
+        Err(e) => {
+            error!("Settings actor error: {}", e);
+            Err(service_unavailable!("Settings service unavailable"))
+        }
+    }
+}
+
+async fn run_lint(
+    app_state: &AppState,
+    settings: LintSettings,
+) -> std::result::Result<LintReport, Result<HttpResponse>> {
+    let graph = match app_state.graph_service_addr.send(GetGraphData).await {
+        Ok(Ok(graph)) => graph,
+        Ok(Err(e)) => return Err(error_json!("Failed to read graph", e)),
+        Err(e) => {
+            error!("Graph service error: {}", e);
+            return Err(service_unavailable!("Graph service unavailable"));
+        }
+    };
+    match web::block(move || load_pages().map(|pages| lint(&graph, &pages, &settings))).await {
+        Ok(Ok(report)) => Ok(report),
+        Ok(Err(e)) => Err(error_json!("Failed to load pages", e)),
+        Err(e) => Err(error_json!("Lint run aborted", e)),
+    }
+}
+
+/// GET /api/lint?rule=orphanPage&minSeverity=warning
+///
+/// Lints the whole vault and returns the findings, worst first. `rule` and
+/// `minSeverity` narrow the listing; counts always cover every finding.
+pub async fn get_lint(
+    _auth: AuthenticatedUser,
+    app_state: web::Data<AppState>,
+    query: web::Query<LintQuery>,
+) -> Result<HttpResponse> {
+    let rule = match query.rule.as_deref().map(|r| (r, LintRule::parse(r))) {
+        Some((name, None)) => return bad_request!(format!("unknown lint rule '{}'", name)),
+        Some((_, rule)) => rule,
+        None => None,
+    };
+
+    let settings = match lint_settings(&app_state).await {
+        Ok(settings) => settings,
+        Err(response) => return response,
+    };
+    let mut report = match run_lint(&app_state, settings).await {
+        Ok(report) => report,
+        Err(response) => return response,
+    };
+
+    let min_severity = query.min_severity.unwrap_or(LintSeverity::Info);
+    report
+        .findings
+        .retain(|f| f.severity >= min_severity && rule.map_or(true, |r| f.rule == r));
+    ok_json!(report)
+}
+
+/// POST /api/lint/flags
+///
+/// Re-lints the vault and sets each offending node's `lint` badge to its
+/// worst severity, or clears every badge when `lint.flagNodes` is off.
+/// Clients get the changed nodes in a `graphDiff`.
+pub async fn refresh_lint_flags(
+    auth: AuthenticatedUser,
+    app_state: web::Data<AppState>,
+) -> Result<HttpResponse> {
+    auth.require_power_user()?;
+    let settings = match lint_settings(&app_state).await {
+        Ok(settings) => settings,
+        Err(response) => return response,
+    };
+
+    let flag_nodes = settings.flag_nodes;
+    let values = if flag_nodes {
+        match run_lint(&app_state, settings).await {
+            Ok(report) => report.node_flags(),
+            Err(response) => return response,
+        }
+    } else {
+        HashMap::new()
+    };
+
+    let msg = SetNodeMetadataFlag {
+        key: LINT_FLAG.to_string(),
+        values,
+    };
+    match app_state.graph_service_addr.send(msg).await {
+        Ok(Ok(flagged)) => {
+            debug!("[Lint] Flagged {} node(s)", flagged);
+            ok_json!(FlagResponse {
+                flag_nodes,
+                flagged
+            })
+        }
+        Ok(Err(e)) => error_json!("Failed to flag nodes", e),
+        Err(e) => {
+            error!("Graph service error: {}", e);
+            service_unavailable!("Graph service unavailable")
+        }
+    }
+}
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("/lint", web::get().to(get_lint))
+        .route("/lint/flags", web::post().to(refresh_lint_flags));
+}
@@ -0,0 +1,177 @@
+// src/handlers/maintenance_handler.rs
+//! Vault maintenance endpoints (`/api/maintenance/*`).
+
+use actix_web::{web, HttpResponse, Result};
+use log::{error, warn};
+use serde::Deserialize;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::actors::messages::{GetSettings, UpdateNodeFromMetadata};
+use crate::config::WriteMode;
+use crate::services::duplicate_detection_service::{
+    load_pages, merge_pages, DuplicateDetector, DEFAULT_CONTENT_THRESHOLD,
+};
+use crate::services::embedding_sync_service::embeddings;
+use crate::services::file_service::markdown_dir;
+use crate::services::link_checker_service::LinkCheckerService;
+use crate::services::write_back_service::{write_mode, PageEdit, WriteBackService};
+use crate::settings::auth_extractor::AuthenticatedUser;
+use crate::AppState;
+use crate::{bad_request, error_json, ok_json, service_unavailable};
+
+#[derive(Debug, Deserialize)]
+pub struct DuplicatesQuery {
+    pub threshold: Option<f32>,
+}
+
+/// GET /api/maintenance/duplicates
+///
+/// Scans every page for likely duplicates (identical content, near-identical
+/// titles, similar content). `?threshold=` tunes the content-similarity cut-off;
+/// content is compared by stored page embeddings where they exist.
+pub async fn get_duplicates(
+    auth: AuthenticatedUser,
+    query: web::Query<DuplicatesQuery>,
+) -> Result<HttpResponse> {
+    auth.require_power_user()?;
+    let threshold = query.threshold.unwrap_or(DEFAULT_CONTENT_THRESHOLD);
+    if !(0.0..=1.0).contains(&threshold) {
+        return bad_request!("threshold must be between 0 and 1");
+    }
+
+    let vectors = embeddings().page_vectors();
+    let result = web::block(move || -> Result<_, String> {
+        let pages = load_pages()?;
+        let candidates = DuplicateDetector::new(threshold, None)
+            .with_page_vectors(vectors)
+            .find(&pages);
+        Ok((pages.len(), candidates))
+    })
+    .await;
+
+    match result {
+        Ok(Ok((scanned, candidates))) => ok_json!(serde_json::json!({
+            "scanned": scanned,
+            "threshold": threshold,
+            "candidates": candidates,
+        })),
+        Ok(Err(e)) => error_json!("Failed to scan pages", e),
+        Err(e) => error_json!("Duplicate scan aborted", e),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeRequest {
+    pub keep: String,
+    pub duplicate: String,
+    /// Write back even if either page changed remotely.
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// POST /api/maintenance/duplicates/merge
+///
+/// Folds `duplicate` into `keep` and replaces `duplicate` with a redirect stub.
+/// The merge is always applied locally; if writing it back would overwrite
+/// remote changes the response is 409 with the conflicts and the edits stay
+/// queued.
+pub async fn merge_duplicates(
+    auth: AuthenticatedUser,
+    app_state: web::Data<AppState>,
+    write_back: Option<web::Data<Arc<WriteBackService>>>,
+    body: web::Json<MergeRequest>,
+) -> Result<HttpResponse> {
+    auth.require_power_user()?;
+    let MergeRequest {
+        keep,
+        duplicate,
+        force,
+    } = body.into_inner();
+
+    let report = match web::block(move || merge_pages(&keep, &duplicate)).await {
+        Ok(Ok(report)) => report,
+        Ok(Err(e)) => return bad_request!(e),
+        Err(e) => {
+            error!("[Maintenance] Merge task failed: {}", e);
+            return error_json!("Merge failed", e);
+        }
+    };
+
+    for metadata in [&report.kept_metadata, &report.stub_metadata].into_iter().flatten() {
+        let msg = UpdateNodeFromMetadata {
+            metadata_id: metadata.file_name.clone(),
+            metadata: metadata.clone(),
+        };
+        match app_state.graph_service_addr.send(msg).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => warn!("[Maintenance] Graph not refreshed after merge: {}", e),
+            Err(e) => warn!("[Maintenance] Graph service unreachable after merge: {}", e),
+        }
+    }
+
+    if let Some(write_back) = write_back {
+        let edits: Vec<PageEdit> = [
+            (&report.kept_metadata, format!("merge in '{}'", report.redirected)),
+            (&report.stub_metadata, format!("redirect to '{}'", report.kept)),
+        ]
+        .into_iter()
+        .filter_map(|(metadata, summary)| {
+            let metadata = metadata.as_ref()?;
+            let path = Path::new(markdown_dir()).join(&metadata.file_name);
+            Some(PageEdit {
+                file_name: metadata.file_name.clone(),
+                content: std::fs::read_to_string(path).ok()?,
+                summary,
+                base_sha: metadata.file_blob_sha.clone(),
+            })
+        })
+        .collect();
+        let mode = match app_state.settings_addr.send(GetSettings).await {
+            Ok(Ok(settings)) => write_mode(&settings),
+            _ => WriteMode::default(),
+        };
+        match write_back.submit(edits, mode, force).await {
+            Ok(outcome) if !outcome.conflicts().is_empty() => {
+                return Ok(HttpResponse::Conflict().json(serde_json::json!({
+                    "error": "Merged locally, but the pages changed remotely",
+                    "merge": report,
+                    "conflicts": outcome.conflicts(),
+                })));
+            }
+            Ok(_) => {}
+            Err(e) => warn!("[Maintenance] Merge saved locally but not written back: {}", e),
+        }
+    }
+
+    ok_json!(report)
+}
+
+/// GET /api/maintenance/broken_links
+///
+/// External URLs that failed their last check, with the pages citing them.
+pub async fn get_broken_links(
+    checker: Option<web::Data<Arc<LinkCheckerService>>>,
+) -> Result<HttpResponse> {
+    let checker = match checker {
+        Some(c) => c,
+        None => return service_unavailable!("Link checker is not running"),
+    };
+    let broken = checker.broken_links().await;
+    ok_json!(serde_json::json!({
+        "lastPass": checker.last_pass().await,
+        "checked": checker.checked_count().await,
+        "brokenCount": broken.len(),
+        "broken": broken,
+    }))
+}
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/maintenance")
+            .route("/duplicates", web::get().to(get_duplicates))
+            .route("/duplicates/merge", web::post().to(merge_duplicates))
+            .route("/broken_links", web::get().to(get_broken_links)),
+    );
+}
@@ -4,7 +4,16 @@ use std::collections::HashMap;
 use std::sync::atomic::Ordering;
 use std::time::Instant;
 
+use crate::actors::messages::GetPhysicsOrchestratorActor;
+use crate::actors::physics_orchestrator_actor::GetComputeBudgetUsage;
+use crate::handlers::socket_flow_handler::hibernation::{hibernation_stats, HibernationStats};
+use crate::handlers::socket_flow_handler::panic_guard::{session_panic_stats, SessionPanicStats};
 use crate::ok_json;
+use crate::physics::compute_budget::ComputeBudgetUsage;
+use crate::physics::simulation_watchdog::{watchdog_stats, WatchdogStats};
+use crate::physics::stability_monitor::{readback_scrub_stats, ReadbackScrubStats};
+use crate::services::github::object_store::{object_store, ObjectStoreStats};
+use crate::services::github::tree_cache::{tree_listing_cache, TreeCacheStats};
 use crate::AppState;
 use crate::utils::network::CircuitBreakerStats;
 
@@ -16,8 +25,23 @@ pub struct ProcessStartTime(pub Instant);
 pub struct MetricsResponse {
     pub uptime_secs: u64,
     pub active_connections: usize,
+    /// WebSocket frame handler panics contained without killing the session.
+    pub websocket_panics: SessionPanicStats,
+    /// Idle WebSocket sessions that have stopped receiving broadcasts.
+    pub websocket_hibernation: HibernationStats,
     pub event_bus: EventBusMetrics,
     pub circuit_breakers: HashMap<String, CircuitBreakerStats>,
+    /// Physics compute budget caps and actual usage over the last second.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compute_budget: Option<ComputeBudgetUsage>,
+    /// Non-finite values repaired or rejected in GPU position readbacks.
+    pub readback_scrub: ReadbackScrubStats,
+    /// Physics pipeline stalls detected and restarts attempted.
+    pub simulation_watchdog: WatchdogStats,
+    /// GitHub tree listing cache effectiveness.
+    pub github_tree_cache: TreeCacheStats,
+    /// Page blobs served from the local object store versus downloaded.
+    pub github_object_store: ObjectStoreStats,
 }
 
 #[derive(Serialize)]
@@ -29,8 +53,10 @@ pub struct EventBusMetrics {
 
 /// GET /api/metrics
 ///
-/// Returns JSON with process uptime, active WebSocket connections,
-/// event bus publish/handler/error counters, and circuit breaker states.
+/// Returns JSON with process uptime, active WebSocket connections and
+/// hibernated sessions, event bus publish/handler/error counters, circuit breaker states,
+/// physics compute budget usage, GPU readback scrub counters and simulation
+/// watchdog incidents.
 pub async fn get_metrics(
     app_state: web::Data<AppState>,
     start_time: web::Data<ProcessStartTime>,
@@ -47,11 +73,23 @@ pub async fn get_metrics(
     // will automatically populate.
     let circuit_breakers: HashMap<String, CircuitBreakerStats> = HashMap::new();
 
+    let compute_budget = match app_state.graph_service_addr.send(GetPhysicsOrchestratorActor).await {
+        Ok(Ok(physics)) => physics.send(GetComputeBudgetUsage).await.ok(),
+        _ => None,
+    };
+
     let response = MetricsResponse {
         uptime_secs,
         active_connections,
+        websocket_panics: session_panic_stats(),
+        websocket_hibernation: hibernation_stats(),
         event_bus: event_bus_metrics,
         circuit_breakers,
+        compute_budget,
+        readback_scrub: readback_scrub_stats(),
+        simulation_watchdog: watchdog_stats(),
+        github_tree_cache: tree_listing_cache().stats(),
+        github_object_store: object_store().stats(),
     };
 
     ok_json!(response)
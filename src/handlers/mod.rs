@@ -59,6 +59,65 @@ pub use memory_flash_handler::configure_routes as configure_memory_flash_routes;
 pub mod enrichment_proposals_handler;
 pub use enrichment_proposals_handler::configure_routes as configure_enrichment_proposals_routes;
 
+// Vault maintenance (duplicates, broken links)
+pub mod maintenance_handler;
+pub use maintenance_handler::configure_routes as configure_maintenance_routes;
+
+// Graph index consistency checks (routed under /admin)
+pub mod graph_consistency_handler;
+
+// Incremental page embeddings (routed under /admin)
+pub mod embedding_sync_handler;
+
+// External API usage and cost (routed under /admin)
+pub mod cost_handler;
+
+// Aggregated ops dashboard document (routed under /admin)
+pub mod admin_overview_handler;
+
+// Page write-back to GitHub (direct commits or batched PRs)
+pub mod write_back_handler;
+pub use write_back_handler::configure_routes as configure_write_back_routes;
+
+// Zip vault uploads for deployments without GitHub (routed under /admin)
+pub mod vault_upload_handler;
+
+// Edge provenance inspection
+pub mod edge_handler;
+pub use edge_handler::configure_routes as configure_edge_routes;
+
+// Bulk node lookup with field projection
+pub mod node_query_handler;
+pub use node_query_handler::configure_routes as configure_node_query_routes;
+
+// Outgoing webhook delivery log
+pub mod webhook_handler;
+pub use webhook_handler::configure_routes as configure_webhook_routes;
+
+// Camera path recordings and replay export
+pub mod recording_handler;
+pub use recording_handler::configure_routes as configure_recording_routes;
+
+// Per-device XR room anchors
+pub mod xr_anchor_handler;
+pub use xr_anchor_handler::configure_routes as configure_xr_anchor_routes;
+
+// Positional audio cue catalog, opt-in and emit
+pub mod audio_cue_handler;
+pub use audio_cue_handler::configure_routes as configure_audio_cue_routes;
+
+// Conversation-scoped chat highlight layers
+pub mod highlight_handler;
+pub use highlight_handler::configure_routes as configure_highlight_routes;
+
+// Graph lint report
+pub mod lint_handler;
+pub use lint_handler::configure_routes as configure_lint_routes;
+
+// Rate-limited public graph mirror (/public/graph/*)
+pub mod public_api_handler;
+pub use public_api_handler::configure_routes as configure_public_api_routes;
+
 // Layout mode system (ADR-031)
 pub mod layout_handler;
 pub use layout_handler::configure_layout_routes;
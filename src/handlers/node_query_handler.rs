@@ -0,0 +1,255 @@
+// src/handlers/node_query_handler.rs
+//! Bulk node lookup with field projection (`/api/nodes/*`).
+//!
+//! `GET /api/graph/data` ships every node with every field. Clients that
+//! hydrate only what is on screen instead post the visible ids and the fields
+//! they need, and get back just those. Fields are taken from the node's
+//! schema 2 [`NodeWithPosition`] view, so they have the same shape here as
+//! in `GET /api/graph/data`.
+
+use actix_web::{web, HttpResponse, Result};
+use log::error;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use visionclaw_domain::models::node::Node;
+
+use crate::actors::messages::GetGraphData;
+use crate::protocol::graph_json::{JsonSchema, NodeWithPosition};
+use crate::AppState;
+use crate::{bad_request, error_json, ok_json};
+
+const MAX_IDS: usize = 5000;
+
+/// A projectable node field. `MetadataKey` selects a single metadata entry
+/// via `metadata.<key>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeField {
+    Label,
+    MetadataId,
+    Type,
+    Size,
+    Color,
+    Weight,
+    Group,
+    Position,
+    Velocity,
+    LastModified,
+    FileSize,
+    Metadata,
+    MetadataKey(String),
+}
+
+impl NodeField {
+    pub fn parse(name: &str) -> Option<Self> {
+        if let Some(key) = name.strip_prefix("metadata.") {
+            return (!key.is_empty()).then(|| NodeField::MetadataKey(key.to_string()));
+        }
+        Some(match name {
+            "label" => NodeField::Label,
+            "metadataId" => NodeField::MetadataId,
+            "type" => NodeField::Type,
+            "size" => NodeField::Size,
+            "color" => NodeField::Color,
+            "weight" => NodeField::Weight,
+            "group" => NodeField::Group,
+            "position" => NodeField::Position,
+            "velocity" => NodeField::Velocity,
+            "lastModified" => NodeField::LastModified,
+            "fileSize" => NodeField::FileSize,
+            "metadata" => NodeField::Metadata,
+            _ => return None,
+        })
+    }
+
+    fn key(&self) -> &str {
+        match self {
+            NodeField::Label => "label",
+            NodeField::MetadataId => "metadataId",
+            NodeField::Type => "type",
+            NodeField::Size => "size",
+            NodeField::Color => "color",
+            NodeField::Weight => "weight",
+            NodeField::Group => "group",
+            NodeField::Position => "position",
+            NodeField::Velocity => "velocity",
+            NodeField::LastModified => "lastModified",
+            NodeField::FileSize => "fileSize",
+            NodeField::Metadata => "metadata",
+            NodeField::MetadataKey(key) => key,
+        }
+    }
+
+    /// The field from `view`, the node's encoded [`NodeWithPosition`].
+    /// `metadata.<key>` reads the stored string map.
+    fn value(&self, node: &Node, view: &Map<String, Value>) -> Value {
+        let typed_meta = |key: &str| {
+            view.get("metadata")
+                .and_then(|meta| meta.get(key))
+                .cloned()
+                .unwrap_or(Value::Null)
+        };
+        match self {
+            NodeField::LastModified | NodeField::FileSize => typed_meta(self.key()),
+            NodeField::MetadataKey(key) => node
+                .metadata
+                .get(key)
+                .map_or(Value::Null, |v| Value::String(v.clone())),
+            field => view.get(field.key()).cloned().unwrap_or(Value::Null),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeQueryRequest {
+    pub ids: Vec<u32>,
+    /// Defaults to `["label"]`. `id` is always included.
+    #[serde(default)]
+    pub fields: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeQueryResponse {
+    pub nodes: Vec<Map<String, Value>>,
+    /// Requested ids that are not in the graph.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub missing: Vec<u32>,
+}
+
+pub fn parse_fields(names: &[String]) -> std::result::Result<Vec<NodeField>, String> {
+    if names.is_empty() {
+        return Ok(vec![NodeField::Label]);
+    }
+    let mut fields = Vec::with_capacity(names.len());
+    for name in names {
+        if name == "id" {
+            continue;
+        }
+        let field = NodeField::parse(name).ok_or_else(|| format!("Unknown field '{}'", name))?;
+        if !fields.contains(&field) {
+            fields.push(field);
+        }
+    }
+    Ok(fields)
+}
+
+/// Project `ids` (in request order) against the node map. `null`-valued
+/// fields are omitted to keep the payload small.
+pub fn project(
+    node_map: &HashMap<u32, &Node>,
+    ids: &[u32],
+    fields: &[NodeField],
+) -> NodeQueryResponse {
+    let mut nodes = Vec::with_capacity(ids.len());
+    let mut missing = Vec::new();
+    for &id in ids {
+        let Some(node) = node_map.get(&id) else {
+            missing.push(id);
+            continue;
+        };
+        let view = match JsonSchema::CURRENT.encode(&NodeWithPosition::from(*node)) {
+            Value::Object(view) => view,
+            _ => Map::new(),
+        };
+        let mut row = Map::with_capacity(fields.len() + 1);
+        row.insert("id".to_string(), id.into());
+        for field in fields {
+            let value = field.value(node, &view);
+            if !value.is_null() {
+                row.insert(field.key().to_string(), value);
+            }
+        }
+        nodes.push(row);
+    }
+    NodeQueryResponse { nodes, missing }
+}
+
+/// POST /api/nodes/query
+///
+/// `{"ids": [1, 2, 3], "fields": ["label", "size", "lastModified"]}`
+pub async fn query_nodes(
+    app_state: web::Data<AppState>,
+    request: web::Json<NodeQueryRequest>,
+) -> Result<HttpResponse> {
+    let mut request = request.into_inner();
+    if request.ids.len() > MAX_IDS {
+        return bad_request!(format!("At most {} ids per query", MAX_IDS));
+    }
+    let fields = match parse_fields(&request.fields) {
+        Ok(fields) => fields,
+        Err(e) => return bad_request!(e),
+    };
+    let mut seen = std::collections::HashSet::with_capacity(request.ids.len());
+    request.ids.retain(|id| seen.insert(*id));
+
+    let graph_data = match app_state.graph_service_addr.send(GetGraphData).await {
+        Ok(Ok(data)) => data,
+        Ok(Err(e)) => return error_json!("Failed to read graph", e),
+        Err(e) => {
+            error!("[Nodes] Graph service unreachable: {}", e);
+            return error_json!("Graph service unavailable", e.to_string());
+        }
+    };
+
+    let node_map: HashMap<u32, &Node> = graph_data.nodes.iter().map(|n| (n.id, n)).collect();
+    ok_json!(project(&node_map, &request.ids, &fields))
+}
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::scope("/nodes").route("/query", web::post().to(query_nodes)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn projects_only_requested_fields() {
+        let mut node = Node::new_with_id("page-a".to_string(), Some(7));
+        node.label = "Page A".to_string();
+        node.size = Some(2.5);
+        node.metadata.insert(
+            "last_modified".to_string(),
+            "2024-01-01T00:00:00Z".to_string(),
+        );
+        node.metadata
+            .insert("file_size".to_string(), "2048".to_string());
+        let map: HashMap<u32, &Node> = [(7, &node)].into_iter().collect();
+
+        let fields = parse_fields(&[
+            "label".to_string(),
+            "size".to_string(),
+            "lastModified".to_string(),
+            "fileSize".to_string(),
+            "color".to_string(),
+        ])
+        .unwrap();
+        let resp = project(&map, &[7, 9], &fields);
+
+        assert_eq!(resp.missing, vec![9]);
+        let row = &resp.nodes[0];
+        assert_eq!(row["id"], 7);
+        assert_eq!(row["label"], "Page A");
+        assert_eq!(row["lastModified"], "2024-01-01T00:00:00Z");
+        assert_eq!(row["fileSize"], 2048, "sizes are numbers");
+        assert!(!row.contains_key("color"), "null fields are omitted");
+        assert!(!row.contains_key("metadataId"));
+
+        let position = parse_fields(&["position".to_string()]).unwrap();
+        let row = &project(&map, &[7], &position).nodes[0];
+        let view = JsonSchema::CURRENT.encode(&NodeWithPosition::from(&node));
+        assert_eq!(row["position"], view["position"], "same shape as the graph view");
+    }
+
+    #[test]
+    fn field_parsing() {
+        assert_eq!(parse_fields(&[]).unwrap(), vec![NodeField::Label]);
+        assert_eq!(
+            parse_fields(&["id".to_string(), "metadata.domain".to_string()]).unwrap(),
+            vec![NodeField::MetadataKey("domain".to_string())]
+        );
+        assert!(parse_fields(&["password".to_string()]).is_err());
+    }
+}
@@ -2,6 +2,7 @@ use crate::actors::messages::{GetMetadata, GetSettings};
 use visionclaw_domain::models::metadata::Metadata;
 use crate::services::github::content_enhanced::ExtendedFileMetadata;
 use crate::ok_json;
+use crate::services::file_service::markdown_dir;
 use crate::AppState;
 use actix_web::{web, HttpResponse, Result};
 use futures::future::join_all;
@@ -123,7 +124,7 @@ pub async fn get_pages(app_state: web::Data<AppState>) -> Result<HttpResponse> {
                         Some(PageInfo {
                             id,
                             title: meta.file_name.clone(),
-                            path: format!("/app/data/markdown/{}", meta.file_name),
+                            path: format!("{}/{}", markdown_dir(), meta.file_name),
                             parent: None,
                             modified,
                         })
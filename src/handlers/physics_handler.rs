@@ -8,7 +8,7 @@ use log::warn;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use crate::{ok_json, error_json};
+use crate::{ok_json, error_json, bad_request};
 use crate::AppState;
 use crate::settings::auth_extractor::AuthenticatedUser;
 
@@ -16,7 +16,14 @@ use crate::application::physics_service::{
     LayoutOptimizationRequest, PhysicsService, SimulationParams,
 };
 use visionclaw_domain::models::graph::GraphData;
+use crate::actors::messages::{ControlSimulation, SetSimulationQuality};
 use crate::models::simulation_params::SettleMode;
+use crate::physics::quality::{
+    save_simulation_quality, simulation_quality, QualityLevel, QualityPreset,
+};
+use crate::physics::simulation_control::{
+    simulation_control, SimulationCommand, MAX_STEPS_PER_REQUEST,
+};
 use visionclaw_domain::ports::gpu_physics_adapter::PhysicsParameters;
 
 #[derive(Debug, Deserialize)]
@@ -408,7 +415,141 @@ pub async fn set_settle_mode(
     })
 }
 
+#[derive(Debug, Deserialize)]
+pub struct StepProfileQuery {
+    /// Newest frames to include; defaults to the whole window.
+    pub frames: Option<usize>,
+}
+
+/// GET /simulation/profile -- per-stage timing of the last N physics steps
+/// (lock wait, upload, kernel, readback, encode, broadcast) with a summary.
+pub async fn get_step_profile(query: web::Query<StepProfileQuery>) -> ActixResult<HttpResponse> {
+    let frames = query
+        .frames
+        .unwrap_or(crate::physics::step_profile::PROFILE_CAPACITY);
+    let profile = crate::physics::step_profile::step_profiler().profile(frames);
+    ok_json!(profile)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StepSimulationQuery {
+    /// Physics steps to run before holding again; defaults to 1.
+    pub steps: Option<u32>,
+}
+
+async fn control_simulation(
+    state: &AppState,
+    command: SimulationCommand,
+) -> ActixResult<HttpResponse> {
+    match state
+        .graph_service_addr
+        .send(ControlSimulation { command })
+        .await
+    {
+        Ok(Ok(status)) => ok_json!(status),
+        Ok(Err(e)) => error_json!("Simulation control failed: {}", e),
+        Err(e) => error_json!("Graph service unavailable: {}", e),
+    }
+}
+
+/// POST /simulation/pause -- hold the physics loop until resumed, regardless
+/// of equilibrium or interaction.
+pub async fn pause_simulation(
+    user: AuthenticatedUser,
+    state: web::Data<AppState>,
+) -> ActixResult<HttpResponse> {
+    user.require_power_user()?;
+    control_simulation(&state, SimulationCommand::Pause).await
+}
+
+/// POST /simulation/resume -- lift an operator pause (and an equilibrium pause).
+pub async fn resume_simulation(
+    user: AuthenticatedUser,
+    state: web::Data<AppState>,
+) -> ActixResult<HttpResponse> {
+    user.require_power_user()?;
+    control_simulation(&state, SimulationCommand::Resume).await
+}
+
+/// POST /simulation/step?steps=N -- run exactly N physics steps, then hold.
+pub async fn step_simulation(
+    user: AuthenticatedUser,
+    state: web::Data<AppState>,
+    query: web::Query<StepSimulationQuery>,
+) -> ActixResult<HttpResponse> {
+    user.require_power_user()?;
+    let steps = query.steps.unwrap_or(1);
+    if steps == 0 || steps > MAX_STEPS_PER_REQUEST {
+        return bad_request!(format!(
+            "steps must be between 1 and {}",
+            MAX_STEPS_PER_REQUEST
+        ));
+    }
+    control_simulation(&state, SimulationCommand::Step { steps }).await
+}
+
+/// GET /simulation/control -- current pause/step state.
+pub async fn get_simulation_control() -> ActixResult<HttpResponse> {
+    ok_json!(simulation_control().status())
+}
+
+/// Switch the quality level and keep it for the next start. Shared with the
+/// `simulationQuality` socket message. Collision needs no message: semantic
+/// forces read the level when they build the GPU config.
+pub(crate) async fn apply_quality(
+    state: &AppState,
+    level: QualityLevel,
+) -> Result<QualityPreset, String> {
+    let preset = state
+        .graph_service_addr
+        .send(SetSimulationQuality { level })
+        .await
+        .map_err(|e| format!("Graph service unavailable: {}", e))??;
+    let saved = tokio::task::spawn_blocking(move || save_simulation_quality(level))
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|saved| saved);
+    if let Err(e) = saved {
+        warn!("Simulation quality {:?} applied but not saved: {}", level, e);
+    }
+    Ok(preset)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetQualityRequest {
+    pub level: QualityLevel,
+}
+
+/// GET /simulation/quality -- the quality level in force and what it sets.
+pub async fn get_simulation_quality() -> ActixResult<HttpResponse> {
+    ok_json!(simulation_quality())
+}
+
+/// POST /simulation/quality -- switch to `low`, `medium` or `high`, setting
+/// tick rate, collision and readback frequency together.
+pub async fn set_simulation_quality(
+    user: AuthenticatedUser,
+    state: web::Data<AppState>,
+    req: web::Json<SetQualityRequest>,
+) -> ActixResult<HttpResponse> {
+    user.require_power_user()?;
+    match apply_quality(&state, req.level).await {
+        Ok(preset) => ok_json!(preset),
+        Err(e) => error_json!("Failed to set simulation quality: {}", e),
+    }
+}
+
 pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/simulation")
+            .route("/profile", web::get().to(get_step_profile))
+            .route("/control", web::get().to(get_simulation_control))
+            .route("/pause", web::post().to(pause_simulation))
+            .route("/resume", web::post().to(resume_simulation))
+            .route("/step", web::post().to(step_simulation))
+            .route("/quality", web::get().to(get_simulation_quality))
+            .route("/quality", web::post().to(set_simulation_quality)),
+    );
     cfg.service(
         web::scope("/physics")
             .route("/start", web::post().to(start_simulation))
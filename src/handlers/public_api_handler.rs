@@ -0,0 +1,71 @@
+// src/handlers/public_api_handler.rs
+//! Read-only public graph mirror (`/public/graph/*`).
+//!
+//! Mounted outside `/api` so it shares none of the authenticated API's
+//! middleware, and served entirely from the cached `PublicGraphService`
+//! snapshot. Every route is rate limited per client.
+
+use actix_web::{http::header, web, HttpResponse, Result};
+use std::sync::Arc;
+
+use crate::middleware::RateLimit;
+use crate::services::public_graph_service::PublicGraphService;
+use crate::{not_found, ok_json};
+
+fn cached(response: Result<HttpResponse>, max_age: u64) -> Result<HttpResponse> {
+    response.map(|mut resp| {
+        if let Ok(value) = header::HeaderValue::from_str(&format!("public, max-age={}", max_age)) {
+            resp.headers_mut().insert(header::CACHE_CONTROL, value);
+        }
+        resp.headers_mut().insert(
+            header::ACCESS_CONTROL_ALLOW_ORIGIN,
+            header::HeaderValue::from_static("*"),
+        );
+        resp
+    })
+}
+
+fn service(
+    public: Option<web::Data<Arc<PublicGraphService>>>,
+) -> Option<web::Data<Arc<PublicGraphService>>> {
+    public.filter(|p| p.settings().enabled)
+}
+
+/// GET /public/graph/summary
+pub async fn get_summary(
+    public: Option<web::Data<Arc<PublicGraphService>>>,
+) -> Result<HttpResponse> {
+    let Some(public) = service(public) else {
+        return not_found!("Public API is disabled");
+    };
+    let snapshot = public.snapshot();
+    cached(
+        ok_json!(&snapshot.summary),
+        public.settings().cache_ttl_secs,
+    )
+}
+
+/// GET /public/graph/nodes/{id}
+pub async fn get_node(
+    public: Option<web::Data<Arc<PublicGraphService>>>,
+    path: web::Path<u32>,
+) -> Result<HttpResponse> {
+    let Some(public) = service(public) else {
+        return not_found!("Public API is disabled");
+    };
+    let id = path.into_inner();
+    let snapshot = public.snapshot();
+    match snapshot.nodes.get(&id) {
+        Some(node) => cached(ok_json!(node), public.settings().cache_ttl_secs),
+        None => not_found!(format!("Node {} not found", id)),
+    }
+}
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig, requests_per_minute: u32) {
+    cfg.service(
+        web::scope("/public/graph")
+            .wrap(RateLimit::per_minute(requests_per_minute as usize))
+            .route("/summary", web::get().to(get_summary))
+            .route("/nodes/{id}", web::get().to(get_node)),
+    );
+}
@@ -1,9 +1,19 @@
 use crate::handlers::validation_handler::ValidationService;
+use crate::actors::messages::{GetGraphData, GetSettings};
+use crate::handlers::audio_cue_handler::cue_nodes;
 use crate::models::ragflow_chat::{RagflowChatRequest, RagflowChatResponse};
-use crate::services::ragflow_service::{ChatResponse, RAGFlowError};
+use crate::services::chat_agent::{self, AgentMessage, AgentProvider};
+use crate::services::audio_cue_service::{CHAT_CITATION, SEARCH_HIT};
+use crate::services::graph_stats_chat::{self, GraphStatsAnswer};
+use crate::services::graph_tools::{ClientAction, ToolResult, ToolRole, ToolSession};
+use crate::services::highlight_service::{HighlightLayer, HighlightReason, NewHighlight};
+use crate::ports::graph_repository::GraphRepository;
+use crate::services::ragflow_service::{ChatResponse, CitationSink, RAGFlowError};
 use crate::types::speech::SpeechOptions;
 use crate::utils::validation::errors::DetailedValidationError;
-use crate::utils::validation::rate_limit::{extract_client_id, EndpointRateLimits, RateLimiter};
+use crate::utils::validation::rate_limit::{
+    extract_client_id, EndpointRateLimits, RateLimitConfig, RateLimiter,
+};
 use crate::utils::validation::sanitization::Sanitizer;
 use crate::utils::validation::MAX_REQUEST_SIZE;
 use crate::AppState;
@@ -14,10 +24,11 @@ use actix_web::{web, HttpResponse, Responder, ResponseError, Result};
 use futures::StreamExt;
 use log::{debug, error, info, warn};
 use serde::{Deserialize, Serialize};
+use once_cell::sync::OnceCell;
 use serde_json::{json, Value};
 use std::sync::Arc;
 use crate::{
-    ok_json, error_json,
+    ok_json, error_json, bad_request,
     too_many_requests, service_unavailable,
 };
 
@@ -45,6 +56,106 @@ pub struct SendMessageRequest {
     pub enable_tts: Option<bool>,
 }
 
+/// Answer questions about the graph itself ("how many notes link to X?")
+/// from the graph service rather than the RAG provider. `None` when the
+/// question is not one, or the graph cannot be read, so it goes to RAGFlow.
+async fn graph_stats_reply(state: &AppState, question: &str) -> Option<GraphStatsAnswer> {
+    let intent = graph_stats_chat::recognise(question)?;
+    let graph = match state.graph_service_addr.send(GetGraphData).await {
+        Ok(Ok(graph)) => graph,
+        Ok(Err(e)) => {
+            warn!("Graph stats question falling back to RAGFlow: {}", e);
+            return None;
+        }
+        Err(e) => {
+            warn!("Graph stats question falling back to RAGFlow: {}", e);
+            return None;
+        }
+    };
+    let analytics = state
+        .node_analytics
+        .read()
+        .map(|a| a.clone())
+        .unwrap_or_default();
+    let reply = graph_stats_chat::answer(&intent, &graph, &analytics);
+    debug!("Answered {:?} from the graph: {}", intent, reply.answer);
+    Some(reply)
+}
+
+/// Highlight the nodes a graph answer is about, for the asker only.
+fn highlight_citations(
+    state: &AppState,
+    owner: &str,
+    conversation_id: Option<String>,
+    stats: &GraphStatsAnswer,
+) -> Option<u64> {
+    if stats.node_ids.is_empty() {
+        return None;
+    }
+    cue_nodes(state, CHAT_CITATION, owner, &stats.node_ids);
+    let new = NewHighlight {
+        conversation_id,
+        ..NewHighlight::new(owner, HighlightReason::Citation, stats.node_ids.clone())
+    };
+    match crate::handlers::highlight_handler::publish(state, new) {
+        Ok(layer) => Some(layer.id),
+        Err(e) => {
+            warn!("Could not highlight graph answer: {}", e);
+            None
+        }
+    }
+}
+
+/// Lower-cased file stem, so "notes/Rust.md", "Rust.md" and "Rust" compare equal.
+fn page_key(name: &str) -> String {
+    let name = name.trim();
+    let stem = name.rsplit('/').next().unwrap_or(name);
+    stem.trim_end_matches(".md").to_lowercase()
+}
+
+/// Highlight and cue the nodes for the documents a RAGFlow answer cites, for
+/// `owner` only. A document matches a node by file name or label.
+fn cite_documents(
+    state: &web::Data<AppState>,
+    owner: &str,
+    conversation_id: Option<String>,
+) -> CitationSink {
+    let state = state.clone();
+    let owner = owner.to_string();
+    Box::new(move |names| {
+        tokio::spawn(async move {
+            let nodes = match state.graph_repository.get_node_map().await {
+                Ok(nodes) => nodes,
+                Err(e) => {
+                    warn!("Could not resolve cited documents: {}", e);
+                    return;
+                }
+            };
+            let wanted: Vec<String> = names.iter().map(|n| page_key(n)).collect();
+            let mut node_ids: Vec<u32> = nodes
+                .values()
+                .filter(|n| {
+                    wanted.contains(&page_key(&n.metadata_id))
+                        || wanted.contains(&n.label.to_lowercase())
+                })
+                .map(|n| n.id)
+                .collect();
+            if node_ids.is_empty() {
+                return;
+            }
+            node_ids.sort_unstable();
+            cue_nodes(&state, CHAT_CITATION, &owner, &node_ids);
+            let new = NewHighlight {
+                conversation_id,
+                ..NewHighlight::new(owner, HighlightReason::Citation, node_ids)
+            };
+            if let Err(e) = crate::handlers::highlight_handler::publish(&state, new) {
+                warn!("Could not highlight cited documents: {}", e);
+            }
+        });
+    })
+}
+
 // Implement ResponseError for RAGFlowError
 impl ResponseError for RAGFlowError {
     fn error_response(&self) -> HttpResponse {
@@ -53,10 +164,36 @@ impl ResponseError for RAGFlowError {
 }
 
 pub async fn send_message(
-    _auth: crate::settings::auth_extractor::AuthenticatedUser,
+    auth: crate::settings::auth_extractor::AuthenticatedUser,
     state: web::Data<AppState>,
     request: web::Json<SendMessageRequest>,
 ) -> Result<HttpResponse, actix_web::Error> {
+    if let Some(stats) = graph_stats_reply(&state, &request.question).await {
+        let highlight =
+            highlight_citations(&state, &auth.pubkey, request.session_id.clone(), &stats);
+        if request.enable_tts.unwrap_or(false) {
+            if let Some(speech_service) = &state.speech_service {
+                let speech_service = speech_service.clone();
+                let answer = stats.answer.clone();
+                actix_web::rt::spawn(async move {
+                    if let Err(e) = speech_service
+                        .text_to_speech(answer, SpeechOptions::default())
+                        .await
+                    {
+                        error!("Error processing TTS for graph answer: {:?}", e);
+                    }
+                });
+            }
+        }
+        return Ok(HttpResponse::Ok().json(json!({
+            "answer": stats.answer,
+            "success": true,
+            "source": "graph",
+            "graphStats": stats,
+            "highlightId": highlight,
+        })));
+    }
+
     let ragflow_service = match &state.ragflow_service {
         Some(service) => service,
         None => {
@@ -71,6 +208,7 @@ pub async fn send_message(
     };
 
     let enable_tts = request.enable_tts.unwrap_or(false);
+    let on_cited = cite_documents(&state, &auth.pubkey, request.session_id.clone());
     
     match ragflow_service
         .send_message(
@@ -79,6 +217,7 @@ pub async fn send_message(
             false, 
             None,  
             request.stream.unwrap_or(true),
+            Some(on_cited),
         )
         .await
     {
@@ -147,6 +286,136 @@ pub async fn send_message(
     }
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentChatRequest {
+    pub question: String,
+    #[serde(default)]
+    pub history: Vec<AgentMessage>,
+    /// Scopes the highlights the answer leaves behind.
+    pub conversation_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentChatResponse {
+    pub answer: String,
+    pub tool_calls: Vec<ToolResult>,
+    pub actions: Vec<ClientAction>,
+    /// Highlight layers published for the `focus` actions.
+    pub highlights: Vec<HighlightLayer>,
+}
+
+/// Agent turns per user per minute when `openai.rateLimit` is not set.
+const AGENT_TURNS_PER_MINUTE: u32 = 10;
+
+/// Per-user limit on agent turns, built from the settings on first use.
+static AGENT_LIMITER: OnceCell<RateLimiter> = OnceCell::new();
+
+/// `openai.rateLimit` counts completions a minute, and one turn may make up
+/// to [`chat_agent::MAX_ROUNDS`] of them.
+fn agent_limiter(rate_limit: Option<u32>) -> &'static RateLimiter {
+    AGENT_LIMITER.get_or_init(|| {
+        let turns = rate_limit.map_or(AGENT_TURNS_PER_MINUTE, |r| {
+            (r / chat_agent::MAX_ROUNDS as u32).max(1)
+        });
+        RateLimiter::new(RateLimitConfig {
+            requests_per_minute: turns,
+            burst_size: turns.min(3),
+            ..Default::default()
+        })
+    })
+}
+
+/// Chat that can act on the graph: the model may search, read pages, query
+/// stats, focus the view and (power users) add temporary nodes. See
+/// `services::chat_agent` and `services::graph_tools`.
+///
+/// `POST /api/ragflow/agent` with `{"question": "...", "history": [{"role", "content"}], "conversationId": "..."}`
+/// returns `{answer, toolCalls, actions, highlights}`; clients apply `actions`
+/// to their view. Focused subgraphs are also published as highlight layers
+/// for the caller's sessions. Turns are rate limited per user (429).
+pub async fn agent_chat(
+    auth: crate::settings::auth_extractor::AuthenticatedUser,
+    state: web::Data<AppState>,
+    request: web::Json<AgentChatRequest>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let request = request.into_inner();
+    if request.question.trim().is_empty() {
+        return bad_request!("question must not be empty");
+    }
+
+    let openai = match state.settings_addr.send(GetSettings).await {
+        Ok(Ok(settings)) => settings.openai,
+        _ => None,
+    };
+    if !agent_limiter(openai.as_ref().and_then(|o| o.rate_limit)).is_allowed(&auth.pubkey) {
+        return too_many_requests!("Too many agent chat requests");
+    }
+    let model = openai.as_ref().and_then(|o| o.chat_model.as_deref());
+    let provider = match (openai.as_ref().and_then(AgentProvider::openai), &state.ragflow_service) {
+        (Some(provider), _) => provider,
+        (None, Some(ragflow)) => AgentProvider::ragflow(ragflow, model),
+        (None, None) => return service_unavailable!("No chat provider is configured"),
+    };
+
+    let graph = match state.graph_service_addr.send(GetGraphData).await {
+        Ok(Ok(graph)) => graph,
+        Ok(Err(e)) => return error_json!("Failed to read graph", e),
+        Err(e) => return error_json!("Failed to read graph", e),
+    };
+    let analytics = state
+        .node_analytics
+        .read()
+        .map(|a| a.clone())
+        .unwrap_or_default();
+    let mut session = ToolSession::new(
+        ToolRole::of(auth.is_power_user),
+        graph,
+        analytics,
+        state.graph_service_addr.clone(),
+    );
+
+    match chat_agent::run(&provider, &mut session, &request.history, &request.question).await {
+        Ok(answer) => {
+            info!(
+                "Chat agent answered {} with {} tool call(s)",
+                auth.pubkey,
+                session.results.len()
+            );
+            cue_nodes(&state, SEARCH_HIT, &auth.pubkey, &session.found);
+            let highlights = session
+                .actions
+                .iter()
+                .filter_map(|action| match action {
+                    ClientAction::Focus { node_id, node_ids } => {
+                        cue_nodes(&state, CHAT_CITATION, &auth.pubkey, &[*node_id]);
+                        let new = NewHighlight {
+                            conversation_id: request.conversation_id.clone(),
+                            focus_node: Some(*node_id),
+                            ..NewHighlight::new(
+                                auth.pubkey.as_str(),
+                                HighlightReason::Focus,
+                                node_ids.clone(),
+                            )
+                        };
+                        crate::handlers::highlight_handler::publish(&state, new)
+                            .map_err(|e| warn!("Could not highlight focused subgraph: {}", e))
+                            .ok()
+                    }
+                })
+                .collect();
+            ok_json!(AgentChatResponse {
+                answer,
+                tool_calls: session.results,
+                actions: session.actions,
+                highlights,
+            })
+        }
+        Err(e) => error_json!("Chat agent failed", e),
+    }
+}
+
 pub async fn create_session(
     _auth: crate::settings::auth_extractor::AuthenticatedUser,
     state: web::Data<AppState>,
@@ -314,6 +583,7 @@ async fn handle_ragflow_chat(
             current_session_id.clone(),
             payload.question.clone(),
             stream_preference,
+            None,
         )
         .await
     {
@@ -498,6 +768,24 @@ impl EnhancedRagFlowHandler {
         
         self.validate_question_content(question)?;
 
+        if let Some(stats) = graph_stats_reply(&state, question).await {
+            info!(
+                "Answered {} question from the graph for client: {}",
+                stats.intent, client_id
+            );
+            let highlight = highlight_citations(&state, &pubkey, session_id.clone(), &stats);
+            if enable_tts {
+                self.process_tts_request(&state, &stats.answer).await;
+            }
+            return ok_json!(json!({
+                "answer": stats.answer,
+                "sessionId": session_id,
+                "source": "graph",
+                "graphStats": stats,
+                "highlightId": highlight,
+            }));
+        }
+
         
         let ragflow_service = match &state.ragflow_service {
             Some(service) => service,
@@ -532,8 +820,14 @@ impl EnhancedRagFlowHandler {
             self.process_tts_request(&state, question).await;
         }
 
+        let on_cited = cite_documents(&state, &pubkey, Some(current_session_id.clone()));
         match ragflow_service
-            .send_chat_message(current_session_id.clone(), question.to_string(), stream)
+            .send_chat_message(
+                current_session_id.clone(),
+                question.to_string(),
+                stream,
+                Some(on_cited),
+            )
             .await
         {
             Ok(ChatResponse::Buffered {
@@ -799,7 +1093,8 @@ pub fn config(cfg: &mut ServiceConfig) {
         .service(
             web::scope("/ragflow")
                 .route("/session", web::post().to(create_session)) 
-                .route("/message", web::post().to(send_message))   
+                .route("/message", web::post().to(send_message))
+                .route("/agent", web::post().to(agent_chat))   
                 .route("/chat", web::post().to(|req: HttpRequest, state: web::Data<AppState>, payload: web::Json<serde_json::Value>, handler: web::Data<EnhancedRagFlowHandler>| async move {
 
                     handler.chat_enhanced(req, state, payload).await
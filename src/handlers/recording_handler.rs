@@ -0,0 +1,121 @@
+// src/handlers/recording_handler.rs
+//! Camera recording catalogue and replay export (`/api/recordings/*`).
+//!
+//! Recordings are created over the socket; these routes list them and run the
+//! export job that turns a stopped recording into a deterministic replay
+//! bundle for offline video rendering.
+
+use actix_web::{http::header, web, HttpResponse, Result};
+use serde::Deserialize;
+
+use crate::services::camera_recording_service::{recordings, ExportStatus, DEFAULT_EXPORT_FPS};
+use crate::settings::auth_extractor::AuthenticatedUser;
+use crate::{bad_request, conflict, error_json, not_found, ok_json};
+
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    pub fps: Option<u32>,
+}
+
+/// GET /api/recordings
+pub async fn list_recordings(auth: AuthenticatedUser) -> Result<HttpResponse> {
+    auth.require_power_user()?;
+    let list = web::block(|| recordings().list()).await.unwrap_or_default();
+    ok_json!(serde_json::json!({
+        "total": list.len(),
+        "recordings": list,
+    }))
+}
+
+/// GET /api/recordings/{id}
+pub async fn get_recording(
+    auth: AuthenticatedUser,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    auth.require_power_user()?;
+    let id = path.into_inner();
+    match recordings().get(&id) {
+        Some(meta) => ok_json!(serde_json::json!({
+            "recording": meta,
+            "export": recordings().export_status(&id),
+        })),
+        None => not_found!(format!("Recording {} not found", id)),
+    }
+}
+
+/// POST /api/recordings/{id}/export?fps=30
+///
+/// Starts the export on the blocking pool and returns immediately; poll
+/// `GET /api/recordings/{id}` for the job status and manifest.
+pub async fn start_export(
+    auth: AuthenticatedUser,
+    path: web::Path<String>,
+    query: web::Query<ExportQuery>,
+) -> Result<HttpResponse> {
+    auth.require_power_user()?;
+    let id = path.into_inner();
+    let fps = query.fps.unwrap_or(DEFAULT_EXPORT_FPS);
+    if !(1..=120).contains(&fps) {
+        return bad_request!("fps must be between 1 and 120");
+    }
+    let Some(meta) = recordings().get(&id) else {
+        return not_found!(format!("Recording {} not found", id));
+    };
+    if meta.stopped_at.is_none() {
+        return conflict!("Recording is still in progress");
+    }
+    if !recordings().begin_export(&id, fps) {
+        return conflict!("An export for this recording is already running");
+    }
+
+    let job_id = id.clone();
+    tokio::task::spawn_blocking(move || {
+        let _ = recordings().export(&job_id, fps);
+    });
+
+    Ok(HttpResponse::Accepted().json(serde_json::json!({
+        "recordingId": id,
+        "export": ExportStatus::Running { fps },
+    })))
+}
+
+/// GET /api/recordings/{id}/bundle
+pub async fn download_bundle(
+    auth: AuthenticatedUser,
+    path: web::Path<String>,
+) -> Result<HttpResponse> {
+    auth.require_power_user()?;
+    let id = path.into_inner();
+    if !matches!(
+        recordings().export_status(&id),
+        None | Some(ExportStatus::Completed { .. })
+    ) {
+        return conflict!("Export has not completed");
+    }
+    let Some(bundle_path) = recordings().bundle_path(&id) else {
+        return bad_request!("Invalid recording id");
+    };
+    match tokio::fs::read(&bundle_path).await {
+        Ok(bytes) => Ok(HttpResponse::Ok()
+            .content_type("application/json")
+            .insert_header((
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"replay-{}.json\"", id),
+            ))
+            .body(bytes)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            not_found!("Recording has not been exported")
+        }
+        Err(e) => error_json!(format!("Failed to read bundle: {}", e)),
+    }
+}
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/recordings")
+            .route("", web::get().to(list_recordings))
+            .route("/{id}", web::get().to(get_recording))
+            .route("/{id}/export", web::post().to(start_export))
+            .route("/{id}/bundle", web::get().to(download_bundle)),
+    );
+}
@@ -364,6 +364,9 @@ impl From<&crate::config::WebSocketSettings> for WebSocketSettingsDTO {
             reconnect_attempts: settings.reconnect_attempts,
             reconnect_delay: settings.reconnect_delay,
             update_rate: settings.update_rate,
+            echo_suppression_ms: settings.echo_suppression_ms,
+            physics_authority_policy: settings.physics_authority_policy.clone(),
+            idle_hibernation_ms: settings.idle_hibernation_ms,
         }
     }
 }
@@ -416,6 +419,8 @@ impl From<&crate::config::XRSettings> for XRSettingsDTO {
             hand_ray_color: settings.hand_ray_color.clone(),
             hand_ray_width: settings.hand_ray_width,
             gesture_smoothing: settings.gesture_smoothing,
+            gestures: settings.gestures.clone(),
+            audio_cues: settings.audio_cues.clone(),
             enable_haptics: settings.enable_haptics,
             haptic_intensity: settings.haptic_intensity,
             drag_threshold: settings.drag_threshold,
@@ -500,6 +505,9 @@ impl From<&crate::config::OpenAISettings> for OpenAISettingsDTO {
             base_url: settings.base_url.clone(),
             timeout: settings.timeout,
             rate_limit: settings.rate_limit,
+            chat_model: settings.chat_model.clone(),
+            embedding_model: settings.embedding_model.clone(),
+            embed_on_startup: settings.embed_on_startup,
         }
     }
 }
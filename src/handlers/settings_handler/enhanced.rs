@@ -4,6 +4,7 @@ use crate::actors::messages::{GetSettings, UpdateSettings};
 use crate::app_state::AppState;
 use crate::config::AppFullSettings;
 use crate::handlers::validation_handler::ValidationService;
+use crate::services::settings_history_service::{ChangeOrigin, ChangeSource};
 use crate::utils::validation::rate_limit::{
     extract_client_id, EndpointRateLimits, RateLimitConfig, RateLimiter,
 };
@@ -236,6 +237,7 @@ impl EnhancedSettingsHandler {
             .settings_addr
             .send(UpdateSettings {
                 settings: app_settings.clone(),
+                origin: ChangeOrigin::new(client_id.clone(), ChangeSource::Rest),
             })
             .await
         {
@@ -383,6 +385,7 @@ impl EnhancedSettingsHandler {
             .settings_addr
             .send(UpdateSettings {
                 settings: default_settings.clone(),
+                origin: ChangeOrigin::new(client_id.clone(), ChangeSource::Rest),
             })
             .await
         {
@@ -5,6 +5,8 @@ use crate::actors::messages::{
 };
 use crate::app_state::AppState;
 use crate::config::AppFullSettings;
+use crate::services::settings_history_service::{ChangeOrigin, ChangeSource};
+use crate::utils::validation::rate_limit::extract_client_id;
 use crate::{bad_request, error_json, ok_json, service_unavailable};
 use actix_web::{web, Error, HttpRequest, HttpResponse};
 use log::{debug, error, info, warn};
@@ -163,7 +165,7 @@ pub async fn propagate_physics_to_gpu_with_layout(
 }
 
 pub async fn update_compute_mode(
-    _req: HttpRequest,
+    req: HttpRequest,
     state: web::Data<AppState>,
     payload: web::Json<Value>,
 ) -> Result<HttpResponse, Error> {
@@ -213,6 +215,7 @@ pub async fn update_compute_mode(
         .settings_addr
         .send(UpdateSettings {
             settings: app_settings.clone(),
+            origin: ChangeOrigin::new(extract_client_id(&req), ChangeSource::Rest),
         })
         .await
     {
@@ -239,7 +242,7 @@ pub async fn update_compute_mode(
 }
 
 pub async fn update_constraints(
-    _req: HttpRequest,
+    req: HttpRequest,
     state: web::Data<AppState>,
     payload: web::Json<Value>,
 ) -> Result<HttpResponse, Error> {
@@ -293,6 +296,7 @@ pub async fn update_constraints(
         .settings_addr
         .send(UpdateSettings {
             settings: app_settings.clone(),
+            origin: ChangeOrigin::new(extract_client_id(&req), ChangeSource::Rest),
         })
         .await
     {
@@ -318,7 +322,7 @@ pub async fn update_constraints(
 }
 
 pub async fn update_stress_optimization(
-    _req: HttpRequest,
+    req: HttpRequest,
     state: web::Data<AppState>,
     payload: web::Json<Value>,
 ) -> Result<HttpResponse, Error> {
@@ -372,6 +376,7 @@ pub async fn update_stress_optimization(
         .settings_addr
         .send(UpdateSettings {
             settings: app_settings.clone(),
+            origin: ChangeOrigin::new(extract_client_id(&req), ChangeSource::Rest),
         })
         .await
     {
@@ -3,7 +3,11 @@
 use crate::actors::messages::GetSettings;
 use crate::app_state::AppState;
 use crate::config::path_access::JsonPathAccessible;
-use actix_web::{web, Error, HttpRequest, HttpResponse};
+use crate::config::AppFullSettings;
+use crate::handlers::workspace_handler::session_settings;
+use crate::settings::auth_extractor::AuthenticatedUser;
+use actix_web::dev::Payload;
+use actix_web::{web, Error, FromRequest, HttpRequest, HttpResponse};
 use log::{error, warn};
 use serde_json::{json, Value};
 use std::borrow::Cow;
@@ -91,11 +95,12 @@ async fn get_setting_by_path(
 
 async fn update_setting_by_path(
     _req: HttpRequest,
-    _auth: crate::settings::auth_extractor::AuthenticatedUser,
+    auth: crate::settings::auth_extractor::AuthenticatedUser,
     state: web::Data<AppState>,
     payload: web::Json<Value>,
 ) -> Result<HttpResponse, Error> {
     use crate::actors::messages::UpdateSettings;
+    use crate::services::settings_history_service::{ChangeOrigin, ChangeSource};
     use log::info;
     use crate::bad_request;
     use super::physics::propagate_physics_to_gpu;
@@ -131,6 +136,7 @@ async fn update_setting_by_path(
                 .settings_addr
                 .send(UpdateSettings {
                     settings: app_settings.clone(),
+                    origin: ChangeOrigin::new(auth.pubkey.clone(), ChangeSource::Rest),
                 })
                 .await
             {
@@ -241,20 +247,56 @@ fn redact_settings_secrets(mut value: Value) -> Value {
     value
 }
 
-async fn get_settings(
-    _req: HttpRequest,
-    state: web::Data<AppState>,
-) -> Result<HttpResponse, Error> {
-    let app_settings = match state.settings_addr.send(GetSettings).await {
-        Ok(Ok(settings)) => settings,
+/// Workspace the caller is working in, from `X-Workspace-Id` or `?workspace=`.
+fn requested_workspace(req: &HttpRequest) -> Option<String> {
+    let from_header = req
+        .headers()
+        .get("X-Workspace-Id")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    from_header
+        .or_else(|| {
+            web::Query::<std::collections::HashMap<String, String>>::from_query(req.query_string())
+                .ok()
+                .and_then(|q| q.get("workspace").cloned())
+        })
+        .map(|w| w.trim().to_string())
+        .filter(|w| !w.is_empty())
+}
+
+/// Global settings, or — when the request names a workspace — the global
+/// settings with that workspace's overlay applied. Workspace overlays are
+/// only served to authenticated callers.
+async fn resolve_settings(
+    req: &HttpRequest,
+    state: &AppState,
+) -> Result<AppFullSettings, Result<HttpResponse, Error>> {
+    if let Some(workspace_id) = requested_workspace(req) {
+        AuthenticatedUser::from_request(req, &mut Payload::None)
+            .await
+            .map_err(Err)?;
+        return session_settings(state, &workspace_id).await.map_err(Ok);
+    }
+    match state.settings_addr.send(GetSettings).await {
+        Ok(Ok(settings)) => Ok(settings),
         Ok(Err(e)) => {
             error!("Failed to get settings: {}", e);
-            return error_json!("Failed to retrieve settings");
+            Err(error_json!("Failed to retrieve settings"))
         }
         Err(e) => {
             error!("Settings actor error: {}", e);
-            return service_unavailable!("Settings service unavailable");
+            Err(service_unavailable!("Settings service unavailable"))
         }
+    }
+}
+
+async fn get_settings(
+    req: HttpRequest,
+    state: web::Data<AppState>,
+) -> Result<HttpResponse, Error> {
+    let app_settings = match resolve_settings(&req, &state).await {
+        Ok(settings) => settings,
+        Err(response) => return response,
     };
 
     let response_dto: SettingsResponseDTO = (&app_settings).into();
@@ -267,19 +309,12 @@ async fn get_settings(
 }
 
 async fn get_current_settings(
-    _req: HttpRequest,
+    req: HttpRequest,
     state: web::Data<AppState>,
 ) -> Result<HttpResponse, Error> {
-    let app_settings = match state.settings_addr.send(GetSettings).await {
-        Ok(Ok(settings)) => settings,
-        Ok(Err(e)) => {
-            error!("Failed to get settings: {}", e);
-            return error_json!("Failed to retrieve settings");
-        }
-        Err(e) => {
-            error!("Settings actor error: {}", e);
-            return service_unavailable!("Settings service unavailable");
-        }
+    let app_settings = match resolve_settings(&req, &state).await {
+        Ok(settings) => settings,
+        Err(response) => return response,
     };
 
     let response_dto: SettingsResponseDTO = (&app_settings).into();
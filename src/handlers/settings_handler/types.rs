@@ -497,6 +497,9 @@ pub struct WebSocketSettingsDTO {
     pub reconnect_attempts: u32,
     pub reconnect_delay: u64,
     pub update_rate: u32,
+    pub echo_suppression_ms: u64,
+    pub physics_authority_policy: String,
+    pub idle_hibernation_ms: u64,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -548,6 +551,10 @@ pub struct XRSettingsDTO {
     pub hand_ray_color: String,
     pub hand_ray_width: f32,
     pub gesture_smoothing: f32,
+    #[serde(default)]
+    pub gestures: crate::config::XRGestureSettings,
+    #[serde(default)]
+    pub audio_cues: crate::config::XRAudioCueSettings,
     pub enable_haptics: bool,
     pub haptic_intensity: f32,
     pub drag_threshold: f32,
@@ -642,6 +649,12 @@ pub struct OpenAISettingsDTO {
     pub timeout: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rate_limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chat_model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embedding_model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embed_on_startup: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
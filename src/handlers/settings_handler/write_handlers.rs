@@ -10,6 +10,7 @@ use serde_json::{json, Value};
 use crate::{ok_json, error_json, bad_request, service_unavailable};
 
 use crate::handlers::settings_validation_fix::convert_to_snake_case_recursive;
+use crate::services::settings_history_service::{ChangeOrigin, ChangeSource};
 use crate::settings::auth_extractor::AuthenticatedUser;
 
 use super::types::{SettingsResponseDTO, value_type_name};
@@ -19,7 +20,7 @@ use super::helpers::extract_physics_updates;
 
 pub async fn update_settings(
     _req: HttpRequest,
-    _user: AuthenticatedUser,
+    user: AuthenticatedUser,
     state: web::Data<AppState>,
     payload: web::Json<Value>,
 ) -> Result<HttpResponse, Error> {
@@ -197,6 +198,7 @@ pub async fn update_settings(
         .settings_addr
         .send(UpdateSettings {
             settings: app_settings.clone(),
+            origin: ChangeOrigin::new(user.pubkey.clone(), ChangeSource::Rest),
         })
         .await
     {
@@ -239,7 +241,7 @@ pub async fn update_settings(
 
 pub async fn reset_settings(
     _req: HttpRequest,
-    _user: AuthenticatedUser,
+    user: AuthenticatedUser,
     state: web::Data<AppState>,
 ) -> Result<HttpResponse, Error> {
 
@@ -256,6 +258,7 @@ pub async fn reset_settings(
         .settings_addr
         .send(UpdateSettings {
             settings: default_settings.clone(),
+            origin: ChangeOrigin::new(user.pubkey.clone(), ChangeSource::Rest),
         })
         .await
     {
@@ -280,7 +283,7 @@ pub async fn reset_settings(
 
 pub async fn save_settings(
     _req: HttpRequest,
-    _user: AuthenticatedUser,
+    user: AuthenticatedUser,
     state: web::Data<AppState>,
     payload: Option<web::Json<Value>>,
 ) -> Result<HttpResponse, Error> {
@@ -329,6 +332,7 @@ pub async fn save_settings(
                 .settings_addr
                 .send(UpdateSettings {
                     settings: app_settings.clone(),
+                    origin: ChangeOrigin::new(user.pubkey.clone(), ChangeSource::Rest),
                 })
                 .await
             {
@@ -415,7 +419,7 @@ pub async fn batch_get_settings(
 }
 
 pub async fn batch_update_settings(
-    _user: AuthenticatedUser,
+    user: AuthenticatedUser,
     state: web::Data<AppState>,
     payload: web::Json<Value>,
 ) -> Result<HttpResponse, Error> {
@@ -516,6 +520,7 @@ pub async fn batch_update_settings(
             .settings_addr
             .send(UpdateSettings {
                 settings: app_settings.clone(),
+                origin: ChangeOrigin::new(user.pubkey.clone(), ChangeSource::Rest),
             })
             .await
         {
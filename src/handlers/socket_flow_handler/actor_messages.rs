@@ -30,7 +30,7 @@ impl Handler<BroadcastPositionUpdate> for SocketFlowServer {
     type Result = ();
 
     fn handle(&mut self, msg: BroadcastPositionUpdate, ctx: &mut Self::Context) -> Self::Result {
-        if msg.0.is_empty() {
+        if msg.0.is_empty() || self.withhold_while_hibernating() {
             return;
         }
 
@@ -52,7 +52,7 @@ impl Handler<BroadcastPositionUpdate> for SocketFlowServer {
                 analytics_ref,
             )
         };
-        ctx.binary(binary_data);
+        self.send_positions(ctx, &msg.0, binary_data);
 
         if self.should_log_update() {
             debug!(
@@ -70,7 +70,15 @@ impl Handler<SendToClientBinary> for SocketFlowServer {
     type Result = ();
 
     fn handle(&mut self, msg: SendToClientBinary, ctx: &mut Self::Context) {
-        ctx.binary(msg.0);
+        if (self.layout_playback.is_some() || self.overview)
+            && super::layout_playback::is_position_frame(&msg.0)
+        {
+            return;
+        }
+        if self.withhold_while_hibernating() {
+            return;
+        }
+        self.relay_broadcast_frame(ctx, msg.0);
     }
 }
 
@@ -78,6 +86,9 @@ impl Handler<SendToClientText> for SocketFlowServer {
     type Result = ();
 
     fn handle(&mut self, msg: SendToClientText, ctx: &mut Self::Context) {
+        if self.withhold_while_hibernating() {
+            return;
+        }
         ctx.text(msg.0);
     }
 }
@@ -91,6 +102,10 @@ impl Handler<SendInitialGraphLoad> for SocketFlowServer {
     fn handle(&mut self, msg: SendInitialGraphLoad, ctx: &mut Self::Context) -> Self::Result {
         use crate::utils::socket_flow_messages::Message;
 
+        if self.withhold_while_hibernating() {
+            return;
+        }
+
         let initial_load = Message::InitialGraphLoad {
             nodes: msg.nodes,
             edges: msg.edges,
@@ -119,6 +134,10 @@ impl Handler<SendPositionUpdate> for SocketFlowServer {
     fn handle(&mut self, msg: SendPositionUpdate, ctx: &mut Self::Context) -> Self::Result {
         use crate::utils::socket_flow_messages::Message;
 
+        if self.withhold_while_hibernating() {
+            return;
+        }
+
         let position_update = Message::PositionUpdate {
             node_id: msg.node_id,
             x: msg.x,
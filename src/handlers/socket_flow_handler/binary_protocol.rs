@@ -1,10 +1,42 @@
 use actix::prelude::*;
 use log::{debug, error, info, trace, warn};
+use std::collections::HashMap;
 
 use crate::utils::binary_protocol;
+use crate::utils::socket_flow_messages::BinaryNodeData;
 
+use super::handshake::LOCAL_PHYSICS_CAPABILITY;
+use super::position_updates::sanitize_position;
 use super::types::{SocketFlowServer, WEBSOCKET_RATE_LIMITER};
 
+/// Largest velocity component accepted from a client frame.
+const MAX_CLIENT_VELOCITY: f32 = 1000.0;
+
+/// Drop entries with non-finite or out-of-bounds positions or velocities and
+/// collapse duplicate ids (last one wins). Returns the accepted entries in id
+/// order and the number rejected.
+pub(crate) fn validate_client_positions(
+    nodes: Vec<(u32, BinaryNodeData)>,
+) -> (Vec<(u32, BinaryNodeData)>, usize) {
+    let total = nodes.len();
+    let mut accepted: HashMap<u32, BinaryNodeData> = HashMap::with_capacity(total);
+    let mut rejected = 0;
+    for (node_id, data) in nodes {
+        let velocity_ok = [data.vx, data.vy, data.vz]
+            .iter()
+            .all(|v| v.is_finite() && v.abs() <= MAX_CLIENT_VELOCITY);
+        match sanitize_position(data.x, data.y, data.z) {
+            Some(_) if velocity_ok => {
+                accepted.insert(node_id, BinaryNodeData { node_id, ..data });
+            }
+            _ => rejected += 1,
+        }
+    }
+    let mut accepted: Vec<_> = accepted.into_iter().collect();
+    accepted.sort_unstable_by_key(|(id, _)| *id);
+    (accepted, rejected)
+}
+
 /// Handle incoming binary WebSocket messages (position updates, voice data, broadcast acks).
 impl SocketFlowServer {
     pub(crate) fn handle_binary_message(
@@ -78,74 +110,10 @@ impl SocketFlowServer {
             }
         }
 
-        // Fall back to legacy binary node data protocol
+        // Fall back to node position frames (V3/V5 layout) from clients
+        // computing physics locally.
         match binary_protocol::decode_node_data(data) {
-            Ok(nodes) => {
-                info!("Decoded {} nodes from binary message", nodes.len());
-                let _nodes_vec: Vec<_> = nodes.clone().into_iter().collect();
-
-                {
-                    let app_state = self.app_state.clone();
-                    let nodes_vec: Vec<_> = nodes.clone().into_iter().collect();
-
-                    let fut = async move {
-                        for (node_id, node_data) in &nodes_vec {
-                            if *node_id < 5 {
-                                debug!(
-                                    "Processing binary update for node ID: {} with position [{:.3}, {:.3}, {:.3}]",
-                                    node_id, node_data.x, node_data.y, node_data.z
-                                );
-                            }
-                        }
-
-                        info!(
-                            "Received {} node positions from client (feedback loop disabled)",
-                            nodes_vec.len()
-                        );
-                        info!("Updated node positions from binary data (preserving server-side properties)");
-                        info!("Preparing to recalculate layout after client-side node position update");
-
-                        use crate::actors::messages::GetSettingByPath;
-                        let settings_addr = app_state.settings_addr.clone();
-
-                        if let Ok(Ok(_iterations_val)) = settings_addr
-                            .send(GetSettingByPath {
-                                path: "visualisation.graphs.logseq.physics.iterations".to_string(),
-                            })
-                            .await
-                        {
-                            if let Ok(Ok(_spring_val)) = settings_addr
-                                .send(GetSettingByPath {
-                                    path: "visualisation.graphs.logseq.physics.spring_k".to_string(),
-                                })
-                                .await
-                            {
-                                if let Ok(Ok(_repulsion_val)) = settings_addr
-                                    .send(GetSettingByPath {
-                                        path: "visualisation.graphs.logseq.physics.repel_k"
-                                            .to_string(),
-                                    })
-                                    .await
-                                {
-                                    use crate::actors::messages::SimulationStep;
-                                    if let Err(e) = app_state
-                                        .graph_service_addr
-                                        .send(SimulationStep)
-                                        .await
-                                    {
-                                        error!("Failed to trigger simulation step: {}", e);
-                                    } else {
-                                        info!("Successfully triggered layout recalculation");
-                                    }
-                                }
-                            }
-                        }
-                    };
-
-                    let fut = fut.into_actor(self);
-                    ctx.spawn(fut.map(|_, _, _| ()));
-                }
-            }
+            Ok(nodes) => self.ingest_client_positions(nodes, ctx),
             Err(e) => {
                 error!("Failed to decode binary message: {}", e);
                 let error_msg = serde_json::json!({
@@ -164,4 +132,119 @@ impl SocketFlowServer {
             }
         }
     }
+
+    /// Merge a client-computed position frame into the authoritative graph.
+    ///
+    /// Only authenticated sessions that negotiated the `localPhysics`
+    /// capability may drive positions; everyone else's frames are ignored, as
+    /// they were before local-mode ingestion existed. The coordinator then
+    /// drops frames from sessions not holding physics authority.
+    fn ingest_client_positions(
+        &mut self,
+        nodes: Vec<(u32, BinaryNodeData)>,
+        ctx: &mut <Self as Actor>::Context,
+    ) {
+        if self.pubkey.is_none() || !self.protocol.has(LOCAL_PHYSICS_CAPABILITY) {
+            debug!(
+                "Ignoring {} client node positions: session has not negotiated local physics",
+                nodes.len()
+            );
+            return;
+        }
+
+        let (positions, rejected) = validate_client_positions(nodes);
+        if rejected > 0 {
+            warn!(
+                "[LocalPhysics] Rejected {} invalid node positions from client {:?}",
+                rejected, self.client_id
+            );
+            let msg = serde_json::json!({
+                "type": "positionsRejected",
+                "rejected": rejected,
+                "accepted": positions.len(),
+            });
+            ctx.text(msg.to_string());
+        }
+        if positions.is_empty() {
+            return;
+        }
+
+        let Some(client_id) = self.client_id else {
+            return;
+        };
+
+        use crate::actors::messages::SubmitClientPositions;
+        let submit = self.client_manager_addr.send(SubmitClientPositions {
+            client_id,
+            positions,
+            echo_window_ms: self.echo_suppression_ms,
+        });
+        super::panic_guard::spawn_guarded(ctx, "binary(client positions)",
+            submit
+                .into_actor(self)
+                .map(move |result, _act, _ctx| match result {
+                    Ok(Ok(count)) => {
+                        debug!(
+                            "[LocalPhysics] Merged {} node positions from client {}",
+                            count, client_id
+                        )
+                    }
+                    Ok(Err(e)) => debug!(
+                        "[LocalPhysics] Dropped frame from client {}: {}",
+                        client_id, e
+                    ),
+                    Err(e) => warn!("[LocalPhysics] Client coordinator unreachable: {}", e),
+                }),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: u32, x: f32, vx: f32) -> (u32, BinaryNodeData) {
+        (
+            id,
+            BinaryNodeData {
+                node_id: 0,
+                x,
+                y: 1.0,
+                z: 2.0,
+                vx,
+                vy: 0.0,
+                vz: 0.0,
+            },
+        )
+    }
+
+    #[test]
+    fn rejects_non_finite_and_out_of_bounds_entries() {
+        let (accepted, rejected) = validate_client_positions(vec![
+            node(3, 10.0, 0.5),
+            node(1, f32::NAN, 0.0),
+            node(2, 1.0e9, 0.0),
+            node(4, 0.0, f32::INFINITY),
+            node(5, 0.0, 5000.0),
+        ]);
+        assert_eq!(rejected, 4);
+        assert_eq!(accepted.len(), 1);
+        assert_eq!(accepted[0].0, 3);
+        assert_eq!(accepted[0].1.node_id, 3);
+    }
+
+    #[test]
+    fn duplicate_ids_keep_the_last_entry() {
+        let (accepted, rejected) = validate_client_positions(vec![
+            node(7, 1.0, 0.0),
+            node(2, 0.0, 0.0),
+            node(7, 4.0, 0.0),
+        ]);
+        assert_eq!(rejected, 0);
+        assert_eq!(
+            accepted.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+            vec![2, 7]
+        );
+        assert_eq!(accepted[1].1.x, 4.0);
+    }
 }
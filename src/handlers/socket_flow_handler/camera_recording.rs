@@ -0,0 +1,172 @@
+// Opt-in camera path recording over the socket.
+//
+//   cameraRecordingStart            -> cameraRecordingStarted { recordingId }
+//   cameraPose { position, rotation, fov }
+//   cameraRecordingStop             -> cameraRecordingStopped { recording }
+//
+// Poses are timestamped with the server clock. Each pose may also trigger a
+// position frame capture (at most every FRAME_INTERVAL_MS) so the export job
+// can pair the camera path with the layout as it was at that moment.
+
+use actix::prelude::*;
+use log::{debug, warn};
+
+use crate::actors::messages::GetGraphData;
+use crate::services::camera_recording_service::{
+    recordings, CameraPose, FramePosition, PositionFrame,
+};
+
+use super::types::SocketFlowServer;
+
+fn send_error(ctx: &mut <SocketFlowServer as Actor>::Context, message: &str) {
+    let msg = serde_json::json!({ "type": "error", "message": message });
+    ctx.text(msg.to_string());
+}
+
+fn parse_floats<const N: usize>(value: Option<&serde_json::Value>) -> Option<[f32; N]> {
+    let arr = value?.as_array()?;
+    if arr.len() != N {
+        return None;
+    }
+    let mut out = [0.0f32; N];
+    for (slot, v) in out.iter_mut().zip(arr) {
+        *slot = v.as_f64()? as f32;
+    }
+    Some(out)
+}
+
+pub(crate) fn handle_camera_recording_start(
+    act: &mut SocketFlowServer,
+    ctx: &mut <SocketFlowServer as Actor>::Context,
+) {
+    if act.pubkey.is_none() {
+        send_error(ctx, "Camera recording requires authentication");
+        return;
+    }
+    if let Some(id) = &act.camera_recording {
+        send_error(ctx, &format!("Recording {} is already active", id));
+        return;
+    }
+    match recordings().start(act.pubkey.clone()) {
+        Ok(meta) => {
+            act.camera_recording = Some(meta.id.clone());
+            let msg = serde_json::json!({
+                "type": "cameraRecordingStarted",
+                "recordingId": meta.id,
+                "startedAt": meta.started_at,
+            });
+            ctx.text(msg.to_string());
+        }
+        Err(e) => {
+            warn!("[Recording] Failed to start recording: {}", e);
+            send_error(ctx, "Failed to start camera recording");
+        }
+    }
+}
+
+pub(crate) fn handle_camera_pose(
+    act: &mut SocketFlowServer,
+    msg: &serde_json::Value,
+    ctx: &mut <SocketFlowServer as Actor>::Context,
+) {
+    let Some(id) = act.camera_recording.clone() else {
+        // Poses outside a recording are ignored: recording is strictly opt-in.
+        return;
+    };
+    let store = recordings();
+    let Some(t_ms) = store.elapsed_ms(&id) else {
+        warn!("[Recording] {} reached its duration limit, stopping", id);
+        stop_recording(act, ctx);
+        return;
+    };
+
+    let data = msg.get("data").unwrap_or(msg);
+    let (Some(position), Some(rotation)) = (
+        parse_floats::<3>(data.get("position")),
+        parse_floats::<4>(data.get("rotation")),
+    ) else {
+        debug!("[Recording] Ignoring malformed cameraPose");
+        return;
+    };
+    let fov = data.get("fov").and_then(|v| v.as_f64()).unwrap_or(60.0) as f32;
+
+    let pose = CameraPose {
+        t_ms,
+        position,
+        rotation,
+        fov,
+    };
+    if let Err(e) = store.append_pose(&id, pose) {
+        debug!("[Recording] Dropped pose for {}: {}", id, e);
+        return;
+    }
+
+    if store.claim_frame(&id, t_ms) {
+        let graph_addr = act.app_state.graph_service_addr.clone();
+        actix::spawn(async move {
+            let graph = match graph_addr.send(GetGraphData).await {
+                Ok(Ok(graph)) => graph,
+                _ => return,
+            };
+            let nodes = graph
+                .nodes
+                .iter()
+                .map(|n| FramePosition {
+                    id: n.id,
+                    x: n.data.x,
+                    y: n.data.y,
+                    z: n.data.z,
+                })
+                .collect();
+            if let Err(e) = recordings().append_frame(&id, PositionFrame { t_ms, nodes }) {
+                warn!(
+                    "[Recording] Failed to write position frame for {}: {}",
+                    id, e
+                );
+            }
+        });
+    }
+}
+
+pub(crate) fn handle_camera_recording_stop(
+    act: &mut SocketFlowServer,
+    ctx: &mut <SocketFlowServer as Actor>::Context,
+) {
+    if act.camera_recording.is_none() {
+        send_error(ctx, "No camera recording is active");
+        return;
+    }
+    stop_recording(act, ctx);
+}
+
+fn stop_recording(act: &mut SocketFlowServer, ctx: &mut <SocketFlowServer as Actor>::Context) {
+    let Some(id) = act.camera_recording.take() else {
+        return;
+    };
+    match recordings().stop(&id) {
+        Ok(meta) => {
+            let msg = serde_json::json!({
+                "type": "cameraRecordingStopped",
+                "recordingId": id,
+                "recording": meta,
+            });
+            ctx.text(msg.to_string());
+        }
+        Err(e) => {
+            warn!("[Recording] Failed to finalise recording {}: {}", id, e);
+            send_error(ctx, "Failed to finalise camera recording");
+        }
+    }
+}
+
+/// Finalise any recording left open when the connection drops.
+pub(crate) fn finish_on_disconnect(act: &mut SocketFlowServer) {
+    if let Some(id) = act.camera_recording.take() {
+        if let Err(e) = recordings().stop(&id) {
+            warn!(
+                "[Recording] Failed to finalise recording {} on disconnect: {}",
+                id, e
+            );
+        }
+    }
+}
@@ -10,6 +10,10 @@ pub(crate) fn handle_authenticate(
     ctx: &mut <SocketFlowServer as Actor>::Context,
 ) {
     info!("Client sent authenticate message");
+    let highlight_changes = msg
+        .get("highlightChanges")
+        .and_then(|h| h.as_bool())
+        .unwrap_or(true);
 
     if let Some(event_b64) = msg.get("event").and_then(|e| e.as_str()) {
         // --- NIP-98 path: { type: "authenticate", event: "<base64>" } ---
@@ -19,7 +23,7 @@ pub(crate) fn handle_authenticate(
         let auth_header = format!("Nostr {}", event_b64);
         let ws_url = act.connection_url.clone();
 
-        ctx.spawn(
+        super::panic_guard::spawn_guarded(ctx, "authenticate",
             actix::fut::wrap_future::<_, SocketFlowServer>(async move {
                 if let Some(ref ns) = nostr_service {
                     match ns
@@ -34,7 +38,7 @@ pub(crate) fn handle_authenticate(
                 }
                 (None, client_id, cm_addr)
             })
-            .map(|(user_opt, client_id, cm_addr), act, ctx| {
+            .map(move |(user_opt, client_id, cm_addr), act, ctx| {
                 if let Some(user) = user_opt {
                     act.pubkey = Some(user.pubkey.clone());
                     act.is_power_user = user.is_power_user;
@@ -58,6 +62,12 @@ pub(crate) fn handle_authenticate(
                     if let Ok(msg_str) = serde_json::to_string(&response) {
                         ctx.text(msg_str);
                     }
+                    super::visit_summary::send_visit_summary(
+                        act,
+                        ctx,
+                        user.pubkey.clone(),
+                        highlight_changes,
+                    );
                     info!(
                         "NIP-98 WS authenticated: pubkey={}, power_user={}",
                         user.pubkey, user.is_power_user
@@ -94,7 +104,7 @@ pub(crate) fn handle_authenticate(
             let client_id = act.client_id;
             let cm_addr = act.client_manager_addr.clone();
 
-            ctx.spawn(
+            super::panic_guard::spawn_guarded(ctx, "authenticate",
                 actix::fut::wrap_future::<_, SocketFlowServer>(async move {
                     if let Some(ref ns) = nostr_service {
                         if let Some(user) = ns.get_session(&token).await {
@@ -130,6 +140,14 @@ pub(crate) fn handle_authenticate(
                         if let Ok(msg_str) = serde_json::to_string(&response) {
                             ctx.text(msg_str);
                         }
+                        if !is_ephemeral {
+                            super::visit_summary::send_visit_summary(
+                                act,
+                                ctx,
+                                user.pubkey.clone(),
+                                highlight_changes,
+                            );
+                        }
                         info!(
                             "Client authenticated: pubkey={}, power_user={}, ephemeral={}",
                             user.pubkey, user.is_power_user, is_ephemeral
@@ -217,7 +235,7 @@ pub(crate) fn handle_filter_update(
         let cm_addr = act.client_manager_addr.clone();
         let pubkey = act.pubkey.clone();
 
-        ctx.spawn(
+        super::panic_guard::spawn_guarded(ctx, "filter_update",
             actix::fut::wrap_future::<_, SocketFlowServer>(async move {
                 match cm_addr.send(update.clone()).await {
                     Ok(Ok(())) => (true, pubkey, update),
@@ -330,7 +348,7 @@ pub(crate) fn handle_ontology_validation(
         };
 
         let fut = actix::fut::wrap_future::<_, SocketFlowServer>(fut);
-        ctx.spawn(fut.map(|response, _act, ctx| {
+        super::panic_guard::spawn_guarded(ctx, "ontology_validation", fut.map(|response, _act, ctx| {
             if let Ok(msg_str) = serde_json::to_string(&response) {
                 ctx.text(msg_str);
             }
@@ -404,7 +422,7 @@ pub(crate) fn handle_ontology_reasoning(
             }
         };
         let fut = actix::fut::wrap_future::<_, SocketFlowServer>(fut);
-        ctx.spawn(fut.map(|response, _act, ctx| {
+        super::panic_guard::spawn_guarded(ctx, "ontology_reasoning", fut.map(|response, _act, ctx| {
             if let Ok(msg_str) = serde_json::to_string(&response) {
                 ctx.text(msg_str);
             }
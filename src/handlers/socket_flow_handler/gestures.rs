@@ -0,0 +1,380 @@
+// Hand-tracking gestures from thin XR clients.
+//
+//   gesture { gesture: "pinch" | "grab" | "point", phase: "start" | "move" | "end",
+//             ray: { origin: [x, y, z], direction: [x, y, z] }, nodeId? }
+//     -> gestureAction { gesture, action, nodeId, position }   (on start)
+//
+// The ray is in graph space. On `start` the server takes `nodeId` if the
+// client already resolved a target, else picks the node nearest along the
+// ray within `xr.gestures.pickRadius` from the graph's node map, and runs the
+// action `xr.gestures` maps the gesture to. An `end` that arrives while its
+// `start` is still resolving cancels it. The actions are:
+//
+//   select  NodeInteractionMessage::Selected, as a click would.
+//   drag    the nodeDrag* flow; each `move` keeps the node at its grab
+//           distance along the new ray until `end`.
+//   focus   nothing server-side; the reply carries the node position for
+//           the client to fly to.
+//   none    ignored, but still answered so the client can give feedback.
+
+use actix::prelude::*;
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::actors::messages::{GetSettings, NodeInteractionMessage, NodeInteractionType};
+use crate::config::{GestureAction, XRGestureSettings};
+use crate::ports::graph_repository::GraphRepository;
+
+use super::position_updates::{
+    handle_node_drag_end, handle_node_drag_start, handle_node_drag_update,
+};
+use super::types::SocketFlowServer;
+
+type Vec3 = [f32; 3];
+
+fn dot(a: Vec3, b: Vec3) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn sub(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GestureKind {
+    Pinch,
+    Grab,
+    Point,
+}
+
+impl GestureKind {
+    pub fn action(self, settings: &XRGestureSettings) -> GestureAction {
+        match self {
+            GestureKind::Pinch => settings.pinch,
+            GestureKind::Grab => settings.grab,
+            GestureKind::Point => settings.point,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GesturePhase {
+    Start,
+    Move,
+    End,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct Ray {
+    pub origin: Vec3,
+    pub direction: Vec3,
+}
+
+impl Ray {
+    /// The same ray with a unit direction, or `None` if it is degenerate.
+    fn normalized(self) -> Option<Self> {
+        let len = dot(self.direction, self.direction).sqrt();
+        if !self.origin.iter().all(|v| v.is_finite()) || !len.is_finite() || len < 1e-6 {
+            return None;
+        }
+        Some(Ray {
+            origin: self.origin,
+            direction: self.direction.map(|v| v / len),
+        })
+    }
+
+    /// Distance along the ray to the foot of the perpendicular from `point`.
+    fn along(&self, point: Vec3) -> f32 {
+        dot(sub(point, self.origin), self.direction)
+    }
+
+    fn at(&self, distance: f32) -> Vec3 {
+        [
+            self.origin[0] + self.direction[0] * distance,
+            self.origin[1] + self.direction[1] * distance,
+            self.origin[2] + self.direction[2] * distance,
+        ]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GestureEvent {
+    pub gesture: GestureKind,
+    pub phase: GesturePhase,
+    #[serde(default)]
+    pub ray: Option<Ray>,
+    #[serde(default)]
+    pub node_id: Option<u32>,
+}
+
+/// A node held by a drag gesture.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GestureDrag {
+    pub gesture: GestureKind,
+    pub node_id: u32,
+    /// Distance along the ray at which the node was grabbed.
+    pub distance: f32,
+    pub position: Vec3,
+}
+
+fn parse_gesture(msg: &serde_json::Value) -> Result<GestureEvent, String> {
+    let body = msg.get("data").unwrap_or(msg);
+    let mut event =
+        GestureEvent::deserialize(body).map_err(|e| format!("Invalid gesture: {}", e))?;
+    if let Some(ray) = event.ray {
+        event.ray = Some(
+            ray.normalized()
+                .ok_or("ray needs a finite origin and a non-zero direction")?,
+        );
+    }
+    if event.phase != GesturePhase::End && event.ray.is_none() {
+        return Err("ray is required for start and move".to_string());
+    }
+    Ok(event)
+}
+
+/// Node nearest the ray origin among those within `radius` of the ray, with
+/// its distance along the ray. Nodes behind the origin are ignored.
+pub fn pick_node(
+    ray: &Ray,
+    nodes: impl IntoIterator<Item = (u32, Vec3)>,
+    radius: f32,
+) -> Option<(u32, f32)> {
+    let radius_sq = radius * radius;
+    nodes
+        .into_iter()
+        .filter_map(|(id, position)| {
+            let offset = sub(position, ray.origin);
+            let along = dot(offset, ray.direction);
+            let off_ray_sq = dot(offset, offset) - along * along;
+            (along >= 0.0 && off_ray_sq <= radius_sq).then_some((id, along))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+fn drag_message(node_id: u32, position: Vec3) -> serde_json::Value {
+    serde_json::json!({
+        "data": {
+            "nodeId": node_id,
+            "position": { "x": position[0], "y": position[1], "z": position[2] },
+        }
+    })
+}
+
+fn gesture_error(ctx: &mut <SocketFlowServer as Actor>::Context, message: &str) {
+    let err = serde_json::json!({
+        "type": "error",
+        "code": "gestureRejected",
+        "message": message,
+    });
+    ctx.text(err.to_string());
+}
+
+/// Let go of the node held by a drag gesture, if any.
+fn end_gesture_drag(act: &mut SocketFlowServer, ctx: &mut <SocketFlowServer as Actor>::Context) {
+    if let Some(drag) = act.gesture_drag.take() {
+        handle_node_drag_end(act, &drag_message(drag.node_id, drag.position), ctx);
+    }
+}
+
+pub(crate) fn handle_gesture(
+    act: &mut SocketFlowServer,
+    msg: &serde_json::Value,
+    ctx: &mut <SocketFlowServer as Actor>::Context,
+) {
+    // Gestures drive the same actions as drags, so the same rule applies.
+    if act.pubkey.is_none() {
+        gesture_error(ctx, "Gestures require an authenticated session");
+        return;
+    }
+    let event = match parse_gesture(msg) {
+        Ok(event) => event,
+        Err(e) => {
+            gesture_error(ctx, &e);
+            return;
+        }
+    };
+
+    let held = act
+        .gesture_drag
+        .filter(|drag| drag.gesture == event.gesture);
+    match (event.phase, event.ray) {
+        (GesturePhase::Start, Some(ray)) => start_gesture(act, event, ray, ctx),
+        (GesturePhase::Move, Some(ray)) => {
+            if let Some(mut drag) = held {
+                drag.position = ray.at(drag.distance);
+                act.gesture_drag = Some(drag);
+                handle_node_drag_update(act, &drag_message(drag.node_id, drag.position), ctx);
+            }
+        }
+        (GesturePhase::End, _) => {
+            if let Some(pending) = act.gesture_starts.remove(&event.gesture) {
+                ctx.cancel_future(pending);
+            }
+            if held.is_some() {
+                end_gesture_drag(act, ctx);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn start_gesture(
+    act: &mut SocketFlowServer,
+    event: GestureEvent,
+    ray: Ray,
+    ctx: &mut <SocketFlowServer as Actor>::Context,
+) {
+    let app_state = act.app_state.clone();
+    let fut = async move {
+        let settings = app_state
+            .settings_addr
+            .send(GetSettings)
+            .await
+            .map_err(|e| format!("Settings unavailable: {}", e))?
+            .map_err(|e| format!("Settings unavailable: {}", e))?;
+        let gestures = settings.xr.gestures;
+        let action = event.gesture.action(&gestures);
+        if action == GestureAction::None {
+            return Ok((action, None));
+        }
+
+        let nodes = app_state
+            .graph_repository
+            .get_node_map()
+            .await
+            .map_err(|e| format!("Graph service unavailable: {}", e))?;
+        let position_of = |id: u32| {
+            nodes
+                .get(&id)
+                .map(|node| (id, [node.data.x, node.data.y, node.data.z]))
+        };
+        let target = match event.node_id {
+            Some(node_id) => position_of(node_id),
+            None => pick_node(
+                &ray,
+                nodes
+                    .iter()
+                    .map(|(&id, node)| (id, [node.data.x, node.data.y, node.data.z])),
+                gestures.pick_radius,
+            )
+            .and_then(|(picked, _)| position_of(picked)),
+        }
+        .map(|(id, position)| (id, position, ray.along(position).max(0.0)));
+        Ok::<_, String>((action, target))
+    };
+
+    let gesture = event.gesture;
+    let fut = actix::fut::wrap_future::<_, SocketFlowServer>(fut);
+    let handle = super::panic_guard::spawn_guarded(ctx, "gesture", fut.map(move |result, act, ctx| {
+        act.gesture_starts.remove(&gesture);
+        let (action, target) = match result {
+            Ok(resolved) => resolved,
+            Err(e) => {
+                warn!(
+                    "[Gesture] Client {:?} {:?} failed: {}",
+                    act.client_id, gesture, e
+                );
+                gesture_error(ctx, &e);
+                return;
+            }
+        };
+        debug!(
+            "[Gesture] Client {:?} {:?} -> {:?} on {:?}",
+            act.client_id,
+            gesture,
+            action,
+            target.map(|(id, _, _)| id)
+        );
+
+        if let Some((node_id, position, distance)) = target {
+            match action {
+                GestureAction::Select => {
+                    act.app_state
+                        .graph_service_addr
+                        .do_send(NodeInteractionMessage {
+                            node_id,
+                            interaction_type: NodeInteractionType::Selected,
+                            position: Some(position),
+                        });
+                }
+                GestureAction::Drag => {
+                    end_gesture_drag(act, ctx);
+                    handle_node_drag_start(act, &drag_message(node_id, position), ctx);
+                    // The drag handler may refuse, e.g. too many drags at once.
+                    if act.dragged_nodes.contains(&node_id) {
+                        act.gesture_drag = Some(GestureDrag {
+                            gesture,
+                            node_id,
+                            distance,
+                            position,
+                        });
+                    }
+                }
+                GestureAction::Focus | GestureAction::None => {}
+            }
+        }
+
+        let reply = serde_json::json!({
+            "type": "gestureAction",
+            "gesture": gesture,
+            "action": action,
+            "nodeId": target.map(|(id, _, _)| id),
+            "position": target.map(|(_, position, _)| position),
+        });
+        ctx.text(reply.to_string());
+    }));
+    // A newer start of the same gesture supersedes one still resolving.
+    if let Some(previous) = act.gesture_starts.insert(gesture, handle) {
+        ctx.cancel_future(previous);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn picks_nearest_node_along_ray() {
+        let event = parse_gesture(&json!({
+            "type": "gesture",
+            "data": {
+                "gesture": "grab",
+                "phase": "start",
+                "ray": { "origin": [0.0, 0.0, 0.0], "direction": [0.0, 0.0, -2.0] }
+            }
+        }))
+        .unwrap();
+        assert_eq!(event.gesture, GestureKind::Grab);
+        let ray = event.ray.unwrap();
+        assert_eq!(ray.direction, [0.0, 0.0, -1.0]);
+
+        let nodes = [
+            (1, [0.0, 0.0, 10.0]),  // behind the hand
+            (2, [3.0, 0.0, -20.0]), // in reach, further away
+            (3, [1.0, 1.0, -8.0]),  // nearest in reach
+            (4, [9.0, 0.0, -2.0]),  // too far off the ray
+        ];
+        assert_eq!(pick_node(&ray, nodes, 5.0), Some((3, 8.0)));
+        assert_eq!(pick_node(&ray, nodes, 1.0), None);
+        assert_eq!(ray.at(8.0), [0.0, 0.0, -8.0]);
+
+        let settings = XRGestureSettings::default();
+        assert_eq!(GestureKind::Pinch.action(&settings), GestureAction::Select);
+        assert_eq!(GestureKind::Point.action(&settings), GestureAction::Focus);
+
+        assert!(parse_gesture(&json!({"gesture": "wave", "phase": "start"})).is_err());
+        assert!(parse_gesture(&json!({"gesture": "pinch", "phase": "move"})).is_err());
+        assert!(parse_gesture(&json!({
+            "gesture": "pinch",
+            "phase": "start",
+            "ray": { "origin": [0.0, 0.0, 0.0], "direction": [0.0, 0.0, 0.0] }
+        }))
+        .is_err());
+        assert!(parse_gesture(&json!({"gesture": "pinch", "phase": "end"})).is_ok());
+    }
+}
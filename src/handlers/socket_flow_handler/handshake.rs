@@ -0,0 +1,470 @@
+// `hello` handshake: per-session protocol negotiation.
+//
+// A client may send, at any point after connecting,
+//
+//   {"type":"hello","data":{"protocolVersions":[3,5],
+//    "encodings":["binary","json"],"compression":["deflate","none"],
+//    "capabilities":["graphDiff"]}}
+//
+// and the server answers `hello_ack` with the chosen version, encoding and
+// compression plus the capability intersection. Lists are in client
+// preference order. `jsonSchema`, `fieldCasing` and `stringMetadata`
+// select the JSON node/edge schema (see `protocol::graph_json`), and
+// `locale` (BCP 47) picks native or romanized node labels. `deviceId` names
+// an XR device so the ack carries its saved room anchor. Sessions that
+// never say hello keep the legacy defaults (protocol 3, uncompressed
+// binary, JSON schema 1), so older clients are unaffected.
+
+use actix::prelude::*;
+use flate2::write::DeflateEncoder;
+use flate2::Compression as DeflateLevel;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::io::Write;
+
+use crate::protocol::graph_json::{FieldCasing, JsonSchema, SUPPORTED_JSON_SCHEMAS};
+use crate::utils::binary_protocol;
+use crate::utils::socket_flow_messages::BinaryNodeData;
+
+use super::types::SocketFlowServer;
+
+pub const SUPPORTED_PROTOCOL_VERSIONS: &[u32] = &[3, 5];
+pub const LEGACY_PROTOCOL_VERSION: u32 = 3;
+/// Client computes physics itself and pushes binary position frames back.
+pub const LOCAL_PHYSICS_CAPABILITY: &str = "localPhysics";
+/// Lets the client upload a saved layout once after `hello`; see `layout_seed`.
+pub const LAYOUT_SEED_CAPABILITY: &str = "layoutSeed";
+pub const SUPPORTED_CAPABILITIES: &[&str] = &[
+    "nodeTypeFlags",
+    "graphDiff",
+    "cameraRecording",
+    "heartbeatDirectives",
+    LOCAL_PHYSICS_CAPABILITY,
+    LAYOUT_SEED_CAPABILITY,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Encoding {
+    Binary,
+    Json,
+}
+
+impl Encoding {
+    pub const SUPPORTED: &'static [&'static str] = &["binary", "json"];
+
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "binary" => Some(Encoding::Binary),
+            "json" => Some(Encoding::Json),
+            // "protobuf" is recognised but not offered by this server.
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FrameCompression {
+    None,
+    Deflate,
+}
+
+impl FrameCompression {
+    pub const SUPPORTED: &'static [&'static str] = &["deflate", "none"];
+
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "none" => Some(FrameCompression::None),
+            "deflate" => Some(FrameCompression::Deflate),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientHello {
+    #[serde(default, alias = "protocolVersion")]
+    pub protocol_versions: VersionList,
+    #[serde(default)]
+    pub encodings: Vec<String>,
+    #[serde(default)]
+    pub compression: Vec<String>,
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+    #[serde(default)]
+    pub json_schema: Option<u32>,
+    #[serde(default)]
+    pub field_casing: Option<FieldCasing>,
+    #[serde(default)]
+    pub string_metadata: Option<bool>,
+    #[serde(default)]
+    pub locale: Option<String>,
+    /// XR device id; `hello_ack` then carries its room anchor.
+    #[serde(default)]
+    pub device_id: Option<String>,
+}
+
+/// Accepts either a single version or a list.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(from = "VersionListRepr")]
+pub struct VersionList(pub Vec<u32>);
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum VersionListRepr {
+    One(u32),
+    Many(Vec<u32>),
+}
+
+impl From<VersionListRepr> for VersionList {
+    fn from(repr: VersionListRepr) -> Self {
+        match repr {
+            VersionListRepr::One(v) => VersionList(vec![v]),
+            VersionListRepr::Many(v) => VersionList(v),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionProtocol {
+    pub protocol_version: u32,
+    pub encoding: Encoding,
+    pub compression: FrameCompression,
+    pub capabilities: BTreeSet<String>,
+    /// Schema for JSON node and edge payloads.
+    pub json_schema: JsonSchema,
+    /// False until the client completes a `hello`.
+    pub negotiated: bool,
+}
+
+impl Default for SessionProtocol {
+    fn default() -> Self {
+        Self {
+            protocol_version: LEGACY_PROTOCOL_VERSION,
+            encoding: Encoding::Binary,
+            compression: FrameCompression::None,
+            capabilities: BTreeSet::new(),
+            json_schema: JsonSchema::LEGACY,
+            negotiated: false,
+        }
+    }
+}
+
+impl SessionProtocol {
+    pub fn has(&self, capability: &str) -> bool {
+        self.capabilities.contains(capability)
+    }
+}
+
+/// What the server can offer, as advertised in `connection_established`.
+pub fn server_offer() -> serde_json::Value {
+    serde_json::json!({
+        "supported": SUPPORTED_PROTOCOL_VERSIONS,
+        "preferred": LEGACY_PROTOCOL_VERSION,
+        "encodings": Encoding::SUPPORTED,
+        "compression": FrameCompression::SUPPORTED,
+        "capabilities": SUPPORTED_CAPABILITIES,
+        "jsonSchemas": SUPPORTED_JSON_SCHEMAS,
+    })
+}
+
+pub fn negotiate(hello: &ClientHello) -> Result<SessionProtocol, String> {
+    let protocol_version = if hello.protocol_versions.0.is_empty() {
+        LEGACY_PROTOCOL_VERSION
+    } else {
+        hello
+            .protocol_versions
+            .0
+            .iter()
+            .copied()
+            .filter(|v| SUPPORTED_PROTOCOL_VERSIONS.contains(v))
+            .max()
+            .ok_or_else(|| {
+                format!(
+                    "No common protocol version (client {:?}, server {:?})",
+                    hello.protocol_versions.0, SUPPORTED_PROTOCOL_VERSIONS
+                )
+            })?
+    };
+
+    let encoding = if hello.encodings.is_empty() {
+        Encoding::Binary
+    } else {
+        hello
+            .encodings
+            .iter()
+            .find_map(|e| Encoding::parse(e))
+            .ok_or_else(|| format!("No common encoding (client {:?})", hello.encodings))?
+    };
+
+    let compression = hello
+        .compression
+        .iter()
+        .find_map(|c| FrameCompression::parse(c))
+        .unwrap_or(FrameCompression::None);
+
+    let capabilities = hello
+        .capabilities
+        .iter()
+        .filter(|c| SUPPORTED_CAPABILITIES.contains(&c.as_str()))
+        .cloned()
+        .collect();
+
+    // Unlike REST, an unspecified schema stays legacy for existing clients.
+    let json_schema = JsonSchema::resolve(
+        Some(hello.json_schema.unwrap_or(JsonSchema::LEGACY.version)),
+        hello.field_casing,
+    )?
+    .with_string_metadata(hello.string_metadata)
+    .with_locale(hello.locale.as_deref());
+
+    Ok(SessionProtocol {
+        protocol_version,
+        encoding,
+        compression,
+        capabilities,
+        json_schema,
+        negotiated: true,
+    })
+}
+
+/// Rewrite a V5 position frame (`[5][u64 sequence][v3 nodes]`) as the plain
+/// v3 frame a session that negotiated protocol 3 expects. Other frames pass
+/// through unchanged.
+pub fn frame_for_version(data: Vec<u8>, protocol_version: u32) -> Vec<u8> {
+    if protocol_version != LEGACY_PROTOCOL_VERSION || data.first() != Some(&5) || data.len() < 9 {
+        return data;
+    }
+    let mut out = Vec::with_capacity(data.len() - 8);
+    out.push(LEGACY_PROTOCOL_VERSION as u8);
+    out.extend_from_slice(&data[9..]);
+    out
+}
+
+pub fn deflate(data: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut encoder = DeflateEncoder::new(Vec::with_capacity(data.len() / 2), DeflateLevel::fast());
+    encoder.write_all(data)?;
+    encoder.finish()
+}
+
+pub(crate) fn handle_hello(
+    act: &mut SocketFlowServer,
+    msg: &serde_json::Value,
+    ctx: &mut <SocketFlowServer as Actor>::Context,
+) {
+    let hello: ClientHello = match msg.get("data").cloned().map(serde_json::from_value) {
+        Some(Ok(hello)) => hello,
+        None => ClientHello::default(),
+        Some(Err(e)) => {
+            warn!("[WebSocket] Malformed hello: {}", e);
+            let err = serde_json::json!({
+                "type": "hello_error",
+                "message": format!("Malformed hello: {}", e),
+            });
+            ctx.text(err.to_string());
+            return;
+        }
+    };
+
+    match negotiate(&hello) {
+        Ok(protocol) => {
+            info!(
+                "[WebSocket] Client {:?} negotiated protocol v{} ({:?}, {:?}, caps {:?})",
+                act.client_id,
+                protocol.protocol_version,
+                protocol.encoding,
+                protocol.compression,
+                protocol.capabilities
+            );
+            if let Some(device_id) = hello.device_id.as_deref() {
+                use crate::services::xr_anchor_service::validate_device_id;
+                match validate_device_id(device_id) {
+                    Ok(()) => act.xr_device_id = Some(device_id.to_string()),
+                    Err(e) => warn!("[WebSocket] Ignoring hello deviceId: {}", e),
+                }
+            }
+            let mut ack = serde_json::json!({
+                "type": "hello_ack",
+                "data": {
+                    "protocolVersion": protocol.protocol_version,
+                    "encoding": protocol.encoding,
+                    "compression": protocol.compression,
+                    "capabilities": protocol.capabilities,
+                    "jsonSchema": protocol.json_schema,
+                    "server": server_offer(),
+                }
+            });
+            if let Some(anchor) = act.xr_anchor() {
+                ack["data"]["xrAnchor"] = anchor;
+            }
+            act.protocol = protocol;
+            ctx.text(ack.to_string());
+        }
+        Err(e) => {
+            // Keep whatever the session had; the client may retry or fall back.
+            warn!("[WebSocket] Hello negotiation failed: {}", e);
+            let err = serde_json::json!({
+                "type": "hello_error",
+                "message": e,
+                "server": server_offer(),
+            });
+            ctx.text(err.to_string());
+        }
+    }
+}
+
+impl SocketFlowServer {
+    /// Send a live position frame in the session's negotiated encoding.
+    /// `binary` is the already-encoded frame for binary sessions. Withheld
+    /// during layout playback and overview mode.
+    pub(crate) fn send_positions(
+        &self,
+        ctx: &mut <Self as Actor>::Context,
+        nodes: &[(u32, BinaryNodeData)],
+        binary: Vec<u8>,
+    ) {
+        if self.layout_playback.is_some() || self.overview {
+            return;
+        }
+        self.write_positions(ctx, nodes, binary);
+    }
+
+    /// [`Self::send_positions`] without the gate, for frames the client asked for.
+    pub(crate) fn write_positions(
+        &self,
+        ctx: &mut <Self as Actor>::Context,
+        nodes: &[(u32, BinaryNodeData)],
+        binary: Vec<u8>,
+    ) {
+        match self.protocol.encoding {
+            Encoding::Json => {
+                let rows: Vec<[serde_json::Value; 7]> = nodes
+                    .iter()
+                    .map(|(id, d)| {
+                        [
+                            (*id).into(),
+                            d.x.into(),
+                            d.y.into(),
+                            d.z.into(),
+                            d.vx.into(),
+                            d.vy.into(),
+                            d.vz.into(),
+                        ]
+                    })
+                    .collect();
+                let frame = serde_json::json!({ "type": "positions", "nodes": rows });
+                ctx.text(frame.to_string());
+            }
+            Encoding::Binary => self.send_binary_frame(ctx, binary),
+        }
+    }
+
+    /// Relay a pre-encoded frame from the client coordinator in the session's
+    /// negotiated form. Coordinator position frames are always V5 binary, so
+    /// JSON sessions get them decoded into `positions` rows and sessions that
+    /// negotiated v3 get the broadcast sequence stripped.
+    pub(crate) fn relay_broadcast_frame(&self, ctx: &mut <Self as Actor>::Context, data: Vec<u8>) {
+        if !super::layout_playback::is_position_frame(&data) {
+            self.send_binary_frame(ctx, data);
+            return;
+        }
+        match self.protocol.encoding {
+            Encoding::Json => match binary_protocol::decode_node_data(&data) {
+                Ok(nodes) => self.write_positions(ctx, &nodes, Vec::new()),
+                Err(e) => warn!("[WebSocket] Dropping undecodable position frame: {}", e),
+            },
+            Encoding::Binary => {
+                let frame = if self.protocol.negotiated {
+                    frame_for_version(data, self.protocol.protocol_version)
+                } else {
+                    data
+                };
+                self.send_binary_frame(ctx, frame);
+            }
+        }
+    }
+
+    /// Send a binary frame, compressed if the session negotiated it.
+    pub(crate) fn send_binary_frame(&self, ctx: &mut <Self as Actor>::Context, data: Vec<u8>) {
+        match self.protocol.compression {
+            FrameCompression::None => ctx.binary(data),
+            FrameCompression::Deflate => match deflate(&data) {
+                Ok(compressed) => ctx.binary(compressed),
+                Err(e) => warn!("[WebSocket] Failed to deflate frame, dropping: {}", e),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::graph_json::LabelVariant;
+
+    fn hello(json: serde_json::Value) -> ClientHello {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn picks_highest_common_version_and_first_supported_preferences() {
+        let h = hello(serde_json::json!({
+            "protocolVersions": [3, 5, 9],
+            "encodings": ["protobuf", "json", "binary"],
+            "compression": ["zstd", "deflate"],
+            "capabilities": ["graphDiff", "quantization"],
+        }));
+        let p = negotiate(&h).unwrap();
+        assert_eq!(p.protocol_version, 5);
+        assert_eq!(p.encoding, Encoding::Json);
+        assert_eq!(p.compression, FrameCompression::Deflate);
+        assert!(p.has("graphDiff"));
+        assert!(!p.has("quantization"));
+        assert_eq!(p.json_schema, JsonSchema::LEGACY);
+
+        let p = negotiate(&hello(serde_json::json!({ "jsonSchema": 2 }))).unwrap();
+        assert_eq!(p.json_schema, JsonSchema::CURRENT);
+
+        let p = negotiate(&hello(serde_json::json!({ "locale": "de-DE" }))).unwrap();
+        assert_eq!(p.json_schema.labels, LabelVariant::Romanized);
+    }
+
+    #[test]
+    fn v5_frames_are_rewritten_for_v3_sessions() {
+        let mut v5 = vec![5u8];
+        v5.extend_from_slice(&42u64.to_le_bytes());
+        v5.extend_from_slice(&[1, 2, 3]);
+        assert_eq!(frame_for_version(v5.clone(), 3), vec![3, 1, 2, 3]);
+        assert_eq!(frame_for_version(v5.clone(), 5), v5);
+        assert_eq!(frame_for_version(vec![3, 9], 3), vec![3, 9]);
+    }
+
+    #[test]
+    fn legacy_defaults_and_incompatible_clients() {
+        let p = negotiate(&hello(serde_json::json!({ "protocolVersion": 3 }))).unwrap();
+        assert_eq!(p.encoding, Encoding::Binary);
+        assert_eq!(p.compression, FrameCompression::None);
+
+        assert!(negotiate(&hello(serde_json::json!({ "protocolVersions": [7] }))).is_err());
+        assert!(negotiate(&hello(serde_json::json!({ "encodings": ["protobuf"] }))).is_err());
+        assert!(negotiate(&hello(serde_json::json!({ "jsonSchema": 9 }))).is_err());
+    }
+
+    #[test]
+    fn deflate_round_trips() {
+        use flate2::read::DeflateDecoder;
+        use std::io::Read;
+
+        let data: Vec<u8> = (0..4096u32).flat_map(|i| (i % 7).to_le_bytes()).collect();
+        let compressed = deflate(&data).unwrap();
+        assert!(compressed.len() < data.len());
+        let mut out = Vec::new();
+        DeflateDecoder::new(&compressed[..])
+            .read_to_end(&mut out)
+            .unwrap();
+        assert_eq!(out, data);
+    }
+}
@@ -0,0 +1,172 @@
+//! Idle session hibernation.
+//!
+//! A dashboard left open overnight keeps receiving every position frame. A
+//! session that has sent no text or binary frame for
+//! `system.websocket.idleHibernationMs` is put to sleep: pushed frames
+//! (position broadcasts, coordinator text and graph loads) are dropped, and
+//! the client is told once:
+//!
+//!   { type: "hibernating", idleMs }
+//!
+//! Server pings continue, so the connection stays open; the pongs browsers
+//! send back on their own do not count as activity. The next text or binary
+//! frame from the client wakes the session before that frame is handled:
+//!
+//!   { type: "resumed", hibernatedMs }
+//!
+//! followed by a state sync and a full position snapshot, since the client
+//! missed everything in between. Counters are reported in `/api/metrics`.
+
+use actix::prelude::*;
+use log::info;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use super::types::SocketFlowServer;
+
+/// Longest gap between idle checks.
+const MAX_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Shortest gap between idle checks.
+const MIN_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HibernationStats {
+    /// Sessions hibernating right now.
+    pub hibernating: u64,
+    /// Times a session has entered hibernation.
+    pub hibernations: u64,
+    /// Times a hibernating session has woken up.
+    pub wakes: u64,
+    /// Pushed frames dropped because their session was hibernating.
+    pub frames_withheld: u64,
+}
+
+#[derive(Default)]
+struct HibernationCounters {
+    hibernating: AtomicU64,
+    hibernations: AtomicU64,
+    wakes: AtomicU64,
+    frames_withheld: AtomicU64,
+}
+
+static COUNTERS: Lazy<HibernationCounters> = Lazy::new(HibernationCounters::default);
+
+/// Process-wide hibernation counters for `/api/metrics`.
+pub fn hibernation_stats() -> HibernationStats {
+    HibernationStats {
+        hibernating: COUNTERS.hibernating.load(Ordering::Relaxed),
+        hibernations: COUNTERS.hibernations.load(Ordering::Relaxed),
+        wakes: COUNTERS.wakes.load(Ordering::Relaxed),
+        frames_withheld: COUNTERS.frames_withheld.load(Ordering::Relaxed),
+    }
+}
+
+/// How often to look for idleness: a quarter of the threshold, so a session
+/// sleeps at most 25% late, clamped to sensible bounds.
+pub fn check_interval(idle_after: Duration) -> Duration {
+    (idle_after / 4).clamp(MIN_CHECK_INTERVAL, MAX_CHECK_INTERVAL)
+}
+
+/// Whether a session idle for `idle_for` should hibernate. A zero threshold
+/// disables hibernation.
+pub fn should_hibernate(idle_for: Duration, idle_after: Duration) -> bool {
+    !idle_after.is_zero() && idle_for >= idle_after
+}
+
+impl SocketFlowServer {
+    /// Start the idle check; a no-op when hibernation is disabled.
+    pub(crate) fn start_hibernation_timer(&self, ctx: &mut <Self as Actor>::Context) {
+        if self.idle_hibernation.is_zero() {
+            return;
+        }
+        ctx.run_interval(check_interval(self.idle_hibernation), |act, ctx| {
+            if !act.hibernating
+                && should_hibernate(act.last_client_activity.elapsed(), act.idle_hibernation)
+            {
+                act.hibernate(ctx);
+            }
+        });
+    }
+
+    fn hibernate(&mut self, ctx: &mut <Self as Actor>::Context) {
+        self.hibernating = true;
+        COUNTERS.hibernating.fetch_add(1, Ordering::Relaxed);
+        COUNTERS.hibernations.fetch_add(1, Ordering::Relaxed);
+        let idle_ms = self.last_client_activity.elapsed().as_millis() as u64;
+        info!(
+            "[WebSocket] Client {:?} hibernating after {}ms idle",
+            self.client_id, idle_ms
+        );
+        ctx.text(serde_json::json!({ "type": "hibernating", "idleMs": idle_ms }).to_string());
+        // Idle time now counts towards how long the session slept.
+        self.last_client_activity = Instant::now();
+    }
+
+    /// Record a frame from the client, waking the session if it was asleep.
+    pub(crate) fn note_client_activity(&mut self, ctx: &mut <Self as Actor>::Context) {
+        let slept_for = self.last_client_activity.elapsed();
+        self.last_client_activity = Instant::now();
+        if !self.hibernating {
+            return;
+        }
+        self.hibernating = false;
+        COUNTERS.hibernating.fetch_sub(1, Ordering::Relaxed);
+        COUNTERS.wakes.fetch_add(1, Ordering::Relaxed);
+        info!(
+            "[WebSocket] Client {:?} resumed after {}ms hibernating",
+            self.client_id,
+            slept_for.as_millis()
+        );
+        ctx.text(
+            serde_json::json!({ "type": "resumed", "hibernatedMs": slept_for.as_millis() as u64 })
+                .to_string(),
+        );
+        self.send_full_state_sync(ctx);
+        super::position_updates::handle_request_full_snapshot(self, &serde_json::json!({}), ctx);
+    }
+
+    /// Whether a pushed frame should be dropped; counts the ones that are.
+    pub(crate) fn withhold_while_hibernating(&self) -> bool {
+        if self.hibernating {
+            COUNTERS.frames_withheld.fetch_add(1, Ordering::Relaxed);
+        }
+        self.hibernating
+    }
+
+    /// Keep the gauge honest when a hibernating session disconnects.
+    pub(crate) fn end_hibernation_on_disconnect(&mut self) {
+        if std::mem::take(&mut self.hibernating) {
+            COUNTERS.hibernating.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn idle_threshold_and_check_interval() {
+        let thirty_min = Duration::from_secs(30 * 60);
+        assert!(!should_hibernate(Duration::from_secs(60), thirty_min));
+        assert!(should_hibernate(thirty_min, thirty_min));
+        assert!(!should_hibernate(
+            Duration::from_secs(86_400),
+            Duration::ZERO
+        ));
+
+        assert_eq!(check_interval(thirty_min), MAX_CHECK_INTERVAL);
+        assert_eq!(
+            check_interval(Duration::from_secs(20)),
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            check_interval(Duration::from_millis(100)),
+            MIN_CHECK_INTERVAL
+        );
+    }
+}
@@ -318,7 +318,15 @@ pub async fn socket_flow_handler(
         client_ip.clone(),
     );
 
-    ws_server.is_reconnection = is_reconnection;
+    // Clients back from a network blip name the session they are resuming
+    ws_server.resume_request = super::session_resume::parse_resume_query(req.query_string());
+    ws_server.is_reconnection = is_reconnection || ws_server.resume_request.is_some();
+
+    // XR clients name their device so connection_established carries its room anchor
+    ws_server.xr_device_id = url::form_urlencoded::parse(req.query_string().as_bytes())
+        .find(|(k, _)| k == "deviceId")
+        .map(|(_, v)| v.into_owned())
+        .filter(|d| crate::services::xr_anchor_service::validate_device_id(d).is_ok());
 
     // Store HTTP-equivalent URL for NIP-98 WS auth validation
     {
@@ -0,0 +1,118 @@
+// Per-session content language filter.
+//
+//   set_language_filter { languages: ["eng", "deu", "und"] | [] | null }
+//     -> set_language_filter_success { languages }
+//
+// Only pages detected as one of `languages` (ISO 639-3; `und` for pages
+// whose language could not be detected) are streamed to this session; an
+// empty list or null shows every language. The coordinator recomputes the
+// session's subset and resends the initial graph load when it changes. See
+// `services::language_detection`.
+
+use actix::prelude::*;
+use log::{info, warn};
+
+use crate::actors::messages::SetLanguageFilter;
+use crate::services::language_detection::parse_languages;
+
+use super::types::SocketFlowServer;
+
+fn language_filter_error(ctx: &mut <SocketFlowServer as Actor>::Context, message: &str) {
+    let err = serde_json::json!({
+        "type": "error",
+        "code": "languageFilterRejected",
+        "message": message,
+    });
+    ctx.text(err.to_string());
+}
+
+/// Requested languages, normalised and sorted; `None` clears the filter.
+fn parse_language_filter(msg: &serde_json::Value) -> Result<Option<Vec<String>>, String> {
+    let body = msg.get("data").unwrap_or(msg);
+    let codes = match body.get("languages") {
+        None | Some(serde_json::Value::Null) => return Ok(None),
+        Some(serde_json::Value::Array(codes)) => codes
+            .iter()
+            .map(|c| c.as_str().ok_or("languages must be strings"))
+            .collect::<Result<Vec<&str>, _>>()?,
+        Some(_) => return Err("languages must be an array of language codes or null".to_string()),
+    };
+    Ok(parse_languages(codes)?.map(|languages| {
+        let mut languages: Vec<String> = languages.into_iter().collect();
+        languages.sort();
+        languages
+    }))
+}
+
+pub(crate) fn handle_set_language_filter(
+    act: &mut SocketFlowServer,
+    msg: &serde_json::Value,
+    ctx: &mut <SocketFlowServer as Actor>::Context,
+) {
+    let Some(client_id) = act.client_id else {
+        language_filter_error(ctx, "Client registration in progress, please retry");
+        return;
+    };
+    let languages = match parse_language_filter(msg) {
+        Ok(languages) => languages,
+        Err(e) => {
+            language_filter_error(ctx, &e);
+            return;
+        }
+    };
+
+    let update = SetLanguageFilter {
+        client_id,
+        languages: languages.clone(),
+    };
+    let fut = actix::fut::wrap_future::<_, SocketFlowServer>(act.client_manager_addr.send(update));
+    super::panic_guard::spawn_guarded(ctx, "set_language_filter", fut.map(move |result, act, ctx| match result {
+        Ok(Ok(())) => {
+            info!(
+                "[WebSocket] Client {:?} language filter {:?}",
+                act.client_id, languages
+            );
+            let reply = serde_json::json!({
+                "type": "set_language_filter_success",
+                "languages": languages,
+            });
+            ctx.text(reply.to_string());
+        }
+        Ok(Err(e)) => {
+            warn!(
+                "[WebSocket] Client {:?} language filter rejected: {}",
+                act.client_id, e
+            );
+            language_filter_error(ctx, &e);
+        }
+        Err(e) => {
+            warn!(
+                "[WebSocket] Client {:?} language filter failed: {}",
+                act.client_id, e
+            );
+            language_filter_error(ctx, "Client coordinator unavailable");
+        }
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_and_normalises_languages() {
+        assert_eq!(
+            parse_language_filter(&json!({"languages": ["DEU", "eng", "deu"]})),
+            Ok(Some(vec!["deu".to_string(), "eng".to_string()]))
+        );
+        assert_eq!(
+            parse_language_filter(&json!({"data": {"languages": []}})),
+            Ok(None)
+        );
+        assert_eq!(parse_language_filter(&json!({"languages": null})), Ok(None));
+        assert!(parse_language_filter(&json!({"languages": "eng"})).is_err());
+        assert!(parse_language_filter(&json!({"languages": [1]})).is_err());
+        assert!(parse_language_filter(&json!({"languages": ["english"]})).is_err());
+    }
+}
@@ -0,0 +1,93 @@
+// Time-travel playback of recent layout history.
+//
+//   layoutPlayback { enabled: true }   -> layoutPlayback { enabled, intervalMs, frames: [t, ...] }
+//   layoutHistorySeek { t }            -> layoutHistoryFrame { t, nodes: [[id, x, y, z], ...] }
+//   layoutPlayback { enabled: false }  -> layoutPlayback { enabled: false }
+//
+// While playback is on, live position frames to this session are withheld so
+// the client can scrub without the simulation overwriting what it shows.
+// Leaving playback resumes the live stream on the next broadcast.
+
+use actix::prelude::*;
+use log::info;
+
+use crate::services::layout_history_service::layout_history;
+
+use super::types::SocketFlowServer;
+
+/// Live position frames are the only thing playback withholds; control,
+/// voice and agent-action frames still reach the client.
+pub(crate) fn is_position_frame(data: &[u8]) -> bool {
+    crate::utils::binary_protocol::is_position_frame(data)
+}
+
+pub(crate) fn handle_layout_playback(
+    act: &mut SocketFlowServer,
+    msg: &serde_json::Value,
+    ctx: &mut <SocketFlowServer as Actor>::Context,
+) {
+    let enabled = msg
+        .get("enabled")
+        .or_else(|| msg.get("data").and_then(|d| d.get("enabled")))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true);
+
+    let history = layout_history();
+    let reply = if enabled {
+        let frames: Vec<i64> = history.timeline().iter().map(|f| f.t).collect();
+        act.layout_playback = Some(frames.last().copied().unwrap_or_default());
+        serde_json::json!({
+            "type": "layoutPlayback",
+            "enabled": true,
+            "intervalMs": history.interval_ms(),
+            "frames": frames,
+        })
+    } else {
+        act.layout_playback = None;
+        serde_json::json!({ "type": "layoutPlayback", "enabled": false })
+    };
+    info!(
+        "[WebSocket] Client {:?} layout playback {}",
+        act.client_id,
+        if enabled { "on" } else { "off" }
+    );
+    ctx.text(reply.to_string());
+}
+
+pub(crate) fn handle_layout_history_seek(
+    act: &mut SocketFlowServer,
+    msg: &serde_json::Value,
+    ctx: &mut <SocketFlowServer as Actor>::Context,
+) {
+    if act.layout_playback.is_none() {
+        let err = serde_json::json!({
+            "type": "error",
+            "message": "Enable layoutPlayback before seeking",
+        });
+        ctx.text(err.to_string());
+        return;
+    }
+    let t = msg
+        .get("t")
+        .or_else(|| msg.get("data").and_then(|d| d.get("t")))
+        .and_then(|v| v.as_i64())
+        .unwrap_or(i64::MAX);
+
+    let reply = match layout_history().at(t) {
+        Some(frame) => {
+            act.layout_playback = Some(frame.t);
+            let nodes: Vec<[serde_json::Value; 4]> = frame
+                .nodes
+                .iter()
+                .map(|p| [p.id.into(), p.x.into(), p.y.into(), p.z.into()])
+                .collect();
+            serde_json::json!({ "type": "layoutHistoryFrame", "t": frame.t, "nodes": nodes })
+        }
+        None => serde_json::json!({
+            "type": "layoutHistoryFrame",
+            "t": serde_json::Value::Null,
+            "nodes": [],
+        }),
+    };
+    ctx.text(reply.to_string());
+}
@@ -0,0 +1,130 @@
+// Answering a scheduled layout refresh proposal.
+//
+//   <- layoutProposal { proposal: { id, currentStress, proposedStress, improvement, ... } }
+//   applyLayoutProposal { id, transitionMs? }
+//     -> layoutProposalApplied { id, applied, transitionMs }   (to every client)
+//   dismissLayoutProposal { id }
+//     -> layoutProposalDismissed { id }                        (to every client)
+//
+// Proposals come from `services::layout_refresh_service`; both replies are
+// broadcast so every client animates to the new layout or hides the prompt.
+// As with `seedLayout`, only a power user or the sole connected client may
+// answer, since the layout is shared.
+
+use actix::prelude::*;
+use log::{info, warn};
+use std::sync::atomic::Ordering;
+
+use crate::handlers::layout_handler::{apply_proposal, dismiss_proposal};
+
+use super::types::SocketFlowServer;
+
+fn proposal_error(ctx: &mut <SocketFlowServer as Actor>::Context, message: &str) {
+    let err = serde_json::json!({
+        "type": "error",
+        "code": "layoutProposalRejected",
+        "message": message,
+    });
+    ctx.text(err.to_string());
+}
+
+/// `id` and `transitionMs` from the message or its `data`.
+fn parse_answer(msg: &serde_json::Value) -> Result<(String, Option<u64>), String> {
+    let body = msg.get("data").unwrap_or(msg);
+    let id = body
+        .get("id")
+        .and_then(|id| id.as_str())
+        .filter(|id| !id.is_empty())
+        .ok_or("Layout proposal id is required")?;
+    Ok((
+        id.to_string(),
+        body.get("transitionMs").and_then(|t| t.as_u64()),
+    ))
+}
+
+fn may_answer(act: &SocketFlowServer) -> bool {
+    act.is_power_user || act.app_state.active_connections.load(Ordering::SeqCst) <= 1
+}
+
+pub(crate) fn handle_apply_layout_proposal(
+    act: &mut SocketFlowServer,
+    msg: &serde_json::Value,
+    ctx: &mut <SocketFlowServer as Actor>::Context,
+) {
+    if !may_answer(act) {
+        proposal_error(
+            ctx,
+            "Other clients are connected; only a power user can apply a new layout",
+        );
+        return;
+    }
+    let (id, transition_ms) = match parse_answer(msg) {
+        Ok(answer) => answer,
+        Err(e) => {
+            proposal_error(ctx, &e);
+            return;
+        }
+    };
+
+    let app_state = act.app_state.clone();
+    let fut = async move { apply_proposal(&app_state, &id, transition_ms).await };
+
+    let fut = actix::fut::wrap_future::<_, SocketFlowServer>(fut);
+    super::panic_guard::spawn_guarded(ctx, "applyLayoutProposal", fut.map(move |result, act, ctx| match result {
+        Ok(report) => info!(
+            "[WebSocket] Client {:?} applied layout proposal to {} of {} nodes",
+            act.client_id, report.applied, report.total_nodes
+        ),
+        Err(e) => {
+            warn!(
+                "[WebSocket] Client {:?} layout proposal not applied: {}",
+                act.client_id, e
+            );
+            proposal_error(ctx, &e);
+        }
+    }));
+}
+
+pub(crate) fn handle_dismiss_layout_proposal(
+    act: &mut SocketFlowServer,
+    msg: &serde_json::Value,
+    ctx: &mut <SocketFlowServer as Actor>::Context,
+) {
+    if !may_answer(act) {
+        proposal_error(
+            ctx,
+            "Other clients are connected; only a power user can dismiss a new layout",
+        );
+        return;
+    }
+    match parse_answer(msg) {
+        Ok((id, _)) if dismiss_proposal(&act.app_state, &id) => {
+            info!(
+                "[WebSocket] Client {:?} dismissed layout proposal {}",
+                act.client_id, id
+            );
+        }
+        Ok((id, _)) => proposal_error(ctx, &format!("No open layout proposal '{}'", id)),
+        Err(e) => proposal_error(ctx, &e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_answers() {
+        assert_eq!(
+            parse_answer(&json!({"type": "applyLayoutProposal", "id": "p1", "transitionMs": 800})),
+            Ok(("p1".to_string(), Some(800)))
+        );
+        assert_eq!(
+            parse_answer(&json!({"data": {"id": "p2"}})),
+            Ok(("p2".to_string(), None))
+        );
+        assert!(parse_answer(&json!({"id": ""})).is_err());
+        assert!(parse_answer(&json!({})).is_err());
+    }
+}
@@ -0,0 +1,181 @@
+// Seeding the shared layout from a client's saved arrangement.
+//
+//   seedLayout { positions: [[id, x, y, z], ...] }
+//     -> layoutSeeded { applied, unknown, rejected, totalNodes }
+//
+// Sent once, right after a `hello` that negotiated `layoutSeed`. Ids may
+// carry wire type flags; they are matched against the graph without them.
+// The positions are written into the GPU buffers before the next physics
+// step, and a settled simulation is resumed, so the next broadcast shows
+// the saved layout. Because the
+// layout is shared, only a power user or the sole connected client may seed.
+
+use actix::prelude::*;
+use log::{info, warn};
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+
+use crate::actors::messages::{ForceResumePhysics, SeedPositions};
+use crate::physics::layout_seed::MAX_SEED_POSITIONS;
+use crate::utils::binary_protocol::clear_all_flags;
+
+use super::handshake::LAYOUT_SEED_CAPABILITY;
+use super::position_updates::sanitize_position;
+use super::types::SocketFlowServer;
+
+fn seed_error(ctx: &mut <SocketFlowServer as Actor>::Context, message: &str) {
+    let err = serde_json::json!({
+        "type": "error",
+        "code": "layoutSeedRejected",
+        "message": message,
+    });
+    ctx.text(err.to_string());
+}
+
+/// Parse `[[id, x, y, z], ...]`, dropping entries that are malformed or out
+/// of bounds. Returns the positions and the number dropped.
+fn parse_positions(raw: &[serde_json::Value]) -> (HashMap<u32, [f32; 3]>, usize) {
+    let mut positions = HashMap::with_capacity(raw.len());
+    let mut rejected = 0;
+    for entry in raw {
+        let parsed = entry.as_array().filter(|a| a.len() == 4).and_then(|a| {
+            let id = a[0].as_u64().and_then(|id| u32::try_from(id).ok())?;
+            let coord = |i: usize| a[i].as_f64().map(|v| v as f32);
+            let (x, y, z) = sanitize_position(coord(1)?, coord(2)?, coord(3)?)?;
+            Some((clear_all_flags(id), [x, y, z]))
+        });
+        match parsed {
+            Some((id, pos)) => {
+                positions.insert(id, pos);
+            }
+            None => rejected += 1,
+        }
+    }
+    (positions, rejected)
+}
+
+pub(crate) fn handle_seed_layout(
+    act: &mut SocketFlowServer,
+    msg: &serde_json::Value,
+    ctx: &mut <SocketFlowServer as Actor>::Context,
+) {
+    if !act.protocol.has(LAYOUT_SEED_CAPABILITY) {
+        seed_error(ctx, "Negotiate the layoutSeed capability in hello first");
+        return;
+    }
+    if act.layout_seeded {
+        seed_error(ctx, "Layout was already seeded for this session");
+        return;
+    }
+    let sole_client = act.app_state.active_connections.load(Ordering::SeqCst) <= 1;
+    if !act.is_power_user && !sole_client {
+        seed_error(
+            ctx,
+            "Other clients are connected; only a power user can seed the layout",
+        );
+        return;
+    }
+
+    let raw = match msg
+        .get("positions")
+        .or_else(|| msg.get("data").and_then(|d| d.get("positions")))
+        .and_then(|v| v.as_array())
+    {
+        Some(raw) => raw,
+        None => {
+            seed_error(ctx, "seedLayout requires a positions array");
+            return;
+        }
+    };
+    if raw.len() > MAX_SEED_POSITIONS {
+        seed_error(
+            ctx,
+            &format!(
+                "Layout has {} positions; the limit is {}",
+                raw.len(),
+                MAX_SEED_POSITIONS
+            ),
+        );
+        return;
+    }
+
+    let (positions, rejected) = parse_positions(raw);
+    // Only one attempt per session, whatever the outcome.
+    act.layout_seeded = true;
+
+    let app_state = act.app_state.clone();
+    let fut = async move {
+        let gpu_addr = app_state
+            .get_gpu_compute_addr()
+            .await
+            .ok_or_else(|| "GPU compute is not available".to_string())?;
+        let report = gpu_addr
+            .send(SeedPositions { positions })
+            .await
+            .map_err(|e| format!("GPU actor unavailable: {}", e))??;
+        // A settled simulation is not stepped, so it would never broadcast
+        // the seeded positions.
+        if report.applied > 0 {
+            if let Err(e) = app_state
+                .graph_service_addr
+                .send(ForceResumePhysics {
+                    reason: "Layout seeded by client".to_string(),
+                })
+                .await
+            {
+                warn!("Failed to resume physics after layout seed: {}", e);
+            }
+        }
+        Ok(report)
+    };
+
+    let fut = actix::fut::wrap_future::<_, SocketFlowServer>(fut);
+    super::panic_guard::spawn_guarded(ctx, "seedLayout", fut.map(move |result, act, ctx| match result {
+        Ok(report) => {
+            info!(
+                "[WebSocket] Client {:?} seeded {} of {} node positions ({} unknown, {} rejected)",
+                act.client_id, report.applied, report.total_nodes, report.unknown, rejected
+            );
+            let reply = serde_json::json!({
+                "type": "layoutSeeded",
+                "applied": report.applied,
+                "unknown": report.unknown,
+                "rejected": rejected,
+                "totalNodes": report.total_nodes,
+            });
+            ctx.text(reply.to_string());
+        }
+        Err(e) => {
+            warn!(
+                "[WebSocket] Client {:?} layout seed refused: {}",
+                act.client_id, e
+            );
+            seed_error(ctx, &e);
+        }
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_entries_and_drops_bad_ones() {
+        let raw = json!([
+            [0x4000_0007u32, 1.0, 2.0, 3.0],
+            [8, 1.0, "x", 3.0],
+            [9, 1.0, 2.0, 1.0e9],
+            [10, 1.0, 2.0],
+            [11, -4.0, 0.0, 4.0]
+        ]);
+        let (positions, rejected) = parse_positions(raw.as_array().unwrap());
+        assert_eq!(rejected, 3);
+        assert_eq!(
+            positions.get(&7),
+            Some(&[1.0, 2.0, 3.0]),
+            "flags are stripped"
+        );
+        assert_eq!(positions.get(&11), Some(&[-4.0, 0.0, 4.0]));
+    }
+}
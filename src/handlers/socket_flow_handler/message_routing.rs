@@ -8,10 +8,16 @@ use super::types::SocketFlowServer;
 
 /// Text message dispatch -- routes parsed JSON messages to the appropriate handler.
 ///
-/// Handles: ping, update_physics_params, request_full_snapshot, requestInitialData,
+/// Handles: ping, hello, update_physics_params, request_full_snapshot, requestInitialData,
 /// enableRandomization, requestBotsGraph, requestBotsPositions, subscribe_position_updates,
 /// requestPositionUpdates (legacy), authenticate, filter_update, requestSwarmTelemetry,
-/// ontology_* messages.
+/// ontology_* messages, nodeDrag*, cameraRecordingStart/cameraPose/cameraRecordingStop,
+/// requestAuthority/releaseAuthority, layoutPlayback/layoutHistorySeek, seedLayout,
+/// applyLayoutProposal/dismissLayoutProposal, simulationControl, simulationQuality, gesture, overview/requestOverviewFrame,
+/// nodeBudget, settingsPatch, set_language_filter.
+///
+/// Message shapes are declared in `visionclaw_contracts::socket_protocol`; its drift
+/// test fails when an arm here has no matching `ClientMessage` variant.
 impl SocketFlowServer {
     pub(crate) fn handle_text_message(
         &mut self,
@@ -31,6 +37,7 @@ impl SocketFlowServer {
             Ok(msg) => {
                 match msg.get("type").and_then(|t| t.as_str()) {
                     Some("ping") => self.handle_json_ping(&msg, ctx),
+                    Some("hello") => super::handshake::handle_hello(self, &msg, ctx),
                     Some("update_physics_params") => {
                         warn!("Client attempted deprecated WebSocket physics update - ignoring");
                         ctx.text(r#"{"type":"error","message":"Physics updates must use REST API: POST /api/analytics/params"}"#);
@@ -91,6 +98,60 @@ impl SocketFlowServer {
                     Some("nodeDragUpdate") => {
                         super::position_updates::handle_node_drag_update(self, &msg, ctx);
                     }
+                    Some("cameraRecordingStart") => {
+                        super::camera_recording::handle_camera_recording_start(self, ctx);
+                    }
+                    Some("cameraPose") => {
+                        super::camera_recording::handle_camera_pose(self, &msg, ctx);
+                    }
+                    Some("cameraRecordingStop") => {
+                        super::camera_recording::handle_camera_recording_stop(self, ctx);
+                    }
+                    Some("requestAuthority") => {
+                        super::position_updates::handle_request_authority(self, &msg, ctx);
+                    }
+                    Some("releaseAuthority") => {
+                        super::position_updates::handle_release_authority(self);
+                    }
+                    Some("layoutPlayback") => {
+                        super::layout_playback::handle_layout_playback(self, &msg, ctx);
+                    }
+                    Some("layoutHistorySeek") => {
+                        super::layout_playback::handle_layout_history_seek(self, &msg, ctx);
+                    }
+                    Some("seedLayout") => {
+                        super::layout_seed::handle_seed_layout(self, &msg, ctx);
+                    }
+                    Some("applyLayoutProposal") => {
+                        super::layout_proposal::handle_apply_layout_proposal(self, &msg, ctx);
+                    }
+                    Some("dismissLayoutProposal") => {
+                        super::layout_proposal::handle_dismiss_layout_proposal(self, &msg, ctx);
+                    }
+                    Some("simulationControl") => {
+                        super::simulation_control::handle_simulation_control(self, &msg, ctx);
+                    }
+                    Some("simulationQuality") => {
+                        super::simulation_control::handle_simulation_quality(self, &msg, ctx);
+                    }
+                    Some("gesture") => {
+                        super::gestures::handle_gesture(self, &msg, ctx);
+                    }
+                    Some("overview") => {
+                        super::overview::handle_overview(self, &msg, ctx);
+                    }
+                    Some("requestOverviewFrame") => {
+                        super::overview::handle_request_overview_frame(self, ctx);
+                    }
+                    Some("nodeBudget") => {
+                        super::node_budget::handle_node_budget(self, &msg, ctx);
+                    }
+                    Some("settingsPatch") => {
+                        super::settings_patch::handle_settings_patch(self, &msg, ctx);
+                    }
+                    Some("set_language_filter") => {
+                        super::language_filter::handle_set_language_filter(self, &msg, ctx);
+                    }
                     _ => {
                         warn!("[WebSocket] Unknown message type: {:?}", msg);
                     }
@@ -8,6 +8,21 @@ pub mod binary_protocol;
 pub mod position_updates;
 pub mod filter_auth;
 pub mod http_handler;
+pub mod camera_recording;
+pub mod layout_playback;
+pub mod handshake;
+pub mod panic_guard;
+pub mod layout_seed;
+pub mod layout_proposal;
+pub mod simulation_control;
+pub mod gestures;
+pub mod overview;
+pub mod node_budget;
+pub mod hibernation;
+pub mod settings_patch;
+pub mod language_filter;
+pub mod visit_summary;
+pub mod session_resume;
 
 // Re-export public API (preserves all external imports)
 pub use types::{PreReadSocketSettings, SocketFlowServer};
@@ -22,6 +37,11 @@ use log::{debug, error, info, warn};
 
 impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for SocketFlowServer {
     fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
+        // Browsers answer server pings on their own, so only frames the
+        // client chose to send keep the session awake.
+        if matches!(msg, Ok(ws::Message::Text(_) | ws::Message::Binary(_))) {
+            self.note_client_activity(ctx);
+        }
         match msg {
             Ok(ws::Message::Ping(msg)) => {
                 debug!("[WebSocket] Received standard ping");
@@ -32,10 +52,18 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for SocketFlowServer
                 self.last_activity = std::time::Instant::now();
             }
             Ok(ws::Message::Text(text)) => {
-                self.handle_text_message(&text, ctx);
+                self.guarded(
+                    ctx,
+                    || panic_guard::text_message_type(&text),
+                    |this, ctx| this.handle_text_message(&text, ctx),
+                );
             }
             Ok(ws::Message::Binary(data)) => {
-                self.handle_binary_message(&data, ctx);
+                self.guarded(
+                    ctx,
+                    || panic_guard::binary_message_type(&data),
+                    |this, ctx| this.handle_binary_message(&data, ctx),
+                );
             }
             Ok(ws::Message::Close(reason)) => {
                 info!("[WebSocket] Client initiated close: {:?}", reason);
@@ -0,0 +1,208 @@
+// Per-session node budget.
+//
+//   nodeBudget { maxNodes?: n | null, focusNodeId?: id | null }
+//     -> nodeBudget { maxNodes, focusNodeId }
+//
+// A missing field keeps its current value and `null` clears it. With a
+// budget set, the coordinator narrows this session's position broadcasts to
+// at most `maxNodes` nodes: the focus node's neighbourhood plus the most
+// important of the rest (see `actors::node_budget`). Clients send a new
+// `focusNodeId` as the user moves around; whenever the subset changes the
+// session gets a fresh initial graph load holding just that subset.
+
+use actix::prelude::*;
+use log::{info, warn};
+
+use crate::actors::messages::SetNodeBudget;
+
+use super::types::SocketFlowServer;
+
+/// Smallest budget worth streaming.
+pub const MIN_NODE_BUDGET: usize = 10;
+
+fn budget_error(ctx: &mut <SocketFlowServer as Actor>::Context, message: &str) {
+    let err = serde_json::json!({
+        "type": "error",
+        "code": "nodeBudgetRejected",
+        "message": message,
+    });
+    ctx.text(err.to_string());
+}
+
+/// `Some(None)` when `key` is `null`, `None` when it is absent.
+fn nullable_u64(body: &serde_json::Value, key: &str) -> Result<Option<Option<u64>>, String> {
+    match body.get(key) {
+        None => Ok(None),
+        Some(serde_json::Value::Null) => Ok(Some(None)),
+        Some(v) => v
+            .as_u64()
+            .map(|n| Some(Some(n)))
+            .ok_or_else(|| format!("{} must be a non-negative integer or null", key)),
+    }
+}
+
+/// The budget and focus after applying `msg` to the current ones.
+fn parse_budget_update(
+    msg: &serde_json::Value,
+    max_nodes: Option<usize>,
+    focus_node: Option<u32>,
+) -> Result<(Option<usize>, Option<u32>), String> {
+    let body = msg.get("data").unwrap_or(msg);
+    let max_nodes = match nullable_u64(body, "maxNodes")? {
+        None => max_nodes,
+        Some(None) => None,
+        Some(Some(n)) if (n as usize) < MIN_NODE_BUDGET => {
+            return Err(format!("maxNodes must be at least {}", MIN_NODE_BUDGET));
+        }
+        Some(Some(n)) => Some(n as usize),
+    };
+    let focus_node = match nullable_u64(body, "focusNodeId")? {
+        None => focus_node,
+        Some(None) => None,
+        Some(Some(id)) => {
+            Some(u32::try_from(id).map_err(|_| "focusNodeId is out of range".to_string())?)
+        }
+    };
+    Ok((max_nodes, focus_node))
+}
+
+pub(crate) fn handle_node_budget(
+    act: &mut SocketFlowServer,
+    msg: &serde_json::Value,
+    ctx: &mut <SocketFlowServer as Actor>::Context,
+) {
+    let Some(client_id) = act.client_id else {
+        budget_error(ctx, "Client registration in progress, please retry");
+        return;
+    };
+    let (max_nodes, focus_node) = match parse_budget_update(msg, act.node_budget, act.focus_node) {
+        Ok(update) => update,
+        Err(e) => {
+            budget_error(ctx, &e);
+            return;
+        }
+    };
+
+    let update = SetNodeBudget {
+        client_id,
+        max_nodes,
+        focus_node,
+    };
+    let fut = actix::fut::wrap_future::<_, SocketFlowServer>(act.client_manager_addr.send(update));
+    super::panic_guard::spawn_guarded(ctx, "nodeBudget", fut.map(move |result, act, ctx| match result {
+        Ok(Ok(())) => {
+            act.node_budget = max_nodes;
+            act.focus_node = focus_node;
+            info!(
+                "[WebSocket] Client {:?} node budget {:?}, focus {:?}",
+                act.client_id, max_nodes, focus_node
+            );
+            let reply = serde_json::json!({
+                "type": "nodeBudget",
+                "maxNodes": max_nodes,
+                "focusNodeId": focus_node,
+            });
+            ctx.text(reply.to_string());
+        }
+        Ok(Err(e)) => {
+            warn!(
+                "[WebSocket] Client {:?} node budget rejected: {}",
+                act.client_id, e
+            );
+            budget_error(ctx, &e);
+        }
+        Err(e) => {
+            warn!(
+                "[WebSocket] Client {:?} node budget failed: {}",
+                act.client_id, e
+            );
+            budget_error(ctx, "Client coordinator unavailable");
+        }
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn update(
+        msg: serde_json::Value,
+        max_nodes: Option<usize>,
+        focus_node: Option<u32>,
+    ) -> Result<(Option<usize>, Option<u32>), String> {
+        parse_budget_update(&msg, max_nodes, focus_node)
+    }
+
+    #[test]
+    fn reads_the_body_from_data_or_the_message_itself() {
+        assert_eq!(
+            update(json!({"data": {"maxNodes": 500}}), None, None),
+            Ok((Some(500), None))
+        );
+        assert_eq!(
+            update(json!({"maxNodes": 500}), None, None),
+            Ok((Some(500), None))
+        );
+    }
+
+    #[test]
+    fn missing_fields_keep_their_current_values() {
+        assert_eq!(
+            update(json!({"data": {"maxNodes": 500}}), None, Some(7)),
+            Ok((Some(500), Some(7)))
+        );
+        assert_eq!(
+            update(json!({"focusNodeId": 9}), Some(500), Some(7)),
+            Ok((Some(500), Some(9)))
+        );
+        assert_eq!(
+            update(json!({}), Some(500), Some(7)),
+            Ok((Some(500), Some(7)))
+        );
+    }
+
+    #[test]
+    fn null_clears_a_field() {
+        assert_eq!(
+            update(
+                json!({"maxNodes": null, "focusNodeId": null}),
+                Some(500),
+                Some(7)
+            ),
+            Ok((None, None))
+        );
+        assert_eq!(
+            update(json!({"focusNodeId": null}), Some(500), Some(7)),
+            Ok((Some(500), None))
+        );
+    }
+
+    #[test]
+    fn budget_must_reach_the_minimum() {
+        let min = MIN_NODE_BUDGET as u64;
+        assert_eq!(
+            update(json!({"maxNodes": min}), None, None),
+            Ok((Some(MIN_NODE_BUDGET), None))
+        );
+        assert!(update(json!({"maxNodes": min - 1}), None, None).is_err());
+        assert!(update(json!({"maxNodes": 0}), Some(500), None).is_err());
+    }
+
+    #[test]
+    fn non_integer_values_are_rejected() {
+        for value in [json!("lots"), json!(-5), json!(12.5), json!(true)] {
+            assert!(update(json!({"maxNodes": value}), None, None).is_err());
+            assert!(update(json!({"focusNodeId": value}), None, None).is_err());
+        }
+    }
+
+    #[test]
+    fn focus_id_must_fit_a_node_id() {
+        assert_eq!(
+            update(json!({"focusNodeId": u32::MAX}), None, None),
+            Ok((None, Some(u32::MAX)))
+        );
+        assert!(update(json!({"focusNodeId": 1u64 << 40}), None, None).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+// Overview mode: coarse frames for zoomed-out clients.
+//
+//   overview { enabled: true }   -> overview { enabled, nodeCount, nodes: [{ id, members }], edges: [[a, b, w]] }
+//   requestOverviewFrame         -> overviewFrame { nodes: [[coarseId, x, y, z], ...] }
+//   overview { enabled: false }  -> overview { enabled: false }
+//
+// While overview is on, live position frames to this session are withheld;
+// the client polls `requestOverviewFrame` at whatever rate it renders at and
+// gets one position per coarse node, the centroid of its members. Coarse ids
+// are numbered from 0 and overlap real node ids, so they travel in their own
+// `overviewFrame` text frame, never in a position frame. The coarse graph
+// itself is cached in `layout::coarsen` per graph revision.
+
+use actix::prelude::*;
+use log::{info, warn};
+use std::sync::Arc;
+
+use crate::actors::messages::GetGraphData;
+use crate::app_state::AppState;
+use crate::layout::coarsen::{overview, CoarseGraph};
+use crate::services::diff_journal_service::diff_journal;
+
+use super::types::SocketFlowServer;
+
+fn overview_error(ctx: &mut <SocketFlowServer as Actor>::Context, message: &str) {
+    let err = serde_json::json!({
+        "type": "error",
+        "code": "overviewUnavailable",
+        "message": message,
+    });
+    ctx.text(err.to_string());
+}
+
+/// Coarse graph for the current graph, with the current member positions.
+async fn current_overview(
+    app_state: Arc<AppState>,
+) -> Result<(Arc<CoarseGraph>, Vec<(u32, [f32; 3])>, usize), String> {
+    // Read before the graph: a change in between only costs a rebuild later.
+    let revision = diff_journal().revision();
+    let graph = app_state
+        .graph_service_addr
+        .send(GetGraphData)
+        .await
+        .map_err(|e| format!("Graph service unavailable: {}", e))??;
+    let node_count = graph.nodes.len();
+    let (coarse, positions) = actix_web::web::block(move || {
+        let coarse = overview(&graph, revision);
+        let positions = coarse.positions(
+            graph
+                .nodes
+                .iter()
+                .map(|n| (n.id, [n.data.x, n.data.y, n.data.z])),
+        );
+        (coarse, positions)
+    })
+    .await
+    .map_err(|e| format!("Failed to build overview: {}", e))?;
+    Ok((coarse, positions, node_count))
+}
+
+fn send_overview_frame(
+    ctx: &mut <SocketFlowServer as Actor>::Context,
+    positions: &[(u32, [f32; 3])],
+) {
+    let nodes: Vec<[serde_json::Value; 4]> = positions
+        .iter()
+        .map(|&(id, [x, y, z])| [id.into(), x.into(), y.into(), z.into()])
+        .collect();
+    ctx.text(serde_json::json!({ "type": "overviewFrame", "nodes": nodes }).to_string());
+}
+
+pub(crate) fn handle_overview(
+    act: &mut SocketFlowServer,
+    msg: &serde_json::Value,
+    ctx: &mut <SocketFlowServer as Actor>::Context,
+) {
+    let enabled = msg
+        .get("enabled")
+        .or_else(|| msg.get("data").and_then(|d| d.get("enabled")))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true);
+
+    if !enabled {
+        act.overview = false;
+        info!("[WebSocket] Client {:?} overview off", act.client_id);
+        ctx.text(serde_json::json!({ "type": "overview", "enabled": false }).to_string());
+        return;
+    }
+
+    let fut =
+        actix::fut::wrap_future::<_, SocketFlowServer>(current_overview(act.app_state.clone()));
+    super::panic_guard::spawn_guarded(ctx, "overview", fut.map(|result, act, ctx| match result {
+        Ok((coarse, positions, node_count)) => {
+            act.overview = true;
+            info!(
+                "[WebSocket] Client {:?} overview on: {} -> {} nodes",
+                act.client_id,
+                node_count,
+                coarse.nodes.len()
+            );
+            let reply = serde_json::json!({
+                "type": "overview",
+                "enabled": true,
+                "nodeCount": node_count,
+                "nodes": coarse.nodes,
+                "edges": coarse.edges,
+            });
+            ctx.text(reply.to_string());
+            send_overview_frame(ctx, &positions);
+        }
+        Err(e) => {
+            warn!(
+                "[WebSocket] Client {:?} overview failed: {}",
+                act.client_id, e
+            );
+            overview_error(ctx, &e);
+        }
+    }));
+}
+
+pub(crate) fn handle_request_overview_frame(
+    act: &mut SocketFlowServer,
+    ctx: &mut <SocketFlowServer as Actor>::Context,
+) {
+    if !act.overview {
+        overview_error(ctx, "Enable overview before requesting overview frames");
+        return;
+    }
+    let fut =
+        actix::fut::wrap_future::<_, SocketFlowServer>(current_overview(act.app_state.clone()));
+    super::panic_guard::spawn_guarded(ctx, "requestOverviewFrame", fut.map(|result, act, ctx| match result {
+        // The client may have left overview while the frame was built.
+        Ok((_, positions, _)) if act.overview => send_overview_frame(ctx, &positions),
+        Ok(_) => {}
+        Err(e) => overview_error(ctx, &e),
+    }));
+}
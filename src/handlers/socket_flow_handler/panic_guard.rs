@@ -0,0 +1,226 @@
+//! Panic isolation for client frames.
+//!
+//! A panic inside a frame handler would otherwise unwind out of the actor's
+//! task: the session dies without a close frame and `stopped()` never runs,
+//! so the coordinator keeps a dead recipient in its broadcast list. Frames
+//! are dispatched through [`SocketFlowServer::guarded`] instead, which logs
+//! the frame type that failed, tells the client, and keeps the session
+//! alive. A session that keeps panicking is closed cleanly.
+//!
+//! `catch_unwind` only covers the synchronous part of a handler. Work a
+//! handler hands to `ctx.spawn` runs later on the actor's task, so it is
+//! spawned through [`spawn_guarded`] and each poll, including the `.map`
+//! continuation that touches the actor, is contained the same way. Futures
+//! given to `actix::spawn` run as their own tasks; a panic there ends only
+//! that task and never reaches the session.
+
+use actix::prelude::*;
+use actix_web_actors::ws;
+use log::error;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::any::Any;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::task::{Context, Poll};
+
+use super::types::SocketFlowServer;
+
+/// Panics one session may absorb before it is closed.
+pub const MAX_SESSION_PANICS: u32 = 5;
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionPanicStats {
+    pub panics: u64,
+    /// Sessions closed after reaching [`MAX_SESSION_PANICS`].
+    pub sessions_closed: u64,
+    pub last_message_type: Option<String>,
+}
+
+#[derive(Default)]
+struct PanicCounters {
+    panics: AtomicU64,
+    sessions_closed: AtomicU64,
+    last_message_type: Mutex<Option<String>>,
+}
+
+static COUNTERS: Lazy<PanicCounters> = Lazy::new(PanicCounters::default);
+
+/// Process-wide handler panic counters for `/api/metrics`.
+pub fn session_panic_stats() -> SessionPanicStats {
+    SessionPanicStats {
+        panics: COUNTERS.panics.load(Ordering::Relaxed),
+        sessions_closed: COUNTERS.sessions_closed.load(Ordering::Relaxed),
+        last_message_type: COUNTERS
+            .last_message_type
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone(),
+    }
+}
+
+/// The `type` of a text frame, or a placeholder for frames without one.
+pub(crate) fn text_message_type(text: &str) -> String {
+    if text.trim() == "ping" {
+        return "ping".to_string();
+    }
+    match serde_json::from_str::<serde_json::Value>(text) {
+        Ok(msg) => msg
+            .get("type")
+            .and_then(|t| t.as_str())
+            .unwrap_or("untyped")
+            .to_string(),
+        Err(_) => "invalid-json".to_string(),
+    }
+}
+
+/// Binary frames carry no type name; the leading byte tells them apart.
+pub(crate) fn binary_message_type(data: &[u8]) -> String {
+    match data.first() {
+        Some(b) => format!("binary(0x{:02x}, {} bytes)", b, data.len()),
+        None => "binary(empty)".to_string(),
+    }
+}
+
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s
+    } else {
+        "non-string panic payload"
+    }
+}
+
+impl SocketFlowServer {
+    /// Run a frame handler, containing any panic to this frame. `message_type`
+    /// is only evaluated if the handler panics.
+    pub(crate) fn guarded(
+        &mut self,
+        ctx: &mut <Self as Actor>::Context,
+        message_type: impl FnOnce() -> String,
+        handler: impl FnOnce(&mut Self, &mut <Self as Actor>::Context),
+    ) {
+        let payload = match catch_unwind(AssertUnwindSafe(|| handler(self, ctx))) {
+            Ok(()) => return,
+            Err(payload) => payload,
+        };
+        self.report_panic(ctx, message_type(), payload);
+    }
+
+    /// Log and count a contained panic, tell the client, and close the
+    /// session once it has absorbed [`MAX_SESSION_PANICS`].
+    fn report_panic(
+        &mut self,
+        ctx: &mut <Self as Actor>::Context,
+        message_type: String,
+        payload: Box<dyn Any + Send>,
+    ) {
+        self.handler_panics += 1;
+        COUNTERS.panics.fetch_add(1, Ordering::Relaxed);
+        *COUNTERS
+            .last_message_type
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(message_type.clone());
+        error!(
+            "[WebSocket] Handler panicked on '{}' from client {:?} ({}): {}",
+            message_type,
+            self.client_id,
+            self.client_ip,
+            panic_message(payload.as_ref())
+        );
+
+        let recoverable = self.handler_panics < MAX_SESSION_PANICS;
+        let error_msg = serde_json::json!({
+            "type": "error",
+            "code": "handlerPanic",
+            "messageType": message_type,
+            "message": "Internal error while handling message",
+            "recoverable": recoverable
+        });
+        ctx.text(error_msg.to_string());
+
+        if !recoverable {
+            COUNTERS.sessions_closed.fetch_add(1, Ordering::Relaxed);
+            error!(
+                "[WebSocket] Closing client {:?} after {} handler panics",
+                self.client_id, self.handler_panics
+            );
+            ctx.close(Some(ws::CloseReason {
+                code: ws::CloseCode::Error,
+                description: Some("Repeated internal errors".to_string()),
+            }));
+            ctx.stop();
+        }
+    }
+}
+
+/// Future spawned from a frame handler; a panic while polling it is
+/// reported like one in the handler and completes the future.
+pub(crate) struct Guarded<F> {
+    inner: Pin<Box<F>>,
+    message_type: &'static str,
+}
+
+impl<F> ActorFuture<SocketFlowServer> for Guarded<F>
+where
+    F: ActorFuture<SocketFlowServer, Output = ()>,
+{
+    type Output = ();
+
+    fn poll(
+        mut self: Pin<&mut Self>,
+        srv: &mut SocketFlowServer,
+        ctx: &mut <SocketFlowServer as Actor>::Context,
+        task: &mut Context<'_>,
+    ) -> Poll<()> {
+        let message_type = self.message_type;
+        let inner = &mut self.inner;
+        match catch_unwind(AssertUnwindSafe(|| inner.as_mut().poll(srv, ctx, task))) {
+            Ok(poll) => poll,
+            Err(payload) => {
+                srv.report_panic(ctx, message_type.to_string(), payload);
+                Poll::Ready(())
+            }
+        }
+    }
+}
+
+/// `ctx.spawn` for work started by a frame handler. `message_type` names
+/// the frame in the panic report.
+pub(crate) fn spawn_guarded<F>(
+    ctx: &mut <SocketFlowServer as Actor>::Context,
+    message_type: &'static str,
+    fut: F,
+) -> SpawnHandle
+where
+    F: ActorFuture<SocketFlowServer, Output = ()> + 'static,
+{
+    ctx.spawn(Guarded {
+        inner: Box::pin(fut),
+        message_type,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_the_failing_frame_and_panic() {
+        assert_eq!(text_message_type(r#"{"type":"nodeDragStart"}"#), "nodeDragStart");
+        assert_eq!(text_message_type(r#"{"data":1}"#), "untyped");
+        assert_eq!(text_message_type("{oops"), "invalid-json");
+        assert_eq!(text_message_type(" ping "), "ping");
+        assert_eq!(binary_message_type(&[0x03, 0, 0]), "binary(0x03, 3 bytes)");
+        assert_eq!(binary_message_type(&[]), "binary(empty)");
+
+        let payload = catch_unwind(|| panic!("index {} out of range", 7)).unwrap_err();
+        assert_eq!(panic_message(payload.as_ref()), "index 7 out of range");
+        let payload = catch_unwind(|| std::panic::panic_any(42)).unwrap_err();
+        assert_eq!(panic_message(payload.as_ref()), "non-string panic payload");
+    }
+}
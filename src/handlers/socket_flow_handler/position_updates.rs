@@ -3,6 +3,7 @@ use actix_web_actors::ws;
 use log::{debug, info, trace, warn};
 use std::time::Instant;
 
+use crate::protocol::graph_json::{EdgeJson, NodePositionJson};
 use crate::utils::binary_protocol;
 use crate::utils::socket_flow_messages::{BinaryNodeData, BinaryNodeDataClient};
 use crate::utils::validation::rate_limit::EndpointRateLimits;
@@ -20,7 +21,7 @@ const MIN_DRAG_INTERVAL_MS: u64 = 16;
 
 /// Validate that a position is finite and within sane world-space bounds.
 /// Returns `None` for NaN, Infinity, or out-of-range values (VULN-05).
-fn sanitize_position(x: f32, y: f32, z: f32) -> Option<(f32, f32, f32)> {
+pub(crate) fn sanitize_position(x: f32, y: f32, z: f32) -> Option<(f32, f32, f32)> {
     const MAX_BOUND: f32 = 10000.0;
     if x.is_finite() && y.is_finite() && z.is_finite()
         && x.abs() <= MAX_BOUND && y.abs() <= MAX_BOUND && z.abs() <= MAX_BOUND
@@ -31,6 +32,24 @@ fn sanitize_position(x: f32, y: f32, z: f32) -> Option<(f32, f32, f32)> {
     }
 }
 
+/// Withhold `node_ids` from this session's position broadcasts for the
+/// configured window. The next physics broadcast still carries the position
+/// the server had before the client's update landed, and echoing it back
+/// rubber-bands the node under the user's cursor.
+pub(crate) fn suppress_echo(act: &SocketFlowServer, node_ids: Vec<u32>) {
+    if act.echo_suppression_ms == 0 || node_ids.is_empty() {
+        return;
+    }
+    if let Some(client_id) = act.client_id {
+        use crate::actors::messages::SuppressPositionEcho;
+        act.client_manager_addr.do_send(SuppressPositionEcho {
+            client_id,
+            node_ids,
+            window_ms: act.echo_suppression_ms,
+        });
+    }
+}
+
 /// Fetch nodes from the graph service for streaming position updates.
 ///
 /// Pre-flags all node IDs with their type (agent, knowledge, ontology) so that
@@ -207,7 +226,7 @@ pub(crate) fn handle_request_full_snapshot(
     };
 
     let fut = actix::fut::wrap_future::<_, SocketFlowServer>(fut);
-    ctx.spawn(fut.map(move |snapshot, _act, ctx| {
+    super::panic_guard::spawn_guarded(ctx, "request_full_snapshot", fut.map(move |snapshot, _act, ctx| {
         let mut all_nodes = Vec::new();
 
         // IDs were already stamped with the correct type flag when the snapshot
@@ -228,7 +247,7 @@ pub(crate) fn handle_request_full_snapshot(
             let sssp = _act.app_state.node_sssp.read().ok();
             let sssp_ref = sssp.as_deref();
             let binary_data = binary_protocol::encode_node_data_with_live_analytics(&all_nodes, analytics_ref, sssp_ref);
-            ctx.binary(binary_data);
+            _act.send_positions(ctx, &all_nodes, binary_data);
             debug!("Sent position snapshot with {} nodes", all_nodes.len());
         }
     }));
@@ -269,7 +288,7 @@ pub(crate) fn handle_request_bots_graph(
 
     let graph_addr = act.app_state.graph_service_addr.clone();
 
-    ctx.spawn(
+    super::panic_guard::spawn_guarded(ctx, "requestBotsGraph",
         actix::fut::wrap_future::<_, SocketFlowServer>(async move {
             use crate::actors::messages::GetBotsGraphData;
             match graph_addr.send(GetBotsGraphData).await {
@@ -277,37 +296,12 @@ pub(crate) fn handle_request_bots_graph(
                 _ => None,
             }
         })
-        .map(|graph_data_opt, _act, ctx| {
+        .map(|graph_data_opt, act, ctx| {
             if let Some(graph_data) = graph_data_opt {
-                let minimal_nodes: Vec<serde_json::Value> = graph_data
-                    .nodes
-                    .iter()
-                    .map(|node| {
-                        serde_json::json!({
-                            "id": node.id,
-                            "metadata_id": node.metadata_id,
-                            "x": node.data.x,
-                            "y": node.data.y,
-                            "z": node.data.z,
-                            "vx": node.data.vx,
-                            "vy": node.data.vy,
-                            "vz": node.data.vz
-                        })
-                    })
-                    .collect();
-
-                let minimal_edges: Vec<serde_json::Value> = graph_data
-                    .edges
-                    .iter()
-                    .map(|edge| {
-                        serde_json::json!({
-                            "id": edge.id,
-                            "source": edge.source,
-                            "target": edge.target,
-                            "weight": edge.weight
-                        })
-                    })
-                    .collect();
+                let schema = act.protocol.json_schema;
+                let minimal_nodes =
+                    schema.encode_all(graph_data.nodes.iter().map(NodePositionJson::from));
+                let minimal_edges = schema.encode_all(graph_data.edges.iter().map(EdgeJson::from));
 
                 let response = serde_json::json!({
                     "type": "botsGraphUpdate",
@@ -372,7 +366,7 @@ pub(crate) fn handle_request_bots_positions(
 
     let app_state = act.app_state.clone();
 
-    ctx.spawn(
+    super::panic_guard::spawn_guarded(ctx, "requestBotsPositions",
         actix::fut::wrap_future::<_, SocketFlowServer>(async move {
             let bots_nodes =
                 crate::handlers::bots_handler::get_bots_positions(&app_state.bots_client).await;
@@ -416,7 +410,7 @@ pub(crate) fn handle_request_bots_positions(
                     binary_data.len()
                 );
 
-                ctx.binary(binary_data);
+                _act.send_positions(ctx, &nodes_data, binary_data);
             }
         }),
     );
@@ -519,7 +513,7 @@ pub(crate) fn handle_subscribe_position_updates(
         let fut = fetch_nodes(app_state.clone(), settings_addr.clone());
         let fut = actix::fut::wrap_future::<_, SocketFlowServer>(fut);
 
-        ctx.spawn(fut.map(move |result, act, ctx| {
+        super::panic_guard::spawn_guarded(ctx, "subscribe_position_updates", fut.map(move |result, act, ctx| {
             // A newer subscribe superseded this loop while the fetch was in flight;
             // drop the result so only the latest loop broadcasts (no duplicate frames).
             if act.position_sub_generation != my_generation {
@@ -587,7 +581,7 @@ pub(crate) fn handle_subscribe_position_updates(
                     );
                 }
 
-                ctx.binary(binary_data);
+                act.send_positions(ctx, &nodes, binary_data);
 
                 let next_interval = std::time::Duration::from_millis(actual_interval);
                 ctx.run_later(next_interval, move |act, ctx| {
@@ -645,7 +639,7 @@ pub(crate) fn handle_request_swarm_telemetry(
 
     let app_state = act.app_state.clone();
 
-    ctx.spawn(
+    super::panic_guard::spawn_guarded(ctx, "requestSwarmTelemetry",
         actix::fut::wrap_future::<_, SocketFlowServer>(async move {
             match crate::handlers::bots_handler::fetch_hive_mind_agents(&app_state, None).await {
                 Ok(agents) => {
@@ -702,7 +696,7 @@ pub(crate) fn handle_request_swarm_telemetry(
                 let sssp = _act.app_state.node_sssp.read().ok();
                 let sssp_ref = sssp.as_deref();
                 let binary_data = binary_protocol::encode_node_data_with_live_analytics(&nodes_data, analytics_ref, sssp_ref);
-                ctx.binary(binary_data);
+                _act.send_positions(ctx, &nodes_data, binary_data);
             }
 
             let telemetry_response = serde_json::json!({
@@ -785,6 +779,7 @@ pub(crate) fn handle_node_drag_start(
     // Track drag state on this connection
     act.dragged_nodes.insert(node_id);
     act.drag_last_update.insert(node_id, Instant::now());
+    suppress_echo(act, vec![node_id]);
 
     // Pin the node at client position + notify physics to resume if paused
     let app_state = act.app_state.clone();
@@ -815,7 +810,7 @@ pub(crate) fn handle_node_drag_start(
         });
     };
 
-    ctx.spawn(actix::fut::wrap_future::<_, SocketFlowServer>(fut).map(|_, _, _| ()));
+    super::panic_guard::spawn_guarded(ctx, "nodeDragStart", actix::fut::wrap_future::<_, SocketFlowServer>(fut).map(|_, _, _| ()));
 
     // Acknowledge to the client
     let ack = serde_json::json!({
@@ -910,6 +905,7 @@ pub(crate) fn handle_node_drag_update(
 
     // Update the drag timeout tracker
     act.drag_last_update.insert(node_id, Instant::now());
+    suppress_echo(act, vec![node_id]);
 
     let app_state = act.app_state.clone();
     let client_manager_addr = act.client_manager_addr.clone();
@@ -917,7 +913,14 @@ pub(crate) fn handle_node_drag_update(
     let fut = async move {
         let settle_start = Instant::now();
 
-        // 1. Move the pinned node to the new client-reported position (velocity zeroed)
+        // 1. Move the pinned node to the new client-reported position (velocity zeroed).
+        //    Physics samples the drag too, for the configured release behaviour.
+        use crate::actors::messages::{NodeInteractionMessage, NodeInteractionType};
+        app_state.graph_service_addr.do_send(NodeInteractionMessage {
+            node_id,
+            interaction_type: NodeInteractionType::Dragged,
+            position: Some([pos_x, pos_y, pos_z]),
+        });
         use crate::actors::messages::UpdateNodePositions;
         let pinned_data = BinaryNodeData {
             node_id,
@@ -998,13 +1001,15 @@ pub(crate) fn handle_node_drag_update(
         }
     };
 
-    ctx.spawn(actix::fut::wrap_future::<_, SocketFlowServer>(fut).map(|_, _act, _ctx| {}));
+    super::panic_guard::spawn_guarded(ctx, "nodeDragUpdate", actix::fut::wrap_future::<_, SocketFlowServer>(fut).map(|_, _act, _ctx| {}));
 }
 
 /// Handle `nodeDragEnd` from client.
 ///
 /// Unpins the node, runs one final settle cycle with the node free, and
-/// broadcasts the resulting positions to all clients.
+/// broadcasts the resulting positions to all clients. What the node does
+/// next (glide, spring back, stay pinned) is decided by the physics
+/// orchestrator from `physics.dragRelease`, so every client sees the same.
 ///
 /// Expected message shape:
 /// ```json
@@ -1048,7 +1053,8 @@ pub(crate) fn handle_node_drag_end(
     let client_manager_addr = act.client_manager_addr.clone();
 
     let fut = async move {
-        // 1. Notify physics that the drag interaction ended (node released)
+        // 1. Notify physics that the drag interaction ended (node released);
+        //    it applies the configured release behaviour
         use crate::actors::messages::{NodeInteractionMessage, NodeInteractionType};
         app_state.graph_service_addr.do_send(NodeInteractionMessage {
             node_id,
@@ -1120,7 +1126,7 @@ pub(crate) fn handle_node_drag_end(
         }
     };
 
-    ctx.spawn(actix::fut::wrap_future::<_, SocketFlowServer>(fut).map(|_, _act, _ctx| {}));
+    super::panic_guard::spawn_guarded(ctx, "nodeDragEnd", actix::fut::wrap_future::<_, SocketFlowServer>(fut).map(|_, _act, _ctx| {}));
 
     // Acknowledge drag end
     let ack = serde_json::json!({
@@ -1133,6 +1139,59 @@ pub(crate) fn handle_node_drag_end(
     }
 }
 
+/// Handle `requestAuthority` from a local-physics client.
+///
+/// ```json
+/// { "type": "requestAuthority", "data": { "workspace": "default" } }
+/// ```
+///
+/// The coordinator replies `authority_granted` or `authority_denied`.
+pub(crate) fn handle_request_authority(
+    act: &mut SocketFlowServer,
+    msg: &serde_json::Value,
+    ctx: &mut <SocketFlowServer as Actor>::Context,
+) {
+    use super::handshake::LOCAL_PHYSICS_CAPABILITY;
+
+    let reason = if act.pubkey.is_none() {
+        Some("Physics authority requires authentication")
+    } else if !act.protocol.has(LOCAL_PHYSICS_CAPABILITY) {
+        Some("Negotiate the localPhysics capability via hello first")
+    } else {
+        None
+    };
+    let (Some(client_id), None) = (act.client_id, reason) else {
+        let deny = serde_json::json!({
+            "type": "authority_denied",
+            "message": reason.unwrap_or("Client is not registered yet"),
+        });
+        ctx.text(deny.to_string());
+        return;
+    };
+
+    let workspace = msg
+        .get("data")
+        .and_then(|d| d.get("workspace"))
+        .and_then(|w| w.as_str())
+        .unwrap_or_default()
+        .to_string();
+
+    use crate::actors::messages::RequestPhysicsAuthority;
+    act.client_manager_addr.do_send(RequestPhysicsAuthority {
+        client_id,
+        workspace,
+    });
+}
+
+/// Handle `releaseAuthority`; the coordinator confirms with `authority_revoked`.
+pub(crate) fn handle_release_authority(act: &mut SocketFlowServer) {
+    if let Some(client_id) = act.client_id {
+        use crate::actors::messages::ReleasePhysicsAuthority;
+        act.client_manager_addr
+            .do_send(ReleasePhysicsAuthority { client_id });
+    }
+}
+
 /// Periodic timeout checker: if no drag update has been received for a node
 /// within `drag_timeout_ms`, automatically unpin it (safety net for dropped
 /// connections or missed dragEnd messages).
@@ -0,0 +1,96 @@
+// Resuming a dropped session without the full initial payload.
+//
+//   connection_established { ..., resumeToken, revision, resumed, resumeRejected? }
+//
+// Clients keep `resumeToken` and the `revision` of the last `graphDiff` they
+// applied. After a network blip they reconnect with
+//
+//   /wss?resume=<resumeToken>&revision=<revision>
+//
+// and, within `diff_journal_service::RESUME_WINDOW`, get only what they missed:
+//
+//   sessionResumed { fromRevision, toRevision, diffs: [graphDiff, ...] }
+//
+// followed by a binary position snapshot, instead of state_sync and the
+// initial graph load. Otherwise `resumeRejected` says why ("unknownToken",
+// "expired" or "gap") and the full payload is sent as usual. The resumed
+// session keeps its token.
+
+use actix::prelude::*;
+use log::info;
+use std::time::Instant;
+
+use crate::services::diff_journal_service::{diff_journal, Replay, ResumeRejected};
+
+use super::types::SocketFlowServer;
+
+/// `?resume=` and `?revision=` from the connection URL.
+pub(crate) fn parse_resume_query(query: &str) -> Option<(String, Option<u64>)> {
+    let mut token = None;
+    let mut revision = None;
+    for (k, v) in url::form_urlencoded::parse(query.as_bytes()) {
+        match k.as_ref() {
+            "resume" if !v.is_empty() && v.len() <= 64 => token = Some(v.into_owned()),
+            "revision" => revision = v.parse().ok(),
+            _ => {}
+        }
+    }
+    token.map(|t| (t, revision))
+}
+
+impl SocketFlowServer {
+    /// Take up the session named in the connection URL, if asked to. On
+    /// success the session adopts the old token.
+    pub(crate) fn try_resume(&mut self) -> Option<Result<Replay, ResumeRejected>> {
+        let (token, revision) = self.resume_request.take()?;
+        let result = diff_journal().resume(&token, revision, Instant::now());
+        if result.is_ok() {
+            self.resume_token = token;
+        }
+        Some(result)
+    }
+
+    /// Send the missed diffs and a fresh position snapshot.
+    pub(crate) fn send_replay(&mut self, replay: Replay, ctx: &mut <Self as Actor>::Context) {
+        info!(
+            "[WebSocket] Resumed session at revision {}, replaying {} diff(s)",
+            replay.from_revision,
+            replay.diffs.len()
+        );
+        let mut message = serde_json::to_value(&replay).unwrap_or_default();
+        message["type"] = "sessionResumed".into();
+        ctx.text(message.to_string());
+        super::position_updates::handle_request_full_snapshot(self, &serde_json::json!({}), ctx);
+    }
+
+    /// Park the session so a reconnect can resume it. A hibernating session
+    /// was withheld diffs, so it is left to reload in full.
+    pub(crate) fn park_session(&self) {
+        if !self.hibernating {
+            diff_journal().park(
+                &self.resume_token,
+                diff_journal().revision(),
+                Instant::now(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_resume_query() {
+        assert_eq!(
+            parse_resume_query("token=x&resume=abc&revision=12"),
+            Some(("abc".to_string(), Some(12)))
+        );
+        assert_eq!(
+            parse_resume_query("resume=abc&revision=soon"),
+            Some(("abc".to_string(), None))
+        );
+        assert_eq!(parse_resume_query("revision=12"), None);
+        assert_eq!(parse_resume_query("resume="), None);
+    }
+}
@@ -0,0 +1,117 @@
+// Settings writes over the WebSocket.
+//
+//   settingsPatch { section: "physics" | "rendering" | "visual", values: {...} }
+//     -> settingsPatch { section, values }
+//
+// `values` is a partial update applied exactly as the matching
+// PUT /api/settings/{section} would apply it, validation included; the reply
+// carries the section as it now stands. Only authenticated sessions may
+// write. Each change lands in the settings history attributed to the
+// session's pubkey with source "websocket".
+
+use actix::prelude::*;
+use log::{info, warn};
+
+use crate::services::settings_history_service::{ChangeOrigin, ChangeSource};
+use crate::settings::api::settings_routes::apply_settings_patch;
+
+use super::types::SocketFlowServer;
+
+fn patch_error(ctx: &mut <SocketFlowServer as Actor>::Context, message: &str) {
+    let err = serde_json::json!({
+        "type": "error",
+        "code": "settingsPatchRejected",
+        "message": message,
+    });
+    ctx.text(err.to_string());
+}
+
+fn parse_patch(msg: &serde_json::Value) -> Result<(String, serde_json::Value), String> {
+    let body = msg.get("data").unwrap_or(msg);
+    let section = body
+        .get("section")
+        .and_then(|s| s.as_str())
+        .ok_or("section is required")?;
+    let values = body
+        .get("values")
+        .filter(|v| v.is_object())
+        .ok_or("values must be an object")?;
+    Ok((section.to_string(), values.clone()))
+}
+
+pub(crate) fn handle_settings_patch(
+    act: &mut SocketFlowServer,
+    msg: &serde_json::Value,
+    ctx: &mut <SocketFlowServer as Actor>::Context,
+) {
+    let Some(pubkey) = act.pubkey.clone() else {
+        patch_error(ctx, "Authenticate before changing settings");
+        return;
+    };
+    let (section, values) = match parse_patch(msg) {
+        Ok(patch) => patch,
+        Err(e) => {
+            patch_error(ctx, &e);
+            return;
+        }
+    };
+
+    let app_state = act.app_state.clone();
+    let fut = async move {
+        let origin = ChangeOrigin::new(pubkey, ChangeSource::WebSocket);
+        let applied = apply_settings_patch(
+            &app_state,
+            &app_state.sqlite_settings_repository,
+            &section,
+            values,
+            &origin,
+        )
+        .await;
+        (section, applied)
+    };
+
+    let fut = actix::fut::wrap_future::<_, SocketFlowServer>(fut);
+    super::panic_guard::spawn_guarded(ctx, "settingsPatch", fut.map(|(section, result), act, ctx| match result {
+        Ok(values) => {
+            info!(
+                "[WebSocket] Client {:?} patched {} settings",
+                act.client_id, section
+            );
+            let reply = serde_json::json!({
+                "type": "settingsPatch",
+                "section": section,
+                "values": values,
+            });
+            ctx.text(reply.to_string());
+        }
+        Err(e) => {
+            warn!(
+                "[WebSocket] Client {:?} {} settings patch failed: {}",
+                act.client_id,
+                section,
+                e.message()
+            );
+            patch_error(ctx, e.message());
+        }
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_section_and_values() {
+        assert_eq!(
+            parse_patch(&json!({"section": "physics", "values": {"springK": 0.2}})),
+            Ok(("physics".to_string(), json!({"springK": 0.2})))
+        );
+        assert_eq!(
+            parse_patch(&json!({"data": {"section": "visual", "values": {}}})),
+            Ok(("visual".to_string(), json!({})))
+        );
+        assert!(parse_patch(&json!({"values": {}})).is_err());
+        assert!(parse_patch(&json!({"section": "physics", "values": 3})).is_err());
+    }
+}
@@ -0,0 +1,194 @@
+// Operator pause / resume / step over the WebSocket, mirroring
+// POST /api/simulation/{pause,resume,step}.
+//
+//   simulationControl { action: "pause" | "resume" | "step", steps?: N }
+//     -> simulationControl { mode, remaining?, singleSteps }
+//
+// Quality level, mirroring POST /api/simulation/quality:
+//
+//   simulationQuality { level: "low" | "medium" | "high" }
+//     -> simulationQuality { level, tickHz, collision, readbackEvery }
+//
+// The simulation is shared, so only power users may control it.
+
+use actix::prelude::*;
+use log::{info, warn};
+
+use crate::actors::messages::ControlSimulation;
+use crate::handlers::physics_handler::apply_quality;
+use crate::physics::quality::QualityLevel;
+use crate::physics::simulation_control::{SimulationCommand, MAX_STEPS_PER_REQUEST};
+
+use super::types::SocketFlowServer;
+
+fn control_error(ctx: &mut <SocketFlowServer as Actor>::Context, message: &str) {
+    let err = serde_json::json!({
+        "type": "error",
+        "code": "simulationControlRejected",
+        "message": message,
+    });
+    ctx.text(err.to_string());
+}
+
+fn parse_command(msg: &serde_json::Value) -> Result<SimulationCommand, String> {
+    let body = msg.get("data").unwrap_or(msg);
+    match body.get("action").and_then(|a| a.as_str()) {
+        Some("pause") => Ok(SimulationCommand::Pause),
+        Some("resume") => Ok(SimulationCommand::Resume),
+        Some("step") => {
+            let steps = match body.get("steps") {
+                None => 1,
+                Some(v) => v
+                    .as_u64()
+                    .and_then(|n| u32::try_from(n).ok())
+                    .ok_or("steps must be a positive integer")?,
+            };
+            if steps == 0 || steps > MAX_STEPS_PER_REQUEST {
+                return Err(format!(
+                    "steps must be between 1 and {}",
+                    MAX_STEPS_PER_REQUEST
+                ));
+            }
+            Ok(SimulationCommand::Step { steps })
+        }
+        other => Err(format!(
+            "Unknown simulationControl action {:?}; expected pause, resume or step",
+            other
+        )),
+    }
+}
+
+pub(crate) fn handle_simulation_control(
+    act: &mut SocketFlowServer,
+    msg: &serde_json::Value,
+    ctx: &mut <SocketFlowServer as Actor>::Context,
+) {
+    if !act.is_power_user {
+        control_error(ctx, "Only a power user can pause or step the simulation");
+        return;
+    }
+    let command = match parse_command(msg) {
+        Ok(command) => command,
+        Err(e) => {
+            control_error(ctx, &e);
+            return;
+        }
+    };
+
+    let graph_addr = act.app_state.graph_service_addr.clone();
+    let fut = async move {
+        graph_addr
+            .send(ControlSimulation { command })
+            .await
+            .map_err(|e| format!("Graph service unavailable: {}", e))?
+    };
+
+    let fut = actix::fut::wrap_future::<_, SocketFlowServer>(fut);
+    super::panic_guard::spawn_guarded(ctx, "simulationControl", fut.map(move |result, act, ctx| match result {
+        Ok(status) => {
+            info!(
+                "[WebSocket] Client {:?} simulation control {:?} -> {:?}",
+                act.client_id, command, status.mode
+            );
+            let mut reply = serde_json::to_value(status).unwrap_or_default();
+            reply["type"] = serde_json::Value::from("simulationControl");
+            ctx.text(reply.to_string());
+        }
+        Err(e) => {
+            warn!(
+                "[WebSocket] Client {:?} simulation control failed: {}",
+                act.client_id, e
+            );
+            control_error(ctx, &e);
+        }
+    }));
+}
+
+fn parse_quality(msg: &serde_json::Value) -> Result<QualityLevel, String> {
+    let body = msg.get("data").unwrap_or(msg);
+    body.get("level")
+        .and_then(|l| l.as_str())
+        .ok_or("simulationQuality needs a level: low, medium or high")?
+        .parse()
+}
+
+pub(crate) fn handle_simulation_quality(
+    act: &mut SocketFlowServer,
+    msg: &serde_json::Value,
+    ctx: &mut <SocketFlowServer as Actor>::Context,
+) {
+    if !act.is_power_user {
+        control_error(ctx, "Only a power user can change the simulation quality");
+        return;
+    }
+    let level = match parse_quality(msg) {
+        Ok(level) => level,
+        Err(e) => {
+            control_error(ctx, &e);
+            return;
+        }
+    };
+
+    let app_state = act.app_state.clone();
+    let fut = async move { apply_quality(&app_state, level).await };
+
+    let fut = actix::fut::wrap_future::<_, SocketFlowServer>(fut);
+    super::panic_guard::spawn_guarded(ctx, "simulationQuality", fut.map(move |result, act, ctx| match result {
+        Ok(preset) => {
+            info!(
+                "[WebSocket] Client {:?} set simulation quality {:?}",
+                act.client_id, preset.level
+            );
+            let mut reply = serde_json::to_value(preset).unwrap_or_default();
+            reply["type"] = serde_json::Value::from("simulationQuality");
+            ctx.text(reply.to_string());
+        }
+        Err(e) => {
+            warn!(
+                "[WebSocket] Client {:?} simulation quality failed: {}",
+                act.client_id, e
+            );
+            control_error(ctx, &e);
+        }
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_actions() {
+        let parse = |v: serde_json::Value| parse_command(&v);
+        assert_eq!(
+            parse(json!({"type": "simulationControl", "action": "pause"})),
+            Ok(SimulationCommand::Pause)
+        );
+        assert_eq!(
+            parse(json!({"data": {"action": "step"}})),
+            Ok(SimulationCommand::Step { steps: 1 })
+        );
+        assert_eq!(
+            parse(json!({"action": "step", "steps": 5})),
+            Ok(SimulationCommand::Step { steps: 5 })
+        );
+        assert!(parse(json!({"action": "step", "steps": 0})).is_err());
+        assert!(parse(json!({"action": "step", "steps": -1})).is_err());
+        assert!(parse(json!({"action": "rewind"})).is_err());
+    }
+
+    #[test]
+    fn parses_quality_levels() {
+        assert_eq!(
+            parse_quality(&json!({"type": "simulationQuality", "level": "low"})),
+            Ok(QualityLevel::Low)
+        );
+        assert_eq!(
+            parse_quality(&json!({"data": {"level": "high"}})),
+            Ok(QualityLevel::High)
+        );
+        assert!(parse_quality(&json!({"level": "ultra"})).is_err());
+        assert!(parse_quality(&json!({})).is_err());
+    }
+}
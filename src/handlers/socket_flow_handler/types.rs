@@ -1,6 +1,6 @@
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use actix::prelude::*;
 use actix_web_actors::ws;
@@ -37,6 +37,8 @@ pub struct PreReadSocketSettings {
     pub motion_damping: f32,
     pub heartbeat_interval_ms: u64,
     pub heartbeat_timeout_ms: u64,
+    pub echo_suppression_ms: u64,
+    pub idle_hibernation_ms: u64,
 }
 
 #[allow(dead_code)]
@@ -92,6 +94,9 @@ pub struct SocketFlowServer {
     pub(crate) drag_last_update: HashMap<u32, Instant>,
     /// Maximum time (ms) with no position update before auto-unpin. Default 500ms.
     pub(crate) drag_timeout_ms: u64,
+    /// How long (ms) nodes this client moved are withheld from its own
+    /// position broadcasts. 0 disables echo suppression.
+    pub(crate) echo_suppression_ms: u64,
 
     // FIX 6: Per-client node type filter for binary position stream.
     // When non-empty, only nodes whose type matches one of these strings
@@ -111,6 +116,54 @@ pub struct SocketFlowServer {
     /// ADR-031 item 4: Pending server-to-client directives embedded in pong frames.
     /// Drained on each `send_pong` call via the `WebSocketHeartbeat` trait override.
     pub(crate) pending_directives: Vec<HeartbeatDirective>,
+
+    /// Active opt-in camera recording started by this client, if any.
+    pub(crate) camera_recording: Option<String>,
+
+    /// Protocol settings negotiated via `hello`; legacy defaults until then.
+    pub(crate) protocol: super::handshake::SessionProtocol,
+
+    /// Keyframe time the client is viewing during layout playback. While
+    /// set, live position frames are withheld from this session.
+    pub(crate) layout_playback: Option<i64>,
+
+    /// Frame handler panics absorbed so far; see `panic_guard`.
+    pub(crate) handler_panics: u32,
+
+    /// Set once the client has sent `seedLayout`; it may only seed once.
+    pub(crate) layout_seeded: bool,
+
+    /// XR device named via `?deviceId=` or `hello`, for its room anchor.
+    pub(crate) xr_device_id: Option<String>,
+
+    /// Node held by an in-progress drag gesture; see `gestures`.
+    pub(crate) gesture_drag: Option<super::gestures::GestureDrag>,
+
+    /// Gesture `start`s still resolving their target, cancelled by an `end`
+    /// that overtakes them.
+    pub(crate) gesture_starts: HashMap<super::gestures::GestureKind, SpawnHandle>,
+
+    /// Zoomed-out client asking for coarse frames; live position frames are
+    /// withheld while set. See `overview`.
+    pub(crate) overview: bool,
+
+    /// Node budget and focus last accepted by the coordinator; see `node_budget`.
+    pub(crate) node_budget: Option<usize>,
+    pub(crate) focus_node: Option<u32>,
+
+    /// Idle time after which the session hibernates; zero disables. See
+    /// `hibernation`.
+    pub(crate) idle_hibernation: Duration,
+    /// Last frame received from the client. Unlike `last_activity`, server
+    /// sends do not touch it.
+    pub(crate) last_client_activity: Instant,
+    pub(crate) hibernating: bool,
+
+    /// Token a reconnecting client presents to resume this session; see
+    /// `session_resume`.
+    pub(crate) resume_token: String,
+    /// `?resume=` token and last-seen revision from the connection URL.
+    pub(crate) resume_request: Option<(String, Option<u64>)>,
 }
 
 impl SocketFlowServer {
@@ -126,6 +179,7 @@ impl SocketFlowServer {
         let max_update_rate = pre_read_settings.max_update_rate;
         let motion_threshold = pre_read_settings.motion_threshold;
         let motion_damping = pre_read_settings.motion_damping;
+        let echo_suppression_ms = pre_read_settings.echo_suppression_ms;
 
         let position_deadband = DEFAULT_POSITION_DEADBAND;
         let velocity_deadband = DEFAULT_VELOCITY_DEADBAND;
@@ -168,12 +222,39 @@ impl SocketFlowServer {
             dragged_nodes: HashSet::new(),
             drag_last_update: HashMap::new(),
             drag_timeout_ms: 500,
+            echo_suppression_ms,
             subscribed_node_types: HashSet::new(),
             position_sub_generation: 0,
             pending_directives: Vec::new(),
+            camera_recording: None,
+            protocol: super::handshake::SessionProtocol::default(),
+            layout_playback: None,
+            handler_panics: 0,
+            layout_seeded: false,
+            xr_device_id: None,
+            gesture_drag: None,
+            gesture_starts: HashMap::new(),
+            overview: false,
+            node_budget: None,
+            focus_node: None,
+            idle_hibernation: Duration::from_millis(pre_read_settings.idle_hibernation_ms),
+            last_client_activity: Instant::now(),
+            hibernating: false,
+            resume_token: uuid::Uuid::new_v4().to_string(),
+            resume_request: None,
         }
     }
 
+    /// Saved room anchor for this user and XR device, if the session is
+    /// authenticated and named a device.
+    pub(crate) fn xr_anchor(&self) -> Option<serde_json::Value> {
+        use crate::services::xr_anchor_service::xr_anchors;
+        let pubkey = self.pubkey.as_deref()?;
+        let device_id = self.xr_device_id.as_deref()?;
+        let anchor = xr_anchors().get(pubkey, Some(device_id))?;
+        serde_json::to_value(anchor).ok()
+    }
+
     /// ADR-031 item 4: Queue a directive to be sent to this client in the next pong.
     pub fn queue_directive(&mut self, directive: HeartbeatDirective) {
         self.pending_directives.push(directive);
@@ -524,26 +605,42 @@ impl Actor for SocketFlowServer {
             });
             self.heartbeat_timer_set = true;
         }
+        self.start_hibernation_timer(ctx);
 
-        self.send_full_state_sync(ctx);
+        let resume = self.try_resume();
+        let resumed = matches!(resume, Some(Ok(_)));
+        if !resumed {
+            self.send_full_state_sync(ctx);
+        }
         self.state_synced = true;
 
-        let response = serde_json::json!({
+        let mut response = serde_json::json!({
             "type": "connection_established",
             "timestamp": chrono::Utc::now().timestamp_millis(),
             "is_reconnection": is_reconnection,
-            "state_sync_sent": true,
-            "protocol": {
-                "supported": [3, 5],
-                "preferred": 3
-            }
+            "state_sync_sent": !resumed,
+            "protocol": super::handshake::server_offer(),
+            "resumeToken": self.resume_token,
+            "revision": crate::services::diff_journal_service::diff_journal().revision(),
+            "resumed": resumed,
         });
+        if let Some(Err(reason)) = resume {
+            response["resumeRejected"] = reason.as_str().into();
+        }
+        if let Some(anchor) = self.xr_anchor() {
+            response["xrAnchor"] = anchor;
+        }
 
         if let Ok(msg_str) = serde_json::to_string(&response) {
             ctx.text(msg_str);
             self.last_activity = std::time::Instant::now();
         }
 
+        if let Some(Ok(replay)) = resume {
+            self.send_replay(replay, ctx);
+            return;
+        }
+
         let loading_msg = serde_json::json!({
             "type": "loading",
             "message": if is_reconnection { "Restoring state..." } else { "Calculating initial layout..." }
@@ -576,6 +673,9 @@ impl Actor for SocketFlowServer {
             );
         }
         self.drag_last_update.clear();
+        super::camera_recording::finish_on_disconnect(self);
+        self.park_session();
+        self.end_hibernation_on_disconnect();
 
         if let Some(client_id) = self.client_id {
             let cm_addr = self.client_manager_addr.clone();
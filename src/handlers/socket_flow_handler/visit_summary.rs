@@ -0,0 +1,84 @@
+// "Since you were last here", sent once a session authenticates.
+//
+//   authenticate { ..., highlightChanges?: bool }
+//     -> authenticate_success
+//     -> visitSummary { revision, sinceRevision, since, created, edited, removed,
+//                       newClusters, linkChanges, counts, newNodeIds, truncated, highlightId? }
+//
+// Only sent when the graph changed since the user's last visit; the first
+// visit just records where they are. New pages are highlighted for the user
+// unless `highlightChanges` is false. See `services::visit_service`.
+
+use actix::prelude::*;
+use log::{info, warn};
+use std::sync::Arc;
+
+use crate::actors::messages::GetGraphData;
+use crate::app_state::AppState;
+use crate::handlers::highlight_handler;
+use crate::services::highlight_service::{HighlightReason, NewHighlight, MAX_NODES_PER_LAYER};
+use crate::services::visit_service::{visits, VisitSummary};
+
+use super::types::SocketFlowServer;
+
+async fn summarise_visit(
+    app_state: Arc<AppState>,
+    pubkey: String,
+) -> Result<Option<VisitSummary>, String> {
+    let graph = app_state
+        .graph_service_addr
+        .send(GetGraphData)
+        .await
+        .map_err(|e| format!("Graph service unavailable: {}", e))??;
+    let analytics = app_state
+        .node_analytics
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    actix_web::web::block(move || visits().visit(&pubkey, &graph, &analytics))
+        .await
+        .map_err(|e| format!("Failed to compare graph revisions: {}", e))
+}
+
+pub(crate) fn send_visit_summary(
+    act: &mut SocketFlowServer,
+    ctx: &mut <SocketFlowServer as Actor>::Context,
+    pubkey: String,
+    highlight: bool,
+) {
+    let app_state = act.app_state.clone();
+    let fut =
+        actix::fut::wrap_future::<_, SocketFlowServer>(summarise_visit(app_state, pubkey.clone()));
+    super::panic_guard::spawn_guarded(ctx, "visitSummary", fut.map(move |result, act, ctx| match result {
+        Ok(Some(mut summary)) => {
+            if highlight && !summary.new_node_ids.is_empty() {
+                let new = NewHighlight::new(
+                    pubkey.clone(),
+                    HighlightReason::SinceLastVisit,
+                    summary
+                        .new_node_ids
+                        .iter()
+                        .take(MAX_NODES_PER_LAYER)
+                        .copied()
+                        .collect(),
+                );
+                match highlight_handler::publish(&act.app_state, new) {
+                    Ok(layer) => summary.highlight_id = Some(layer.id),
+                    Err(e) => warn!("[WebSocket] Visit highlight skipped: {}", e),
+                }
+            }
+            info!(
+                "[WebSocket] {} returns to revision {} (last saw {}): {} created, {} edited, {} removed",
+                pubkey,
+                summary.revision,
+                summary.since_revision,
+                summary.counts.created,
+                summary.counts.edited,
+                summary.counts.removed
+            );
+            ctx.text(summary.message());
+        }
+        Ok(None) => {}
+        Err(e) => warn!("[WebSocket] Visit summary for {} failed: {}", pubkey, e),
+    }));
+}
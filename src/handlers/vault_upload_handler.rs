@@ -0,0 +1,99 @@
+// src/handlers/vault_upload_handler.rs
+//! Vault upload endpoint (`POST /api/admin/upload_vault`) for deployments
+//! that do not sync from GitHub.
+
+use actix_web::{web, HttpResponse, Result};
+use log::{error, info};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::actors::messages::UpdateMetadata;
+use crate::handlers::admin_sync_handler::SyncStatisticsDto;
+use crate::services::embedding_sync_service::{self, UpdateTrigger};
+use crate::services::file_service::FileService;
+use crate::services::github_sync_service::GitHubSyncService;
+use crate::services::vault_upload_service::{extract_vault, install_vault};
+use crate::settings::auth_extractor::AuthenticatedUser;
+use crate::AppState;
+use crate::{bad_request, error_json, ok_json};
+
+#[derive(Debug, Deserialize)]
+pub struct UploadQuery {
+    /// Remove the current content files before unpacking.
+    #[serde(default)]
+    pub replace: bool,
+}
+
+/// POST /api/admin/upload_vault
+///
+/// The body is a zip archive. Content files are unpacked into the local
+/// markdown directory, metadata is regenerated from that directory and the
+/// uploaded files are ingested into the graph, replacing it when `replace` is
+/// set and merging into it otherwise. Entries that were not extracted are
+/// listed with the reason.
+pub async fn upload_vault(
+    auth: AuthenticatedUser,
+    app_state: web::Data<AppState>,
+    sync_service: web::Data<Arc<GitHubSyncService>>,
+    query: web::Query<UploadQuery>,
+    body: web::Bytes,
+) -> Result<HttpResponse> {
+    auth.require_power_user()?;
+    if body.is_empty() {
+        return bad_request!("Request body must be a zip archive");
+    }
+    info!("Vault upload received: {} bytes", body.len());
+
+    let vault = match web::block(move || extract_vault(&body)).await {
+        Ok(Ok(vault)) => vault,
+        Ok(Err(e)) => return bad_request!("Invalid vault archive", e),
+        Err(e) => return error_json!("Vault extraction aborted", e),
+    };
+    if vault.files.is_empty() {
+        return bad_request!("Archive contains no content files");
+    }
+
+    let replace = query.replace;
+    let result = web::block(move || {
+        let installed = install_vault(&vault, replace)
+            .and_then(|removed| Ok((removed, FileService::scan_local_files_to_metadata()?)));
+        (vault, installed)
+    })
+    .await;
+    let (vault, removed, metadata) = match result {
+        Ok((vault, Ok((removed, metadata)))) => (vault, removed, metadata),
+        Ok((_, Err(e))) => return error_json!("Failed to install vault", e),
+        Err(e) => return error_json!("Vault install aborted", e),
+    };
+
+    let metadata_files = metadata.len();
+    if let Err(e) = app_state.metadata_addr.send(UpdateMetadata { metadata }).await {
+        error!("Failed to send UpdateMetadata to MetadataActor: {}", e);
+    }
+
+    let stats = match sync_service.rebuild_from_files(vault.as_sync_input(), replace).await {
+        Ok(stats) => stats,
+        Err(e) => return error_json!("Graph rebuild failed", e),
+    };
+    embedding_sync_service::spawn_reload_and_update(
+        app_state.graph_service_addr.clone(),
+        app_state.settings_addr.clone(),
+        UpdateTrigger::Requested,
+    );
+    info!(
+        "Vault upload installed {} files ({} skipped, {} removed): {} nodes, {} edges",
+        vault.files.len(),
+        vault.skipped.len(),
+        removed,
+        stats.total_nodes,
+        stats.total_edges
+    );
+
+    ok_json!(serde_json::json!({
+        "written": vault.files.len(),
+        "removed": removed,
+        "skipped": vault.skipped,
+        "metadataFiles": metadata_files,
+        "statistics": SyncStatisticsDto::from(stats),
+    }))
+}
@@ -0,0 +1,39 @@
+// src/handlers/webhook_handler.rs
+//! Outgoing webhook inspection (`/api/webhooks/*`).
+
+use actix_web::{web, HttpResponse, Result};
+use std::sync::Arc;
+
+use crate::services::webhook_service::{DeliveryStatus, WebhookService};
+use crate::settings::auth_extractor::AuthenticatedUser;
+use crate::{ok_json, service_unavailable};
+
+/// GET /api/webhooks/deliveries
+///
+/// Recent delivery attempts (newest first) with their final status. Restricted
+/// to power users because the log exposes receiver URLs.
+pub async fn get_deliveries(
+    auth: AuthenticatedUser,
+    webhooks: Option<web::Data<Arc<WebhookService>>>,
+) -> Result<HttpResponse> {
+    auth.require_power_user()?;
+    let webhooks = match webhooks {
+        Some(w) => w,
+        None => return service_unavailable!("Webhook service is not running"),
+    };
+    let deliveries = webhooks.deliveries().await;
+    let failed = deliveries
+        .iter()
+        .filter(|d| d.status == DeliveryStatus::Failed)
+        .count();
+    ok_json!(serde_json::json!({
+        "active": webhooks.is_active().await,
+        "total": deliveries.len(),
+        "failed": failed,
+        "deliveries": deliveries,
+    }))
+}
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::scope("/webhooks").route("/deliveries", web::get().to(get_deliveries)));
+}
@@ -7,6 +7,12 @@
 //! - DELETE /api/workspace/{id} - Soft delete workspace
 //! - POST /api/workspace/{id}/favorite - Toggle favorite status
 //! - POST /api/workspace/{id}/archive - Archive/unarchive workspace
+//! - GET/PUT /api/workspace/{id}/simulation - Per-workspace GPU simulation params
+//! - GET /api/workspace/simulation/metrics - Per-workspace GPU step metrics
+//! - POST /api/workspace/{id}/activate - Upload the graph into the workspace's GPU context
+//! - GET /api/workspace/{id}/positions - Current layout of an activated workspace
+//! - GET/PATCH /api/workspace/{id}/settings - Per-workspace settings overlay
+//!   (`GET /api/settings` with `X-Workspace-Id` serves the merged result)
 
 use actix::Addr;
 use actix_web::{web, HttpResponse, Result as ActixResult};
@@ -22,11 +28,20 @@ use crate::actors::messages::{
     ArchiveWorkspace, CreateWorkspace, DeleteWorkspace, GetWorkspace, GetWorkspaceCount,
     GetWorkspaces, ToggleFavoriteWorkspace, UpdateWorkspace,
 };
+use crate::actors::messages::{
+    GetGraphData, GetSettings, GetWorkspaceGpuMetrics, GetWorkspacePositions,
+    PatchWorkspaceSettings, RemoveWorkspaceGpuContext, UpdateWorkspaceSimulationParams,
+    UploadWorkspaceGraph,
+};
+use crate::config::AppFullSettings;
+use crate::actors::gpu::workspace_contexts::DEFAULT_WORKSPACE_ID;
 use crate::actors::workspace_actor::WorkspaceActor;
+use crate::models::simulation_params::SimulationParams;
+use crate::AppState;
 use crate::models::workspace::{
-    CreateWorkspaceRequest, SortDirection, UpdateWorkspaceRequest, WorkspaceFilter,
-    WorkspaceListResponse, WorkspaceQuery, WorkspaceResponse, WorkspaceSortBy, WorkspaceStatus,
-    WorkspaceType,
+    overlay_settings, CreateWorkspaceRequest, SortDirection, UpdateWorkspaceRequest, Workspace,
+    WorkspaceFilter, WorkspaceListResponse, WorkspaceQuery, WorkspaceResponse, WorkspaceSortBy,
+    WorkspaceStatus, WorkspaceType, OVERLAYABLE_SETTINGS,
 };
 
 pub fn config(cfg: &mut web::ServiceConfig) {
@@ -37,11 +52,18 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             .route("/list", web::get().to(list_workspaces))
             .route("/create", web::post().to(create_workspace))
             .route("/count", web::get().to(get_workspace_count))
+            .route("/simulation/metrics", web::get().to(get_simulation_metrics))
             .route("/{id}", web::get().to(get_workspace))
             .route("/{id}", web::put().to(update_workspace))
             .route("/{id}", web::delete().to(delete_workspace))
             .route("/{id}/favorite", web::post().to(toggle_favorite_workspace))
-            .route("/{id}/archive", web::post().to(archive_workspace)),
+            .route("/{id}/archive", web::post().to(archive_workspace))
+            .route("/{id}/simulation", web::get().to(get_workspace_simulation))
+            .route("/{id}/simulation", web::put().to(update_workspace_simulation))
+            .route("/{id}/activate", web::post().to(activate_workspace))
+            .route("/{id}/positions", web::get().to(get_workspace_positions))
+            .route("/{id}/settings", web::get().to(get_workspace_settings))
+            .route("/{id}/settings", web::patch().to(patch_workspace_settings)),
     );
 }
 
@@ -294,6 +316,7 @@ async fn update_workspace(
 async fn delete_workspace(
     _auth: crate::settings::auth_extractor::AuthenticatedUser,
     workspace_actor: web::Data<Addr<WorkspaceActor>>,
+    app_state: web::Data<AppState>,
     path: web::Path<String>,
 ) -> ActixResult<HttpResponse> {
     let workspace_id = path.into_inner();
@@ -315,6 +338,11 @@ async fn delete_workspace(
                 "Successfully deleted (archived) workspace with ID: {}",
                 workspace_id
             );
+            if let Some(gpu) = app_state.get_gpu_compute_addr().await {
+                gpu.do_send(RemoveWorkspaceGpuContext {
+                    workspace_id: workspace_id.clone(),
+                });
+            }
             ok_json!(WorkspaceResponse::success_no_data(
                 "Workspace deleted successfully",
             ))
@@ -503,10 +531,434 @@ async fn get_workspace_count(
     }
 }
 
+// ============================================================================
+// Per-workspace GPU simulation
+// ============================================================================
+
+async fn get_simulation_metrics(app_state: web::Data<AppState>) -> ActixResult<HttpResponse> {
+    let Some(gpu) = app_state.get_gpu_compute_addr().await else {
+        return Ok(HttpResponse::ServiceUnavailable()
+            .json(WorkspaceResponse::error("GPU compute is not available")));
+    };
+    match gpu.send(GetWorkspaceGpuMetrics).await {
+        Ok(metrics) => ok_json!(json!({ "workspaces": metrics })),
+        Err(e) => {
+            error!("Failed to communicate with GPU compute actor: {}", e);
+            Ok(HttpResponse::InternalServerError()
+                .json(WorkspaceResponse::error("Service temporarily unavailable")))
+        }
+    }
+}
+
+async fn get_workspace_simulation(
+    workspace_actor: web::Data<Addr<WorkspaceActor>>,
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> ActixResult<HttpResponse> {
+    let workspace_id = path.into_inner();
+    if let Err(response) = ensure_known_workspace(&workspace_actor, &workspace_id).await {
+        return Ok(response);
+    }
+    let Some(gpu) = app_state.get_gpu_compute_addr().await else {
+        return Ok(HttpResponse::ServiceUnavailable()
+            .json(WorkspaceResponse::error("GPU compute is not available")));
+    };
+    match gpu.send(GetWorkspaceGpuMetrics).await {
+        Ok(metrics) => match metrics.into_iter().find(|m| m.workspace_id == workspace_id) {
+            Some(metrics) => ok_json!(json!({ "workspaceId": workspace_id, "metrics": metrics })),
+            None => Ok(HttpResponse::NotFound().json(WorkspaceResponse::error(format!(
+                "No simulation context for workspace '{}'",
+                workspace_id
+            )))),
+        },
+        Err(e) => {
+            error!("Failed to communicate with GPU compute actor: {}", e);
+            Ok(HttpResponse::InternalServerError()
+                .json(WorkspaceResponse::error("Service temporarily unavailable")))
+        }
+    }
+}
+
+async fn update_workspace_simulation(
+    _auth: crate::settings::auth_extractor::AuthenticatedUser,
+    workspace_actor: web::Data<Addr<WorkspaceActor>>,
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    body: web::Json<SimulationParams>,
+) -> ActixResult<HttpResponse> {
+    let workspace_id = path.into_inner();
+    if workspace_id.trim().is_empty() {
+        return Ok(HttpResponse::BadRequest()
+            .json(WorkspaceResponse::error("Workspace ID cannot be empty")));
+    }
+    if let Err(response) = ensure_known_workspace(&workspace_actor, &workspace_id).await {
+        return Ok(response);
+    }
+    let Some(gpu) = app_state.get_gpu_compute_addr().await else {
+        return Ok(HttpResponse::ServiceUnavailable()
+            .json(WorkspaceResponse::error("GPU compute is not available")));
+    };
+    match gpu
+        .send(UpdateWorkspaceSimulationParams {
+            workspace_id: workspace_id.clone(),
+            params: body.into_inner(),
+        })
+        .await
+    {
+        Ok(Ok(())) => {
+            info!("Updated simulation params for workspace {}", workspace_id);
+            ok_json!(WorkspaceResponse::success_no_data(
+                "Simulation parameters updated",
+            ))
+        }
+        Ok(Err(e)) => Ok(HttpResponse::BadRequest().json(WorkspaceResponse::error(e))),
+        Err(e) => {
+            error!("Failed to communicate with GPU compute actor: {}", e);
+            Ok(HttpResponse::InternalServerError()
+                .json(WorkspaceResponse::error("Service temporarily unavailable")))
+        }
+    }
+}
+
+/// POST /api/workspace/{id}/activate
+///
+/// Switches a client onto a workspace: pushes the workspace's effective physics
+/// into its GPU context and uploads the current graph into that context's own
+/// buffers, so the layout evolves independently of the default workspace.
+async fn activate_workspace(
+    _auth: crate::settings::auth_extractor::AuthenticatedUser,
+    workspace_actor: web::Data<Addr<WorkspaceActor>>,
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> ActixResult<HttpResponse> {
+    let workspace_id = path.into_inner();
+    if workspace_id == DEFAULT_WORKSPACE_ID {
+        return ok_json!(json!({ "workspaceId": workspace_id, "activated": true }));
+    }
+    let workspace = match fetch_workspace(&workspace_actor, &workspace_id).await {
+        Ok(workspace) => workspace,
+        Err(response) => return Ok(response),
+    };
+    if workspace.is_archived() {
+        return Ok(HttpResponse::Conflict()
+            .json(WorkspaceResponse::error("Archived workspaces cannot be activated")));
+    }
+    let global = match fetch_global_settings(&app_state).await {
+        Ok(global) => global,
+        Err(response) => return Ok(response),
+    };
+    let effective = match effective_settings(&global, &workspace) {
+        Ok(effective) => effective,
+        Err(e) => return Ok(HttpResponse::InternalServerError().json(WorkspaceResponse::error(e))),
+    };
+    let Some(gpu) = app_state.get_gpu_compute_addr().await else {
+        return Ok(HttpResponse::ServiceUnavailable()
+            .json(WorkspaceResponse::error("GPU compute is not available")));
+    };
+    let graph = match app_state.graph_service_addr.send(GetGraphData).await {
+        Ok(Ok(graph)) => graph,
+        Ok(Err(e)) => {
+            error!("Failed to read graph for workspace {}: {}", workspace_id, e);
+            return Ok(HttpResponse::InternalServerError()
+                .json(WorkspaceResponse::error("Failed to read graph")));
+        }
+        Err(e) => {
+            error!("Failed to communicate with graph actor: {}", e);
+            return Ok(HttpResponse::InternalServerError()
+                .json(WorkspaceResponse::error("Service temporarily unavailable")));
+        }
+    };
+
+    let params = SimulationParams::from(effective.get_physics("logseq"));
+    let upload = async {
+        gpu.send(UpdateWorkspaceSimulationParams {
+            workspace_id: workspace_id.clone(),
+            params,
+        })
+        .await
+        .map_err(|e| e.to_string())??;
+        gpu.send(UploadWorkspaceGraph {
+            workspace_id: workspace_id.clone(),
+            graph,
+        })
+        .await
+        .map_err(|e| e.to_string())?
+    };
+    match upload.await {
+        Ok(()) => {
+            info!("Activated GPU simulation for workspace {}", workspace_id);
+            ok_json!(json!({ "workspaceId": workspace_id, "activated": true }))
+        }
+        Err(e) => {
+            warn!("Failed to activate workspace {}: {}", workspace_id, e);
+            Ok(HttpResponse::ServiceUnavailable().json(WorkspaceResponse::error(e)))
+        }
+    }
+}
+
+/// GET /api/workspace/{id}/positions
+///
+/// Current layout of an activated workspace as `[{id, x, y, z}]`.
+async fn get_workspace_positions(
+    workspace_actor: web::Data<Addr<WorkspaceActor>>,
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> ActixResult<HttpResponse> {
+    let workspace_id = path.into_inner();
+    if workspace_id == DEFAULT_WORKSPACE_ID {
+        return Ok(HttpResponse::BadRequest().json(WorkspaceResponse::error(
+            "Default workspace positions are streamed over the graph socket",
+        )));
+    }
+    if let Err(response) = fetch_workspace(&workspace_actor, &workspace_id).await {
+        return Ok(response);
+    }
+    let Some(gpu) = app_state.get_gpu_compute_addr().await else {
+        return Ok(HttpResponse::ServiceUnavailable()
+            .json(WorkspaceResponse::error("GPU compute is not available")));
+    };
+    match gpu
+        .send(GetWorkspacePositions {
+            workspace_id: workspace_id.clone(),
+        })
+        .await
+    {
+        Ok(Ok(positions)) => {
+            let nodes: Vec<_> = positions
+                .into_iter()
+                .map(|(id, [x, y, z])| json!({ "id": id, "x": x, "y": y, "z": z }))
+                .collect();
+            ok_json!(json!({ "workspaceId": workspace_id, "nodes": nodes }))
+        }
+        Ok(Err(e)) => Ok(HttpResponse::Conflict().json(WorkspaceResponse::error(e))),
+        Err(e) => {
+            error!("Failed to communicate with GPU compute actor: {}", e);
+            Ok(HttpResponse::InternalServerError()
+                .json(WorkspaceResponse::error("Service temporarily unavailable")))
+        }
+    }
+}
+
+/// GET /api/workspace/{id}/settings
+///
+/// The workspace's overlay and the settings it produces over the global ones.
+async fn get_workspace_settings(
+    _auth: crate::settings::auth_extractor::AuthenticatedUser,
+    workspace_actor: web::Data<Addr<WorkspaceActor>>,
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> ActixResult<HttpResponse> {
+    let workspace_id = path.into_inner();
+    let workspace = match fetch_workspace(&workspace_actor, &workspace_id).await {
+        Ok(workspace) => workspace,
+        Err(response) => return Ok(response),
+    };
+    let global = match fetch_global_settings(&app_state).await {
+        Ok(global) => global,
+        Err(response) => return Ok(response),
+    };
+    match effective_settings(&global, &workspace) {
+        Ok(effective) => ok_json!(settings_view(&workspace, &effective)),
+        Err(e) => {
+            error!("Stored settings overlay for workspace {} is invalid: {}", workspace_id, e);
+            Ok(HttpResponse::InternalServerError().json(WorkspaceResponse::error(e)))
+        }
+    }
+}
+
+/// PATCH /api/workspace/{id}/settings
+///
+/// Body is a JSON merge patch over the workspace's overlay, e.g.
+/// `{"visualisation":{"graphs":{"logseq":{"physics":{"springK":0.2}}}}}`;
+/// `null` drops an override. The merged result must still be valid settings.
+async fn patch_workspace_settings(
+    _auth: crate::settings::auth_extractor::AuthenticatedUser,
+    workspace_actor: web::Data<Addr<WorkspaceActor>>,
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    body: web::Json<serde_json::Value>,
+) -> ActixResult<HttpResponse> {
+    let workspace_id = path.into_inner();
+    let patch = body.into_inner();
+    let Some(sections) = patch.as_object() else {
+        return Ok(HttpResponse::BadRequest()
+            .json(WorkspaceResponse::error("Settings patch must be a JSON object")));
+    };
+    if let Some(key) = sections.keys().find(|k| !OVERLAYABLE_SETTINGS.contains(&k.as_str())) {
+        return Ok(HttpResponse::BadRequest().json(WorkspaceResponse::error(format!(
+            "'{}' cannot be overridden per workspace (allowed: {})",
+            key,
+            OVERLAYABLE_SETTINGS.join(", ")
+        ))));
+    }
+    let touches_physics = ["logseq", "visionclaw"].iter().any(|graph| {
+        patch
+            .pointer(&format!("/visualisation/graphs/{}/physics", graph))
+            .is_some()
+    });
+
+    let mut candidate = match fetch_workspace(&workspace_actor, &workspace_id).await {
+        Ok(workspace) => workspace,
+        Err(response) => return Ok(response),
+    };
+    let global = match fetch_global_settings(&app_state).await {
+        Ok(global) => global,
+        Err(response) => return Ok(response),
+    };
+    candidate.patch_settings_overlay(patch.clone());
+    if let Err(e) = effective_settings(&global, &candidate) {
+        return Ok(HttpResponse::BadRequest().json(WorkspaceResponse::error(e)));
+    }
+
+    let workspace = match workspace_actor
+        .send(PatchWorkspaceSettings {
+            workspace_id: workspace_id.clone(),
+            patch,
+        })
+        .await
+    {
+        Ok(Ok(workspace)) => workspace,
+        Ok(Err(e)) => return Ok(HttpResponse::NotFound().json(WorkspaceResponse::error(e))),
+        Err(e) => {
+            error!("Failed to communicate with workspace actor: {}", e);
+            return Ok(HttpResponse::InternalServerError()
+                .json(WorkspaceResponse::error("Service temporarily unavailable")));
+        }
+    };
+    // A concurrent patch may have landed in between; report what was stored.
+    let effective = match effective_settings(&global, &workspace) {
+        Ok(effective) => effective,
+        Err(e) => return Ok(HttpResponse::Conflict().json(WorkspaceResponse::error(e))),
+    };
+
+    if touches_physics {
+        if let Some(gpu) = app_state.get_gpu_compute_addr().await {
+            let params = SimulationParams::from(effective.get_physics("logseq"));
+            match gpu
+                .send(UpdateWorkspaceSimulationParams {
+                    workspace_id: workspace_id.clone(),
+                    params,
+                })
+                .await
+            {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => debug!("Workspace {} has no GPU context yet: {}", workspace_id, e),
+                Err(e) => warn!("Failed to push workspace physics to GPU: {}", e),
+            }
+        }
+    }
+
+    info!("Patched settings overlay for workspace {}", workspace_id);
+    ok_json!(settings_view(&workspace, &effective))
+}
+
 // ============================================================================
 // Helper Types and Functions
 // ============================================================================
 
+async fn fetch_workspace(
+    workspace_actor: &Addr<WorkspaceActor>,
+    workspace_id: &str,
+) -> Result<Workspace, HttpResponse> {
+    match send_with_default_timeout(
+        workspace_actor,
+        GetWorkspace {
+            workspace_id: workspace_id.to_string(),
+        },
+        "Workspace",
+    )
+    .await
+    {
+        Ok(Ok(workspace)) => Ok(workspace),
+        Ok(Err(e)) => Err(HttpResponse::NotFound().json(WorkspaceResponse::error(e))),
+        Err(e) => {
+            error!("Failed to communicate with workspace actor: {}", e);
+            Err(HttpResponse::InternalServerError()
+                .json(WorkspaceResponse::error("Service temporarily unavailable")))
+        }
+    }
+}
+
+/// Settings as a session working in `workspace_id` sees them: the global
+/// settings with that workspace's overlay applied. `"default"` resolves to the
+/// global settings unchanged.
+pub(crate) async fn session_settings(
+    app_state: &AppState,
+    workspace_id: &str,
+) -> Result<AppFullSettings, HttpResponse> {
+    let global = fetch_global_settings(app_state).await?;
+    if workspace_id == DEFAULT_WORKSPACE_ID {
+        return Ok(global);
+    }
+    let workspace = fetch_workspace(&app_state.workspace_addr, workspace_id).await?;
+    effective_settings(&global, &workspace).map_err(|e| {
+        error!("Stored settings overlay for workspace {} is invalid: {}", workspace_id, e);
+        HttpResponse::InternalServerError().json(WorkspaceResponse::error(e))
+    })
+}
+
+/// Rejects ids that name no stored workspace. `"default"` always resolves to
+/// the primary layout.
+async fn ensure_known_workspace(
+    workspace_actor: &Addr<WorkspaceActor>,
+    workspace_id: &str,
+) -> Result<(), HttpResponse> {
+    if workspace_id == DEFAULT_WORKSPACE_ID {
+        return Ok(());
+    }
+    fetch_workspace(workspace_actor, workspace_id).await.map(|_| ())
+}
+
+async fn fetch_global_settings(app_state: &AppState) -> Result<AppFullSettings, HttpResponse> {
+    match app_state.settings_addr.send(GetSettings).await {
+        Ok(Ok(settings)) => Ok(settings),
+        Ok(Err(e)) => {
+            error!("Failed to read global settings: {}", e);
+            Err(HttpResponse::InternalServerError()
+                .json(WorkspaceResponse::error("Failed to read settings")))
+        }
+        Err(e) => {
+            error!("Failed to communicate with settings actor: {}", e);
+            Err(HttpResponse::InternalServerError()
+                .json(WorkspaceResponse::error("Service temporarily unavailable")))
+        }
+    }
+}
+
+/// Global settings with the workspace overlay layered on top, re-validated.
+fn effective_settings(
+    global: &AppFullSettings,
+    workspace: &Workspace,
+) -> Result<AppFullSettings, String> {
+    let Some(overlay) = workspace.settings_overlay() else {
+        return Ok(global.clone());
+    };
+    let mut merged =
+        serde_json::to_value(global).map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    overlay_settings(&mut merged, overlay);
+    let effective: AppFullSettings = serde_json::from_value(merged)
+        .map_err(|e| format!("Invalid settings override: {}", e))?;
+    effective
+        .validate()
+        .map_err(|e| format!("Invalid settings override: {}", e))?;
+    Ok(effective)
+}
+
+/// Only the overridable sections are echoed back; the rest of the settings
+/// tree (credentials, service endpoints) stays out of workspace responses.
+fn settings_view(workspace: &Workspace, effective: &AppFullSettings) -> serde_json::Value {
+    let effective = serde_json::to_value(effective).unwrap_or_default();
+    let sections: serde_json::Map<String, serde_json::Value> = OVERLAYABLE_SETTINGS
+        .iter()
+        .filter_map(|key| effective.get(*key).map(|v| (key.to_string(), v.clone())))
+        .collect();
+    json!({
+        "workspaceId": workspace.id,
+        "overlay": workspace.settings_overlay().cloned().unwrap_or_else(|| json!({})),
+        "effective": sections,
+    })
+}
+
 #[derive(serde::Deserialize, Debug)]
 struct WorkspaceQueryParams {
     page: Option<usize>,
@@ -0,0 +1,84 @@
+// src/handlers/write_back_handler.rs
+//! Page write-back status and control (`/api/writeback/*`).
+//!
+//! `github.writeMode` decides whether edits are committed directly or batched
+//! into a pull request; these endpoints show what is pending and the state
+//! of the open PR, and let a power user push the batch now. Edits that would
+//! overwrite remote changes are parked and listed with a three-way diff while
+//! the rest are pushed; if every edit is parked the flush answers 409.
+//! `?force=true` pushes them anyway.
+
+use actix_web::{web, HttpResponse, Result};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::actors::messages::GetSettings;
+use crate::config::WriteMode;
+use crate::services::write_back_service::{write_mode, FlushOutcome, WriteBackService};
+use crate::settings::auth_extractor::AuthenticatedUser;
+use crate::AppState;
+use crate::{error_json, ok_json, service_unavailable};
+
+async fn current_mode(app_state: &AppState) -> WriteMode {
+    match app_state.settings_addr.send(GetSettings).await {
+        Ok(Ok(settings)) => write_mode(&settings),
+        _ => WriteMode::default(),
+    }
+}
+
+/// GET /api/writeback
+///
+/// Write mode, queued edits, and the live state of the write-back PR.
+pub async fn get_write_back_status(
+    _auth: AuthenticatedUser,
+    app_state: web::Data<AppState>,
+    service: Option<web::Data<Arc<WriteBackService>>>,
+) -> Result<HttpResponse> {
+    let Some(service) = service else {
+        return service_unavailable!("Write-back is not configured");
+    };
+    let mode = current_mode(&app_state).await;
+    ok_json!(service.status(mode).await)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FlushQuery {
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// 409 carrying the conflicts, or the usual JSON body for any other outcome.
+pub fn flush_response(outcome: FlushOutcome) -> Result<HttpResponse> {
+    match outcome {
+        FlushOutcome::Conflict { .. } => Ok(HttpResponse::Conflict().json(outcome)),
+        other => ok_json!(other),
+    }
+}
+
+/// POST /api/writeback/flush[?force=true]
+///
+/// Push queued edits now instead of waiting for the periodic flush.
+pub async fn flush_write_back(
+    auth: AuthenticatedUser,
+    app_state: web::Data<AppState>,
+    service: Option<web::Data<Arc<WriteBackService>>>,
+    query: web::Query<FlushQuery>,
+) -> Result<HttpResponse> {
+    auth.require_power_user()?;
+    let Some(service) = service else {
+        return service_unavailable!("Write-back is not configured");
+    };
+    let mode = current_mode(&app_state).await;
+    match service.flush(mode, query.force).await {
+        Ok(outcome) => flush_response(outcome),
+        Err(e) => error_json!("Write-back flush failed", e),
+    }
+}
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/writeback")
+            .route("", web::get().to(get_write_back_status))
+            .route("/flush", web::post().to(flush_write_back)),
+    );
+}
@@ -0,0 +1,91 @@
+// src/handlers/xr_anchor_handler.rs
+//! Per-device XR room anchors (`/api/xr/anchor`).
+//!
+//! Sockets also get the anchor in `connection_established` and `hello_ack`
+//! when they name their device; see `services::xr_anchor_service`.
+
+use actix_web::{web, HttpResponse, Result};
+use chrono::Utc;
+use log::info;
+use serde::Deserialize;
+
+use crate::services::xr_anchor_service::{validate_device_id, xr_anchors, AnchorUpdate};
+use crate::settings::auth_extractor::AuthenticatedUser;
+use crate::{bad_request, error_json, ok_json};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceQuery {
+    pub device_id: Option<String>,
+}
+
+/// GET /api/xr/anchor?deviceId=quest-3
+///
+/// The device's anchor, else the user's most recent one with
+/// `inherited: true`; `{"anchor": null}` if the user has none.
+pub async fn get_anchor(
+    auth: AuthenticatedUser,
+    query: web::Query<DeviceQuery>,
+) -> Result<HttpResponse> {
+    if let Some(device_id) = query.device_id.as_deref() {
+        if let Err(e) = validate_device_id(device_id) {
+            return bad_request!(e);
+        }
+    }
+    let anchor = xr_anchors().get(&auth.pubkey, query.device_id.as_deref());
+    ok_json!(serde_json::json!({ "anchor": anchor }))
+}
+
+/// PUT /api/xr/anchor
+///
+/// `{"deviceId": "quest-3", "position": [0, 0, -1.5], "rotation": [0, 0, 0, 1], "scale": 1}`
+pub async fn put_anchor(
+    auth: AuthenticatedUser,
+    body: web::Json<AnchorUpdate>,
+) -> Result<HttpResponse> {
+    let update = body.into_inner();
+    let device_id = update.device_id.clone();
+    let anchor = match update.into_anchor(Utc::now()) {
+        Ok(anchor) => anchor,
+        Err(e) => return bad_request!(e),
+    };
+    let pubkey = auth.pubkey.clone();
+    match web::block(move || xr_anchors().put(&pubkey, device_id, anchor)).await {
+        Ok(Ok(anchor)) => {
+            info!(
+                "[XR] Saved room anchor for {} on device {}",
+                auth.pubkey, anchor.device_id
+            );
+            ok_json!(serde_json::json!({ "anchor": anchor }))
+        }
+        Ok(Err(e)) => error_json!("Failed to save anchor", e),
+        Err(e) => error_json!("Failed to save anchor", e),
+    }
+}
+
+/// DELETE /api/xr/anchor?deviceId=quest-3
+pub async fn delete_anchor(
+    auth: AuthenticatedUser,
+    query: web::Query<DeviceQuery>,
+) -> Result<HttpResponse> {
+    let Some(device_id) = query.into_inner().device_id else {
+        return bad_request!("deviceId is required");
+    };
+    let pubkey = auth.pubkey;
+    match web::block(move || xr_anchors().delete(&pubkey, &device_id)).await {
+        Ok(Ok(removed)) => ok_json!(serde_json::json!({ "removed": removed })),
+        Ok(Err(e)) => error_json!("Failed to delete anchor", e),
+        Err(e) => error_json!("Failed to delete anchor", e),
+    }
+}
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/xr").service(
+            web::resource("/anchor")
+                .route(web::get().to(get_anchor))
+                .route(web::put().to(put_anchor))
+                .route(web::delete().to(delete_anchor)),
+        ),
+    );
+}
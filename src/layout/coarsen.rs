@@ -0,0 +1,360 @@
+//! Coarse graph for overview mode.
+//!
+//! Zoomed out, a client cannot tell ten thousand nodes apart, so it can ask
+//! for an overview instead: a graph of about [`OVERVIEW_RATIO`] of the nodes
+//! whose positions are the centroids of the nodes each one stands for.
+//!
+//! The coarse graph is built by repeated heavy-edge matching. Each level
+//! visits nodes from the lowest degree up and pairs each one with the
+//! unmatched neighbour it shares the heaviest edge with; a node whose
+//! neighbours are all taken joins the group of its heaviest neighbour
+//! instead, so stars collapse in one level. Parallel edges between two groups
+//! are merged, summing their weights. Levels repeat until the target size is
+//! reached or a level stops shrinking the graph. Nodes with no edges cannot
+//! be matched and are gathered into a single group.
+//!
+//! The structure only changes when nodes or edges do, so [`overview`] caches
+//! it per graph revision; positions are recomputed per frame with
+//! [`CoarseGraph::positions`].
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use log::info;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+
+use crate::models::graph::GraphData;
+
+/// Share of the full graph's nodes the overview aims for.
+pub const OVERVIEW_RATIO: f32 = 0.05;
+
+/// Safety stop; each level at least halves the matched part of the graph.
+const MAX_LEVELS: u32 = 32;
+
+/// A level that removes less than this share of the nodes ends coarsening.
+const MIN_LEVEL_SHRINK: f32 = 0.05;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoarseNode {
+    pub id: u32,
+    /// Full-graph node ids this node stands for, ascending.
+    pub members: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoarseGraph {
+    pub nodes: Vec<CoarseNode>,
+    /// `[source, target, weight]` with `source < target`.
+    pub edges: Vec<(u32, u32, f32)>,
+    /// Matching levels it took to get here.
+    pub levels: u32,
+    #[serde(skip)]
+    membership: HashMap<u32, u32>,
+}
+
+/// Node count the overview aims for.
+pub fn overview_target(node_count: usize) -> usize {
+    ((node_count as f32 * OVERVIEW_RATIO).ceil() as usize).max(1)
+}
+
+impl CoarseGraph {
+    /// Coarsen until at most `target` nodes remain, or no level helps.
+    /// Edges to unknown nodes and self-loops are ignored; non-positive
+    /// weights count as 1.
+    pub fn build(node_ids: &[u32], edges: &[(u32, u32, f32)], target: usize) -> Self {
+        let index: HashMap<u32, usize> = node_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, i))
+            .collect();
+        let mut adjacency: Vec<HashMap<usize, f32>> = vec![HashMap::new(); node_ids.len()];
+        for &(source, target, weight) in edges {
+            let (Some(&a), Some(&b)) = (index.get(&source), index.get(&target)) else {
+                continue;
+            };
+            if a == b {
+                continue;
+            }
+            let weight = if weight.is_finite() && weight > 0.0 {
+                weight
+            } else {
+                1.0
+            };
+            *adjacency[a].entry(b).or_default() += weight;
+            *adjacency[b].entry(a).or_default() += weight;
+        }
+
+        let (linked, isolated): (Vec<usize>, Vec<usize>) =
+            (0..node_ids.len()).partition(|&i| !adjacency[i].is_empty());
+        let linked_target = target
+            .saturating_sub(usize::from(!isolated.is_empty()))
+            .max(1);
+
+        // Work on the linked part only, renumbered densely.
+        let renumber: HashMap<usize, usize> = linked
+            .iter()
+            .enumerate()
+            .map(|(new, &old)| (old, new))
+            .collect();
+        let mut groups: Vec<Vec<u32>> = linked.iter().map(|&i| vec![node_ids[i]]).collect();
+        let mut adjacency: Vec<HashMap<usize, f32>> = linked
+            .iter()
+            .map(|&i| {
+                adjacency[i]
+                    .iter()
+                    .map(|(j, w)| (renumber[j], *w))
+                    .collect()
+            })
+            .collect();
+
+        let mut levels = 0;
+        while groups.len() > linked_target && levels < MAX_LEVELS {
+            let before = groups.len();
+            (groups, adjacency) = match_level(groups, &adjacency);
+            levels += 1;
+            if (before - groups.len()) as f32 <= before as f32 * MIN_LEVEL_SHRINK {
+                break;
+            }
+        }
+
+        let mut edges: Vec<(u32, u32, f32)> = adjacency
+            .iter()
+            .enumerate()
+            .flat_map(|(a, neighbours)| {
+                neighbours
+                    .iter()
+                    .filter(move |(b, _)| a < **b)
+                    .map(move |(b, w)| (a as u32, *b as u32, *w))
+            })
+            .collect();
+        edges.sort_by_key(|&(a, b, _)| (a, b));
+
+        if !isolated.is_empty() {
+            groups.push(isolated.iter().map(|&i| node_ids[i]).collect());
+        }
+        let nodes: Vec<CoarseNode> = groups
+            .into_iter()
+            .enumerate()
+            .map(|(id, mut members)| {
+                members.sort_unstable();
+                CoarseNode {
+                    id: id as u32,
+                    members,
+                }
+            })
+            .collect();
+        let membership = nodes
+            .iter()
+            .flat_map(|n| n.members.iter().map(move |m| (*m, n.id)))
+            .collect();
+
+        CoarseGraph {
+            nodes,
+            edges,
+            levels,
+            membership,
+        }
+    }
+
+    /// The coarse node standing for a full-graph node.
+    pub fn coarse_id(&self, node_id: u32) -> Option<u32> {
+        self.membership.get(&node_id).copied()
+    }
+
+    /// Centroid of each coarse node's members among `positions`. Coarse nodes
+    /// with no known member positions are left out.
+    pub fn positions(
+        &self,
+        positions: impl IntoIterator<Item = (u32, [f32; 3])>,
+    ) -> Vec<(u32, [f32; 3])> {
+        let mut sums = vec![([0.0f32; 3], 0u32); self.nodes.len()];
+        for (node_id, p) in positions {
+            if let Some(coarse) = self.coarse_id(node_id) {
+                let (sum, count) = &mut sums[coarse as usize];
+                for axis in 0..3 {
+                    sum[axis] += p[axis];
+                }
+                *count += 1;
+            }
+        }
+        sums.into_iter()
+            .enumerate()
+            .filter(|(_, (_, count))| *count > 0)
+            .map(|(id, (sum, count))| (id as u32, sum.map(|v| v / count as f32)))
+            .collect()
+    }
+}
+
+/// One level of heavy-edge matching over `groups`.
+fn match_level(
+    groups: Vec<Vec<u32>>,
+    adjacency: &[HashMap<usize, f32>],
+) -> (Vec<Vec<u32>>, Vec<HashMap<usize, f32>>) {
+    const UNSET: usize = usize::MAX;
+    let n = groups.len();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by_key(|&i| (adjacency[i].len(), i));
+
+    // Heaviest edge first; on a tie prefer the smaller group, then the lower
+    // index, so results do not depend on hash order.
+    let heaviest = |u: usize, eligible: &dyn Fn(usize) -> bool| {
+        adjacency[u]
+            .iter()
+            .filter(|(v, _)| eligible(**v))
+            .max_by(|(a, wa), (b, wb)| {
+                wa.total_cmp(wb)
+                    .then_with(|| groups[**b].len().cmp(&groups[**a].len()))
+                    .then_with(|| b.cmp(a))
+            })
+            .map(|(v, _)| *v)
+    };
+
+    let mut mate = vec![UNSET; n];
+    let mut next = 0;
+    for &u in &order {
+        if mate[u] != UNSET {
+            continue;
+        }
+        if let Some(v) = heaviest(u, &|v| mate[v] == UNSET) {
+            mate[u] = next;
+            mate[v] = next;
+            next += 1;
+        }
+    }
+    // Every neighbour of a node still unmatched is taken; join the heaviest.
+    for &u in &order {
+        if mate[u] == UNSET {
+            mate[u] = match heaviest(u, &|v| mate[v] != UNSET) {
+                Some(v) => mate[v],
+                None => {
+                    let id = next;
+                    next += 1;
+                    id
+                }
+            };
+        }
+    }
+
+    let mut coarse_groups = vec![Vec::new(); next];
+    let mut coarse_adjacency = vec![HashMap::new(); next];
+    for (u, members) in groups.iter().enumerate() {
+        coarse_groups[mate[u]].extend_from_slice(members);
+        for (v, w) in &adjacency[u] {
+            if mate[u] != mate[*v] {
+                *coarse_adjacency[mate[u]].entry(mate[*v]).or_default() += *w;
+            }
+        }
+    }
+    (coarse_groups, coarse_adjacency)
+}
+
+/// Graph revision, node count and edge count. The revision moves with every
+/// graph diff; the counts catch a rebuild that replaces the graph without one.
+type OverviewKey = (u64, usize, usize);
+
+static OVERVIEW: Lazy<Mutex<Option<(OverviewKey, Arc<CoarseGraph>)>>> =
+    Lazy::new(|| Mutex::new(None));
+
+/// Coarse graph for `graph` at `revision` (see
+/// `services::diff_journal_service`), rebuilt only when either has changed.
+pub fn overview(graph: &GraphData, revision: u64) -> Arc<CoarseGraph> {
+    let key = (revision, graph.nodes.len(), graph.edges.len());
+    let cached = OVERVIEW.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    if let Some((cached_key, coarse)) = cached {
+        if cached_key == key {
+            return coarse;
+        }
+    }
+    let node_ids: Vec<u32> = graph.nodes.iter().map(|n| n.id).collect();
+    let edges: Vec<(u32, u32, f32)> = graph
+        .edges
+        .iter()
+        .map(|e| (e.source, e.target, e.weight))
+        .collect();
+    let coarse = Arc::new(CoarseGraph::build(
+        &node_ids,
+        &edges,
+        overview_target(node_ids.len()),
+    ));
+    info!(
+        "Built overview graph: {} -> {} nodes in {} level(s)",
+        node_ids.len(),
+        coarse.nodes.len(),
+        coarse.levels
+    );
+    *OVERVIEW.lock().unwrap_or_else(|e| e.into_inner()) = Some((key, Arc::clone(&coarse)));
+    coarse
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::edge::Edge;
+    use crate::models::node::Node;
+
+    #[test]
+    fn contracts_to_target_and_averages_positions() {
+        // Ten 10-node rings joined in a chain by heavy edges, plus two
+        // unlinked nodes.
+        let mut ids: Vec<u32> = (0..100).collect();
+        ids.extend([500, 501]);
+        let mut edges = Vec::new();
+        for ring in 0..10u32 {
+            for i in 0..10u32 {
+                edges.push((ring * 10 + i, ring * 10 + (i + 1) % 10, 1.0));
+            }
+            if ring > 0 {
+                edges.push((ring * 10 - 1, ring * 10, 5.0));
+            }
+        }
+        edges.push((3, 3, 1.0));
+        edges.push((3, 999, 1.0));
+
+        let coarse = CoarseGraph::build(&ids, &edges, overview_target(ids.len()));
+        assert!(coarse.nodes.len() <= overview_target(ids.len()) + 1);
+        assert!(coarse.levels > 0);
+
+        // Every node belongs to exactly one coarse node.
+        let mut members: Vec<u32> = coarse
+            .nodes
+            .iter()
+            .flat_map(|n| n.members.clone())
+            .collect();
+        members.sort_unstable();
+        assert_eq!(members, ids);
+
+        // The unlinked nodes share the last group.
+        let unlinked = coarse.nodes.last().unwrap();
+        assert_eq!(unlinked.members, vec![500, 501]);
+        assert!(coarse
+            .edges
+            .iter()
+            .all(|&(a, b, w)| a < b && b != unlinked.id && w > 0.0));
+
+        let positions = coarse.positions([(500, [2.0, 0.0, 0.0]), (501, [4.0, 2.0, 0.0])]);
+        assert_eq!(positions, vec![(unlinked.id, [3.0, 1.0, 0.0])]);
+
+        // Coarsening is deterministic.
+        let again = CoarseGraph::build(&ids, &edges, overview_target(ids.len()));
+        assert_eq!(again.nodes, coarse.nodes);
+    }
+
+    #[test]
+    fn overview_is_rebuilt_only_for_a_new_revision() {
+        let mut graph = GraphData::new();
+        for id in 1..=4 {
+            graph.nodes.push(Node::new_with_id(format!("{}.md", id), Some(id)));
+        }
+        graph.edges.push(Edge::new(1, 2, 1.0));
+        let first = overview(&graph, 7);
+        assert!(Arc::ptr_eq(&first, &overview(&graph, 7)));
+
+        graph.edges[0] = Edge::new(3, 4, 1.0);
+        let rebuilt = overview(&graph, 8);
+        assert!(!Arc::ptr_eq(&first, &rebuilt));
+        assert_eq!(rebuilt.coarse_id(3), rebuilt.coarse_id(4));
+    }
+}
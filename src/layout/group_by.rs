@@ -0,0 +1,401 @@
+//! Group-by layout: thematic clustering around invisible group anchors.
+//!
+//! Nodes are bucketed by a metadata attribute (tag, namespace, node type or
+//! age) rather than by community detection. Each bucket gets an anchor; the
+//! anchors push each other apart until their spheres no longer overlap, then
+//! a short force pass pulls members towards their own anchor, pushes them
+//! out of foreign groups and lets edges bend the result a little. Anchors are
+//! never emitted as nodes — they only shape the layout.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use log::info;
+use serde::Serialize;
+
+use crate::models::node::Node;
+use crate::protocol::node_metadata::parse_timestamp;
+
+/// Buckets beyond this many are folded into [`OTHER_GROUP`].
+pub const MAX_GROUPS: usize = 64;
+pub const OTHER_GROUP: &str = "(other)";
+pub const UNGROUPED: &str = "(none)";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GroupBy {
+    /// First entry of the comma-separated `tags` metadata.
+    Tag,
+    /// Logseq namespace: the label up to the first `/`.
+    Namespace,
+    NodeType,
+    /// Coarse age of `last_modified`: week, month, quarter, year, older.
+    AgeBucket,
+}
+
+impl GroupBy {
+    pub const ALL: &'static [&'static str] = &["tag", "namespace", "nodeType", "ageBucket"];
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "tag" | "tags" => Some(GroupBy::Tag),
+            "namespace" => Some(GroupBy::Namespace),
+            "nodeType" | "node_type" | "type" => Some(GroupBy::NodeType),
+            "ageBucket" | "age_bucket" | "age" => Some(GroupBy::AgeBucket),
+            _ => None,
+        }
+    }
+
+    /// The bucket `node` falls into. Nodes without the attribute share
+    /// [`UNGROUPED`].
+    pub fn key_for(&self, node: &Node, now: DateTime<Utc>) -> String {
+        let key = match self {
+            GroupBy::Tag => node.metadata.get("tags").and_then(|raw| {
+                raw.split(',')
+                    .map(str::trim)
+                    .find(|t| !t.is_empty())
+                    .map(str::to_lowercase)
+            }),
+            GroupBy::Namespace => node
+                .label
+                .split_once('/')
+                .map(|(ns, _)| ns.trim().to_lowercase())
+                .filter(|ns| !ns.is_empty()),
+            GroupBy::NodeType => node.node_type.clone().filter(|t| !t.is_empty()),
+            GroupBy::AgeBucket => node
+                .metadata
+                .get("last_modified")
+                .and_then(|ts| parse_timestamp(ts))
+                .map(|ts| age_bucket((now - ts).num_days()).to_string()),
+        };
+        key.unwrap_or_else(|| UNGROUPED.to_string())
+    }
+}
+
+fn age_bucket(days: i64) -> &'static str {
+    match days {
+        i64::MIN..=7 => "week",
+        8..=31 => "month",
+        32..=92 => "quarter",
+        93..=365 => "year",
+        _ => "older",
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GroupLayoutConfig {
+    /// Gap left between neighbouring group spheres.
+    pub group_gap: f32,
+    /// Spacing used to size each group's sphere from its member count.
+    pub node_spacing: f32,
+    /// Pull of a member towards its own anchor once outside the group radius.
+    pub attraction: f32,
+    /// Push of a member out of a foreign group's sphere.
+    pub repulsion: f32,
+    /// Spring stiffness for edges within a group; kept weak so groups stay
+    /// readable. Cross-group edges exert no pull.
+    pub edge_strength: f32,
+    pub iterations: usize,
+}
+
+impl Default for GroupLayoutConfig {
+    fn default() -> Self {
+        Self {
+            group_gap: 120.0,
+            node_spacing: 40.0,
+            attraction: 0.2,
+            repulsion: 0.5,
+            edge_strength: 0.01,
+            iterations: 60,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupAnchor {
+    pub key: String,
+    pub position: [f32; 3],
+    pub radius: f32,
+    pub members: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct GroupLayout {
+    /// One position per input node, in input order.
+    pub positions: Vec<(f32, f32, f32)>,
+    /// Index into `anchors` for each input node.
+    pub group_of: Vec<usize>,
+    pub anchors: Vec<GroupAnchor>,
+}
+
+type Vec3 = [f32; 3];
+
+fn sub(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn len(v: Vec3) -> f32 {
+    (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt()
+}
+
+fn add_scaled(a: &mut Vec3, v: Vec3, s: f32) {
+    a[0] += v[0] * s;
+    a[1] += v[1] * s;
+    a[2] += v[2] * s;
+}
+
+fn fibonacci_point(i: usize, total: usize) -> Vec3 {
+    if total <= 1 {
+        return [0.0, 0.0, 0.0];
+    }
+    let golden_ratio = (1.0 + 5.0f32.sqrt()) / 2.0;
+    let theta = 2.0 * std::f32::consts::PI * i as f32 / golden_ratio;
+    let phi = (1.0 - 2.0 * (i as f32 + 0.5) / total as f32).acos();
+    [phi.sin() * theta.cos(), phi.cos(), phi.sin() * theta.sin()]
+}
+
+/// Lay out `nodes` (id, group key) around one anchor per group.
+pub fn compute_group_layout(
+    nodes: &[(u32, String)],
+    edges: &[(u32, u32, f32)],
+    config: &GroupLayoutConfig,
+) -> GroupLayout {
+    let n = nodes.len();
+    if n == 0 {
+        return GroupLayout {
+            positions: vec![],
+            group_of: vec![],
+            anchors: vec![],
+        };
+    }
+
+    // Largest groups first, ties by key, so the result is deterministic.
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for (_, key) in nodes {
+        *counts.entry(key.as_str()).or_insert(0) += 1;
+    }
+    let mut keys: Vec<(&str, usize)> = counts.into_iter().collect();
+    keys.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    if keys.len() > MAX_GROUPS {
+        let folded: usize = keys[MAX_GROUPS - 1..].iter().map(|(_, c)| c).sum();
+        keys.truncate(MAX_GROUPS - 1);
+        keys.push((OTHER_GROUP, folded));
+    }
+    let key_idx: HashMap<&str, usize> =
+        keys.iter().enumerate().map(|(i, (k, _))| (*k, i)).collect();
+    let other_idx = keys.len() - 1;
+    let group_of: Vec<usize> = nodes
+        .iter()
+        .map(|(_, key)| key_idx.get(key.as_str()).copied().unwrap_or(other_idx))
+        .collect();
+
+    let g = keys.len();
+    let radii: Vec<f32> = keys
+        .iter()
+        .map(|(_, c)| config.node_spacing * (*c as f32).cbrt().max(1.0))
+        .collect();
+
+    // --- Anchors: start on a sphere, then separate until no spheres overlap ---
+    let spread = (radii.iter().sum::<f32>() + config.group_gap * g as f32) / std::f32::consts::PI;
+    let mut anchors: Vec<Vec3> = (0..g)
+        .map(|i| {
+            let p = fibonacci_point(i, g);
+            [p[0] * spread, p[1] * spread, p[2] * spread]
+        })
+        .collect();
+    for _ in 0..100 {
+        let mut moved = false;
+        for a in 0..g {
+            for b in (a + 1)..g {
+                let d = sub(anchors[b], anchors[a]);
+                let dist = len(d).max(1e-3);
+                let min_dist = radii[a] + radii[b] + config.group_gap;
+                if dist < min_dist {
+                    let push = (min_dist - dist) / 2.0 / dist;
+                    add_scaled(&mut anchors[b], d, push);
+                    add_scaled(&mut anchors[a], d, -push);
+                    moved = true;
+                }
+            }
+        }
+        if !moved {
+            break;
+        }
+    }
+
+    // --- Members: seed inside their group sphere ---
+    let mut local_idx = vec![0usize; g];
+    let mut positions: Vec<Vec3> = group_of
+        .iter()
+        .map(|&gi| {
+            let total = keys[gi].1;
+            let i = local_idx[gi];
+            local_idx[gi] += 1;
+            let p = fibonacci_point(i, total);
+            // Spread members through the volume, not just over the surface.
+            let r = radii[gi] * ((i as f32 + 1.0) / total as f32).cbrt();
+            let c = anchors[gi];
+            [c[0] + p[0] * r, c[1] + p[1] * r, c[2] + p[2] * r]
+        })
+        .collect();
+
+    let id_to_idx: HashMap<u32, usize> = nodes
+        .iter()
+        .enumerate()
+        .map(|(i, (id, _))| (*id, i))
+        .collect();
+    let links: Vec<(usize, usize, f32)> = edges
+        .iter()
+        .filter_map(|&(s, t, w)| Some((*id_to_idx.get(&s)?, *id_to_idx.get(&t)?, w)))
+        // Springs only act inside a group: a cross-group edge would otherwise
+        // drag members out of their sphere towards the other group.
+        .filter(|&(s, t, _)| s != t && group_of[s] == group_of[t])
+        .collect();
+
+    // --- Force pass ---
+    let mut forces = vec![[0.0f32; 3]; n];
+    for _ in 0..config.iterations {
+        forces.iter_mut().for_each(|f| *f = [0.0; 3]);
+
+        for (i, pos) in positions.iter().enumerate() {
+            let own = group_of[i];
+            // Intra-group attraction: only outside the group radius.
+            let to_anchor = sub(anchors[own], *pos);
+            let d = len(to_anchor);
+            if d > radii[own] {
+                add_scaled(
+                    &mut forces[i],
+                    to_anchor,
+                    config.attraction * (d - radii[own]) / d,
+                );
+            }
+            // Inter-group repulsion: out of any foreign sphere.
+            for (gi, anchor) in anchors.iter().enumerate() {
+                if gi == own {
+                    continue;
+                }
+                let away = sub(*pos, *anchor);
+                let d = len(away).max(1e-3);
+                let reach = radii[gi] + config.group_gap / 2.0;
+                if d < reach {
+                    add_scaled(&mut forces[i], away, config.repulsion * (reach - d) / d);
+                }
+            }
+        }
+
+        for &(s, t, w) in &links {
+            let d = sub(positions[t], positions[s]);
+            let k = config.edge_strength * w.clamp(0.0, 1.0);
+            add_scaled(&mut forces[s], d, k);
+            add_scaled(&mut forces[t], d, -k);
+        }
+
+        for (pos, f) in positions.iter_mut().zip(&forces) {
+            add_scaled(pos, *f, 1.0);
+        }
+    }
+
+    info!("Computed group layout for {} nodes in {} groups", n, g);
+
+    GroupLayout {
+        positions: positions.into_iter().map(|p| (p[0], p[1], p[2])).collect(),
+        group_of,
+        anchors: keys
+            .iter()
+            .zip(anchors)
+            .zip(radii)
+            .map(|(((key, members), position), radius)| GroupAnchor {
+                key: key.to_string(),
+                position,
+                radius,
+                members: *members,
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn centroid(layout: &GroupLayout, group: usize) -> Vec3 {
+        let mut c = [0.0; 3];
+        let mut count = 0.0;
+        for (p, &g) in layout.positions.iter().zip(&layout.group_of) {
+            if g == group {
+                add_scaled(&mut c, [p.0, p.1, p.2], 1.0);
+                count += 1.0;
+            }
+        }
+        [c[0] / count, c[1] / count, c[2] / count]
+    }
+
+    #[test]
+    fn groups_are_separated_and_members_stay_near_their_anchor() {
+        let nodes: Vec<(u32, String)> = (0..30)
+            .map(|i| (i, ["rust", "go", "zig"][i as usize % 3].to_string()))
+            .collect();
+        // Cross-group edges should not pull the groups together.
+        let edges: Vec<(u32, u32, f32)> = (0..29).map(|i| (i, i + 1, 1.0)).collect();
+        let config = GroupLayoutConfig::default();
+        let layout = compute_group_layout(&nodes, &edges, &config);
+
+        assert_eq!(layout.anchors.len(), 3);
+        assert_eq!(layout.positions.len(), 30);
+        for (gi, anchor) in layout.anchors.iter().enumerate() {
+            let c = centroid(&layout, gi);
+            assert!(len(sub(c, anchor.position)) < anchor.radius);
+        }
+        for a in 0..3 {
+            for b in (a + 1)..3 {
+                let (ga, gb) = (&layout.anchors[a], &layout.anchors[b]);
+                assert!(len(sub(ga.position, gb.position)) >= ga.radius + gb.radius);
+            }
+        }
+        assert!(layout
+            .positions
+            .iter()
+            .all(|p| p.0.is_finite() && p.1.is_finite() && p.2.is_finite()));
+    }
+
+    #[test]
+    fn overflow_groups_fold_into_other() {
+        let nodes: Vec<(u32, String)> = (0..(MAX_GROUPS as u32 + 10))
+            .map(|i| (i, format!("g{}", i)))
+            .collect();
+        let layout = compute_group_layout(&nodes, &[], &GroupLayoutConfig::default());
+        assert_eq!(layout.anchors.len(), MAX_GROUPS);
+        let other = layout.anchors.last().unwrap();
+        assert_eq!(other.key, OTHER_GROUP);
+        assert_eq!(other.members, 11);
+    }
+
+    #[test]
+    fn group_keys_from_node_attributes() {
+        let now = DateTime::parse_from_rfc3339("2024-06-30T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut node = Node::new_with_id("ml/transformers".to_string(), Some(1));
+        node.label = "ML/Transformers".to_string();
+        node.metadata
+            .insert("tags".to_string(), " , AI, research".to_string());
+        // Written by the graph actor with chrono's `Display`.
+        node.metadata.insert(
+            "last_modified".to_string(),
+            "2024-06-10 00:00:00 UTC".to_string(),
+        );
+
+        assert_eq!(GroupBy::Tag.key_for(&node, now), "ai");
+        assert_eq!(GroupBy::Namespace.key_for(&node, now), "ml");
+        assert_eq!(GroupBy::NodeType.key_for(&node, now), UNGROUPED);
+        assert_eq!(GroupBy::AgeBucket.key_for(&node, now), "month");
+        node.metadata.insert(
+            "last_modified".to_string(),
+            "2024-06-28T12:00:00Z".to_string(),
+        );
+        assert_eq!(GroupBy::AgeBucket.key_for(&node, now), "week");
+        assert_eq!(GroupBy::parse("node_type"), Some(GroupBy::NodeType));
+        assert_eq!(GroupBy::parse("community"), None);
+    }
+}
@@ -1,2 +1,4 @@
 pub mod types;
 pub mod engines;
+pub mod group_by;
+pub mod coarsen;
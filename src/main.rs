@@ -31,6 +31,7 @@ use visionclaw_server::{
     services::nostr_bridge::NostrBridge,
     services::{
 
+        embedding_sync_service,
         github::{content_enhanced::EnhancedContentAPI, ContentAPI, GitHubClient, GitHubConfig},
         github_sync_service::GitHubSyncService,
         ragflow_service::RAGFlowService,
@@ -177,6 +178,56 @@ fn enforce_release_env_hygiene() {
 #[inline(always)]
 fn enforce_release_env_hygiene() {}
 
+/// Watch `metadata_file` and seed Oxigraph once it holds entries, then tell
+/// the actors to reload. Stops watching once the graph is ready.
+async fn build_graph_when_metadata_arrives(
+    metadata_file: std::path::PathBuf,
+    kg_repo: Arc<dyn visionclaw_server::ports::knowledge_graph_repository::KnowledgeGraphRepository>,
+    readiness: Arc<visionclaw_server::services::graph_readiness::GraphReadinessTracker>,
+    graph_service_addr: actix::Addr<visionclaw_server::actors::GraphServiceSupervisor>,
+) {
+    use visionclaw_server::services::file_service::{FileService, GraphSeedOutcome};
+    use visionclaw_server::services::graph_readiness::{watch_file, ReadinessEvent};
+
+    let (_watcher, mut changes) = match watch_file(&metadata_file) {
+        Ok(watch) => watch,
+        Err(e) => {
+            error!("[Readiness] {}; the graph will not be built until restart.", e);
+            readiness.record(ReadinessEvent::LoadFailed(e));
+            return;
+        }
+    };
+    info!("[Readiness] Watching {} for metadata", metadata_file.display());
+
+    // The file may have been written between the caller's check and the
+    // watcher starting, so look once before waiting for events.
+    loop {
+        readiness.record(ReadinessEvent::LoadStarted);
+        match FileService::load_graph_from_files(&kg_repo).await {
+            Ok(GraphSeedOutcome::NoMetadata) => {
+                readiness.record(ReadinessEvent::MetadataMissing(metadata_file.clone()));
+            }
+            Ok(GraphSeedOutcome::AlreadyPopulated { nodes } | GraphSeedOutcome::Seeded { nodes }) => {
+                info!("[Readiness] Metadata arrived; graph built with {} node(s)", nodes);
+                graph_service_addr.do_send(ReloadGraphFromDatabase);
+                readiness.record(ReadinessEvent::Loaded { nodes });
+                return;
+            }
+            Err(e) => {
+                warn!("[Readiness] Failed to build graph from metadata: {}", e);
+                readiness.record(ReadinessEvent::LoadFailed(e));
+            }
+        }
+
+        if changes.recv().await.is_none() {
+            return;
+        }
+        // Writers emit several events per save; let them settle and coalesce.
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        while changes.try_recv().is_ok() {}
+    }
+}
+
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     // Install a global panic hook that logs location + payload to stderr.
@@ -245,8 +296,14 @@ async fn main() -> std::io::Result<()> {
     let process_start_time = Instant::now();
 
     info!("--- Configuration Verification ---");
-    info!("MARKDOWN_DIR: {}", visionclaw_server::services::file_service::MARKDOWN_DIR);
-    info!("METADATA_PATH: {}", "/workspace/ext/data/metadata/metadata.json");
+    let storage = visionclaw_server::config::dev_config::storage();
+    info!("DATA_DIR: {}", storage.data_dir().display());
+    info!("MARKDOWN_DIR: {}", visionclaw_server::services::file_service::markdown_dir());
+    info!("METADATA_DIR: {}", storage.metadata_dir().display());
+    if let Some(ptx_dir) = visionclaw_server::config::dev_config::gpu().ptx_path.clone() {
+        info!("PTX_DIR: {}", ptx_dir.display());
+        visionclaw_server::utils::ptx::set_ptx_dir(ptx_dir);
+    }
     info!("---------------------------------");
 
     // REMOVED: init_logging()? call - using advanced_logging instead
@@ -262,19 +319,9 @@ async fn main() -> std::io::Result<()> {
 
     
     
-    let log_dir = if std::path::Path::new("/app/logs").exists() {
-        "/app/logs".to_string()
-    } else if std::path::Path::new("/workspace/ext/logs").exists() {
-        "/workspace/ext/logs".to_string()
-    } else {
-        
-        std::env::temp_dir()
-            .join("webxr_telemetry")
-            .to_string_lossy()
-            .to_string()
-    };
-
-    let log_dir = std::env::var("TELEMETRY_LOG_DIR").unwrap_or(log_dir);
+    let log_dir = std::env::var("TELEMETRY_LOG_DIR").unwrap_or_else(|_| {
+        storage.logs_dir().to_string_lossy().to_string()
+    });
 
     if let Err(e) = init_telemetry_logger(&log_dir, 100) {
         error!("Failed to initialize telemetry logger: {}", e);
@@ -282,14 +329,13 @@ async fn main() -> std::io::Result<()> {
         info!("Telemetry logger initialized with directory: {}", log_dir);
     }
 
+    // Before any sync lists the repo, so non-markdown sources are picked up.
+    visionclaw_server::services::parsers::content_parser::register_builtin_parsers();
+
     
     let settings = match AppFullSettings::new() {
         Ok(s) => {
-            info!(
-                "AppFullSettings loaded successfully from: {}",
-                std::env::var("SETTINGS_FILE_PATH")
-                    .unwrap_or_else(|_| "/app/settings.yaml".to_string())
-            );
+            info!("AppFullSettings defaults loaded; persisted settings come from the database");
 
             
             match to_json(&s.visualisation.rendering) {
@@ -327,6 +373,9 @@ async fn main() -> std::io::Result<()> {
                 }
             }
 
+            visionclaw_server::utils::log_throttle::set_interval_secs(
+                s.system.debug.hot_path_log_interval_secs,
+            );
             Arc::new(RwLock::new(s)) 
         }
         Err(e) => {
@@ -349,7 +398,7 @@ async fn main() -> std::io::Result<()> {
     // SQLite settings repository for routes (ADR-11 §D5).
     // SettingsActor removed: OptimizedSettingsActor in AppState is the single source of truth.
     info!("Initializing SQLite settings repository for routes");
-    let data_dir = std::env::var("DATA_DIR").unwrap_or_else(|_| "./data".to_string());
+    let data_dir = visionclaw_server::config::dev_config::storage().data_dir();
     let settings_db_path = std::path::Path::new(&data_dir).join("settings.sqlite3");
     let settings_repository = match visionclaw_server::adapters::SqliteSettingsRepository::open(&settings_db_path).await {
         Ok(repo) => Arc::new(repo),
@@ -543,18 +592,43 @@ async fn main() -> std::io::Result<()> {
     info!("[Startup] Step 2: Populating Oxigraph store from local files...");
     {
         use visionclaw_server::ports::knowledge_graph_repository::KnowledgeGraphRepository;
+        use visionclaw_server::services::file_service::{FileService, GraphSeedOutcome};
+        use visionclaw_server::services::graph_readiness::ReadinessEvent;
         let kg_repo: Arc<dyn KnowledgeGraphRepository> = app_state.graph_adapter.clone() as Arc<dyn KnowledgeGraphRepository>;
-        if let Err(e) = visionclaw_server::services::file_service::FileService::load_graph_from_files(&kg_repo).await {
-            error!("[Startup] FATAL: Failed to populate Oxigraph store: {}. Application is in DEGRADED state.", e);
-            app_state.set_degraded(format!("Oxigraph init failed: {}", e));
-        } else {
-            info!("[Startup] SUCCESS: Oxigraph store is populated and ready.");
+        app_state.graph_readiness.record(ReadinessEvent::LoadStarted);
+        match FileService::load_graph_from_files(&kg_repo).await {
+            Ok(GraphSeedOutcome::AlreadyPopulated { nodes } | GraphSeedOutcome::Seeded { nodes }) => {
+                info!("[Startup] SUCCESS: Oxigraph store is populated and ready.");
+                app_state.graph_readiness.record(ReadinessEvent::Loaded { nodes });
+            }
+            Ok(GraphSeedOutcome::NoMetadata) => {
+                // Build the graph whenever metadata.json turns up instead of
+                // starting empty; /readyz reports 503 until then.
+                let path = std::path::PathBuf::from(visionclaw_server::services::file_service::metadata_path());
+                warn!("[Startup] No metadata at {}; watching for it before building the graph.", path.display());
+                app_state.graph_readiness.record(ReadinessEvent::MetadataMissing(path.clone()));
+                tokio::spawn(build_graph_when_metadata_arrives(
+                    path,
+                    kg_repo,
+                    app_state.graph_readiness.clone(),
+                    app_state.graph_service_addr.clone(),
+                ));
+            }
+            Err(e) => {
+                error!("[Startup] FATAL: Failed to populate Oxigraph store: {}. Application is in DEGRADED state.", e);
+                app_state.set_degraded(format!("Oxigraph init failed: {}", e));
+                app_state.graph_readiness.record(ReadinessEvent::LoadFailed(e));
+            }
         }
     }
 
     // Step 3: Notify Actors.
     info!("[Startup] Step 3: Notifying actors to reload graph state from database...");
-    app_state.graph_service_addr.do_send(ReloadGraphFromDatabase);
+    embedding_sync_service::spawn_reload_and_update(
+        app_state.graph_service_addr.clone(),
+        app_state.settings_addr.clone(),
+        embedding_sync_service::UpdateTrigger::Startup,
+    );
     info!("[Startup] SUCCESS: Actors notified.");
     info!("--- Data Orchestration Sequence Complete ---");
 
@@ -650,6 +724,74 @@ async fn main() -> std::io::Result<()> {
         info!("[main] NostrBridge not started (VISIONCLAW_NOSTR_PRIVKEY or FORUM_RELAY_URL not set)");
     }
 
+    // Daily digest job; idles until `digest.enabled` is set.
+    let digest_service = Arc::new(visionclaw_server::services::digest_service::DigestService::new(
+        app_state.graph_service_addr.clone(),
+        app_state.settings_addr.clone(),
+        app_state.node_analytics.clone(),
+        Some(perplexity_service.clone()),
+    ));
+    digest_service.spawn();
+    info!("[main] Digest service scheduled");
+
+    // Background re-layout proposals; idles until `layoutRefresh.enabled` is set.
+    let layout_refresh_service = Arc::new(visionclaw_server::services::layout_refresh_service::LayoutRefreshService::new(
+        app_state.graph_service_addr.clone(),
+        app_state.settings_addr.clone(),
+        app_state.client_manager_addr.clone(),
+    ));
+    layout_refresh_service.spawn();
+
+    // Server-side page edits reach GitHub directly or via a PR (`github.writeMode`).
+    let write_back_service = Arc::new(visionclaw_server::services::write_back_service::WriteBackService::new(
+        github_pr_service.clone(),
+        content_api.clone(),
+    ));
+    write_back_service.clone().spawn_flush_loop(app_state.settings_addr.clone());
+
+    // Link-rot checks; idles until `linkCheck.enabled` is set.
+    let link_checker = Arc::new(visionclaw_server::services::link_checker_service::LinkCheckerService::new(
+        app_state.graph_service_addr.clone(),
+        app_state.settings_addr.clone(),
+    ));
+    link_checker.clone().spawn();
+
+    // Outgoing webhooks; drops events until `webhooks.enabled` lists endpoints.
+    let webhook_service = Arc::new(visionclaw_server::services::webhook_service::WebhookService::new(
+        app_state.settings_addr.clone(),
+    ));
+    webhook_service.clone().spawn();
+
+    // Compute budget: caps GPU kernel time / CPU share of the physics pipeline.
+    let compute_budget = settings.read().await.compute_budget.clone().unwrap_or_default();
+    match app_state
+        .graph_service_addr
+        .send(visionclaw_server::actors::messages::GetPhysicsOrchestratorActor)
+        .await
+    {
+        Ok(Ok(physics)) => physics.do_send(
+            visionclaw_server::actors::physics_orchestrator_actor::SetComputeBudget {
+                settings: compute_budget,
+            },
+        ),
+        _ => warn!("[main] Physics orchestrator unavailable; compute budget not applied"),
+    }
+
+    // Public read-only graph mirror; only mounted when `publicApi.enabled`.
+    let public_api_settings = settings.read().await.public_api.clone().unwrap_or_default();
+    let public_api_rpm = public_api_settings.requests_per_minute;
+    let public_graph_service = if public_api_settings.enabled {
+        let service = Arc::new(visionclaw_server::services::public_graph_service::PublicGraphService::new(
+            public_api_settings,
+            app_state.graph_service_addr.clone(),
+        ));
+        service.clone().spawn();
+        info!("[main] Public graph API enabled ({} req/min)", public_api_rpm);
+        Some(service)
+    } else {
+        None
+    };
+
     let app_state_data = web::Data::new(app_state);
     let validation_service = web::Data::new(validation_handler::ValidationService::new());
 
@@ -684,6 +826,8 @@ async fn main() -> std::io::Result<()> {
             motion_damping: s.system.websocket.motion_damping,
             heartbeat_interval_ms: s.system.websocket.heartbeat_interval, 
             heartbeat_timeout_ms: s.system.websocket.heartbeat_timeout,   
+            echo_suppression_ms: s.system.websocket.echo_suppression_ms,
+            idle_hibernation_ms: s.system.websocket.idle_hibernation_ms,
         }
     };
     let pre_read_ws_settings_data = web::Data::new(pre_read_ws_settings);
@@ -838,14 +982,26 @@ async fn main() -> std::io::Result<()> {
             .app_data(web::Data::new(github_sync_service.clone()))
             .app_data(web::Data::new(ontology_query_service.clone()))
             .app_data(web::Data::new(ontology_mutation_service.clone()))
+            .app_data(web::Data::new(write_back_service.clone()))
             .app_data(settings_repo_data.clone())
             .app_data(briefing_service.clone())
             .app_data(nostr_publisher.clone())
             .app_data(validation_service.clone())
             .app_data(physics_service.clone())
+            .app_data(web::Data::new(link_checker.clone()))
+            .app_data(web::Data::new(webhook_service.clone()))
             // PRD-008 — XR presence handler (Quest 3 native APK)
             .app_data(presence_handler_state.clone());
 
+            let app = match public_graph_service.clone() {
+                Some(public) => app
+                    .app_data(web::Data::new(public))
+                    .configure(|cfg| {
+                        visionclaw_server::handlers::configure_public_api_routes(cfg, public_api_rpm)
+                    }),
+                None => app,
+            };
+
             // Inject pre-initialised Solid pod state (avoids async init in worker threads)
             #[cfg(feature = "solid-pod-embed")]
             let app = app.app_data(solid_state.clone());
@@ -938,6 +1094,36 @@ async fn main() -> std::io::Result<()> {
                     // Layout mode system (ADR-031)
                     .configure(visionclaw_server::handlers::configure_layout_routes)
 
+                    // Vault maintenance (duplicate pages, link rot)
+                    .configure(visionclaw_server::handlers::configure_maintenance_routes)
+
+                    // Page write-back to GitHub (direct or PR mode)
+                    .configure(visionclaw_server::handlers::configure_write_back_routes)
+
+                    // Edge provenance (why two nodes are connected)
+                    .configure(visionclaw_server::handlers::configure_edge_routes)
+
+                    // Bulk node lookup (lazy hydration of visible nodes)
+                    .configure(visionclaw_server::handlers::configure_node_query_routes)
+
+                    // Outgoing webhooks (delivery log)
+                    .configure(visionclaw_server::handlers::configure_webhook_routes)
+
+                    // Camera recordings (replay export)
+                    .configure(visionclaw_server::handlers::configure_recording_routes)
+
+                    // XR room anchors
+                    .configure(visionclaw_server::handlers::configure_xr_anchor_routes)
+
+                    // Positional audio cues
+                    .configure(visionclaw_server::handlers::configure_audio_cue_routes)
+
+                    // Chat highlight layers
+                    .configure(visionclaw_server::handlers::configure_highlight_routes)
+
+                    // Graph lint report
+                    .configure(visionclaw_server::handlers::configure_lint_routes)
+
             );
 
             app
@@ -17,7 +17,9 @@ pub use visionclaw_domain::models::simulation_params::{
 };
 
 use visionclaw_domain::types::layout::LayoutMode;
-use visionclaw_domain::types::physics_config::{AutoBalanceConfig, AutoPauseConfig, PhysicsSettings};
+use visionclaw_domain::types::physics_config::{
+    AutoBalanceConfig, AutoPauseConfig, DragReleaseConfig, PhysicsSettings,
+};
 
 // GPU-aligned simulation parameters. Mirrors the CUDA `SimParams` struct;
 // must match its size and layout exactly (see `const _:()` assertion below).
@@ -110,6 +112,7 @@ impl SimParams {
             auto_balance_interval_ms: 100,
             auto_balance_config: AutoBalanceConfig::default(),
             auto_pause_config: AutoPauseConfig::default(),
+            drag_release: DragReleaseConfig::default(),
             equilibrium_stability_counter: 0,
             is_physics_paused: false,
             iterations: 100,
@@ -178,7 +178,9 @@ impl UserSettings {
     }
 
     fn get_settings_path(pubkey: &str) -> PathBuf {
-        PathBuf::from("/app/user_settings").join(format!("{}.yaml", pubkey))
+        crate::config::dev_config::storage()
+            .path("user_settings")
+            .join(format!("{}.yaml", pubkey))
     }
 
     
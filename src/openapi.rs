@@ -480,6 +480,15 @@ pub struct NodeWithPosition {
     /// Group membership
     #[serde(skip_serializing_if = "Option::is_none")]
     pub group: Option<String>,
+    /// Label priority in 0..=1 (only with `label_hints=true`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label_priority: Option<f32>,
+    /// Suggested label LOD level, 0 = always shown (only with `label_hints=true`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label_lod: Option<u8>,
+    /// Hex colour of the node's first tag, stable across sessions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag_color: Option<String>,
 }
 
 /// 3D vector
@@ -514,6 +523,9 @@ pub struct EdgeData {
     /// Relationship type URI
     #[serde(skip_serializing_if = "Option::is_none")]
     pub relationship_type: Option<String>,
+    /// Hex colour of a tag shared by both endpoints
+    #[serde(rename = "tagColor", skip_serializing_if = "Option::is_none")]
+    pub tag_color: Option<String>,
 }
 
 /// Metadata entry
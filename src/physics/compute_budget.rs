@@ -0,0 +1,181 @@
+//! Compute budget enforcement for the physics pipeline.
+//!
+//! The orchestrator feeds every completed step's busy time into
+//! [`ComputeBudget::record`], which returns the idle time required before the
+//! next step so that the long-run busy fraction stays under the configured cap
+//! (`maxGpuMsPerSecond` for GPU steps, `maxCpuPercent` for the CPU fallback).
+//! A one-second sliding window backs the usage figures reported in metrics.
+
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::config::ComputeBudgetSettings;
+
+const WINDOW: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetResource {
+    Gpu,
+    Cpu,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComputeBudgetUsage {
+    pub enabled: bool,
+    pub max_gpu_ms_per_second: f32,
+    pub max_cpu_percent: f32,
+    /// Busy milliseconds over the last second, per resource.
+    pub gpu_ms_last_second: f32,
+    pub cpu_ms_last_second: f32,
+    /// Steps whose successor was delayed to stay within budget.
+    pub throttled_steps: u64,
+    pub throttled_ms_total: f64,
+}
+
+#[derive(Debug)]
+pub struct ComputeBudget {
+    settings: ComputeBudgetSettings,
+    window: VecDeque<(Instant, BudgetResource, f32)>,
+    throttled_steps: u64,
+    throttled_ms_total: f64,
+}
+
+impl Default for ComputeBudget {
+    fn default() -> Self {
+        Self::new(ComputeBudgetSettings::default())
+    }
+}
+
+impl ComputeBudget {
+    pub fn new(settings: ComputeBudgetSettings) -> Self {
+        Self {
+            settings,
+            window: VecDeque::new(),
+            throttled_steps: 0,
+            throttled_ms_total: 0.0,
+        }
+    }
+
+    pub fn settings(&self) -> &ComputeBudgetSettings {
+        &self.settings
+    }
+
+    pub fn set_settings(&mut self, settings: ComputeBudgetSettings) {
+        self.settings = settings;
+    }
+
+    /// Allowed busy fraction for a resource, or `None` when uncapped.
+    fn duty_cycle(&self, resource: BudgetResource) -> Option<f32> {
+        if !self.settings.enabled {
+            return None;
+        }
+        let fraction = match resource {
+            BudgetResource::Gpu => self.settings.max_gpu_ms_per_second / 1000.0,
+            BudgetResource::Cpu => self.settings.max_cpu_percent / 100.0,
+        };
+        (fraction > 0.0 && fraction < 1.0).then_some(fraction)
+    }
+
+    fn prune(&mut self, now: Instant) {
+        while let Some(&(at, _, _)) = self.window.front() {
+            if now.duration_since(at) > WINDOW {
+                self.window.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Record a step that kept `resource` busy for `busy_ms` and return the
+    /// minimum idle time before the next step may start.
+    pub fn record(&mut self, resource: BudgetResource, busy_ms: f32, now: Instant) -> Duration {
+        self.prune(now);
+        let busy_ms = busy_ms.max(0.0);
+        self.window.push_back((now, resource, busy_ms));
+
+        let Some(fraction) = self.duty_cycle(resource) else {
+            return Duration::ZERO;
+        };
+        // busy / (busy + idle) <= fraction  =>  idle >= busy * (1 - f) / f
+        let idle_ms = busy_ms * (1.0 - fraction) / fraction;
+        Duration::from_secs_f32(idle_ms / 1000.0)
+    }
+
+    /// Note that the pipeline actually waited `extra` beyond its natural cadence.
+    pub fn note_throttled(&mut self, extra: Duration) {
+        if extra > Duration::ZERO {
+            self.throttled_steps += 1;
+            self.throttled_ms_total += extra.as_secs_f64() * 1000.0;
+        }
+    }
+
+    pub fn usage(&self, now: Instant) -> ComputeBudgetUsage {
+        let used = |r: BudgetResource| {
+            self.window
+                .iter()
+                .filter(|(at, res, _)| *res == r && now.duration_since(*at) <= WINDOW)
+                .map(|(_, _, ms)| *ms)
+                .sum::<f32>()
+        };
+        ComputeBudgetUsage {
+            enabled: self.settings.enabled,
+            max_gpu_ms_per_second: self.settings.max_gpu_ms_per_second,
+            max_cpu_percent: self.settings.max_cpu_percent,
+            gpu_ms_last_second: used(BudgetResource::Gpu),
+            cpu_ms_last_second: used(BudgetResource::Cpu),
+            throttled_steps: self.throttled_steps,
+            throttled_ms_total: self.throttled_ms_total,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capped(gpu_ms: f32, cpu_percent: f32) -> ComputeBudget {
+        ComputeBudget::new(ComputeBudgetSettings {
+            enabled: true,
+            max_gpu_ms_per_second: gpu_ms,
+            max_cpu_percent: cpu_percent,
+        })
+    }
+
+    #[test]
+    fn disabled_budget_never_delays() {
+        let mut budget = ComputeBudget::default();
+        assert_eq!(budget.record(BudgetResource::Gpu, 50.0, Instant::now()), Duration::ZERO);
+    }
+
+    #[test]
+    fn idle_time_keeps_duty_cycle_under_cap() {
+        // 250 ms/s => 25% duty cycle: a 10 ms step needs 30 ms idle.
+        let mut budget = capped(250.0, 100.0);
+        let idle = budget.record(BudgetResource::Gpu, 10.0, Instant::now());
+        assert!((idle.as_secs_f32() * 1000.0 - 30.0).abs() < 0.01);
+
+        // CPU cap is independent of the GPU cap.
+        let mut budget = capped(1000.0, 50.0);
+        let idle = budget.record(BudgetResource::Cpu, 8.0, Instant::now());
+        assert!((idle.as_secs_f32() * 1000.0 - 8.0).abs() < 0.01);
+        assert_eq!(budget.record(BudgetResource::Gpu, 8.0, Instant::now()), Duration::ZERO);
+    }
+
+    #[test]
+    fn usage_reports_sliding_window() {
+        let mut budget = capped(500.0, 100.0);
+        let start = Instant::now();
+        budget.record(BudgetResource::Gpu, 20.0, start);
+        budget.record(BudgetResource::Gpu, 30.0, start + Duration::from_millis(500));
+        budget.note_throttled(Duration::from_millis(5));
+
+        let usage = budget.usage(start + Duration::from_millis(600));
+        assert_eq!(usage.gpu_ms_last_second, 50.0);
+        assert_eq!(usage.throttled_steps, 1);
+
+        let later = budget.usage(start + Duration::from_millis(1200));
+        assert_eq!(later.gpu_ms_last_second, 30.0);
+    }
+}
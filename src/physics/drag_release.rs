@@ -0,0 +1,263 @@
+//! Server-side behaviour of a node when a drag ends.
+//!
+//! Drags never reach the GPU: the orchestrator overrides a dragged node's
+//! broadcast position instead. On release it consults
+//! `physics.dragRelease.mode` in settings so every client sees the same
+//! outcome:
+//!
+//! - `free` drops the override and the node rejoins the simulation.
+//! - `inertia` glides on with the velocity of the last drag movement,
+//!   losing `inertiaDecay` of its speed each frame.
+//! - `springBack` eases back to where the drag started over
+//!   `springBackFrames` frames.
+//! - `pin` keeps the override until the node is dragged again.
+//!
+//! The orchestrator feeds drag samples into a [`DragTrack`], turns it into a
+//! [`ReleasePlan`] on release, and advances any [`ReleaseMotion`] once per
+//! broadcast frame. The outcome is also written into the GPU simulation: on
+//! release the node is placed where the drag ended with its release
+//! velocity, a pinned node is held there, and a motion's last frame is
+//! written back when it ends, so the node never snaps back to a stale
+//! simulated position.
+
+use std::time::Instant;
+
+pub use crate::config::{DragReleaseConfig, DragReleaseMode};
+
+/// Glides end once slower than this, in world units per second.
+const MIN_GLIDE_SPEED: f32 = 1.0;
+
+/// Samples further apart than this carry no useful release velocity.
+const MAX_SAMPLE_GAP_SECS: f32 = 0.25;
+
+type Vec3 = [f32; 3];
+
+fn length(v: Vec3) -> f32 {
+    (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt()
+}
+
+/// Where a drag started and its two most recent samples.
+#[derive(Debug, Clone)]
+pub struct DragTrack {
+    origin: Vec3,
+    previous: Option<(Vec3, Instant)>,
+    last: (Vec3, Instant),
+}
+
+impl DragTrack {
+    pub fn start(position: Vec3, at: Instant) -> Self {
+        Self {
+            origin: position,
+            previous: None,
+            last: (position, at),
+        }
+    }
+
+    pub fn moved(&mut self, position: Vec3, at: Instant) {
+        self.previous = Some(self.last);
+        self.last = (position, at);
+    }
+
+    pub fn origin(&self) -> Vec3 {
+        self.origin
+    }
+
+    pub fn position(&self) -> Vec3 {
+        self.last.0
+    }
+
+    /// Velocity between the last two samples, in units per second. Zero if
+    /// the pointer stopped before release.
+    pub fn velocity(&self) -> Vec3 {
+        let Some((from, from_at)) = self.previous else {
+            return [0.0; 3];
+        };
+        let (to, to_at) = self.last;
+        let dt = to_at.saturating_duration_since(from_at).as_secs_f32();
+        if dt <= 0.0 || dt > MAX_SAMPLE_GAP_SECS {
+            return [0.0; 3];
+        }
+        [
+            (to[0] - from[0]) / dt,
+            (to[1] - from[1]) / dt,
+            (to[2] - from[2]) / dt,
+        ]
+    }
+}
+
+/// A node moving under server control after release.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReleaseMotion {
+    Glide {
+        position: Vec3,
+        velocity: Vec3,
+        decay: f32,
+    },
+    Return {
+        from: Vec3,
+        to: Vec3,
+        frame: u32,
+        frames: u32,
+    },
+}
+
+impl ReleaseMotion {
+    /// Position and velocity to broadcast for the current frame.
+    pub fn current(&self) -> (Vec3, Vec3) {
+        match *self {
+            ReleaseMotion::Glide {
+                position, velocity, ..
+            } => (position, velocity),
+            ReleaseMotion::Return {
+                from,
+                to,
+                frame,
+                frames,
+            } => {
+                let t = frame as f32 / frames as f32;
+                let eased = t * t * (3.0 - 2.0 * t);
+                let lerp = |a: f32, b: f32| a + (b - a) * eased;
+                (
+                    [
+                        lerp(from[0], to[0]),
+                        lerp(from[1], to[1]),
+                        lerp(from[2], to[2]),
+                    ],
+                    [0.0; 3],
+                )
+            }
+        }
+    }
+
+    /// Advance one frame of `dt` seconds; false once the motion has finished.
+    pub fn advance(&mut self, dt: f32) -> bool {
+        match self {
+            ReleaseMotion::Glide {
+                position,
+                velocity,
+                decay,
+            } => {
+                for axis in 0..3 {
+                    position[axis] += velocity[axis] * dt;
+                    velocity[axis] *= *decay;
+                }
+                length(*velocity) >= MIN_GLIDE_SPEED
+            }
+            ReleaseMotion::Return { frame, frames, .. } => {
+                *frame = (*frame + 1).min(*frames);
+                *frame < *frames
+            }
+        }
+    }
+}
+
+/// What the orchestrator does with a node at the end of a drag.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReleasePlan {
+    Free,
+    Pin(Vec3),
+    Move(ReleaseMotion),
+}
+
+impl ReleasePlan {
+    /// `max_speed` caps the inertia glide, normally the simulation's
+    /// `max_velocity`.
+    pub fn for_release(config: &DragReleaseConfig, track: &DragTrack, max_speed: f32) -> Self {
+        match config.mode {
+            DragReleaseMode::Free => ReleasePlan::Free,
+            DragReleaseMode::Pin => ReleasePlan::Pin(track.position()),
+            DragReleaseMode::Inertia => {
+                let mut velocity = track.velocity().map(|v| v * config.inertia_scale);
+                let speed = length(velocity);
+                if speed < MIN_GLIDE_SPEED {
+                    return ReleasePlan::Free;
+                }
+                if max_speed > 0.0 && speed > max_speed {
+                    velocity = velocity.map(|v| v * max_speed / speed);
+                }
+                ReleasePlan::Move(ReleaseMotion::Glide {
+                    position: track.position(),
+                    velocity,
+                    decay: config.inertia_decay.clamp(0.0, 0.99),
+                })
+            }
+            DragReleaseMode::SpringBack => {
+                if track.position() == track.origin() {
+                    return ReleasePlan::Free;
+                }
+                ReleasePlan::Move(ReleaseMotion::Return {
+                    from: track.position(),
+                    to: track.origin(),
+                    frame: 0,
+                    frames: config.spring_back_frames.max(1),
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn config(mode: DragReleaseMode) -> DragReleaseConfig {
+        DragReleaseConfig {
+            mode,
+            ..DragReleaseConfig::default()
+        }
+    }
+
+    #[test]
+    fn release_modes() {
+        let t0 = Instant::now();
+        let mut track = DragTrack::start([0.0, 0.0, 0.0], t0);
+        track.moved([5.0, 0.0, 0.0], t0 + Duration::from_millis(50));
+        track.moved([10.0, 0.0, 0.0], t0 + Duration::from_millis(100));
+        assert_eq!(track.velocity(), [100.0, 0.0, 0.0]);
+
+        assert_eq!(
+            ReleasePlan::for_release(&config(DragReleaseMode::Free), &track, 50.0),
+            ReleasePlan::Free
+        );
+        assert_eq!(
+            ReleasePlan::for_release(&config(DragReleaseMode::Pin), &track, 50.0),
+            ReleasePlan::Pin([10.0, 0.0, 0.0])
+        );
+
+        // Inertia is capped at max_speed and decays to a stop ahead of the drop.
+        let ReleasePlan::Move(mut glide) =
+            ReleasePlan::for_release(&config(DragReleaseMode::Inertia), &track, 50.0)
+        else {
+            panic!("expected a glide");
+        };
+        assert_eq!(glide.current().1, [50.0, 0.0, 0.0]);
+        let mut frames = 0;
+        while glide.advance(1.0 / 60.0) {
+            frames += 1;
+            assert!(frames < 1000, "glide never stopped");
+        }
+        assert!(glide.current().0[0] > 10.0);
+
+        // Spring-back eases from the drop to the origin in the set frames.
+        let ReleasePlan::Move(mut spring) =
+            ReleasePlan::for_release(&config(DragReleaseMode::SpringBack), &track, 50.0)
+        else {
+            panic!("expected a return");
+        };
+        assert_eq!(spring.current().0, [10.0, 0.0, 0.0]);
+        let frames = config(DragReleaseMode::SpringBack).spring_back_frames;
+        for _ in 1..frames {
+            assert!(spring.advance(1.0 / 60.0));
+        }
+        assert!(!spring.advance(1.0 / 60.0));
+        assert_eq!(spring.current().0, [0.0, 0.0, 0.0]);
+
+        // A pointer that paused before release carries no inertia.
+        track.moved([10.0, 0.0, 0.0], t0 + Duration::from_secs(1));
+        assert_eq!(
+            ReleasePlan::for_release(&config(DragReleaseMode::Inertia), &track, 50.0),
+            ReleasePlan::Free
+        );
+    }
+}
@@ -0,0 +1,208 @@
+//! In-place edge weight updates.
+//!
+//! A new mention between two pages that are already linked changes only the
+//! weight of their edge. Rather than diffing or rebuilding the graph, the
+//! change travels as `(source, target, weight)` tuples: the graph state actor
+//! patches its edge list and the GPU actor patches the spring weights in its
+//! CSR arrays, leaving node buffers and the CSR structure untouched. Edges
+//! act as undirected springs, so a tuple matches either orientation.
+
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use visionclaw_domain::models::edge::Edge;
+
+/// Largest batch accepted in one update.
+pub const MAX_WEIGHT_UPDATES: usize = 100_000;
+
+/// `(source, target, new_weight)` with compact node ids.
+pub type EdgeWeightUpdate = (u32, u32, f32);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EdgeWeightReport {
+    /// Node pairs whose edge weight was changed.
+    pub updated: usize,
+    /// Node pairs with no edge between them.
+    pub unknown: usize,
+}
+
+fn pair_key(a: u32, b: u32) -> (u32, u32) {
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// Check `updates` and reduce them to one weight per node pair; a later
+/// tuple for the same pair wins.
+pub fn collect_updates(updates: &[EdgeWeightUpdate]) -> Result<HashMap<(u32, u32), f32>, String> {
+    if updates.len() > MAX_WEIGHT_UPDATES {
+        return Err(format!(
+            "{} weight updates exceeds the limit of {}",
+            updates.len(),
+            MAX_WEIGHT_UPDATES
+        ));
+    }
+    let mut weights = HashMap::with_capacity(updates.len());
+    for &(source, target, weight) in updates {
+        if !weight.is_finite() || weight < 0.0 {
+            return Err(format!(
+                "Invalid weight {} for edge {} -> {}",
+                weight, source, target
+            ));
+        }
+        weights.insert(pair_key(source, target), weight);
+    }
+    Ok(weights)
+}
+
+/// Set the weight of every edge in `edges` whose endpoints match a pair in
+/// `weights`. Returns the changed edges' indices and the report.
+pub fn apply_to_edges(
+    edges: &mut [Edge],
+    weights: &HashMap<(u32, u32), f32>,
+) -> (Vec<usize>, EdgeWeightReport) {
+    let mut matched = HashSet::with_capacity(weights.len());
+    let mut changed = Vec::new();
+    for (i, edge) in edges.iter_mut().enumerate() {
+        let key = pair_key(edge.source, edge.target);
+        if let Some(&weight) = weights.get(&key) {
+            matched.insert(key);
+            if edge.weight != weight {
+                edge.weight = weight;
+                changed.push(i);
+            }
+        }
+    }
+    let report = EdgeWeightReport {
+        updated: matched.len(),
+        unknown: weights.len() - matched.len(),
+    };
+    (changed, report)
+}
+
+/// Set the weights of the CSR entries for each pair in `weights`, in both
+/// directions. `node_index` maps node ids to CSR rows.
+pub fn apply_to_csr(
+    row_offsets: &[u32],
+    col_indices: &[u32],
+    csr_weights: &mut [f32],
+    node_index: &HashMap<u32, usize>,
+    weights: &HashMap<(u32, u32), f32>,
+) -> EdgeWeightReport {
+    let mut set_row = |row: usize, col: usize, weight: f32| -> bool {
+        let (Some(&start), Some(&end)) = (row_offsets.get(row), row_offsets.get(row + 1)) else {
+            return false;
+        };
+        let mut found = false;
+        for entry in start as usize..(end as usize).min(col_indices.len()) {
+            if col_indices[entry] as usize == col {
+                csr_weights[entry] = weight;
+                found = true;
+            }
+        }
+        found
+    };
+
+    let mut report = EdgeWeightReport::default();
+    for (&(a, b), &weight) in weights {
+        let found = match (node_index.get(&a), node_index.get(&b)) {
+            (Some(&ia), Some(&ib)) => {
+                // Self-loops are stored once; `|` keeps both calls.
+                set_row(ia, ib, weight) | set_row(ib, ia, weight)
+            }
+            _ => false,
+        };
+        if found {
+            report.updated += 1;
+        } else {
+            report.unknown += 1;
+        }
+    }
+    report
+}
+
+/// Weight changes taking `old` to `new`, or `None` when the two edge lists
+/// do not link the same node pairs and so need a structural update.
+pub fn weight_changes(old: &[Edge], new: &[Edge]) -> Option<Vec<EdgeWeightUpdate>> {
+    let weights = |edges: &[Edge]| -> HashMap<(u32, u32), f32> {
+        edges
+            .iter()
+            .map(|e| (pair_key(e.source, e.target), e.weight))
+            .collect()
+    };
+    let old = weights(old);
+    let new = weights(new);
+    if old.len() != new.len() {
+        return None;
+    }
+    let mut updates = Vec::new();
+    for (&(a, b), &weight) in &new {
+        match old.get(&(a, b)) {
+            Some(&previous) if previous == weight => {}
+            Some(_) => updates.push((a, b, weight)),
+            None => return None,
+        }
+    }
+    Some(updates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn patches_edges_and_csr_in_both_directions() {
+        let weights =
+            collect_updates(&[(2, 1, 3.0), (0, 1, 9.0), (0, 1, 2.0), (0, 2, 1.0)]).unwrap();
+        assert_eq!(weights.len(), 3);
+        assert!(collect_updates(&[(0, 1, f32::NAN)]).is_err());
+        assert!(collect_updates(&[(0, 1, -1.0)]).is_err());
+
+        let mut edges = vec![Edge::new(0, 1, 1.0), Edge::new(1, 2, 1.0)];
+        let (changed, report) = apply_to_edges(&mut edges, &weights);
+        assert_eq!(changed, vec![0, 1]);
+        assert_eq!(
+            report,
+            EdgeWeightReport {
+                updated: 2,
+                unknown: 1
+            }
+        );
+        assert_eq!((edges[0].weight, edges[1].weight), (2.0, 3.0));
+
+        // CSR for 0-1 and 1-2, mirrored: row 0 -> [1], row 1 -> [0, 2], row 2 -> [1].
+        let row_offsets = [0, 1, 3, 4];
+        let col_indices = [1, 0, 2, 1];
+        let mut csr_weights = [1.0; 4];
+        let node_index: HashMap<u32, usize> = (0..3).map(|i| (i, i as usize)).collect();
+        let report = apply_to_csr(
+            &row_offsets,
+            &col_indices,
+            &mut csr_weights,
+            &node_index,
+            &weights,
+        );
+        assert_eq!(
+            report,
+            EdgeWeightReport {
+                updated: 2,
+                unknown: 1
+            }
+        );
+        assert_eq!(csr_weights, [2.0, 2.0, 3.0, 3.0]);
+    }
+
+    #[test]
+    fn weight_changes_only_for_the_same_pairs() {
+        let old = vec![Edge::new(0, 1, 1.0), Edge::new(1, 2, 1.0)];
+        let reweighted = vec![Edge::new(1, 0, 1.0), Edge::new(1, 2, 4.0)];
+        assert_eq!(weight_changes(&old, &reweighted), Some(vec![(1, 2, 4.0)]));
+        assert_eq!(weight_changes(&old, &old), Some(Vec::new()));
+
+        let rewired = vec![Edge::new(0, 1, 1.0), Edge::new(0, 2, 1.0)];
+        assert_eq!(weight_changes(&old, &rewired), None);
+        assert_eq!(weight_changes(&old, &old[..1]), None);
+    }
+}
@@ -0,0 +1,84 @@
+//! Seeding the live layout from positions a client saved earlier.
+//!
+//! A returning client can send the layout it stored locally right after the
+//! handshake. Positions are matched to the current graph by node id and
+//! written into the GPU buffers before the next step; nodes the client does
+//! not know keep their current position. A layout whose ids mostly miss the
+//! current graph is refused rather than half-applied.
+
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Largest layout accepted in one seed.
+pub const MAX_SEED_POSITIONS: usize = 200_000;
+/// Fraction of submitted ids that must exist in the graph.
+pub const MIN_MATCH_RATIO: f32 = 0.5;
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeedReport {
+    /// Positions written into the layout.
+    pub applied: usize,
+    /// Submitted ids that are not in the graph.
+    pub unknown: usize,
+    /// Nodes in the graph; those not applied keep their position.
+    pub total_nodes: usize,
+}
+
+/// Overwrite the entries of `x`/`y`/`z` (GPU index order) whose node id is
+/// in `seed`. Returns the number written.
+pub fn apply_seed(
+    index_to_node_id: &[u32],
+    seed: &HashMap<u32, [f32; 3]>,
+    x: &mut [f32],
+    y: &mut [f32],
+    z: &mut [f32],
+) -> usize {
+    let mut applied = 0;
+    for (i, node_id) in index_to_node_id.iter().enumerate().take(x.len()) {
+        if let Some(&[px, py, pz]) = seed.get(node_id) {
+            x[i] = px;
+            y[i] = py;
+            z[i] = pz;
+            applied += 1;
+        }
+    }
+    applied
+}
+
+/// `Err` if too few of `submitted` ids matched to trust the layout.
+pub fn check_match(applied: usize, submitted: usize) -> Result<(), String> {
+    if submitted == 0 {
+        return Err("Layout contains no positions".to_string());
+    }
+    if (applied as f32) < submitted as f32 * MIN_MATCH_RATIO {
+        return Err(format!(
+            "Only {} of {} saved nodes exist in the current graph",
+            applied, submitted
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn applies_matching_ids_and_rejects_stale_layouts() {
+        let ids = [10, 11, 12];
+        let (mut x, mut y, mut z) = (vec![0.0; 3], vec![0.0; 3], vec![0.0; 3]);
+        let seed: HashMap<u32, [f32; 3]> = [(12, [1.0, 2.0, 3.0]), (99, [9.0, 9.0, 9.0])]
+            .into_iter()
+            .collect();
+
+        let applied = apply_seed(&ids, &seed, &mut x, &mut y, &mut z);
+        assert_eq!(applied, 1);
+        assert_eq!((x[2], y[2], z[2]), (1.0, 2.0, 3.0));
+        assert_eq!(x[0], 0.0, "unseeded nodes keep their position");
+
+        assert!(check_match(applied, seed.len()).is_ok());
+        assert!(check_match(1, 3).is_err());
+        assert!(check_match(0, 0).is_err());
+    }
+}
@@ -21,6 +21,12 @@
 //!   physics constraints, bridging semantic reasoning with physical simulation to
 //!   enforce ontological relationships in graph layout.
 //!
+//! CPU versions of the kernel's force kinds (springs, repulsion, gravity,
+//! collision, constraints) live as composable `ForceModel`s in the
+//! standalone `visionclaw-physics` crate, with their own Criterion
+//! benchmarks (`cargo bench -p visionclaw-physics`). They are a separate
+//! copy written from the kernel, not what the GPU or `simd_forces` runs.
+//!
 //! ## Integration
 //!
 //! This module integrates with:
@@ -59,11 +65,20 @@
 //! solver.optimize(&mut graph_data, &final_constraint_set)?;
 //! ```
 
+pub mod compute_budget;
+pub mod drag_release;
+pub mod edge_weights;
+pub mod layout_seed;
 pub mod lsh;
 pub mod ontology_constraint_mapper;
 pub mod ontology_constraints;
+pub mod quality;
 pub mod semantic_constraints;
 pub mod simd_forces;
+pub mod simulation_control;
+pub mod simulation_watchdog;
+pub mod stability_monitor;
+pub mod step_profile;
 pub mod stress_majorization;
 
 // Phase 5 (ADR-01 D5): LayoutEngine trait + five engine implementations.
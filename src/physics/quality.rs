@@ -0,0 +1,148 @@
+//! Named simulation quality levels.
+//!
+//! A level bundles the knobs that trade layout fidelity for GPU time, so an
+//! operator on a shared or thermally limited GPU can turn the simulation down
+//! with one call instead of tuning each parameter:
+//!
+//! | level  | tick rate | collision | readback       |
+//! |--------|-----------|-----------|----------------|
+//! | high   | 60 Hz     | allowed   | every step     |
+//! | medium | 30 Hz     | allowed   | every 2nd step |
+//! | low    | 20 Hz     | off       | every 3rd step |
+//!
+//! `high` is the default and matches the simulation's behaviour before
+//! levels existed. The tick rate sets the orchestrator's Continuous-mode
+//! cadence (FastSettle still runs flat out). Collision can only be turned
+//! off by a level: where it is allowed, the operator's collision setting
+//! decides. Readback is the download of positions for broadcast; steps in
+//! between still integrate on the GPU but report no kinetic energy, so
+//! convergence and the divergence guard see only read-back steps, and
+//! FastSettle reads back every step. Forces are untouched: the GPU kernel
+//! has no Barnes-Hut tree, so there is no theta to trade, and every level
+//! settles to the same layout.
+//!
+//! The level is process-wide and kept in `simulation_quality.json` under
+//! the data dir; the orchestrator and semantic forces read it as they go.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+use std::time::Duration;
+
+use crate::config::dev_config;
+use crate::services::json_file::JsonFile;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QualityLevel {
+    Low,
+    Medium,
+    #[default]
+    High,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QualityPreset {
+    pub level: QualityLevel,
+    pub tick_hz: u32,
+    /// Whether collision may run; the operator's setting still applies.
+    pub collision: bool,
+    /// Positions are read back every this many steps.
+    pub readback_every: u32,
+}
+
+impl QualityLevel {
+    pub fn preset(self) -> QualityPreset {
+        let (tick_hz, collision, readback_every) = match self {
+            QualityLevel::High => (60, true, 1),
+            QualityLevel::Medium => (30, true, 2),
+            QualityLevel::Low => (20, false, 3),
+        };
+        QualityPreset {
+            level: self,
+            tick_hz,
+            collision,
+            readback_every,
+        }
+    }
+}
+
+impl std::str::FromStr for QualityLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "low" => Ok(QualityLevel::Low),
+            "medium" => Ok(QualityLevel::Medium),
+            "high" => Ok(QualityLevel::High),
+            _ => Err(format!(
+                "Unknown quality level {:?}; expected low, medium or high",
+                s
+            )),
+        }
+    }
+}
+
+impl QualityPreset {
+    /// Target time between Continuous-mode steps.
+    pub fn tick_interval(&self) -> Duration {
+        Duration::from_millis(1000 / u64::from(self.tick_hz.max(1)))
+    }
+
+    /// Whether positions should be read back after `iteration`.
+    pub fn reads_back(&self, iteration: u32) -> bool {
+        self.readback_every <= 1 || iteration % self.readback_every == 0
+    }
+}
+
+fn quality_file() -> JsonFile {
+    JsonFile::new(
+        dev_config::storage().path("simulation_quality.json"),
+        "simulation quality",
+    )
+}
+
+static QUALITY: Lazy<RwLock<QualityLevel>> = Lazy::new(|| RwLock::new(quality_file().load()));
+
+/// The preset currently in force.
+pub fn simulation_quality() -> QualityPreset {
+    QUALITY.read().unwrap_or_else(|e| e.into_inner()).preset()
+}
+
+pub fn set_simulation_quality(level: QualityLevel) -> QualityPreset {
+    *QUALITY.write().unwrap_or_else(|e| e.into_inner()) = level;
+    level.preset()
+}
+
+/// Keep `level` for the next start. Blocking; call off the async threads.
+pub fn save_simulation_quality(level: QualityLevel) -> Result<(), String> {
+    quality_file().save(&level)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_trade_fidelity_for_cost() {
+        let high = QualityLevel::High.preset();
+        assert_eq!(high.tick_interval(), Duration::from_millis(16));
+        assert!((0..5).all(|i| high.reads_back(i)));
+
+        let low = QualityLevel::Low.preset();
+        assert_eq!(low.tick_interval(), Duration::from_millis(50));
+        assert!(!low.collision);
+        let read: Vec<u32> = (0..7).filter(|&i| low.reads_back(i)).collect();
+        assert_eq!(read, vec![0, 3, 6]);
+
+        let medium = QualityLevel::Medium.preset();
+        assert!(medium.tick_hz < high.tick_hz && medium.tick_hz > low.tick_hz);
+
+        assert_eq!("Medium".parse::<QualityLevel>(), Ok(QualityLevel::Medium));
+        assert!("ultra".parse::<QualityLevel>().is_err());
+        let json = serde_json::to_value(low).unwrap();
+        assert_eq!(json["level"], "low");
+        assert_eq!(json["readbackEvery"], 3);
+    }
+}
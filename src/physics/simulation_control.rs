@@ -0,0 +1,162 @@
+//! Operator pause, resume and single-step for the physics loop.
+//!
+//! This is separate from the orchestrator's own `is_physics_paused`, which
+//! the equilibrium check sets and any interaction clears. An operator pause
+//! holds until an explicit resume, whatever the layout is doing, so a
+//! presenter can freeze the scene. While paused, `step` lets exactly N
+//! physics steps through, running them even if the layout had settled, and
+//! then holds again.
+//!
+//! The orchestrator asks [`SimulationControl::gate`] before every step and
+//! calls [`SimulationControl::step_taken`] once a step has run; a step the
+//! GPU skipped is not counted.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// Largest number of steps one `step` request may queue.
+pub const MAX_STEPS_PER_REQUEST: u32 = 10_000;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(tag = "mode", rename_all = "camelCase")]
+pub enum SimulationMode {
+    #[default]
+    Running,
+    Paused,
+    /// Paused, with `remaining` steps still to run.
+    Stepping {
+        remaining: u32,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SimulationCommand {
+    Pause,
+    Resume,
+    Step { steps: u32 },
+}
+
+/// What the physics loop should do with its next tick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepGate {
+    /// Normal operation; the orchestrator's own pause rules apply.
+    Run,
+    /// Run one step regardless of equilibrium or settle state.
+    Single,
+    /// Do nothing until resumed or stepped.
+    Hold,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationControlStatus {
+    #[serde(flatten)]
+    pub mode: SimulationMode,
+    /// Steps run through `step` since startup.
+    pub single_steps: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct SimulationControl {
+    mode: SimulationMode,
+    single_steps: u64,
+}
+
+impl SimulationControl {
+    pub fn apply(&mut self, command: SimulationCommand) -> Result<SimulationControlStatus, String> {
+        self.mode = match command {
+            SimulationCommand::Pause => SimulationMode::Paused,
+            SimulationCommand::Resume => SimulationMode::Running,
+            SimulationCommand::Step { steps } => {
+                if steps == 0 || steps > MAX_STEPS_PER_REQUEST {
+                    return Err(format!(
+                        "steps must be between 1 and {}, got {}",
+                        MAX_STEPS_PER_REQUEST, steps
+                    ));
+                }
+                // Stepping a running simulation pauses it first.
+                let queued = match self.mode {
+                    SimulationMode::Stepping { remaining } => remaining,
+                    _ => 0,
+                };
+                SimulationMode::Stepping {
+                    remaining: queued.saturating_add(steps).min(MAX_STEPS_PER_REQUEST),
+                }
+            }
+        };
+        Ok(self.status())
+    }
+
+    pub fn gate(&self) -> StepGate {
+        match self.mode {
+            SimulationMode::Running => StepGate::Run,
+            SimulationMode::Paused => StepGate::Hold,
+            SimulationMode::Stepping { .. } => StepGate::Single,
+        }
+    }
+
+    /// Record that a gated single step ran.
+    pub fn step_taken(&mut self) {
+        if let SimulationMode::Stepping { remaining } = self.mode {
+            self.single_steps += 1;
+            self.mode = if remaining > 1 {
+                SimulationMode::Stepping {
+                    remaining: remaining - 1,
+                }
+            } else {
+                SimulationMode::Paused
+            };
+        }
+    }
+
+    pub fn status(&self) -> SimulationControlStatus {
+        SimulationControlStatus {
+            mode: self.mode,
+            single_steps: self.single_steps,
+        }
+    }
+}
+
+static CONTROL: Lazy<Mutex<SimulationControl>> =
+    Lazy::new(|| Mutex::new(SimulationControl::default()));
+
+/// Process-wide control shared by the orchestrator, REST and WebSocket.
+pub fn simulation_control() -> std::sync::MutexGuard<'static, SimulationControl> {
+    CONTROL.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_exactly_then_holds() {
+        let mut control = SimulationControl::default();
+        assert_eq!(control.gate(), StepGate::Run);
+        control.step_taken();
+        assert_eq!(control.status().single_steps, 0, "only gated steps count");
+
+        control.apply(SimulationCommand::Pause).unwrap();
+        assert_eq!(control.gate(), StepGate::Hold);
+        assert!(control.apply(SimulationCommand::Step { steps: 0 }).is_err());
+
+        control.apply(SimulationCommand::Step { steps: 1 }).unwrap();
+        let status = control.apply(SimulationCommand::Step { steps: 2 }).unwrap();
+        assert_eq!(status.mode, SimulationMode::Stepping { remaining: 3 });
+        for _ in 0..3 {
+            assert_eq!(control.gate(), StepGate::Single);
+            control.step_taken();
+        }
+        assert_eq!(control.gate(), StepGate::Hold);
+        assert_eq!(control.status().single_steps, 3);
+
+        let status = control.apply(SimulationCommand::Resume).unwrap();
+        assert_eq!(status.mode, SimulationMode::Running);
+        assert_eq!(
+            serde_json::to_value(status).unwrap(),
+            serde_json::json!({"mode": "running", "singleSteps": 3})
+        );
+    }
+}
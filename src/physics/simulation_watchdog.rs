@@ -0,0 +1,226 @@
+//! Detects a physics pipeline that has stopped making progress.
+//!
+//! The sequential pipeline only advances when the GPU actor answers each
+//! `ComputeForces` with a `PhysicsStepCompleted`. If that reply never comes
+//! (a hung GPU future) or only ever reports skipped steps (a wedged
+//! `is_computing` flag), positions freeze without any error. The orchestrator
+//! feeds every completed step into a [`SimulationWatchdog`] and polls it from
+//! its heartbeat; a stall past the threshold triggers a restart of the loop.
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// No real step for this long while one is expected counts as a stall.
+pub const DEFAULT_STALL_AFTER: Duration = Duration::from_secs(5);
+/// Restarts attempted without any progress before the watchdog gives up
+/// until the next real step.
+pub const MAX_RESTARTS: u32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StallKind {
+    /// No step reply at all.
+    NoReply,
+    /// Replies keep arriving but every step was skipped.
+    SkippedSteps,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StallIncident {
+    pub kind: StallKind,
+    pub stalled_ms: u64,
+    /// Last iteration that completed before the stall.
+    pub iteration: u64,
+    /// Restart attempt this incident triggered, starting at 1.
+    pub attempt: u32,
+    pub detected_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchdogStats {
+    pub stalls: u64,
+    pub restarts: u64,
+    /// Stalls left unhandled because the restart limit was reached.
+    pub abandoned: u64,
+    pub last_incident: Option<StallIncident>,
+}
+
+#[derive(Default)]
+struct WatchdogCounters {
+    stalls: AtomicU64,
+    restarts: AtomicU64,
+    abandoned: AtomicU64,
+    last_incident: Mutex<Option<StallIncident>>,
+}
+
+static COUNTERS: Lazy<WatchdogCounters> = Lazy::new(WatchdogCounters::default);
+
+/// Process-wide watchdog counters for `/api/metrics`.
+pub fn watchdog_stats() -> WatchdogStats {
+    WatchdogStats {
+        stalls: COUNTERS.stalls.load(Ordering::Relaxed),
+        restarts: COUNTERS.restarts.load(Ordering::Relaxed),
+        abandoned: COUNTERS.abandoned.load(Ordering::Relaxed),
+        last_incident: COUNTERS
+            .last_incident
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone(),
+    }
+}
+
+/// What the orchestrator should do about a stall.
+#[derive(Debug, Clone)]
+pub enum StallAction {
+    Restart(StallIncident),
+    /// The restart limit was hit; the incident is reported once and the
+    /// watchdog stays quiet until a real step completes.
+    GiveUp(StallIncident),
+}
+
+#[derive(Debug)]
+pub struct SimulationWatchdog {
+    stall_after: Duration,
+    last_reply: Instant,
+    last_progress: Instant,
+    last_iteration: u64,
+    attempts: u32,
+    gave_up: bool,
+}
+
+impl Default for SimulationWatchdog {
+    fn default() -> Self {
+        Self::new(DEFAULT_STALL_AFTER, Instant::now())
+    }
+}
+
+impl SimulationWatchdog {
+    pub fn new(stall_after: Duration, now: Instant) -> Self {
+        Self {
+            stall_after,
+            last_reply: now,
+            last_progress: now,
+            last_iteration: 0,
+            attempts: 0,
+            gave_up: false,
+        }
+    }
+
+    /// Note a `PhysicsStepCompleted`. Only a step that was not skipped counts
+    /// as progress and clears the restart count.
+    pub fn record_step(&mut self, iteration: u64, skipped: bool, now: Instant) {
+        self.last_reply = now;
+        if !skipped {
+            self.last_progress = now;
+            self.last_iteration = iteration;
+            self.attempts = 0;
+            self.gave_up = false;
+        }
+    }
+
+    /// Poll from the heartbeat. `expecting` is whether the pipeline should be
+    /// stepping right now; time spent paused, settled or initialising never
+    /// counts towards a stall.
+    pub fn check(&mut self, expecting: bool, now: Instant) -> Option<StallAction> {
+        if !expecting {
+            self.last_reply = now;
+            self.last_progress = now;
+            return None;
+        }
+        let stalled = now.saturating_duration_since(self.last_progress);
+        if stalled < self.stall_after || self.gave_up {
+            return None;
+        }
+
+        let kind = if now.saturating_duration_since(self.last_reply) >= self.stall_after {
+            StallKind::NoReply
+        } else {
+            StallKind::SkippedSteps
+        };
+        self.attempts += 1;
+        let incident = StallIncident {
+            kind,
+            stalled_ms: stalled.as_millis() as u64,
+            iteration: self.last_iteration,
+            attempt: self.attempts,
+            detected_at: Utc::now(),
+        };
+        COUNTERS.stalls.fetch_add(1, Ordering::Relaxed);
+        *COUNTERS
+            .last_incident
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(incident.clone());
+
+        // Give each restart a full window before judging it.
+        self.last_progress = now;
+        self.last_reply = now;
+        if self.attempts > MAX_RESTARTS {
+            self.gave_up = true;
+            COUNTERS.abandoned.fetch_add(1, Ordering::Relaxed);
+            Some(StallAction::GiveUp(incident))
+        } else {
+            COUNTERS.restarts.fetch_add(1, Ordering::Relaxed);
+            Some(StallAction::Restart(incident))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: Duration = Duration::from_secs(5);
+
+    #[test]
+    fn idle_time_never_counts_as_a_stall() {
+        let t0 = Instant::now();
+        let mut dog = SimulationWatchdog::new(WINDOW, t0);
+        assert!(dog.check(false, t0 + Duration::from_secs(60)).is_none());
+        assert!(dog.check(true, t0 + Duration::from_secs(61)).is_none());
+    }
+
+    #[test]
+    fn classifies_stalls_and_gives_up_after_limit() {
+        let t0 = Instant::now();
+        let mut dog = SimulationWatchdog::new(WINDOW, t0);
+        dog.record_step(10, false, t0);
+        dog.record_step(11, true, t0 + Duration::from_secs(4));
+
+        let at = |s| t0 + Duration::from_secs(s);
+        match dog.check(true, at(6)) {
+            Some(StallAction::Restart(i)) => {
+                assert_eq!(
+                    (i.kind, i.iteration, i.attempt),
+                    (StallKind::SkippedSteps, 10, 1)
+                )
+            }
+            other => panic!("expected restart, got {:?}", other),
+        }
+        assert!(
+            dog.check(true, at(8)).is_none(),
+            "restart gets a full window"
+        );
+
+        let mut last = None;
+        for n in 1..=MAX_RESTARTS {
+            last = dog.check(true, at(6 + 6 * n as u64));
+        }
+        match last {
+            Some(StallAction::GiveUp(i)) => assert_eq!(i.kind, StallKind::NoReply),
+            other => panic!("expected give-up, got {:?}", other),
+        }
+        assert!(dog.check(true, at(60)).is_none());
+
+        dog.record_step(12, false, at(61));
+        assert!(matches!(
+            dog.check(true, at(67)),
+            Some(StallAction::Restart(_))
+        ));
+    }
+}
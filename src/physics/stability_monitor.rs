@@ -0,0 +1,438 @@
+//! Per-frame stability checks for the physics pipeline.
+//!
+//! The orchestrator runs [`StabilityMonitor::check`] on every position batch
+//! before it is broadcast. A batch containing non-finite positions, runaway
+//! velocities, or a kinetic-energy spike far above the recent baseline is
+//! reported as a [`SimulationAnomaly`]; the orchestrator then pauses the
+//! simulation, writes a snapshot via [`write_snapshot`] and tells clients why,
+//! rather than streaming an exploded layout.
+//!
+//! Upstream of that, [`scrub_non_finite`] repairs the odd NaN/Inf node in a
+//! GPU readback from the last-known-good frame so a single corrupt value does
+//! not cost the whole frame. Occurrences are counted in
+//! [`readback_scrub_stats`], reported by `/api/metrics`.
+
+use glam::Vec3;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::config::dev_config;
+use crate::models::simulation_params::SimulationParams;
+use crate::utils::socket_flow_messages::BinaryNodeData;
+
+/// Directory under the storage root used unless `SIMULATION_SNAPSHOT_DIR`
+/// is set.
+const SNAPSHOT_SUBDIR: &str = "simulation_snapshots";
+
+/// Offending node ids kept per anomaly; the snapshot has the full state.
+const MAX_REPORTED_NODES: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AnomalyKind {
+    NonFinitePosition,
+    RunawayVelocity,
+    EnergySpike,
+}
+
+impl AnomalyKind {
+    pub fn reason(&self) -> &'static str {
+        match self {
+            AnomalyKind::NonFinitePosition => "nan_positions",
+            AnomalyKind::RunawayVelocity => "runaway_velocity",
+            AnomalyKind::EnergySpike => "energy_spike",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationAnomaly {
+    pub kind: AnomalyKind,
+    pub reason: &'static str,
+    pub detail: String,
+    pub node_ids: Vec<u32>,
+    /// Unix ms.
+    pub detected_at: i64,
+    /// Snapshot file id, once written.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snapshot: Option<String>,
+}
+
+impl SimulationAnomaly {
+    pub fn new(kind: AnomalyKind, detail: String, mut node_ids: Vec<u32>) -> Self {
+        node_ids.truncate(MAX_REPORTED_NODES);
+        Self {
+            kind,
+            reason: kind.reason(),
+            detail,
+            node_ids,
+            detected_at: chrono::Utc::now().timestamp_millis(),
+            snapshot: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct StabilityThresholds {
+    /// Any single node moving faster than this is runaway.
+    pub max_speed: f32,
+    /// Per-node kinetic energy this many times the baseline is a spike.
+    pub energy_spike_factor: f64,
+    /// Spikes below this per-node energy are ignored; a settled graph has a
+    /// near-zero baseline that ordinary interaction would otherwise trip.
+    pub min_spike_energy: f64,
+    /// Frames observed before energy spikes are judged.
+    pub warmup_frames: u32,
+    /// Weight of the newest frame in the energy baseline.
+    pub baseline_alpha: f64,
+}
+
+impl Default for StabilityThresholds {
+    fn default() -> Self {
+        Self {
+            max_speed: 10_000.0,
+            energy_spike_factor: 50.0,
+            min_spike_energy: 1_000.0,
+            warmup_frames: 30,
+            baseline_alpha: 0.05,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct StabilityMonitor {
+    thresholds: StabilityThresholds,
+    baseline: Option<f64>,
+    frames: u32,
+}
+
+impl StabilityMonitor {
+    pub fn new(thresholds: StabilityThresholds) -> Self {
+        Self {
+            thresholds,
+            baseline: None,
+            frames: 0,
+        }
+    }
+
+    /// Forget the energy baseline, e.g. after a resume or new graph.
+    pub fn reset(&mut self) {
+        self.baseline = None;
+        self.frames = 0;
+    }
+
+    /// Inspect one position batch. Anomalous frames do not feed the baseline.
+    pub fn check(&mut self, positions: &[(u32, BinaryNodeData)]) -> Option<SimulationAnomaly> {
+        if positions.is_empty() {
+            return None;
+        }
+        let non_finite: Vec<u32> = positions
+            .iter()
+            .filter(|(_, d)| !(d.x.is_finite() && d.y.is_finite() && d.z.is_finite()))
+            .map(|(id, _)| *id)
+            .collect();
+        if !non_finite.is_empty() {
+            let detail = format!("{} node(s) with non-finite positions", non_finite.len());
+            return Some(SimulationAnomaly::new(
+                AnomalyKind::NonFinitePosition,
+                detail,
+                non_finite,
+            ));
+        }
+
+        let max_sq = self.thresholds.max_speed * self.thresholds.max_speed;
+        let mut energy = 0.0f64;
+        let mut runaway = Vec::new();
+        for (id, d) in positions {
+            let speed_sq = d.vx * d.vx + d.vy * d.vy + d.vz * d.vz;
+            if speed_sq.is_nan() || speed_sq > max_sq {
+                runaway.push(*id);
+            } else {
+                energy += 0.5 * speed_sq as f64;
+            }
+        }
+        if !runaway.is_empty() {
+            let detail = format!(
+                "{} node(s) faster than {}",
+                runaway.len(),
+                self.thresholds.max_speed
+            );
+            return Some(SimulationAnomaly::new(
+                AnomalyKind::RunawayVelocity,
+                detail,
+                runaway,
+            ));
+        }
+
+        let per_node = energy / positions.len() as f64;
+        self.frames = self.frames.saturating_add(1);
+        if let Some(baseline) = self.baseline {
+            if self.frames > self.thresholds.warmup_frames
+                && per_node >= self.thresholds.min_spike_energy
+                && per_node > baseline * self.thresholds.energy_spike_factor
+            {
+                let detail = format!(
+                    "per-node kinetic energy {:.1} vs baseline {:.1}",
+                    per_node, baseline
+                );
+                return Some(SimulationAnomaly::new(
+                    AnomalyKind::EnergySpike,
+                    detail,
+                    Vec::new(),
+                ));
+            }
+        }
+        let alpha = self.thresholds.baseline_alpha;
+        self.baseline = Some(match self.baseline {
+            Some(b) => b + alpha * (per_node - b),
+            None => per_node,
+        });
+        None
+    }
+}
+
+/// Largest share of nodes in one readback that may be scrubbed. Above it the
+/// frame is treated as corrupt and handed to the anomaly path.
+pub const MAX_SCRUB_FRACTION: f64 = 0.01;
+
+#[derive(Debug, Default)]
+struct ScrubCounters {
+    scrubbed_frames: AtomicU64,
+    scrubbed_nodes: AtomicU64,
+    rejected_frames: AtomicU64,
+}
+
+static SCRUB_COUNTERS: Lazy<ScrubCounters> = Lazy::new(ScrubCounters::default);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadbackScrubStats {
+    /// Readbacks repaired in place.
+    pub scrubbed_frames: u64,
+    /// Nodes whose position or velocity was replaced.
+    pub scrubbed_nodes: u64,
+    /// Readbacks with too many non-finite nodes to repair.
+    pub rejected_frames: u64,
+}
+
+pub fn readback_scrub_stats() -> ReadbackScrubStats {
+    ReadbackScrubStats {
+        scrubbed_frames: SCRUB_COUNTERS.scrubbed_frames.load(Ordering::Relaxed),
+        scrubbed_nodes: SCRUB_COUNTERS.scrubbed_nodes.load(Ordering::Relaxed),
+        rejected_frames: SCRUB_COUNTERS.rejected_frames.load(Ordering::Relaxed),
+    }
+}
+
+pub fn record_scrubbed_frame(nodes: usize) {
+    SCRUB_COUNTERS
+        .scrubbed_frames
+        .fetch_add(1, Ordering::Relaxed);
+    SCRUB_COUNTERS
+        .scrubbed_nodes
+        .fetch_add(nodes as u64, Ordering::Relaxed);
+}
+
+pub fn record_rejected_frame() {
+    SCRUB_COUNTERS
+        .rejected_frames
+        .fetch_add(1, Ordering::Relaxed);
+}
+
+fn vec_is_finite(v: Vec3) -> bool {
+    v.x.is_finite() && v.y.is_finite() && v.z.is_finite()
+}
+
+/// Replace non-finite positions and velocities in a readback with the node's
+/// last-known-good values. `frame` and `node_ids` are indexed by GPU slot;
+/// `last_good` holds `(node_id, position, velocity)` per slot.
+///
+/// Returns the number of nodes repaired, or `Err(count)` with the number of
+/// non-finite nodes when there are more than [`MAX_SCRUB_FRACTION`] of them or
+/// any lacks a last-known-good entry. The frame is left untouched on error.
+pub fn scrub_non_finite(
+    frame: &mut [(Vec3, Vec3)],
+    node_ids: &[u32],
+    last_good: &[(u32, Vec3, Vec3)],
+) -> Result<usize, usize> {
+    let bad: Vec<usize> = frame
+        .iter()
+        .enumerate()
+        .filter(|(_, (p, v))| !(vec_is_finite(*p) && vec_is_finite(*v)))
+        .map(|(i, _)| i)
+        .collect();
+    if bad.is_empty() {
+        return Ok(0);
+    }
+    let too_many = bad.len() as f64 > frame.len() as f64 * MAX_SCRUB_FRACTION;
+    let unrecoverable = bad.iter().any(|&i| {
+        !matches!(
+            (last_good.get(i), node_ids.get(i)),
+            (Some((id, _, _)), Some(node)) if id == node
+        )
+    });
+    if too_many || unrecoverable {
+        return Err(bad.len());
+    }
+    for &i in &bad {
+        let (_, good_pos, good_vel) = last_good[i];
+        let (pos, vel) = &mut frame[i];
+        if !vec_is_finite(*pos) {
+            *pos = good_pos;
+        }
+        if !vec_is_finite(*vel) {
+            *vel = good_vel;
+        }
+    }
+    Ok(bad.len())
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Snapshot<'a> {
+    anomaly: &'a SimulationAnomaly,
+    iteration: u64,
+    params: &'a SimulationParams,
+    /// `[id, x, y, z, vx, vy, vz]`; non-finite values serialise as null.
+    nodes: Vec<[f64; 7]>,
+}
+
+pub fn snapshot_dir() -> PathBuf {
+    std::env::var_os("SIMULATION_SNAPSHOT_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| dev_config::storage().path(SNAPSHOT_SUBDIR))
+}
+
+/// File id under which the snapshot for `anomaly` is written.
+pub fn snapshot_id(anomaly: &SimulationAnomaly) -> String {
+    format!("{}-{}", anomaly.detected_at, anomaly.reason)
+}
+
+/// Write the offending state to `<dir>/<snapshot_id>.json` and return the id.
+pub fn write_snapshot(
+    dir: &Path,
+    anomaly: &SimulationAnomaly,
+    iteration: u64,
+    params: &SimulationParams,
+    positions: &[(u32, BinaryNodeData)],
+) -> io::Result<String> {
+    std::fs::create_dir_all(dir)?;
+    let id = snapshot_id(anomaly);
+    let snapshot = Snapshot {
+        anomaly,
+        iteration,
+        params,
+        nodes: positions
+            .iter()
+            .map(|(id, d)| {
+                [
+                    *id as f64,
+                    d.x as f64,
+                    d.y as f64,
+                    d.z as f64,
+                    d.vx as f64,
+                    d.vy as f64,
+                    d.vz as f64,
+                ]
+            })
+            .collect(),
+    };
+    let json = serde_json::to_vec(&snapshot).map_err(io::Error::other)?;
+    std::fs::write(dir.join(format!("{}.json", id)), json)?;
+    Ok(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: u32, x: f32, v: f32) -> (u32, BinaryNodeData) {
+        (
+            id,
+            BinaryNodeData {
+                node_id: id,
+                x,
+                y: 0.0,
+                z: 0.0,
+                vx: v,
+                vy: 0.0,
+                vz: 0.0,
+            },
+        )
+    }
+
+    #[test]
+    fn flags_non_finite_positions_and_runaway_velocities() {
+        let mut monitor = StabilityMonitor::default();
+
+        let a = monitor
+            .check(&[node(1, 0.0, 1.0), node(2, f32::NAN, 1.0)])
+            .unwrap();
+        assert_eq!(a.kind, AnomalyKind::NonFinitePosition);
+        assert_eq!(a.node_ids, vec![2]);
+
+        let a = monitor
+            .check(&[node(1, 0.0, 1.0), node(3, 0.0, 1.0e6)])
+            .unwrap();
+        assert_eq!(a.kind, AnomalyKind::RunawayVelocity);
+        assert_eq!(a.node_ids, vec![3]);
+
+        assert!(monitor.check(&[node(1, 0.0, 1.0)]).is_none());
+    }
+
+    #[test]
+    fn energy_spike_is_judged_against_the_baseline_after_warmup() {
+        let mut monitor = StabilityMonitor::new(StabilityThresholds {
+            warmup_frames: 5,
+            ..Default::default()
+        });
+        let calm = [node(1, 0.0, 10.0), node(2, 0.0, 10.0)];
+        let hot = [node(1, 0.0, 2_000.0), node(2, 0.0, 2_000.0)];
+
+        for _ in 0..6 {
+            assert!(monitor.check(&calm).is_none());
+        }
+        let a = monitor.check(&hot).unwrap();
+        assert_eq!(a.kind, AnomalyKind::EnergySpike);
+        assert_eq!(a.reason, "energy_spike");
+
+        monitor.reset();
+        assert!(monitor.check(&hot).is_none(), "no baseline after reset");
+    }
+
+    #[test]
+    fn scrubs_isolated_non_finite_nodes_from_last_known_good() {
+        let n = 200;
+        let ids: Vec<u32> = (0..n).collect();
+        let good: Vec<(u32, Vec3, Vec3)> = ids
+            .iter()
+            .map(|&id| (id, Vec3::splat(id as f32), Vec3::ONE))
+            .collect();
+        let mut frame: Vec<(Vec3, Vec3)> = good.iter().map(|&(_, p, v)| (p * 2.0, v)).collect();
+
+        frame[7].0.y = f32::NAN;
+        frame[9].1.x = f32::INFINITY;
+        assert_eq!(scrub_non_finite(&mut frame, &ids, &good), Ok(2));
+        assert_eq!(frame[7].0, Vec3::splat(7.0));
+        assert_eq!(
+            frame[9],
+            (Vec3::splat(18.0), Vec3::ONE),
+            "finite position kept"
+        );
+        assert_eq!(frame[8].0, Vec3::splat(16.0), "healthy nodes untouched");
+
+        // Over the threshold (1% of 200 = 2 nodes) the frame is rejected.
+        for (pos, _) in frame.iter_mut().take(3) {
+            pos.x = f32::NAN;
+        }
+        assert_eq!(scrub_non_finite(&mut frame, &ids, &good), Err(3));
+        assert!(frame[0].0.x.is_nan(), "rejected frames are not modified");
+
+        // No last-known-good to fall back on.
+        let mut frame = vec![(Vec3::NAN, Vec3::ZERO)];
+        assert_eq!(scrub_non_finite(&mut frame, &[1], &[]), Err(1));
+    }
+}
@@ -0,0 +1,196 @@
+//! Per-stage timing of recent physics steps.
+//!
+//! A slow frame can come from waiting for the GPU lock, uploading parameters,
+//! the kernels themselves, reading positions back, or encoding and sending
+//! the broadcast. The GPU actor records the first four for every completed
+//! step (kernel time from CUDA events, the rest from host instants); the
+//! client coordinator attaches encode and send time when it broadcasts. The
+//! last [`PROFILE_CAPACITY`] frames are kept for `GET /api/simulation/profile`.
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// Frames kept in the rolling window.
+pub const PROFILE_CAPACITY: usize = 600;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepTimings {
+    pub iteration: u64,
+    pub recorded_at: DateTime<Utc>,
+    /// Waiting for GPU access and the compute mutex.
+    pub lock_wait_ms: f32,
+    /// Parameter and velocity uploads before the kernels run.
+    pub upload_ms: f32,
+    /// Kernel time measured with CUDA events; `None` when the stability
+    /// gate skipped the step.
+    pub kernel_ms: Option<f32>,
+    /// Position and velocity readback.
+    pub readback_ms: f32,
+    /// Wire encoding; `None` if this frame was not broadcast.
+    pub encode_ms: Option<f32>,
+    /// Handing frames to client sessions; `None` if not broadcast.
+    pub broadcast_ms: Option<f32>,
+}
+
+impl StepTimings {
+    pub fn total_ms(&self) -> f32 {
+        self.lock_wait_ms
+            + self.upload_ms
+            + self.kernel_ms.unwrap_or(0.0)
+            + self.readback_ms
+            + self.encode_ms.unwrap_or(0.0)
+            + self.broadcast_ms.unwrap_or(0.0)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StageSummary {
+    pub stage: &'static str,
+    /// Frames that recorded this stage.
+    pub samples: usize,
+    pub mean_ms: f32,
+    pub p95_ms: f32,
+    pub max_ms: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepProfile {
+    pub capacity: usize,
+    pub stages: Vec<StageSummary>,
+    /// Stage with the largest mean time, if any frames were recorded.
+    pub dominant_stage: Option<&'static str>,
+    /// Oldest first.
+    pub frames: Vec<StepTimings>,
+}
+
+fn summarise(stage: &'static str, mut values: Vec<f32>) -> StageSummary {
+    values.retain(|v| v.is_finite());
+    if values.is_empty() {
+        return StageSummary {
+            stage,
+            samples: 0,
+            mean_ms: 0.0,
+            p95_ms: 0.0,
+            max_ms: 0.0,
+        };
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    let p95_index = ((values.len() as f32 * 0.95).ceil() as usize).saturating_sub(1);
+    StageSummary {
+        stage,
+        samples: values.len(),
+        mean_ms: values.iter().sum::<f32>() / values.len() as f32,
+        p95_ms: values[p95_index.min(values.len() - 1)],
+        max_ms: values[values.len() - 1],
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct StepProfiler {
+    frames: VecDeque<StepTimings>,
+}
+
+impl StepProfiler {
+    pub fn record_step(&mut self, timings: StepTimings) {
+        if self.frames.len() == PROFILE_CAPACITY {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(timings);
+    }
+
+    /// Attach broadcast timings to the newest frame. Broadcasts are rate
+    /// limited, so most frames never get one; a frame is only filled once.
+    pub fn record_broadcast(&mut self, encode_ms: f32, broadcast_ms: f32) {
+        if let Some(frame) = self.frames.back_mut() {
+            if frame.encode_ms.is_none() {
+                frame.encode_ms = Some(encode_ms);
+                frame.broadcast_ms = Some(broadcast_ms);
+            }
+        }
+    }
+
+    /// Summary and frames for the newest `last` frames.
+    pub fn profile(&self, last: usize) -> StepProfile {
+        let skip = self.frames.len().saturating_sub(last);
+        let frames: Vec<StepTimings> = self.frames.iter().skip(skip).cloned().collect();
+        let collect = |f: fn(&StepTimings) -> Option<f32>| -> Vec<f32> {
+            frames.iter().filter_map(f).collect()
+        };
+        let stages = vec![
+            summarise("lockWait", collect(|t| Some(t.lock_wait_ms))),
+            summarise("upload", collect(|t| Some(t.upload_ms))),
+            summarise("kernel", collect(|t| t.kernel_ms)),
+            summarise("readback", collect(|t| Some(t.readback_ms))),
+            summarise("encode", collect(|t| t.encode_ms)),
+            summarise("broadcast", collect(|t| t.broadcast_ms)),
+            summarise("total", collect(|t| Some(t.total_ms()))),
+        ];
+        let dominant_stage = stages
+            .iter()
+            .filter(|s| s.stage != "total" && s.samples > 0)
+            .max_by(|a, b| a.mean_ms.total_cmp(&b.mean_ms))
+            .map(|s| s.stage);
+        StepProfile {
+            capacity: PROFILE_CAPACITY,
+            stages,
+            dominant_stage,
+            frames,
+        }
+    }
+}
+
+static PROFILER: Lazy<Mutex<StepProfiler>> = Lazy::new(|| Mutex::new(StepProfiler::default()));
+
+/// Process-wide profiler shared by the GPU actor and the client coordinator.
+pub fn step_profiler() -> std::sync::MutexGuard<'static, StepProfiler> {
+    PROFILER.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(iteration: u64, kernel_ms: Option<f32>) -> StepTimings {
+        StepTimings {
+            iteration,
+            recorded_at: Utc::now(),
+            lock_wait_ms: 0.5,
+            upload_ms: 0.1,
+            kernel_ms,
+            readback_ms: 2.0,
+            encode_ms: None,
+            broadcast_ms: None,
+        }
+    }
+
+    #[test]
+    fn summarises_window_and_attaches_broadcasts() {
+        let mut profiler = StepProfiler::default();
+        for i in 0..(PROFILE_CAPACITY as u64 + 10) {
+            profiler.record_step(frame(i, Some(4.0)));
+        }
+        profiler.record_step(frame(9999, None));
+        profiler.record_broadcast(1.0, 0.5);
+        profiler.record_broadcast(7.0, 7.0);
+
+        let profile = profiler.profile(20);
+        assert_eq!(profile.frames.len(), 20);
+        let last = profile.frames.last().unwrap();
+        assert_eq!((last.encode_ms, last.broadcast_ms), (Some(1.0), Some(0.5)));
+        assert!((last.total_ms() - 4.1).abs() < 1e-5);
+
+        let kernel = &profile.stages[2];
+        assert_eq!(
+            (kernel.stage, kernel.samples, kernel.max_ms),
+            ("kernel", 19, 4.0)
+        );
+        assert_eq!(profile.dominant_stage, Some("kernel"));
+        assert_eq!(profiler.profile(usize::MAX).frames.len(), PROFILE_CAPACITY);
+    }
+}
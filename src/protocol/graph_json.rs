@@ -0,0 +1,557 @@
+//! JSON schemas for nodes and edges sent to clients.
+//!
+//! Every handler that puts nodes or edges into a JSON payload builds one of
+//! the views below and encodes it through a [`JsonSchema`], so a new field is
+//! added once here rather than in each handler. Field names in the docs are
+//! the camelCase (schema 2) spelling.
+//!
+//! Schema versions:
+//! - `1` — legacy: snake_case keys and the original field sets; fields listed
+//!   in [`WireType::SINCE_V2`] are omitted. WebSocket sessions use this until
+//!   they ask for a newer schema in `hello`.
+//! - `2` — current: camelCase keys, all fields. REST default.
+//!
+//! Casing can be overridden independently of the version. Keys inside opaque
+//! maps (`metadata`, `userData`) are user data and are not rewritten; only
+//! the typed metadata fields follow the casing.
+//!
+//! Schema 2 sends node `metadata` typed (see [`NodeMetadata`]); schema 1
+//! keeps the stored string map. The `stringMetadata` flag overrides either
+//! default.
+//!
+//! Node labels are sent as written unless the client's locale selects the
+//! romanized variant (see [`LabelVariant`]); nodes without one keep their
+//! native label either way.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+use super::node_metadata::NodeMetadata;
+use crate::types::vec3::Vec3Data;
+use visionclaw_domain::models::edge::{Edge, EdgeProvenance};
+use visionclaw_domain::models::node::Node;
+
+pub const SUPPORTED_JSON_SCHEMAS: &[u32] = &[1, 2];
+pub const LEGACY_JSON_SCHEMA: u32 = 1;
+pub const CURRENT_JSON_SCHEMA: u32 = 2;
+
+/// Keys whose values are free-form maps and keep their keys as stored.
+const OPAQUE_KEYS: &[&str] = &["metadata", "userData"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldCasing {
+    Camel,
+    Snake,
+}
+
+/// Languages usually written in a non-Latin script. A reader with one of
+/// these locales gets native labels; anyone else gets romanized ones.
+const NON_LATIN_LANGUAGES: &[&str] = &[
+    "ar", "be", "bg", "el", "fa", "he", "ja", "kk", "ko", "ky", "mk", "mn", "ps", "ru", "sr", "uk",
+    "ur", "yi", "zh",
+];
+
+/// Which form of a node's label is sent in `label`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelVariant {
+    /// The label as written.
+    #[default]
+    Native,
+    /// The romanized label from `metadata.label_romanized`, where present.
+    Romanized,
+}
+
+impl LabelVariant {
+    /// Variant for a BCP 47 locale such as `en-GB`, `ja` or `sr-Latn`. An
+    /// explicit script subtag decides; otherwise the language does. No
+    /// locale keeps native labels.
+    pub fn for_locale(locale: Option<&str>) -> Self {
+        let Some(locale) = locale.map(str::trim).filter(|l| !l.is_empty()) else {
+            return Self::Native;
+        };
+        let mut subtags = locale.split(['-', '_']);
+        let language = subtags.next().unwrap_or_default().to_ascii_lowercase();
+        let script = subtags.find(|s| s.len() == 4 && s.chars().all(|c| c.is_ascii_alphabetic()));
+        match script {
+            Some(script) if script.eq_ignore_ascii_case("latn") => Self::Romanized,
+            Some(_) => Self::Native,
+            None if NON_LATIN_LANGUAGES.contains(&language.as_str()) => Self::Native,
+            None => Self::Romanized,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonSchema {
+    pub version: u32,
+    pub casing: FieldCasing,
+    /// Send node metadata as the stored string map instead of typed values.
+    pub string_metadata: bool,
+    pub labels: LabelVariant,
+}
+
+impl Default for JsonSchema {
+    fn default() -> Self {
+        Self::CURRENT
+    }
+}
+
+/// A view that can be encoded under any schema version.
+pub trait WireType: Serialize {
+    /// camelCase names of fields added in schema 2.
+    const SINCE_V2: &'static [&'static str] = &[];
+
+    /// Typed replacement for the `metadata` field, if the view carries
+    /// node metadata.
+    fn typed_metadata(&self, _casing: FieldCasing) -> Option<Value> {
+        None
+    }
+
+    /// Romanized form of the view's `label`, if it has one.
+    fn romanized_label(&self) -> Option<&str> {
+        None
+    }
+}
+
+impl JsonSchema {
+    pub const CURRENT: Self = Self {
+        version: CURRENT_JSON_SCHEMA,
+        casing: FieldCasing::Camel,
+        string_metadata: false,
+        labels: LabelVariant::Native,
+    };
+    pub const LEGACY: Self = Self {
+        version: LEGACY_JSON_SCHEMA,
+        casing: FieldCasing::Snake,
+        string_metadata: true,
+        labels: LabelVariant::Native,
+    };
+
+    /// The schema for `version` with its default casing, overridden by
+    /// `casing` if given.
+    pub fn resolve(version: Option<u32>, casing: Option<FieldCasing>) -> Result<Self, String> {
+        let base = match version {
+            None | Some(CURRENT_JSON_SCHEMA) => Self::CURRENT,
+            Some(LEGACY_JSON_SCHEMA) => Self::LEGACY,
+            Some(v) => {
+                return Err(format!(
+                    "Unsupported JSON schema {} (supported: {:?})",
+                    v, SUPPORTED_JSON_SCHEMAS
+                ))
+            }
+        };
+        Ok(Self {
+            casing: casing.unwrap_or(base.casing),
+            ..base
+        })
+    }
+
+    /// Override the schema's metadata format if `string_metadata` is given.
+    pub fn with_string_metadata(self, string_metadata: Option<bool>) -> Self {
+        Self {
+            string_metadata: string_metadata.unwrap_or(self.string_metadata),
+            ..self
+        }
+    }
+
+    /// Pick the label variant for `locale` if one is given.
+    pub fn with_locale(self, locale: Option<&str>) -> Self {
+        match locale {
+            Some(_) => Self {
+                labels: LabelVariant::for_locale(locale),
+                ..self
+            },
+            None => self,
+        }
+    }
+
+    pub fn encode<T: WireType>(&self, item: &T) -> Value {
+        let mut value = serde_json::to_value(item).unwrap_or(Value::Null);
+        if self.version < 2 {
+            if let Value::Object(map) = &mut value {
+                for field in T::SINCE_V2 {
+                    map.remove(*field);
+                }
+            }
+        }
+        if self.casing == FieldCasing::Snake {
+            value = to_snake_keys(value);
+        }
+        if !self.string_metadata {
+            if let (Value::Object(map), Some(typed)) =
+                (&mut value, item.typed_metadata(self.casing))
+            {
+                map.insert("metadata".to_string(), typed);
+            }
+        }
+        if self.labels == LabelVariant::Romanized {
+            if let (Value::Object(map), Some(romanized)) = (&mut value, item.romanized_label()) {
+                if map.contains_key("label") {
+                    map.insert("label".to_string(), Value::from(romanized));
+                }
+            }
+        }
+        value
+    }
+
+    pub fn encode_all<T: WireType>(&self, items: impl IntoIterator<Item = T>) -> Vec<Value> {
+        items.into_iter().map(|item| self.encode(&item)).collect()
+    }
+}
+
+pub(crate) fn camel_to_snake(key: &str) -> String {
+    let mut out = String::with_capacity(key.len() + 4);
+    for c in key.chars() {
+        if c.is_ascii_uppercase() {
+            out.push('_');
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn to_snake_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(k, v)| {
+                    let v = if OPAQUE_KEYS.contains(&k.as_str()) {
+                        v
+                    } else {
+                        to_snake_keys(v)
+                    };
+                    (camel_to_snake(&k), v)
+                })
+                .collect::<Map<String, Value>>(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(to_snake_keys).collect()),
+        other => other,
+    }
+}
+
+/// Full node with nested position, as served by `GET /api/graph/data`.
+///
+/// `{id, metadataId, label, position: {x,y,z}, velocity: {x,y,z}, metadata?,
+/// type?, size?, color?, weight?, group?, labelPriority?, labelLod?,
+/// tagColor?}`; the label hints are schema 2 only and present when requested
+/// (see [`super::label_hints`]). `tagColor` (schema 2) is the colour of the
+/// node's first tag, see `services::tag_color_service`.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeWithPosition {
+    pub id: u32,
+    pub metadata_id: String,
+    pub label: String,
+    pub position: Vec3Data,
+    pub velocity: Vec3Data,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
+    #[serde(rename = "type")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label_priority: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label_lod: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag_color: Option<String>,
+}
+
+impl WireType for NodeWithPosition {
+    const SINCE_V2: &'static [&'static str] = &["labelPriority", "labelLod", "tagColor"];
+
+    fn typed_metadata(&self, casing: FieldCasing) -> Option<Value> {
+        (!self.metadata.is_empty()).then(|| NodeMetadata::from(&self.metadata).to_json(casing))
+    }
+
+    fn romanized_label(&self) -> Option<&str> {
+        self.metadata.get("label_romanized").map(String::as_str)
+    }
+}
+
+impl From<&Node> for NodeWithPosition {
+    fn from(node: &Node) -> Self {
+        Self {
+            id: node.id,
+            metadata_id: node.metadata_id.clone(),
+            label: node.label.clone(),
+            position: node.data.position().into(),
+            velocity: node.data.velocity().into(),
+            metadata: node.metadata.clone(),
+            node_type: node.node_type.clone(),
+            size: node.size,
+            color: node.color.clone(),
+            weight: node.weight,
+            group: node.group.clone(),
+            label_priority: None,
+            label_lod: None,
+            tag_color: None,
+        }
+    }
+}
+
+/// Flat position row for streamed graphs such as `botsGraphUpdate`.
+///
+/// `{id, metadataId, x, y, z, vx, vy, vz, label, type?}`; `label` and `type`
+/// are schema 2 only.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NodePositionJson<'a> {
+    pub id: u32,
+    pub metadata_id: &'a str,
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub vx: f32,
+    pub vy: f32,
+    pub vz: f32,
+    pub label: &'a str,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub node_type: Option<&'a str>,
+    #[serde(skip)]
+    pub label_romanized: Option<&'a str>,
+}
+
+impl WireType for NodePositionJson<'_> {
+    const SINCE_V2: &'static [&'static str] = &["label", "type"];
+
+    fn romanized_label(&self) -> Option<&str> {
+        self.label_romanized
+    }
+}
+
+impl<'a> From<&'a Node> for NodePositionJson<'a> {
+    fn from(node: &'a Node) -> Self {
+        Self {
+            id: node.id,
+            metadata_id: &node.metadata_id,
+            x: node.data.x,
+            y: node.data.y,
+            z: node.data.z,
+            vx: node.data.vx,
+            vy: node.data.vy,
+            vz: node.data.vz,
+            label: &node.label,
+            node_type: node.node_type.as_deref(),
+            label_romanized: node.metadata.get("label_romanized").map(String::as_str),
+        }
+    }
+}
+
+/// Identity of a node without layout, used in query results.
+///
+/// `{id, label, metadataId, type}`; `type` is `null` for untyped nodes.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeSummaryJson<'a> {
+    pub id: u32,
+    pub label: &'a str,
+    pub metadata_id: &'a str,
+    #[serde(rename = "type")]
+    pub node_type: Option<&'a str>,
+    #[serde(skip)]
+    pub label_romanized: Option<&'a str>,
+}
+
+impl WireType for NodeSummaryJson<'_> {
+    fn romanized_label(&self) -> Option<&str> {
+        self.label_romanized
+    }
+}
+
+impl<'a> From<&'a Node> for NodeSummaryJson<'a> {
+    fn from(node: &'a Node) -> Self {
+        Self {
+            id: node.id,
+            label: &node.label,
+            metadata_id: &node.metadata_id,
+            node_type: node.node_type.as_deref(),
+            label_romanized: node.metadata.get("label_romanized").map(String::as_str),
+        }
+    }
+}
+
+/// Edge as sent to clients.
+///
+/// `{id, source, target, weight, edgeType?, owlPropertyIri?, metadata?,
+/// provenance?, tagColor?}`; schema 1 has only the first four, as the legacy
+/// stream sent them. `tagColor` is the colour of a tag both endpoints share.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EdgeJson<'a> {
+    pub id: &'a str,
+    pub source: u32,
+    pub target: u32,
+    pub weight: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub edge_type: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owl_property_iri: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<&'a HashMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<&'a EdgeProvenance>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag_color: Option<String>,
+}
+
+impl WireType for EdgeJson<'_> {
+    const SINCE_V2: &'static [&'static str] = &[
+        "edgeType",
+        "owlPropertyIri",
+        "metadata",
+        "provenance",
+        "tagColor",
+    ];
+}
+
+impl<'a> From<&'a Edge> for EdgeJson<'a> {
+    fn from(edge: &'a Edge) -> Self {
+        Self {
+            id: &edge.id,
+            source: edge.source,
+            target: edge.target,
+            weight: edge.weight,
+            edge_type: edge.edge_type.as_deref(),
+            owl_property_iri: edge.owl_property_iri.as_deref(),
+            metadata: edge.metadata.as_ref(),
+            provenance: edge.provenance.as_ref(),
+            tag_color: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node() -> Node {
+        let mut node = Node::new_with_id("page-a".to_string(), Some(7));
+        node.label = "Page A".to_string();
+        node.node_type = Some("page".to_string());
+        node.metadata
+            .insert("fileSize".to_string(), "12".to_string());
+        node
+    }
+
+    #[test]
+    fn legacy_schema_keeps_original_shape() {
+        let node = node();
+        let legacy = JsonSchema::LEGACY.encode(&NodePositionJson::from(&node));
+        let keys: Vec<&str> = legacy
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(|k| k.as_str())
+            .collect();
+        assert_eq!(keys.len(), 8);
+        assert!(keys.contains(&"metadata_id"));
+        assert!(!keys.contains(&"label"));
+
+        let current = JsonSchema::CURRENT.encode(&NodePositionJson::from(&node));
+        assert_eq!(current["metadataId"], "page-a");
+        assert_eq!(current["type"], "page");
+    }
+
+    #[test]
+    fn legacy_edges_keep_the_four_original_fields() {
+        let mut edge = Edge::new(1, 2, 0.5);
+        edge.edge_type = Some("explicit_link".to_string());
+        let mut json = EdgeJson::from(&edge);
+        json.tag_color = Some("#ff0000".to_string());
+
+        let legacy = JsonSchema::LEGACY.encode(&json);
+        let mut keys: Vec<&str> = legacy
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(|k| k.as_str())
+            .collect();
+        keys.sort_unstable();
+        assert_eq!(keys, ["id", "source", "target", "weight"]);
+
+        let current = JsonSchema::CURRENT.encode(&json);
+        assert_eq!(current["edgeType"], "explicit_link");
+        assert_eq!(current["tagColor"], "#ff0000");
+    }
+
+    #[test]
+    fn casing_override_leaves_opaque_maps_alone() {
+        let schema = JsonSchema::resolve(None, Some(FieldCasing::Snake)).unwrap();
+        assert_eq!(schema.version, CURRENT_JSON_SCHEMA);
+        let full = schema.encode(&NodeWithPosition::from(&node()));
+        assert_eq!(full["metadata_id"], "page-a");
+        assert_eq!(full["metadata"]["file_size"], 12);
+
+        let strings = schema
+            .with_string_metadata(Some(true))
+            .encode(&NodeWithPosition::from(&node()));
+        assert_eq!(strings["metadata"]["fileSize"], "12");
+
+        assert_eq!(
+            JsonSchema::resolve(Some(1), None).unwrap(),
+            JsonSchema::LEGACY
+        );
+        assert!(JsonSchema::resolve(Some(9), None).is_err());
+    }
+
+    #[test]
+    fn locale_selects_label_variant() {
+        assert_eq!(LabelVariant::for_locale(None), LabelVariant::Native);
+        assert_eq!(
+            LabelVariant::for_locale(Some("ko-KR")),
+            LabelVariant::Native
+        );
+        assert_eq!(
+            LabelVariant::for_locale(Some("en")),
+            LabelVariant::Romanized
+        );
+        assert_eq!(
+            LabelVariant::for_locale(Some("sr_Latn_RS")),
+            LabelVariant::Romanized
+        );
+        assert_eq!(
+            LabelVariant::for_locale(Some("uz-Cyrl")),
+            LabelVariant::Native
+        );
+
+        let mut node = node();
+        node.label = "한국어".to_string();
+        node.metadata
+            .insert("label_romanized".to_string(), "hangugeo".to_string());
+        let schema = JsonSchema::CURRENT.with_locale(Some("en-US"));
+        assert_eq!(
+            schema.encode(&NodeSummaryJson::from(&node))["label"],
+            "hangugeo"
+        );
+        assert_eq!(
+            schema.encode(&NodeWithPosition::from(&node))["label"],
+            "hangugeo"
+        );
+        let native = JsonSchema::CURRENT.with_locale(Some("ko"));
+        assert_eq!(
+            native.encode(&NodePositionJson::from(&node))["label"],
+            "한국어"
+        );
+        // Schema 1 has no label on position rows; romanizing must not add one.
+        let legacy = JsonSchema::LEGACY.with_locale(Some("en"));
+        assert!(legacy
+            .encode(&NodePositionJson::from(&node))
+            .get("label")
+            .is_none());
+    }
+}
@@ -0,0 +1,191 @@
+//! Server-side label decluttering hints.
+//!
+//! Dense clusters produce overlapping labels, and clients that each decide
+//! on their own which to hide show different viewers different pictures. On
+//! request (`label_hints=true` on `GET /api/graph/data`) the server ranks
+//! nodes by a cheap centrality score and suggests a label LOD level:
+//!
+//! - `0` — always labelled (top 2% by priority)
+//! - `1` — labelled at medium zoom (next 8%)
+//! - `2` — labelled close up (next 20%)
+//! - `3` — labelled only on hover or focus
+//!
+//! A node is then pushed down a level while a higher-priority node already
+//! holds a label in the same grid cell at that level, so a tight cluster
+//! shows one label per cell rather than a pile. Cells halve in size at each
+//! level, following the zoom. Priorities are in `0..=1`; ties are broken by
+//! node id so every client gets the same answer.
+
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// Level for labels shown only on hover or focus.
+pub const MAX_LABEL_LOD: u8 = 3;
+
+/// Cumulative share of nodes, by priority rank, placed at levels 0..3.
+const LEVEL_SHARES: [f32; MAX_LABEL_LOD as usize] = [0.02, 0.10, 0.30];
+/// Grid cells across the layout extent at level 0.
+const LEVEL0_CELLS: f32 = 8.0;
+/// Weight of a node's own degree against its neighbours' degrees.
+const DEGREE_WEIGHT: f32 = 0.7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelHint {
+    pub priority: f32,
+    pub lod: u8,
+}
+
+/// `values` scaled by `ln(1 + v) / ln(1 + max)`, so hubs do not flatten
+/// everything else to zero.
+fn log_normalise(values: &[f32]) -> Vec<f32> {
+    let max = values.iter().fold(0.0f32, |m, &v| m.max(v));
+    if max <= 0.0 {
+        return vec![0.0; values.len()];
+    }
+    let denom = max.ln_1p();
+    values.iter().map(|v| v.ln_1p() / denom).collect()
+}
+
+/// Hints for `nodes` (id, position) given the edges between them. Edges to
+/// nodes not in `nodes` are ignored.
+pub fn compute_label_hints(
+    nodes: &[(u32, [f32; 3])],
+    edges: &[(u32, u32)],
+) -> HashMap<u32, LabelHint> {
+    let n = nodes.len();
+    let index: HashMap<u32, usize> = nodes
+        .iter()
+        .enumerate()
+        .map(|(i, (id, _))| (*id, i))
+        .collect();
+
+    let mut pairs = Vec::with_capacity(edges.len());
+    let mut degree = vec![0.0f32; n];
+    for (source, target) in edges {
+        if let (Some(&a), Some(&b)) = (index.get(source), index.get(target)) {
+            if a != b {
+                degree[a] += 1.0;
+                degree[b] += 1.0;
+                pairs.push((a, b));
+            }
+        }
+    }
+    // Sum of neighbour degrees: a node bridging hubs outranks an equally
+    // connected node on the periphery.
+    let mut neighbour = vec![0.0f32; n];
+    for &(a, b) in &pairs {
+        neighbour[a] += degree[b];
+        neighbour[b] += degree[a];
+    }
+    let degree = log_normalise(&degree);
+    let neighbour = log_normalise(&neighbour);
+    let priority: Vec<f32> = degree
+        .iter()
+        .zip(&neighbour)
+        .map(|(d, nb)| DEGREE_WEIGHT * d + (1.0 - DEGREE_WEIGHT) * nb)
+        .collect();
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| {
+        priority[b]
+            .total_cmp(&priority[a])
+            .then(nodes[a].0.cmp(&nodes[b].0))
+    });
+
+    let mut min = [f32::INFINITY; 3];
+    let mut max = [f32::NEG_INFINITY; 3];
+    for (_, p) in nodes {
+        for axis in 0..3 {
+            if p[axis].is_finite() {
+                min[axis] = min[axis].min(p[axis]);
+                max[axis] = max[axis].max(p[axis]);
+            }
+        }
+    }
+    let extent = (0..3)
+        .map(|axis| max[axis] - min[axis])
+        .filter(|e| e.is_finite())
+        .fold(1.0f32, f32::max);
+
+    let mut occupied: Vec<HashSet<[i32; 3]>> = vec![HashSet::new(); MAX_LABEL_LOD as usize];
+    let mut hints = HashMap::with_capacity(n);
+    for (rank, &i) in order.iter().enumerate() {
+        let mut lod = LEVEL_SHARES
+            .iter()
+            .position(|&share| (rank as f32) < share * n as f32)
+            .map_or(MAX_LABEL_LOD, |level| level as u8);
+        let (id, position) = nodes[i];
+        while lod < MAX_LABEL_LOD {
+            let cell_size = extent / (LEVEL0_CELLS * f32::from(1u8 << lod));
+            let mut cell = [0i32; 3];
+            for axis in 0..3 {
+                let offset = (position[axis] - min[axis]) / cell_size;
+                cell[axis] = if offset.is_finite() {
+                    offset.floor() as i32
+                } else {
+                    0
+                };
+            }
+            if occupied[lod as usize].insert(cell) {
+                break;
+            }
+            lod += 1;
+        }
+        hints.insert(
+            id,
+            LabelHint {
+                priority: priority[i],
+                lod,
+            },
+        );
+    }
+    hints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranks_hubs_first_and_declutters_clusters() {
+        // Hub 0 with 49 leaves on a ring, five of them piled on one spot,
+        // plus 50 unconnected nodes far away.
+        let mut nodes = vec![(0u32, [0.0f32, 0.0, 0.0])];
+        let mut edges = Vec::new();
+        for id in 1..50u32 {
+            let position = if id <= 5 {
+                [600.0, 600.0, 0.0]
+            } else {
+                let a = id as f32 * 0.13;
+                [100.0 * a.cos(), 100.0 * a.sin(), 0.0]
+            };
+            nodes.push((id, position));
+            edges.push((0, id));
+        }
+        for id in 50..100u32 {
+            nodes.push((id, [1000.0 + id as f32 * 40.0, 500.0, 0.0]));
+        }
+        edges.push((7, 999));
+
+        let hints = compute_label_hints(&nodes, &edges);
+        assert_eq!(hints.len(), 100);
+        assert_eq!(
+            hints[&0],
+            LabelHint {
+                priority: 1.0,
+                lod: 0
+            }
+        );
+        assert_eq!(hints[&60].priority, 0.0);
+        assert_eq!(hints[&60].lod, MAX_LABEL_LOD);
+
+        // Leaves 1..=5 share a cell at every level: at most one label each
+        // below the hover-only level, assigned in id order.
+        let piled: Vec<u8> = (1..=5).map(|id| hints[&id].lod).collect();
+        assert_eq!(piled, vec![0, 1, 2, 3, 3]);
+        assert!(hints[&1].priority > 0.0 && hints[&1].priority < 1.0);
+
+        assert_eq!(compute_label_hints(&nodes, &edges), hints, "deterministic");
+    }
+}
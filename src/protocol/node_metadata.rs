@@ -0,0 +1,216 @@
+//! Typed view of `Node.metadata`.
+//!
+//! Node metadata is stored as a string map, so sizes arrive as `"1000"` and
+//! timestamps in whatever format the producer used. [`NodeMetadata`] parses
+//! the well-known keys (under either their snake_case or camelCase spelling)
+//! into numbers, booleans, lists and RFC3339 timestamps. Keys it does not
+//! know, and known keys whose value does not parse, are passed through
+//! unchanged so nothing is lost. Schema 1 clients and clients that set the
+//! `stringMetadata` flag keep receiving the raw map.
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+use super::graph_json::FieldCasing;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeMetadata {
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub node_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public: Option<bool>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_domain: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owl_class_iri: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maturity: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality_score: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authority_score: Option<f64>,
+    /// Everything else, as stored.
+    #[serde(skip)]
+    pub extra: BTreeMap<String, String>,
+}
+
+/// Timestamps are written as RFC3339 by newer code and with chrono's
+/// `Display` (`2024-01-01 00:00:00 UTC`) by older code.
+pub fn parse_timestamp(raw: &str) -> Option<DateTime<Utc>> {
+    let raw = raw.trim();
+    if let Ok(ts) = DateTime::parse_from_rfc3339(raw) {
+        return Some(ts.with_timezone(&Utc));
+    }
+    let naive = raw.strip_suffix(" UTC").unwrap_or(raw);
+    NaiveDateTime::parse_from_str(naive, "%Y-%m-%d %H:%M:%S%.f")
+        .ok()
+        .map(|ts| ts.and_utc())
+}
+
+pub fn parse_size(raw: &str) -> Option<u64> {
+    raw.trim().parse().ok()
+}
+
+fn parse_bool(raw: &str) -> Option<bool> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "true" | "yes" | "1" => Some(true),
+        "false" | "no" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+fn parse_number(raw: &str) -> Option<f64> {
+    raw.trim().parse::<f64>().ok().filter(|v| v.is_finite())
+}
+
+fn parse_tags(raw: &str) -> Option<Vec<String>> {
+    let tags: Vec<String> = raw
+        .split(',')
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .collect();
+    (!tags.is_empty()).then_some(tags)
+}
+
+/// Fill an empty string field; a second spelling of the key is not consumed.
+fn take_str(slot: &mut Option<String>, value: &str) -> bool {
+    if slot.is_some() {
+        return false;
+    }
+    *slot = Some(value.to_string());
+    true
+}
+
+fn take<T>(slot: &mut Option<T>, parsed: Option<T>) -> bool {
+    if slot.is_some() || parsed.is_none() {
+        return false;
+    }
+    *slot = parsed;
+    true
+}
+
+impl From<&HashMap<String, String>> for NodeMetadata {
+    fn from(map: &HashMap<String, String>) -> Self {
+        let mut meta = NodeMetadata::default();
+        // Sorted so that with both spellings present the result is stable.
+        let mut entries: Vec<(&String, &String)> = map.iter().collect();
+        entries.sort_unstable();
+        for (key, value) in entries {
+            let consumed = match key.as_str() {
+                "type" => take_str(&mut meta.node_type, value),
+                "file_name" | "fileName" => take_str(&mut meta.file_name, value),
+                "source_file" | "sourceFile" => take_str(&mut meta.source_file, value),
+                "source_domain" | "sourceDomain" => take_str(&mut meta.source_domain, value),
+                "owl_class_iri" | "owlClassIri" => take_str(&mut meta.owl_class_iri, value),
+                "maturity" => take_str(&mut meta.maturity, value),
+                "file_size" | "fileSize" => take(&mut meta.file_size, parse_size(value)),
+                "last_modified" | "lastModified" => {
+                    take(&mut meta.last_modified, parse_timestamp(value))
+                }
+                "public" => take(&mut meta.public, parse_bool(value)),
+                "quality_score" | "qualityScore" => {
+                    take(&mut meta.quality_score, parse_number(value))
+                }
+                "authority_score" | "authorityScore" => {
+                    take(&mut meta.authority_score, parse_number(value))
+                }
+                "tags" if meta.tags.is_empty() => match parse_tags(value) {
+                    Some(tags) => {
+                        meta.tags = tags;
+                        true
+                    }
+                    None => false,
+                },
+                _ => false,
+            };
+            if !consumed {
+                meta.extra.insert(key.clone(), value.clone());
+            }
+        }
+        meta
+    }
+}
+
+impl NodeMetadata {
+    /// JSON object with typed fields in `casing` and the pass-through keys
+    /// verbatim.
+    pub fn to_json(&self, casing: FieldCasing) -> Value {
+        let Ok(Value::Object(typed)) = serde_json::to_value(self) else {
+            return Value::Null;
+        };
+        let mut out = serde_json::Map::with_capacity(typed.len() + self.extra.len());
+        for (key, value) in self.extra.iter() {
+            out.insert(key.clone(), Value::String(value.clone()));
+        }
+        for (key, value) in typed {
+            let key = match casing {
+                FieldCasing::Camel => key,
+                FieldCasing::Snake => super::graph_json::camel_to_snake(&key),
+            };
+            out.insert(key, value);
+        }
+        Value::Object(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(entries: &[(&str, &str)]) -> HashMap<String, String> {
+        entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn parses_known_keys_and_keeps_the_rest() {
+        let meta = NodeMetadata::from(&map(&[
+            ("type", "page"),
+            ("file_size", "1000"),
+            ("last_modified", "2024-01-01 00:00:00 UTC"),
+            ("public", "true"),
+            ("tags", "rust, graphs"),
+            ("quality_score", "not-a-number"),
+            ("domain", "ai"),
+        ]));
+        assert_eq!(meta.file_size, Some(1000));
+        assert_eq!(meta.public, Some(true));
+        assert_eq!(meta.tags, vec!["rust", "graphs"]);
+        assert_eq!(meta.quality_score, None);
+
+        let json = meta.to_json(FieldCasing::Camel);
+        assert_eq!(json["fileSize"], 1000);
+        assert_eq!(json["lastModified"], "2024-01-01T00:00:00Z");
+        assert_eq!(json["quality_score"], "not-a-number");
+        assert_eq!(json["domain"], "ai");
+        assert!(json.get("file_size").is_none());
+
+        let snake = meta.to_json(FieldCasing::Snake);
+        assert_eq!(snake["file_size"], 1000);
+    }
+
+    #[test]
+    fn accepts_both_timestamp_formats() {
+        assert_eq!(
+            parse_timestamp("2024-03-05T10:00:00+02:00"),
+            parse_timestamp("2024-03-05 08:00:00 UTC")
+        );
+        assert!(parse_timestamp("yesterday").is_none());
+    }
+}
@@ -0,0 +1,159 @@
+//! Positional audio cues for XR clients.
+//!
+//! When something happens at a node (it appears, a search hits it, a chat
+//! answer cites it) opted-in users get a lightweight text frame:
+//!
+//! ```json
+//! {"type": "audioCues", "cues": [{"cueId": "nodeAppear", "nodeId": 12, "position": [1, 2, 3]}], "timestamp": 1760000000000}
+//! ```
+//!
+//! Clients look the cue id up in the catalog (`xr.audioCues` in settings,
+//! served by `GET /api/audio-cues`) and play its sound from the node's
+//! position. Node appearances go to every opted-in user; search hits and
+//! citations go only to the user who searched or chatted. Cues are off until
+//! a user enables them; the set of users who have is kept in
+//! `audio_cues.json` in the data directory.
+
+use crate::config::dev_config;
+use crate::services::json_file::JsonFile;
+use log::info;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+pub const NODE_APPEAR: &str = "nodeAppear";
+pub const SEARCH_HIT: &str = "searchHit";
+pub const CHAT_CITATION: &str = "chatCitation";
+
+/// Cues beyond this in one batch are dropped; a bulk import should not turn
+/// into a wall of sound.
+pub const MAX_CUES_PER_MESSAGE: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioCue {
+    pub cue_id: String,
+    pub node_id: u32,
+    /// Node position in graph space when the cue fired.
+    pub position: [f32; 3],
+}
+
+impl AudioCue {
+    pub fn new(cue_id: &str, node_id: u32, position: [f32; 3]) -> Self {
+        Self {
+            cue_id: cue_id.to_string(),
+            node_id,
+            position,
+        }
+    }
+}
+
+/// The `audioCues` frame for a batch, keeping the first
+/// [`MAX_CUES_PER_MESSAGE`] cues.
+pub fn cue_message(cues: &[AudioCue], timestamp_ms: i64) -> String {
+    let cues = &cues[..cues.len().min(MAX_CUES_PER_MESSAGE)];
+    serde_json::json!({
+        "type": "audioCues",
+        "cues": cues,
+        "timestamp": timestamp_ms,
+    })
+    .to_string()
+}
+
+/// Pubkeys of the users who have turned cues on.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CuePreferences {
+    #[serde(default)]
+    pub enabled: BTreeSet<String>,
+}
+
+impl CuePreferences {
+    pub fn is_enabled(&self, user: &str) -> bool {
+        self.enabled.contains(user)
+    }
+
+    /// Returns whether anything changed.
+    pub fn set(&mut self, user: &str, enabled: bool) -> bool {
+        if enabled {
+            self.enabled.insert(user.to_string())
+        } else {
+            self.enabled.remove(user)
+        }
+    }
+}
+
+/// Process-wide cue preferences persisted to disk.
+pub struct AudioCueService {
+    file: JsonFile,
+    prefs: RwLock<CuePreferences>,
+}
+
+impl AudioCueService {
+    pub fn load(path: PathBuf) -> Self {
+        let file = JsonFile::new(path, "audio cue preferences").pretty();
+        let prefs: CuePreferences = file.load();
+        info!("Audio cues enabled for {} user(s)", prefs.enabled.len());
+        Self {
+            file,
+            prefs: RwLock::new(prefs),
+        }
+    }
+
+    pub fn is_enabled(&self, user: &str) -> bool {
+        self.prefs
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_enabled(user)
+    }
+
+    pub fn set_enabled(&self, user: &str, enabled: bool) -> Result<(), String> {
+        let mut prefs = self.prefs.write().unwrap_or_else(|e| e.into_inner());
+        let mut updated = prefs.clone();
+        if !updated.set(user, enabled) {
+            return Ok(());
+        }
+        self.file.save(&updated)?;
+        *prefs = updated;
+        Ok(())
+    }
+}
+
+static SERVICE: Lazy<AudioCueService> =
+    Lazy::new(|| AudioCueService::load(dev_config::storage().path("audio_cues.json")));
+
+pub fn audio_cues() -> &'static AudioCueService {
+    &SERVICE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batches_are_capped_and_opt_in() {
+        let cues: Vec<AudioCue> = (0..40)
+            .map(|id| AudioCue::new(NODE_APPEAR, id, [id as f32, 0.0, 0.0]))
+            .collect();
+        let message: serde_json::Value = serde_json::from_str(&cue_message(&cues, 1_000)).unwrap();
+        assert_eq!(message["type"], "audioCues");
+        assert_eq!(
+            message["cues"].as_array().unwrap().len(),
+            MAX_CUES_PER_MESSAGE
+        );
+        assert_eq!(
+            message["cues"][1],
+            serde_json::json!({"cueId": "nodeAppear", "nodeId": 1, "position": [1.0, 0.0, 0.0]})
+        );
+
+        let mut prefs = CuePreferences::default();
+        assert!(!prefs.is_enabled("alice"));
+        assert!(prefs.set("alice", true));
+        assert!(!prefs.set("alice", true));
+        assert!(prefs.is_enabled("alice"));
+        assert!(prefs.set("alice", false));
+        assert!(!prefs.set("bob", false));
+        assert!(prefs.enabled.is_empty());
+    }
+}
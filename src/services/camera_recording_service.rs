@@ -0,0 +1,649 @@
+//! Opt-in camera path recording and deterministic replay export.
+//!
+//! A client starts a recording over the socket (`cameraRecordingStart`) and
+//! then streams `cameraPose` samples. Poses are appended to
+//! `<root>/<id>/camera.jsonl`; alongside them the socket handler samples node
+//! positions at most every [`FRAME_INTERVAL_MS`] into `positions.jsonl`, using
+//! the same server-side clock so the two streams line up.
+//!
+//! [`CameraRecordingService::export`] resamples both streams onto a fixed frame
+//! rate and writes `bundle.json`: every output frame carries an interpolated
+//! camera pose and the index of the position frame in effect at that instant.
+//! The bundle depends only on the recorded files, so re-running an export
+//! yields byte-identical output (its SHA-256 is recorded in the manifest).
+//!
+//! The root defaults to `/app/data/recordings` and can be overridden with
+//! `RECORDINGS_DIR`.
+
+use crate::config::dev_config;
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Directory under the storage root used unless `RECORDINGS_DIR` is set.
+const RECORDINGS_SUBDIR: &str = "recordings";
+
+/// Minimum spacing between recorded position frames.
+pub const FRAME_INTERVAL_MS: u64 = 100;
+
+pub const DEFAULT_EXPORT_FPS: u32 = 30;
+const MAX_EXPORT_FPS: u32 = 120;
+
+/// Recordings are capped so a forgotten session cannot fill the disk.
+const MAX_DURATION_MS: u64 = 30 * 60 * 1000;
+
+const BUNDLE_VERSION: u32 = 1;
+
+static RECORDINGS: Lazy<CameraRecordingService> = Lazy::new(|| {
+    let root = std::env::var_os("RECORDINGS_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| dev_config::storage().path(RECORDINGS_SUBDIR));
+    CameraRecordingService::new(root)
+});
+
+/// Process-wide recording store shared by the socket and REST handlers.
+pub fn recordings() -> &'static CameraRecordingService {
+    &RECORDINGS
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CameraPose {
+    /// Milliseconds since the recording started (server clock).
+    pub t_ms: u64,
+    pub position: [f32; 3],
+    /// Orientation quaternion `[x, y, z, w]`.
+    pub rotation: [f32; 4],
+    pub fov: f32,
+}
+
+impl CameraPose {
+    fn is_finite(&self) -> bool {
+        self.position
+            .iter()
+            .chain(self.rotation.iter())
+            .all(|v| v.is_finite())
+            && self.fov.is_finite()
+    }
+
+    /// Linear interpolation of position/fov and normalised lerp of rotation,
+    /// taking the short way round.
+    fn lerp(&self, other: &CameraPose, t_ms: u64) -> CameraPose {
+        let span = other.t_ms.saturating_sub(self.t_ms);
+        let alpha = if span == 0 {
+            0.0
+        } else {
+            (t_ms.saturating_sub(self.t_ms) as f32 / span as f32).clamp(0.0, 1.0)
+        };
+        let mix = |a: f32, b: f32| a + (b - a) * alpha;
+
+        let dot: f32 = (0..4).map(|i| self.rotation[i] * other.rotation[i]).sum();
+        let sign = if dot < 0.0 { -1.0 } else { 1.0 };
+        let mut rotation = [0.0; 4];
+        for (i, r) in rotation.iter_mut().enumerate() {
+            *r = mix(self.rotation[i], other.rotation[i] * sign);
+        }
+        let norm = rotation.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if norm > f32::EPSILON {
+            rotation.iter_mut().for_each(|v| *v /= norm);
+        }
+
+        CameraPose {
+            t_ms,
+            position: [
+                mix(self.position[0], other.position[0]),
+                mix(self.position[1], other.position[1]),
+                mix(self.position[2], other.position[2]),
+            ],
+            rotation,
+            fov: mix(self.fov, other.fov),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FramePosition {
+    pub id: u32,
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionFrame {
+    pub t_ms: u64,
+    pub nodes: Vec<FramePosition>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingMeta {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    pub started_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stopped_at: Option<DateTime<Utc>>,
+    pub pose_count: u64,
+    pub frame_count: u64,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayFrame {
+    pub index: u32,
+    pub t_ms: u64,
+    pub camera: CameraPose,
+    /// Index into [`ReplayBundle::position_frames`].
+    pub position_frame: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayBundle {
+    pub version: u32,
+    pub recording_id: String,
+    pub fps: u32,
+    pub duration_ms: u64,
+    pub position_frames: Vec<PositionFrame>,
+    pub frames: Vec<ReplayFrame>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportManifest {
+    pub recording_id: String,
+    pub version: u32,
+    pub fps: u32,
+    pub frame_count: usize,
+    pub duration_ms: u64,
+    pub bundle_path: String,
+    pub bundle_sha256: String,
+    pub exported_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "state", rename_all = "camelCase")]
+pub enum ExportStatus {
+    Running { fps: u32 },
+    Completed { manifest: ExportManifest },
+    Failed { error: String },
+}
+
+struct ActiveRecording {
+    meta: RecordingMeta,
+    started: Instant,
+    last_frame_ms: Option<u64>,
+}
+
+pub struct CameraRecordingService {
+    root: PathBuf,
+    active: Mutex<HashMap<String, ActiveRecording>>,
+    exports: Mutex<HashMap<String, ExportStatus>>,
+}
+
+fn is_valid_id(id: &str) -> bool {
+    !id.is_empty() && id.len() <= 64 && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+fn append_jsonl<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    let mut line = serde_json::to_vec(value)?;
+    line.push(b'\n');
+    file.write_all(&line)
+}
+
+fn read_jsonl<T: for<'de> Deserialize<'de>>(path: &Path) -> io::Result<Vec<T>> {
+    let file = match File::open(path) {
+        Ok(f) => f,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut out = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        // A crash mid-append can leave a truncated last line; skip it.
+        match serde_json::from_str(&line) {
+            Ok(v) => out.push(v),
+            Err(e) => warn!(
+                "[Recording] Skipping malformed line in {}: {}",
+                path.display(),
+                e
+            ),
+        }
+    }
+    Ok(out)
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_vec_pretty(value)?)?;
+    fs::rename(tmp, path)
+}
+
+/// Resample recorded poses and position frames onto a fixed frame rate.
+pub fn build_bundle(
+    recording_id: &str,
+    mut poses: Vec<CameraPose>,
+    mut position_frames: Vec<PositionFrame>,
+    fps: u32,
+) -> Result<ReplayBundle, String> {
+    if poses.is_empty() {
+        return Err("Recording has no camera poses".to_string());
+    }
+    let fps = fps.clamp(1, MAX_EXPORT_FPS);
+    poses.sort_by_key(|p| p.t_ms);
+    position_frames.sort_by_key(|f| f.t_ms);
+    for frame in &mut position_frames {
+        frame.nodes.sort_by_key(|n| n.id);
+    }
+
+    let duration_ms = poses
+        .last()
+        .map(|p| p.t_ms)
+        .max(position_frames.last().map(|f| f.t_ms))
+        .unwrap_or(0);
+    let frame_count = duration_ms * fps as u64 / 1000 + 1;
+
+    let mut frames = Vec::with_capacity(frame_count as usize);
+    let mut pose_idx = 0;
+    let mut pos_idx = 0;
+    for index in 0..frame_count {
+        let t_ms = index * 1000 / fps as u64;
+
+        while pose_idx + 1 < poses.len() && poses[pose_idx + 1].t_ms <= t_ms {
+            pose_idx += 1;
+        }
+        let camera = match poses.get(pose_idx + 1) {
+            Some(next) if poses[pose_idx].t_ms <= t_ms => poses[pose_idx].lerp(next, t_ms),
+            _ => CameraPose {
+                t_ms,
+                ..poses[pose_idx]
+            },
+        };
+
+        while pos_idx + 1 < position_frames.len() && position_frames[pos_idx + 1].t_ms <= t_ms {
+            pos_idx += 1;
+        }
+
+        frames.push(ReplayFrame {
+            index: index as u32,
+            t_ms,
+            camera,
+            position_frame: pos_idx as u32,
+        });
+    }
+
+    Ok(ReplayBundle {
+        version: BUNDLE_VERSION,
+        recording_id: recording_id.to_string(),
+        fps,
+        duration_ms,
+        position_frames,
+        frames,
+    })
+}
+
+impl CameraRecordingService {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            active: Mutex::new(HashMap::new()),
+            exports: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn dir(&self, id: &str) -> PathBuf {
+        self.root.join(id)
+    }
+
+    pub fn bundle_path(&self, id: &str) -> Option<PathBuf> {
+        is_valid_id(id).then(|| self.dir(id).join("bundle.json"))
+    }
+
+    pub fn start(&self, owner: Option<String>) -> io::Result<RecordingMeta> {
+        let id = uuid::Uuid::new_v4().to_string();
+        fs::create_dir_all(self.dir(&id))?;
+        let meta = RecordingMeta {
+            id: id.clone(),
+            owner,
+            started_at: Utc::now(),
+            stopped_at: None,
+            pose_count: 0,
+            frame_count: 0,
+            duration_ms: 0,
+        };
+        write_json(&self.dir(&id).join("meta.json"), &meta)?;
+        let mut active = self.active.lock().unwrap_or_else(|p| p.into_inner());
+        active.insert(
+            id.clone(),
+            ActiveRecording {
+                meta: meta.clone(),
+                started: Instant::now(),
+                last_frame_ms: None,
+            },
+        );
+        info!("[Recording] Started camera recording {}", id);
+        Ok(meta)
+    }
+
+    /// Milliseconds since `id` started, or `None` if it is not recording or
+    /// has hit the duration cap.
+    pub fn elapsed_ms(&self, id: &str) -> Option<u64> {
+        let active = self.active.lock().unwrap_or_else(|p| p.into_inner());
+        let elapsed = active.get(id)?.started.elapsed().as_millis() as u64;
+        (elapsed <= MAX_DURATION_MS).then_some(elapsed)
+    }
+
+    pub fn append_pose(&self, id: &str, pose: CameraPose) -> io::Result<()> {
+        if !pose.is_finite() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "non-finite camera pose",
+            ));
+        }
+        let mut active = self.active.lock().unwrap_or_else(|p| p.into_inner());
+        let Some(rec) = active.get_mut(id) else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "recording is not active",
+            ));
+        };
+        append_jsonl(&self.dir(id).join("camera.jsonl"), &pose)?;
+        rec.meta.pose_count += 1;
+        rec.meta.duration_ms = rec.meta.duration_ms.max(pose.t_ms);
+        Ok(())
+    }
+
+    /// Whether a position frame should be captured at `t_ms`. Reserves the
+    /// slot so concurrent pose messages do not trigger duplicate captures.
+    pub fn claim_frame(&self, id: &str, t_ms: u64) -> bool {
+        let mut active = self.active.lock().unwrap_or_else(|p| p.into_inner());
+        let Some(rec) = active.get_mut(id) else {
+            return false;
+        };
+        let due = !matches!(rec.last_frame_ms, Some(last) if t_ms < last + FRAME_INTERVAL_MS);
+        if due {
+            rec.last_frame_ms = Some(t_ms);
+        }
+        due
+    }
+
+    pub fn append_frame(&self, id: &str, frame: PositionFrame) -> io::Result<()> {
+        let mut active = self.active.lock().unwrap_or_else(|p| p.into_inner());
+        let Some(rec) = active.get_mut(id) else {
+            // Stopped while the snapshot was in flight.
+            return Ok(());
+        };
+        append_jsonl(&self.dir(id).join("positions.jsonl"), &frame)?;
+        rec.meta.frame_count += 1;
+        rec.meta.duration_ms = rec.meta.duration_ms.max(frame.t_ms);
+        Ok(())
+    }
+
+    pub fn stop(&self, id: &str) -> io::Result<Option<RecordingMeta>> {
+        let rec = {
+            let mut active = self.active.lock().unwrap_or_else(|p| p.into_inner());
+            active.remove(id)
+        };
+        let Some(mut rec) = rec else {
+            return Ok(None);
+        };
+        rec.meta.stopped_at = Some(Utc::now());
+        write_json(&self.dir(id).join("meta.json"), &rec.meta)?;
+        info!(
+            "[Recording] Stopped camera recording {} ({} poses, {} frames)",
+            id, rec.meta.pose_count, rec.meta.frame_count
+        );
+        Ok(Some(rec.meta))
+    }
+
+    pub fn get(&self, id: &str) -> Option<RecordingMeta> {
+        if !is_valid_id(id) {
+            return None;
+        }
+        if let Some(rec) = self
+            .active
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .get(id)
+        {
+            return Some(rec.meta.clone());
+        }
+        let bytes = fs::read(self.dir(id).join("meta.json")).ok()?;
+        let meta: RecordingMeta = serde_json::from_slice(&bytes).ok()?;
+        if meta.stopped_at.is_none() {
+            return Some(self.recover_orphan(id, meta));
+        }
+        Some(meta)
+    }
+
+    /// Finalise a recording that is not active but was never stopped — its
+    /// session ended without `stop` (server restart, or a failed write on
+    /// disconnect). Counts and duration are rebuilt from the streams on disk so
+    /// the recording can still be exported.
+    fn recover_orphan(&self, id: &str, mut meta: RecordingMeta) -> RecordingMeta {
+        let dir = self.dir(id);
+        let poses: Vec<CameraPose> = read_jsonl(&dir.join("camera.jsonl")).unwrap_or_default();
+        let frames: Vec<PositionFrame> =
+            read_jsonl(&dir.join("positions.jsonl")).unwrap_or_default();
+        meta.pose_count = poses.len() as u64;
+        meta.frame_count = frames.len() as u64;
+        meta.duration_ms = poses
+            .iter()
+            .map(|p| p.t_ms)
+            .chain(frames.iter().map(|f| f.t_ms))
+            .max()
+            .unwrap_or(0);
+        meta.stopped_at =
+            Some(meta.started_at + chrono::Duration::milliseconds(meta.duration_ms as i64));
+        match write_json(&dir.join("meta.json"), &meta) {
+            Ok(()) => info!(
+                "[Recording] Recovered orphaned recording {} ({} poses, {} frames)",
+                id, meta.pose_count, meta.frame_count
+            ),
+            Err(e) => warn!("[Recording] Failed to persist recovered recording {}: {}", id, e),
+        }
+        meta
+    }
+
+    /// All recordings on disk, newest first.
+    pub fn list(&self) -> Vec<RecordingMeta> {
+        let Ok(entries) = fs::read_dir(&self.root) else {
+            return Vec::new();
+        };
+        let mut out: Vec<RecordingMeta> = entries
+            .filter_map(|e| e.ok())
+            .filter_map(|e| e.file_name().into_string().ok())
+            .filter_map(|id| self.get(&id))
+            .collect();
+        out.sort_by_key(|m| std::cmp::Reverse(m.started_at));
+        out
+    }
+
+    pub fn export_status(&self, id: &str) -> Option<ExportStatus> {
+        self.exports
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .get(id)
+            .cloned()
+    }
+
+    /// Mark an export as running. Returns `false` if one is already in flight.
+    pub fn begin_export(&self, id: &str, fps: u32) -> bool {
+        let mut exports = self.exports.lock().unwrap_or_else(|p| p.into_inner());
+        if matches!(exports.get(id), Some(ExportStatus::Running { .. })) {
+            return false;
+        }
+        exports.insert(id.to_string(), ExportStatus::Running { fps });
+        true
+    }
+
+    /// Build and write the replay bundle for a stopped recording. Blocking;
+    /// callers run it on the blocking pool.
+    pub fn export(&self, id: &str, fps: u32) -> Result<ExportManifest, String> {
+        let result = self.export_inner(id, fps);
+        let status = match &result {
+            Ok(manifest) => ExportStatus::Completed {
+                manifest: manifest.clone(),
+            },
+            Err(e) => ExportStatus::Failed { error: e.clone() },
+        };
+        self.exports
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .insert(id.to_string(), status);
+        result
+    }
+
+    fn export_inner(&self, id: &str, fps: u32) -> Result<ExportManifest, String> {
+        let meta = self
+            .get(id)
+            .ok_or_else(|| format!("Recording {} not found", id))?;
+        if meta.stopped_at.is_none() {
+            return Err("Recording is still in progress".to_string());
+        }
+        let dir = self.dir(id);
+        let poses: Vec<CameraPose> =
+            read_jsonl(&dir.join("camera.jsonl")).map_err(|e| e.to_string())?;
+        let frames: Vec<PositionFrame> =
+            read_jsonl(&dir.join("positions.jsonl")).map_err(|e| e.to_string())?;
+
+        let bundle = build_bundle(id, poses, frames, fps)?;
+        let bytes = serde_json::to_vec(&bundle).map_err(|e| e.to_string())?;
+        let bundle_path = dir.join("bundle.json");
+        fs::write(&bundle_path, &bytes).map_err(|e| e.to_string())?;
+
+        let manifest = ExportManifest {
+            recording_id: id.to_string(),
+            version: bundle.version,
+            fps: bundle.fps,
+            frame_count: bundle.frames.len(),
+            duration_ms: bundle.duration_ms,
+            bundle_path: bundle_path.display().to_string(),
+            bundle_sha256: hex::encode(Sha256::digest(&bytes)),
+            exported_at: Utc::now(),
+        };
+        write_json(&dir.join("manifest.json"), &manifest).map_err(|e| e.to_string())?;
+        info!(
+            "[Recording] Exported {} ({} frames @ {} fps)",
+            id, manifest.frame_count, manifest.fps
+        );
+        Ok(manifest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pose(t_ms: u64, x: f32) -> CameraPose {
+        CameraPose {
+            t_ms,
+            position: [x, 0.0, 0.0],
+            rotation: [0.0, 0.0, 0.0, 1.0],
+            fov: 60.0,
+        }
+    }
+
+    fn frame(t_ms: u64, x: f32) -> PositionFrame {
+        PositionFrame {
+            t_ms,
+            nodes: vec![FramePosition {
+                id: 1,
+                x,
+                y: 0.0,
+                z: 0.0,
+            }],
+        }
+    }
+
+    #[test]
+    fn bundle_interpolates_camera_and_holds_positions() {
+        let bundle = build_bundle(
+            "rec",
+            vec![pose(0, 0.0), pose(1000, 10.0)],
+            vec![frame(0, 1.0), frame(500, 2.0)],
+            10,
+        )
+        .unwrap();
+
+        assert_eq!(bundle.frames.len(), 11);
+        let mid = &bundle.frames[5];
+        assert_eq!(mid.t_ms, 500);
+        assert!((mid.camera.position[0] - 5.0).abs() < 1e-4);
+        assert_eq!(mid.position_frame, 1);
+        assert_eq!(bundle.frames[4].position_frame, 0);
+        assert!((bundle.frames[10].camera.position[0] - 10.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn export_is_deterministic() {
+        let root = std::env::temp_dir().join(format!("recordings-{}", uuid::Uuid::new_v4()));
+        let service = CameraRecordingService::new(&root);
+        let meta = service.start(None).unwrap();
+        service.append_pose(&meta.id, pose(0, 0.0)).unwrap();
+        service.append_pose(&meta.id, pose(200, 4.0)).unwrap();
+        assert!(service.claim_frame(&meta.id, 0));
+        assert!(!service.claim_frame(&meta.id, 50));
+        service.append_frame(&meta.id, frame(0, 1.0)).unwrap();
+
+        assert!(
+            service.export(&meta.id, 30).is_err(),
+            "active recordings cannot export"
+        );
+        service.stop(&meta.id).unwrap();
+
+        let first = service.export(&meta.id, 30).unwrap();
+        let second = service.export(&meta.id, 30).unwrap();
+        assert_eq!(first.bundle_sha256, second.bundle_sha256);
+        assert_eq!(first.frame_count, 7);
+        assert_eq!(service.list().len(), 1);
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn orphaned_recordings_are_recovered_for_export() {
+        let root = std::env::temp_dir().join(format!("recordings-{}", uuid::Uuid::new_v4()));
+        let id = {
+            let service = CameraRecordingService::new(&root);
+            let meta = service.start(None).unwrap();
+            service.append_pose(&meta.id, pose(0, 0.0)).unwrap();
+            service.append_pose(&meta.id, pose(300, 2.0)).unwrap();
+            service.append_frame(&meta.id, frame(0, 1.0)).unwrap();
+            meta.id
+        };
+
+        // A fresh store has no active session for `id`, as after a restart.
+        let service = CameraRecordingService::new(&root);
+        let meta = service.get(&id).unwrap();
+        assert!(meta.stopped_at.is_some());
+        assert_eq!(meta.pose_count, 2);
+        assert_eq!(meta.frame_count, 1);
+        assert_eq!(meta.duration_ms, 300);
+        assert!(service.export(&id, 10).is_ok());
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn rejects_path_like_ids() {
+        let service = CameraRecordingService::new("/nonexistent");
+        assert!(service.get("../etc").is_none());
+        assert!(service.bundle_path("a/b").is_none());
+    }
+}
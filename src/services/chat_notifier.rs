@@ -0,0 +1,163 @@
+//! Slack / Discord notifiers layered on the webhook event hub.
+//!
+//! Chat webhooks take a plain message rather than our JSON envelope, so each
+//! [`GraphEvent`] is rendered to a short human-readable line and wrapped in the
+//! platform's payload shape. Delivery, retries and the delivery log are shared
+//! with the generic webhooks in [`super::webhook_service`], and like them the
+//! `webhooks.slack` / `webhooks.discord` blocks are re-read on every dispatch,
+//! so a notifier configured at runtime starts posting with the next event.
+
+use serde_json::{json, Value};
+
+use super::webhook_service::GraphEvent;
+use crate::config::ChatNotifierSettings;
+
+/// Discord rejects `content` longer than 2000 characters.
+const DISCORD_MAX_CHARS: usize = 2000;
+/// Slack truncates long `text`; keep messages readable.
+const SLACK_MAX_CHARS: usize = 3000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatPlatform {
+    Slack,
+    Discord,
+}
+
+impl ChatPlatform {
+    fn max_chars(self) -> usize {
+        match self {
+            ChatPlatform::Slack => SLACK_MAX_CHARS,
+            ChatPlatform::Discord => DISCORD_MAX_CHARS,
+        }
+    }
+}
+
+pub fn notifier_accepts(settings: &ChatNotifierSettings, event: &str) -> bool {
+    settings.events.iter().any(|e| e == event || e == "*")
+}
+
+/// One-line (plus optional detail) rendering of an event for a chat channel.
+pub fn render_message(event: &GraphEvent) -> String {
+    match event {
+        GraphEvent::SyncCompleted {
+            files_scanned,
+            files_processed,
+            duration_ms,
+            error_count,
+        } => {
+            let status = if *error_count > 0 { ":warning:" } else { ":white_check_mark:" };
+            format!(
+                "{} Sync completed: {} of {} files processed in {:.1}s ({} errors)",
+                status,
+                files_processed,
+                files_scanned,
+                *duration_ms as f64 / 1000.0,
+                error_count
+            )
+        }
+        GraphEvent::NodeCreated { label, metadata_id, .. } => {
+            format!(":new: Node created: {} ({})", label, metadata_id)
+        }
+        GraphEvent::ClusterChanged {
+            clusters,
+            assigned_nodes,
+            changed_nodes,
+        } => format!(
+            ":busts_in_silhouette: Clusters changed: {} nodes moved; {} nodes across {} clusters",
+            changed_nodes, assigned_nodes, clusters
+        ),
+        GraphEvent::Error { source, message } => {
+            format!(":rotating_light: Error in {}: {}", source, message)
+        }
+        GraphEvent::GpuFallback { kernel, reason } => format!(
+            ":warning: GPU fallback: {} ran on the CPU ({})",
+            kernel, reason
+        ),
+        GraphEvent::DigestPublished {
+            date,
+            page,
+            created,
+            edited,
+            removed,
+            summary,
+        } => {
+            let mut text = format!(
+                ":newspaper: Daily digest {} — {} created, {} edited, {} removed ({})",
+                date, created, edited, removed, page
+            );
+            if let Some(summary) = summary {
+                text.push('\n');
+                text.push_str(summary);
+            }
+            text
+        }
+    }
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut out: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    out.push('…');
+    out
+}
+
+/// Request body for the platform's incoming-webhook API.
+pub fn payload(platform: ChatPlatform, event: &GraphEvent) -> Value {
+    let text = truncate(&render_message(event), platform.max_chars());
+    match platform {
+        ChatPlatform::Slack => json!({ "text": text }),
+        ChatPlatform::Discord => json!({ "content": text }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn platform_payload_shapes() {
+        let event = GraphEvent::Error {
+            source: "github_sync".into(),
+            message: "rate limited".into(),
+        };
+        let slack = payload(ChatPlatform::Slack, &event);
+        assert!(slack["text"].as_str().unwrap().contains("github_sync"));
+        let discord = payload(ChatPlatform::Discord, &event);
+        assert!(discord["content"].as_str().unwrap().contains("rate limited"));
+    }
+
+    #[test]
+    fn chat_notifier_alone_activates_webhooks() {
+        let settings = crate::config::WebhookSettings {
+            enabled: true,
+            slack: Some(ChatNotifierSettings {
+                webhook_url: "https://hooks.example.invalid".into(),
+                events: vec!["error".into()],
+            }),
+            ..Default::default()
+        };
+        assert!(crate::services::webhook_service::is_active(&settings));
+    }
+
+    #[test]
+    fn discord_messages_are_truncated() {
+        let event = GraphEvent::Error {
+            source: "x".into(),
+            message: "y".repeat(5000),
+        };
+        let discord = payload(ChatPlatform::Discord, &event);
+        assert_eq!(discord["content"].as_str().unwrap().chars().count(), DISCORD_MAX_CHARS);
+    }
+
+    #[test]
+    fn default_events_filter() {
+        let settings = ChatNotifierSettings {
+            webhook_url: "https://hooks.example.invalid".into(),
+            events: vec!["error".into(), "gpu.fallback".into()],
+        };
+        assert!(notifier_accepts(&settings, "error"));
+        assert!(!notifier_accepts(&settings, "node.created"));
+    }
+}
@@ -0,0 +1,484 @@
+//! Usage and estimated cost of paid external APIs.
+//!
+//! Each call to OpenAI (chat, embeddings, text-to-speech, realtime voice,
+//! transcription) or Perplexity records its requests and tokens, characters
+//! for speech or seconds for transcribed audio, against the current UTC day.
+//! The cost is estimated from list prices ([`PRICES`]), as no provider
+//! reports one. Days are kept in `api_costs.json` under the data dir for
+//! [`RETENTION_DAYS`] and served by `GET /api/admin/costs`. The file is
+//! written [`SAVE_DELAY`] after a call, once for a burst of calls, off the
+//! async threads.
+//!
+//! With `apiCosts.enabled`, [`CostService::enrichment_allowed`] refuses once
+//! the day's or month's spend reaches its cap, and background enrichment
+//! (page embeddings, digest summaries) waits until it rolls over.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use crate::config::dev_config;
+use crate::services::json_file::JsonFile;
+use crate::config::ApiCostSettings;
+
+/// Days of usage kept.
+pub const RETENTION_DAYS: i64 = 90;
+
+/// How long after a recorded call the ledger is written.
+pub const SAVE_DELAY: std::time::Duration = std::time::Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ApiProvider {
+    OpenaiChat,
+    OpenaiEmbeddings,
+    OpenaiTts,
+    OpenaiRealtime,
+    OpenaiTranscription,
+    Perplexity,
+}
+
+/// List prices in USD.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Price {
+    pub provider: ApiProvider,
+    /// The model the price is for.
+    pub model: &'static str,
+    pub per_million_input_tokens: f64,
+    pub per_million_output_tokens: f64,
+    pub per_million_characters: f64,
+    pub per_minute_of_audio: f64,
+    pub per_request: f64,
+}
+
+pub const PRICES: [Price; 6] = [
+    Price {
+        provider: ApiProvider::OpenaiChat,
+        model: "gpt-4o-mini",
+        per_million_input_tokens: 0.15,
+        per_million_output_tokens: 0.60,
+        per_million_characters: 0.0,
+        per_minute_of_audio: 0.0,
+        per_request: 0.0,
+    },
+    Price {
+        provider: ApiProvider::OpenaiEmbeddings,
+        model: "text-embedding-3-small",
+        per_million_input_tokens: 0.02,
+        per_million_output_tokens: 0.0,
+        per_million_characters: 0.0,
+        per_minute_of_audio: 0.0,
+        per_request: 0.0,
+    },
+    Price {
+        provider: ApiProvider::OpenaiTts,
+        model: "tts-1",
+        per_million_input_tokens: 0.0,
+        per_million_output_tokens: 0.0,
+        per_million_characters: 15.0,
+        per_minute_of_audio: 0.0,
+        per_request: 0.0,
+    },
+    // Audio token rates; realtime text tokens cost less, so this errs high.
+    Price {
+        provider: ApiProvider::OpenaiRealtime,
+        model: "gpt-4o-realtime-preview",
+        per_million_input_tokens: 100.0,
+        per_million_output_tokens: 200.0,
+        per_million_characters: 0.0,
+        per_minute_of_audio: 0.0,
+        per_request: 0.0,
+    },
+    Price {
+        provider: ApiProvider::OpenaiTranscription,
+        model: "whisper-1",
+        per_million_input_tokens: 0.0,
+        per_million_output_tokens: 0.0,
+        per_million_characters: 0.0,
+        per_minute_of_audio: 0.006,
+        per_request: 0.0,
+    },
+    Price {
+        provider: ApiProvider::Perplexity,
+        model: "sonar",
+        per_million_input_tokens: 1.0,
+        per_million_output_tokens: 1.0,
+        per_million_characters: 0.0,
+        per_minute_of_audio: 0.0,
+        per_request: 0.005,
+    },
+];
+
+pub fn price(provider: ApiProvider) -> &'static Price {
+    PRICES
+        .iter()
+        .find(|p| p.provider == provider)
+        .expect("every provider has a price")
+}
+
+/// Rough token count for providers that do not report usage.
+pub fn estimate_tokens(text: &str) -> u64 {
+    text.chars().count().div_ceil(4) as u64
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiUsage {
+    pub requests: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub characters: u64,
+    #[serde(default)]
+    pub audio_seconds: f64,
+    pub estimated_cost_usd: f64,
+}
+
+impl ApiUsage {
+    /// One request of `input` and `output` tokens.
+    pub fn tokens(input: u64, output: u64) -> Self {
+        Self {
+            requests: 1,
+            input_tokens: input,
+            output_tokens: output,
+            ..Default::default()
+        }
+    }
+
+    /// One request of `characters` characters.
+    pub fn characters(characters: u64) -> Self {
+        Self {
+            requests: 1,
+            characters,
+            ..Default::default()
+        }
+    }
+
+    /// One request transcribing `seconds` of audio.
+    pub fn audio(seconds: f64) -> Self {
+        Self {
+            requests: 1,
+            audio_seconds: seconds,
+            ..Default::default()
+        }
+    }
+
+    fn priced(mut self, price: &Price) -> Self {
+        self.estimated_cost_usd = (self.input_tokens as f64 * price.per_million_input_tokens
+            + self.output_tokens as f64 * price.per_million_output_tokens
+            + self.characters as f64 * price.per_million_characters)
+            / 1_000_000.0
+            + self.audio_seconds / 60.0 * price.per_minute_of_audio
+            + self.requests as f64 * price.per_request;
+        self
+    }
+
+    fn add(&mut self, other: &ApiUsage) {
+        self.requests += other.requests;
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.characters += other.characters;
+        self.audio_seconds += other.audio_seconds;
+        self.estimated_cost_usd += other.estimated_cost_usd;
+    }
+}
+
+/// Usage per day (`YYYY-MM-DD`) and provider.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CostLedger {
+    pub days: BTreeMap<NaiveDate, BTreeMap<ApiProvider, ApiUsage>>,
+}
+
+impl CostLedger {
+    pub fn record(&mut self, day: NaiveDate, provider: ApiProvider, usage: ApiUsage) {
+        let usage = usage.priced(price(provider));
+        self.days
+            .entry(day)
+            .or_default()
+            .entry(provider)
+            .or_default()
+            .add(&usage);
+        let oldest = day - Duration::days(RETENTION_DAYS - 1);
+        self.days.retain(|d, _| *d >= oldest);
+    }
+
+    /// Estimated spend over `from..=to`.
+    pub fn spent(&self, from: NaiveDate, to: NaiveDate) -> f64 {
+        self.days
+            .range(from..=to)
+            .flat_map(|(_, providers)| providers.values())
+            .map(|u| u.estimated_cost_usd)
+            .sum()
+    }
+
+    /// The last `days` days up to `today`, newest first, with totals.
+    pub fn summary(&self, today: NaiveDate, days: i64) -> CostSummary {
+        let from = today - Duration::days(days.max(1) - 1);
+        let mut providers: BTreeMap<ApiProvider, ApiUsage> = BTreeMap::new();
+        let mut listed = Vec::new();
+        for (date, usage) in self.days.range(from..=today).rev() {
+            for (provider, u) in usage {
+                providers.entry(*provider).or_default().add(u);
+            }
+            listed.push(DayCosts {
+                date: *date,
+                total_usd: usage.values().map(|u| u.estimated_cost_usd).sum(),
+                providers: usage.clone(),
+            });
+        }
+        CostSummary {
+            from,
+            to: today,
+            total_usd: providers.values().map(|u| u.estimated_cost_usd).sum(),
+            providers,
+            days: listed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DayCosts {
+    pub date: NaiveDate,
+    pub total_usd: f64,
+    pub providers: BTreeMap<ApiProvider, ApiUsage>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostSummary {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub total_usd: f64,
+    pub providers: BTreeMap<ApiProvider, ApiUsage>,
+    pub days: Vec<DayCosts>,
+}
+
+/// Spend against the configured caps.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetStatus {
+    pub enabled: bool,
+    pub daily_spent_usd: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub daily_budget_usd: Option<f64>,
+    pub monthly_spent_usd: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub monthly_budget_usd: Option<f64>,
+    /// Why enrichment is paused, if it is.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paused: Option<String>,
+}
+
+pub fn budget_status(
+    ledger: &CostLedger,
+    settings: &ApiCostSettings,
+    today: NaiveDate,
+) -> BudgetStatus {
+    let month_start = today.with_day0(0).unwrap_or(today);
+    let daily = ledger.spent(today, today);
+    let monthly = ledger.spent(month_start, today);
+    let paused = if !settings.enabled {
+        None
+    } else if let Some(cap) = settings.daily_budget_usd.filter(|cap| daily >= *cap) {
+        Some(format!(
+            "Daily API budget of ${:.2} reached (${:.2} spent)",
+            cap, daily
+        ))
+    } else {
+        settings
+            .monthly_budget_usd
+            .filter(|cap| monthly >= *cap)
+            .map(|cap| {
+                format!(
+                    "Monthly API budget of ${:.2} reached (${:.2} spent)",
+                    cap, monthly
+                )
+            })
+    };
+    BudgetStatus {
+        enabled: settings.enabled,
+        daily_spent_usd: daily,
+        daily_budget_usd: settings.daily_budget_usd,
+        monthly_spent_usd: monthly,
+        monthly_budget_usd: settings.monthly_budget_usd,
+        paused,
+    }
+}
+
+/// Process-wide API usage ledger.
+pub struct CostService {
+    file: JsonFile,
+    ledger: Arc<RwLock<CostLedger>>,
+    /// A write is scheduled and will include every call recorded before it.
+    save_pending: Arc<AtomicBool>,
+}
+
+fn today() -> NaiveDate {
+    Utc::now().date_naive()
+}
+
+impl CostService {
+    pub fn load(path: PathBuf) -> Self {
+        let file = JsonFile::new(path, "API usage");
+        let ledger: CostLedger = file.load();
+        info!("Loaded API usage for {} day(s)", ledger.days.len());
+        Self {
+            file,
+            ledger: Arc::new(RwLock::new(ledger)),
+            save_pending: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Record one call's usage against today. The ledger is written
+    /// [`SAVE_DELAY`] later, on the blocking pool.
+    pub fn record(&self, provider: ApiProvider, usage: ApiUsage) {
+        self.ledger
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .record(today(), provider, usage);
+        self.schedule_save();
+    }
+
+    fn schedule_save(&self) {
+        if self.save_pending.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            // Outside a runtime (tools, tests) there is no thread to block.
+            self.save_pending.store(false, Ordering::SeqCst);
+            let ledger = self.ledger.read().unwrap_or_else(|e| e.into_inner());
+            if let Err(e) = self.file.save(&*ledger) {
+                warn!("[Costs] {}", e);
+            }
+            return;
+        };
+        let file = self.file.clone();
+        let ledger = self.ledger.clone();
+        let pending = self.save_pending.clone();
+        runtime.spawn(async move {
+            tokio::time::sleep(SAVE_DELAY).await;
+            // Cleared first so a call recorded during the write schedules another.
+            pending.store(false, Ordering::SeqCst);
+            let snapshot = ledger.read().unwrap_or_else(|e| e.into_inner()).clone();
+            match tokio::task::spawn_blocking(move || file.save(&snapshot)).await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => warn!("[Costs] {}", e),
+                Err(e) => warn!("[Costs] Saving API usage failed: {}", e),
+            }
+        });
+    }
+
+    pub fn summary(&self, days: i64) -> CostSummary {
+        self.ledger
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .summary(today(), days)
+    }
+
+    pub fn budget(&self, settings: &ApiCostSettings) -> BudgetStatus {
+        budget_status(
+            &self.ledger.read().unwrap_or_else(|e| e.into_inner()),
+            settings,
+            today(),
+        )
+    }
+
+    /// `Err` with the reason when background enrichment should wait.
+    pub fn enrichment_allowed(&self, settings: &ApiCostSettings) -> Result<(), String> {
+        match self.budget(settings).paused {
+            Some(reason) => Err(reason),
+            None => Ok(()),
+        }
+    }
+}
+
+static SERVICE: Lazy<CostService> =
+    Lazy::new(|| CostService::load(dev_config::storage().path("api_costs.json")));
+
+pub fn costs() -> &'static CostService {
+    &SERVICE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prices_usage_and_enforces_caps() {
+        let day = |d: &str| NaiveDate::parse_from_str(d, "%Y-%m-%d").unwrap();
+        let mut ledger = CostLedger::default();
+        ledger.record(
+            day("2026-03-01"),
+            ApiProvider::OpenaiChat,
+            ApiUsage::tokens(1_000_000, 1_000_000),
+        );
+        ledger.record(
+            day("2026-03-02"),
+            ApiProvider::OpenaiTts,
+            ApiUsage::characters(100_000),
+        );
+        ledger.record(
+            day("2026-03-02"),
+            ApiProvider::Perplexity,
+            ApiUsage::tokens(1_000, 1_000),
+        );
+        ledger.record(
+            day("2026-03-02"),
+            ApiProvider::Perplexity,
+            ApiUsage::tokens(1_000, 1_000),
+        );
+        assert!((ledger.spent(day("2026-03-01"), day("2026-03-01")) - 0.75).abs() < 1e-9);
+        assert!((ledger.spent(day("2026-03-02"), day("2026-03-02")) - 1.514).abs() < 1e-9);
+
+        let summary = ledger.summary(day("2026-03-02"), 1);
+        assert_eq!(summary.days.len(), 1);
+        assert_eq!(summary.providers[&ApiProvider::Perplexity].requests, 2);
+        assert!(!summary.providers.contains_key(&ApiProvider::OpenaiChat));
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["days"][0]["date"], "2026-03-02");
+        assert!(json["providers"]["openaiTts"]["characters"] == 100_000);
+
+        ledger.record(
+            day("2026-03-02"),
+            ApiProvider::OpenaiTranscription,
+            ApiUsage::audio(90.0),
+        );
+        assert!((ledger.spent(day("2026-03-02"), day("2026-03-02")) - 1.523).abs() < 1e-9);
+        let old: ApiUsage = serde_json::from_str(
+            r#"{"requests":1,"inputTokens":0,"outputTokens":0,"characters":5,"estimatedCostUsd":0.1}"#,
+        )
+        .unwrap();
+        assert_eq!(old.audio_seconds, 0.0);
+
+        let mut settings = ApiCostSettings {
+            enabled: false,
+            daily_budget_usd: Some(1.0),
+            monthly_budget_usd: Some(2.0),
+        };
+        assert!(budget_status(&ledger, &settings, day("2026-03-02"))
+            .paused
+            .is_none());
+        settings.enabled = true;
+        let status = budget_status(&ledger, &settings, day("2026-03-02"));
+        assert!(status.paused.unwrap().starts_with("Daily"));
+        let status = budget_status(&ledger, &settings, day("2026-03-03"));
+        assert!(status.paused.unwrap().starts_with("Monthly"));
+        assert!(budget_status(&ledger, &settings, day("2026-04-01"))
+            .paused
+            .is_none());
+
+        ledger.record(
+            day("2026-06-30"),
+            ApiProvider::OpenaiEmbeddings,
+            ApiUsage::tokens(10, 0),
+        );
+        assert_eq!(ledger.days.keys().next().copied(), Some(day("2026-06-30")));
+    }
+}
@@ -0,0 +1,243 @@
+//! Graph revisions and session resumption.
+//!
+//! Every `graphDiff` broadcast by `GraphStateActor` is stamped with the next
+//! graph revision and kept in a bounded journal. Each socket session is given
+//! a resume token; when the connection drops the session is parked with the
+//! revision it had reached. A client that reconnects with that token within
+//! [`RESUME_WINDOW`] is replayed only the diffs it missed, rather than the
+//! full initial payload, so a mobile or XR network blip goes unnoticed.
+//!
+//! Resumption falls back to a full load when the token is unknown or stale,
+//! or when the journal no longer reaches back to the client's revision:
+//! either it has rolled over, or the graph changed in a way that is not
+//! expressed as diffs — a rebuild or reload, which renumbers nodes, or any
+//! node or edge added, removed or updated outside the ephemeral path (see
+//! [`DiffJournal::reset`]).
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long a dropped session can still be resumed.
+pub const RESUME_WINDOW: Duration = Duration::from_secs(120);
+
+/// Diffs kept for replay.
+pub const JOURNAL_CAPACITY: usize = 2048;
+
+static DIFF_JOURNAL: Lazy<DiffJournal> =
+    Lazy::new(|| DiffJournal::new(JOURNAL_CAPACITY, RESUME_WINDOW));
+
+/// Process-wide journal shared by the graph actor and socket sessions.
+pub fn diff_journal() -> &'static DiffJournal {
+    &DIFF_JOURNAL
+}
+
+/// Diffs to send a resuming session, oldest first.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Replay {
+    pub from_revision: u64,
+    pub to_revision: u64,
+    pub diffs: Vec<serde_json::Value>,
+}
+
+/// Why a session could not be resumed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResumeRejected {
+    UnknownToken,
+    Expired,
+    /// The journal no longer covers the client's revision.
+    Gap,
+}
+
+impl ResumeRejected {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::UnknownToken => "unknownToken",
+            Self::Expired => "expired",
+            Self::Gap => "gap",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Parked {
+    revision: u64,
+    since: Instant,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    revision: u64,
+    /// Oldest revision a client can resume from: every diff after it is
+    /// still in `entries`.
+    floor: u64,
+    entries: VecDeque<(u64, serde_json::Value)>,
+    parked: HashMap<String, Parked>,
+}
+
+#[derive(Debug)]
+pub struct DiffJournal {
+    capacity: usize,
+    window: Duration,
+    inner: Mutex<Inner>,
+}
+
+impl DiffJournal {
+    pub fn new(capacity: usize, window: Duration) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            window,
+            inner: Mutex::new(Inner::default()),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn revision(&self) -> u64 {
+        self.lock().revision
+    }
+
+    /// Stamp `diff` with the next revision and keep it for replay. Returns
+    /// the stamped diff for broadcasting.
+    pub fn record(&self, mut diff: serde_json::Value) -> serde_json::Value {
+        let mut inner = self.lock();
+        inner.revision += 1;
+        let revision = inner.revision;
+        diff["revision"] = revision.into();
+        if inner.entries.len() == self.capacity {
+            if let Some((evicted, _)) = inner.entries.pop_front() {
+                inner.floor = evicted;
+            }
+        }
+        inner.entries.push_back((revision, diff.clone()));
+        diff
+    }
+
+    /// The graph changed outside the diff stream; nothing before now can be
+    /// replayed. Takes a revision of its own, so a client that had seen
+    /// everything up to the change still falls below the floor.
+    pub fn reset(&self) {
+        let mut inner = self.lock();
+        inner.revision += 1;
+        inner.floor = inner.revision;
+        inner.entries.clear();
+    }
+
+    /// Diffs after `revision`, or `None` if some of them are gone.
+    pub fn since(&self, revision: u64) -> Option<Vec<serde_json::Value>> {
+        let inner = self.lock();
+        if revision < inner.floor || revision > inner.revision {
+            return None;
+        }
+        let start = inner.entries.partition_point(|(r, _)| *r <= revision);
+        Some(
+            inner
+                .entries
+                .iter()
+                .skip(start)
+                .map(|(_, d)| d.clone())
+                .collect(),
+        )
+    }
+
+    /// Remember a dropped session until the window passes.
+    pub fn park(&self, token: &str, revision: u64, now: Instant) {
+        let window = self.window;
+        let mut inner = self.lock();
+        inner
+            .parked
+            .retain(|_, p| now.saturating_duration_since(p.since) <= window);
+        inner.parked.insert(
+            token.to_string(),
+            Parked {
+                revision,
+                since: now,
+            },
+        );
+    }
+
+    /// Take up a parked session. The client's own last-seen revision is
+    /// preferred to the one recorded at disconnect, since diffs sent just
+    /// before the drop may never have arrived.
+    pub fn resume(
+        &self,
+        token: &str,
+        client_revision: Option<u64>,
+        now: Instant,
+    ) -> Result<Replay, ResumeRejected> {
+        let parked = self
+            .lock()
+            .parked
+            .remove(token)
+            .ok_or(ResumeRejected::UnknownToken)?;
+        if now.saturating_duration_since(parked.since) > self.window {
+            return Err(ResumeRejected::Expired);
+        }
+        let from_revision = client_revision.unwrap_or(parked.revision);
+        let diffs = self.since(from_revision).ok_or(ResumeRejected::Gap)?;
+        Ok(Replay {
+            from_revision,
+            to_revision: from_revision + diffs.len() as u64,
+            diffs,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn replays_missed_diffs_within_the_window() {
+        let journal = DiffJournal::new(3, Duration::from_secs(60));
+        let t0 = Instant::now();
+        let first = journal.record(json!({ "type": "graphDiff", "added": [1] }));
+        assert_eq!(first["revision"], 1);
+        journal.park("a", 1, t0);
+        journal.record(json!({ "type": "graphDiff", "added": [2] }));
+        journal.record(json!({ "type": "graphDiff", "removed": [1] }));
+
+        let replay = journal
+            .resume("a", None, t0 + Duration::from_secs(5))
+            .unwrap();
+        assert_eq!((replay.from_revision, replay.to_revision), (1, 3));
+        assert_eq!(replay.diffs[0]["added"], json!([2]));
+        assert_eq!(
+            journal.resume("a", None, t0).unwrap_err(),
+            ResumeRejected::UnknownToken
+        );
+
+        journal.park("b", 3, t0);
+        assert_eq!(
+            journal.resume("b", None, t0 + Duration::from_secs(61)),
+            Err(ResumeRejected::Expired)
+        );
+
+        // Revision 1 rolls out of a three-entry journal.
+        journal.record(json!({ "type": "graphDiff" }));
+        journal.record(json!({ "type": "graphDiff" }));
+        journal.park("c", 1, t0);
+        assert_eq!(journal.resume("c", None, t0), Err(ResumeRejected::Gap));
+        journal.park("d", 2, t0);
+        assert_eq!(journal.resume("d", None, t0).unwrap().diffs.len(), 3);
+
+        journal.reset();
+        assert_eq!(journal.revision(), 6);
+        journal.park("e", 4, t0);
+        assert_eq!(journal.resume("e", None, t0), Err(ResumeRejected::Gap));
+        journal.park("f", 5, t0);
+        assert_eq!(journal.resume("f", Some(5), t0), Err(ResumeRejected::Gap));
+        journal.park("g", 6, t0);
+        assert!(journal.resume("g", None, t0).unwrap().diffs.is_empty());
+        let next = journal.record(json!({ "type": "graphDiff" }));
+        assert_eq!(next["revision"], 7);
+        journal.park("h", 6, t0);
+        assert_eq!(journal.resume("h", None, t0).unwrap().diffs, vec![next]);
+    }
+}
@@ -0,0 +1,721 @@
+//! Digest Service — scheduled daily digest of what changed in the vault.
+//!
+//! Once a day (at `digest.hourUtc`) the service compares the current metadata
+//! store and cluster assignments against the snapshot taken on the previous
+//! run, renders a Logseq-flavoured markdown page listing created / edited /
+//! removed pages, link changes, and newly formed clusters, optionally asks the
+//! configured LLM for a short prose summary, and writes the page back into the
+//! vault under `digests/YYYY-MM-DD.md`. The page is then registered with the
+//! graph via `AddNodesFromMetadata` so it shows up as a node linked to the
+//! pages it mentions.
+//!
+//! The diffing and rendering are pure functions so they can be tested without
+//! actors or the filesystem.
+
+use actix::Addr;
+use chrono::{DateTime, NaiveDate, Utc};
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::actors::graph_service_supervisor::GraphServiceSupervisor;
+use crate::actors::messages::{AddNodesFromMetadata, GetGraphData, GetSettings};
+use crate::actors::optimized_settings_actor::OptimizedSettingsActor;
+use crate::config::{ApiCostSettings, DigestSettings};
+use crate::services::file_service::{FileService, markdown_dir};
+use crate::services::cost_service::costs;
+use crate::services::json_file::JsonFile;
+use crate::services::perplexity_service::PerplexityService;
+use crate::services::webhook_service::{self, GraphEvent};
+use crate::utils::binary_protocol::{NodeAnalytics, NODE_ID_MASK};
+use visionclaw_domain::models::metadata::{Metadata, MetadataStore};
+
+/// Clusters smaller than this are noise and never reported.
+const MIN_CLUSTER_SIZE: usize = 3;
+
+/// A cluster counts as "new" when its best Jaccard overlap with every cluster
+/// from the previous snapshot is below this threshold.
+const NEW_CLUSTER_JACCARD: f64 = 0.5;
+
+/// Poll interval while the digest is disabled, so enabling it via the
+/// settings API takes effect without a restart.
+const DISABLED_POLL: Duration = Duration::from_secs(3600);
+
+/// Page fingerprint stored between runs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PageFingerprint {
+    pub sha1: String,
+    pub links: BTreeSet<String>,
+}
+
+/// State persisted after each run; the next digest is the diff against it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DigestSnapshot {
+    pub taken_at: Option<DateTime<Utc>>,
+    pub pages: BTreeMap<String, PageFingerprint>,
+    /// Each cluster as a sorted list of page names.
+    pub clusters: Vec<Vec<String>>,
+}
+
+impl DigestSnapshot {
+    /// Build a snapshot from the metadata store and per-page cluster ids.
+    pub fn capture(
+        metadata: &MetadataStore,
+        cluster_of_page: &HashMap<String, u32>,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let pages = metadata
+            .iter()
+            .map(|(key, meta)| {
+                (
+                    page_name(key),
+                    PageFingerprint {
+                        sha1: meta.sha1.clone(),
+                        links: meta.topic_counts.keys().cloned().collect(),
+                    },
+                )
+            })
+            .collect();
+
+        let mut grouped: BTreeMap<u32, Vec<String>> = BTreeMap::new();
+        for (page, cluster_id) in cluster_of_page {
+            grouped.entry(*cluster_id).or_default().push(page_name(page));
+        }
+        let clusters = grouped
+            .into_values()
+            .filter(|members| members.len() >= MIN_CLUSTER_SIZE)
+            .map(|mut members| {
+                members.sort();
+                members
+            })
+            .collect();
+
+        Self {
+            taken_at: Some(now),
+            pages,
+            clusters,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkChange {
+    pub page: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DigestReport {
+    pub date: NaiveDate,
+    pub since: Option<DateTime<Utc>>,
+    pub created: Vec<String>,
+    pub edited: Vec<String>,
+    pub removed: Vec<String>,
+    pub link_changes: Vec<LinkChange>,
+    pub new_clusters: Vec<Vec<String>>,
+    pub summary: Option<String>,
+}
+
+impl DigestReport {
+    pub fn is_empty(&self) -> bool {
+        self.created.is_empty()
+            && self.edited.is_empty()
+            && self.removed.is_empty()
+            && self.link_changes.is_empty()
+            && self.new_clusters.is_empty()
+    }
+
+    /// Every page the digest links to, used as the digest node's topic counts.
+    pub fn mentioned_pages(&self) -> BTreeSet<String> {
+        let mut pages: BTreeSet<String> = BTreeSet::new();
+        pages.extend(self.created.iter().cloned());
+        pages.extend(self.edited.iter().cloned());
+        pages.extend(self.link_changes.iter().map(|c| c.page.clone()));
+        for cluster in &self.new_clusters {
+            pages.extend(cluster.iter().cloned());
+        }
+        pages
+    }
+}
+
+/// Diff two snapshots. Pages under the digest directory are ignored so the
+/// digest never reports on itself.
+pub fn diff_snapshots(
+    previous: &DigestSnapshot,
+    current: &DigestSnapshot,
+    date: NaiveDate,
+    digest_dir: &str,
+) -> DigestReport {
+    let digest_prefix = format!("{}/", digest_dir.trim_matches('/'));
+    let is_digest = |name: &String| name.starts_with(&digest_prefix);
+
+    let mut report = DigestReport {
+        date,
+        since: previous.taken_at,
+        ..Default::default()
+    };
+
+    // The first run only establishes the baseline; listing every page as
+    // "created" (and every cluster as "new") would just be noise.
+    if previous.taken_at.is_none() {
+        return report;
+    }
+
+    for (name, page) in current.pages.iter().filter(|(n, _)| !is_digest(n)) {
+        match previous.pages.get(name) {
+            None => report.created.push(name.clone()),
+            Some(prev) => {
+                if prev.sha1 != page.sha1 {
+                    report.edited.push(name.clone());
+                }
+                let added: Vec<String> = page.links.difference(&prev.links).cloned().collect();
+                let removed: Vec<String> = prev.links.difference(&page.links).cloned().collect();
+                if !added.is_empty() || !removed.is_empty() {
+                    report.link_changes.push(LinkChange {
+                        page: name.clone(),
+                        added,
+                        removed,
+                    });
+                }
+            }
+        }
+    }
+
+    report.removed = previous
+        .pages
+        .keys()
+        .filter(|name| !is_digest(name) && !current.pages.contains_key(*name))
+        .cloned()
+        .collect();
+
+    report.new_clusters = current
+        .clusters
+        .iter()
+        .filter(|cluster| {
+            previous
+                .clusters
+                .iter()
+                .all(|prev| jaccard(cluster, prev) < NEW_CLUSTER_JACCARD)
+        })
+        .cloned()
+        .collect();
+
+    report
+}
+
+fn jaccard(a: &[String], b: &[String]) -> f64 {
+    let a: BTreeSet<&String> = a.iter().collect();
+    let b: BTreeSet<&String> = b.iter().collect();
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f64 / union as f64
+}
+
+fn page_name(key: &str) -> String {
+    key.trim_end_matches(".md").to_string()
+}
+
+fn push_section(out: &mut String, title: &str, items: &[String], max_items: usize) {
+    if items.is_empty() {
+        return;
+    }
+    out.push_str(&format!("- ## {} ({})\n", title, items.len()));
+    for item in items.iter().take(max_items) {
+        out.push_str(&format!("  - {}\n", item));
+    }
+    if items.len() > max_items {
+        out.push_str(&format!("  - … and {} more\n", items.len() - max_items));
+    }
+}
+
+fn links(pages: &[String]) -> String {
+    pages
+        .iter()
+        .map(|p| format!("[[{}]]", p))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Render the digest as a Logseq page (page properties + bullet outline).
+pub fn render_markdown(report: &DigestReport, max_items: usize) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("title:: Daily Digest {}\n", report.date));
+    out.push_str("type:: digest\n");
+    if let Some(since) = report.since {
+        out.push_str(&format!("since:: {}\n", since.to_rfc3339()));
+    }
+    out.push('\n');
+
+    if let Some(summary) = report.summary.as_deref().filter(|s| !s.trim().is_empty()) {
+        out.push_str("- ## Summary\n");
+        for line in summary.lines().filter(|l| !l.trim().is_empty()) {
+            out.push_str(&format!("  - {}\n", line.trim().trim_start_matches("- ")));
+        }
+    }
+
+    if report.is_empty() {
+        out.push_str("- No changes since the previous digest.\n");
+        return out;
+    }
+
+    let as_links = |pages: &[String]| -> Vec<String> {
+        pages.iter().map(|p| format!("[[{}]]", p)).collect()
+    };
+    push_section(&mut out, "Pages created", &as_links(&report.created), max_items);
+    push_section(&mut out, "Pages edited", &as_links(&report.edited), max_items);
+    push_section(&mut out, "Pages removed", &report.removed, max_items);
+
+    let link_lines: Vec<String> = report
+        .link_changes
+        .iter()
+        .map(|change| {
+            let mut parts = Vec::new();
+            if !change.added.is_empty() {
+                parts.push(format!("added {}", links(&change.added)));
+            }
+            if !change.removed.is_empty() {
+                parts.push(format!("removed {}", links(&change.removed)));
+            }
+            format!("[[{}]] {}", change.page, parts.join("; "))
+        })
+        .collect();
+    push_section(&mut out, "Link changes", &link_lines, max_items);
+
+    let cluster_lines: Vec<String> = report
+        .new_clusters
+        .iter()
+        .map(|members| {
+            let shown: Vec<String> = members.iter().take(8).cloned().collect();
+            let more = members.len().saturating_sub(shown.len());
+            if more > 0 {
+                format!("{} pages: {} … +{}", members.len(), links(&shown), more)
+            } else {
+                format!("{} pages: {}", members.len(), links(&shown))
+            }
+        })
+        .collect();
+    push_section(&mut out, "New clusters", &cluster_lines, max_items);
+
+    out
+}
+
+/// Delay from `now` until the next `hour_utc:00`.
+pub fn next_run_delay(now: DateTime<Utc>, hour_utc: u32) -> Duration {
+    let hour = hour_utc.min(23);
+    let today = now
+        .date_naive()
+        .and_hms_opt(hour, 0, 0)
+        .map(|t| t.and_utc())
+        .unwrap_or(now);
+    let next = if today > now {
+        today
+    } else {
+        today + chrono::Duration::days(1)
+    };
+    (next - now).to_std().unwrap_or(Duration::from_secs(60))
+}
+
+#[derive(Debug)]
+pub enum DigestError {
+    Io(String),
+    Graph(String),
+    Config(String),
+}
+
+impl std::fmt::Display for DigestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DigestError::Io(msg) => write!(f, "Digest I/O error: {}", msg),
+            DigestError::Graph(msg) => write!(f, "Digest graph error: {}", msg),
+            DigestError::Config(msg) => write!(f, "Digest config error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for DigestError {}
+
+pub struct DigestService {
+    graph_service_addr: Addr<GraphServiceSupervisor>,
+    settings_addr: Addr<OptimizedSettingsActor>,
+    node_analytics: Arc<std::sync::RwLock<HashMap<u32, NodeAnalytics>>>,
+    perplexity: Option<Arc<PerplexityService>>,
+    snapshot: JsonFile,
+    /// Held for a whole run so two runs never read-modify-write the snapshot
+    /// (or the metadata store) at the same time.
+    running: tokio::sync::Mutex<()>,
+}
+
+/// Where the previous run's snapshot lives under `data_dir`.
+fn snapshot_file(data_dir: &Path) -> JsonFile {
+    JsonFile::new(
+        data_dir.join("digests").join("last_snapshot.json"),
+        "digest snapshot",
+    )
+}
+
+/// Run blocking file work on the blocking pool.
+async fn blocking<T, F>(work: F) -> Result<T, DigestError>
+where
+    F: FnOnce() -> Result<T, String> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| DigestError::Io(e.to_string()))?
+        .map_err(DigestError::Io)
+}
+
+impl DigestService {
+    pub fn new(
+        graph_service_addr: Addr<GraphServiceSupervisor>,
+        settings_addr: Addr<OptimizedSettingsActor>,
+        node_analytics: Arc<std::sync::RwLock<HashMap<u32, NodeAnalytics>>>,
+        perplexity: Option<Arc<PerplexityService>>,
+    ) -> Self {
+        let data_dir = crate::config::dev_config::storage().data_dir();
+        Self {
+            graph_service_addr,
+            settings_addr,
+            node_analytics,
+            perplexity,
+            snapshot: snapshot_file(&data_dir),
+            running: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    async fn current_settings(&self) -> DigestSettings {
+        match self.settings_addr.send(GetSettings).await {
+            Ok(Ok(settings)) => settings.digest.unwrap_or_default(),
+            Ok(Err(e)) => {
+                warn!("[DigestService] Failed to read settings: {}", e);
+                DigestSettings::default()
+            }
+            Err(e) => {
+                warn!("[DigestService] Settings actor unreachable: {}", e);
+                DigestSettings::default()
+            }
+        }
+    }
+
+    /// Run forever, generating one digest per day at the configured hour.
+    pub fn spawn(self: Arc<Self>) {
+        tokio::spawn(async move {
+            loop {
+                let settings = self.current_settings().await;
+                if !settings.enabled {
+                    tokio::time::sleep(DISABLED_POLL).await;
+                    continue;
+                }
+
+                let delay = next_run_delay(Utc::now(), settings.hour_utc);
+                debug!("[DigestService] Next digest in {}s", delay.as_secs());
+                tokio::time::sleep(delay).await;
+
+                // Re-read: the digest may have been disabled while we slept.
+                let settings = self.current_settings().await;
+                if !settings.enabled {
+                    continue;
+                }
+                match self.run_once(&settings).await {
+                    Ok(path) => info!("[DigestService] Wrote digest {}", path.display()),
+                    Err(e) => warn!("[DigestService] Digest generation failed: {}", e),
+                }
+            }
+        });
+    }
+
+    /// Generate, write and link one digest. Returns the written file path.
+    pub async fn run_once(&self, settings: &DigestSettings) -> Result<PathBuf, DigestError> {
+        let output_dir = settings.output_dir.trim_matches('/');
+        if output_dir.is_empty() || output_dir.split('/').any(|c| c == ".." || c.is_empty()) {
+            return Err(DigestError::Config(format!(
+                "invalid digest output directory '{}'",
+                settings.output_dir
+            )));
+        }
+
+        let _running = self.running.lock().await;
+        let now = Utc::now();
+        let metadata = blocking(FileService::load_or_create_metadata).await?;
+        let cluster_of_page = self.cluster_assignments().await?;
+        let current = DigestSnapshot::capture(&metadata, &cluster_of_page, now);
+        let snapshot = self.snapshot.clone();
+        let previous: DigestSnapshot = blocking(move || Ok(snapshot.load())).await?;
+
+        let mut report = diff_snapshots(&previous, &current, now.date_naive(), output_dir);
+        if settings.llm_summary && !report.is_empty() {
+            report.summary = self.summarise(&report, settings.max_items_per_section).await;
+        }
+
+        let markdown = render_markdown(&report, settings.max_items_per_section);
+        let dir = Path::new(markdown_dir()).join(output_dir);
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .map_err(|e| DigestError::Io(e.to_string()))?;
+        let file_path = dir.join(format!("{}.md", report.date));
+        tokio::fs::write(&file_path, &markdown)
+            .await
+            .map_err(|e| DigestError::Io(e.to_string()))?;
+
+        self.link_into_graph(metadata, &report, output_dir, &markdown, now)
+            .await?;
+        let snapshot = self.snapshot.clone();
+        if let Err(e) = blocking(move || snapshot.save(&current)).await {
+            warn!("[DigestService] Failed to persist snapshot: {}", e);
+        }
+
+        webhook_service::emit(GraphEvent::DigestPublished {
+            date: report.date.to_string(),
+            page: format!("{}/{}", output_dir, report.date),
+            created: report.created.len(),
+            edited: report.edited.len(),
+            removed: report.removed.len(),
+            summary: report.summary.clone(),
+        });
+
+        Ok(file_path)
+    }
+
+    /// Map page (metadata id) → cluster id using the latest analytics results.
+    async fn cluster_assignments(&self) -> Result<HashMap<String, u32>, DigestError> {
+        let graph = self
+            .graph_service_addr
+            .send(GetGraphData)
+            .await
+            .map_err(|e| DigestError::Graph(e.to_string()))?
+            .map_err(DigestError::Graph)?;
+
+        let analytics = match self.node_analytics.read() {
+            Ok(guard) => guard.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        };
+
+        // Analytics are keyed by masked node id; cluster_id 0 means unclustered
+        // (ADR-031 I-6).
+        Ok(graph
+            .nodes
+            .iter()
+            .filter_map(|node| {
+                analytics
+                    .get(&(node.id & NODE_ID_MASK))
+                    .filter(|a| a.cluster_id != 0)
+                    .map(|a| (node.metadata_id.clone(), a.cluster_id))
+            })
+            .collect())
+    }
+
+    async fn summarise(&self, report: &DigestReport, max_items: usize) -> Option<String> {
+        let perplexity = self.perplexity.as_ref()?;
+        let budget = match self.settings_addr.send(GetSettings).await {
+            Ok(Ok(settings)) => settings.api_costs.unwrap_or_default(),
+            _ => ApiCostSettings::default(),
+        };
+        if let Err(reason) = costs().enrichment_allowed(&budget) {
+            info!("[DigestService] LLM summary skipped: {}", reason);
+            return None;
+        }
+        let outline = render_markdown(report, max_items);
+        let messages = vec![
+            (
+                "system".to_string(),
+                "You summarise daily changes to a personal knowledge graph. Reply with at most \
+                 five short bullet points, no preamble."
+                    .to_string(),
+            ),
+            ("user".to_string(), outline),
+        ];
+        match perplexity.chat_completion(messages).await {
+            Ok(summary) => Some(summary),
+            Err(e) => {
+                warn!("[DigestService] LLM summary skipped: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Register the digest page in the metadata store and add it to the graph.
+    async fn link_into_graph(
+        &self,
+        mut metadata: MetadataStore,
+        report: &DigestReport,
+        output_dir: &str,
+        markdown: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(), DigestError> {
+        use sha1::{Digest, Sha1};
+
+        let key = format!("{}/{}.md", output_dir, report.date);
+        let mut hasher = Sha1::new();
+        hasher.update(markdown.as_bytes());
+
+        let entry = Metadata {
+            file_name: key.clone(),
+            file_size: markdown.len(),
+            sha1: format!("{:x}", hasher.finalize()),
+            last_modified: now,
+            last_content_change: Some(now),
+            topic_counts: report
+                .mentioned_pages()
+                .into_iter()
+                .map(|page| (page, 1))
+                .collect(),
+            ..Default::default()
+        };
+
+        metadata.insert(key.clone(), entry.clone());
+        blocking(move || FileService::save_metadata(&metadata).map_err(|e| e.to_string())).await?;
+
+        let mut addition = MetadataStore::new();
+        addition.insert(key, entry);
+        self.graph_service_addr
+            .send(AddNodesFromMetadata { metadata: addition })
+            .await
+            .map_err(|e| DigestError::Graph(e.to_string()))?
+            .map_err(DigestError::Graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(sha1: &str, links: &[&str]) -> PageFingerprint {
+        PageFingerprint {
+            sha1: sha1.to_string(),
+            links: links.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 16).unwrap()
+    }
+
+    #[test]
+    fn diff_reports_created_edited_removed_and_links() {
+        let mut previous = DigestSnapshot {
+            taken_at: Some(Utc::now()),
+            ..Default::default()
+        };
+        previous.pages.insert("A".into(), page("1", &["B"]));
+        previous.pages.insert("B".into(), page("2", &[]));
+        previous.pages.insert("Gone".into(), page("3", &[]));
+
+        let mut current = DigestSnapshot::default();
+        current.pages.insert("A".into(), page("1", &["C"]));
+        current.pages.insert("B".into(), page("2b", &[]));
+        current.pages.insert("C".into(), page("4", &[]));
+        current.pages.insert("digests/2026-10-15".into(), page("5", &[]));
+
+        let report = diff_snapshots(&previous, &current, date(), "digests");
+        assert_eq!(report.created, vec!["C".to_string()]);
+        assert_eq!(report.edited, vec!["B".to_string()]);
+        assert_eq!(report.removed, vec!["Gone".to_string()]);
+        assert_eq!(
+            report.link_changes,
+            vec![LinkChange {
+                page: "A".into(),
+                added: vec!["C".into()],
+                removed: vec!["B".into()],
+            }]
+        );
+    }
+
+    #[test]
+    fn new_clusters_require_low_overlap_with_previous() {
+        let names = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let previous = DigestSnapshot {
+            taken_at: Some(Utc::now()),
+            clusters: vec![names(&["a", "b", "c", "d"])],
+            ..Default::default()
+        };
+        let current = DigestSnapshot {
+            clusters: vec![names(&["a", "b", "c", "e"]), names(&["x", "y", "z"])],
+            ..Default::default()
+        };
+        let report = diff_snapshots(&previous, &current, date(), "digests");
+        assert_eq!(report.new_clusters, vec![names(&["x", "y", "z"])]);
+    }
+
+    #[test]
+    fn first_run_only_establishes_baseline() {
+        let mut current = DigestSnapshot {
+            clusters: vec![vec!["a".into(), "b".into(), "c".into()]],
+            ..Default::default()
+        };
+        current.pages.insert("A".into(), page("1", &[]));
+        let report = diff_snapshots(&DigestSnapshot::default(), &current, date(), "digests");
+        assert!(report.is_empty());
+    }
+
+    #[test]
+    fn render_links_pages_and_truncates_sections() {
+        let report = DigestReport {
+            date: date(),
+            created: vec!["One".into(), "Two".into(), "Three".into()],
+            ..Default::default()
+        };
+        let md = render_markdown(&report, 2);
+        assert!(md.starts_with("title:: Daily Digest 2026-10-16\n"));
+        assert!(md.contains("- ## Pages created (3)"));
+        assert!(md.contains("  - [[One]]"));
+        assert!(!md.contains("[[Three]]"));
+        assert!(md.contains("… and 1 more"));
+    }
+
+    #[test]
+    fn render_empty_report() {
+        let report = DigestReport {
+            date: date(),
+            ..Default::default()
+        };
+        assert!(render_markdown(&report, 10).contains("No changes since the previous digest."));
+    }
+
+    #[test]
+    fn next_run_delay_rolls_to_tomorrow() {
+        let now = DateTime::parse_from_rfc3339("2026-10-16T07:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(next_run_delay(now, 8), Duration::from_secs(30 * 60));
+        assert_eq!(next_run_delay(now, 6), Duration::from_secs(22 * 3600 + 30 * 60));
+    }
+
+    #[test]
+    fn snapshot_round_trips_through_its_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = snapshot_file(dir.path());
+        let mut snapshot = DigestSnapshot {
+            clusters: vec![vec!["a".into(), "b".into(), "c".into()]],
+            ..Default::default()
+        };
+        snapshot.pages.insert("A".into(), page("1", &["B"]));
+        file.save(&snapshot).unwrap();
+
+        let loaded: DigestSnapshot = file.load();
+        assert_eq!(loaded.pages, snapshot.pages);
+        assert_eq!(loaded.clusters, snapshot.clusters);
+        assert!(dir.path().join("digests/last_snapshot.json").exists());
+    }
+
+    #[test]
+    fn corrupt_snapshot_loads_as_a_fresh_baseline() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = snapshot_file(dir.path());
+        std::fs::create_dir_all(dir.path().join("digests")).unwrap();
+        std::fs::write(file.path(), "{not json").unwrap();
+
+        let loaded: DigestSnapshot = file.load();
+        assert!(loaded.pages.is_empty());
+        assert!(loaded.taken_at.is_none());
+    }
+}
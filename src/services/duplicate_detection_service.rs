@@ -0,0 +1,590 @@
+//! Duplicate page detection and merge.
+//!
+//! Three signals, cheapest first:
+//! 1. identical content — SHA1 over whitespace-normalised markdown,
+//! 2. similar titles — case / separator-insensitive page names,
+//! 3. similar content — cosine over the stored page embeddings (see
+//!    `embedding_sync_service`) or an `EmbeddingProvider` when supplied,
+//!    otherwise word-set Jaccard (same fallback as pathfinding).
+//!
+//! Merging folds the duplicate's outbound links into the kept page, moves its
+//! metadata (topic counts, inbound references) onto the kept page, and turns
+//! the duplicate into a redirect stub so existing `[[links]]` keep resolving.
+
+use chrono::{DateTime, Utc};
+use log::info;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::services::file_service::{FileService, markdown_dir};
+use crate::services::pathfinding::EmbeddingProvider;
+use visionclaw_domain::models::metadata::{Metadata, MetadataStore};
+
+/// Default similarity above which two pages are flagged as near-duplicates.
+pub const DEFAULT_CONTENT_THRESHOLD: f32 = 0.85;
+
+/// Pages whose lengths differ by more than this ratio are never compared —
+/// they cannot reach the threshold and skipping them keeps the pass cheap.
+const MIN_LENGTH_RATIO: f32 = 0.6;
+
+/// Pages with fewer tokens than this are too short for content similarity
+/// to mean anything (stubs, empty journals).
+const MIN_TOKENS: usize = 20;
+
+#[derive(Debug, Clone)]
+pub struct PageText {
+    pub name: String,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateReason {
+    IdenticalContent,
+    SimilarTitle,
+    SimilarContent,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateCandidate {
+    pub pages: Vec<String>,
+    pub reason: DuplicateReason,
+    pub score: f32,
+}
+
+pub struct DuplicateDetector {
+    content_threshold: f32,
+    embedder: Option<Arc<dyn EmbeddingProvider>>,
+    /// Precomputed vectors keyed by page file name (`name.md`).
+    page_vectors: Option<Arc<HashMap<String, Vec<f32>>>>,
+}
+
+impl Default for DuplicateDetector {
+    fn default() -> Self {
+        Self::new(DEFAULT_CONTENT_THRESHOLD, None)
+    }
+}
+
+impl DuplicateDetector {
+    pub fn new(content_threshold: f32, embedder: Option<Arc<dyn EmbeddingProvider>>) -> Self {
+        Self {
+            content_threshold: content_threshold.clamp(0.0, 1.0),
+            embedder,
+            page_vectors: None,
+        }
+    }
+
+    /// Score content by these vectors instead of asking the embedder. Pages
+    /// without one fall back to Jaccard.
+    pub fn with_page_vectors(mut self, vectors: Arc<HashMap<String, Vec<f32>>>) -> Self {
+        self.page_vectors = Some(vectors);
+        self
+    }
+
+    /// Run all passes. Identical-content groups are reported once and their
+    /// members are excluded from the pairwise passes.
+    pub fn find(&self, pages: &[PageText]) -> Vec<DuplicateCandidate> {
+        let mut candidates = Vec::new();
+        let mut reported: HashSet<(usize, usize)> = HashSet::new();
+
+        let mut by_hash: HashMap<String, Vec<usize>> = HashMap::new();
+        for (i, page) in pages.iter().enumerate() {
+            let normalised = normalise_content(&page.content);
+            if normalised.is_empty() {
+                continue;
+            }
+            by_hash.entry(sha1_hex(&normalised)).or_default().push(i);
+        }
+        for group in by_hash.values().filter(|g| g.len() > 1) {
+            for (a, &i) in group.iter().enumerate() {
+                for &j in &group[a + 1..] {
+                    reported.insert((i.min(j), i.max(j)));
+                }
+            }
+            let mut names: Vec<String> = group.iter().map(|&i| pages[i].name.clone()).collect();
+            names.sort();
+            candidates.push(DuplicateCandidate {
+                pages: names,
+                reason: DuplicateReason::IdenticalContent,
+                score: 1.0,
+            });
+        }
+
+        let mut by_title: HashMap<String, Vec<usize>> = HashMap::new();
+        for (i, page) in pages.iter().enumerate() {
+            by_title.entry(normalise_title(&page.name)).or_default().push(i);
+        }
+        for group in by_title.values().filter(|g| g.len() > 1) {
+            for (a, &i) in group.iter().enumerate() {
+                for &j in &group[a + 1..] {
+                    if reported.insert((i.min(j), i.max(j))) {
+                        candidates.push(pair(pages, i, j, DuplicateReason::SimilarTitle, 1.0));
+                    }
+                }
+            }
+        }
+
+        let embeddings: Option<Vec<Option<Vec<f32>>>> = match (&self.page_vectors, &self.embedder) {
+            (Some(vectors), _) => Some(
+                pages
+                    .iter()
+                    .map(|p| vectors.get(&format!("{}.md", p.name)).cloned())
+                    .collect(),
+            ),
+            (None, Some(e)) => Some(
+                pages
+                    .iter()
+                    .map(|p| e.embed_text(&p.content).ok())
+                    .collect(),
+            ),
+            (None, None) => None,
+        };
+        let tokens: Vec<HashSet<String>> = pages.iter().map(|p| tokenise(&p.content)).collect();
+
+        for i in 0..pages.len() {
+            if tokens[i].len() < MIN_TOKENS {
+                continue;
+            }
+            for j in (i + 1)..pages.len() {
+                if tokens[j].len() < MIN_TOKENS || reported.contains(&(i, j)) {
+                    continue;
+                }
+                let (small, large) = if tokens[i].len() < tokens[j].len() {
+                    (tokens[i].len(), tokens[j].len())
+                } else {
+                    (tokens[j].len(), tokens[i].len())
+                };
+                if (small as f32) / (large as f32) < MIN_LENGTH_RATIO {
+                    continue;
+                }
+
+                let score = match embeddings.as_ref().map(|v| (&v[i], &v[j])) {
+                    Some((Some(a), Some(b))) => cosine(a, b),
+                    _ => jaccard(&tokens[i], &tokens[j]),
+                };
+                if score >= self.content_threshold {
+                    reported.insert((i, j));
+                    candidates.push(pair(pages, i, j, DuplicateReason::SimilarContent, score));
+                }
+            }
+        }
+
+        candidates.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.pages.cmp(&b.pages))
+        });
+        candidates
+    }
+}
+
+fn pair(pages: &[PageText], i: usize, j: usize, reason: DuplicateReason, score: f32) -> DuplicateCandidate {
+    let mut names = vec![pages[i].name.clone(), pages[j].name.clone()];
+    names.sort();
+    DuplicateCandidate {
+        pages: names,
+        reason,
+        score,
+    }
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let denom = norm(a) * norm(b);
+    if denom < 1e-9 {
+        0.0
+    } else {
+        dot / denom
+    }
+}
+
+fn sha1_hex(content: &str) -> String {
+    use sha1::{Digest, Sha1};
+    let mut hasher = Sha1::new();
+    hasher.update(content.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Collapse whitespace and drop Logseq page-property lines (`key:: value`),
+/// which differ between otherwise identical copies (ids, timestamps).
+pub fn normalise_content(content: &str) -> String {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !is_property_line(line))
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn is_property_line(line: &str) -> bool {
+    match line.find(":: ") {
+        Some(idx) => !line[..idx].contains(' ') && !line.starts_with('-'),
+        None => line.ends_with("::") && !line.contains(' '),
+    }
+}
+
+/// `Machine-Learning`, `machine_learning` and `Machine Learning` collapse to
+/// the same key.
+pub fn normalise_title(name: &str) -> String {
+    name.trim_end_matches(".md")
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect()
+}
+
+fn tokenise(content: &str) -> HashSet<String> {
+    normalise_content(content)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.len() > 2)
+        .map(|w| w.to_lowercase())
+        .collect()
+}
+
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f32 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f32 / union as f32
+}
+
+/// All `[[wiki links]]` in a page, without alias suffixes.
+pub fn extract_wikilinks(content: &str) -> BTreeSet<String> {
+    lazy_static::lazy_static! {
+        static ref WIKILINK: Regex = Regex::new(r"\[\[([^\]|]+)(?:\|[^\]]*)?\]\]").expect("valid wikilink regex");
+    }
+    WIKILINK
+        .captures_iter(content)
+        .map(|c| c[1].trim().to_string())
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergedContent {
+    pub kept: String,
+    pub stub: String,
+    pub added_links: Vec<String>,
+}
+
+/// Pure merge: append the duplicate's links that the kept page lacks and
+/// render the redirect stub that replaces the duplicate.
+pub fn merge_content(
+    keep: &str,
+    keep_content: &str,
+    duplicate: &str,
+    duplicate_content: &str,
+    now: DateTime<Utc>,
+) -> MergedContent {
+    let existing = extract_wikilinks(keep_content);
+    let added_links: Vec<String> = extract_wikilinks(duplicate_content)
+        .into_iter()
+        .filter(|l| !existing.contains(l) && l != keep && l != duplicate)
+        .collect();
+
+    let mut kept = keep_content.trim_end().to_string();
+    if !added_links.is_empty() {
+        kept.push_str(&format!("\n- Merged from [[{}]]\n", duplicate));
+        for link in &added_links {
+            kept.push_str(&format!("  - [[{}]]\n", link));
+        }
+    } else {
+        kept.push('\n');
+    }
+
+    let stub = format!(
+        "redirect:: [[{keep}]]\n\n- This page was merged into [[{keep}]] on {date}.\n",
+        keep = keep,
+        date = now.format("%Y-%m-%d")
+    );
+
+    MergedContent {
+        kept,
+        stub,
+        added_links,
+    }
+}
+
+/// Rewrite the metadata store for a merge: the kept page absorbs the
+/// duplicate's topic counts, every page that referenced the duplicate now
+/// references the kept page, and the duplicate becomes a one-link stub.
+pub fn merge_metadata(
+    store: &mut MetadataStore,
+    keep_key: &str,
+    duplicate_key: &str,
+    merged: &MergedContent,
+    now: DateTime<Utc>,
+) {
+    let keep = keep_key.trim_end_matches(".md").to_string();
+    let duplicate = duplicate_key.trim_end_matches(".md").to_string();
+
+    let duplicate_counts = store
+        .get(duplicate_key)
+        .map(|m| m.topic_counts.clone())
+        .unwrap_or_default();
+
+    for (key, meta) in store.iter_mut() {
+        if key == duplicate_key {
+            continue;
+        }
+        if let Some(count) = meta.topic_counts.remove(&duplicate) {
+            if key != keep_key {
+                *meta.topic_counts.entry(keep.clone()).or_insert(0) += count;
+            }
+        }
+    }
+
+    if let Some(meta) = store.get_mut(keep_key) {
+        for (topic, count) in duplicate_counts {
+            if topic != keep && topic != duplicate {
+                *meta.topic_counts.entry(topic).or_insert(0) += count;
+            }
+        }
+        meta.sha1 = sha1_hex(&merged.kept);
+        meta.file_size = merged.kept.len();
+        meta.hyperlink_count += merged.added_links.len();
+        meta.last_modified = now;
+        meta.last_content_change = Some(now);
+        meta.change_count = Some(meta.change_count.unwrap_or(0) + 1);
+    }
+
+    let stub = Metadata {
+        file_name: duplicate_key.to_string(),
+        file_size: merged.stub.len(),
+        sha1: sha1_hex(&merged.stub),
+        node_id: store
+            .get(duplicate_key)
+            .map(|m| m.node_id.clone())
+            .unwrap_or_else(|| "0".to_string()),
+        // Still the remote version the stub replaces; write-back diffs against it.
+        file_blob_sha: store
+            .get(duplicate_key)
+            .and_then(|m| m.file_blob_sha.clone()),
+        last_modified: now,
+        last_content_change: Some(now),
+        topic_counts: HashMap::from([(keep, 1)]),
+        ..Default::default()
+    };
+    store.insert(duplicate_key.to_string(), stub);
+}
+
+/// Load every page listed in the metadata store from the markdown directory.
+pub fn load_pages() -> Result<Vec<PageText>, String> {
+    let metadata = FileService::load_or_create_metadata()?;
+    let mut pages = Vec::with_capacity(metadata.len());
+    for key in metadata.keys() {
+        let path = Path::new(markdown_dir()).join(key);
+        if let Ok(content) = std::fs::read_to_string(&path) {
+            pages.push(PageText {
+                name: key.trim_end_matches(".md").to_string(),
+                content,
+            });
+        }
+    }
+    Ok(pages)
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeReport {
+    pub kept: String,
+    pub redirected: String,
+    pub added_links: Vec<String>,
+    #[serde(skip)]
+    pub kept_metadata: Option<Metadata>,
+    #[serde(skip)]
+    pub stub_metadata: Option<Metadata>,
+}
+
+fn page_key(name: &str) -> Result<String, String> {
+    let name = name.trim().trim_end_matches(".md");
+    if name.is_empty() || name.contains('/') || name.contains('\\') || name.contains("..") {
+        return Err(format!("invalid page name '{}'", name));
+    }
+    Ok(format!("{}.md", name))
+}
+
+/// Merge `duplicate` into `keep` on disk and in the metadata store.
+pub fn merge_pages(keep: &str, duplicate: &str) -> Result<MergeReport, String> {
+    let keep_key = page_key(keep)?;
+    let duplicate_key = page_key(duplicate)?;
+    if keep_key == duplicate_key {
+        return Err("cannot merge a page into itself".to_string());
+    }
+
+    let mut store = FileService::load_or_create_metadata()?;
+    for key in [&keep_key, &duplicate_key] {
+        if !store.contains_key(key) {
+            return Err(format!("page '{}' not found", key.trim_end_matches(".md")));
+        }
+    }
+
+    let keep_path = Path::new(markdown_dir()).join(&keep_key);
+    let duplicate_path = Path::new(markdown_dir()).join(&duplicate_key);
+    let keep_content = std::fs::read_to_string(&keep_path).map_err(|e| e.to_string())?;
+    let duplicate_content = std::fs::read_to_string(&duplicate_path).map_err(|e| e.to_string())?;
+
+    let now = Utc::now();
+    let keep_name = keep_key.trim_end_matches(".md");
+    let duplicate_name = duplicate_key.trim_end_matches(".md");
+    let merged = merge_content(keep_name, &keep_content, duplicate_name, &duplicate_content, now);
+
+    std::fs::write(&keep_path, &merged.kept).map_err(|e| e.to_string())?;
+    std::fs::write(&duplicate_path, &merged.stub).map_err(|e| e.to_string())?;
+
+    merge_metadata(&mut store, &keep_key, &duplicate_key, &merged, now);
+    FileService::save_metadata(&store).map_err(|e| e.to_string())?;
+
+    info!(
+        "[Duplicates] Merged '{}' into '{}' ({} links carried over)",
+        duplicate_name,
+        keep_name,
+        merged.added_links.len()
+    );
+
+    Ok(MergeReport {
+        kept: keep_name.to_string(),
+        redirected: duplicate_name.to_string(),
+        added_links: merged.added_links,
+        kept_metadata: store.get(&keep_key).cloned(),
+        stub_metadata: store.get(&duplicate_key).cloned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(name: &str, content: &str) -> PageText {
+        PageText {
+            name: name.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    fn body(extra: &str) -> String {
+        format!(
+            "- graph neural networks learn representations over nodes and edges using \
+             message passing between neighbours aggregated across several layers with \
+             attention weights and residual connections for deep stacks {}",
+            extra
+        )
+    }
+
+    #[test]
+    fn identical_content_ignores_properties_and_whitespace() {
+        let pages = vec![
+            page("A", "id:: 123\n- hello   world\n"),
+            page("B", "id:: 456\n\n- hello world"),
+            page("C", "- something else"),
+        ];
+        let found = DuplicateDetector::default().find(&pages);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].reason, DuplicateReason::IdenticalContent);
+        assert_eq!(found[0].pages, vec!["A".to_string(), "B".to_string()]);
+    }
+
+    #[test]
+    fn similar_titles_are_flagged() {
+        let pages = vec![
+            page("Machine Learning", "- one"),
+            page("machine-learning", "- two"),
+        ];
+        let found = DuplicateDetector::default().find(&pages);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].reason, DuplicateReason::SimilarTitle);
+    }
+
+    #[test]
+    fn similar_content_uses_jaccard_fallback() {
+        let pages = vec![page("GNN", &body("today")), page("Graph Nets", &body("tomorrow"))];
+        let found = DuplicateDetector::default().find(&pages);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].reason, DuplicateReason::SimilarContent);
+        assert!(found[0].score >= DEFAULT_CONTENT_THRESHOLD);
+    }
+
+    #[test]
+    fn stored_vectors_take_precedence_over_jaccard() {
+        let pages = vec![
+            page("GNN", &body("today")),
+            page("Graph Nets", &body("tomorrow")),
+            page("Message Passing", &body("yesterday")),
+        ];
+        let vectors: HashMap<String, Vec<f32>> = [
+            ("GNN.md".to_string(), vec![1.0, 0.0]),
+            ("Graph Nets.md".to_string(), vec![0.0, 1.0]),
+        ]
+        .into();
+        let found = DuplicateDetector::default()
+            .with_page_vectors(Arc::new(vectors))
+            .find(&pages);
+        // The embedded pair is judged by its vectors; pairs with an
+        // unembedded page still fall back to Jaccard.
+        assert_eq!(found.len(), 2);
+        assert!(found
+            .iter()
+            .all(|c| c.pages.contains(&"Message Passing".to_string())));
+    }
+
+    #[test]
+    fn short_pages_are_not_compared() {
+        let pages = vec![page("A", "- tiny note one"), page("B", "- tiny note two")];
+        assert!(DuplicateDetector::default().find(&pages).is_empty());
+    }
+
+    #[test]
+    fn merge_carries_links_and_writes_stub() {
+        let now = Utc::now();
+        let merged = merge_content(
+            "Keep",
+            "- see [[X]]\n",
+            "Dup",
+            "- see [[X]] and [[Y]]\n- back to [[Keep]]\n",
+            now,
+        );
+        assert_eq!(merged.added_links, vec!["Y".to_string()]);
+        assert!(merged.kept.contains("- Merged from [[Dup]]\n  - [[Y]]"));
+        assert!(merged.stub.starts_with("redirect:: [[Keep]]"));
+    }
+
+    #[test]
+    fn merge_metadata_retargets_inbound_references() {
+        let now = Utc::now();
+        let mut store = MetadataStore::new();
+        let mut keep = Metadata::default();
+        keep.topic_counts.insert("X".into(), 1);
+        let mut dup = Metadata::default();
+        dup.topic_counts.insert("Y".into(), 2);
+        let mut other = Metadata::default();
+        other.topic_counts.insert("Dup".into(), 3);
+        store.insert("Keep.md".into(), keep);
+        store.insert("Dup.md".into(), dup);
+        store.insert("Other.md".into(), other);
+
+        let merged = merge_content("Keep", "- [[X]]", "Dup", "- [[Y]]", now);
+        merge_metadata(&mut store, "Keep.md", "Dup.md", &merged, now);
+
+        assert_eq!(store["Keep.md"].topic_counts.get("Y"), Some(&2));
+        assert_eq!(store["Other.md"].topic_counts.get("Keep"), Some(&3));
+        assert!(!store["Other.md"].topic_counts.contains_key("Dup"));
+        assert_eq!(store["Dup.md"].topic_counts.get("Keep"), Some(&1));
+    }
+
+    #[test]
+    fn page_key_rejects_traversal() {
+        assert!(page_key("../etc/passwd").is_err());
+        assert!(page_key("a/b").is_err());
+        assert_eq!(page_key("Page").unwrap(), "Page.md");
+    }
+}
@@ -0,0 +1,992 @@
+//! Incremental page embeddings.
+//!
+//! After a sync reloads the graph, each page's text is hashed and only the
+//! pages whose hash changed (or that are new) are sent to OpenAI's
+//! `/embeddings` endpoint (model `openai.embeddingModel`, default
+//! [`DEFAULT_MODEL`]); vectors for deleted pages are dropped. Vectors are
+//! kept in `embeddings.json` under the data dir and indexed in a
+//! [`HnswIndex`], which is updated in place. The update waits for the
+//! reload to finish (see [`spawn_reload_and_update`]); the one at server
+//! start only runs with `openai.embedOnStartup` set.
+//!
+//! Each page links to its [`SIMILAR_NEIGHBOURS`] nearest pages scoring at
+//! least [`MIN_SIMILARITY`] with `semantic` edges (ids `semantic-{a}-{b}`).
+//! Only the links of changed pages, and of the pages they were linked to,
+//! are recomputed. The index and links are keyed by page, since reloads
+//! renumber nodes; node ids are looked up only when edges are written.
+//!
+//! Requests go out in batches of at most [`MAX_BATCH_INPUTS`] pages and
+//! [`MAX_BATCH_TOKENS`] estimated tokens, paced to `openai.rateLimit`
+//! requests per minute (default [`DEFAULT_REQUESTS_PER_MINUTE`]) and
+//! [`TOKENS_PER_MINUTE`]. Every run produces an [`EmbeddingSyncReport`] with
+//! the tokens used and their estimated cost, served by
+//! `GET /api/admin/embeddings`. Updates stop while an `apiCosts` budget cap
+//! is reached (see `services::cost_service`).
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use actix::Addr;
+use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use crate::actors::graph_service_supervisor::GraphServiceSupervisor;
+use crate::actors::messages::{
+    AddEdge, GetGraphData, GetSettings, ReloadGraphFromDatabase, RemoveEdge,
+};
+use crate::actors::OptimizedSettingsActor;
+use crate::config::dev_config;
+use crate::services::json_file::JsonFile;
+use crate::config::{ApiCostSettings, OpenAISettings};
+use crate::services::cost_service::{costs, price, ApiProvider, ApiUsage};
+use crate::services::file_service::markdown_dir;
+use crate::services::hnsw_index::HnswIndex;
+use visionclaw_domain::models::edge::{Edge, EdgeProvenance, ProvenanceSource};
+use visionclaw_domain::models::graph::GraphData;
+
+/// Model used when `openai.embeddingModel` is not set.
+pub const DEFAULT_MODEL: &str = "text-embedding-3-small";
+
+/// Pages per embeddings request.
+pub const MAX_BATCH_INPUTS: usize = 64;
+
+/// Estimated tokens per embeddings request.
+pub const MAX_BATCH_TOKENS: usize = 100_000;
+
+/// Characters of a page that are embedded (about 6k tokens).
+pub const MAX_INPUT_CHARS: usize = 24_000;
+
+/// Request budget when `openai.rateLimit` is not set.
+pub const DEFAULT_REQUESTS_PER_MINUTE: u32 = 60;
+
+/// Token budget per minute.
+pub const TOKENS_PER_MINUTE: usize = 1_000_000;
+
+/// Similar pages each page is linked to.
+pub const SIMILAR_NEIGHBOURS: usize = 5;
+
+/// Lowest cosine similarity that earns a link.
+pub const MIN_SIMILARITY: f32 = 0.75;
+
+/// Id prefix of the edges this service owns.
+pub const EDGE_PREFIX: &str = "semantic-";
+
+const OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+const MAX_ATTEMPTS: usize = 3;
+const SEARCH_EF: usize = 64;
+
+/// Rough token count used for batching and cost estimates.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+pub fn estimate_cost_usd(tokens: usize) -> f64 {
+    tokens as f64 * price(ApiProvider::OpenaiEmbeddings).per_million_input_tokens / 1_000_000.0
+}
+
+fn content_hash(text: &str) -> String {
+    hex::encode(Sha256::digest(text.as_bytes()))
+}
+
+mod vector_base64 {
+    use base64::Engine as _;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(vector: &[f32], serializer: S) -> Result<S::Ok, S::Error> {
+        let bytes: Vec<u8> = vector.iter().flat_map(|x| x.to_le_bytes()).collect();
+        serializer.serialize_str(&base64::engine::general_purpose::STANDARD.encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<f32>, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(serde::de::Error::custom)?;
+        Ok(bytes
+            .chunks_exact(4)
+            .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageEmbedding {
+    pub hash: String,
+    #[serde(with = "vector_base64")]
+    pub vector: Vec<f32>,
+}
+
+/// Embeddings on disk, keyed by page.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EmbeddingStore {
+    pub model: String,
+    pub pages: BTreeMap<String, PageEmbedding>,
+}
+
+/// A page's text as it would be embedded.
+#[derive(Debug, Clone)]
+pub struct PageText {
+    pub page: String,
+    pub text: String,
+    pub hash: String,
+}
+
+impl PageText {
+    pub fn new(page: impl Into<String>, text: &str) -> Self {
+        let text: String = text.chars().take(MAX_INPUT_CHARS).collect();
+        Self {
+            page: page.into(),
+            hash: content_hash(&text),
+            text,
+        }
+    }
+}
+
+/// Index ids for pages that last for the life of the process.
+///
+/// Node ids are renumbered on every graph reload, so the index and the
+/// similarity links are keyed by page and only translated to node ids when
+/// edges are written (see [`reconcile_edges`]).
+#[derive(Debug, Default)]
+pub struct PageSlots {
+    ids: HashMap<String, u32>,
+    pages: HashMap<u32, String>,
+    next: u32,
+}
+
+impl PageSlots {
+    /// The page's id, allocating one the first time it is seen.
+    pub fn id(&mut self, page: &str) -> u32 {
+        if let Some(&id) = self.ids.get(page) {
+            return id;
+        }
+        let id = self.next;
+        self.next += 1;
+        self.ids.insert(page.to_string(), id);
+        self.pages.insert(id, page.to_string());
+        id
+    }
+
+    pub fn page(&self, id: u32) -> Option<&str> {
+        self.pages.get(&id).map(String::as_str)
+    }
+
+    pub fn remove(&mut self, page: &str) -> Option<u32> {
+        let id = self.ids.remove(page)?;
+        self.pages.remove(&id);
+        Some(id)
+    }
+}
+
+/// The file a page node is stored in, relative to the markdown directory.
+fn page_file(metadata_id: &str) -> Option<String> {
+    if metadata_id.is_empty() || metadata_id.contains("..") || metadata_id.starts_with('/') {
+        return None;
+    }
+    Some(if metadata_id.ends_with(".md") {
+        metadata_id.to_string()
+    } else {
+        format!("{}.md", metadata_id)
+    })
+}
+
+/// What a sync has to do.
+#[derive(Debug, Default)]
+pub struct EmbeddingPlan {
+    /// New pages and pages whose text changed.
+    pub embed: Vec<PageText>,
+    /// Pages that no longer exist.
+    pub remove: Vec<String>,
+    pub unchanged: usize,
+}
+
+pub fn plan(store: &EmbeddingStore, pages: Vec<PageText>) -> EmbeddingPlan {
+    let mut plan = EmbeddingPlan::default();
+    let current: HashSet<String> = pages.iter().map(|p| p.page.clone()).collect();
+    for page in pages {
+        match store.pages.get(&page.page) {
+            Some(stored) if stored.hash == page.hash => plan.unchanged += 1,
+            _ => plan.embed.push(page),
+        }
+    }
+    plan.remove = store
+        .pages
+        .keys()
+        .filter(|p| !current.contains(*p))
+        .cloned()
+        .collect();
+    plan
+}
+
+/// Split pages into request-sized batches, in order.
+pub fn batches(pages: &[PageText], max_inputs: usize, max_tokens: usize) -> Vec<Vec<usize>> {
+    let mut batches: Vec<Vec<usize>> = Vec::new();
+    let mut tokens = 0;
+    for (i, page) in pages.iter().enumerate() {
+        let cost = estimate_tokens(&page.text);
+        let full = batches
+            .last()
+            .is_none_or(|b| b.len() >= max_inputs || tokens + cost > max_tokens);
+        if full {
+            batches.push(Vec::new());
+            tokens = 0;
+        }
+        batches.last_mut().unwrap().push(i);
+        tokens += cost;
+    }
+    batches
+}
+
+/// Sliding one-minute budget of requests and tokens.
+#[derive(Debug)]
+pub struct RateLimiter {
+    requests_per_minute: usize,
+    tokens_per_minute: usize,
+    sent: VecDeque<(Instant, usize)>,
+}
+
+impl RateLimiter {
+    pub fn new(requests_per_minute: u32, tokens_per_minute: usize) -> Self {
+        Self {
+            requests_per_minute: requests_per_minute.max(1) as usize,
+            tokens_per_minute: tokens_per_minute.max(1),
+            sent: VecDeque::new(),
+        }
+    }
+
+    pub fn set_requests_per_minute(&mut self, requests_per_minute: u32) {
+        self.requests_per_minute = requests_per_minute.max(1) as usize;
+    }
+
+    /// How long to wait before sending a request of `tokens` at `now`.
+    pub fn delay(&mut self, tokens: usize, now: Instant) -> Duration {
+        let window = Duration::from_secs(60);
+        while self
+            .sent
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) >= window)
+        {
+            self.sent.pop_front();
+        }
+        let mut used: usize = self.sent.iter().map(|(_, t)| t).sum();
+        let mut requests = self.sent.len();
+        // Drop the oldest sends until this one fits; it can go when they age out.
+        let mut until = None;
+        for (at, t) in &self.sent {
+            if requests < self.requests_per_minute
+                && (used + tokens <= self.tokens_per_minute || used == 0)
+            {
+                break;
+            }
+            requests -= 1;
+            used -= t;
+            until = Some(*at + window);
+        }
+        until.map_or(Duration::ZERO, |at| at.saturating_duration_since(now))
+    }
+
+    pub fn record(&mut self, tokens: usize, at: Instant) {
+        self.sent.push_back((at, tokens));
+    }
+}
+
+/// Undirected similarity links between [`PageSlots`] ids, keyed
+/// `(smaller id, larger id)`.
+pub type SimilarityPairs = BTreeMap<(u32, u32), f32>;
+
+fn pair(a: u32, b: u32) -> (u32, u32) {
+    (a.min(b), a.max(b))
+}
+
+/// Recompute the links of `changed` nodes, and of nodes linked to them or to
+/// `removed` ones. Returns how many nodes were re-linked.
+pub fn refresh_pairs(
+    index: &HnswIndex,
+    pairs: &mut SimilarityPairs,
+    changed: &HashSet<u32>,
+    removed: &HashSet<u32>,
+) -> usize {
+    let mut affected: HashSet<u32> = changed.clone();
+    for &(a, b) in pairs.keys() {
+        if changed.contains(&a) || removed.contains(&a) {
+            affected.insert(b);
+        }
+        if changed.contains(&b) || removed.contains(&b) {
+            affected.insert(a);
+        }
+    }
+    affected.retain(|id| !removed.contains(id) && index.contains(*id));
+    pairs.retain(|(a, b), _| {
+        !affected.contains(a)
+            && !affected.contains(b)
+            && !removed.contains(a)
+            && !removed.contains(b)
+    });
+    for &id in &affected {
+        let Some(vector) = index.vector(id) else {
+            continue;
+        };
+        for (other, similarity) in index.search(vector, SIMILAR_NEIGHBOURS + 1, SEARCH_EF) {
+            if other != id && similarity >= MIN_SIMILARITY {
+                pairs.insert(pair(id, other), similarity);
+            }
+        }
+    }
+    affected.len()
+}
+
+fn similarity_edge(a: u32, b: u32, similarity: f32) -> Edge {
+    let mut edge = Edge::new(a, b, similarity)
+        .with_edge_type("semantic".to_string())
+        .with_provenance(EdgeProvenance::from_source(
+            ProvenanceSource::Semantic,
+            similarity,
+        ));
+    edge.id = format!("{}{}-{}", EDGE_PREFIX, a, b);
+    edge
+}
+
+/// Edges to add and edge ids to remove so the graph's semantic edges match
+/// `pairs`, translating pages to the graph's current node ids.
+pub fn reconcile_edges(
+    graph: &GraphData,
+    pairs: &SimilarityPairs,
+    slots: &PageSlots,
+) -> (Vec<Edge>, Vec<String>) {
+    let nodes: HashMap<String, u32> = graph
+        .nodes
+        .iter()
+        .filter_map(|n| Some((page_file(&n.metadata_id)?, n.id)))
+        .collect();
+    let existing: HashSet<&str> = graph
+        .edges
+        .iter()
+        .filter(|e| e.id.starts_with(EDGE_PREFIX))
+        .map(|e| e.id.as_str())
+        .collect();
+    let mut wanted = HashSet::new();
+    let mut add = Vec::new();
+    for (&(a, b), &similarity) in pairs {
+        let node = |slot| slots.page(slot).and_then(|page| nodes.get(page)).copied();
+        let (Some(a), Some(b)) = (node(a), node(b)) else {
+            continue;
+        };
+        let (a, b) = pair(a, b);
+        let edge = similarity_edge(a, b, similarity);
+        if !existing.contains(edge.id.as_str()) {
+            add.push(edge.clone());
+        }
+        wanted.insert(edge.id);
+    }
+    let remove = existing
+        .into_iter()
+        .filter(|id| !wanted.contains(*id))
+        .map(str::to_string)
+        .collect();
+    (add, remove)
+}
+
+/// Outcome of one embedding sync.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddingSyncReport {
+    /// Milliseconds since the Unix epoch.
+    pub started_at: i64,
+    pub duration_ms: u64,
+    pub model: String,
+    pub pages: usize,
+    pub embedded: usize,
+    pub unchanged: usize,
+    pub removed: usize,
+    /// Changed pages left for the next sync after a failed request.
+    pub pending: usize,
+    pub requests: usize,
+    pub estimated_tokens: usize,
+    /// Tokens OpenAI reported using.
+    pub billed_tokens: usize,
+    pub estimated_cost_usd: f64,
+    pub relinked_pages: usize,
+    pub edges_added: usize,
+    pub edges_removed: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+struct EmbeddingClient {
+    client: Client,
+    url: String,
+    api_key: String,
+    model: String,
+}
+
+impl EmbeddingClient {
+    fn new(settings: &OpenAISettings, model: &str) -> Option<Self> {
+        let api_key = settings.api_key.clone().filter(|k| !k.is_empty())?;
+        let base = settings.base_url.as_deref().unwrap_or(OPENAI_BASE_URL);
+        let client = Client::builder()
+            .timeout(Duration::from_secs(settings.timeout.unwrap_or(60)))
+            .build()
+            .unwrap_or_default();
+        Some(Self {
+            client,
+            url: format!("{}/embeddings", base.trim_end_matches('/')),
+            api_key,
+            model: model.to_string(),
+        })
+    }
+
+    /// Vectors for `inputs`, in order, and the tokens billed.
+    async fn embed(&self, inputs: &[&str]) -> Result<(Vec<Vec<f32>>, usize), String> {
+        #[derive(Deserialize)]
+        struct Item {
+            index: usize,
+            embedding: Vec<f32>,
+        }
+        #[derive(Deserialize)]
+        struct Usage {
+            total_tokens: usize,
+        }
+        #[derive(Deserialize)]
+        struct Response {
+            data: Vec<Item>,
+            usage: Option<Usage>,
+        }
+
+        let body = json!({"model": self.model, "input": inputs});
+        for attempt in 1..=MAX_ATTEMPTS {
+            let response = self
+                .client
+                .post(&self.url)
+                .bearer_auth(&self.api_key)
+                .json(&body)
+                .send()
+                .await
+                .map_err(|e| format!("Embeddings request failed: {}", e))?;
+            let status = response.status();
+            if status == StatusCode::TOO_MANY_REQUESTS && attempt < MAX_ATTEMPTS {
+                let wait = response
+                    .headers()
+                    .get("retry-after")
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.parse::<u64>().ok())
+                    .unwrap_or(20);
+                warn!("[Embeddings] Rate limited; retrying in {}s", wait);
+                tokio::time::sleep(Duration::from_secs(wait)).await;
+                continue;
+            }
+            if !status.is_success() {
+                let text = response.text().await.unwrap_or_default();
+                return Err(format!("Embeddings endpoint returned {}: {}", status, text));
+            }
+            let mut parsed: Response = response
+                .json()
+                .await
+                .map_err(|e| format!("Unreadable embeddings response: {}", e))?;
+            if parsed.data.len() != inputs.len() {
+                return Err(format!(
+                    "Asked for {} embeddings, got {}",
+                    inputs.len(),
+                    parsed.data.len()
+                ));
+            }
+            parsed.data.sort_by_key(|item| item.index);
+            let tokens = parsed.usage.map_or(0, |u| u.total_tokens);
+            return Ok((
+                parsed.data.into_iter().map(|i| i.embedding).collect(),
+                tokens,
+            ));
+        }
+        Err("Embeddings endpoint kept rate limiting".to_string())
+    }
+}
+
+/// Read the text of every page node in `graph` from `base`.
+fn collect_pages(graph: &GraphData, base: &Path) -> Vec<PageText> {
+    let Ok(base) = base.canonicalize() else {
+        return Vec::new();
+    };
+    graph
+        .nodes
+        .iter()
+        .filter_map(|node| {
+            let file = page_file(&node.metadata_id)?;
+            let path = base.join(&file).canonicalize().ok()?;
+            if !path.starts_with(&base) {
+                return None;
+            }
+            let content = std::fs::read_to_string(path).ok()?;
+            Some(PageText::new(
+                file,
+                &format!("{}\n\n{}", node.label, content),
+            ))
+        })
+        .collect()
+}
+
+struct IndexState {
+    store: EmbeddingStore,
+    slots: PageSlots,
+    index: Option<HnswIndex>,
+    pairs: Option<SimilarityPairs>,
+    limiter: RateLimiter,
+}
+
+pub struct EmbeddingSyncService {
+    file: JsonFile,
+    state: tokio::sync::Mutex<IndexState>,
+    last_report: RwLock<Option<EmbeddingSyncReport>>,
+    /// Copy of the stored vectors for readers that must not wait on a run.
+    vectors: RwLock<Arc<HashMap<String, Vec<f32>>>>,
+    /// Pages with a stored embedding, kept current batch by batch so status
+    /// reads never wait on a run.
+    pages: AtomicUsize,
+    rerun: AtomicBool,
+}
+
+fn vector_snapshot(store: &EmbeddingStore) -> Arc<HashMap<String, Vec<f32>>> {
+    Arc::new(
+        store
+            .pages
+            .iter()
+            .map(|(page, entry)| (page.clone(), entry.vector.clone()))
+            .collect(),
+    )
+}
+
+fn model_name(openai: &OpenAISettings) -> String {
+    openai
+        .embedding_model
+        .clone()
+        .filter(|m| !m.is_empty())
+        .unwrap_or_else(|| DEFAULT_MODEL.to_string())
+}
+
+impl EmbeddingSyncService {
+    pub fn load(path: PathBuf) -> Self {
+        let file = JsonFile::new(path, "embeddings");
+        let store: EmbeddingStore = file.load();
+        info!("Loaded embeddings for {} page(s)", store.pages.len());
+        let vectors = RwLock::new(vector_snapshot(&store));
+        let pages = AtomicUsize::new(store.pages.len());
+        Self {
+            file,
+            state: tokio::sync::Mutex::new(IndexState {
+                store,
+                slots: PageSlots::default(),
+                index: None,
+                pairs: None,
+                limiter: RateLimiter::new(DEFAULT_REQUESTS_PER_MINUTE, TOKENS_PER_MINUTE),
+            }),
+            last_report: RwLock::new(None),
+            vectors,
+            pages,
+            rerun: AtomicBool::new(false),
+        }
+    }
+
+    pub fn last_report(&self) -> Option<EmbeddingSyncReport> {
+        self.last_report
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Stored vectors keyed by page file name, as of the last run.
+    pub fn page_vectors(&self) -> Arc<HashMap<String, Vec<f32>>> {
+        self.vectors
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.state.try_lock().is_err()
+    }
+
+    /// Pages with a stored embedding, including those a running update has
+    /// embedded so far.
+    pub fn page_count(&self) -> usize {
+        self.pages.load(Ordering::Relaxed)
+    }
+
+    async fn save(&self, store: &EmbeddingStore) -> Result<(), String> {
+        let file = self.file.clone();
+        let store = store.clone();
+        tokio::task::spawn_blocking(move || file.save(&store))
+            .await
+            .map_err(|e| format!("Saving embeddings failed: {}", e))?
+    }
+
+    /// Bring embeddings and semantic edges up to date with the graph.
+    pub async fn update(
+        &self,
+        graph_service: &Addr<GraphServiceSupervisor>,
+        openai: &OpenAISettings,
+        budget: &ApiCostSettings,
+    ) -> Result<EmbeddingSyncReport, String> {
+        let model = model_name(openai);
+        let client = EmbeddingClient::new(openai, &model)
+            .ok_or("openai.apiKey is not set; embeddings are disabled")?;
+        let started = Instant::now();
+        let mut report = EmbeddingSyncReport {
+            started_at: chrono::Utc::now().timestamp_millis(),
+            model: model.clone(),
+            ..Default::default()
+        };
+
+        let graph = graph_service
+            .send(GetGraphData)
+            .await
+            .map_err(|e| format!("Graph service unavailable: {}", e))??;
+        let pages = {
+            let graph = graph.clone();
+            tokio::task::spawn_blocking(move || collect_pages(&graph, Path::new(markdown_dir())))
+                .await
+                .map_err(|e| format!("Reading pages failed: {}", e))?
+        };
+        report.pages = pages.len();
+
+        let mut state = self.state.lock().await;
+        let state = &mut *state;
+        state
+            .limiter
+            .set_requests_per_minute(openai.rate_limit.unwrap_or(DEFAULT_REQUESTS_PER_MINUTE));
+        if state.store.model != model {
+            if !state.store.pages.is_empty() {
+                info!(
+                    "[Embeddings] Model changed from '{}' to '{}'; re-embedding every page",
+                    state.store.model, model
+                );
+            }
+            state.store = EmbeddingStore {
+                model: model.clone(),
+                pages: BTreeMap::new(),
+            };
+            state.slots = PageSlots::default();
+            state.index = None;
+            state.pairs = None;
+            self.pages.store(0, Ordering::Relaxed);
+        }
+        let store = &mut state.store;
+        let slots = &mut state.slots;
+        let index = state.index.get_or_insert_with(|| {
+            let mut index = HnswIndex::default();
+            for (page, entry) in &store.pages {
+                index.insert(slots.id(page), &entry.vector);
+            }
+            index
+        });
+
+        let plan = plan(store, pages);
+        report.unchanged = plan.unchanged;
+        let mut changed: HashSet<u32> = HashSet::new();
+        let mut removed: HashSet<u32> = HashSet::new();
+
+        for page in &plan.remove {
+            store.pages.remove(page);
+            if let Some(id) = slots.remove(page) {
+                index.remove(id);
+                removed.insert(id);
+            }
+        }
+        report.removed = plan.remove.len();
+        self.pages.store(store.pages.len(), Ordering::Relaxed);
+
+        for batch in batches(&plan.embed, MAX_BATCH_INPUTS, MAX_BATCH_TOKENS) {
+            let tokens: usize = batch
+                .iter()
+                .map(|&i| estimate_tokens(&plan.embed[i].text))
+                .sum();
+            if let Err(reason) = costs().enrichment_allowed(budget) {
+                info!("[Embeddings] Pausing: {}", reason);
+                report.error = Some(reason);
+                break;
+            }
+            let wait = state.limiter.delay(tokens, Instant::now());
+            if !wait.is_zero() {
+                debug!("[Embeddings] Waiting {:?} for the rate limit", wait);
+                tokio::time::sleep(wait).await;
+            }
+            state.limiter.record(tokens, Instant::now());
+            report.requests += 1;
+            let inputs: Vec<&str> = batch.iter().map(|&i| plan.embed[i].text.as_str()).collect();
+            let (vectors, billed) = match client.embed(&inputs).await {
+                Ok(result) => result,
+                Err(e) => {
+                    warn!("[Embeddings] {}", e);
+                    report.error = Some(e);
+                    break;
+                }
+            };
+            report.estimated_tokens += tokens;
+            report.billed_tokens += billed;
+            costs().record(
+                ApiProvider::OpenaiEmbeddings,
+                ApiUsage::tokens(if billed > 0 { billed } else { tokens } as u64, 0),
+            );
+            for (&i, vector) in batch.iter().zip(vectors) {
+                let page = &plan.embed[i];
+                let id = slots.id(&page.page);
+                index.insert(id, &vector);
+                changed.insert(id);
+                store.pages.insert(
+                    page.page.clone(),
+                    PageEmbedding {
+                        hash: page.hash.clone(),
+                        vector,
+                    },
+                );
+                report.embedded += 1;
+            }
+            self.pages.store(store.pages.len(), Ordering::Relaxed);
+        }
+        report.pending = plan.embed.len() - report.embedded;
+        let cost_tokens = if report.billed_tokens > 0 {
+            report.billed_tokens
+        } else {
+            report.estimated_tokens
+        };
+        report.estimated_cost_usd = estimate_cost_usd(cost_tokens);
+
+        if report.embedded + report.removed > 0 {
+            if let Err(e) = self.save(store).await {
+                warn!("[Embeddings] {}", e);
+            }
+            *self.vectors.write().unwrap_or_else(|e| e.into_inner()) = vector_snapshot(store);
+        }
+
+        // A fresh process has the vectors but not the links: derive them all.
+        let fresh = state.pairs.is_none();
+        let pairs = state.pairs.get_or_insert_with(SimilarityPairs::new);
+        report.relinked_pages = if fresh {
+            let all: HashSet<u32> = store.pages.keys().map(|page| slots.id(page)).collect();
+            refresh_pairs(index, pairs, &all, &HashSet::new())
+        } else {
+            refresh_pairs(index, pairs, &changed, &removed)
+        };
+
+        // The reload that preceded this drops edges that are not stored, so
+        // reconcile against the live graph rather than the last run.
+        let graph = graph_service
+            .send(GetGraphData)
+            .await
+            .map_err(|e| format!("Graph service unavailable: {}", e))??;
+        let (add, remove) = reconcile_edges(&graph, pairs, slots);
+        report.edges_added = add.len();
+        report.edges_removed = remove.len();
+        for edge_id in remove {
+            graph_service.do_send(RemoveEdge { edge_id });
+        }
+        for edge in add {
+            graph_service.do_send(AddEdge { edge });
+        }
+
+        report.duration_ms = started.elapsed().as_millis() as u64;
+        info!(
+            "[Embeddings] {} embedded, {} unchanged, {} removed in {} request(s); ~{} tokens (${:.4}); edges +{} -{}",
+            report.embedded,
+            report.unchanged,
+            report.removed,
+            report.requests,
+            cost_tokens,
+            report.estimated_cost_usd,
+            report.edges_added,
+            report.edges_removed
+        );
+        *self.last_report.write().unwrap_or_else(|e| e.into_inner()) = Some(report.clone());
+        Ok(report)
+    }
+}
+
+static SERVICE: Lazy<EmbeddingSyncService> =
+    Lazy::new(|| EmbeddingSyncService::load(dev_config::storage().path("embeddings.json")));
+
+pub fn embeddings() -> &'static EmbeddingSyncService {
+    &SERVICE
+}
+
+/// What asked for an embedding update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateTrigger {
+    /// Server start; runs only with `openai.embedOnStartup`.
+    Startup,
+    /// A sync, upload or admin request.
+    Requested,
+}
+
+/// Update embeddings in the background. A request made while an update is
+/// running queues one more run.
+pub fn spawn_update(
+    graph_service: Addr<GraphServiceSupervisor>,
+    settings: Addr<OptimizedSettingsActor>,
+    trigger: UpdateTrigger,
+) {
+    let service = embeddings();
+    if service.is_running() {
+        service.rerun.store(true, Ordering::SeqCst);
+        return;
+    }
+    actix_web::rt::spawn(async move {
+        loop {
+            let (openai, budget) = match settings.send(GetSettings).await {
+                Ok(Ok(settings)) => (
+                    settings.openai.unwrap_or_default(),
+                    settings.api_costs.unwrap_or_default(),
+                ),
+                _ => {
+                    warn!("[Embeddings] Settings unavailable; skipping update");
+                    return;
+                }
+            };
+            if openai.api_key.as_deref().is_none_or(str::is_empty) {
+                debug!("[Embeddings] No OpenAI API key; skipping update");
+                return;
+            }
+            if trigger == UpdateTrigger::Startup && openai.embed_on_startup != Some(true) {
+                info!("[Embeddings] openai.embedOnStartup is off; not embedding at startup");
+                return;
+            }
+            if let Err(reason) = costs().enrichment_allowed(&budget) {
+                info!("[Embeddings] Skipping update: {}", reason);
+                return;
+            }
+            if let Err(e) = service.update(&graph_service, &openai, &budget).await {
+                warn!("[Embeddings] Update failed: {}", e);
+            }
+            if !service.rerun.swap(false, Ordering::SeqCst) {
+                return;
+            }
+        }
+    });
+}
+
+/// Reload the graph from the database and, once the reload has finished,
+/// update embeddings against it. Updating earlier would read node ids from
+/// before the reload's renumbering, and the reload would drop the edges it
+/// added.
+pub fn spawn_reload_and_update(
+    graph_service: Addr<GraphServiceSupervisor>,
+    settings: Addr<OptimizedSettingsActor>,
+    trigger: UpdateTrigger,
+) {
+    actix_web::rt::spawn(async move {
+        match graph_service.send(ReloadGraphFromDatabase).await {
+            Ok(Ok(())) => spawn_update(graph_service, settings, trigger),
+            Ok(Err(e)) => warn!("[Embeddings] Graph reload failed; skipping update: {}", e),
+            Err(e) => warn!("[Embeddings] Graph service unavailable; skipping update: {}", e),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use visionclaw_domain::models::node::Node;
+
+    #[test]
+    fn plans_batches_paces_and_links_incrementally() {
+        let mut store = EmbeddingStore::default();
+        for (page, text) in [("a.md", "alpha"), ("b.md", "beta"), ("c.md", "gamma")] {
+            store.pages.insert(
+                page.to_string(),
+                PageEmbedding {
+                    hash: content_hash(text),
+                    vector: vec![1.0, 0.0],
+                },
+            );
+        }
+        let plan = plan(
+            &store,
+            vec![
+                PageText::new("a.md", "alpha"),
+                PageText::new("b.md", "beta"),
+                PageText::new("c.md", "gamma, edited"),
+                PageText::new("d.md", "delta"),
+            ],
+        );
+        assert_eq!(plan.unchanged, 2);
+        assert_eq!(
+            plan.embed
+                .iter()
+                .map(|p| p.page.as_str())
+                .collect::<Vec<_>>(),
+            vec!["c.md", "d.md"]
+        );
+        assert!(plan.remove.is_empty());
+        let json = serde_json::to_string(&store).unwrap();
+        let back: EmbeddingStore = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.pages["a.md"].vector, vec![1.0, 0.0]);
+
+        let pages: Vec<PageText> = [40, 40, 400, 40]
+            .iter()
+            .enumerate()
+            .map(|(i, &len)| PageText::new(format!("{}.md", i), &"x".repeat(len)))
+            .collect();
+        assert_eq!(batches(&pages, 2, 105), vec![vec![0, 1], vec![2], vec![3]]);
+        assert!((estimate_cost_usd(1_000_000) - 0.02).abs() < 1e-12);
+
+        let start = Instant::now();
+        let mut limiter = RateLimiter::new(2, 100);
+        assert_eq!(limiter.delay(60, start), Duration::ZERO);
+        limiter.record(60, start);
+        let later = start + Duration::from_secs(10);
+        assert_eq!(limiter.delay(60, later), Duration::from_secs(50));
+        assert_eq!(limiter.delay(30, later), Duration::ZERO);
+        limiter.record(30, later);
+        assert_eq!(
+            limiter.delay(1, later + Duration::from_secs(20)),
+            Duration::from_secs(30)
+        );
+        assert_eq!(
+            limiter.delay(1, start + Duration::from_secs(61)),
+            Duration::ZERO
+        );
+
+        let mut index = HnswIndex::default();
+        index.insert(1, &[1.0, 0.0]);
+        index.insert(2, &[0.9, 0.1]);
+        index.insert(3, &[0.0, 1.0]);
+        let mut pairs = SimilarityPairs::new();
+        refresh_pairs(&index, &mut pairs, &[1, 2, 3].into(), &HashSet::new());
+        assert_eq!(pairs.keys().copied().collect::<Vec<_>>(), vec![(1, 2)]);
+
+        index.insert(3, &[1.0, 0.05]);
+        let relinked = refresh_pairs(&index, &mut pairs, &[3].into(), &HashSet::new());
+        assert_eq!(relinked, 1);
+        assert_eq!(
+            pairs.keys().copied().collect::<Vec<_>>(),
+            vec![(1, 2), (1, 3), (2, 3)]
+        );
+        index.remove(1);
+        refresh_pairs(&index, &mut pairs, &HashSet::new(), &[1].into());
+        assert_eq!(pairs.keys().copied().collect::<Vec<_>>(), vec![(2, 3)]);
+
+        // Slots 1..=3 are pages one.md..three.md; a reload has given them
+        // node ids 30, 20 and 10, and one.md is gone.
+        let mut slots = PageSlots::default();
+        for page in ["zero.md", "one.md", "two.md", "three.md"] {
+            slots.id(page);
+        }
+        let mut graph = GraphData::new();
+        for (page, id) in [("two", 20), ("three.md", 10)] {
+            graph.nodes.push(Node::new_with_id(page.to_string(), Some(id)));
+        }
+        graph.edges.push(similarity_edge(1, 2, 0.9));
+        let (add, remove) = reconcile_edges(&graph, &pairs, &slots);
+        assert_eq!(
+            add.iter().map(|e| e.id.as_str()).collect::<Vec<_>>(),
+            vec!["semantic-10-20"]
+        );
+        assert_eq!(remove, vec!["semantic-1-2".to_string()]);
+        assert_eq!(slots.remove("two.md"), Some(2));
+        assert_eq!(slots.id("two.md"), 4);
+    }
+}
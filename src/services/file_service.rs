@@ -1,14 +1,18 @@
 use super::github::{ContentAPI, GitHubClient, GitHubConfig};
+use crate::config::dev_config;
 use crate::config::AppFullSettings;
 use visionclaw_domain::models::graph::GraphData;
 use visionclaw_domain::models::node::Node as AppNode; // Use an alias to avoid confusion
-use visionclaw_domain::models::edge::Edge as AppEdge;
+use visionclaw_domain::models::edge::{Edge as AppEdge, EdgeProvenance, ProvenanceSource};
 use visionclaw_domain::models::metadata::{Metadata, MetadataOps, MetadataStore};
 use crate::ports::knowledge_graph_repository::KnowledgeGraphRepository;
+use crate::services::parsers::content_parser::file_stem;
+use crate::services::parsers::content_parsers;
 use crate::time;
 use actix_web::web;
 use chrono::Utc;
 use log::{debug, error, info, warn};
+use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -24,12 +28,44 @@ use tokio::sync::RwLock;
 use tokio::time::sleep;
 use rand::Rng;
 
-// Constants
-const METADATA_PATH: &str = "/workspace/ext/data/metadata/metadata.json";
-const BASE_PATH_MARKER: &str = "/workspace/ext/data/metadata/base_path.txt";
-pub const MARKDOWN_DIR: &str = "/workspace/ext/data/markdown";
+// Vault paths, resolved once from `storage` in dev_config
+static METADATA_PATH: Lazy<String> =
+    Lazy::new(|| vault_path(dev_config::storage().metadata_dir().join("metadata.json")));
+static BASE_PATH_MARKER: Lazy<String> =
+    Lazy::new(|| vault_path(dev_config::storage().metadata_dir().join("base_path.txt")));
+static MARKDOWN_DIR: Lazy<String> =
+    Lazy::new(|| vault_path(dev_config::storage().markdown_dir()));
+
+fn vault_path(path: std::path::PathBuf) -> String {
+    path.to_string_lossy().into_owned()
+}
+
+/// Path of the vault's `metadata.json`.
+pub fn metadata_path() -> &'static str {
+    &METADATA_PATH
+}
+
+fn base_path_marker() -> &'static str {
+    &BASE_PATH_MARKER
+}
+
+/// Directory of the vault's markdown files.
+pub fn markdown_dir() -> &'static str {
+    &MARKDOWN_DIR
+}
 const GITHUB_API_DELAY: Duration = Duration::from_millis(500);
 
+/// What [`FileService::load_graph_from_files`] found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphSeedOutcome {
+    /// The store already held a graph; nothing was written.
+    AlreadyPopulated { nodes: usize },
+    /// The store was seeded from local files.
+    Seeded { nodes: usize },
+    /// `metadata.json` is missing, empty or does not parse yet.
+    NoMetadata,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ProcessedFile {
     pub file_name: String,
@@ -111,7 +147,7 @@ impl FileService {
 
         
         let temp_filename = format!("temp_{}.md", time::timestamp_seconds());
-        let temp_path = format!("{}/{}", MARKDOWN_DIR, temp_filename);
+        let temp_path = format!("{}/{}", markdown_dir(), temp_filename);
         if let Err(e) = fs::write(&temp_path, &content) {
             return Err(Error::new(std::io::ErrorKind::Other, e.to_string()));
         }
@@ -119,7 +155,7 @@ impl FileService {
         
         let valid_nodes: Vec<String> = metadata
             .keys()
-            .map(|name| name.trim_end_matches(".md").to_string())
+            .map(|name| file_stem(name).to_string())
             .collect();
 
         let references = Self::extract_references(&content, &valid_nodes);
@@ -158,7 +194,7 @@ impl FileService {
 
     
     pub async fn load_file(&self, filename: &str) -> Result<GraphData, Error> {
-        let file_path = format!("{}/{}", MARKDOWN_DIR, filename);
+        let file_path = format!("{}/{}", markdown_dir(), filename);
         if !Path::new(&file_path).exists() {
             return Err(Error::new(
                 std::io::ErrorKind::NotFound,
@@ -175,7 +211,7 @@ impl FileService {
         
         let valid_nodes: Vec<String> = metadata
             .keys()
-            .map(|name| name.trim_end_matches(".md").to_string())
+            .map(|name| file_stem(name).to_string())
             .collect();
 
         let references = Self::extract_references(&content, &valid_nodes);
@@ -202,11 +238,11 @@ impl FileService {
     
     pub fn load_or_create_metadata() -> Result<MetadataStore, String> {
         // Use the correct metadata path constant
-        let metadata_dir = Path::new(METADATA_PATH).parent().unwrap_or(Path::new("/workspace/ext/data/metadata"));
+        let metadata_dir = Path::new(metadata_path()).parent().unwrap_or(Path::new("."));
         std::fs::create_dir_all(metadata_dir)
             .map_err(|e| format!("Failed to create metadata directory: {}", e))?;
 
-        let metadata_path = METADATA_PATH;
+        let metadata_path = metadata_path();
 
         match File::open(metadata_path) {
             Ok(file) => {
@@ -239,7 +275,7 @@ impl FileService {
     
     pub fn load_graph_data() -> Result<Option<GraphData>, String> {
         // Use metadata directory path for graph.json
-        let metadata_dir = Path::new(METADATA_PATH).parent().unwrap_or(Path::new("/workspace/ext/data/metadata"));
+        let metadata_dir = Path::new(metadata_path()).parent().unwrap_or(Path::new("."));
         let graph_path = metadata_dir.join("graph.json");
 
         match File::open(&graph_path) {
@@ -344,7 +380,7 @@ impl FileService {
         Self::ensure_directories()?;
 
         
-        let basic_github_files = content_api.list_markdown_files("").await?;
+        let basic_github_files = content_api.list_markdown_files_in_sources().await?;
         info!(
             "Found {} markdown files in GitHub",
             basic_github_files.len()
@@ -384,7 +420,7 @@ impl FileService {
                     {
                         Ok(content) => {
                             // Check for public-access:: true (new) or public:: true (legacy)
-                            let is_public = Self::is_public_file(&content);
+                            let is_public = Self::is_public_source(&file_basic_meta.name, &content);
 
                             if !is_public {
                                 debug!(
@@ -394,7 +430,7 @@ impl FileService {
                                 return Ok(None);
                             }
 
-                            let file_path = format!("{}/{}", MARKDOWN_DIR, file_extended_meta.name);
+                            let file_path = format!("{}/{}", markdown_dir(), file_extended_meta.name);
                             if let Err(e) = fs::write(&file_path, &content) {
                                 error!("Failed to write file {}: {}", file_path, e);
                                 return Err(e.into());
@@ -466,11 +502,11 @@ impl FileService {
     fn update_topic_counts(metadata_store: &mut MetadataStore) -> Result<(), Error> {
         let valid_nodes: Vec<String> = metadata_store
             .keys()
-            .map(|name| name.trim_end_matches(".md").to_string())
+            .map(|name| file_stem(name).to_string())
             .collect();
 
         for file_name in metadata_store.keys().cloned().collect::<Vec<_>>() {
-            let file_path = format!("{}/{}", MARKDOWN_DIR, file_name);
+            let file_path = format!("{}/{}", markdown_dir(), file_name);
             if let Ok(content) = fs::read_to_string(&file_path) {
                 let references = Self::extract_references(&content, &valid_nodes);
                 let topic_counts = Self::convert_references_to_topic_counts(references);
@@ -486,13 +522,13 @@ impl FileService {
 
     
     fn has_valid_local_setup() -> bool {
-        if let Ok(metadata_content) = fs::read_to_string(METADATA_PATH) {
+        if let Ok(metadata_content) = fs::read_to_string(metadata_path()) {
             if metadata_content.trim().is_empty() {
                 return false;
             }
 
             if let Ok(metadata) = serde_json::from_str::<MetadataStore>(&metadata_content) {
-                return metadata.validate_files(MARKDOWN_DIR);
+                return metadata.validate_files(markdown_dir());
             }
         }
         false
@@ -500,7 +536,7 @@ impl FileService {
 
     /// Check if GITHUB_BASE_PATH changed since last successful sync
     fn base_path_changed(current_base_path: &str) -> bool {
-        match fs::read_to_string(BASE_PATH_MARKER) {
+        match fs::read_to_string(base_path_marker()) {
             Ok(stored) => stored.trim() != current_base_path.trim(),
             Err(_) => false, // No marker = first run, not a "change"
         }
@@ -508,10 +544,10 @@ impl FileService {
 
     /// Record the current base path for future change detection
     fn save_base_path_marker(base_path: &str) {
-        if let Some(parent) = Path::new(BASE_PATH_MARKER).parent() {
+        if let Some(parent) = Path::new(base_path_marker()).parent() {
             let _ = fs::create_dir_all(parent);
         }
-        if let Err(e) = fs::write(BASE_PATH_MARKER, base_path) {
+        if let Err(e) = fs::write(base_path_marker(), base_path) {
             warn!("Failed to write base path marker: {}", e);
         }
     }
@@ -519,17 +555,17 @@ impl FileService {
     /// Clear local markdown files and metadata for a fresh ingest
     fn clear_local_cache() {
         // Remove metadata.json
-        if let Err(e) = fs::remove_file(METADATA_PATH) {
+        if let Err(e) = fs::remove_file(metadata_path()) {
             if e.kind() != std::io::ErrorKind::NotFound {
-                warn!("Failed to remove {}: {}", METADATA_PATH, e);
+                warn!("Failed to remove {}: {}", metadata_path(), e);
             }
         }
 
-        // Remove all .md files from the markdown directory
-        if let Ok(entries) = fs::read_dir(MARKDOWN_DIR) {
+        // Remove all source files from the markdown directory
+        if let Ok(entries) = fs::read_dir(markdown_dir()) {
             for entry in entries.flatten() {
                 let path = entry.path();
-                if path.extension().map_or(false, |ext| ext == "md") {
+                if content_parsers().is_content_file(&path.to_string_lossy()) {
                     if let Err(e) = fs::remove_file(&path) {
                         warn!("Failed to remove {}: {}", path.display(), e);
                     }
@@ -541,16 +577,16 @@ impl FileService {
 
     
     fn ensure_directories() -> Result<(), Error> {
-        let markdown_dir = Path::new(MARKDOWN_DIR);
-        let metadata_path = Path::new(METADATA_PATH);
+        let markdown_path = Path::new(markdown_dir());
+        let metadata_file = Path::new(metadata_path());
 
         info!("Ensuring directories exist...");
-        info!("MARKDOWN_DIR (absolute): {:?}", fs::canonicalize(markdown_dir.parent().unwrap_or(Path::new("/"))).unwrap_or_else(|_| markdown_dir.to_path_buf()));
-        info!("METADATA_PATH (absolute): {:?}", fs::canonicalize(metadata_path.parent().unwrap_or(Path::new("/"))).unwrap_or_else(|_| metadata_path.to_path_buf()));
+        info!("MARKDOWN_DIR (absolute): {:?}", fs::canonicalize(markdown_path.parent().unwrap_or(Path::new("/"))).unwrap_or_else(|_| markdown_path.to_path_buf()));
+        info!("METADATA_PATH (absolute): {:?}", fs::canonicalize(metadata_file.parent().unwrap_or(Path::new("/"))).unwrap_or_else(|_| metadata_file.to_path_buf()));
 
-        if !markdown_dir.exists() {
-            info!("Creating markdown directory at {:?}", markdown_dir);
-            fs::create_dir_all(markdown_dir).map_err(|e| {
+        if !markdown_path.exists() {
+            info!("Creating markdown directory at {:?}", markdown_path);
+            fs::create_dir_all(markdown_path).map_err(|e| {
                 Error::new(
                     std::io::ErrorKind::Other,
                     format!("Failed to create markdown directory: {}", e),
@@ -560,7 +596,7 @@ impl FileService {
             #[cfg(unix)]
             {
                 use std::os::unix::fs::PermissionsExt;
-                fs::set_permissions(markdown_dir, fs::Permissions::from_mode(0o777)).map_err(
+                fs::set_permissions(markdown_path, fs::Permissions::from_mode(0o777)).map_err(
                     |e| {
                         Error::new(
                             std::io::ErrorKind::Other,
@@ -572,8 +608,8 @@ impl FileService {
         }
 
         
-        let metadata_dir = Path::new(METADATA_PATH).parent()
-            .expect("METADATA_PATH constant has a known parent directory");
+        let metadata_dir = Path::new(metadata_path()).parent()
+            .expect("metadata path has a parent directory");
         if !metadata_dir.exists() {
             info!("Creating metadata directory at {:?}", metadata_dir);
             fs::create_dir_all(metadata_dir).map_err(|e| {
@@ -597,7 +633,7 @@ impl FileService {
         }
 
         
-        let test_file = format!("{}/test_permissions", MARKDOWN_DIR);
+        let test_file = format!("{}/test_permissions", markdown_dir());
         match fs::write(&test_file, "test") {
             Ok(_) => {
                 info!("Successfully wrote test file to {}", test_file);
@@ -616,7 +652,7 @@ impl FileService {
                 if let Ok(current_dir) = std::env::current_dir() {
                     error!("Current directory: {:?}", current_dir);
                 }
-                if let Ok(dir_contents) = fs::read_dir(MARKDOWN_DIR) {
+                if let Ok(dir_contents) = fs::read_dir(markdown_dir()) {
                     error!("Directory contents: {:?}", dir_contents);
                 }
                 Err(Error::new(
@@ -631,7 +667,7 @@ impl FileService {
     pub fn save_metadata(metadata: &MetadataStore) -> Result<(), Error> {
         let json = crate::utils::json::to_json_pretty(metadata)
             .map_err(|e| Error::new(std::io::ErrorKind::Other, e.to_string()))?;
-        fs::write(METADATA_PATH, json)
+        fs::write(metadata_path(), json)
             .map_err(|e| Error::new(std::io::ErrorKind::Other, e.to_string()))?;
         Ok(())
     }
@@ -639,23 +675,23 @@ impl FileService {
     /// Scan local markdown files and create metadata from them
     /// This is used as a fallback when GitHub sync fails or when local files exist
     pub fn scan_local_files_to_metadata() -> Result<MetadataStore, String> {
-        info!("Scanning local markdown files from {}", MARKDOWN_DIR);
+        info!("Scanning local markdown files from {}", markdown_dir());
 
-        let markdown_dir = Path::new(MARKDOWN_DIR);
-        if !markdown_dir.exists() {
-            return Err(format!("Markdown directory does not exist: {}", MARKDOWN_DIR));
+        let markdown_path = Path::new(markdown_dir());
+        if !markdown_path.exists() {
+            return Err(format!("Markdown directory does not exist: {}", markdown_dir()));
         }
 
         let mut metadata_store = MetadataStore::new();
         let mut node_id_counter: u32 = 1;
 
-        // Read all .md files from the directory
-        let entries = fs::read_dir(markdown_dir)
+        // Read all source files (markdown and registered types) from the directory
+        let entries = fs::read_dir(markdown_path)
             .map_err(|e| format!("Failed to read markdown directory: {}", e))?;
 
         for entry in entries.flatten() {
             let path = entry.path();
-            if path.extension().map_or(false, |ext| ext == "md") {
+            if path.is_file() && content_parsers().is_content_file(&path.to_string_lossy()) {
                 let file_name = match path.file_name().and_then(|n| n.to_str()) {
                     Some(name) => name.to_string(),
                     None => continue,
@@ -695,11 +731,11 @@ impl FileService {
         // Update topic counts (cross-references between files)
         let valid_nodes: Vec<String> = metadata_store
             .keys()
-            .map(|name| name.trim_end_matches(".md").to_string())
+            .map(|name| file_stem(name).to_string())
             .collect();
 
         for file_name in metadata_store.keys().cloned().collect::<Vec<_>>() {
-            let file_path = format!("{}/{}", MARKDOWN_DIR, file_name);
+            let file_path = format!("{}/{}", markdown_dir(), file_name);
             if let Ok(content) = fs::read_to_string(&file_path) {
                 let references = Self::extract_references(&content, &valid_nodes);
                 let topic_counts = Self::convert_references_to_topic_counts(references);
@@ -738,6 +774,21 @@ impl FileService {
         re.find_iter(content).count()
     }
 
+    /// Distinct http(s) URLs in a page — markdown link targets and bare URLs.
+    /// Trailing punctuation that markdown prose commonly glues onto a bare URL
+    /// is stripped.
+    pub fn extract_external_links(content: &str) -> Vec<String> {
+        let re = Regex::new(r#"https?://[^\s<>()\[\]"'`]+"#).expect("Invalid regex pattern");
+        let mut links: Vec<String> = re
+            .find_iter(content)
+            .map(|m| m.as_str().trim_end_matches(|c| matches!(c, '.' | ',' | ';' | ':' | '!' | '?')).to_string())
+            .filter(|url| url.len() > "https://".len())
+            .collect();
+        links.sort();
+        links.dedup();
+        links
+    }
+
     /// Extract `owl:class:: prefix:LocalName` from a logseq markdown OntologyBlock.
     /// Returns the full IRI value (e.g. "mv:ArbitrationDecisionEngine") if present.
     /// Used to surface ontology-tagged pages as ontology_node nodes so the
@@ -773,6 +824,43 @@ impl FileService {
         false
     }
 
+    /// Whether a source file is published: `public:: true` for markdown, the
+    /// registered [`ContentParser`](crate::services::parsers::ContentParser)'s
+    /// verdict for every other type. Files that fail to parse are not.
+    fn is_public_source(file_name: &str, content: &str) -> bool {
+        match content_parsers().for_path(file_name) {
+            Some(parser) => parser
+                .parse(content, file_name)
+                .map(|doc| doc.public)
+                .unwrap_or(false),
+            None => Self::is_public_file(content),
+        }
+    }
+
+    /// Page name and link targets of a local source file. Markdown links are
+    /// `[[wikilinks]]`; other types are read by their content parser.
+    fn page_links(file_name: &str, content: &str, wikilink_re: &Regex) -> (String, Vec<String>) {
+        if let Some(parser) = content_parsers().for_path(file_name) {
+            return match parser.parse(content, file_name) {
+                Ok(doc) => {
+                    let title = doc.title.trim();
+                    let name = if title.is_empty() { file_stem(file_name) } else { title };
+                    (name.to_string(), doc.links)
+                }
+                Err(e) => {
+                    debug!("{} parse failed for {}: {}", parser.name(), file_name, e);
+                    (file_stem(file_name).to_string(), Vec::new())
+                }
+            };
+        }
+        let links = wikilink_re
+            .captures_iter(content)
+            .filter_map(|cap| cap.get(1))
+            .map(|m| m.as_str().to_string())
+            .collect();
+        (file_stem(file_name).to_string(), links)
+    }
+
     /// Extract ontology data from markdown content with new header format
     fn extract_ontology_data(content: &str) -> OntologyData {
         let mut data = OntologyData::default();
@@ -848,6 +936,8 @@ impl FileService {
             perplexity_link: String::new(),
             last_perplexity_process: None,
             topic_counts: HashMap::new(),
+            external_links: Self::extract_external_links(content),
+            language: crate::services::language_detection::detect_language(content),
             // Ontology fields
             term_id: ontology.term_id,
             preferred_term: ontology.preferred_term,
@@ -912,7 +1002,7 @@ impl FileService {
         match content_api.fetch_file_content(download_url).await {
             Ok(content) => {
                 // Check for public-access:: true (new) or public:: true (legacy)
-                let is_public = Self::is_public_file(&content);
+                let is_public = Self::is_public_source(file_name, &content);
                 if !is_public {
                     info!("should_process_file: File {} does not have public marker, skipping", file_name);
                 } else {
@@ -943,7 +1033,7 @@ impl FileService {
 
         
         info!("fetch_and_process_files: Calling list_markdown_files...");
-        let basic_github_files = match content_api.list_markdown_files("").await {
+        let basic_github_files = match content_api.list_markdown_files_in_sources().await {
             Ok(files) => {
                 info!(
                     "fetch_and_process_files: Successfully retrieved {} file entries from GitHub",
@@ -1038,7 +1128,7 @@ impl FileService {
                     match content_api.fetch_file_content(&file_extended_meta.download_url).await {
                         Ok(content) => {
                             // Check for public-access:: true (new) or public:: true (legacy)
-                            let is_public = Self::is_public_file(&content);
+                            let is_public = Self::is_public_source(&file_extended_meta.name, &content);
 
                             if !is_public {
                                 info!("fetch_and_process_files: File {} does not have public marker",
@@ -1048,7 +1138,7 @@ impl FileService {
 
                             info!("fetch_and_process_files: File {} is marked as public, writing to disk", file_extended_meta.name);
 
-                            let file_path = format!("{}/{}", MARKDOWN_DIR, file_extended_meta.name);
+                            let file_path = format!("{}/{}", markdown_dir(), file_extended_meta.name);
                             if let Err(e) = fs::write(&file_path, &content) {
                                 error!("Failed to write file {}: {}", file_path, e);
                                 return Err(e.into());
@@ -1130,11 +1220,14 @@ impl FileService {
     /// (~31k canonical entities) and replace it with the local cache (840
     /// legacy-format nodes) — silently regressing the corpus on every boot.
     ///
+    /// Returns [`GraphSeedOutcome::NoMetadata`] rather than an error when
+    /// there is nothing to load, so startup can wait for the file to arrive.
+    ///
     /// Phase 2 note: stale-node pruning via SPARQL DELETE is not yet implemented.
     /// todo!("Phase 2: stale node pruning via OxigraphGraphRepository")
     pub async fn load_graph_from_files(
         graph_repo: &Arc<dyn crate::ports::knowledge_graph_repository::KnowledgeGraphRepository>,
-    ) -> Result<(), String> {
+    ) -> Result<GraphSeedOutcome, String> {
         info!("Starting to load graph from local files into Oxigraph store (ADR-11)...");
 
         // Idempotency guard: skip if the store is already populated.
@@ -1145,7 +1238,9 @@ impl FileService {
                     existing.nodes.len(),
                     existing.edges.len()
                 );
-                return Ok(());
+                return Ok(GraphSeedOutcome::AlreadyPopulated {
+                    nodes: existing.nodes.len(),
+                });
             }
             Ok(_) => {
                 info!("Oxigraph store is empty — proceeding with local-file seed.");
@@ -1158,20 +1253,34 @@ impl FileService {
             }
         }
 
-        let metadata = Self::load_or_create_metadata()?;
+        // A metadata.json caught mid-write does not parse yet. Like a missing
+        // file it means the metadata has not arrived, so the readiness watcher
+        // retries instead of startup failing.
+        let metadata: MetadataStore = match File::open(metadata_path()) {
+            Ok(file) => match serde_json::from_reader(file) {
+                Ok(metadata) => metadata,
+                Err(e) => {
+                    warn!("metadata.json does not parse yet ({}); treating it as missing.", e);
+                    return Ok(GraphSeedOutcome::NoMetadata);
+                }
+            },
+            Err(_) => Self::load_or_create_metadata()?,
+        };
         if metadata.is_empty() {
             warn!("metadata.json is empty. No data to load into Oxigraph store.");
-            return Ok(());
+            return Ok(GraphSeedOutcome::NoMetadata);
         }
 
         let mut graph_data = GraphData::new();
 
-        // Phase 1: Create nodes and collect file contents + actual IDs.
+        // Phase 1: Create nodes and collect link targets + actual IDs.
+        let wikilink_re = Regex::new(r"\[\[([^\]|]+)(?:\|[^\]]+)?\]\]")
+            .expect("Invalid wikilink regex");
         let mut term_to_id: HashMap<String, u32> = HashMap::new();
-        let mut file_contents: Vec<(String, u32)> = Vec::new();
+        let mut file_links: Vec<(Vec<String>, u32)> = Vec::new();
 
         for (filename, meta) in metadata.iter() {
-            let file_path = Path::new(MARKDOWN_DIR).join(filename);
+            let file_path = Path::new(markdown_dir()).join(filename);
             let content = match fs::read_to_string(&file_path) {
                 Ok(c) => c,
                 Err(e) => {
@@ -1181,8 +1290,9 @@ impl FileService {
             };
 
             let meta_node_id = meta.node_id.parse::<u32>().unwrap_or(0);
+            let (page_name, links) = Self::page_links(filename, &content, &wikilink_re);
             let mut node = AppNode::new_with_id(filename.clone(), Some(meta_node_id));
-            node.label = meta.file_name.trim_end_matches(".md").to_string();
+            node.label = page_name;
             node.size = Some(meta.node_size as f32);
 
             // Detect ontology classification from file content.
@@ -1207,10 +1317,13 @@ impl FileService {
             let actual_id = node.id;
             if let Some(ref term) = meta.preferred_term {
                 term_to_id.insert(term.to_lowercase(), actual_id);
+            } else if content_parsers().for_path(filename).is_some() {
+                // Plugin documents carry no preferred-term; resolve them by title.
+                term_to_id.entry(node.label.to_lowercase()).or_insert(actual_id);
             }
 
             graph_data.nodes.push(node);
-            file_contents.push((content, actual_id));
+            file_links.push((links, actual_id));
         }
 
         info!(
@@ -1218,20 +1331,20 @@ impl FileService {
             graph_data.nodes.len(), term_to_id.len()
         );
 
-        // Phase 2: Extract edges from wikilinks.
-        let wikilink_re = Regex::new(r"\[\[([^\]|]+)(?:\|[^\]]+)?\]\]")
-            .expect("Invalid wikilink regex");
+        // Phase 2: Extract edges from the collected links.
         let mut seen_edges = std::collections::HashSet::new();
 
-        for (content, source_id) in &file_contents {
-            for cap in wikilink_re.captures_iter(content) {
-                if let Some(link_match) = cap.get(1) {
-                    let target = link_match.as_str().trim().to_lowercase();
-                    if let Some(&target_id) = term_to_id.get(&target) {
-                        let edge_key = (*source_id, target_id);
-                        if target_id != *source_id && seen_edges.insert(edge_key) {
-                            graph_data.edges.push(AppEdge::new(*source_id, target_id, 1.0));
-                        }
+        for (links, source_id) in &file_links {
+            for link in links {
+                let target = link.trim().to_lowercase();
+                if let Some(&target_id) = term_to_id.get(&target) {
+                    let edge_key = (*source_id, target_id);
+                    if target_id != *source_id && seen_edges.insert(edge_key) {
+                        graph_data.edges.push(
+                            AppEdge::new(*source_id, target_id, 1.0).with_provenance(
+                                EdgeProvenance::from_source(ProvenanceSource::Wikilink, 1.0),
+                            ),
+                        );
                     }
                 }
             }
@@ -1253,6 +1366,8 @@ impl FileService {
             "Successfully synced Oxigraph store: {} nodes upserted from local files.",
             graph_data.nodes.len()
         );
-        Ok(())
+        Ok(GraphSeedOutcome::Seeded {
+            nodes: graph_data.nodes.len(),
+        })
     }
 }
\ No newline at end of file
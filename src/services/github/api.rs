@@ -18,7 +18,11 @@ pub struct GitHubClient {
     repo: String,
     base_path: String,
     base_paths: Vec<String>,
-    branch: String,
+    /// `GITHUB_BRANCH`; used when no `github.ref` override is active.
+    default_ref: String,
+    /// Branch, tag or commit currently ingested. Switched at runtime via
+    /// `PUT /api/admin/github/ref`.
+    active_ref: std::sync::RwLock<String>,
     settings: Arc<RwLock<AppFullSettings>>,
 }
 
@@ -66,10 +70,23 @@ impl GitHubClient {
             .replace("//", "/")
             .replace('\\', "/");
 
+        // `github.basePaths` in settings takes precedence over the env list.
+        let configured_paths = settings
+            .read()
+            .await
+            .github
+            .as_ref()
+            .map(|g| g.base_paths.clone())
+            .filter(|paths| !paths.is_empty());
+        if let Some(paths) = &configured_paths {
+            info!("GitHub source paths overridden by settings: {:?}", paths);
+        }
+
         // Normalise every configured ingest path the same way as base_path so
         // the Trees API prefix filter can match each source dir.
-        let base_paths: Vec<String> = config
-            .base_paths
+        let base_paths: Vec<String> = configured_paths
+            .as_ref()
+            .unwrap_or(&config.base_paths)
             .iter()
             .map(|p| {
                 let decoded = urlencoding::decode(p)
@@ -88,6 +105,22 @@ impl GitHubClient {
         } else {
             base_paths
         };
+        // The first source path is the root for relative Contents API paths.
+        let base_path = match configured_paths {
+            Some(_) => base_paths[0].clone(),
+            None => base_path,
+        };
+
+        let active_ref = settings
+            .read()
+            .await
+            .github
+            .as_ref()
+            .and_then(|g| g.git_ref.clone())
+            .unwrap_or_else(|| config.branch.clone());
+        if active_ref != config.branch {
+            info!("GitHub content ref overridden by settings: '{}'", active_ref);
+        }
 
         if debug_enabled {
             debug!(
@@ -104,7 +137,8 @@ impl GitHubClient {
             repo: config.repo,
             base_path,
             base_paths,
-            branch: config.branch,
+            default_ref: config.branch,
+            active_ref: std::sync::RwLock::new(active_ref),
             settings: Arc::clone(&settings),
         })
     }
@@ -161,7 +195,7 @@ impl GitHubClient {
                     log::debug!("Path is empty, using base path only: '{}'", decoded_base);
                 }
                 decoded_base
-            } else if decoded_path.starts_with(&decoded_base) {
+            } else if decoded_path.starts_with(&decoded_base) || self.under_other_source(&decoded_path) {
                 
                 if debug_enabled {
                     log::debug!(
@@ -190,13 +224,36 @@ impl GitHubClient {
     }
 
 
+    /// Whether `path` already starts with one of the secondary source paths,
+    /// i.e. is a repository path rather than one relative to `base_path`.
+    fn under_other_source(&self, path: &str) -> bool {
+        self.base_paths.iter().skip(1).any(|source| {
+            let source = source.trim_matches('/');
+            !source.is_empty()
+                && path
+                    .strip_prefix(source)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+    }
+
+    /// Contents API URL for a path relative to the repository root.
+    pub(crate) fn contents_url_at(&self, repo_path: &str) -> String {
+        format!(
+            "https://api.github.com/repos/{}/{}/contents/{}?ref={}",
+            self.owner,
+            self.repo,
+            repo_path.trim_matches('/'),
+            self.encoded_ref()
+        )
+    }
+
     pub async fn get_contents_url(&self, path: &str) -> String {
         let settings = self.settings.read().await;
         let _debug_enabled = crate::utils::logging::is_debug_enabled();
         drop(settings);
 
         info!("get_contents_url: Building GitHub API URL - Owner: '{}', Repo: '{}', Base path: '{}', Input path: '{}', Branch: '{}'",
-            self.owner, self.repo, self.base_path, path, self.branch);
+            self.owner, self.repo, self.base_path, path, self.branch());
 
         let full_path = self.get_full_path(path).await;
 
@@ -207,7 +264,7 @@ impl GitHubClient {
 
         let url = format!(
             "https://api.github.com/repos/{}/{}/contents/{}?ref={}",
-            self.owner, self.repo, full_path, self.branch
+            self.owner, self.repo, full_path, self.encoded_ref()
         );
 
         info!("get_contents_url: Final GitHub API URL: '{}'", url);
@@ -245,8 +302,29 @@ impl GitHubClient {
         &self.base_paths
     }
 
-    pub(crate) fn branch(&self) -> &str {
-        &self.branch
+    /// The ref (branch, tag or commit SHA) content is read from.
+    pub(crate) fn branch(&self) -> String {
+        self.active_ref
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// The active ref, percent-encoded for a `?ref=` query value.
+    pub(crate) fn encoded_ref(&self) -> String {
+        urlencoding::encode(&self.branch()).into_owned()
+    }
+
+    pub(crate) fn default_ref(&self) -> &str {
+        &self.default_ref
+    }
+
+    /// Switch the ingested ref; `None` reverts to `GITHUB_BRANCH`. Returns
+    /// the ref now active.
+    pub(crate) fn set_ref(&self, git_ref: Option<String>) -> String {
+        let next = git_ref.unwrap_or_else(|| self.default_ref.clone());
+        *self.active_ref.write().unwrap_or_else(|e| e.into_inner()) = next.clone();
+        next
     }
 
 }
@@ -1,9 +1,12 @@
 use super::api::GitHubClient;
 use super::types::GitHubFileBasicMetadata;
+use super::tree_cache::{source_key, tree_listing_cache};
 use crate::errors::VisionClawResult;
+use crate::services::parsers::content_parsers;
 use chrono::{DateTime, Utc};
 use log::{debug, error, info, warn};
 use serde_json::Value;
+use std::collections::HashSet;
 use std::sync::Arc;
 use crate::utils::time;
 
@@ -17,23 +20,198 @@ impl EnhancedContentAPI {
         Self { client }
     }
 
-    /// List all markdown files using GitHub's Git Trees API (single API call).
-    /// Returns all .md files under the configured base_path with their SHA hashes.
-    /// This replaces the recursive Contents API approach that required one call per directory.
+    /// First configured source path, used when a page's repo path is unknown.
+    pub fn base_path(&self) -> &str {
+        self.client.base_path()
+    }
+
+    /// Every configured source path.
+    pub fn base_paths(&self) -> &[String] {
+        self.client.base_paths()
+    }
+
+    /// Whether a repository path lies under a configured source path.
+    pub fn in_sources(&self, path: &str) -> bool {
+        under_sources(&source_prefixes(self.client.base_paths()), path)
+    }
+
+    /// List all markdown files under the configured source paths using the
+    /// Git Trees API.
+    ///
+    /// Each source path is resolved to its own subtree and only that subtree
+    /// is walked, so a vault inside a large monorepo never pulls the rest of
+    /// the repository. Listings are cached per source path and reused until
+    /// that subtree's SHA changes; commits elsewhere in the repo leave them
+    /// valid.
     pub async fn list_markdown_files_via_tree(
         &self,
     ) -> VisionClawResult<Vec<GitHubFileBasicMetadata>> {
-        // Dual-source ingest: a single recursive tree call returns the whole
-        // repo; keep every .md file under ANY configured source path. An empty
-        // / "/" prefix means no filtering (whole repo).
-        let base_prefixes: Vec<String> = self
-            .client
-            .base_paths()
+        // An empty prefix list means no filtering (whole repo).
+        let base_prefixes = source_prefixes(self.client.base_paths());
+        let branch = self.client.branch();
+
+        let listings: Vec<Arc<Vec<GitHubFileBasicMetadata>>> =
+            match self.resolve_tree_sha(&branch).await {
+                Ok(root_sha) if base_prefixes.is_empty() => {
+                    vec![self.source_listing(&branch, &root_sha, "").await?]
+                }
+                Ok(root_sha) => {
+                    let mut listings = Vec::with_capacity(base_prefixes.len());
+                    for prefix in &base_prefixes {
+                        listings.push(self.source_listing(&branch, &root_sha, prefix).await?);
+                    }
+                    listings
+                }
+                Err(e) => {
+                    warn!(
+                        "list_markdown_files_via_tree: Could not resolve tree SHA for {}, walking whole tree uncached: {}",
+                        branch, e
+                    );
+                    tree_listing_cache().record_lookup_failure();
+                    vec![Arc::new(self.fetch_markdown_tree(&branch, "").await?.0)]
+                }
+            };
+
+        // Overlapping source paths (`a` and `a/b`) list some files twice.
+        let mut seen = HashSet::new();
+        let markdown_files: Vec<GitHubFileBasicMetadata> = listings
             .iter()
-            .map(|p| p.trim_matches('/').to_string())
-            .filter(|p| !p.is_empty() && p != "/")
-            .map(|p| format!("{}/", p))
+            .flat_map(|listing| listing.iter())
+            .filter(|file| under_sources(&base_prefixes, &file.path) && !is_excluded(&file.path))
+            .filter(|file| seen.insert(file.path.clone()))
+            .cloned()
             .collect();
+
+        info!(
+            "list_markdown_files_via_tree: Found {} markdown files under sources {:?}",
+            markdown_files.len(),
+            base_prefixes
+        );
+        Ok(markdown_files)
+    }
+
+    /// Markdown listing of one source path (`prefix`, with trailing `/`, or
+    /// empty for the whole tree), served from cache while its subtree SHA is
+    /// unchanged.
+    async fn source_listing(
+        &self,
+        branch: &str,
+        root_sha: &str,
+        prefix: &str,
+    ) -> VisionClawResult<Arc<Vec<GitHubFileBasicMetadata>>> {
+        let tree_sha = if prefix.is_empty() {
+            root_sha.to_string()
+        } else {
+            match self.resolve_subtree_sha(root_sha, prefix).await? {
+                Some(sha) => sha,
+                None => {
+                    warn!(
+                        "list_markdown_files_via_tree: Source path '{}' not found on {}",
+                        prefix, branch
+                    );
+                    return Ok(Arc::new(Vec::new()));
+                }
+            }
+        };
+
+        let cache = tree_listing_cache();
+        let key = source_key(self.client.owner(), self.client.repo(), branch, prefix);
+        if let Some(files) = cache.get(&key, &tree_sha) {
+            info!(
+                "list_markdown_files_via_tree: Tree {} for '{}' unchanged, reusing {} cached entries",
+                tree_sha,
+                prefix,
+                files.len()
+            );
+            return Ok(files);
+        }
+        let (files, truncated) = self.fetch_markdown_tree(&tree_sha, prefix).await?;
+        if truncated {
+            // An incomplete listing must not outlive this sync.
+            Ok(Arc::new(files))
+        } else {
+            Ok(cache.insert(key, tree_sha, files))
+        }
+    }
+
+    /// SHA of the tree at `path` below `root_sha`, walked one level per
+    /// segment. `None` if any segment is missing or not a directory.
+    async fn resolve_subtree_sha(
+        &self,
+        root_sha: &str,
+        path: &str,
+    ) -> VisionClawResult<Option<String>> {
+        let mut sha = root_sha.to_string();
+        for segment in path.split('/').filter(|s| !s.is_empty()) {
+            let url = format!(
+                "https://api.github.com/repos/{}/{}/git/trees/{}",
+                self.client.owner(),
+                self.client.repo(),
+                sha
+            );
+            let response = self
+                .client
+                .client()
+                .get(&url)
+                .header("Authorization", format!("Bearer {}", self.client.token()))
+                .header("Accept", "application/vnd.github+json")
+                .send()
+                .await?;
+            let status = response.status();
+            if !status.is_success() {
+                return Err(format!("GitHub Trees API error ({}) resolving '{}'", status, path).into());
+            }
+            let tree: Value = response.json().await?;
+            let child = tree["tree"].as_array().and_then(|entries| {
+                entries.iter().find(|entry| {
+                    entry["type"].as_str() == Some("tree") && entry["path"].as_str() == Some(segment)
+                })
+            });
+            match child.and_then(|entry| entry["sha"].as_str()) {
+                Some(child_sha) => sha = child_sha.to_string(),
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(sha))
+    }
+
+    /// Tree SHA of the commit `git_ref` (a branch, tag or commit SHA) points at.
+    pub async fn resolve_tree_sha(&self, git_ref: &str) -> VisionClawResult<String> {
+        let url = format!(
+            "https://api.github.com/repos/{}/{}/commits/{}",
+            self.client.owner(),
+            self.client.repo(),
+            encode_ref_path(git_ref)
+        );
+        let response = self
+            .client
+            .client()
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", self.client.token()))
+            .header("Accept", "application/vnd.github+json")
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("GitHub Commits API error ({}) for ref '{}'", status, git_ref).into());
+        }
+        let commit_data: Value = response.json().await?;
+        commit_data["commit"]["tree"]["sha"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| "GitHub Commits API returned no tree SHA".into())
+    }
+
+    /// Every .md blob in the tree, unfiltered. `tree_ref` is a tree SHA or a
+    /// branch name; `prefix` is the repo path of that tree (with trailing
+    /// `/`, empty for the root) and is prepended to every entry path. The
+    /// flag reports a truncated (incomplete) response.
+    async fn fetch_markdown_tree(
+        &self,
+        tree_ref: &str,
+        prefix: &str,
+    ) -> VisionClawResult<(Vec<GitHubFileBasicMetadata>, bool)> {
         let branch = self.client.branch();
 
         // Git Trees API with recursive=1 returns the entire tree in one call
@@ -41,7 +219,7 @@ impl EnhancedContentAPI {
             "https://api.github.com/repos/{}/{}/git/trees/{}?recursive=1",
             self.client.owner(),
             self.client.repo(),
-            branch
+            encode_ref_path(tree_ref)
         );
 
         info!("list_markdown_files_via_tree: Fetching tree from: {}", tree_url);
@@ -90,29 +268,12 @@ impl EnhancedContentAPI {
             let entry_type = entry["type"].as_str().unwrap_or("");
             let entry_path = entry["path"].as_str().unwrap_or("");
 
-            // Only process blob (file) entries that are .md files under a source path
-            if entry_type != "blob" || !entry_path.ends_with(".md") {
-                continue;
-            }
-
-            // Keep the file if it sits under ANY configured source path. An
-            // empty prefix list means no filtering (ingest the whole repo).
-            if !base_prefixes.is_empty()
-                && !base_prefixes
-                    .iter()
-                    .any(|prefix| entry_path.starts_with(prefix.as_str()))
-            {
-                continue;
-            }
-
-            // Skip Logseq backup directories and non-content paths
-            if entry_path.contains("/bak/")
-                || entry_path.contains("/logseq/")
-                || entry_path.contains("/.recycle/")
-                || entry_path.contains("/journals/")
-            {
+            // Only markdown and file types with a registered content parser
+            if entry_type != "blob" || !content_parsers().is_content_file(entry_path) {
                 continue;
             }
+            let entry_path = format!("{}{}", prefix, entry_path);
+            let entry_path = entry_path.as_str();
 
             let sha = entry["sha"].as_str().unwrap_or("").to_string();
             let size = entry["size"].as_u64().unwrap_or(0);
@@ -142,20 +303,49 @@ impl EnhancedContentAPI {
             });
         }
 
-        info!(
-            "list_markdown_files_via_tree: Found {} markdown files under sources {:?}",
-            markdown_files.len(),
-            base_prefixes
-        );
-        Ok(markdown_files)
+        Ok((markdown_files, truncated))
     }
 
+    /// Contents API listing of `path`, relative to the first source path.
     pub fn list_markdown_files<'a>(
         &'a self,
         path: &'a str,
     ) -> std::pin::Pin<Box<dyn std::future::Future<Output = VisionClawResult<Vec<GitHubFileBasicMetadata>>> + Send + 'a>> {
         Box::pin(async move {
-            self.list_markdown_files_impl(path).await
+            let repo_path = GitHubClient::get_full_path(&self.client, path).await;
+            self.list_markdown_files_at(repo_path).await
+        })
+    }
+
+    /// Contents API listing of every configured source path. Slower than
+    /// [`Self::list_markdown_files_via_tree`]; used as its fallback.
+    pub async fn list_markdown_files_in_sources(
+        &self,
+    ) -> VisionClawResult<Vec<GitHubFileBasicMetadata>> {
+        let prefixes = source_prefixes(self.client.base_paths());
+        let roots: Vec<String> = if prefixes.is_empty() {
+            vec![String::new()]
+        } else {
+            prefixes.iter().map(|p| p.trim_end_matches('/').to_string()).collect()
+        };
+        let mut seen = HashSet::new();
+        let mut files = Vec::new();
+        for root in roots {
+            for file in self.list_markdown_files_at(root).await? {
+                if seen.insert(file.path.clone()) {
+                    files.push(file);
+                }
+            }
+        }
+        Ok(files)
+    }
+
+    fn list_markdown_files_at(
+        &self,
+        repo_path: String,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = VisionClawResult<Vec<GitHubFileBasicMetadata>>> + Send + '_>> {
+        Box::pin(async move {
+            self.list_markdown_files_impl(&repo_path).await
         })
     }
 
@@ -167,7 +357,7 @@ impl EnhancedContentAPI {
 
         // GitHub Contents API returns all items in a single response (no pagination).
         // per_page/page params are ignored by this endpoint.
-        let contents_url = GitHubClient::get_contents_url(&self.client, path).await;
+        let contents_url = self.client.contents_url_at(path);
 
         debug!("list_markdown_files: Fetching from: {}", contents_url);
 
@@ -206,7 +396,7 @@ impl EnhancedContentAPI {
             let file_type = file["type"].as_str().unwrap_or("unknown");
             let file_name = file["name"].as_str().unwrap_or("unnamed");
 
-            if file_type == "file" && file_name.ends_with(".md") {
+            if file_type == "file" && content_parsers().is_content_file(file_name) {
                 debug!("list_markdown_files: Found markdown file: {}", file_name);
                 all_markdown_files.push(GitHubFileBasicMetadata {
                     name: file_name.to_string(),
@@ -227,7 +417,7 @@ impl EnhancedContentAPI {
 
                 debug!("list_markdown_files: Recursively processing directory: {}", dir_path);
 
-                match self.list_markdown_files(dir_path).await {
+                match self.list_markdown_files_at(dir_path.to_string()).await {
                     Ok(mut subdir_files) => {
                         let count = subdir_files.len();
                         debug!("list_markdown_files: Found {} files in subdirectory {}", count, dir_path);
@@ -282,6 +472,7 @@ impl EnhancedContentAPI {
         );
 
         debug!("Fetching commits for path: {}", encoded_path);
+        let git_ref = self.client.branch();
 
         let response = self
             .client
@@ -291,7 +482,7 @@ impl EnhancedContentAPI {
             .header("Accept", "application/vnd.github+json")
             .query(&[
                 ("path", encoded_path.as_str()),
-                ("ref", self.client.branch()),
+                ("ref", git_ref.as_str()),
                 ("per_page", if check_actual_changes { "10" } else { "1" }),
             ])
             .send()
@@ -423,7 +614,7 @@ impl EnhancedContentAPI {
             self.client.owner(),
             self.client.repo(),
             encoded_path,
-            self.client.branch()
+            self.client.encoded_ref()
         );
 
         let response = self
@@ -471,6 +662,38 @@ impl EnhancedContentAPI {
     }
 }
 
+/// Configured source paths as `dir/` prefixes. Empty when any of them is the
+/// repository root, meaning nothing is filtered.
+fn source_prefixes(base_paths: &[String]) -> Vec<String> {
+    let trimmed: Vec<&str> = base_paths.iter().map(|p| p.trim_matches('/')).collect();
+    if trimmed.iter().any(|p| p.is_empty()) {
+        return Vec::new();
+    }
+    trimmed.iter().map(|p| format!("{}/", p)).collect()
+}
+
+fn under_sources(prefixes: &[String], path: &str) -> bool {
+    prefixes.is_empty() || prefixes.iter().any(|prefix| path.starts_with(prefix.as_str()))
+}
+
+/// Percent-encode a ref for use as a URL path component. Each segment is
+/// encoded on its own so `feature/x` keeps its separator.
+fn encode_ref_path(git_ref: &str) -> String {
+    git_ref
+        .split('/')
+        .map(|segment| urlencoding::encode(segment).into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Logseq backup directories and other non-content paths.
+fn is_excluded(path: &str) -> bool {
+    path.contains("/bak/")
+        || path.contains("/logseq/")
+        || path.contains("/.recycle/")
+        || path.contains("/journals/")
+}
+
 #[derive(Debug, Clone)]
 pub struct ExtendedFileMetadata {
     pub name: String,
@@ -481,3 +704,29 @@ pub struct ExtendedFileMetadata {
     pub last_content_modified: DateTime<Utc>,
     pub file_type: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn source_prefixes_limit_paths() {
+        let prefixes = source_prefixes(&["/vault/pages/".into(), "notes".into()]);
+        assert_eq!(prefixes, vec!["vault/pages/".to_string(), "notes/".to_string()]);
+        assert!(under_sources(&prefixes, "vault/pages/a.md"));
+        assert!(under_sources(&prefixes, "notes/deep/b.md"));
+        assert!(!under_sources(&prefixes, "vault/pagesx/c.md"));
+        assert!(!under_sources(&prefixes, "src/README.md"));
+
+        let whole_repo = source_prefixes(&["notes".into(), "/".into()]);
+        assert!(whole_repo.is_empty());
+        assert!(under_sources(&whole_repo, "src/README.md"));
+    }
+
+    #[test]
+    fn refs_are_encoded_per_segment() {
+        assert_eq!(encode_ref_path("main"), "main");
+        assert_eq!(encode_ref_path("feature/a b#1"), "feature/a%20b%231");
+        assert_eq!(encode_ref_path("v1.0?x=y"), "v1.0%3Fx%3Dy");
+    }
+}
@@ -5,11 +5,15 @@
 //! - Pull Request API: Manages creation and updates of pull requests
 //! - Common types and error handling
 //! - Configuration: Environment-based configuration
+//! - Tree cache: per-branch markdown listing keyed by tree SHA
+//! - Object store: fetched blobs on disk keyed by blob SHA, with sync manifests
 
 pub mod api;
 pub mod config;
 pub mod content_enhanced;
+pub mod object_store;
 pub mod pr;
+pub mod tree_cache;
 pub mod types;
 
 pub use api::GitHubClient;
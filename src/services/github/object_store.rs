@@ -0,0 +1,463 @@
+//! Content-addressed store for fetched page blobs.
+//!
+//! Blobs are kept under their git blob SHA1, the id the tree listing already
+//! reports, so a file whose SHA is on disk is read locally instead of being
+//! downloaded again. Every sync also records a manifest mapping path to SHA.
+//! Manifests are small because blobs are shared, so each one is kept as a
+//! snapshot, and comparing two of them finds renames without reading content.
+//!
+//! Layout under the root (`OBJECT_STORE_DIR`, default `/app/data/objects`):
+//! `ab/cdef…` blobs, `manifest.json` for the latest sync and
+//! `snapshots/<millis>.json` for history.
+//!
+//! A blob is live while the latest manifest or any retained snapshot names
+//! it. After each sync the store is swept and every other blob is deleted,
+//! apart from recent ones a sync in progress may not have recorded yet.
+
+use crate::config::dev_config;
+use chrono::{DateTime, Utc};
+use log::{debug, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime};
+
+/// Directory under the storage root used unless `OBJECT_STORE_DIR` is set.
+pub const OBJECTS_SUBDIR: &str = "objects";
+/// Snapshots beyond this many are pruned, oldest first.
+const MAX_SNAPSHOTS: usize = 100;
+/// Unreferenced blobs younger than this survive a sweep.
+const GC_GRACE: Duration = Duration::from_secs(60 * 60);
+
+static OBJECT_STORE: Lazy<ObjectStore> = Lazy::new(|| {
+    ObjectStore::new(
+        std::env::var_os("OBJECT_STORE_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| dev_config::storage().path(OBJECTS_SUBDIR)),
+    )
+});
+
+/// Process-wide blob store used by the GitHub sync.
+pub fn object_store() -> &'static ObjectStore {
+    &OBJECT_STORE
+}
+
+/// SHA git gives a blob holding `content`.
+pub fn git_blob_sha(content: &str) -> String {
+    use sha1::{Digest, Sha1};
+    let mut hasher = Sha1::new();
+    hasher.update(format!("blob {}\0", content.len()).as_bytes());
+    hasher.update(content.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Rename {
+    pub from: String,
+    pub to: String,
+    pub sha: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Manifest {
+    pub created_at: DateTime<Utc>,
+    /// Path to blob SHA for every file in the listing.
+    pub files: BTreeMap<String, String>,
+    /// Renames relative to the previous manifest.
+    #[serde(default)]
+    pub renames: Vec<Rename>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectStoreStats {
+    /// Blobs served from disk instead of downloaded.
+    pub hits: u64,
+    pub misses: u64,
+    pub writes: u64,
+    pub bytes_written: u64,
+    pub write_failures: u64,
+    /// Unreferenced blobs deleted by sweeps.
+    pub blobs_collected: u64,
+    pub bytes_collected: u64,
+}
+
+/// Outcome of one sweep.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SweepReport {
+    pub referenced: usize,
+    pub removed: usize,
+    pub bytes_freed: u64,
+}
+
+#[derive(Debug)]
+pub struct ObjectStore {
+    root: PathBuf,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    writes: AtomicU64,
+    bytes_written: AtomicU64,
+    write_failures: AtomicU64,
+    blobs_collected: AtomicU64,
+    bytes_collected: AtomicU64,
+}
+
+/// Paths present in `previous` but not `current` whose blob reappears under
+/// a new path. Each old path pairs with at most one new path.
+pub fn detect_renames(
+    previous: &BTreeMap<String, String>,
+    current: &BTreeMap<String, String>,
+) -> Vec<Rename> {
+    let mut gone: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (path, sha) in previous.iter().rev() {
+        if !current.contains_key(path) {
+            gone.entry(sha.as_str()).or_default().push(path.as_str());
+        }
+    }
+
+    current
+        .iter()
+        .filter(|(path, _)| !previous.contains_key(*path))
+        .filter_map(|(path, sha)| {
+            let from = gone.get_mut(sha.as_str())?.pop()?;
+            Some(Rename {
+                from: from.to_string(),
+                to: path.clone(),
+                sha: sha.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Write through a temporary file so readers never see a partial object.
+fn write_atomic(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, bytes)?;
+    fs::rename(&tmp, path)
+}
+
+impl ObjectStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            writes: AtomicU64::new(0),
+            bytes_written: AtomicU64::new(0),
+            write_failures: AtomicU64::new(0),
+            blobs_collected: AtomicU64::new(0),
+            bytes_collected: AtomicU64::new(0),
+        }
+    }
+
+    /// `None` unless `sha` is a full lowercase hex SHA1, which also keeps
+    /// callers from addressing anything outside the store.
+    fn object_path(&self, sha: &str) -> Option<PathBuf> {
+        let valid = sha.len() == 40
+            && sha
+                .bytes()
+                .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b));
+        valid.then(|| self.root.join(&sha[..2]).join(&sha[2..]))
+    }
+
+    fn snapshot_dir(&self) -> PathBuf {
+        self.root.join("snapshots")
+    }
+
+    pub fn contains(&self, sha: &str) -> bool {
+        self.object_path(sha).is_some_and(|p| p.is_file())
+    }
+
+    /// The blob stored under `sha`. A blob whose content no longer hashes to
+    /// its name is removed and reported as missing.
+    pub fn get(&self, sha: &str) -> Option<String> {
+        let content = self
+            .object_path(sha)
+            .and_then(|path| match fs::read_to_string(&path) {
+                Ok(content) if git_blob_sha(&content) == sha => Some(content),
+                Ok(_) => {
+                    warn!("Object {} is corrupt; discarding", sha);
+                    let _ = fs::remove_file(&path);
+                    None
+                }
+                Err(_) => None,
+            });
+        let counter = if content.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        content
+    }
+
+    /// Store `content` and return its SHA. Already-present blobs are not
+    /// rewritten.
+    pub fn put(&self, content: &str) -> Result<String, String> {
+        let sha = git_blob_sha(content);
+        let path = self
+            .object_path(&sha)
+            .ok_or_else(|| format!("invalid object id {}", sha))?;
+        if path.is_file() {
+            return Ok(sha);
+        }
+        match write_atomic(&path, content.as_bytes()) {
+            Ok(()) => {
+                self.writes.fetch_add(1, Ordering::Relaxed);
+                self.bytes_written
+                    .fetch_add(content.len() as u64, Ordering::Relaxed);
+                Ok(sha)
+            }
+            Err(e) => {
+                self.write_failures.fetch_add(1, Ordering::Relaxed);
+                Err(format!("Failed to write object {}: {}", sha, e))
+            }
+        }
+    }
+
+    /// Manifest of the most recent sync.
+    pub fn latest_manifest(&self) -> Option<Manifest> {
+        let raw = fs::read_to_string(self.root.join("manifest.json")).ok()?;
+        serde_json::from_str(&raw)
+            .map_err(|e| warn!("Unreadable object store manifest: {}", e))
+            .ok()
+    }
+
+    /// Record `files` as the latest manifest and as a new snapshot. Renames
+    /// against the previous manifest are detected and stored alongside.
+    pub fn record_manifest(&self, files: BTreeMap<String, String>) -> Result<Manifest, String> {
+        let renames = self
+            .latest_manifest()
+            .map(|previous| detect_renames(&previous.files, &files))
+            .unwrap_or_default();
+        let manifest = Manifest {
+            created_at: Utc::now(),
+            files,
+            renames,
+        };
+        let json = serde_json::to_vec(&manifest)
+            .map_err(|e| format!("Failed to serialise manifest: {}", e))?;
+
+        let snapshot = self
+            .snapshot_dir()
+            .join(format!("{}.json", manifest.created_at.timestamp_millis()));
+        write_atomic(&snapshot, &json)
+            .and_then(|_| write_atomic(&self.root.join("manifest.json"), &json))
+            .map_err(|e| format!("Failed to write manifest: {}", e))?;
+        self.prune_snapshots();
+        Ok(manifest)
+    }
+
+    /// Snapshot ids (creation time in milliseconds), oldest first.
+    pub fn snapshots(&self) -> Vec<i64> {
+        let mut ids: Vec<i64> = fs::read_dir(self.snapshot_dir())
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter_map(|e| e.file_name().to_str()?.strip_suffix(".json")?.parse().ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+        ids.sort_unstable();
+        ids
+    }
+
+    pub fn snapshot(&self, id: i64) -> Option<Manifest> {
+        let raw = fs::read_to_string(self.snapshot_dir().join(format!("{}.json", id))).ok()?;
+        serde_json::from_str(&raw).ok()
+    }
+
+    fn prune_snapshots(&self) {
+        let ids = self.snapshots();
+        for id in &ids[..ids.len().saturating_sub(MAX_SNAPSHOTS)] {
+            let path = self.snapshot_dir().join(format!("{}.json", id));
+            if let Err(e) = fs::remove_file(&path) {
+                debug!("Failed to prune snapshot {}: {}", path.display(), e);
+            }
+        }
+    }
+
+    /// Blob SHAs named by the latest manifest or a retained snapshot. `None`
+    /// when there is no manifest yet or a snapshot cannot be read, since
+    /// nothing can safely be called unreferenced then.
+    pub fn referenced(&self) -> Option<HashSet<String>> {
+        let mut live: HashSet<String> = self.latest_manifest()?.files.into_values().collect();
+        for id in self.snapshots() {
+            match self.snapshot(id) {
+                Some(snapshot) => live.extend(snapshot.files.into_values()),
+                None => {
+                    warn!("Snapshot {} unreadable; skipping object sweep", id);
+                    return None;
+                }
+            }
+        }
+        Some(live)
+    }
+
+    /// Delete every blob that is unreferenced and older than the grace
+    /// period. Blocking; run off the async workers.
+    pub fn sweep(&self) -> SweepReport {
+        self.sweep_older_than(GC_GRACE)
+    }
+
+    fn sweep_older_than(&self, grace: Duration) -> SweepReport {
+        let Some(live) = self.referenced() else {
+            return SweepReport::default();
+        };
+        let mut report = SweepReport {
+            referenced: live.len(),
+            ..SweepReport::default()
+        };
+        let cutoff = SystemTime::now().checked_sub(grace).unwrap_or(SystemTime::UNIX_EPOCH);
+        let Ok(fanout) = fs::read_dir(&self.root) else {
+            return report;
+        };
+        for dir in fanout.flatten() {
+            let prefix = dir.file_name().to_string_lossy().into_owned();
+            // Blob fan-out directories are two hex digits; skip snapshots/.
+            if prefix.len() != 2 || !dir.path().is_dir() {
+                continue;
+            }
+            let Ok(entries) = fs::read_dir(dir.path()) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                // Leftover `.tmp` files from interrupted writes go too.
+                let sha = format!("{}{}", prefix, name.trim_end_matches(".tmp"));
+                if live.contains(&sha) && !name.ends_with(".tmp") {
+                    continue;
+                }
+                let Ok(meta) = entry.metadata() else {
+                    continue;
+                };
+                if meta.modified().map_or(true, |modified| modified > cutoff) {
+                    continue;
+                }
+                match fs::remove_file(entry.path()) {
+                    Ok(()) => {
+                        report.removed += 1;
+                        report.bytes_freed += meta.len();
+                    }
+                    Err(e) => debug!("Failed to sweep {}: {}", entry.path().display(), e),
+                }
+            }
+        }
+        self.blobs_collected
+            .fetch_add(report.removed as u64, Ordering::Relaxed);
+        self.bytes_collected
+            .fetch_add(report.bytes_freed, Ordering::Relaxed);
+        report
+    }
+
+    pub fn stats(&self) -> ObjectStoreStats {
+        ObjectStoreStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            writes: self.writes.load(Ordering::Relaxed),
+            bytes_written: self.bytes_written.load(Ordering::Relaxed),
+            write_failures: self.write_failures.load(Ordering::Relaxed),
+            blobs_collected: self.blobs_collected.load(Ordering::Relaxed),
+            bytes_collected: self.bytes_collected.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn listing(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+        entries
+            .iter()
+            .map(|(p, s)| (p.to_string(), s.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn git_blob_sha_matches_git() {
+        assert_eq!(git_blob_sha(""), "e69de29bb2d1d6434b8b29ae775ad8c2e48c5391");
+        assert_eq!(
+            git_blob_sha("hello\n"),
+            "ce013625030ba8dba906f756967f9e9ca394464a"
+        );
+    }
+
+    #[test]
+    fn blobs_round_trip_and_dedupe() {
+        let dir = tempdir().unwrap();
+        let store = ObjectStore::new(dir.path());
+
+        let sha = store.put("hello\n").unwrap();
+        assert_eq!(store.put("hello\n").unwrap(), sha);
+        assert_eq!(store.get(&sha).as_deref(), Some("hello\n"));
+        assert!(store.get("../../etc/passwd").is_none());
+
+        fs::write(store.object_path(&sha).unwrap(), "tampered").unwrap();
+        assert!(store.get(&sha).is_none(), "corrupt blob rejected");
+        assert!(!store.contains(&sha));
+
+        let stats = store.stats();
+        assert_eq!((stats.writes, stats.hits, stats.misses), (1, 1, 2));
+    }
+
+    #[test]
+    fn manifests_record_renames_and_snapshots() {
+        let dir = tempdir().unwrap();
+        let store = ObjectStore::new(dir.path());
+
+        store
+            .record_manifest(listing(&[("pages/a.md", "s1"), ("pages/b.md", "s2")]))
+            .unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        let second = store
+            .record_manifest(listing(&[("pages/c.md", "s1"), ("pages/b.md", "s3")]))
+            .unwrap();
+
+        assert_eq!(
+            second.renames,
+            vec![Rename {
+                from: "pages/a.md".into(),
+                to: "pages/c.md".into(),
+                sha: "s1".into()
+            }]
+        );
+        let ids = store.snapshots();
+        assert_eq!(ids.len(), 2);
+        assert_eq!(store.snapshot(ids[0]).unwrap().files["pages/a.md"], "s1");
+        assert_eq!(store.latest_manifest().unwrap().files.len(), 2);
+    }
+
+    #[test]
+    fn sweep_removes_only_unreferenced_blobs() {
+        let dir = tempdir().unwrap();
+        let store = ObjectStore::new(dir.path());
+
+        let kept = store.put("kept\n").unwrap();
+        let historic = store.put("historic\n").unwrap();
+        let orphan = store.put("orphan\n").unwrap();
+        assert_eq!(store.sweep_older_than(Duration::ZERO).removed, 0, "no manifest yet");
+
+        store.record_manifest(listing(&[("pages/a.md", &historic)])).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        store.record_manifest(listing(&[("pages/a.md", &kept)])).unwrap();
+
+        assert_eq!(store.sweep().removed, 0, "recent blobs are within the grace period");
+        let report = store.sweep_older_than(Duration::ZERO);
+        assert_eq!((report.referenced, report.removed), (2, 1));
+        assert!(store.contains(&kept));
+        assert!(store.contains(&historic), "still named by a snapshot");
+        assert!(!store.contains(&orphan));
+        assert_eq!(store.stats().blobs_collected, 1);
+    }
+}
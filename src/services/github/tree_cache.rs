@@ -0,0 +1,245 @@
+//! Per-branch cache of the flattened markdown listing from the Git Trees API.
+//!
+//! A recursive tree fetch on a large vault is the slowest part of a sync. A
+//! tree's SHA is cheap to resolve and changes whenever any file under it
+//! does, so each source path's listing is only re-walked when its subtree SHA
+//! moves. Stats are exposed through `/api/metrics`.
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use super::types::GitHubFileBasicMetadata;
+
+static TREE_CACHE: Lazy<TreeListingCache> = Lazy::new(TreeListingCache::default);
+
+/// Process-wide listing cache shared by every `ContentAPI` instance.
+pub fn tree_listing_cache() -> &'static TreeListingCache {
+    &TREE_CACHE
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TreeCacheStats {
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+    /// Branch lookups that failed and forced an uncached walk.
+    pub sha_lookup_failures: u64,
+    pub cached_files: usize,
+}
+
+#[derive(Debug)]
+struct CachedTree {
+    tree_sha: String,
+    files: Arc<Vec<GitHubFileBasicMetadata>>,
+}
+
+#[derive(Debug, Default)]
+pub struct TreeListingCache {
+    trees: Mutex<HashMap<String, CachedTree>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    sha_lookup_failures: AtomicU64,
+}
+
+/// Cache key for one branch of one repository.
+pub fn branch_key(owner: &str, repo: &str, branch: &str) -> String {
+    format!("{}/{}@{}", owner, repo, branch)
+}
+
+/// Cache key for the source path `prefix` (empty for the whole tree) on one
+/// branch.
+pub fn source_key(owner: &str, repo: &str, branch: &str, prefix: &str) -> String {
+    if prefix.is_empty() {
+        branch_key(owner, repo, branch)
+    } else {
+        format!("{}:{}", branch_key(owner, repo, branch), prefix)
+    }
+}
+
+impl TreeListingCache {
+    /// The cached listing for `key`, if it was taken from `tree_sha`.
+    pub fn get(&self, key: &str, tree_sha: &str) -> Option<Arc<Vec<GitHubFileBasicMetadata>>> {
+        let trees = self.trees.lock().unwrap_or_else(|e| e.into_inner());
+        match trees.get(key) {
+            Some(cached) if cached.tree_sha == tree_sha => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(Arc::clone(&cached.files))
+            }
+            _ => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    pub fn insert(
+        &self,
+        key: String,
+        tree_sha: String,
+        files: Vec<GitHubFileBasicMetadata>,
+    ) -> Arc<Vec<GitHubFileBasicMetadata>> {
+        let files = Arc::new(files);
+        self.trees.lock().unwrap_or_else(|e| e.into_inner()).insert(
+            key,
+            CachedTree {
+                tree_sha,
+                files: Arc::clone(&files),
+            },
+        );
+        files
+    }
+
+    pub fn record_lookup_failure(&self) {
+        self.sha_lookup_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn clear(&self) {
+        self.trees.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    pub fn stats(&self) -> TreeCacheStats {
+        let trees = self.trees.lock().unwrap_or_else(|e| e.into_inner());
+        TreeCacheStats {
+            entries: trees.len(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            sha_lookup_failures: self.sha_lookup_failures.load(Ordering::Relaxed),
+            cached_files: trees.values().map(|t| t.files.len()).sum(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str) -> GitHubFileBasicMetadata {
+        GitHubFileBasicMetadata {
+            name: path.rsplit('/').next().unwrap().to_string(),
+            path: path.to_string(),
+            sha: format!("sha-{}", path),
+            size: 1,
+            download_url: String::new(),
+        }
+    }
+
+    fn cache_with(key: &str, tree_sha: &str, paths: &[&str]) -> TreeListingCache {
+        let cache = TreeListingCache::default();
+        cache.insert(
+            key.to_string(),
+            tree_sha.to_string(),
+            paths.iter().map(|p| file(p)).collect(),
+        );
+        cache
+    }
+
+    #[test]
+    fn empty_cache_misses() {
+        let cache = TreeListingCache::default();
+        assert!(cache.get(&branch_key("o", "r", "main"), "t1").is_none());
+        assert_eq!(cache.stats().misses, 1);
+    }
+
+    #[test]
+    fn serves_the_listing_taken_from_the_same_tree_sha() {
+        let key = branch_key("o", "r", "main");
+        let cache = cache_with(&key, "t1", &["pages/a.md", "pages/b.md"]);
+        let files = cache.get(&key, "t1").unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, "pages/a.md");
+    }
+
+    #[test]
+    fn insert_and_get_share_one_listing() {
+        let cache = TreeListingCache::default();
+        let key = branch_key("o", "r", "main");
+        let inserted = cache.insert(key.clone(), "t1".into(), vec![file("pages/a.md")]);
+        assert!(Arc::ptr_eq(&inserted, &cache.get(&key, "t1").unwrap()));
+    }
+
+    #[test]
+    fn new_tree_sha_misses() {
+        let key = branch_key("o", "r", "main");
+        let cache = cache_with(&key, "t1", &["pages/a.md"]);
+        assert!(cache.get(&key, "t2").is_none());
+    }
+
+    #[test]
+    fn insert_replaces_the_stale_listing() {
+        let key = branch_key("o", "r", "main");
+        let cache = cache_with(&key, "t1", &["pages/a.md"]);
+        cache.insert(
+            key.clone(),
+            "t2".into(),
+            vec![file("pages/a.md"), file("pages/b.md")],
+        );
+        assert!(cache.get(&key, "t1").is_none());
+        assert_eq!(cache.get(&key, "t2").unwrap().len(), 2);
+        assert_eq!(cache.stats().entries, 1);
+    }
+
+    #[test]
+    fn other_branches_miss() {
+        let cache = cache_with(&branch_key("o", "r", "main"), "t1", &["pages/a.md"]);
+        assert!(cache.get(&branch_key("o", "r", "dev"), "t1").is_none());
+        assert!(cache.get(&branch_key("o", "fork", "main"), "t1").is_none());
+    }
+
+    #[test]
+    fn source_keys_are_per_path_and_the_root_is_the_branch() {
+        assert_eq!(
+            source_key("o", "r", "main", ""),
+            branch_key("o", "r", "main")
+        );
+        assert_eq!(source_key("o", "r", "main", "pages"), "o/r@main:pages");
+        assert_ne!(
+            source_key("o", "r", "main", "pages"),
+            source_key("o", "r", "main", "journals")
+        );
+    }
+
+    #[test]
+    fn clear_drops_listings_but_keeps_counters() {
+        let key = branch_key("o", "r", "main");
+        let cache = cache_with(&key, "t1", &["pages/a.md"]);
+        assert!(cache.get(&key, "t1").is_some());
+        cache.clear();
+        assert!(cache.get(&key, "t1").is_none());
+
+        let stats = cache.stats();
+        assert_eq!((stats.entries, stats.cached_files), (0, 0));
+        assert_eq!((stats.hits, stats.misses), (1, 1));
+    }
+
+    #[test]
+    fn stats_count_hits_misses_failures_and_files() {
+        let cache = TreeListingCache::default();
+        cache.insert(
+            source_key("o", "r", "main", "pages"),
+            "t1".into(),
+            vec![file("pages/a.md"), file("pages/b.md")],
+        );
+        cache.insert(
+            source_key("o", "r", "main", "journals"),
+            "t2".into(),
+            vec![file("journals/2026_10_16.md")],
+        );
+        assert!(cache
+            .get(&source_key("o", "r", "main", "pages"), "t1")
+            .is_some());
+        assert!(cache
+            .get(&source_key("o", "r", "main", "pages"), "t3")
+            .is_none());
+        assert!(cache.get(&branch_key("o", "r", "main"), "t1").is_none());
+        cache.record_lookup_failure();
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (1, 2));
+        assert_eq!(stats.sha_lookup_failures, 1);
+        assert_eq!((stats.entries, stats.cached_files), (2, 3));
+    }
+}
@@ -92,6 +92,47 @@ struct PRResponse {
     number: u64,
 }
 
+/// An open (or previously opened) pull request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PullRequestRef {
+    pub number: u64,
+    pub url: String,
+}
+
+/// Live state of a pull request as reported by GitHub.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PullRequestState {
+    pub number: u64,
+    pub url: String,
+    /// `open` or `closed`.
+    pub state: String,
+    pub merged: bool,
+    pub mergeable: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PRStateResponse {
+    html_url: String,
+    number: u64,
+    state: String,
+    #[serde(default)]
+    merged: bool,
+    mergeable: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ContentsResponse {
+    sha: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BlobContentResponse {
+    content: String,
+    encoding: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct RefResponse {
     object: RefObject,
@@ -209,7 +250,12 @@ impl GitHubPRService {
 
         // 6. Create PR
         let pr_url = self
-            .create_pull_request(title, body, &branch_name)
+            .create_pull_request(
+                title,
+                body,
+                &branch_name,
+                vec!["ontology".to_string(), "agent-proposed".to_string()],
+            )
             .await?;
 
         info!("Created ontology PR: {}", pr_url);
@@ -275,16 +321,28 @@ impl GitHubPRService {
         base_tree_sha: &str,
         file_path: &str,
         blob_sha: &str,
+    ) -> Result<String, String> {
+        self.create_tree_entries(base_tree_sha, &[(file_path.to_string(), blob_sha.to_string())])
+            .await
+    }
+
+    async fn create_tree_entries(
+        &self,
+        base_tree_sha: &str,
+        files: &[(String, String)],
     ) -> Result<String, String> {
         let url = self.api_url("git/trees");
         let body = CreateTreeRequest {
             base_tree: base_tree_sha.to_string(),
-            tree: vec![TreeEntry {
-                path: file_path.to_string(),
-                mode: "100644".to_string(),
-                entry_type: "blob".to_string(),
-                sha: blob_sha.to_string(),
-            }],
+            tree: files
+                .iter()
+                .map(|(path, blob_sha)| TreeEntry {
+                    path: path.clone(),
+                    mode: "100644".to_string(),
+                    entry_type: "blob".to_string(),
+                    sha: blob_sha.clone(),
+                })
+                .collect(),
         };
 
         let resp = self
@@ -413,6 +471,7 @@ impl GitHubPRService {
         title: &str,
         body: &str,
         head_branch: &str,
+        labels: Vec<String>,
     ) -> Result<String, String> {
         let url = self.api_url("pulls");
         let pr_body = CreatePRRequest {
@@ -420,10 +479,7 @@ impl GitHubPRService {
             body: body.to_string(),
             head: head_branch.to_string(),
             base: self.base_branch.clone(),
-            labels: Some(vec![
-                "ontology".to_string(),
-                "agent-proposed".to_string(),
-            ]),
+            labels: Some(labels),
         };
 
         let resp = self
@@ -494,4 +550,179 @@ impl GitHubPRService {
                 )
             })
     }
+
+    pub fn base_branch(&self) -> &str {
+        &self.base_branch
+    }
+
+    /// Head commit of `branch`, or `None` if the branch does not exist.
+    pub async fn branch_head(&self, branch: &str) -> Result<Option<String>, String> {
+        match self.get_ref_sha(branch).await {
+            Ok(sha) => Ok(Some(sha)),
+            Err(e) if e.contains("(404") => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Commit `files` (path, content) on top of `parent_sha` as one commit and
+    /// return its SHA. The branch ref is not moved; see [`Self::move_branch`].
+    pub async fn commit_files(
+        &self,
+        parent_sha: &str,
+        files: &[(String, String)],
+        message: &str,
+    ) -> Result<String, String> {
+        if self.token.is_empty() {
+            return Err("LOGSEQ_PRIVATE_REPO_GITHUB not configured — cannot commit".to_string());
+        }
+        let mut entries = Vec::with_capacity(files.len());
+        for (path, content) in files {
+            entries.push((path.clone(), self.create_blob(content).await?));
+        }
+        let tree_sha = self.create_tree_entries(parent_sha, &entries).await?;
+        self.create_commit(message, &tree_sha, parent_sha).await
+    }
+
+    /// Point `branch` at `sha`, creating it if needed. Existing branches are
+    /// fast-forwarded only, so a concurrent push surfaces as an error instead
+    /// of being overwritten.
+    pub async fn move_branch(&self, branch: &str, sha: &str) -> Result<(), String> {
+        if self.branch_head(branch).await?.is_none() {
+            return self.create_ref(branch, sha).await;
+        }
+        let url = self.api_url(&format!("git/refs/heads/{}", branch));
+        let resp = self
+            .client
+            .patch(&url)
+            .headers(self.headers())
+            .json(&serde_json::json!({ "sha": sha, "force": false }))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to update ref: {}", e))?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(format!("Update ref failed ({}): {}", status, body));
+        }
+        Ok(())
+    }
+
+    /// Open a PR from `head_branch` into the base branch, or return the one
+    /// already open for it.
+    pub async fn open_pull_request(
+        &self,
+        title: &str,
+        body: &str,
+        head_branch: &str,
+    ) -> Result<PullRequestRef, String> {
+        let url = self
+            .create_pull_request(title, body, head_branch, vec!["page-edits".to_string()])
+            .await?;
+        let number = url
+            .rsplit('/')
+            .next()
+            .and_then(|n| n.parse().ok())
+            .ok_or_else(|| format!("Unexpected PR URL '{}'", url))?;
+        Ok(PullRequestRef { number, url })
+    }
+
+    /// Blob SHA of `path` on `branch`, or `None` if the file is absent.
+    pub async fn file_sha(&self, path: &str, branch: &str) -> Result<Option<String>, String> {
+        let encoded: Vec<String> = path
+            .split('/')
+            .map(|segment| urlencoding::encode(segment).into_owned())
+            .collect();
+        let url = self.api_url(&format!(
+            "contents/{}?ref={}",
+            encoded.join("/"),
+            urlencoding::encode(branch)
+        ));
+        let resp = self
+            .client
+            .get(&url)
+            .headers(self.headers())
+            .send()
+            .await
+            .map_err(|e| format!("Failed to look up file: {}", e))?;
+
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(format!("Look up file failed ({}): {}", status, body));
+        }
+
+        let contents: ContentsResponse = resp
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse contents response: {}", e))?;
+        Ok(Some(contents.sha))
+    }
+
+    /// Text of the blob `sha`.
+    pub async fn blob_text(&self, sha: &str) -> Result<String, String> {
+        use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+
+        let url = self.api_url(&format!("git/blobs/{}", sha));
+        let resp = self
+            .client
+            .get(&url)
+            .headers(self.headers())
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch blob: {}", e))?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(format!("Fetch blob failed ({}): {}", status, body));
+        }
+
+        let blob: BlobContentResponse = resp
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse blob response: {}", e))?;
+        let bytes = match blob.encoding.as_str() {
+            "base64" => {
+                let packed: String = blob.content.split_whitespace().collect();
+                BASE64
+                    .decode(packed)
+                    .map_err(|e| format!("Invalid blob encoding: {}", e))?
+            }
+            _ => blob.content.into_bytes(),
+        };
+        String::from_utf8(bytes).map_err(|e| format!("Blob {} is not UTF-8: {}", sha, e))
+    }
+
+    pub async fn pull_request_state(&self, number: u64) -> Result<PullRequestState, String> {
+        let url = self.api_url(&format!("pulls/{}", number));
+        let resp = self
+            .client
+            .get(&url)
+            .headers(self.headers())
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch PR: {}", e))?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(format!("Fetch PR failed ({}): {}", status, body));
+        }
+
+        let pr: PRStateResponse = resp
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse PR response: {}", e))?;
+        Ok(PullRequestState {
+            number: pr.number,
+            url: pr.html_url,
+            state: pr.state,
+            merged: pr.merged,
+            mergeable: pr.mergeable,
+        })
+    }
 }
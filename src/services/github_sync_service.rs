@@ -12,15 +12,18 @@ use crate::adapters::oxigraph_ontology_repository::{OxigraphOntologyRepository,
 use crate::adapters::whelk_inference_engine::WhelkInferenceEngine;
 use crate::adapters::SqliteSettingsRepository;
 use visionclaw_domain::models::canonical_entity::{CanonicalEntity, EntityKind, OutboundLink};
-use visionclaw_domain::models::edge::Edge;
+use visionclaw_domain::models::edge::{Edge, EdgeProvenance, ProvenanceSource};
 use visionclaw_domain::ports::inference_engine::InferenceEngine;
 use crate::ports::knowledge_graph_repository::KnowledgeGraphRepository;
 use visionclaw_domain::ports::ontology_repository::{AxiomType, OntologyRepository, OwlAxiom};
 use crate::services::github::content_enhanced::EnhancedContentAPI;
+use crate::services::github::object_store::object_store;
 use crate::services::github::types::GitHubFileBasicMetadata;
 use crate::services::jsonld_ingest::{self, IngestOutcome, PageMetadata};
-use crate::services::parsers::KnowledgeGraphParser;
+use crate::services::parsers::content_parser::document_to_graph;
+use crate::services::parsers::{content_parsers, ContentParser, KnowledgeGraphParser};
 use crate::services::semantic_type_registry::SEMANTIC_TYPE_REGISTRY;
+use crate::utils::transliteration::prepare_label;
 use futures::stream::{FuturesUnordered, StreamExt};
 use log::{debug, error, info, warn};
 use oxigraph::model::{Quad, Subject};
@@ -119,6 +122,7 @@ fn build_node_from_entity(
     node.id = id;
     node.metadata_id = entity.slug.clone();
     node.label = entity.display_label().to_string();
+    prepare_label(&mut node);
     node.node_type = Some(entity.kind.as_node_type().to_string());
     if matches!(
         entity.kind,
@@ -182,6 +186,7 @@ fn ensure_stub_from_link(
     } else {
         link.target_label.clone()
     };
+    prepare_label(&mut node);
     node.node_type = Some(node_type.to_string());
     node.metadata.insert("type".to_string(), node_type.to_string());
     if matches!(kind, OwlKind::Class | OwlKind::Individual) {
@@ -210,6 +215,7 @@ fn ensure_stub_from_iri(
     node.id = id;
     node.metadata_id = local_name.to_string();
     node.label = local_name.replace('-', " ");
+    prepare_label(&mut node);
     node.node_type = Some(node_type.to_string());
     node.metadata.insert("type".to_string(), node_type.to_string());
     if matches!(kind, OwlKind::Class | OwlKind::Individual) {
@@ -301,7 +307,25 @@ impl GitHubSyncService {
 
         stats.total_files = files.len();
 
+        // Files synced before but absent from this listing (deleted upstream,
+        // or not present on a newly selected ref) only leave the graph through
+        // a full rebuild; the incremental path never visits them.
+        let removed_files = match self.files_missing_from_listing(&files).await {
+            Ok(removed) => removed,
+            Err(e) => {
+                warn!("Failed to compare against previous file list: {}", e);
+                Vec::new()
+            }
+        };
+        if !removed_files.is_empty() {
+            info!(
+                "{} previously synced files no longer present — pruning via full sync",
+                removed_files.len()
+            );
+        }
+
         let force_full_sync = base_path_changed
+            || !removed_files.is_empty()
             || std::env::var("FORCE_FULL_SYNC")
                 .map(|v| v == "1" || v.to_lowercase() == "true")
                 .unwrap_or(false);
@@ -375,13 +399,107 @@ impl GitHubSyncService {
             }
         }
 
+        self.finish_graph_build(deferred_edges, &mut stats).await;
+
+        if let Err(e) = self.update_file_metadata(&all_files_to_process).await {
+            warn!("Failed to update file_metadata: {}", e);
+        }
+        if let Err(e) = self.sync_db.delete_file_sha1s(&removed_files).await {
+            warn!("Failed to drop file_metadata for removed files: {}", e);
+        }
+
+        let listing = files.iter().map(|f| (f.path.clone(), f.sha.clone())).collect();
+        match object_store().record_manifest(listing) {
+            Ok(manifest) => {
+                for rename in &manifest.renames {
+                    info!("Renamed: {} -> {}", rename.from, rename.to);
+                }
+            }
+            Err(e) => warn!("Failed to record sync manifest: {}", e),
+        }
+        match tokio::task::spawn_blocking(|| object_store().sweep()).await {
+            Ok(report) if report.removed > 0 => info!(
+                "Object store sweep removed {} unreferenced blobs ({} bytes)",
+                report.removed, report.bytes_freed
+            ),
+            Ok(_) => {}
+            Err(e) => warn!("Object store sweep failed: {}", e),
+        }
+
+        stats.duration = start_time.elapsed();
+        info!(
+            "Sync complete: {} nodes, {} edges in {:?}",
+            stats.total_nodes, stats.total_edges, stats.duration
+        );
+
+        Ok(stats)
+    }
+
+    /// Rebuild the graph from files already in hand (e.g. an uploaded vault)
+    /// instead of GitHub. Every file is ingested exactly as a full sync would;
+    /// with `replace` the graph is cleared first, otherwise the files are
+    /// merged into it.
+    pub async fn rebuild_from_files(
+        &self,
+        files: Vec<(GitHubFileBasicMetadata, String)>,
+        replace: bool,
+    ) -> Result<SyncStatistics, String> {
+        info!("Rebuilding graph from {} local files", files.len());
+        let start_time = Instant::now();
+        let mut stats = SyncStatistics {
+            total_files: files.len(),
+            kg_files_processed: 0,
+            ontology_files_processed: 0,
+            skipped_files: 0,
+            errors: Vec::new(),
+            duration: Duration::from_secs(0),
+            total_nodes: 0,
+            total_edges: 0,
+        };
+
+        if replace {
+            self.kg_repo
+                .clear_graph()
+                .await
+                .map_err(|e| format!("clear_graph: {}", e))?;
+        }
+
+        let mut deferred_edges: Vec<Edge> = Vec::new();
+        let mut files = files.into_iter().peekable();
+        let mut batch_idx = 0;
+        while files.peek().is_some() {
+            batch_idx += 1;
+            let batch: Vec<_> = files
+                .by_ref()
+                .take(BATCH_SIZE)
+                .map(|(file, content)| (file, Ok(content)))
+                .collect();
+            if let Err(e) = self.ingest_batch(batch, &mut stats, &mut deferred_edges).await {
+                error!("Batch {} failed: {}", batch_idx, e);
+                stats.errors.push(format!("Batch {}: {}", batch_idx, e));
+            }
+        }
+
+        self.finish_graph_build(deferred_edges, &mut stats).await;
+        stats.duration = start_time.elapsed();
+        info!(
+            "Local rebuild complete: {} nodes, {} edges in {:?}",
+            stats.total_nodes, stats.total_edges, stats.duration
+        );
+        Ok(stats)
+    }
+
+    /// Steps shared by every graph build once all batches are in: deferred
+    /// bridge edges, domain roots, stub folding and reasoning. Failures are
+    /// recorded in `stats` and never abort the build.
+    async fn finish_graph_build(&self, deferred_edges: Vec<Edge>, stats: &mut SyncStatistics) {
         // Final pass: write all deferred bridge edges now that every node is present.
         if !deferred_edges.is_empty() {
             info!(
                 "Writing {} deferred bridge edges (final pass)",
                 deferred_edges.len()
             );
-            match self.kg_repo.batch_add_edges(deferred_edges.clone()).await {
+            match self.kg_repo.batch_add_edges(deferred_edges).await {
                 Ok(ids) => {
                     info!("Successfully wrote {} bridge edges", ids.len());
                     stats.total_edges += ids.len();
@@ -394,7 +512,7 @@ impl GitHubSyncService {
         }
 
         // Materialise domain root nodes and hierarchical edges to members.
-        match self.materialise_domain_roots(&mut stats).await {
+        match self.materialise_domain_roots(stats).await {
             Ok(n) => info!("Materialised {} domain root nodes with edges", n),
             Err(e) => {
                 warn!("Domain root materialisation failed (non-fatal): {}", e);
@@ -403,7 +521,7 @@ impl GitHubSyncService {
         }
 
         // Post-sync: fold low-fan-out wikilink stubs into weights + springs.
-        match self.fold_low_fanout_stubs(&mut stats).await {
+        match self.fold_low_fanout_stubs(stats).await {
             Ok(n) => info!("Folded {} low-fan-out linked_page stub nodes", n),
             Err(e) => {
                 warn!("Low-fan-out stub fold failed (non-fatal): {}", e);
@@ -412,25 +530,13 @@ impl GitHubSyncService {
         }
 
         // Post-sync: run Whelk EL++ reasoning over the full ontology graph.
-        match self.run_post_sync_reasoning(&mut stats).await {
+        match self.run_post_sync_reasoning(stats).await {
             Ok(inferred) => info!("Post-sync reasoning produced {} inferred edges", inferred),
             Err(e) => {
                 warn!("Post-sync reasoning failed (non-fatal): {}", e);
                 stats.errors.push(format!("reasoning: {}", e));
             }
         }
-
-        if let Err(e) = self.update_file_metadata(&all_files_to_process).await {
-            warn!("Failed to update file_metadata: {}", e);
-        }
-
-        stats.duration = start_time.elapsed();
-        info!(
-            "Sync complete: {} nodes, {} edges in {:?}",
-            stats.total_nodes, stats.total_edges, stats.duration
-        );
-
-        Ok(stats)
     }
 
     /// Post-sync: fold low-fan-out wikilink stubs out of the rendered graph.
@@ -571,6 +677,7 @@ impl GitHubSyncService {
                     edge_type: Some("co_citation".to_string()),
                     owl_property_iri: None,
                     metadata: None,
+                    provenance: Some(EdgeProvenance::from_source(ProvenanceSource::CoCitation, w)),
                 })
                 .collect();
             match self.kg_repo.batch_add_edges(cocite_edges).await {
@@ -696,6 +803,10 @@ impl GitHubSyncService {
                         edge_type: Some("hierarchical".to_string()),
                         owl_property_iri: None,
                         metadata: None,
+                        provenance: Some(EdgeProvenance::from_source(
+                            ProvenanceSource::Hierarchy,
+                            1.5,
+                        )),
                     };
                     domain_edges.push(edge);
                 }
@@ -832,6 +943,10 @@ impl GitHubSyncService {
                         edge_type: Some("inferred".to_string()),
                         owl_property_iri: None,
                         metadata: Some(edge_meta),
+                        provenance: Some(EdgeProvenance::from_source(
+                            ProvenanceSource::Inferred,
+                            0.4,
+                        )),
                     };
                     inferred_edges.push(edge);
                 }
@@ -1008,10 +1123,6 @@ impl GitHubSyncService {
         stats: &mut SyncStatistics,
         deferred_edges: &mut Vec<Edge>,
     ) -> Result<(), String> {
-        let mut batch_nodes = std::collections::HashMap::new();
-        let mut batch_edges = std::collections::HashMap::new();
-        let mut public_pages = std::collections::HashSet::new();
-
         const PARALLEL_FETCHES: usize = 8;
 
         fn create_fetch_future(
@@ -1025,10 +1136,23 @@ impl GitHubSyncService {
         > {
             let download_url = file.download_url.clone();
             Box::pin(async move {
+                // Unchanged blobs are served from the object store by SHA.
+                if let Some(content) = object_store().get(&file.sha) {
+                    return (file, Ok(content));
+                }
                 let result = content_api
                     .fetch_file_content(&download_url)
                     .await
                     .map_err(|e| format!("Failed to fetch content: {}", e));
+                if let Ok(content) = &result {
+                    match object_store().put(content) {
+                        Ok(sha) if sha != file.sha => {
+                            debug!("{} stored as {} (listed as {})", file.path, sha, file.sha)
+                        }
+                        Ok(_) => {}
+                        Err(e) => warn!("{}", e),
+                    }
+                }
                 (file, result)
             })
         }
@@ -1053,12 +1177,28 @@ impl GitHubSyncService {
             }
         }
 
+        self.ingest_batch(fetched_contents, stats, deferred_edges).await
+    }
+
+    /// Parse a batch of already-fetched files and write its nodes and
+    /// same-batch edges; cross-batch edges go to `deferred_edges`.
+    async fn ingest_batch(
+        &self,
+        fetched_contents: Vec<(GitHubFileBasicMetadata, Result<String, String>)>,
+        stats: &mut SyncStatistics,
+        deferred_edges: &mut Vec<Edge>,
+    ) -> Result<(), String> {
+        let mut batch_nodes = std::collections::HashMap::new();
+        let mut batch_edges = std::collections::HashMap::new();
+        let mut public_pages = std::collections::HashSet::new();
+        let batch_len = fetched_contents.len();
+
         for (idx, (file, content_result)) in fetched_contents.into_iter().enumerate() {
             if idx % 10 == 0 && idx > 0 {
                 info!(
                     "  Progress: {}/{} files (nodes: {}, edges: {})",
                     idx,
-                    files.len(),
+                    batch_len,
                     batch_nodes.len(),
                     batch_edges.len()
                 );
@@ -1176,6 +1316,11 @@ impl GitHubSyncService {
     ) -> Result<(), String> {
         debug!("Processing file: {} ({} bytes)", file.name, content.len());
 
+        if let Some(parser) = content_parsers().for_path(&file.path) {
+            self.process_plugin_file(parser.as_ref(), file, content, nodes, edges);
+            return Ok(());
+        }
+
         // 1. Distill the file's JSON-LD blocks into a single canonical entity.
         let entity = match jsonld_ingest::parse_canonical_entity(content, &file.path) {
             Ok(Some(e)) => e,
@@ -1220,15 +1365,27 @@ impl GitHubSyncService {
             }
             ensure_stub_from_link(target_id, link, nodes);
             let edge_id = format!("{}_{}_wikilink", source_id, target_id);
-            edges.entry(edge_id.clone()).or_insert_with(|| Edge {
-                id: edge_id,
-                source: source_id,
-                target: target_id,
-                weight: 1.0,
-                edge_type: Some("explicit_link".to_string()),
-                metadata: None,
-                owl_property_iri: None,
-            });
+            edges
+                .entry(edge_id.clone())
+                .and_modify(|edge| {
+                    if let Some(p) = edge.provenance.as_mut() {
+                        p.add(ProvenanceSource::Wikilink, 0.0, Some(1), None);
+                    }
+                })
+                .or_insert_with(|| {
+                    let mut provenance = EdgeProvenance::default();
+                    provenance.add(ProvenanceSource::Wikilink, 1.0, Some(1), None);
+                    Edge {
+                        id: edge_id,
+                        source: source_id,
+                        target: target_id,
+                        weight: 1.0,
+                        edge_type: Some("explicit_link".to_string()),
+                        metadata: None,
+                        owl_property_iri: None,
+                        provenance: Some(provenance),
+                    }
+                });
         }
 
         // 4. Run the full JSON-LD ingest to (a) emit typed semantic edges from
@@ -1339,6 +1496,39 @@ impl GitHubSyncService {
         }
     }
 
+    /// Ingest a non-markdown file through its registered [`ContentParser`].
+    /// Follows the plain-page rules: unpublished documents are skipped and
+    /// the page never clobbers a node another file already emitted.
+    fn process_plugin_file(
+        &self,
+        parser: &dyn ContentParser,
+        file: &GitHubFileBasicMetadata,
+        content: &str,
+        nodes: &mut std::collections::HashMap<u32, visionclaw_domain::models::node::Node>,
+        edges: &mut std::collections::HashMap<String, Edge>,
+    ) {
+        let doc = match parser.parse(content, &file.name) {
+            Ok(doc) => doc,
+            Err(e) => {
+                debug!("{} parse failed for {}: {} — skipping", parser.name(), file.name, e);
+                return;
+            }
+        };
+        if !doc.public {
+            debug!("Skipped unpublished {} document: {}", parser.name(), file.name);
+            return;
+        }
+
+        let parsed = document_to_graph(&doc, parser.name(), &file.name, content.len(), &self.kg_parser);
+        for mut node in parsed.nodes {
+            ensure_source_domain(&mut node, &file.path);
+            nodes.entry(node.id).or_insert(node);
+        }
+        for edge in parsed.edges {
+            edges.entry(edge.id.clone()).or_insert(edge);
+        }
+    }
+
     /// Map an IngestOutcome's quads to Edge structs for the force-directed graph.
     ///
     /// Only object-property triples with named-node objects produce graph edges.
@@ -1406,6 +1596,16 @@ impl GitHubSyncService {
                 edge_type: Some(edge_type.to_string()),
                 owl_property_iri: Some(predicate_iri.to_string()),
                 metadata: Some(edge_meta),
+                provenance: Some({
+                    let mut p = EdgeProvenance::default();
+                    p.add(
+                        ProvenanceSource::Ontology,
+                        weight,
+                        None,
+                        Some(predicate_iri.to_string()),
+                    );
+                    p
+                }),
             };
             result.push(edge);
         }
@@ -1564,19 +1764,27 @@ impl GitHubSyncService {
     // ------------------------------------------------------------------
 
     async fn fetch_all_markdown_files(&self) -> Result<Vec<GitHubFileBasicMetadata>, String> {
-        match self.content_api.list_markdown_files_via_tree().await {
+        let mut files = match self.content_api.list_markdown_files_via_tree().await {
             Ok(files) => {
                 info!("Trees API returned {} markdown files", files.len());
-                Ok(files)
+                files
             }
             Err(e) => {
                 warn!("Trees API failed ({}), falling back to Contents API", e);
                 self.content_api
-                    .list_markdown_files("")
+                    .list_markdown_files_in_sources()
                     .await
-                    .map_err(|e| format!("GitHub API error: {}", e))
+                    .map_err(|e| format!("GitHub API error: {}", e))?
             }
+        };
+        // Nothing outside the configured source paths is fetched or turned
+        // into metadata, whichever listing produced it.
+        let listed = files.len();
+        files.retain(|f| self.content_api.in_sources(&f.path));
+        if files.len() < listed {
+            warn!("Skipped {} file(s) outside configured source paths", listed - files.len());
         }
+        Ok(files)
     }
 
     async fn filter_changed_files(
@@ -1595,6 +1803,21 @@ impl GitHubSyncService {
             .collect())
     }
 
+    /// Names of files recorded by a previous sync that are missing from
+    /// `files`.
+    async fn files_missing_from_listing(
+        &self,
+        files: &[GitHubFileBasicMetadata],
+    ) -> Result<Vec<String>, String> {
+        let existing = self.get_existing_file_metadata().await?;
+        let present: std::collections::HashSet<&str> =
+            files.iter().map(|f| f.name.as_str()).collect();
+        Ok(existing
+            .into_keys()
+            .filter(|name| !present.contains(name.as_str()))
+            .collect())
+    }
+
     // ------------------------------------------------------------------
     // SHA1 / SyncConfig persistence via SQLite
     // ------------------------------------------------------------------
@@ -0,0 +1,912 @@
+//! Small declarative graph query language (a Cypher subset).
+//!
+//! ```text
+//! MATCH (a:page {tag: "rust"})-[r:explicit_link*1..2]->(b)
+//! WHERE r.weight >= 0.5 AND b.label CONTAINS "async"
+//! RETURN a.label, b, hops
+//! LIMIT 50
+//! ```
+//!
+//! A query matches a start node pattern and optionally traverses up to N hops
+//! to an end node pattern. Patterns filter on the node type (`:page`) and on
+//! properties (`{label: "X"}`); `WHERE` adds comparisons (`= != < <= > >=
+//! CONTAINS`) on node properties or, for the relationship variable, on each
+//! traversed edge's `weight`/`type`. Traversal runs as BFS over a CSR
+//! adjacency built from the current [`GraphData`]; a node matches a hop range
+//! when its shortest qualifying distance from the start lies inside it.
+//!
+//! Node properties: `id`, `label`, `metadataId`, `type`, `group`, `tag` (any
+//! of the page's tags), plus any raw metadata key.
+
+use serde_json::{Map, Value};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+use crate::protocol::graph_json::{JsonSchema, NodeSummaryJson};
+use visionclaw_domain::models::edge::Edge;
+use visionclaw_domain::models::graph::GraphData;
+use visionclaw_domain::models::node::Node;
+
+pub const DEFAULT_LIMIT: usize = 100;
+pub const MAX_LIMIT: usize = 10_000;
+pub const MAX_HOPS: u32 = 6;
+/// Longest query text accepted, in bytes.
+pub const MAX_QUERY_LEN: usize = 4096;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryError {
+    pub message: String,
+    /// Byte offset into the query text, for parse errors.
+    pub position: Option<usize>,
+}
+
+impl QueryError {
+    fn at(position: usize, message: impl Into<String>) -> Self {
+        Self { message: message.into(), position: Some(position) }
+    }
+
+    fn invalid(message: impl Into<String>) -> Self {
+        Self { message: message.into(), position: None }
+    }
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.position {
+            Some(p) => write!(f, "{} (at offset {})", self.message, p),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+// ---------------------------------------------------------------------------
+// AST
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    Str(String),
+    Num(f64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Outgoing,
+    Incoming,
+    Both,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodePattern {
+    pub var: String,
+    pub node_type: Option<String>,
+    pub props: Vec<(String, Literal)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelPattern {
+    pub var: Option<String>,
+    pub edge_type: Option<String>,
+    pub min_hops: u32,
+    pub max_hops: u32,
+    pub direction: Direction,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    pub var: String,
+    pub prop: String,
+    pub op: CompareOp,
+    pub value: Literal,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Projection {
+    /// `a` — a compact summary of the node.
+    Node(String),
+    /// `a.label`
+    Property(String, String),
+    /// `hops` — distance between start and end node.
+    Hops,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    pub start: NodePattern,
+    pub hop: Option<(RelPattern, NodePattern)>,
+    pub conditions: Vec<Condition>,
+    pub returns: Vec<Projection>,
+    pub limit: usize,
+}
+
+// ---------------------------------------------------------------------------
+// Lexer
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Ident(String),
+    Str(String),
+    Num(f64),
+    Sym(&'static str),
+}
+
+const SYMBOLS: &[&str] = &[
+    "<-", "->", "..", "!=", "<=", ">=", "(", ")", "[", "]", "{", "}", ":", ",", ".", "*", "-",
+    "=", "<", ">",
+];
+
+fn tokenize(src: &str) -> Result<Vec<(Tok, usize)>, QueryError> {
+    if src.len() > MAX_QUERY_LEN {
+        return Err(QueryError::invalid(format!(
+            "query is longer than {} bytes",
+            MAX_QUERY_LEN
+        )));
+    }
+    let mut out = Vec::new();
+    let mut chars = src.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        if c == '"' || c == '\'' {
+            let quote = c;
+            chars.next();
+            let mut s = String::new();
+            loop {
+                let (_, ch) = chars
+                    .next()
+                    .ok_or_else(|| QueryError::at(start, "unterminated string"))?;
+                match ch {
+                    '\\' => {
+                        let (_, esc) = chars
+                            .next()
+                            .ok_or_else(|| QueryError::at(start, "unterminated string"))?;
+                        s.push(esc);
+                    }
+                    ch if ch == quote => break,
+                    ch => s.push(ch),
+                }
+            }
+            out.push((Tok::Str(s), start));
+        } else if c.is_ascii_digit() {
+            let mut end = start;
+            while let Some(&(i, d)) = chars.peek().filter(|(_, d)| d.is_ascii_digit()) {
+                end = i + d.len_utf8();
+                chars.next();
+            }
+            // A '.' followed by a digit is a decimal point; '..' is a range.
+            let mut ahead = chars.clone();
+            if matches!(ahead.next(), Some((_, '.')))
+                && ahead.next().is_some_and(|(_, d)| d.is_ascii_digit())
+            {
+                chars.next();
+                while let Some(&(i, d)) = chars.peek().filter(|(_, d)| d.is_ascii_digit()) {
+                    end = i + d.len_utf8();
+                    chars.next();
+                }
+            }
+            let n = src[start..end]
+                .parse::<f64>()
+                .map_err(|_| QueryError::at(start, "invalid number"))?;
+            out.push((Tok::Num(n), start));
+        } else if c.is_alphabetic() || c == '_' {
+            let mut end = start;
+            while let Some(&(i, ch)) = chars
+                .peek()
+                .filter(|(_, ch)| ch.is_alphanumeric() || *ch == '_')
+            {
+                end = i + ch.len_utf8();
+                chars.next();
+            }
+            if end == start {
+                return Err(QueryError::at(start, "expected an identifier"));
+            }
+            out.push((Tok::Ident(src[start..end].to_string()), start));
+        } else if let Some(sym) = SYMBOLS.iter().find(|s| src[start..].starts_with(**s)) {
+            // Symbols are ASCII: one char per byte.
+            for _ in 0..sym.len() {
+                chars.next();
+            }
+            out.push((Tok::Sym(sym), start));
+        } else {
+            return Err(QueryError::at(start, format!("unexpected character '{}'", c)));
+        }
+    }
+    Ok(out)
+}
+
+// ---------------------------------------------------------------------------
+// Parser
+// ---------------------------------------------------------------------------
+
+struct Parser {
+    toks: Vec<(Tok, usize)>,
+    pos: usize,
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Tok> {
+        self.toks.get(self.pos).map(|(t, _)| t)
+    }
+
+    fn offset(&self) -> usize {
+        self.toks.get(self.pos).map(|(_, o)| *o).unwrap_or(self.end)
+    }
+
+    fn advance(&mut self) -> Option<Tok> {
+        let t = self.toks.get(self.pos).map(|(t, _)| t.clone());
+        self.pos += 1;
+        t
+    }
+
+    fn is_sym(&self, sym: &str) -> bool {
+        matches!(self.peek(), Some(Tok::Sym(s)) if *s == sym)
+    }
+
+    fn is_keyword(&self, kw: &str) -> bool {
+        matches!(self.peek(), Some(Tok::Ident(s)) if s.eq_ignore_ascii_case(kw))
+    }
+
+    fn eat_sym(&mut self, sym: &str) -> bool {
+        if self.is_sym(sym) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect_sym(&mut self, sym: &str) -> Result<(), QueryError> {
+        if self.eat_sym(sym) {
+            Ok(())
+        } else {
+            Err(QueryError::at(self.offset(), format!("expected '{}'", sym)))
+        }
+    }
+
+    fn expect_keyword(&mut self, kw: &str) -> Result<(), QueryError> {
+        if self.is_keyword(kw) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(QueryError::at(self.offset(), format!("expected {}", kw)))
+        }
+    }
+
+    fn ident(&mut self) -> Result<String, QueryError> {
+        let at = self.offset();
+        match self.advance() {
+            Some(Tok::Ident(s)) => Ok(s),
+            _ => Err(QueryError::at(at, "expected identifier")),
+        }
+    }
+
+    fn literal(&mut self) -> Result<Literal, QueryError> {
+        let at = self.offset();
+        let negative = self.eat_sym("-");
+        match self.advance() {
+            Some(Tok::Num(n)) => Ok(Literal::Num(if negative { -n } else { n })),
+            Some(Tok::Str(s)) if !negative => Ok(Literal::Str(s)),
+            // Bare words are accepted as strings: {type: page}
+            Some(Tok::Ident(s)) if !negative => Ok(Literal::Str(s)),
+            _ => Err(QueryError::at(at, "expected a string or number")),
+        }
+    }
+
+    fn hop_count(&mut self) -> Result<Option<u32>, QueryError> {
+        let at = self.offset();
+        match self.peek() {
+            Some(Tok::Num(n)) => {
+                let n = *n;
+                self.pos += 1;
+                if n.fract() != 0.0 || n < 0.0 {
+                    return Err(QueryError::at(at, "hop count must be a whole number"));
+                }
+                Ok(Some(n as u32))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn node_pattern(&mut self) -> Result<NodePattern, QueryError> {
+        self.expect_sym("(")?;
+        let var = self.ident()?;
+        let node_type = if self.eat_sym(":") { Some(self.ident()?) } else { None };
+        let mut props = Vec::new();
+        if self.eat_sym("{") {
+            loop {
+                let key = self.ident()?;
+                self.expect_sym(":")?;
+                props.push((key, self.literal()?));
+                if !self.eat_sym(",") {
+                    break;
+                }
+            }
+            self.expect_sym("}")?;
+        }
+        self.expect_sym(")")?;
+        Ok(NodePattern { var, node_type, props })
+    }
+
+    fn rel_pattern(&mut self) -> Result<RelPattern, QueryError> {
+        let incoming = if self.eat_sym("<-") {
+            true
+        } else {
+            self.expect_sym("-")?;
+            false
+        };
+        let mut rel = RelPattern {
+            var: None,
+            edge_type: None,
+            min_hops: 1,
+            max_hops: 1,
+            direction: Direction::Both,
+        };
+        if self.eat_sym("[") {
+            if let Some(Tok::Ident(_)) = self.peek() {
+                rel.var = Some(self.ident()?);
+            }
+            if self.eat_sym(":") {
+                rel.edge_type = Some(self.ident()?);
+            }
+            if self.eat_sym("*") {
+                let at = self.offset();
+                let min = self.hop_count()?;
+                if self.eat_sym("..") {
+                    rel.min_hops = min.unwrap_or(1);
+                    rel.max_hops = self.hop_count()?.unwrap_or(MAX_HOPS);
+                } else if let Some(n) = min {
+                    rel.min_hops = n;
+                    rel.max_hops = n;
+                } else {
+                    rel.max_hops = MAX_HOPS;
+                }
+                if rel.min_hops > rel.max_hops {
+                    return Err(QueryError::at(at, "hop range minimum exceeds maximum"));
+                }
+                if rel.max_hops > MAX_HOPS {
+                    return Err(QueryError::at(at, format!("at most {} hops are allowed", MAX_HOPS)));
+                }
+            }
+            self.expect_sym("]")?;
+        }
+        let outgoing = if self.eat_sym("->") {
+            true
+        } else {
+            self.expect_sym("-")?;
+            false
+        };
+        rel.direction = match (incoming, outgoing) {
+            (false, true) => Direction::Outgoing,
+            (true, false) => Direction::Incoming,
+            (false, false) => Direction::Both,
+            (true, true) => {
+                return Err(QueryError::at(self.offset(), "relationship cannot point both ways"))
+            }
+        };
+        Ok(rel)
+    }
+
+    fn compare_op(&mut self) -> Result<CompareOp, QueryError> {
+        let at = self.offset();
+        if self.is_keyword("CONTAINS") {
+            self.pos += 1;
+            return Ok(CompareOp::Contains);
+        }
+        let op = match self.advance() {
+            Some(Tok::Sym("=")) => CompareOp::Eq,
+            Some(Tok::Sym("!=")) => CompareOp::Ne,
+            Some(Tok::Sym("<")) => CompareOp::Lt,
+            Some(Tok::Sym("<=")) => CompareOp::Le,
+            Some(Tok::Sym(">")) => CompareOp::Gt,
+            Some(Tok::Sym(">=")) => CompareOp::Ge,
+            _ => return Err(QueryError::at(at, "expected comparison operator")),
+        };
+        Ok(op)
+    }
+
+    fn condition(&mut self) -> Result<Condition, QueryError> {
+        let var = self.ident()?;
+        self.expect_sym(".")?;
+        let prop = self.ident()?;
+        let op = self.compare_op()?;
+        let value = self.literal()?;
+        Ok(Condition { var, prop, op, value })
+    }
+
+    fn projection(&mut self) -> Result<Projection, QueryError> {
+        let name = self.ident()?;
+        if self.eat_sym(".") {
+            Ok(Projection::Property(name, self.ident()?))
+        } else if name.eq_ignore_ascii_case("hops") {
+            Ok(Projection::Hops)
+        } else {
+            Ok(Projection::Node(name))
+        }
+    }
+
+    fn query(&mut self) -> Result<Query, QueryError> {
+        self.expect_keyword("MATCH")?;
+        let start = self.node_pattern()?;
+        let hop = if self.is_sym("-") || self.is_sym("<-") {
+            let rel = self.rel_pattern()?;
+            Some((rel, self.node_pattern()?))
+        } else {
+            None
+        };
+
+        let mut conditions = Vec::new();
+        if self.is_keyword("WHERE") {
+            self.pos += 1;
+            loop {
+                conditions.push(self.condition()?);
+                if !self.is_keyword("AND") {
+                    break;
+                }
+                self.pos += 1;
+            }
+        }
+
+        self.expect_keyword("RETURN")?;
+        let mut returns = vec![self.projection()?];
+        while self.eat_sym(",") {
+            returns.push(self.projection()?);
+        }
+
+        let mut limit = DEFAULT_LIMIT;
+        if self.is_keyword("LIMIT") {
+            self.pos += 1;
+            let at = self.offset();
+            limit = match self.advance() {
+                Some(Tok::Num(n)) if n >= 1.0 && n.fract() == 0.0 => (n as usize).min(MAX_LIMIT),
+                _ => return Err(QueryError::at(at, "LIMIT expects a positive integer")),
+            };
+        }
+
+        if self.pos < self.toks.len() {
+            return Err(QueryError::at(self.offset(), "unexpected trailing input"));
+        }
+        Ok(Query { start, hop, conditions, returns, limit })
+    }
+}
+
+/// Parse and validate a query string.
+pub fn parse_query(src: &str) -> Result<Query, QueryError> {
+    let toks = tokenize(src)?;
+    let mut parser = Parser { toks, pos: 0, end: src.len() };
+    let query = parser.query()?;
+    validate(&query)?;
+    Ok(query)
+}
+
+fn validate(query: &Query) -> Result<(), QueryError> {
+    let end_var = query.hop.as_ref().map(|(_, n)| n.var.as_str());
+    let rel_var = query.hop.as_ref().and_then(|(r, _)| r.var.as_deref());
+    if end_var == Some(query.start.var.as_str()) {
+        return Err(QueryError::invalid("start and end nodes need distinct variables"));
+    }
+    let is_node_var = |v: &str| v == query.start.var || Some(v) == end_var;
+
+    for c in &query.conditions {
+        if Some(c.var.as_str()) == rel_var {
+            if !matches!(c.prop.as_str(), "weight" | "type") {
+                return Err(QueryError::invalid(format!(
+                    "relationship property '{}' is not supported (use weight or type)",
+                    c.prop
+                )));
+            }
+        } else if !is_node_var(&c.var) {
+            return Err(QueryError::invalid(format!("unknown variable '{}'", c.var)));
+        }
+    }
+    for p in &query.returns {
+        match p {
+            Projection::Node(v) | Projection::Property(v, _) if !is_node_var(v) => {
+                return Err(QueryError::invalid(format!("cannot return '{}'", v)));
+            }
+            Projection::Hops if query.hop.is_none() => {
+                return Err(QueryError::invalid("hops requires a relationship pattern"));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Execution
+// ---------------------------------------------------------------------------
+
+/// Host-side CSR adjacency over a graph snapshot. Each direction keeps its own
+/// offsets so incoming traversals don't scan the edge list.
+pub struct CsrGraph<'a> {
+    nodes: &'a [Node],
+    edges: &'a [Edge],
+    out_offsets: Vec<usize>,
+    /// (neighbour node index, edge index)
+    out_adj: Vec<(usize, usize)>,
+    in_offsets: Vec<usize>,
+    in_adj: Vec<(usize, usize)>,
+}
+
+impl<'a> CsrGraph<'a> {
+    pub fn build(graph: &'a GraphData) -> Self {
+        let index: HashMap<u32, usize> =
+            graph.nodes.iter().enumerate().map(|(i, n)| (n.id, i)).collect();
+        let n = graph.nodes.len();
+        let mut out_lists: Vec<Vec<(usize, usize)>> = vec![Vec::new(); n];
+        let mut in_lists: Vec<Vec<(usize, usize)>> = vec![Vec::new(); n];
+        for (ei, e) in graph.edges.iter().enumerate() {
+            if let (Some(&s), Some(&t)) = (index.get(&e.source), index.get(&e.target)) {
+                out_lists[s].push((t, ei));
+                in_lists[t].push((s, ei));
+            }
+        }
+        let (out_offsets, out_adj) = flatten(out_lists);
+        let (in_offsets, in_adj) = flatten(in_lists);
+        Self { nodes: &graph.nodes, edges: &graph.edges, out_offsets, out_adj, in_offsets, in_adj }
+    }
+
+    fn neighbours(&self, node: usize, dir: Direction) -> impl Iterator<Item = &(usize, usize)> {
+        let out: &[(usize, usize)] = match dir {
+            Direction::Outgoing | Direction::Both => {
+                &self.out_adj[self.out_offsets[node]..self.out_offsets[node + 1]]
+            }
+            Direction::Incoming => &[],
+        };
+        let inc: &[(usize, usize)] = match dir {
+            Direction::Incoming | Direction::Both => {
+                &self.in_adj[self.in_offsets[node]..self.in_offsets[node + 1]]
+            }
+            Direction::Outgoing => &[],
+        };
+        out.iter().chain(inc.iter())
+    }
+}
+
+fn flatten(lists: Vec<Vec<(usize, usize)>>) -> (Vec<usize>, Vec<(usize, usize)>) {
+    let mut offsets = Vec::with_capacity(lists.len() + 1);
+    let mut adj = Vec::new();
+    offsets.push(0);
+    for list in lists {
+        adj.extend(list);
+        offsets.push(adj.len());
+    }
+    (offsets, adj)
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Map<String, Value>>,
+    /// True when more rows matched than `limit` allowed.
+    pub truncated: bool,
+}
+
+fn node_property(node: &Node, prop: &str) -> Option<Value> {
+    match prop {
+        "id" => Some(Value::from(node.id)),
+        "label" => Some(Value::from(node.label.clone())),
+        "metadataId" | "metadata_id" => Some(Value::from(node.metadata_id.clone())),
+        "type" => node.node_type.clone().map(Value::from),
+        "group" => node.group.clone().map(Value::from),
+        "tag" | "tags" => node.metadata.get("tags").map(|t| {
+            Value::from(tags_of(t).map(str::to_string).collect::<Vec<_>>())
+        }),
+        other => node.metadata.get(other).cloned().map(Value::from),
+    }
+}
+
+fn tags_of(raw: &str) -> impl Iterator<Item = &str> {
+    raw.split(',').map(str::trim).filter(|t| !t.is_empty())
+}
+
+fn compare_str(actual: &str, op: CompareOp, expected: &Literal) -> bool {
+    match expected {
+        Literal::Num(n) => match actual.trim().parse::<f64>() {
+            Ok(a) => compare_num(a, op, *n),
+            Err(_) => op == CompareOp::Ne,
+        },
+        Literal::Str(s) => match op {
+            CompareOp::Eq => actual.eq_ignore_ascii_case(s),
+            CompareOp::Ne => !actual.eq_ignore_ascii_case(s),
+            CompareOp::Contains => actual.to_lowercase().contains(&s.to_lowercase()),
+            CompareOp::Lt => actual < s.as_str(),
+            CompareOp::Le => actual <= s.as_str(),
+            CompareOp::Gt => actual > s.as_str(),
+            CompareOp::Ge => actual >= s.as_str(),
+        },
+    }
+}
+
+fn compare_num(actual: f64, op: CompareOp, expected: f64) -> bool {
+    match op {
+        CompareOp::Eq => actual == expected,
+        CompareOp::Ne => actual != expected,
+        CompareOp::Lt => actual < expected,
+        CompareOp::Le => actual <= expected,
+        CompareOp::Gt => actual > expected,
+        CompareOp::Ge => actual >= expected,
+        CompareOp::Contains => false,
+    }
+}
+
+fn node_matches(node: &Node, prop: &str, op: CompareOp, value: &Literal) -> bool {
+    match prop {
+        "tag" | "tags" => {
+            let raw = node.metadata.get("tags").map(String::as_str).unwrap_or("");
+            let mut tags = tags_of(raw);
+            // `tag != x` means no tag equals x.
+            if op == CompareOp::Ne {
+                return tags.all(|t| compare_str(t, op, value));
+            }
+            tags.any(|t| compare_str(t, op, value))
+        }
+        "id" => compare_num(node.id as f64, op, match value {
+            Literal::Num(n) => *n,
+            Literal::Str(s) => match s.parse() {
+                Ok(n) => n,
+                Err(_) => return false,
+            },
+        }),
+        _ => match node_property(node, prop) {
+            Some(Value::String(s)) => compare_str(&s, op, value),
+            Some(other) => compare_str(&other.to_string(), op, value),
+            None => op == CompareOp::Ne,
+        },
+    }
+}
+
+fn pattern_matches(node: &Node, pattern: &NodePattern, conditions: &[Condition]) -> bool {
+    if let Some(t) = &pattern.node_type {
+        if !node.node_type.as_deref().is_some_and(|nt| nt.eq_ignore_ascii_case(t)) {
+            return false;
+        }
+    }
+    pattern.props.iter().all(|(k, v)| node_matches(node, k, CompareOp::Eq, v))
+        && conditions
+            .iter()
+            .filter(|c| c.var == pattern.var)
+            .all(|c| node_matches(node, &c.prop, c.op, &c.value))
+}
+
+fn edge_allowed(edge: &Edge, rel: &RelPattern, conditions: &[Condition]) -> bool {
+    if let Some(t) = &rel.edge_type {
+        if !edge.edge_type.as_deref().is_some_and(|et| et.eq_ignore_ascii_case(t)) {
+            return false;
+        }
+    }
+    let Some(var) = rel.var.as_deref() else {
+        return true;
+    };
+    conditions.iter().filter(|c| c.var == var).all(|c| match c.prop.as_str() {
+        "weight" => match &c.value {
+            Literal::Num(n) => compare_num(edge.weight as f64, c.op, *n),
+            Literal::Str(_) => false,
+        },
+        _ => compare_str(edge.edge_type.as_deref().unwrap_or(""), c.op, &c.value),
+    })
+}
+
+fn node_summary(node: &Node) -> Value {
+    JsonSchema::CURRENT.encode(&NodeSummaryJson::from(node))
+}
+
+fn column_name(p: &Projection) -> String {
+    match p {
+        Projection::Node(v) => v.clone(),
+        Projection::Property(v, prop) => format!("{}.{}", v, prop),
+        Projection::Hops => "hops".to_string(),
+    }
+}
+
+fn project(
+    query: &Query,
+    nodes: &[Node],
+    start: usize,
+    end: Option<(usize, u32)>,
+) -> Map<String, Value> {
+    let resolve = |var: &str| -> Option<&Node> {
+        if var == query.start.var {
+            Some(&nodes[start])
+        } else {
+            end.map(|(e, _)| &nodes[e])
+        }
+    };
+    let mut row = Map::new();
+    for p in &query.returns {
+        let value = match p {
+            Projection::Node(v) => resolve(v).map(node_summary).unwrap_or(Value::Null),
+            Projection::Property(v, prop) => {
+                resolve(v).and_then(|n| node_property(n, prop)).unwrap_or(Value::Null)
+            }
+            Projection::Hops => end.map(|(_, h)| Value::from(h)).unwrap_or(Value::Null),
+        };
+        row.insert(column_name(p), value);
+    }
+    row
+}
+
+/// Run a parsed query against a CSR snapshot.
+pub fn execute(query: &Query, graph: &CsrGraph<'_>) -> QueryResult {
+    let columns = query.returns.iter().map(column_name).collect();
+    let mut rows = Vec::new();
+    let mut truncated = false;
+
+    let starts = (0..graph.nodes.len())
+        .filter(|&i| pattern_matches(&graph.nodes[i], &query.start, &query.conditions));
+
+    'outer: for s in starts {
+        let Some((rel, end_pattern)) = &query.hop else {
+            if rows.len() == query.limit {
+                truncated = true;
+                break;
+            }
+            rows.push(project(query, graph.nodes, s, None));
+            continue;
+        };
+
+        // BFS up to max_hops; `dist` records the shortest qualifying distance.
+        let mut dist: HashMap<usize, u32> = HashMap::new();
+        let mut queue = VecDeque::new();
+        dist.insert(s, 0);
+        queue.push_back(s);
+        while let Some(u) = queue.pop_front() {
+            let d = dist[&u];
+            if d >= rel.max_hops {
+                continue;
+            }
+            for &(v, ei) in graph.neighbours(u, rel.direction) {
+                if dist.contains_key(&v) || !edge_allowed(&graph.edges[ei], rel, &query.conditions) {
+                    continue;
+                }
+                dist.insert(v, d + 1);
+                queue.push_back(v);
+            }
+        }
+
+        let mut reached: Vec<(usize, u32)> = dist
+            .into_iter()
+            .filter(|&(v, d)| v != s && d >= rel.min_hops && d <= rel.max_hops)
+            .filter(|&(v, _)| pattern_matches(&graph.nodes[v], end_pattern, &query.conditions))
+            .collect();
+        reached.sort_by_key(|&(v, d)| (d, v));
+
+        for (v, d) in reached {
+            if rows.len() == query.limit {
+                truncated = true;
+                break 'outer;
+            }
+            rows.push(project(query, graph.nodes, s, Some((v, d))));
+        }
+    }
+
+    QueryResult { columns, rows, truncated }
+}
+
+/// Parse `src` and run it against `graph`.
+pub fn run_query(src: &str, graph: &GraphData) -> Result<QueryResult, QueryError> {
+    let query = parse_query(src)?;
+    Ok(execute(&query, &CsrGraph::build(graph)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: u32, label: &str, node_type: &str, tags: &str) -> Node {
+        let mut n = Node::new_with_id(label.to_string(), Some(id));
+        n.label = label.to_string();
+        n.node_type = Some(node_type.to_string());
+        if !tags.is_empty() {
+            n.metadata.insert("tags".to_string(), tags.to_string());
+        }
+        n
+    }
+
+    fn sample() -> GraphData {
+        let mut g = GraphData::new();
+        g.nodes = vec![
+            node(1, "Rust", "page", "lang, systems"),
+            node(2, "Tokio", "page", "async"),
+            node(3, "Futures", "page", "async"),
+            node(4, "Go", "page", "lang"),
+        ];
+        g.edges = vec![
+            Edge::new(1, 2, 1.0).with_edge_type("explicit_link".into()),
+            Edge::new(2, 3, 0.3).with_edge_type("explicit_link".into()),
+            Edge::new(4, 1, 1.0).with_edge_type("co_citation".into()),
+        ];
+        g
+    }
+
+    #[test]
+    fn parses_full_query() {
+        let q = parse_query(
+            "MATCH (a:page {tag: \"lang\"})-[r:explicit_link*1..2]->(b) \
+             WHERE r.weight >= 0.5 AND b.label CONTAINS 'tok' RETURN a.label, b, hops LIMIT 5",
+        )
+        .unwrap();
+        assert_eq!(q.start.node_type.as_deref(), Some("page"));
+        let (rel, end) = q.hop.as_ref().unwrap();
+        assert_eq!((rel.min_hops, rel.max_hops), (1, 2));
+        assert_eq!(rel.direction, Direction::Outgoing);
+        assert_eq!(end.var, "b");
+        assert_eq!(q.conditions.len(), 2);
+        assert_eq!(q.returns.len(), 3);
+        assert_eq!(q.limit, 5);
+    }
+
+    #[test]
+    fn parse_errors_carry_position() {
+        let err = parse_query("MATCH (a RETURN a").unwrap_err();
+        assert!(err.position.is_some());
+        assert!(parse_query("MATCH (a)-[*1..9]->(b) RETURN b").is_err());
+        assert!(parse_query("MATCH (a) RETURN z").is_err());
+        assert!(parse_query("MATCH (a) RETURN hops").is_err());
+    }
+
+    #[test]
+    fn non_ascii_input_is_rejected_or_tokenised() {
+        let err = parse_query("MATCH (a) RETURN €").unwrap_err();
+        assert_eq!(err.position, Some(17));
+        assert!(parse_query("MATCH (a {label: \"Zürich €\"}) RETURN a").is_ok());
+        assert!(parse_query("MATCH (ä) RETURN ä.label").is_ok());
+        assert!(parse_query(&"x".repeat(MAX_QUERY_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn single_node_match_filters_by_tag() {
+        let g = sample();
+        let r = run_query("MATCH (n {tag: lang}) RETURN n.label", &g).unwrap();
+        let labels: Vec<_> = r.rows.iter().map(|row| row["n.label"].clone()).collect();
+        assert_eq!(labels, vec![Value::from("Rust"), Value::from("Go")]);
+    }
+
+    #[test]
+    fn traversal_respects_hops_direction_and_weight() {
+        let g = sample();
+        let r = run_query(
+            "MATCH (a {label: Rust})-[:explicit_link*1..2]->(b) RETURN b.label, hops",
+            &g,
+        )
+        .unwrap();
+        assert_eq!(r.rows.len(), 2);
+        assert_eq!(r.rows[1]["hops"], Value::from(2));
+
+        let r = run_query(
+            "MATCH (a {label: Rust})-[r*1..2]->(b) WHERE r.weight > 0.5 RETURN b.label",
+            &g,
+        )
+        .unwrap();
+        assert_eq!(r.rows.len(), 1);
+
+        let r = run_query("MATCH (a {label: Rust})<-[]-(b) RETURN b.label", &g).unwrap();
+        assert_eq!(r.rows[0]["b.label"], Value::from("Go"));
+    }
+
+    #[test]
+    fn limit_truncates() {
+        let g = sample();
+        let r = run_query("MATCH (n) RETURN n.id LIMIT 2", &g).unwrap();
+        assert_eq!(r.rows.len(), 2);
+        assert!(r.truncated);
+    }
+}
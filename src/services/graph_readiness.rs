@@ -0,0 +1,209 @@
+//! Startup readiness of the knowledge graph.
+//!
+//! The graph is seeded from `metadata.json`. When the file is missing or
+//! empty at startup (a volume still being mounted, a sync that lands later)
+//! the server used to carry on with an empty graph and report ready. Instead
+//! it now records [`GraphReadiness::WaitingForMetadata`], watches the
+//! metadata directory, and builds the graph whenever the file appears.
+//! `/readyz` answers 503 until the state is [`GraphReadiness::Ready`].
+
+use chrono::{DateTime, Utc};
+use log::{debug, warn};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use tokio::sync::mpsc;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum GraphReadiness {
+    /// Startup has not reached the graph load yet.
+    Starting,
+    /// No usable metadata; the file is being watched.
+    #[serde(rename_all = "camelCase")]
+    WaitingForMetadata {
+        path: PathBuf,
+        since: DateTime<Utc>,
+    },
+    Loading,
+    Ready {
+        nodes: usize,
+    },
+    Failed {
+        reason: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReadinessEvent {
+    MetadataMissing(PathBuf),
+    LoadStarted,
+    Loaded { nodes: usize },
+    LoadFailed(String),
+}
+
+impl GraphReadiness {
+    pub fn is_ready(&self) -> bool {
+        matches!(self, GraphReadiness::Ready { .. })
+    }
+
+    /// State after `event`. A ready graph stays ready when a later reload
+    /// finds no metadata or fails: clients keep the graph they have.
+    pub fn advance(&self, event: ReadinessEvent) -> GraphReadiness {
+        match (self, event) {
+            (GraphReadiness::Ready { .. }, ReadinessEvent::MetadataMissing(_))
+            | (GraphReadiness::Ready { .. }, ReadinessEvent::LoadStarted)
+            | (GraphReadiness::Ready { .. }, ReadinessEvent::LoadFailed(_)) => self.clone(),
+            // Keep the original timestamp while still waiting on the same file.
+            (
+                GraphReadiness::WaitingForMetadata { path, .. },
+                ReadinessEvent::MetadataMissing(p),
+            ) if *path == p => self.clone(),
+            (_, ReadinessEvent::MetadataMissing(path)) => GraphReadiness::WaitingForMetadata {
+                path,
+                since: Utc::now(),
+            },
+            (_, ReadinessEvent::LoadStarted) => GraphReadiness::Loading,
+            (_, ReadinessEvent::Loaded { nodes }) => GraphReadiness::Ready { nodes },
+            (_, ReadinessEvent::LoadFailed(reason)) => GraphReadiness::Failed { reason },
+        }
+    }
+
+    /// Why the graph is not ready, for the readiness probe.
+    pub fn not_ready_reason(&self) -> Option<String> {
+        match self {
+            GraphReadiness::Ready { .. } => None,
+            GraphReadiness::Starting => Some("Graph load has not started".to_string()),
+            GraphReadiness::WaitingForMetadata { path, since } => Some(format!(
+                "Waiting for {} (since {})",
+                path.display(),
+                since.to_rfc3339()
+            )),
+            GraphReadiness::Loading => Some("Graph is loading".to_string()),
+            GraphReadiness::Failed { reason } => Some(format!("Graph load failed: {}", reason)),
+        }
+    }
+}
+
+/// Shared readiness state, read synchronously by the readiness probe.
+#[derive(Debug)]
+pub struct GraphReadinessTracker {
+    state: RwLock<GraphReadiness>,
+}
+
+impl Default for GraphReadinessTracker {
+    fn default() -> Self {
+        Self {
+            state: RwLock::new(GraphReadiness::Starting),
+        }
+    }
+}
+
+impl GraphReadinessTracker {
+    pub fn get(&self) -> GraphReadiness {
+        self.state
+            .read()
+            .map(|s| s.clone())
+            .unwrap_or(GraphReadiness::Starting)
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.get().is_ready()
+    }
+
+    /// Apply `event` and return the new state.
+    pub fn record(&self, event: ReadinessEvent) -> GraphReadiness {
+        let mut state = self.state.write().unwrap_or_else(|e| e.into_inner());
+        *state = state.advance(event);
+        state.clone()
+    }
+}
+
+/// Watch the directory holding `file` and send on the returned channel
+/// whenever `file` is created or modified. Events stop when the watcher is
+/// dropped.
+pub fn watch_file(
+    file: &Path,
+) -> Result<(RecommendedWatcher, mpsc::UnboundedReceiver<()>), String> {
+    let dir = file
+        .parent()
+        .filter(|d| !d.as_os_str().is_empty())
+        .unwrap_or(Path::new("."))
+        .to_path_buf();
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let name = file.file_name().map(|n| n.to_os_string());
+
+    let (tx, rx) = mpsc::unbounded_channel();
+    let mut watcher =
+        notify::recommended_watcher(move |res: notify::Result<notify::Event>| match res {
+            Ok(event) => {
+                if !(event.kind.is_create() || event.kind.is_modify()) {
+                    return;
+                }
+                if event
+                    .paths
+                    .iter()
+                    .any(|p| p.file_name().map(|n| n.to_os_string()) == name)
+                {
+                    debug!("Metadata watcher: {:?} on {:?}", event.kind, event.paths);
+                    let _ = tx.send(());
+                }
+            }
+            Err(e) => warn!("Metadata watcher error: {}", e),
+        })
+        .map_err(|e| format!("Failed to create file watcher: {}", e))?;
+    watcher
+        .watch(&dir, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to watch {}: {}", dir.display(), e))?;
+    Ok((watcher, rx))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn waits_for_metadata_then_stays_ready() {
+        let tracker = GraphReadinessTracker::default();
+        assert!(!tracker.is_ready());
+        assert!(tracker.get().not_ready_reason().is_some());
+
+        let path = PathBuf::from("data/metadata/metadata.json");
+        let waiting = tracker.record(ReadinessEvent::MetadataMissing(path.clone()));
+        let GraphReadiness::WaitingForMetadata { since, .. } = waiting else {
+            panic!("expected waiting, got {:?}", waiting);
+        };
+        // A second empty read keeps the original timestamp.
+        assert_eq!(
+            tracker.record(ReadinessEvent::MetadataMissing(path.clone())),
+            waiting
+        );
+        assert!(tracker
+            .get()
+            .not_ready_reason()
+            .unwrap()
+            .contains(&since.to_rfc3339()));
+
+        assert_eq!(
+            tracker.record(ReadinessEvent::LoadStarted),
+            GraphReadiness::Loading
+        );
+        assert_eq!(
+            tracker.record(ReadinessEvent::Loaded { nodes: 42 }),
+            GraphReadiness::Ready { nodes: 42 }
+        );
+        assert!(tracker.is_ready());
+
+        tracker.record(ReadinessEvent::MetadataMissing(path));
+        tracker.record(ReadinessEvent::LoadFailed("disk".to_string()));
+        assert_eq!(tracker.get(), GraphReadiness::Ready { nodes: 42 });
+
+        let failed = GraphReadiness::Loading.advance(ReadinessEvent::LoadFailed("x".into()));
+        assert_eq!(
+            failed.not_ready_reason().as_deref(),
+            Some("Graph load failed: x")
+        );
+    }
+}
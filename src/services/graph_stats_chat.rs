@@ -0,0 +1,585 @@
+//! "Describe my graph": chat questions answered from the graph itself.
+//!
+//! The RAG provider only sees page text, so when asked "how many notes link
+//! to X?" it guesses. Questions about the graph's own shape are recognised
+//! here and answered from `GraphService` data, with the numbers the answer
+//! quotes returned alongside it as `facts`:
+//!
+//! - "describe my graph": node, edge and page counts, components, hubs
+//! - "how many notes link to X?" / "what links to X?": backlinks of X
+//! - "what's my biggest cluster?": largest analytics cluster, or the largest
+//!   connected component when clustering has not run
+//! - "what are my most connected notes?": top nodes by degree
+//! - "how many notes/pages/edges are there?": counts
+//!
+//! Any question can be forced here with the [`PREFIX`] (`/graph how many
+//! notes link to Rust`); a bare `/graph` describes the graph. Everything else
+//! goes to the RAG provider as before.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use visionclaw_domain::analytics::NodeAnalytics;
+use visionclaw_domain::models::graph::GraphData;
+use visionclaw_domain::models::node::Node;
+
+/// Explicit intent prefix routing a chat question to the graph.
+pub const PREFIX: &str = "/graph";
+
+/// Nodes named in a "most connected" or "biggest cluster" answer.
+const TOP_N: usize = 5;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatsIntent {
+    Describe,
+    Backlinks(String),
+    BiggestCluster,
+    MostConnected,
+    Count(CountKind),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CountKind {
+    Nodes,
+    Pages,
+    Edges,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphStatsAnswer {
+    pub intent: &'static str,
+    pub answer: String,
+    /// The numbers the answer quotes, for clients that render them.
+    pub facts: Value,
+    /// Nodes the answer is about, for highlighting.
+    pub node_ids: Vec<u32>,
+}
+
+static BACKLINKS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)^(?:how many (?:notes|pages|nodes) (?:link|point|refer) to|what (?:links|points) to|backlinks (?:to|of|for))\s+(.+?)[\s?.!]*$",
+    )
+    .expect("Invalid regex pattern")
+});
+
+static COUNT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)^how many (notes|pages|nodes|edges|links)(?: (?:are there|do i have|are in my graph|in my graph))?[\s?.!]*$",
+    )
+    .expect("Invalid regex pattern")
+});
+
+static BIGGEST_CLUSTER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)^(?:(?:what|which)(?:'s| is) )?(?:my |the )?(?:biggest|largest) (?:cluster|community|group)(?: in my graph)?[\s?.!]*$",
+    )
+    .expect("Invalid regex pattern")
+});
+
+static MOST_CONNECTED: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)^(?:(?:what|which)(?:'s| is| are) |show me )?(?:my |the )?most (?:connected|linked)(?: (?:notes?|pages?|nodes?))?(?: in my graph)?[\s?.!]*$",
+    )
+    .expect("Invalid regex pattern")
+});
+
+static DESCRIBE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^(?:describe|summari[sz]e) (?:my |the )?graph\b|^graph stat(?:istic)?s\b")
+        .expect("Invalid regex pattern")
+});
+
+fn match_intent(question: &str) -> Option<StatsIntent> {
+    if let Some(caps) = BACKLINKS.captures(question) {
+        let name = caps[1]
+            .trim()
+            .trim_matches(|c| matches!(c, '"' | '\'' | '[' | ']'))
+            .trim();
+        return (!name.is_empty()).then(|| StatsIntent::Backlinks(name.to_string()));
+    }
+    if let Some(caps) = COUNT.captures(question) {
+        let kind = match caps[1].to_lowercase().as_str() {
+            "pages" => CountKind::Pages,
+            "edges" | "links" => CountKind::Edges,
+            _ => CountKind::Nodes,
+        };
+        return Some(StatsIntent::Count(kind));
+    }
+    if BIGGEST_CLUSTER.is_match(question) {
+        return Some(StatsIntent::BiggestCluster);
+    }
+    if MOST_CONNECTED.is_match(question) {
+        return Some(StatsIntent::MostConnected);
+    }
+    DESCRIBE.is_match(question).then_some(StatsIntent::Describe)
+}
+
+/// The graph question being asked, if this is one. With the [`PREFIX`] the
+/// question is always a graph question, falling back to a description.
+pub fn recognise(question: &str) -> Option<StatsIntent> {
+    let question = question.trim();
+    let prefixed = question
+        .get(..PREFIX.len())
+        .filter(|p| p.eq_ignore_ascii_case(PREFIX))
+        .map(|_| &question[PREFIX.len()..])
+        .filter(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+        .map(str::trim);
+    match prefixed {
+        Some(rest) => Some(match_intent(rest).unwrap_or(StatsIntent::Describe)),
+        None => match_intent(question),
+    }
+}
+
+fn label(node: &Node) -> &str {
+    if node.label.is_empty() {
+        &node.metadata_id
+    } else {
+        &node.label
+    }
+}
+
+fn is_page(node: &Node) -> bool {
+    node.population_type() == Some("page")
+}
+
+/// Undirected degree of every node, self-loops ignored.
+pub(crate) fn degrees(graph: &GraphData) -> HashMap<u32, usize> {
+    let mut degrees: HashMap<u32, usize> = graph.nodes.iter().map(|n| (n.id, 0)).collect();
+    for edge in graph.edges.iter().filter(|e| e.source != e.target) {
+        *degrees.entry(edge.source).or_default() += 1;
+        *degrees.entry(edge.target).or_default() += 1;
+    }
+    degrees
+}
+
+/// Neighbours of every node, ignoring edge direction.
+pub(crate) fn adjacency(graph: &GraphData) -> HashMap<u32, Vec<u32>> {
+    let mut adjacency: HashMap<u32, Vec<u32>> = HashMap::new();
+    for edge in &graph.edges {
+        adjacency.entry(edge.source).or_default().push(edge.target);
+        adjacency.entry(edge.target).or_default().push(edge.source);
+    }
+    adjacency
+}
+
+/// `start` and the nodes within `depth` links of it, nearest first, stopping
+/// at `limit` nodes.
+pub(crate) fn reachable(
+    adjacency: &HashMap<u32, Vec<u32>>,
+    start: u32,
+    depth: usize,
+    limit: usize,
+) -> Vec<u32> {
+    let mut seen = HashSet::from([start]);
+    let mut reached = vec![start];
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((id, d)) = queue.pop_front() {
+        if d == depth {
+            continue;
+        }
+        for &next in adjacency.get(&id).into_iter().flatten() {
+            if reached.len() >= limit {
+                return reached;
+            }
+            if seen.insert(next) {
+                reached.push(next);
+                queue.push_back((next, d + 1));
+            }
+        }
+    }
+    reached
+}
+
+/// Connected components, largest first.
+fn components(graph: &GraphData) -> Vec<Vec<u32>> {
+    let adjacency = adjacency(graph);
+    let mut seen = HashSet::new();
+    let mut components = Vec::new();
+    for node in &graph.nodes {
+        if seen.contains(&node.id) {
+            continue;
+        }
+        let component = reachable(&adjacency, node.id, usize::MAX, usize::MAX);
+        seen.extend(component.iter().copied());
+        components.push(component);
+    }
+    components.sort_by_key(|c| std::cmp::Reverse(c.len()));
+    components
+}
+
+/// `ids` ordered by degree, highest first, ties by id.
+fn by_degree(ids: impl IntoIterator<Item = u32>, degrees: &HashMap<u32, usize>) -> Vec<u32> {
+    let mut ids: Vec<u32> = ids.into_iter().collect();
+    ids.sort_by_key(|id| {
+        (
+            std::cmp::Reverse(degrees.get(id).copied().unwrap_or(0)),
+            *id,
+        )
+    });
+    ids
+}
+
+fn labels(graph: &GraphData, ids: &[u32]) -> Vec<String> {
+    let by_id: HashMap<u32, &Node> = graph.nodes.iter().map(|n| (n.id, n)).collect();
+    ids.iter()
+        .filter_map(|id| by_id.get(id))
+        .map(|n| label(n).to_string())
+        .collect()
+}
+
+fn plural(n: usize, one: &str, many: &str) -> String {
+    format!("{} {}", n, if n == 1 { one } else { many })
+}
+
+fn describe(graph: &GraphData) -> GraphStatsAnswer {
+    let degrees = degrees(graph);
+    let components = components(graph);
+    let nodes = graph.nodes.len();
+    let pages = graph.nodes.iter().filter(|n| is_page(n)).count();
+    let isolated = degrees.values().filter(|d| **d == 0).count();
+    let average_degree = if nodes == 0 {
+        0.0
+    } else {
+        degrees.values().sum::<usize>() as f64 / nodes as f64
+    };
+    let largest = components.first().map_or(0, Vec::len);
+    let hubs: Vec<u32> = by_degree(degrees.keys().copied(), &degrees)
+        .into_iter()
+        .take(TOP_N)
+        .collect();
+    let hub_labels = labels(graph, &hubs);
+
+    let mut answer = format!(
+        "Your graph has {} ({}) and {}, in {}; the largest holds {}. \
+         Nodes average {:.1} connections and {} unconnected.",
+        plural(nodes, "node", "nodes"),
+        plural(pages, "page", "pages"),
+        plural(graph.edges.len(), "edge", "edges"),
+        plural(
+            components.len(),
+            "connected component",
+            "connected components"
+        ),
+        plural(largest, "node", "nodes"),
+        average_degree,
+        if isolated == 1 {
+            "1 is".to_string()
+        } else {
+            format!("{} are", isolated)
+        },
+    );
+    if !hub_labels.is_empty() {
+        answer.push_str(&format!(" Best connected: {}.", hub_labels.join(", ")));
+    }
+
+    GraphStatsAnswer {
+        intent: "describe",
+        answer,
+        facts: json!({
+            "nodes": nodes,
+            "pages": pages,
+            "edges": graph.edges.len(),
+            "components": components.len(),
+            "largestComponent": largest,
+            "isolatedNodes": isolated,
+            "averageDegree": average_degree,
+        }),
+        node_ids: hubs,
+    }
+}
+
+fn backlinks(graph: &GraphData, name: &str) -> GraphStatsAnswer {
+    let wanted = name.trim_end_matches(".md").to_lowercase();
+    let targets: HashSet<u32> = graph
+        .nodes
+        .iter()
+        .filter(|n| {
+            n.label.to_lowercase() == wanted
+                || n.metadata_id.trim_end_matches(".md").to_lowercase() == wanted
+        })
+        .map(|n| n.id)
+        .collect();
+    if targets.is_empty() {
+        return GraphStatsAnswer {
+            intent: "backlinks",
+            answer: format!("There is no note called \"{}\" in your graph.", name),
+            facts: json!({ "target": name, "found": false }),
+            node_ids: Vec::new(),
+        };
+    }
+
+    let sources: HashSet<u32> = graph
+        .edges
+        .iter()
+        .filter(|e| targets.contains(&e.target) && !targets.contains(&e.source))
+        .map(|e| e.source)
+        .collect();
+    let degrees = degrees(graph);
+    let sources = by_degree(sources, &degrees);
+    let shown = labels(graph, &sources[..sources.len().min(TOP_N)]);
+
+    let mut answer = format!(
+        "{} link to \"{}\".",
+        plural(sources.len(), "note", "notes"),
+        name
+    );
+    if !shown.is_empty() {
+        let more = sources.len() - shown.len();
+        answer.push_str(&format!(" {}", shown.join(", ")));
+        if more > 0 {
+            answer.push_str(&format!(" and {} more", more));
+        }
+        answer.push('.');
+    }
+
+    let mut node_ids: Vec<u32> = targets.into_iter().collect();
+    node_ids.sort_unstable();
+    node_ids.extend(&sources);
+    GraphStatsAnswer {
+        intent: "backlinks",
+        answer,
+        facts: json!({ "target": name, "found": true, "backlinks": sources.len() }),
+        node_ids,
+    }
+}
+
+fn biggest_cluster(graph: &GraphData, analytics: &HashMap<u32, NodeAnalytics>) -> GraphStatsAnswer {
+    let degrees = degrees(graph);
+    let mut clusters: HashMap<u32, Vec<u32>> = HashMap::new();
+    for node in &graph.nodes {
+        // cluster_id 0 means unclustered.
+        if let Some(a) = analytics.get(&node.id).filter(|a| a.cluster_id != 0) {
+            clusters.entry(a.cluster_id).or_default().push(node.id);
+        }
+    }
+    let (method, count, members) = if clusters.is_empty() {
+        let components = components(graph);
+        let count = components.len();
+        let largest = components.into_iter().next().unwrap_or_default();
+        ("components", count, largest)
+    } else {
+        let count = clusters.len();
+        let largest = clusters
+            .into_iter()
+            .max_by_key(|(id, members)| (members.len(), std::cmp::Reverse(*id)))
+            .map(|(_, members)| members)
+            .unwrap_or_default();
+        ("clustering", count, largest)
+    };
+
+    let members = by_degree(members, &degrees);
+    let share = if graph.nodes.is_empty() {
+        0.0
+    } else {
+        100.0 * members.len() as f64 / graph.nodes.len() as f64
+    };
+    let central = labels(graph, &members[..members.len().min(TOP_N)]);
+    let kind = if method == "clustering" {
+        ("cluster", "clusters")
+    } else {
+        ("connected component", "connected components")
+    };
+    let mut answer = format!(
+        "Your biggest {} has {} ({:.0}% of the graph), out of {}.",
+        kind.0,
+        plural(members.len(), "node", "nodes"),
+        share,
+        plural(count, kind.0, kind.1),
+    );
+    if !central.is_empty() {
+        answer.push_str(&format!(" Its most connected: {}.", central.join(", ")));
+    }
+
+    GraphStatsAnswer {
+        intent: "biggestCluster",
+        answer,
+        facts: json!({
+            "method": method,
+            "clusters": count,
+            "size": members.len(),
+            "sharePercent": share,
+        }),
+        node_ids: members,
+    }
+}
+
+fn most_connected(graph: &GraphData) -> GraphStatsAnswer {
+    let degrees = degrees(graph);
+    let top: Vec<u32> = by_degree(degrees.keys().copied(), &degrees)
+        .into_iter()
+        .filter(|id| degrees[id] > 0)
+        .take(TOP_N)
+        .collect();
+    let listed: Vec<String> = labels(graph, &top)
+        .into_iter()
+        .zip(&top)
+        .map(|(label, id)| format!("{} ({})", label, degrees[id]))
+        .collect();
+    let answer = if listed.is_empty() {
+        "None of your notes are connected yet.".to_string()
+    } else {
+        format!("Your most connected notes: {}.", listed.join(", "))
+    };
+    GraphStatsAnswer {
+        intent: "mostConnected",
+        answer,
+        facts: json!({
+            "nodes": top
+                .iter()
+                .map(|id| json!({ "id": id, "degree": degrees[id] }))
+                .collect::<Vec<_>>(),
+        }),
+        node_ids: top,
+    }
+}
+
+fn count(graph: &GraphData, kind: CountKind) -> GraphStatsAnswer {
+    let (n, answer) = match kind {
+        CountKind::Nodes => {
+            let n = graph.nodes.len();
+            (n, format!("Your graph has {}.", plural(n, "node", "nodes")))
+        }
+        CountKind::Pages => {
+            let n = graph.nodes.iter().filter(|n| is_page(n)).count();
+            (n, format!("Your graph has {}.", plural(n, "page", "pages")))
+        }
+        CountKind::Edges => {
+            let n = graph.edges.len();
+            (n, format!("Your graph has {}.", plural(n, "edge", "edges")))
+        }
+    };
+    let key = match kind {
+        CountKind::Nodes => "nodes",
+        CountKind::Pages => "pages",
+        CountKind::Edges => "edges",
+    };
+    GraphStatsAnswer {
+        intent: "count",
+        answer,
+        facts: json!({ key: n }),
+        node_ids: Vec::new(),
+    }
+}
+
+/// Answer `intent` from `graph` and the latest per-node analytics.
+pub fn answer(
+    intent: &StatsIntent,
+    graph: &GraphData,
+    analytics: &HashMap<u32, NodeAnalytics>,
+) -> GraphStatsAnswer {
+    match intent {
+        StatsIntent::Describe => describe(graph),
+        StatsIntent::Backlinks(name) => backlinks(graph, name),
+        StatsIntent::BiggestCluster => biggest_cluster(graph, analytics),
+        StatsIntent::MostConnected => most_connected(graph),
+        StatsIntent::Count(kind) => count(graph, *kind),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use visionclaw_domain::models::edge::Edge;
+
+    fn graph() -> GraphData {
+        let mut graph = GraphData::new();
+        for (id, name) in [
+            (1, "Rust"),
+            (2, "Cargo"),
+            (3, "Tokio"),
+            (4, "Actix"),
+            (5, "Garden"),
+        ] {
+            let mut node = Node::new_with_id(format!("{}.md", name), Some(id));
+            node.label = name.to_string();
+            node.metadata.insert("type".to_string(), "page".to_string());
+            graph.nodes.push(node);
+        }
+        for (source, target) in [(2, 1), (3, 1), (4, 1), (4, 3)] {
+            graph.edges.push(Edge::new(source, target, 1.0));
+        }
+        graph
+    }
+
+    #[test]
+    fn recognises_and_answers_graph_questions() {
+        assert_eq!(
+            recognise("How many notes link to [[Rust]]?"),
+            Some(StatsIntent::Backlinks("Rust".to_string()))
+        );
+        assert_eq!(
+            recognise("what's my biggest cluster?"),
+            Some(StatsIntent::BiggestCluster)
+        );
+        assert_eq!(
+            recognise("how many pages do I have?"),
+            Some(StatsIntent::Count(CountKind::Pages))
+        );
+        assert_eq!(recognise("Describe my graph"), Some(StatsIntent::Describe));
+        assert_eq!(recognise("/GRAPH"), Some(StatsIntent::Describe));
+        assert_eq!(
+            recognise("/graph most connected"),
+            Some(StatsIntent::MostConnected)
+        );
+        assert_eq!(recognise("how many notes mention async runtimes?"), None);
+        assert_eq!(recognise("What is Rust?"), None);
+        assert_eq!(
+            recognise("which are my most linked notes?"),
+            Some(StatsIntent::MostConnected)
+        );
+        assert_eq!(
+            recognise("who was the most connected person in Florence?"),
+            None
+        );
+        assert_eq!(
+            recognise("summarise the largest group of Roman emperors"),
+            None
+        );
+        assert_eq!(recognise("/graphql schema for my notes"), None);
+
+        let graph = graph();
+        let none = HashMap::new();
+
+        let links = answer(&StatsIntent::Backlinks("rust".to_string()), &graph, &none);
+        assert_eq!(links.facts["backlinks"], 3);
+        assert_eq!(links.node_ids, vec![1, 3, 4, 2]);
+        assert!(links.answer.starts_with("3 notes link to \"rust\"."));
+        let missing = answer(&StatsIntent::Backlinks("Go".to_string()), &graph, &none);
+        assert_eq!(missing.facts["found"], false);
+
+        let described = answer(&StatsIntent::Describe, &graph, &none);
+        assert_eq!(described.facts["components"], 2);
+        assert_eq!(described.facts["isolatedNodes"], 1);
+        assert_eq!(described.node_ids[0], 1);
+
+        let cluster = answer(&StatsIntent::BiggestCluster, &graph, &none);
+        assert_eq!(
+            (cluster.facts["method"].as_str(), cluster.node_ids.len()),
+            (Some("components"), 4)
+        );
+        let analytics: HashMap<u32, NodeAnalytics> = [(1, 1), (2, 1), (3, 2), (4, 2), (5, 2)]
+            .into_iter()
+            .map(|(id, cluster_id)| {
+                let a = NodeAnalytics {
+                    cluster_id,
+                    ..Default::default()
+                };
+                (id, a)
+            })
+            .collect();
+        let cluster = answer(&StatsIntent::BiggestCluster, &graph, &analytics);
+        assert_eq!(cluster.facts["method"], "clustering");
+        assert_eq!(cluster.node_ids, vec![3, 4, 5]);
+
+        let top = answer(&StatsIntent::MostConnected, &graph, &none);
+        assert_eq!(top.node_ids, vec![1, 3, 4, 2]);
+        assert_eq!(
+            answer(&StatsIntent::Count(CountKind::Edges), &graph, &none).facts["edges"],
+            4
+        );
+    }
+}
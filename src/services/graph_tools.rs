@@ -0,0 +1,658 @@
+//! Graph operations exposed to LLM function calling.
+//!
+//! The chat agent (`services::chat_agent`) offers these tools to an
+//! OpenAI-compatible provider and runs the calls the model makes:
+//!
+//! - `search_nodes`: nodes whose label or page name matches a query
+//! - `get_neighbors`: nodes linked to or from a node
+//! - `get_page_content`: the markdown behind a page node
+//! - `graph_stats`: the numeric answers of `services::graph_stats_chat`
+//! - `focus_subgraph`: ask the client to focus a node's neighbourhood
+//! - `create_node`: add a node to the visualisation
+//!
+//! Every call goes through a [`Sandbox`]. It refuses tools the caller's
+//! [`ToolRole`] may not use, caps the calls per turn at [`MAX_TOOL_CALLS`]
+//! and the nodes created at [`MAX_CREATED_NODES`], and clamps every limit,
+//! depth and length argument. Created nodes are ephemeral: they expire after
+//! [`CREATED_NODE_TTL_SECS`] and are never written to the vault, so a
+//! confused model cannot change anyone's notes. Page reads stay inside the
+//! markdown directory. A refused or failed call is returned to the model as
+//! `{"error": ...}` so it can try something else.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use actix::Addr;
+use log::{debug, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::actors::graph_service_supervisor::GraphServiceSupervisor;
+use crate::actors::messages::AddEphemeralNode;
+use crate::services::file_service::markdown_dir;
+use crate::services::graph_stats_chat::{self, adjacency, degrees, reachable, CountKind, StatsIntent};
+use visionclaw_domain::analytics::NodeAnalytics;
+use visionclaw_domain::models::graph::GraphData;
+use visionclaw_domain::models::node::Node;
+
+/// Tool calls allowed in one chat turn.
+pub const MAX_TOOL_CALLS: usize = 8;
+
+/// Most nodes a search or neighbour listing returns.
+pub const MAX_RESULTS: usize = 25;
+
+/// Deepest neighbourhood `focus_subgraph` will take.
+pub const MAX_DEPTH: usize = 2;
+
+/// Most nodes one focus action may name.
+pub const MAX_FOCUS_NODES: usize = 200;
+
+/// Page content beyond this many characters is cut off.
+pub const MAX_CONTENT_CHARS: usize = 6000;
+
+/// Nodes one chat turn may create.
+pub const MAX_CREATED_NODES: usize = 3;
+
+/// Lifetime of nodes created by the model.
+pub const CREATED_NODE_TTL_SECS: u64 = 600;
+
+const MAX_LABEL_CHARS: usize = 120;
+
+/// Who is chatting, from their session. Tools name the least role that may
+/// call them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ToolRole {
+    User,
+    PowerUser,
+}
+
+impl ToolRole {
+    pub fn of(is_power_user: bool) -> Self {
+        if is_power_user {
+            Self::PowerUser
+        } else {
+            Self::User
+        }
+    }
+}
+
+pub struct ToolSpec {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub role: ToolRole,
+    /// JSON Schema of the arguments.
+    pub parameters: Value,
+}
+
+static TOOLS: Lazy<Vec<ToolSpec>> = Lazy::new(|| {
+    vec![
+        ToolSpec {
+            name: "search_nodes",
+            description: "Find nodes whose title matches a query. Returns ids, labels and link counts.",
+            role: ToolRole::User,
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "query": {"type": "string", "description": "Text to look for in node titles"},
+                    "limit": {"type": "integer", "minimum": 1, "maximum": MAX_RESULTS}
+                },
+                "required": ["query"]
+            }),
+        },
+        ToolSpec {
+            name: "get_neighbors",
+            description: "List the nodes linked to or from a node.",
+            role: ToolRole::User,
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "node_id": {"type": "integer"},
+                    "limit": {"type": "integer", "minimum": 1, "maximum": MAX_RESULTS}
+                },
+                "required": ["node_id"]
+            }),
+        },
+        ToolSpec {
+            name: "get_page_content",
+            description: "Read the markdown of a page node.",
+            role: ToolRole::User,
+            parameters: json!({
+                "type": "object",
+                "properties": {"node_id": {"type": "integer"}},
+                "required": ["node_id"]
+            }),
+        },
+        ToolSpec {
+            name: "graph_stats",
+            description: "Exact numbers about the graph: an overview, backlinks of a note, the biggest cluster, the most connected notes, or node/page/edge counts.",
+            role: ToolRole::User,
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "kind": {
+                        "type": "string",
+                        "enum": ["describe", "backlinks", "biggest_cluster", "most_connected", "nodes", "pages", "edges"]
+                    },
+                    "target": {"type": "string", "description": "Note title, for backlinks"}
+                },
+                "required": ["kind"]
+            }),
+        },
+        ToolSpec {
+            name: "focus_subgraph",
+            description: "Focus the user's view on a node and its neighbourhood.",
+            role: ToolRole::User,
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "node_id": {"type": "integer"},
+                    "depth": {"type": "integer", "minimum": 0, "maximum": MAX_DEPTH}
+                },
+                "required": ["node_id"]
+            }),
+        },
+        ToolSpec {
+            name: "create_node",
+            description: "Add a temporary node to the visualisation, optionally next to an existing node. It disappears after a few minutes and is not saved to the notes.",
+            role: ToolRole::PowerUser,
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "label": {"type": "string", "maxLength": MAX_LABEL_CHARS},
+                    "near_node_id": {"type": "integer"}
+                },
+                "required": ["label"]
+            }),
+        },
+    ]
+});
+
+/// Tools `role` may call, in the OpenAI `tools` format.
+pub fn tool_definitions(role: ToolRole) -> Vec<Value> {
+    TOOLS
+        .iter()
+        .filter(|t| t.role <= role)
+        .map(|t| {
+            json!({
+                "type": "function",
+                "function": {
+                    "name": t.name,
+                    "description": t.description,
+                    "parameters": t.parameters,
+                }
+            })
+        })
+        .collect()
+}
+
+/// A call the model asked for. `arguments` is the JSON string the provider
+/// sent.
+#[derive(Debug, Clone)]
+pub struct ToolCall {
+    pub id: String,
+    pub name: String,
+    pub arguments: String,
+}
+
+/// A call as run, reported back to the client.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolResult {
+    pub name: String,
+    pub arguments: Value,
+    pub ok: bool,
+    /// The tool's output, or `{"error": ...}`.
+    pub result: Value,
+}
+
+/// What the client should do to its view once the answer arrives.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ClientAction {
+    #[serde(rename_all = "camelCase")]
+    Focus { node_id: u32, node_ids: Vec<u32> },
+}
+
+/// Per-turn limits and permissions.
+#[derive(Debug)]
+pub struct Sandbox {
+    role: ToolRole,
+    calls: usize,
+    created: usize,
+}
+
+impl Sandbox {
+    pub fn new(role: ToolRole) -> Self {
+        Self {
+            role,
+            calls: 0,
+            created: 0,
+        }
+    }
+
+    /// Admit one call to `name`, counting it against the turn.
+    pub fn admit(&mut self, name: &str) -> Result<&'static ToolSpec, String> {
+        let spec = TOOLS
+            .iter()
+            .find(|t| t.name == name)
+            .ok_or_else(|| format!("Unknown tool '{}'", name))?;
+        if spec.role > self.role {
+            return Err(format!("Tool '{}' is not available to you", name));
+        }
+        if self.calls >= MAX_TOOL_CALLS {
+            return Err(format!(
+                "Tool call limit of {} per message reached",
+                MAX_TOOL_CALLS
+            ));
+        }
+        self.calls += 1;
+        if name == "create_node" {
+            if self.created >= MAX_CREATED_NODES {
+                return Err(format!(
+                    "At most {} nodes can be created per message",
+                    MAX_CREATED_NODES
+                ));
+            }
+            self.created += 1;
+        }
+        Ok(spec)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SearchArgs {
+    query: String,
+    limit: Option<usize>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NeighborArgs {
+    node_id: u32,
+    limit: Option<usize>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PageArgs {
+    node_id: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StatsArgs {
+    kind: String,
+    target: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FocusArgs {
+    node_id: u32,
+    depth: Option<usize>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CreateArgs {
+    label: String,
+    near_node_id: Option<u32>,
+}
+
+fn parse_args<T: for<'de> Deserialize<'de>>(args: &Value) -> Result<T, String> {
+    serde_json::from_value(args.clone()).map_err(|e| format!("Invalid arguments: {}", e))
+}
+
+fn limit(requested: Option<usize>) -> usize {
+    requested.unwrap_or(10).clamp(1, MAX_RESULTS)
+}
+
+fn find_node(graph: &GraphData, id: u32) -> Result<&Node, String> {
+    graph
+        .nodes
+        .iter()
+        .find(|n| n.id == id)
+        .ok_or_else(|| format!("No node with id {}", id))
+}
+
+/// The node ids in a `search_nodes` result.
+fn found_ids(result: &Value) -> impl Iterator<Item = u32> + '_ {
+    result["nodes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|n| n["id"].as_u64())
+        .map(|id| id as u32)
+}
+
+fn search_nodes(graph: &GraphData, args: SearchArgs) -> Result<Value, String> {
+    let query = args.query.trim().to_lowercase();
+    if query.is_empty() {
+        return Err("query must not be empty".to_string());
+    }
+    let degrees = degrees(graph);
+    // Exact titles first, then prefixes, then anywhere; busier nodes first.
+    let mut hits: Vec<(u8, usize, &Node)> = graph
+        .nodes
+        .iter()
+        .filter_map(|n| {
+            let label = n.label.to_lowercase();
+            let page = n.metadata_id.trim_end_matches(".md").to_lowercase();
+            let rank = if label == query || page == query {
+                0
+            } else if label.starts_with(&query) || page.starts_with(&query) {
+                1
+            } else if label.contains(&query) || page.contains(&query) {
+                2
+            } else {
+                return None;
+            };
+            Some((rank, degrees.get(&n.id).copied().unwrap_or(0), n))
+        })
+        .collect();
+    hits.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)).then(a.2.id.cmp(&b.2.id)));
+    let total = hits.len();
+    let nodes: Vec<Value> = hits
+        .into_iter()
+        .take(limit(args.limit))
+        .map(|(_, degree, n)| json!({"id": n.id, "label": n.label, "links": degree}))
+        .collect();
+    Ok(json!({"total": total, "nodes": nodes}))
+}
+
+fn get_neighbors(graph: &GraphData, args: NeighborArgs) -> Result<Value, String> {
+    let node = find_node(graph, args.node_id)?;
+    let labels: HashMap<u32, &str> = graph
+        .nodes
+        .iter()
+        .map(|n| (n.id, n.label.as_str()))
+        .collect();
+    let mut neighbors: Vec<Value> = graph
+        .edges
+        .iter()
+        .filter_map(|e| match (e.source == node.id, e.target == node.id) {
+            (true, false) => Some((e.target, "out", e)),
+            (false, true) => Some((e.source, "in", e)),
+            _ => None,
+        })
+        .map(|(id, direction, e)| {
+            json!({
+                "id": id,
+                "label": labels.get(&id).copied().unwrap_or_default(),
+                "direction": direction,
+                "edgeType": e.edge_type,
+            })
+        })
+        .collect();
+    let total = neighbors.len();
+    neighbors.truncate(limit(args.limit));
+    Ok(
+        json!({"node": {"id": node.id, "label": node.label}, "total": total, "neighbors": neighbors}),
+    )
+}
+
+fn graph_stats(
+    graph: &GraphData,
+    analytics: &HashMap<u32, NodeAnalytics>,
+    args: StatsArgs,
+) -> Result<Value, String> {
+    let intent = match args.kind.as_str() {
+        "describe" => StatsIntent::Describe,
+        "backlinks" => StatsIntent::Backlinks(
+            args.target
+                .filter(|t| !t.trim().is_empty())
+                .ok_or("backlinks needs a target")?,
+        ),
+        "biggest_cluster" => StatsIntent::BiggestCluster,
+        "most_connected" => StatsIntent::MostConnected,
+        "nodes" => StatsIntent::Count(CountKind::Nodes),
+        "pages" => StatsIntent::Count(CountKind::Pages),
+        "edges" => StatsIntent::Count(CountKind::Edges),
+        other => return Err(format!("Unknown stats kind '{}'", other)),
+    };
+    serde_json::to_value(graph_stats_chat::answer(&intent, graph, analytics))
+        .map_err(|e| e.to_string())
+}
+
+/// `node_id` and everything within `depth` links of it, nearest first.
+fn focus_subgraph(graph: &GraphData, args: FocusArgs) -> Result<(Value, ClientAction), String> {
+    let node = find_node(graph, args.node_id)?;
+    let depth = args.depth.unwrap_or(1).min(MAX_DEPTH);
+    let node_ids = reachable(&adjacency(graph), node.id, depth, MAX_FOCUS_NODES);
+    let result =
+        json!({"focused": node.id, "label": node.label, "depth": depth, "nodes": node_ids.len()});
+    Ok((
+        result,
+        ClientAction::Focus {
+            node_id: node.id,
+            node_ids,
+        },
+    ))
+}
+
+fn read_page(node: &Node) -> Result<Value, String> {
+    let name = &node.metadata_id;
+    if name.contains("..") || name.starts_with('/') || name.contains('\0') {
+        return Err("Invalid page name".to_string());
+    }
+    let file = if name.ends_with(".md") {
+        name.clone()
+    } else {
+        format!("{}.md", name)
+    };
+    let base = Path::new(markdown_dir())
+        .canonicalize()
+        .map_err(|e| format!("Pages are unavailable: {}", e))?;
+    let path = base
+        .join(&file)
+        .canonicalize()
+        .map_err(|_| format!("No page content for node {}", node.id))?;
+    if !path.starts_with(&base) {
+        return Err("Invalid page name".to_string());
+    }
+    let content = std::fs::read_to_string(&path)
+        .map_err(|_| format!("No page content for node {}", node.id))?;
+    let truncated = content.chars().count() > MAX_CONTENT_CHARS;
+    let content: String = content.chars().take(MAX_CONTENT_CHARS).collect();
+    Ok(json!({"id": node.id, "page": file, "content": content, "truncated": truncated}))
+}
+
+/// One chat turn's tool calls against a snapshot of the graph.
+pub struct ToolSession {
+    sandbox: Sandbox,
+    graph: Arc<GraphData>,
+    analytics: HashMap<u32, NodeAnalytics>,
+    graph_service: Addr<GraphServiceSupervisor>,
+    pub results: Vec<ToolResult>,
+    pub actions: Vec<ClientAction>,
+    /// Ids `search_nodes` returned this turn, in order, without repeats.
+    pub found: Vec<u32>,
+}
+
+impl ToolSession {
+    pub fn new(
+        role: ToolRole,
+        graph: Arc<GraphData>,
+        analytics: HashMap<u32, NodeAnalytics>,
+        graph_service: Addr<GraphServiceSupervisor>,
+    ) -> Self {
+        Self {
+            sandbox: Sandbox::new(role),
+            graph,
+            analytics,
+            graph_service,
+            results: Vec::new(),
+            actions: Vec::new(),
+            found: Vec::new(),
+        }
+    }
+
+    pub fn definitions(&self) -> Vec<Value> {
+        tool_definitions(self.sandbox.role)
+    }
+
+    /// Run `call` and record it. Returns what to send back to the model.
+    pub async fn call(&mut self, call: &ToolCall) -> Value {
+        let arguments: Value = serde_json::from_str(&call.arguments).unwrap_or(Value::Null);
+        let outcome = match self.sandbox.admit(&call.name) {
+            Ok(_) => self.run(&call.name, &arguments).await,
+            Err(e) => Err(e),
+        };
+        let (ok, result) = match outcome {
+            Ok(result) => (true, result),
+            Err(e) => {
+                warn!("[ChatTools] {} refused: {}", call.name, e);
+                (false, json!({"error": e}))
+            }
+        };
+        debug!("[ChatTools] {}({}) ok={}", call.name, arguments, ok);
+        self.results.push(ToolResult {
+            name: call.name.clone(),
+            arguments,
+            ok,
+            result: result.clone(),
+        });
+        result
+    }
+
+    async fn run(&mut self, name: &str, args: &Value) -> Result<Value, String> {
+        let graph = self.graph.clone();
+        match name {
+            "search_nodes" => {
+                let result = search_nodes(&graph, parse_args(args)?)?;
+                for id in found_ids(&result) {
+                    if !self.found.contains(&id) {
+                        self.found.push(id);
+                    }
+                }
+                Ok(result)
+            }
+            "get_neighbors" => get_neighbors(&graph, parse_args(args)?),
+            "graph_stats" => graph_stats(&graph, &self.analytics, parse_args(args)?),
+            "get_page_content" => {
+                let args: PageArgs = parse_args(args)?;
+                actix_web::web::block(move || read_page(find_node(&graph, args.node_id)?))
+                    .await
+                    .map_err(|e| format!("Page read failed: {}", e))?
+            }
+            "focus_subgraph" => {
+                let (result, action) = focus_subgraph(&graph, parse_args(args)?)?;
+                self.actions.push(action);
+                Ok(result)
+            }
+            "create_node" => self.create_node(parse_args(args)?).await,
+            other => Err(format!("Unknown tool '{}'", other)),
+        }
+    }
+
+    async fn create_node(&self, args: CreateArgs) -> Result<Value, String> {
+        let label: String = args.label.trim().chars().take(MAX_LABEL_CHARS).collect();
+        if label.is_empty() {
+            return Err("label must not be empty".to_string());
+        }
+        let mut node = Node::new_with_id(format!("ephemeral-{}", uuid::Uuid::new_v4()), None);
+        node.label = label.clone();
+        node.node_type = Some("ephemeral".to_string());
+        node.metadata
+            .insert("createdBy".to_string(), "chat".to_string());
+        if let Some(near) = args.near_node_id {
+            let anchor = find_node(&self.graph, near)?;
+            node.data.x = anchor.data.x + 5.0;
+            node.data.y = anchor.data.y + 5.0;
+            node.data.z = anchor.data.z;
+        }
+        let node_id = self
+            .graph_service
+            .send(AddEphemeralNode {
+                node,
+                ttl_secs: CREATED_NODE_TTL_SECS,
+            })
+            .await
+            .map_err(|e| format!("Graph service unavailable: {}", e))??;
+        Ok(json!({"id": node_id, "label": label, "expiresInSecs": CREATED_NODE_TTL_SECS}))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use visionclaw_domain::models::edge::Edge;
+
+    #[test]
+    fn sandbox_and_read_tools() {
+        let user_tools: Vec<String> = tool_definitions(ToolRole::User)
+            .iter()
+            .map(|t| t["function"]["name"].as_str().unwrap().to_string())
+            .collect();
+        assert!(!user_tools.contains(&"create_node".to_string()));
+        assert_eq!(tool_definitions(ToolRole::PowerUser).len(), TOOLS.len());
+
+        let mut sandbox = Sandbox::new(ToolRole::User);
+        assert!(sandbox.admit("create_node").is_err());
+        assert!(sandbox.admit("drop_tables").is_err());
+        for _ in 0..MAX_TOOL_CALLS {
+            assert!(sandbox.admit("search_nodes").is_ok());
+        }
+        assert!(sandbox.admit("search_nodes").is_err());
+        let mut sandbox = Sandbox::new(ToolRole::PowerUser);
+        for _ in 0..MAX_CREATED_NODES {
+            assert!(sandbox.admit("create_node").is_ok());
+        }
+        assert!(sandbox.admit("create_node").is_err());
+
+        let mut graph = GraphData::new();
+        for (id, name) in [(1, "Rust"), (2, "Rustls"), (3, "Trust"), (4, "Go")] {
+            let mut node = Node::new_with_id(format!("{}.md", name), Some(id));
+            node.label = name.to_string();
+            graph.nodes.push(node);
+        }
+        for (source, target) in [(2, 1), (3, 1), (1, 4), (3, 2)] {
+            graph.edges.push(Edge::new(source, target, 1.0));
+        }
+
+        let found = search_nodes(&graph, parse_args(&json!({"query": "rust"})).unwrap()).unwrap();
+        let ids: Vec<u64> = found["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|n| n["id"].as_u64().unwrap())
+            .collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+        assert_eq!(found_ids(&found).collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert!(parse_args::<SearchArgs>(&json!({"query": "x", "path": "/etc"})).is_err());
+
+        let neighbors = get_neighbors(
+            &graph,
+            parse_args(&json!({"node_id": 1, "limit": 500})).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(neighbors["total"], 3);
+        assert_eq!(neighbors["neighbors"][2]["direction"], "out");
+
+        let (_, action) = focus_subgraph(
+            &graph,
+            parse_args(&json!({"node_id": 4, "depth": 9})).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            action,
+            ClientAction::Focus {
+                node_id: 4,
+                node_ids: vec![4, 1, 2, 3]
+            }
+        );
+        assert!(focus_subgraph(&graph, parse_args(&json!({"node_id": 99})).unwrap()).is_err());
+
+        let stats = graph_stats(
+            &graph,
+            &HashMap::new(),
+            parse_args(&json!({"kind": "backlinks", "target": "Rust"})).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(stats["facts"]["backlinks"], 2);
+    }
+}
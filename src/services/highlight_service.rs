@@ -0,0 +1,368 @@
+//! Conversation-scoped highlight layers.
+//!
+//! When a chat answer cites nodes or the chat agent focuses a subgraph, those
+//! nodes are highlighted as a layer belonging to the user, and conversation,
+//! that produced it. Sessions are sent:
+//!
+//! ```json
+//! {"type": "highlightLayer", "action": "add", "layer": {"id": 7, "owner": "npub…", "conversationId": "abc", "reason": "focus", "nodeIds": [3, 17], "focusNode": 3, "shared": false, "createdAt": 1760000000000, "expiresAt": 1760000300000}}
+//! {"type": "highlightLayer", "action": "remove", "layer": {...}}
+//! ```
+//!
+//! A layer goes only to its owner's sessions until the owner shares it, and
+//! then to every signed-in session; unsharing removes it from everyone else.
+//! An `add` for a layer id the client already has replaces it.
+//! Layers are kept in memory only and expire after their TTL (default
+//! [`DEFAULT_TTL_SECS`], at most [`MAX_TTL_SECS`]). A user holds at most
+//! [`MAX_LAYERS_PER_USER`]; adding more drops their oldest. Clients that
+//! connect late fetch the layers they can see from `GET /api/highlights`.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::RwLock;
+
+/// Lifetime of a layer when none is asked for.
+pub const DEFAULT_TTL_SECS: u64 = 300;
+
+/// Longest lifetime a layer may ask for.
+pub const MAX_TTL_SECS: u64 = 3600;
+
+/// Layers one user may hold at once.
+pub const MAX_LAYERS_PER_USER: usize = 20;
+
+/// Nodes one layer may highlight.
+pub const MAX_NODES_PER_LAYER: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HighlightReason {
+    /// Nodes a chat answer cites.
+    Citation,
+    /// A subgraph the chat agent focused.
+    Focus,
+    /// Requested directly by a client.
+    Manual,
+    /// Pages created since the user's last visit.
+    SinceLastVisit,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HighlightLayer {
+    pub id: u64,
+    pub owner: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conversation_id: Option<String>,
+    pub reason: HighlightReason,
+    pub node_ids: Vec<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focus_node: Option<u32>,
+    pub shared: bool,
+    /// Milliseconds since the Unix epoch.
+    pub created_at: i64,
+    pub expires_at: i64,
+}
+
+impl HighlightLayer {
+    pub fn is_visible_to(&self, pubkey: &str) -> bool {
+        self.shared || self.owner == pubkey
+    }
+}
+
+/// A layer to add.
+#[derive(Debug, Clone)]
+pub struct NewHighlight {
+    pub owner: String,
+    pub conversation_id: Option<String>,
+    pub reason: HighlightReason,
+    pub node_ids: Vec<u32>,
+    pub focus_node: Option<u32>,
+    pub ttl_secs: Option<u64>,
+    pub shared: bool,
+}
+
+impl NewHighlight {
+    pub fn new(owner: impl Into<String>, reason: HighlightReason, node_ids: Vec<u32>) -> Self {
+        Self {
+            owner: owner.into(),
+            conversation_id: None,
+            reason,
+            node_ids,
+            focus_node: None,
+            ttl_secs: None,
+            shared: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HighlightAction {
+    Add,
+    Remove,
+}
+
+/// Which sessions a layer event goes to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HighlightAudience {
+    /// Everyone who can see the layer.
+    Viewers,
+    /// Everyone but the owner, e.g. when a layer is unshared.
+    Others,
+}
+
+impl HighlightAudience {
+    pub fn accepts(&self, layer: &HighlightLayer, pubkey: &str) -> bool {
+        match self {
+            Self::Viewers => layer.is_visible_to(pubkey),
+            Self::Others => layer.owner != pubkey,
+        }
+    }
+}
+
+/// The `highlightLayer` frame for an event.
+pub fn layer_message(action: HighlightAction, layer: &HighlightLayer) -> String {
+    serde_json::json!({
+        "type": "highlightLayer",
+        "action": action,
+        "layer": layer,
+    })
+    .to_string()
+}
+
+#[derive(Debug, Default)]
+pub struct HighlightStore {
+    next_id: u64,
+    layers: Vec<HighlightLayer>,
+}
+
+impl HighlightStore {
+    /// Add a layer. Returns it and any of the owner's older layers dropped to
+    /// make room.
+    pub fn add(
+        &mut self,
+        new: NewHighlight,
+        now: i64,
+    ) -> Result<(HighlightLayer, Vec<HighlightLayer>), String> {
+        let mut seen = HashSet::new();
+        let node_ids: Vec<u32> = new
+            .node_ids
+            .into_iter()
+            .filter(|id| seen.insert(*id))
+            .collect();
+        if node_ids.is_empty() {
+            return Err("A highlight needs at least one node".to_string());
+        }
+        if node_ids.len() > MAX_NODES_PER_LAYER {
+            return Err(format!(
+                "A highlight may cover at most {} nodes",
+                MAX_NODES_PER_LAYER
+            ));
+        }
+        let ttl = new
+            .ttl_secs
+            .unwrap_or(DEFAULT_TTL_SECS)
+            .clamp(1, MAX_TTL_SECS);
+
+        let mut evicted = Vec::new();
+        while self.layers.iter().filter(|l| l.owner == new.owner).count() >= MAX_LAYERS_PER_USER {
+            let oldest = self
+                .layers
+                .iter()
+                .position(|l| l.owner == new.owner)
+                .expect("owner has layers");
+            evicted.push(self.layers.remove(oldest));
+        }
+
+        self.next_id += 1;
+        let layer = HighlightLayer {
+            id: self.next_id,
+            owner: new.owner,
+            conversation_id: new.conversation_id,
+            reason: new.reason,
+            node_ids,
+            focus_node: new.focus_node,
+            shared: new.shared,
+            created_at: now,
+            expires_at: now + ttl as i64 * 1000,
+        };
+        self.layers.push(layer.clone());
+        Ok((layer, evicted))
+    }
+
+    /// Layers `pubkey` can see, oldest first.
+    pub fn visible_to(&self, pubkey: &str, now: i64) -> Vec<HighlightLayer> {
+        self.layers
+            .iter()
+            .filter(|l| l.expires_at > now && l.is_visible_to(pubkey))
+            .cloned()
+            .collect()
+    }
+
+    /// Remove `owner`'s layer `id`.
+    pub fn remove(&mut self, owner: &str, id: u64) -> Option<HighlightLayer> {
+        let index = self
+            .layers
+            .iter()
+            .position(|l| l.id == id && l.owner == owner)?;
+        Some(self.layers.remove(index))
+    }
+
+    /// Remove every layer `owner` has in a conversation.
+    pub fn remove_conversation(
+        &mut self,
+        owner: &str,
+        conversation_id: &str,
+    ) -> Vec<HighlightLayer> {
+        let (removed, kept) = std::mem::take(&mut self.layers).into_iter().partition(|l| {
+            l.owner == owner && l.conversation_id.as_deref() == Some(conversation_id)
+        });
+        self.layers = kept;
+        removed
+    }
+
+    /// Share or unshare `owner`'s layer `id`; `None` if there is no such
+    /// layer.
+    pub fn set_shared(&mut self, owner: &str, id: u64, shared: bool) -> Option<HighlightLayer> {
+        let layer = self
+            .layers
+            .iter_mut()
+            .find(|l| l.id == id && l.owner == owner)?;
+        layer.shared = shared;
+        Some(layer.clone())
+    }
+
+    /// Remove layer `id` if it has expired by `now`.
+    pub fn expire(&mut self, id: u64, now: i64) -> Option<HighlightLayer> {
+        let index = self
+            .layers
+            .iter()
+            .position(|l| l.id == id && l.expires_at <= now)?;
+        Some(self.layers.remove(index))
+    }
+}
+
+/// Process-wide highlight layers.
+#[derive(Default)]
+pub struct HighlightService {
+    store: RwLock<HighlightStore>,
+}
+
+fn now_ms() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+impl HighlightService {
+    fn store(&self) -> std::sync::RwLockWriteGuard<'_, HighlightStore> {
+        self.store.write().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn add(&self, new: NewHighlight) -> Result<(HighlightLayer, Vec<HighlightLayer>), String> {
+        self.store().add(new, now_ms())
+    }
+
+    pub fn visible_to(&self, pubkey: &str) -> Vec<HighlightLayer> {
+        self.store
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .visible_to(pubkey, now_ms())
+    }
+
+    pub fn remove(&self, owner: &str, id: u64) -> Option<HighlightLayer> {
+        self.store().remove(owner, id)
+    }
+
+    pub fn remove_conversation(&self, owner: &str, conversation_id: &str) -> Vec<HighlightLayer> {
+        self.store().remove_conversation(owner, conversation_id)
+    }
+
+    pub fn set_shared(&self, owner: &str, id: u64, shared: bool) -> Option<HighlightLayer> {
+        self.store().set_shared(owner, id, shared)
+    }
+
+    pub fn expire(&self, id: u64) -> Option<HighlightLayer> {
+        self.store().expire(id, now_ms())
+    }
+}
+
+static SERVICE: Lazy<HighlightService> = Lazy::new(HighlightService::default);
+
+pub fn highlights() -> &'static HighlightService {
+    &SERVICE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layers_are_scoped_shared_and_expire() {
+        let mut store = HighlightStore::default();
+        let (layer, evicted) = store
+            .add(
+                NewHighlight {
+                    conversation_id: Some("chat-1".to_string()),
+                    ttl_secs: Some(60),
+                    ..NewHighlight::new("alice", HighlightReason::Citation, vec![3, 17, 3])
+                },
+                1_000,
+            )
+            .unwrap();
+        assert!(evicted.is_empty());
+        assert_eq!((layer.id, layer.node_ids.clone()), (1, vec![3, 17]));
+        assert_eq!(layer.expires_at, 61_000);
+        assert!(store
+            .add(
+                NewHighlight::new("alice", HighlightReason::Manual, vec![]),
+                1_000
+            )
+            .is_err());
+
+        assert_eq!(store.visible_to("alice", 2_000).len(), 1);
+        assert!(store.visible_to("bob", 2_000).is_empty());
+        assert!(store.set_shared("bob", layer.id, true).is_none());
+        let shared = store.set_shared("alice", layer.id, true).unwrap();
+        assert_eq!(store.visible_to("bob", 2_000), vec![shared.clone()]);
+        assert!(HighlightAudience::Others.accepts(&shared, "bob"));
+        assert!(!HighlightAudience::Others.accepts(&shared, "alice"));
+
+        assert!(store.expire(layer.id, 2_000).is_none());
+        assert!(store.visible_to("alice", 61_000).is_empty());
+        assert_eq!(store.expire(layer.id, 61_000).map(|l| l.id), Some(1));
+
+        for i in 0..MAX_LAYERS_PER_USER as u32 {
+            store
+                .add(
+                    NewHighlight::new("alice", HighlightReason::Focus, vec![i]),
+                    100_000,
+                )
+                .unwrap();
+        }
+        let (_, evicted) = store
+            .add(
+                NewHighlight::new("alice", HighlightReason::Focus, vec![99]),
+                100_000,
+            )
+            .unwrap();
+        assert_eq!(
+            evicted.iter().map(|l| l.node_ids[0]).collect::<Vec<_>>(),
+            vec![0]
+        );
+
+        store
+            .add(
+                NewHighlight {
+                    conversation_id: Some("chat-2".to_string()),
+                    ..NewHighlight::new("bob", HighlightReason::Focus, vec![5])
+                },
+                100_000,
+            )
+            .unwrap();
+        assert_eq!(store.remove_conversation("alice", "chat-2").len(), 0);
+        assert_eq!(store.remove_conversation("bob", "chat-2").len(), 1);
+        assert!(store.remove("bob", 2).is_none());
+        assert!(store.remove("alice", 2).is_none());
+        assert!(store.remove("alice", 3).is_some());
+    }
+}
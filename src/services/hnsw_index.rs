@@ -0,0 +1,377 @@
+//! In-memory HNSW index over page embeddings.
+//!
+//! A hierarchical navigable small world graph (Malkov & Yashunin) keyed by
+//! node id, using cosine similarity. Unlike a batch-built index it supports
+//! inserting, replacing and removing single vectors, so an embedding sync
+//! only touches the pages that changed. A node's level is derived from its
+//! id, which keeps the index deterministic across rebuilds.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// Links per node on the upper layers; layer 0 keeps twice as many.
+pub const DEFAULT_M: usize = 16;
+
+/// Candidates considered while inserting.
+pub const DEFAULT_EF_CONSTRUCTION: usize = 100;
+
+const MAX_LEVEL: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Scored {
+    distance: f32,
+    id: u32,
+}
+
+impl Eq for Scored {}
+
+impl Ord for Scored {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance
+            .total_cmp(&other.distance)
+            .then(self.id.cmp(&other.id))
+    }
+}
+
+impl PartialOrd for Scored {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct HnswNode {
+    vector: Vec<f32>,
+    /// Neighbours on each layer, from 0 up to the node's level.
+    links: Vec<Vec<u32>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct HnswIndex {
+    m: usize,
+    ef_construction: usize,
+    nodes: HashMap<u32, HnswNode>,
+    entry: Option<u32>,
+}
+
+impl Default for HnswIndex {
+    fn default() -> Self {
+        Self::new(DEFAULT_M, DEFAULT_EF_CONSTRUCTION)
+    }
+}
+
+fn normalise(vector: &[f32]) -> Vec<f32> {
+    let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm == 0.0 {
+        return vector.to_vec();
+    }
+    vector.iter().map(|x| x / norm).collect()
+}
+
+fn distance(a: &[f32], b: &[f32]) -> f32 {
+    1.0 - a.iter().zip(b).map(|(x, y)| x * y).sum::<f32>()
+}
+
+impl HnswIndex {
+    pub fn new(m: usize, ef_construction: usize) -> Self {
+        Self {
+            m: m.max(2),
+            ef_construction: ef_construction.max(1),
+            nodes: HashMap::new(),
+            entry: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn contains(&self, id: u32) -> bool {
+        self.nodes.contains_key(&id)
+    }
+
+    /// The stored (normalised) vector for `id`.
+    pub fn vector(&self, id: u32) -> Option<&[f32]> {
+        self.nodes.get(&id).map(|n| n.vector.as_slice())
+    }
+
+    fn max_links(&self, layer: usize) -> usize {
+        if layer == 0 {
+            self.m * 2
+        } else {
+            self.m
+        }
+    }
+
+    /// Level for `id`, geometrically distributed with ratio `1/m`.
+    fn level_of(&self, id: u32) -> usize {
+        // splitmix64 of the id gives a uniform draw in (0, 1].
+        let mut z = (id as u64).wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        let uniform = ((z >> 11) as f64 + 1.0) / (1u64 << 53) as f64;
+        let level = -uniform.ln() / (self.m as f64).ln();
+        (level as usize).min(MAX_LEVEL)
+    }
+
+    fn level(&self, id: u32) -> usize {
+        self.nodes[&id].links.len() - 1
+    }
+
+    fn distance_to(&self, query: &[f32], id: u32) -> f32 {
+        distance(query, &self.nodes[&id].vector)
+    }
+
+    /// Best `ef` nodes on `layer` reachable from `entries`, nearest first.
+    fn search_layer(&self, query: &[f32], entries: &[u32], ef: usize, layer: usize) -> Vec<Scored> {
+        let mut visited: HashSet<u32> = entries.iter().copied().collect();
+        let mut candidates = BinaryHeap::new();
+        let mut found: BinaryHeap<Scored> = BinaryHeap::new();
+        for &id in entries {
+            let scored = Scored {
+                distance: self.distance_to(query, id),
+                id,
+            };
+            candidates.push(std::cmp::Reverse(scored));
+            found.push(scored);
+        }
+        while found.len() > ef {
+            found.pop();
+        }
+        while let Some(std::cmp::Reverse(current)) = candidates.pop() {
+            if found.len() >= ef && found.peek().is_some_and(|w| current.distance > w.distance) {
+                break;
+            }
+            let Some(links) = self.nodes[&current.id].links.get(layer) else {
+                continue;
+            };
+            for &next in links {
+                if !visited.insert(next) {
+                    continue;
+                }
+                let scored = Scored {
+                    distance: self.distance_to(query, next),
+                    id: next,
+                };
+                if found.len() < ef || found.peek().is_some_and(|w| scored < *w) {
+                    candidates.push(std::cmp::Reverse(scored));
+                    found.push(scored);
+                    if found.len() > ef {
+                        found.pop();
+                    }
+                }
+            }
+        }
+        found.into_sorted_vec()
+    }
+
+    /// Walk down from the entry point to `layer + 1`, keeping the nearest node.
+    fn descend(&self, query: &[f32], to_layer: usize) -> Option<u32> {
+        let mut current = self.entry?;
+        for layer in (to_layer + 1..=self.level(current)).rev() {
+            current = self.search_layer(query, &[current], 1, layer)[0].id;
+        }
+        Some(current)
+    }
+
+    /// Keep the `max` links of `id` on `layer` nearest to it.
+    fn prune(&mut self, id: u32, layer: usize) {
+        let max = self.max_links(layer);
+        let node = &self.nodes[&id];
+        if node.links[layer].len() <= max {
+            return;
+        }
+        let mut scored: Vec<Scored> = node.links[layer]
+            .iter()
+            .map(|&other| Scored {
+                distance: distance(&node.vector, &self.nodes[&other].vector),
+                id: other,
+            })
+            .collect();
+        scored.sort();
+        scored.truncate(max);
+        self.nodes.get_mut(&id).unwrap().links[layer] = scored.iter().map(|s| s.id).collect();
+    }
+
+    /// Insert `vector` for `id`, replacing any vector it already has.
+    pub fn insert(&mut self, id: u32, vector: &[f32]) {
+        self.remove(id);
+        let vector = normalise(vector);
+        let level = self.level_of(id);
+        let Some(entry) = self.entry else {
+            self.nodes.insert(
+                id,
+                HnswNode {
+                    vector,
+                    links: vec![Vec::new(); level + 1],
+                },
+            );
+            self.entry = Some(id);
+            return;
+        };
+
+        let top = self.level(entry);
+        let mut entries = vec![self.descend(&vector, level.min(top)).unwrap_or(entry)];
+        let mut links = vec![Vec::new(); level + 1];
+        for layer in (0..=level.min(top)).rev() {
+            let found = self.search_layer(&vector, &entries, self.ef_construction, layer);
+            links[layer] = found
+                .iter()
+                .take(self.max_links(layer))
+                .map(|s| s.id)
+                .collect();
+            entries = found.iter().map(|s| s.id).collect();
+        }
+        self.nodes.insert(
+            id,
+            HnswNode {
+                vector,
+                links: links.clone(),
+            },
+        );
+        for (layer, neighbours) in links.iter().enumerate() {
+            for &other in neighbours {
+                self.nodes.get_mut(&other).unwrap().links[layer].push(id);
+                self.prune(other, layer);
+            }
+        }
+        if level > top {
+            self.entry = Some(id);
+        }
+    }
+
+    /// Remove `id`, reconnecting the nodes that linked to it. Returns whether
+    /// it was present.
+    pub fn remove(&mut self, id: u32) -> bool {
+        let Some(removed) = self.nodes.remove(&id) else {
+            return false;
+        };
+        let mut orphaned: Vec<(u32, usize)> = Vec::new();
+        for (&other, node) in self.nodes.iter_mut() {
+            for (layer, links) in node.links.iter_mut().enumerate() {
+                let before = links.len();
+                links.retain(|&l| l != id);
+                if links.len() != before {
+                    orphaned.push((other, layer));
+                }
+            }
+        }
+        // Offer each former neighbour the removed node's neighbours instead.
+        for (other, layer) in orphaned {
+            let candidates: Vec<u32> = removed.links[layer]
+                .iter()
+                .copied()
+                .filter(|&c| c != other && self.nodes.contains_key(&c))
+                .collect();
+            let links = &mut self.nodes.get_mut(&other).unwrap().links[layer];
+            for c in candidates {
+                if !links.contains(&c) {
+                    links.push(c);
+                }
+            }
+            self.prune(other, layer);
+        }
+        if self.entry == Some(id) {
+            self.entry = self
+                .nodes
+                .iter()
+                .max_by_key(|(&other, node)| (node.links.len(), std::cmp::Reverse(other)))
+                .map(|(&other, _)| other);
+        }
+        true
+    }
+
+    /// The `k` nodes most similar to `query`, as `(id, cosine similarity)`,
+    /// most similar first. `ef` (at least `k`) trades speed for recall.
+    pub fn search(&self, query: &[f32], k: usize, ef: usize) -> Vec<(u32, f32)> {
+        if k == 0 {
+            return Vec::new();
+        }
+        let query = normalise(query);
+        let Some(entry) = self.descend(&query, 0) else {
+            return Vec::new();
+        };
+        self.search_layer(&query, &[entry], ef.max(k), 0)
+            .into_iter()
+            .take(k)
+            .map(|s| (s.id, 1.0 - s.distance))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vector(seed: u32) -> Vec<f32> {
+        let mut state = seed as u64 * 2_654_435_761 + 1;
+        (0..12)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6_364_136_223_846_793_005)
+                    .wrapping_add(1_442_695_040_888_963_407);
+                (state >> 40) as f32 / (1u64 << 24) as f32 - 0.5
+            })
+            .collect()
+    }
+
+    fn exact(index_vectors: &HashMap<u32, Vec<f32>>, query: &[f32], k: usize) -> Vec<u32> {
+        let query = normalise(query);
+        let mut scored: Vec<Scored> = index_vectors
+            .iter()
+            .map(|(&id, v)| Scored {
+                distance: distance(&query, &normalise(v)),
+                id,
+            })
+            .collect();
+        scored.sort();
+        scored.into_iter().take(k).map(|s| s.id).collect()
+    }
+
+    #[test]
+    fn matches_exact_search_through_inserts_and_removals() {
+        let mut index = HnswIndex::new(4, 32);
+        let mut vectors = HashMap::new();
+        for id in 0..300 {
+            index.insert(id, &vector(id));
+            vectors.insert(id, vector(id));
+        }
+        for id in (0..300).step_by(3) {
+            assert!(index.remove(id));
+            vectors.remove(&id);
+        }
+        assert!(!index.remove(0));
+        // Replacing a vector moves the node.
+        index.insert(1, &vector(1000));
+        vectors.insert(1, vector(1000));
+        assert_eq!(index.len(), 200);
+
+        let hits = index.search(&vector(1000), 1, 50);
+        assert_eq!(hits[0].0, 1);
+        assert!((hits[0].1 - 1.0).abs() < 1e-5);
+
+        let mut recalled = 0;
+        for seed in 0..20 {
+            let query = vector(seed * 7 + 5000);
+            let expected = exact(&vectors, &query, 5);
+            let got: Vec<u32> = index
+                .search(&query, 5, 64)
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect();
+            recalled += expected.iter().filter(|id| got.contains(id)).count();
+        }
+        assert!(recalled >= 95, "recall {}/100", recalled);
+
+        for id in vectors.keys().copied().collect::<Vec<_>>() {
+            index.remove(id);
+        }
+        assert!(index.is_empty());
+        assert!(index.search(&vector(1), 3, 10).is_empty());
+    }
+}
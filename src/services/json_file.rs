@@ -0,0 +1,99 @@
+//! One JSON file in the data directory behind a small in-memory store.
+//!
+//! Tag colours, XR anchors, audio cue preferences, settings history,
+//! embeddings, API costs, visits and the simulation quality level each keep
+//! their state in memory and rewrite a whole file when it changes; the daily
+//! digest keeps the snapshot it diffs against the same way.
+//! [`JsonFile`] is that file: a missing or unreadable file loads as the
+//! default, and a save creates the directory and replaces the file through
+//! a temporary sibling so a crash mid-write never leaves half a file behind.
+
+use log::warn;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone)]
+pub struct JsonFile {
+    path: PathBuf,
+    /// What the file holds, for log and error messages.
+    what: &'static str,
+    pretty: bool,
+}
+
+impl JsonFile {
+    pub fn new(path: PathBuf, what: &'static str) -> Self {
+        Self {
+            path,
+            what,
+            pretty: false,
+        }
+    }
+
+    /// Indented output, for files people may read or edit by hand.
+    pub fn pretty(mut self) -> Self {
+        self.pretty = true;
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The stored value, or the default if the file is missing or unreadable.
+    pub fn load<T: DeserializeOwned + Default>(&self) -> T {
+        let Ok(json) = std::fs::read_to_string(&self.path) else {
+            return T::default();
+        };
+        serde_json::from_str(&json).unwrap_or_else(|e| {
+            warn!(
+                "Ignoring unreadable {} in {}: {}",
+                self.what,
+                self.path.display(),
+                e
+            );
+            T::default()
+        })
+    }
+
+    pub fn save<T: Serialize + ?Sized>(&self, value: &T) -> Result<(), String> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        let json = if self.pretty {
+            serde_json::to_string_pretty(value)
+        } else {
+            serde_json::to_string(value)
+        }
+        .map_err(|e| format!("Failed to serialise {}: {}", self.what, e))?;
+
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+        std::fs::write(&tmp, json)
+            .and_then(|_| std::fs::rename(&tmp, &self.path))
+            .map_err(|e| format!("Failed to write {}: {}", self.path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn missing_or_unreadable_files_load_as_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = JsonFile::new(dir.path().join("nested/store.json"), "test store");
+        assert!(file.load::<BTreeMap<String, u32>>().is_empty());
+
+        let value = BTreeMap::from([("a".to_string(), 1u32)]);
+        file.save(&value).unwrap();
+        assert_eq!(file.load::<BTreeMap<String, u32>>(), value);
+        assert!(!dir.path().join("nested/store.json.tmp").exists());
+
+        std::fs::write(file.path(), "{not json").unwrap();
+        assert!(file.load::<BTreeMap<String, u32>>().is_empty());
+    }
+}
@@ -0,0 +1,197 @@
+//! Rolling history of layout keyframes for time-travel playback.
+//!
+//! `GraphStateActor` samples node positions every [`KEYFRAME_INTERVAL`] into a
+//! ring buffer that keeps the last [`RETENTION`] of layout evolution. Clients
+//! scrub through it over REST (`/api/layout/history`) or the socket
+//! (`layoutPlayback` / `layoutHistorySeek`), which is handy for demos and for
+//! working out when and how a graph "exploded".
+//!
+//! A settled layout produces identical frames; those share the previous
+//! frame's node buffer, so an idle graph costs one `Arc` per keyframe.
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use super::camera_recording_service::FramePosition;
+
+pub const KEYFRAME_INTERVAL: Duration = Duration::from_secs(5);
+pub const RETENTION: Duration = Duration::from_secs(60 * 60);
+
+/// Largest per-node displacement still treated as "unchanged".
+const SETTLED_EPSILON: f32 = 0.01;
+
+static LAYOUT_HISTORY: Lazy<LayoutHistory> = Lazy::new(|| {
+    LayoutHistory::new(
+        KEYFRAME_INTERVAL.as_millis() as i64,
+        (RETENTION.as_secs() / KEYFRAME_INTERVAL.as_secs()) as usize,
+    )
+});
+
+/// Process-wide keyframe store shared by the graph actor and handlers.
+pub fn layout_history() -> &'static LayoutHistory {
+    &LAYOUT_HISTORY
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LayoutKeyframe {
+    /// Unix time in milliseconds.
+    pub t: i64,
+    pub nodes: Arc<Vec<FramePosition>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyframeSummary {
+    pub t: i64,
+    pub node_count: usize,
+}
+
+#[derive(Debug)]
+pub struct LayoutHistory {
+    interval_ms: i64,
+    capacity: usize,
+    frames: Mutex<VecDeque<LayoutKeyframe>>,
+}
+
+fn unchanged(a: &[FramePosition], b: &[FramePosition]) -> bool {
+    a.len() == b.len()
+        && a.iter().zip(b).all(|(p, q)| {
+            p.id == q.id
+                && (p.x - q.x).abs() <= SETTLED_EPSILON
+                && (p.y - q.y).abs() <= SETTLED_EPSILON
+                && (p.z - q.z).abs() <= SETTLED_EPSILON
+        })
+}
+
+impl LayoutHistory {
+    pub fn new(interval_ms: i64, capacity: usize) -> Self {
+        Self {
+            interval_ms: interval_ms.max(1),
+            capacity: capacity.max(1),
+            frames: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub fn interval_ms(&self) -> i64 {
+        self.interval_ms
+    }
+
+    pub fn retention_ms(&self) -> i64 {
+        self.interval_ms * self.capacity as i64
+    }
+
+    /// Whether a keyframe taken at `t` would be kept. Lets callers skip
+    /// collecting positions when the sampler fires early.
+    pub fn is_due(&self, t: i64) -> bool {
+        let frames = self.frames.lock().unwrap_or_else(|e| e.into_inner());
+        !matches!(frames.back(), Some(last) if t - last.t < self.interval_ms)
+    }
+
+    /// Append a keyframe at `t`, evicting the oldest once full. Frames closer
+    /// than the interval to the previous one are dropped.
+    pub fn record(&self, t: i64, nodes: Vec<FramePosition>) -> bool {
+        let mut frames = self.frames.lock().unwrap_or_else(|e| e.into_inner());
+        let nodes = match frames.back() {
+            Some(last) if t - last.t < self.interval_ms => return false,
+            Some(last) if unchanged(&last.nodes, &nodes) => Arc::clone(&last.nodes),
+            _ => Arc::new(nodes),
+        };
+        if frames.len() == self.capacity {
+            frames.pop_front();
+        }
+        frames.push_back(LayoutKeyframe { t, nodes });
+        true
+    }
+
+    pub fn timeline(&self) -> Vec<KeyframeSummary> {
+        let frames = self.frames.lock().unwrap_or_else(|e| e.into_inner());
+        frames
+            .iter()
+            .map(|f| KeyframeSummary {
+                t: f.t,
+                node_count: f.nodes.len(),
+            })
+            .collect()
+    }
+
+    /// The keyframe in effect at `t`: the latest one taken at or before it,
+    /// or the oldest retained frame if `t` predates the history.
+    pub fn at(&self, t: i64) -> Option<LayoutKeyframe> {
+        let frames = self.frames.lock().unwrap_or_else(|e| e.into_inner());
+        let idx = frames.partition_point(|f| f.t <= t);
+        frames.get(idx.saturating_sub(1)).cloned()
+    }
+
+    pub fn clear(&self) {
+        self.frames
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(x: f32) -> Vec<FramePosition> {
+        vec![
+            FramePosition {
+                id: 1,
+                x,
+                y: 0.0,
+                z: 0.0,
+            },
+            FramePosition {
+                id: 2,
+                x: -x,
+                y: 1.0,
+                z: 0.0,
+            },
+        ]
+    }
+
+    #[test]
+    fn keeps_the_most_recent_frames_at_the_interval() {
+        let history = LayoutHistory::new(5_000, 3);
+        assert!(history.record(0, frame(0.0)));
+        assert!(!history.record(1_000, frame(1.0)), "too soon");
+        for (i, t) in [5_000, 10_000, 15_000].into_iter().enumerate() {
+            assert!(history.record(t, frame(i as f32 + 1.0)));
+        }
+        let times: Vec<i64> = history.timeline().iter().map(|f| f.t).collect();
+        assert_eq!(times, vec![5_000, 10_000, 15_000]);
+        assert!(!history.is_due(19_999));
+        assert!(history.is_due(20_000));
+    }
+
+    #[test]
+    fn seeks_to_the_frame_in_effect() {
+        let history = LayoutHistory::new(5_000, 10);
+        history.record(5_000, frame(1.0));
+        history.record(10_000, frame(2.0));
+
+        assert_eq!(history.at(9_999).unwrap().t, 5_000);
+        assert_eq!(history.at(10_000).unwrap().t, 10_000);
+        assert_eq!(history.at(60_000).unwrap().t, 10_000);
+        assert_eq!(history.at(0).unwrap().t, 5_000, "clamps to the oldest");
+        assert!(LayoutHistory::new(5_000, 10).at(0).is_none());
+    }
+
+    #[test]
+    fn settled_frames_share_storage() {
+        let history = LayoutHistory::new(5_000, 10);
+        history.record(0, frame(1.0));
+        history.record(5_000, frame(1.001));
+        history.record(10_000, frame(3.0));
+        let a = history.at(0).unwrap();
+        let b = history.at(5_000).unwrap();
+        let c = history.at(10_000).unwrap();
+        assert!(Arc::ptr_eq(&a.nodes, &b.nodes));
+        assert!(!Arc::ptr_eq(&b.nodes, &c.nodes));
+    }
+}
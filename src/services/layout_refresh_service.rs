@@ -0,0 +1,492 @@
+//! Scheduled layout refresh proposals.
+//!
+//! A long-running server's layout drifts: pages added over weeks land
+//! wherever there was room, and the live simulation only ever relaxes
+//! locally. With `layoutRefresh.enabled` set, this service periodically lays
+//! the graph out again from scratch in a CPU shadow simulation
+//! (`visionclaw-physics`) driven by the live knowledge-graph physics
+//! settings, leaving the live layout untouched, and scores both layouts with
+//! normalised stress. When the fresh one is at least
+//! `minImprovement` better, clients are offered it:
+//!
+//!   layoutProposal { proposal: { id, currentStress, proposedStress, ... } }
+//!
+//! Nothing moves until someone applies it (`applyLayoutProposal` on the
+//! socket or `POST /api/layout/proposal/apply`). The positions are then
+//! matched to the current graph by metadata id, since a reload renumbers
+//! nodes, seeded into the GPU layout and clients animate to them:
+//!
+//!   layoutProposalApplied { id, applied, transitionMs }
+//!
+//! One proposal is open at a time; it lapses after [`PROPOSAL_TTL`], and a
+//! newer one or a dismissal replaces it (`layoutProposalDismissed { id }`).
+//! A proposal most of whose nodes have since left the graph is withdrawn
+//! rather than applied.
+
+use actix::Addr;
+use chrono::{DateTime, Utc};
+use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use visionclaw_physics::{
+    sample_sources, stress, CenterGravity, Collision, Edge as PhysicsEdge, ForcePipeline, Graph,
+    Integrator, Layout, Repulsion, Spring,
+};
+
+use crate::actors::client_coordinator_actor::ClientCoordinatorActor;
+use crate::actors::graph_service_supervisor::GraphServiceSupervisor;
+use crate::actors::messages::{BroadcastMessage, GetGraphData, GetSettings};
+use crate::actors::optimized_settings_actor::OptimizedSettingsActor;
+use crate::config::{LayoutRefreshSettings, PhysicsSettings};
+use crate::models::graph::GraphData;
+
+/// How long a proposal can be applied.
+pub const PROPOSAL_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Client animation time for an applied proposal unless the applier asks
+/// for another.
+pub const DEFAULT_TRANSITION_MS: u64 = 1500;
+pub const MAX_TRANSITION_MS: u64 = 10_000;
+
+/// BFS sources used to sample stress.
+const STRESS_SOURCES: usize = 64;
+
+/// Poll interval while refresh is disabled, so enabling it via the settings
+/// API takes effect without a restart.
+const DISABLED_POLL: Duration = Duration::from_secs(600);
+
+static LAYOUT_PROPOSALS: Lazy<LayoutProposals> = Lazy::new(LayoutProposals::default);
+
+/// Process-wide open proposal, shared by the scheduler and handlers.
+pub fn layout_proposals() -> &'static LayoutProposals {
+    &LAYOUT_PROPOSALS
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LayoutProposal {
+    pub id: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub node_count: usize,
+    pub current_stress: f32,
+    pub proposed_stress: f32,
+    /// Relative stress reduction, `1 - proposed / current`.
+    pub improvement: f32,
+    /// Node metadata id to proposed position.
+    #[serde(skip)]
+    pub positions: Arc<HashMap<String, [f32; 3]>>,
+}
+
+impl LayoutProposal {
+    /// The proposed positions keyed by the ids `graph` gives those nodes
+    /// now. Nodes that have left the graph are dropped.
+    pub fn seed_for(&self, graph: &GraphData) -> HashMap<u32, [f32; 3]> {
+        graph
+            .nodes
+            .iter()
+            .filter_map(|node| Some((node.id, *self.positions.get(&node.metadata_id)?)))
+            .collect()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct LayoutProposals {
+    open: Mutex<Option<LayoutProposal>>,
+}
+
+impl LayoutProposals {
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<LayoutProposal>> {
+        self.open.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// The open proposal, unless it has lapsed.
+    pub fn current(&self, now: DateTime<Utc>) -> Option<LayoutProposal> {
+        let mut open = self.lock();
+        if open.as_ref().is_some_and(|p| p.expires_at <= now) {
+            *open = None;
+        }
+        open.clone()
+    }
+
+    /// Open `proposal`, returning the one it replaces.
+    pub fn offer(&self, proposal: LayoutProposal) -> Option<LayoutProposal> {
+        self.lock().replace(proposal)
+    }
+
+    /// Close proposal `id` to apply it.
+    pub fn take(&self, id: &str, now: DateTime<Utc>) -> Result<LayoutProposal, String> {
+        let mut open = self.lock();
+        match open.take() {
+            Some(p) if p.id == id && p.expires_at > now => Ok(p),
+            Some(p) if p.id == id => Err("Layout proposal has expired".to_string()),
+            other => {
+                *open = other;
+                Err(format!("No open layout proposal '{}'", id))
+            }
+        }
+    }
+
+    /// Close proposal `id` without applying it.
+    pub fn dismiss(&self, id: &str) -> Option<LayoutProposal> {
+        let mut open = self.lock();
+        if open.as_ref().is_some_and(|p| p.id == id) {
+            open.take()
+        } else {
+            None
+        }
+    }
+}
+
+/// The shadow simulation's forces, set from the live `physics` so the
+/// proposal settles where the GPU would keep it.
+fn shadow_pipeline(physics: &PhysicsSettings) -> ForcePipeline {
+    ForcePipeline::new(Integrator {
+        dt: physics.dt,
+        damping: physics.damping,
+        max_force: physics.max_force,
+        max_velocity: physics.max_velocity,
+    })
+    .with(Spring {
+        spring_k: physics.spring_k,
+        rest_length: physics.rest_length,
+        lin_log: physics.lin_log_mode,
+    })
+    .with(Repulsion {
+        repel_k: physics.repel_k,
+        softening: physics.repulsion_softening_epsilon,
+        cutoff: physics.max_repulsion_dist,
+        max_force: physics.max_force,
+    })
+    .with(Collision {
+        radius: physics.separation_radius,
+        max_force: physics.max_force,
+    })
+    .with(CenterGravity {
+        k: physics.center_gravity_k,
+    })
+}
+
+/// Lay `graph` out afresh under `physics` for `iterations` steps and score
+/// it against the current positions. `None` for graphs too small to compare.
+pub fn propose(
+    graph: &GraphData,
+    physics: &PhysicsSettings,
+    iterations: u32,
+    now: DateTime<Utc>,
+) -> Option<LayoutProposal> {
+    let n = graph.nodes.len();
+    if n < 2 {
+        return None;
+    }
+    let index: HashMap<u32, usize> = graph
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(i, node)| (node.id, i))
+        .collect();
+    let edges = graph
+        .edges
+        .iter()
+        .filter_map(|e| {
+            let weight = if e.weight > 0.0 { e.weight } else { 1.0 };
+            Some(PhysicsEdge::weighted(
+                *index.get(&e.source)?,
+                *index.get(&e.target)?,
+                weight,
+            ))
+        })
+        .collect();
+    let physics_graph = Graph::new(n, edges);
+
+    let current: Vec<[f32; 3]> = graph
+        .nodes
+        .iter()
+        .map(|node| [node.data.x, node.data.y, node.data.z])
+        .collect();
+    let radius = (current
+        .iter()
+        .map(|p| p[0] * p[0] + p[1] * p[1] + p[2] * p[2])
+        .sum::<f32>()
+        / n as f32)
+        .sqrt();
+
+    let mut layout = Layout::spiral(n, radius.max(10.0));
+    let mut pipeline = shadow_pipeline(physics);
+    for _ in 0..iterations {
+        pipeline.step(&physics_graph, &mut layout);
+    }
+    if layout.positions.iter().flatten().any(|c| !c.is_finite()) {
+        warn!("[LayoutRefresh] Shadow simulation diverged; no proposal");
+        return None;
+    }
+
+    let sources = sample_sources(n, STRESS_SOURCES);
+    let current_stress = stress(&physics_graph, &current, &sources);
+    let proposed_stress = stress(&physics_graph, &layout.positions, &sources);
+    let improvement = if current_stress > 0.0 {
+        1.0 - proposed_stress / current_stress
+    } else {
+        0.0
+    };
+    let positions = graph
+        .nodes
+        .iter()
+        .zip(layout.positions)
+        .map(|(node, p)| (node.metadata_id.clone(), p))
+        .collect();
+
+    Some(LayoutProposal {
+        id: uuid::Uuid::new_v4().to_string(),
+        created_at: now,
+        expires_at: now + chrono::Duration::from_std(PROPOSAL_TTL).unwrap_or_default(),
+        node_count: n,
+        current_stress,
+        proposed_stress,
+        improvement,
+        positions: Arc::new(positions),
+    })
+}
+
+/// Whether a computed proposal is worth offering.
+pub fn worth_offering(proposal: &LayoutProposal, settings: &LayoutRefreshSettings) -> bool {
+    proposal.improvement > 0.0 && proposal.improvement >= settings.min_improvement
+}
+
+pub struct LayoutRefreshService {
+    graph_service_addr: Addr<GraphServiceSupervisor>,
+    settings_addr: Addr<OptimizedSettingsActor>,
+    client_manager_addr: Addr<ClientCoordinatorActor>,
+}
+
+impl LayoutRefreshService {
+    pub fn new(
+        graph_service_addr: Addr<GraphServiceSupervisor>,
+        settings_addr: Addr<OptimizedSettingsActor>,
+        client_manager_addr: Addr<ClientCoordinatorActor>,
+    ) -> Self {
+        Self {
+            graph_service_addr,
+            settings_addr,
+            client_manager_addr,
+        }
+    }
+
+    /// Refresh settings and the live knowledge-graph physics.
+    async fn current_settings(&self) -> (LayoutRefreshSettings, PhysicsSettings) {
+        match self.settings_addr.send(GetSettings).await {
+            Ok(Ok(settings)) => (
+                settings.layout_refresh.clone().unwrap_or_default(),
+                settings.get_physics("logseq").clone(),
+            ),
+            Ok(Err(e)) => {
+                warn!("[LayoutRefresh] Failed to read settings: {}", e);
+                Default::default()
+            }
+            Err(e) => {
+                warn!("[LayoutRefresh] Settings actor unreachable: {}", e);
+                Default::default()
+            }
+        }
+    }
+
+    /// Run forever, computing one proposal per `intervalMinutes`.
+    pub fn spawn(self: Arc<Self>) {
+        tokio::spawn(async move {
+            loop {
+                let (settings, _) = self.current_settings().await;
+                if !settings.enabled {
+                    tokio::time::sleep(DISABLED_POLL).await;
+                    continue;
+                }
+                tokio::time::sleep(Duration::from_secs(
+                    u64::from(settings.interval_minutes) * 60,
+                ))
+                .await;
+
+                // Re-read: refresh may have been disabled while we slept.
+                let (settings, physics) = self.current_settings().await;
+                if !settings.enabled {
+                    continue;
+                }
+                match self.run_once(&settings, &physics).await {
+                    Ok(Some(p)) => info!(
+                        "[LayoutRefresh] Offered layout {} ({:.0}% lower stress)",
+                        p.id,
+                        p.improvement * 100.0
+                    ),
+                    Ok(None) => debug!("[LayoutRefresh] Current layout is good enough"),
+                    Err(e) => warn!("[LayoutRefresh] Proposal failed: {}", e),
+                }
+            }
+        });
+    }
+
+    /// Compute a proposal and offer it if it clears `minImprovement`.
+    pub async fn run_once(
+        &self,
+        settings: &LayoutRefreshSettings,
+        physics: &PhysicsSettings,
+    ) -> Result<Option<LayoutProposal>, String> {
+        let proposal = compute_proposal(&self.graph_service_addr, settings, physics).await?;
+        if !worth_offering(&proposal, settings) {
+            return Ok(None);
+        }
+        offer(&self.client_manager_addr, proposal.clone());
+        Ok(Some(proposal))
+    }
+}
+
+/// Run the shadow simulation on the current graph under `physics`.
+pub async fn compute_proposal(
+    graph_service_addr: &Addr<GraphServiceSupervisor>,
+    settings: &LayoutRefreshSettings,
+    physics: &PhysicsSettings,
+) -> Result<LayoutProposal, String> {
+    let graph = graph_service_addr
+        .send(GetGraphData)
+        .await
+        .map_err(|e| format!("Graph service unavailable: {}", e))??;
+    if graph.nodes.len() > settings.max_nodes as usize {
+        return Err(format!(
+            "Graph has {} nodes; layout refresh is limited to {}",
+            graph.nodes.len(),
+            settings.max_nodes
+        ));
+    }
+    let iterations = settings.iterations;
+    let physics = physics.clone();
+    tokio::task::spawn_blocking(move || propose(&graph, &physics, iterations, Utc::now()))
+        .await
+        .map_err(|e| format!("Shadow simulation aborted: {}", e))?
+        .ok_or_else(|| "Graph is too small to re-lay out".to_string())
+}
+
+fn broadcast(client_manager_addr: &Addr<ClientCoordinatorActor>, message: serde_json::Value) {
+    client_manager_addr.do_send(BroadcastMessage {
+        message: message.to_string(),
+    });
+}
+
+/// Open `proposal` and tell every client, withdrawing the one it replaces.
+pub fn offer(client_manager_addr: &Addr<ClientCoordinatorActor>, proposal: LayoutProposal) {
+    if let Some(old) = layout_proposals().offer(proposal.clone()) {
+        announce_dismissed(client_manager_addr, &old.id);
+    }
+    broadcast(
+        client_manager_addr,
+        serde_json::json!({ "type": "layoutProposal", "proposal": proposal }),
+    );
+}
+
+pub fn announce_dismissed(client_manager_addr: &Addr<ClientCoordinatorActor>, id: &str) {
+    broadcast(
+        client_manager_addr,
+        serde_json::json!({ "type": "layoutProposalDismissed", "id": id }),
+    );
+}
+
+pub fn announce_applied(
+    client_manager_addr: &Addr<ClientCoordinatorActor>,
+    id: &str,
+    applied: usize,
+    transition_ms: u64,
+) {
+    broadcast(
+        client_manager_addr,
+        serde_json::json!({
+            "type": "layoutProposalApplied",
+            "id": id,
+            "applied": applied,
+            "transitionMs": transition_ms,
+        }),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::edge::Edge;
+    use crate::models::node::Node;
+
+    #[test]
+    fn proposes_a_lower_stress_layout_and_tracks_one_open_proposal() {
+        // A ring whose current layout has every node crossing the circle.
+        let mut graph = GraphData::new();
+        for i in 0..12u32 {
+            let mut node = Node::new_with_id(format!("n{}", i), Some(i + 1));
+            let angle = (i * 5 % 12) as f32 / 12.0 * std::f32::consts::TAU;
+            node.data.x = 100.0 * angle.cos();
+            node.data.y = 100.0 * angle.sin();
+            node.data.z = 0.0;
+            graph.nodes.push(node);
+            graph.edges.push(Edge::new(i + 1, (i + 1) % 12 + 1, 1.0));
+        }
+
+        let now = Utc::now();
+        let proposal = propose(&graph, &PhysicsSettings::default(), 400, now).expect("proposal");
+        assert_eq!(proposal.node_count, 12);
+        assert_eq!(proposal.positions.len(), 12);
+        assert!(
+            proposal.proposed_stress < proposal.current_stress,
+            "{} !< {}",
+            proposal.proposed_stress,
+            proposal.current_stress
+        );
+        let settings = LayoutRefreshSettings::default();
+        assert!(worth_offering(&proposal, &settings));
+        assert!(!worth_offering(
+            &proposal,
+            &LayoutRefreshSettings {
+                min_improvement: 1.0,
+                ..settings
+            }
+        ));
+
+        let proposals = LayoutProposals::default();
+        assert!(proposals.offer(proposal.clone()).is_none());
+        assert!(proposals.take("other", now).is_err());
+        assert!(proposals.current(now).is_some(), "wrong id keeps it open");
+        let later = now + chrono::Duration::hours(2);
+        assert!(proposals.current(later).is_none(), "lapsed");
+
+        proposals.offer(proposal.clone());
+        assert_eq!(proposals.take(&proposal.id, now).unwrap().id, proposal.id);
+        assert!(proposals.take(&proposal.id, now).is_err(), "applied once");
+        proposals.offer(proposal.clone());
+        assert!(proposals.dismiss(&proposal.id).is_some());
+        assert!(proposals.current(now).is_none());
+    }
+
+    #[test]
+    fn seeds_by_metadata_id_after_the_graph_is_renumbered() {
+        let mut graph = GraphData::new();
+        for i in 0..4u32 {
+            let mut node = Node::new_with_id(format!("n{}", i), Some(i + 1));
+            node.data.x = i as f32 * 10.0;
+            graph.nodes.push(node);
+            graph.edges.push(Edge::new(i + 1, (i + 1) % 4 + 1, 1.0));
+        }
+        let proposal =
+            propose(&graph, &PhysicsSettings::default(), 50, Utc::now()).expect("proposal");
+        let by_id = proposal.seed_for(&graph);
+        assert_eq!(by_id.len(), 4);
+
+        // A reload hands out fresh ids and drops "n0".
+        let mut reloaded = GraphData::new();
+        for i in 1..4u32 {
+            reloaded
+                .nodes
+                .push(Node::new_with_id(format!("n{}", i), Some(100 + i)));
+        }
+        let seed = proposal.seed_for(&reloaded);
+        assert_eq!(seed.len(), 3);
+        for i in 1..4u32 {
+            assert_eq!(seed[&(100 + i)], by_id[&(i + 1)]);
+        }
+    }
+}
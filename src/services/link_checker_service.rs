@@ -0,0 +1,459 @@
+//! Link-rot checker for external URLs referenced by pages.
+//!
+//! `FileService` records every http(s) URL a page references in
+//! `Metadata::external_links`. This service periodically walks those URLs,
+//! issues a `HEAD` (falling back to `GET` for servers that reject `HEAD`), and
+//! records the HTTP status per URL. Requests to the same domain are serialised
+//! with a minimum spacing so a vault full of links to one site never hammers
+//! it; distinct domains are checked concurrently up to a small bound.
+//!
+//! Results are served by `GET /api/maintenance/broken_links`. With
+//! `linkCheck.flagNodes` set, pages with broken links also get a
+//! `broken_links` entry in their node metadata so clients can badge them.
+//!
+//! Checking is off unless `linkCheck.enabled` is set; the settings are re-read
+//! before every pass. Page text decides which URLs are fetched, so only
+//! public addresses are contacted: host names are resolved first and
+//! private, loopback, link-local and other non-routable results are refused,
+//! as are literal IPs in those ranges and redirects to them.
+
+use actix::Addr;
+use chrono::{DateTime, Utc};
+use log::{debug, info, warn};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{RwLock, Semaphore};
+
+use crate::actors::graph_service_supervisor::GraphServiceSupervisor;
+use crate::actors::messages::{GetSettings, SetNodeMetadataFlag};
+use crate::actors::optimized_settings_actor::OptimizedSettingsActor;
+use crate::config::LinkCheckSettings;
+use crate::services::file_service::FileService;
+
+const INITIAL_DELAY: Duration = Duration::from_secs(60);
+const DISABLED_POLL: Duration = Duration::from_secs(600);
+const MAX_REDIRECTS: usize = 5;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+const MAX_CONCURRENT_DOMAINS: usize = 8;
+
+/// Node metadata key set on pages that reference at least one broken URL.
+pub const BROKEN_LINKS_FLAG: &str = "broken_links";
+
+#[derive(Debug, Clone, Copy)]
+pub struct LinkCheckConfig {
+    pub enabled: bool,
+    pub interval: Duration,
+    pub domain_delay: Duration,
+    pub flag_nodes: bool,
+}
+
+impl From<&LinkCheckSettings> for LinkCheckConfig {
+    fn from(settings: &LinkCheckSettings) -> Self {
+        Self {
+            enabled: settings.enabled,
+            interval: Duration::from_secs(settings.interval_secs.max(60)),
+            domain_delay: Duration::from_millis(settings.domain_delay_ms),
+            flag_nodes: settings.flag_nodes,
+        }
+    }
+}
+
+/// Whether an address may be contacted: anything private, loopback,
+/// link-local, shared (CGNAT), multicast, documentation or unspecified is
+/// not, including IPv4 addresses wrapped in IPv6.
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, ..] = v4.octets();
+            !(v4.is_private()
+                || v4.is_loopback()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_broadcast()
+                || v4.is_multicast()
+                || v4.is_documentation()
+                || a == 0
+                || (a == 100 && (64..128).contains(&b))
+                || (a == 198 && (18..20).contains(&b))
+                || a >= 240)
+        }
+        IpAddr::V6(v6) => {
+            if let Some(v4) = v6.to_ipv4_mapped() {
+                return is_public_ip(IpAddr::V4(v4));
+            }
+            let first = v6.segments()[0];
+            !(v6.is_loopback()
+                || v6.is_unspecified()
+                || v6.is_multicast()
+                || (first & 0xfe00) == 0xfc00
+                || (first & 0xffc0) == 0xfe80
+                || (first == 0x2001 && v6.segments()[1] == 0x0db8))
+        }
+    }
+}
+
+/// A URL whose host is an IP literal outside the public ranges.
+fn is_blocked_literal(url: &reqwest::Url) -> bool {
+    match url.host() {
+        Some(url::Host::Ipv4(ip)) => !is_public_ip(IpAddr::V4(ip)),
+        Some(url::Host::Ipv6(ip)) => !is_public_ip(IpAddr::V6(ip)),
+        Some(url::Host::Domain(_)) => false,
+        None => true,
+    }
+}
+
+/// Resolver that only hands reqwest public addresses, so a host name
+/// pointing inside the network fails like an unresolvable one.
+struct PublicOnlyResolver;
+
+impl reqwest::dns::Resolve for PublicOnlyResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        Box::pin(async move {
+            let host = name.as_str().to_string();
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .filter(|addr| is_public_ip(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("{} does not resolve to a public address", host).into());
+            }
+            let addrs: reqwest::dns::Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+/// Last observed state of one URL.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkStatus {
+    pub url: String,
+    pub domain: String,
+    /// HTTP status, or `None` when the request failed (DNS, TLS, timeout).
+    pub status: Option<u16>,
+    pub error: Option<String>,
+    pub broken: bool,
+    pub checked_at: DateTime<Utc>,
+    /// Pages (metadata ids) that reference this URL.
+    pub pages: Vec<String>,
+}
+
+/// Whether a check outcome counts as link rot. Transport failures and
+/// 4xx/5xx responses do; 429 only says we were throttled, so it is not.
+pub fn is_broken(status: Option<u16>) -> bool {
+    match status {
+        None => true,
+        Some(429) => false,
+        Some(code) => code >= 400,
+    }
+}
+
+pub fn domain_of(url: &str) -> Option<String> {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.trim_start_matches("www.").to_ascii_lowercase()))
+}
+
+/// Invert page → URLs into domain → (URL → referencing pages).
+pub fn group_by_domain(
+    links_by_page: &HashMap<String, Vec<String>>,
+) -> BTreeMap<String, BTreeMap<String, Vec<String>>> {
+    let mut grouped: BTreeMap<String, BTreeMap<String, Vec<String>>> = BTreeMap::new();
+    for (page, urls) in links_by_page {
+        for url in urls {
+            if let Some(domain) = domain_of(url) {
+                grouped
+                    .entry(domain)
+                    .or_default()
+                    .entry(url.clone())
+                    .or_default()
+                    .push(page.clone());
+            }
+        }
+    }
+    for urls in grouped.values_mut() {
+        for pages in urls.values_mut() {
+            pages.sort();
+            pages.dedup();
+        }
+    }
+    grouped
+}
+
+pub struct LinkCheckerService {
+    client: reqwest::Client,
+    results: RwLock<HashMap<String, LinkStatus>>,
+    last_pass: RwLock<Option<DateTime<Utc>>>,
+    graph_service_addr: Addr<GraphServiceSupervisor>,
+    settings_addr: Addr<OptimizedSettingsActor>,
+}
+
+impl LinkCheckerService {
+    pub fn new(
+        graph_service_addr: Addr<GraphServiceSupervisor>,
+        settings_addr: Addr<OptimizedSettingsActor>,
+    ) -> Self {
+        let redirects = reqwest::redirect::Policy::custom(|attempt| {
+            if attempt.previous().len() >= MAX_REDIRECTS {
+                attempt.error("too many redirects")
+            } else if is_blocked_literal(attempt.url()) {
+                attempt.error("redirect to a non-public address")
+            } else {
+                attempt.follow()
+            }
+        });
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .redirect(redirects)
+            .dns_resolver(Arc::new(PublicOnlyResolver))
+            .user_agent("VisionClaw-LinkChecker/1.0")
+            .build()
+            .expect("Failed to build HTTP client");
+        Self {
+            client,
+            results: RwLock::new(HashMap::new()),
+            last_pass: RwLock::new(None),
+            graph_service_addr,
+            settings_addr,
+        }
+    }
+
+    async fn current_config(&self) -> LinkCheckConfig {
+        match self.settings_addr.send(GetSettings).await {
+            Ok(Ok(settings)) => LinkCheckConfig::from(&settings.link_check.unwrap_or_default()),
+            Ok(Err(e)) => {
+                warn!("[LinkChecker] Failed to read settings: {}", e);
+                LinkCheckConfig::from(&LinkCheckSettings::default())
+            }
+            Err(e) => {
+                warn!("[LinkChecker] Settings actor unreachable: {}", e);
+                LinkCheckConfig::from(&LinkCheckSettings::default())
+            }
+        }
+    }
+
+    /// Idles until `linkCheck.enabled` is set, then runs a pass every
+    /// `linkCheck.intervalSecs`.
+    pub fn spawn(self: Arc<Self>) {
+        tokio::spawn(async move {
+            tokio::time::sleep(INITIAL_DELAY).await;
+            loop {
+                let config = self.current_config().await;
+                if !config.enabled {
+                    tokio::time::sleep(DISABLED_POLL).await;
+                    continue;
+                }
+                self.run_pass(&config).await;
+                tokio::time::sleep(config.interval).await;
+            }
+        });
+    }
+
+    /// Broken links, sorted by domain then URL.
+    pub async fn broken_links(&self) -> Vec<LinkStatus> {
+        let results = self.results.read().await;
+        let mut broken: Vec<LinkStatus> = results.values().filter(|s| s.broken).cloned().collect();
+        broken.sort_by(|a, b| a.domain.cmp(&b.domain).then_with(|| a.url.cmp(&b.url)));
+        broken
+    }
+
+    pub async fn checked_count(&self) -> usize {
+        self.results.read().await.len()
+    }
+
+    pub async fn last_pass(&self) -> Option<DateTime<Utc>> {
+        *self.last_pass.read().await
+    }
+
+    /// Check every URL currently referenced by the vault once.
+    pub async fn run_pass(self: &Arc<Self>, config: &LinkCheckConfig) {
+        let metadata = match FileService::load_or_create_metadata() {
+            Ok(m) => m,
+            Err(e) => {
+                warn!("[LinkChecker] Cannot load metadata: {}", e);
+                return;
+            }
+        };
+        let links_by_page: HashMap<String, Vec<String>> = metadata
+            .into_iter()
+            .filter(|(_, m)| !m.external_links.is_empty())
+            .map(|(id, m)| (id, m.external_links))
+            .collect();
+        let grouped = group_by_domain(&links_by_page);
+        let url_count: usize = grouped.values().map(|u| u.len()).sum();
+        info!(
+            "[LinkChecker] Checking {} URL(s) across {} domain(s)",
+            url_count,
+            grouped.len()
+        );
+
+        let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_DOMAINS));
+        let mut tasks = tokio::task::JoinSet::new();
+        for (domain, urls) in grouped {
+            let this = Arc::clone(self);
+            let semaphore = Arc::clone(&semaphore);
+            let domain_delay = config.domain_delay;
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await.ok()?;
+                let mut statuses = Vec::with_capacity(urls.len());
+                for (i, (url, pages)) in urls.into_iter().enumerate() {
+                    if i > 0 {
+                        tokio::time::sleep(domain_delay).await;
+                    }
+                    let (status, error) = this.check_url(&url).await;
+                    statuses.push(LinkStatus {
+                        broken: is_broken(status),
+                        url,
+                        domain: domain.clone(),
+                        status,
+                        error,
+                        checked_at: Utc::now(),
+                        pages,
+                    });
+                }
+                Some(statuses)
+            });
+        }
+
+        let mut fresh: HashMap<String, LinkStatus> = HashMap::with_capacity(url_count);
+        while let Some(joined) = tasks.join_next().await {
+            if let Ok(Some(statuses)) = joined {
+                for status in statuses {
+                    fresh.insert(status.url.clone(), status);
+                }
+            }
+        }
+
+        let broken = fresh.values().filter(|s| s.broken).count();
+        *self.results.write().await = fresh;
+        *self.last_pass.write().await = Some(Utc::now());
+        info!("[LinkChecker] Pass complete: {} broken of {}", broken, url_count);
+
+        if config.flag_nodes {
+            self.flag_nodes().await;
+        }
+    }
+
+    async fn check_url(&self, url: &str) -> (Option<u16>, Option<String>) {
+        match reqwest::Url::parse(url) {
+            Ok(parsed) if !is_blocked_literal(&parsed) => {}
+            Ok(_) => return (None, Some("non-public address".to_string())),
+            Err(e) => return (None, Some(e.to_string())),
+        }
+        let head = self.client.head(url).send().await;
+        let response = match head {
+            Ok(resp) if matches!(resp.status().as_u16(), 403 | 405 | 501) => {
+                self.client.get(url).send().await
+            }
+            other => other,
+        };
+        match response {
+            Ok(resp) => {
+                let code = resp.status().as_u16();
+                debug!("[LinkChecker] {} -> {}", url, code);
+                (Some(code), None)
+            }
+            Err(e) => (None, Some(e.to_string())),
+        }
+    }
+
+    async fn flag_nodes(&self) {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for status in self.results.read().await.values().filter(|s| s.broken) {
+            for page in &status.pages {
+                *counts.entry(page.clone()).or_insert(0) += 1;
+            }
+        }
+        let values = counts.into_iter().map(|(k, v)| (k, v.to_string())).collect();
+        let msg = SetNodeMetadataFlag {
+            key: BROKEN_LINKS_FLAG.to_string(),
+            values,
+        };
+        match self.graph_service_addr.send(msg).await {
+            Ok(Ok(n)) => debug!("[LinkChecker] Flagged {} node(s) with broken links", n),
+            Ok(Err(e)) => warn!("[LinkChecker] Failed to flag nodes: {}", e),
+            Err(e) => warn!("[LinkChecker] Graph service unreachable: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_defaults_to_off() {
+        let cfg = LinkCheckConfig::from(&LinkCheckSettings::default());
+        assert!(!cfg.enabled);
+        assert!(!cfg.flag_nodes);
+        assert_eq!(cfg.interval, Duration::from_secs(6 * 3600));
+        assert_eq!(cfg.domain_delay, Duration::from_millis(2000));
+
+        let cfg = LinkCheckConfig::from(&LinkCheckSettings {
+            enabled: true,
+            interval_secs: 0,
+            domain_delay_ms: 250,
+            flag_nodes: true,
+        });
+        assert!(cfg.enabled && cfg.flag_nodes);
+        assert_eq!(cfg.interval, Duration::from_secs(60));
+        assert_eq!(cfg.domain_delay, Duration::from_millis(250));
+    }
+
+    #[test]
+    fn refuses_non_public_addresses() {
+        for blocked in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fe80::1",
+            "fd00::1",
+            "::ffff:10.0.0.1",
+        ] {
+            assert!(!is_public_ip(blocked.parse().unwrap()), "{}", blocked);
+        }
+        for public in ["93.184.216.34", "1.1.1.1", "2606:4700::1111"] {
+            assert!(is_public_ip(public.parse().unwrap()), "{}", public);
+        }
+
+        let url = |u: &str| reqwest::Url::parse(u).unwrap();
+        assert!(is_blocked_literal(&url("http://169.254.169.254/latest/meta-data")));
+        assert!(is_blocked_literal(&url("http://[::1]:8080/")));
+        assert!(!is_blocked_literal(&url("https://example.com/")));
+        assert!(!is_blocked_literal(&url("https://1.1.1.1/")));
+    }
+
+    #[test]
+    fn broken_classification() {
+        assert!(is_broken(None));
+        assert!(is_broken(Some(404)));
+        assert!(is_broken(Some(500)));
+        assert!(!is_broken(Some(200)));
+        assert!(!is_broken(Some(301)));
+        assert!(!is_broken(Some(429)));
+    }
+
+    #[test]
+    fn groups_urls_by_domain_with_pages() {
+        let mut links = HashMap::new();
+        links.insert(
+            "A.md".to_string(),
+            vec!["https://www.example.com/x".to_string(), "https://other.org/".to_string()],
+        );
+        links.insert("B.md".to_string(), vec!["https://example.com/x".to_string()]);
+        links.insert("C.md".to_string(), vec!["https://www.example.com/x".to_string()]);
+        let grouped = group_by_domain(&links);
+        assert_eq!(grouped.len(), 2);
+        let example = &grouped["example.com"];
+        assert_eq!(example["https://www.example.com/x"], vec!["A.md".to_string(), "C.md".to_string()]);
+        assert_eq!(example["https://example.com/x"], vec!["B.md".to_string()]);
+    }
+}
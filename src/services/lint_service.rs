@@ -0,0 +1,371 @@
+//! Graph lint rules.
+//!
+//! Checks the vault graph for structural smells, each rule reported at the
+//! severity set in the `lint` settings section (`off` skips it):
+//!
+//! - `orphanPage`: a page with no edges in or out;
+//! - `tooManyLinks`: a page with more than `maxLinks` edges;
+//! - `brokenBlockRef`: a `((uuid))` block reference that matches no
+//!   `id:: uuid` block property anywhere in the vault;
+//! - `tagUsedOnce`: a tag carried by exactly one node, usually a typo.
+//!
+//! Only nodes backed by a page file count as pages; linked-page
+//! placeholders and ontology nodes are not linted for orphans or link
+//! counts. Results are served by `GET /api/lint`. With `flagNodes` set,
+//! `POST /api/lint/flags` also gives offending nodes a `lint` entry in their
+//! metadata holding their worst severity, so clients can badge them.
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::config::{LintSettings, LintSeverity};
+use crate::models::graph::GraphData;
+use crate::models::node::Node;
+use crate::services::duplicate_detection_service::PageText;
+use crate::services::tag_color_service::node_tags;
+
+/// Node metadata key set on nodes with at least one finding.
+pub const LINT_FLAG: &str = "lint";
+
+const UUID: &str = r"[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}";
+
+/// `((uuid))`
+static BLOCK_REF: Lazy<Regex> =
+    Lazy::new(|| Regex::new(&format!(r"\(\(({UUID})\)\)")).expect("Invalid regex pattern"));
+
+/// An `id:: uuid` block property on its own line.
+static BLOCK_ID: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(r"(?m)^\s*(?:-\s+)?id::\s*({UUID})\s*$")).expect("Invalid regex pattern")
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LintRule {
+    OrphanPage,
+    TooManyLinks,
+    BrokenBlockRef,
+    TagUsedOnce,
+}
+
+impl LintRule {
+    pub const ALL: [LintRule; 4] = [
+        LintRule::OrphanPage,
+        LintRule::TooManyLinks,
+        LintRule::BrokenBlockRef,
+        LintRule::TagUsedOnce,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::OrphanPage => "orphanPage",
+            Self::TooManyLinks => "tooManyLinks",
+            Self::BrokenBlockRef => "brokenBlockRef",
+            Self::TagUsedOnce => "tagUsedOnce",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|rule| rule.as_str() == name)
+    }
+
+    pub fn severity(self, settings: &LintSettings) -> LintSeverity {
+        match self {
+            Self::OrphanPage => settings.orphan_page,
+            Self::TooManyLinks => settings.too_many_links,
+            Self::BrokenBlockRef => settings.broken_block_ref,
+            Self::TagUsedOnce => settings.tag_used_once,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintFinding {
+    pub rule: LintRule,
+    pub severity: LintSeverity,
+    /// Graph node, when the page or tag owner is in the graph.
+    pub node_id: Option<u32>,
+    pub metadata_id: Option<String>,
+    pub page: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeverityCounts {
+    pub info: usize,
+    pub warning: usize,
+    pub error: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintReport {
+    pub generated_at: DateTime<Utc>,
+    pub pages_scanned: usize,
+    pub counts: SeverityCounts,
+    /// Worst first, then by page.
+    pub findings: Vec<LintFinding>,
+}
+
+impl LintReport {
+    /// Worst severity per flagged node, keyed by metadata id.
+    pub fn node_flags(&self) -> HashMap<String, String> {
+        let mut worst: HashMap<String, LintSeverity> = HashMap::new();
+        for finding in &self.findings {
+            if let Some(id) = &finding.metadata_id {
+                let entry = worst.entry(id.clone()).or_insert(finding.severity);
+                *entry = (*entry).max(finding.severity);
+            }
+        }
+        worst
+            .into_iter()
+            .map(|(id, severity)| (id, severity_name(severity).to_string()))
+            .collect()
+    }
+}
+
+fn severity_name(severity: LintSeverity) -> &'static str {
+    match severity {
+        LintSeverity::Off => "off",
+        LintSeverity::Info => "info",
+        LintSeverity::Warning => "warning",
+        LintSeverity::Error => "error",
+    }
+}
+
+/// Page name as it appears in file names, labels and metadata ids.
+fn page_key(name: &str) -> String {
+    name.trim().trim_end_matches(".md").to_lowercase()
+}
+
+fn finding(
+    rule: LintRule,
+    severity: LintSeverity,
+    node: Option<&Node>,
+    page: &str,
+    message: String,
+) -> LintFinding {
+    LintFinding {
+        rule,
+        severity,
+        node_id: node.map(|n| n.id),
+        metadata_id: node.map(|n| n.metadata_id.clone()),
+        page: page.to_string(),
+        message,
+    }
+}
+
+/// Run every enabled rule over the graph and the vault's page text.
+pub fn lint(graph: &GraphData, pages: &[PageText], settings: &LintSettings) -> LintReport {
+    let enabled = |rule: LintRule| {
+        let severity = rule.severity(settings);
+        (severity != LintSeverity::Off).then_some(severity)
+    };
+
+    let mut by_page: HashMap<String, &Node> = HashMap::new();
+    for node in &graph.nodes {
+        by_page.entry(page_key(&node.label)).or_insert(node);
+        by_page.insert(page_key(&node.metadata_id), node);
+    }
+    let page_node = |page: &PageText| by_page.get(&page_key(&page.name)).copied();
+
+    let mut degree: HashMap<u32, usize> = HashMap::new();
+    for edge in graph.edges.iter().filter(|e| e.source != e.target) {
+        *degree.entry(edge.source).or_insert(0) += 1;
+        *degree.entry(edge.target).or_insert(0) += 1;
+    }
+
+    let mut findings = Vec::new();
+
+    let orphan = enabled(LintRule::OrphanPage);
+    let crowded = enabled(LintRule::TooManyLinks);
+    if orphan.is_some() || crowded.is_some() {
+        for page in pages {
+            let Some(node) = page_node(page) else {
+                continue;
+            };
+            let links = degree.get(&node.id).copied().unwrap_or(0);
+            if let Some(severity) = orphan.filter(|_| links == 0) {
+                findings.push(finding(
+                    LintRule::OrphanPage,
+                    severity,
+                    Some(node),
+                    &page.name,
+                    "No links to or from this page".to_string(),
+                ));
+            }
+            if let Some(severity) = crowded.filter(|_| links > settings.max_links as usize) {
+                findings.push(finding(
+                    LintRule::TooManyLinks,
+                    severity,
+                    Some(node),
+                    &page.name,
+                    format!("{} links (limit {})", links, settings.max_links),
+                ));
+            }
+        }
+    }
+
+    if let Some(severity) = enabled(LintRule::BrokenBlockRef) {
+        let block_ids: HashSet<String> = pages
+            .iter()
+            .flat_map(|p| BLOCK_ID.captures_iter(&p.content))
+            .map(|c| c[1].to_lowercase())
+            .collect();
+        for page in pages {
+            let mut missing: Vec<String> = BLOCK_REF
+                .captures_iter(&page.content)
+                .map(|c| c[1].to_lowercase())
+                .filter(|id| !block_ids.contains(id))
+                .collect();
+            missing.sort();
+            missing.dedup();
+            for id in missing {
+                findings.push(finding(
+                    LintRule::BrokenBlockRef,
+                    severity,
+                    page_node(page),
+                    &page.name,
+                    format!("Block (({})) does not exist", id),
+                ));
+            }
+        }
+    }
+
+    if let Some(severity) = enabled(LintRule::TagUsedOnce) {
+        let mut carriers: BTreeMap<String, Vec<&Node>> = BTreeMap::new();
+        for node in &graph.nodes {
+            let mut tags = node_tags(&node.metadata);
+            tags.sort();
+            tags.dedup();
+            for tag in tags {
+                carriers.entry(tag).or_default().push(node);
+            }
+        }
+        for (tag, nodes) in carriers {
+            if let [node] = nodes.as_slice() {
+                findings.push(finding(
+                    LintRule::TagUsedOnce,
+                    severity,
+                    Some(node),
+                    &node.label,
+                    format!("Tag '{}' is not used anywhere else", tag),
+                ));
+            }
+        }
+    }
+
+    findings.sort_by(|a, b| {
+        b.severity
+            .cmp(&a.severity)
+            .then_with(|| a.page.cmp(&b.page))
+            .then_with(|| a.rule.cmp(&b.rule))
+    });
+    let mut counts = SeverityCounts::default();
+    for finding in &findings {
+        match finding.severity {
+            LintSeverity::Info => counts.info += 1,
+            LintSeverity::Warning => counts.warning += 1,
+            LintSeverity::Error => counts.error += 1,
+            LintSeverity::Off => {}
+        }
+    }
+
+    LintReport {
+        generated_at: Utc::now(),
+        pages_scanned: pages.len(),
+        counts,
+        findings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::edge::Edge;
+
+    fn node(id: u32, name: &str, tags: &str) -> Node {
+        let mut node = Node::new_with_id(format!("{}.md", name), Some(id));
+        node.label = name.to_string();
+        if !tags.is_empty() {
+            node.metadata.insert("tags".to_string(), tags.to_string());
+        }
+        node
+    }
+
+    fn page(name: &str, content: &str) -> PageText {
+        PageText {
+            name: name.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn reports_each_rule_at_its_severity() {
+        let mut graph = GraphData::new();
+        graph.nodes = vec![
+            node(1, "Hub", "project, rust"),
+            node(2, "Leaf", "project"),
+            node(3, "Lonely", "rsut"),
+            node(4, "Placeholder", ""),
+        ];
+        graph.edges = vec![
+            Edge::new(1, 2, 1.0),
+            Edge::new(1, 4, 1.0),
+            Edge::new(3, 3, 1.0),
+        ];
+        let pages = vec![
+            page("Hub", "- see ((6f1c2d3e-0000-4000-8000-000000000001))"),
+            page(
+                "Leaf",
+                "- quoted block\n  id:: 6F1C2D3E-0000-4000-8000-000000000001\n\
+                 - ((6f1c2d3e-0000-4000-8000-00000000dead))",
+            ),
+            page("Lonely", "nothing here"),
+        ];
+        let settings = LintSettings {
+            max_links: 1,
+            ..LintSettings::default()
+        };
+
+        let report = lint(&graph, &pages, &settings);
+        let found: Vec<(LintRule, &str)> = report
+            .findings
+            .iter()
+            .map(|f| (f.rule, f.page.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (LintRule::BrokenBlockRef, "Leaf"),
+                (LintRule::OrphanPage, "Lonely"),
+                (LintRule::TooManyLinks, "Hub"),
+                (LintRule::TagUsedOnce, "Hub"),
+                (LintRule::TagUsedOnce, "Lonely"),
+            ]
+        );
+        assert_eq!(report.counts.error, 1);
+        assert_eq!(report.counts.warning, 1);
+        assert_eq!(report.counts.info, 3);
+
+        let flags = report.node_flags();
+        assert_eq!(flags["Lonely.md"], "warning");
+        assert_eq!(flags["Hub.md"], "info");
+        assert!(!flags.contains_key("Placeholder.md"));
+
+        let settings = LintSettings {
+            orphan_page: LintSeverity::Off,
+            tag_used_once: LintSeverity::Off,
+            broken_block_ref: LintSeverity::Warning,
+            ..LintSettings::default()
+        };
+        let report = lint(&graph, &pages, &settings);
+        assert_eq!(report.findings.len(), 1);
+        assert_eq!(report.findings[0].severity, LintSeverity::Warning);
+        assert_eq!(report.findings[0].node_id, Some(2));
+    }
+}
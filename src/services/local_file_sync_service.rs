@@ -9,10 +9,12 @@
 //! This avoids pagination issues with 250k+ files by using local baseline.
 
 use crate::adapters::OxigraphOntologyRepository;
+use crate::config::dev_config;
 use crate::ports::knowledge_graph_repository::KnowledgeGraphRepository;
 use crate::services::github::content_enhanced::EnhancedContentAPI;
 use crate::services::github::types::{OntologyFileMetadata, OntologyPriority};
-use crate::services::parsers::{KnowledgeGraphParser, OntologyParser};
+use crate::services::parsers::content_parser::{document_to_graph, file_stem};
+use crate::services::parsers::{content_parsers, ContentParser, KnowledgeGraphParser, OntologyParser};
 use crate::services::ontology_enrichment_service::OntologyEnrichmentService;
 use crate::services::ontology_content_analyzer::OntologyContentAnalyzer;
 use crate::services::ontology_file_cache::{OntologyFileCache, OntologyCacheConfig, CachedOntologyFile};
@@ -25,7 +27,10 @@ use std::time::{Duration, Instant};
 use sha1::{Sha1, Digest};
 
 const BATCH_SIZE: usize = 50;
-const LOCAL_PAGES_DIR: &str = "/app/data/pages";
+
+fn local_pages_dir() -> PathBuf {
+    dev_config::storage().path("pages")
+}
 
 #[derive(Clone)]
 pub struct LocalFileSyncService {
@@ -110,7 +115,7 @@ impl LocalFileSyncService {
         // Step 1: Read all local markdown files
         let local_files = self.scan_local_pages()?;
         stats.total_files = local_files.len();
-        info!("📂 Found {} local markdown files in {}", local_files.len(), LOCAL_PAGES_DIR);
+        info!("📂 Found {} local markdown files in {}", local_files.len(), local_pages_dir().display());
 
         // Step 2: Get SHA1 hashes from GitHub (lightweight API call - only metadata, not content)
         info!("🔍 Fetching GitHub SHA1 hashes for comparison...");
@@ -257,23 +262,24 @@ impl LocalFileSyncService {
         info!("   Files with commit dates: {}", stats.files_with_commit_dates);
     }
 
-    /// Scan local pages directory for markdown files
+    /// Scan local pages directory for markdown files and files of any
+    /// registered content type
     fn scan_local_pages(&self) -> Result<Vec<PathBuf>, String> {
-        let pages_dir = Path::new(LOCAL_PAGES_DIR);
+        let pages_dir = local_pages_dir();
 
         if !pages_dir.exists() {
-            return Err(format!("Local pages directory does not exist: {}", LOCAL_PAGES_DIR));
+            return Err(format!("Local pages directory does not exist: {}", pages_dir.display()));
         }
 
         let mut md_files = Vec::new();
 
-        for entry in fs::read_dir(pages_dir)
-            .map_err(|e| format!("Failed to read directory {}: {}", LOCAL_PAGES_DIR, e))?
+        for entry in fs::read_dir(&pages_dir)
+            .map_err(|e| format!("Failed to read directory {}: {}", pages_dir.display(), e))?
         {
             let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
             let path = entry.path();
 
-            if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("md") {
+            if path.is_file() && content_parsers().is_content_file(&path.to_string_lossy()) {
                 md_files.push(path);
             }
         }
@@ -289,7 +295,7 @@ impl LocalFileSyncService {
 
         // For now, use the existing list_markdown_files (with pagination fix)
         // Future: Implement git tree API for better efficiency
-        let github_files = self.content_api.list_markdown_files("").await
+        let github_files = self.content_api.list_markdown_files_in_sources().await
             .map_err(|e| format!("GitHub API error: {}", e))?;
 
         let mut sha_map = HashMap::new();
@@ -351,6 +357,19 @@ impl LocalFileSyncService {
         public_pages: &mut std::collections::HashSet<String>,
         stats: &mut SyncStatistics,
     ) -> Result<(), String> {
+        // Non-markdown sources carry no Logseq markers or OntologyBlock.
+        if let Some(parser) = content_parsers().for_path(file_name) {
+            return self.process_plugin_file(
+                parser.as_ref(),
+                file_name,
+                content,
+                nodes,
+                edges,
+                public_pages,
+                stats,
+            );
+        }
+
         // Check cache first
         if let Some(cached) = self.ontology_cache.get(file_name, content_sha).await {
             stats.cache_hits += 1;
@@ -442,7 +461,7 @@ impl LocalFileSyncService {
             }
 
             // Add to collections
-            let page_name = file_name.trim_end_matches(".md");
+            let page_name = file_stem(file_name);
             public_pages.insert(page_name.to_string());
 
             for node in parsed.nodes {
@@ -484,6 +503,39 @@ impl LocalFileSyncService {
         Ok(())
     }
 
+    /// Ingest a non-markdown file through its registered [`ContentParser`],
+    /// skipping unpublished documents like pages without `public:: true`.
+    #[allow(clippy::too_many_arguments)]
+    fn process_plugin_file(
+        &self,
+        parser: &dyn ContentParser,
+        file_name: &str,
+        content: &str,
+        nodes: &mut HashMap<u32, visionclaw_domain::models::node::Node>,
+        edges: &mut HashMap<String, visionclaw_domain::models::edge::Edge>,
+        public_pages: &mut std::collections::HashSet<String>,
+        stats: &mut SyncStatistics,
+    ) -> Result<(), String> {
+        let doc = parser
+            .parse(content, file_name)
+            .map_err(|e| format!("{} parse error: {}", parser.name(), e))?;
+        if !doc.public {
+            stats.skipped_files += 1;
+            return Ok(());
+        }
+
+        let parsed = document_to_graph(&doc, parser.name(), file_name, content.len(), &self.kg_parser);
+        public_pages.insert(file_stem(file_name).to_string());
+        for node in parsed.nodes {
+            nodes.entry(node.id).or_insert(node);
+        }
+        for edge in parsed.edges {
+            edges.entry(edge.id.clone()).or_insert(edge);
+        }
+        stats.kg_files_processed += 1;
+        Ok(())
+    }
+
     /// Fetch GitHub commit dates for ontology files (Priority 1 and 2)
     /// This can be called separately to enrich metadata with git history
     pub async fn enrich_with_commit_dates(&self) -> Result<usize, String> {
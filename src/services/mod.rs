@@ -1,14 +1,18 @@
 pub mod agent_visualization_processor;
 pub mod agent_visualization_protocol;
 pub mod bots_client;
+pub mod chat_notifier;
 pub mod file_service;
+pub mod graph_readiness;
 pub mod github;
 pub mod github_sync_service;
+pub mod link_checker_service;
 pub mod local_file_sync_service;
 pub mod management_api_client;
 pub mod multi_mcp_agent_discovery;
 pub mod natural_language_query_service;
 pub mod parsers;
+pub mod graph_query_service;
 pub mod graph_serialization;
 pub mod mcp_relay_manager;
 pub mod nostr_service;
@@ -36,8 +40,33 @@ pub mod semantic_type_registry;
 pub mod ontology_query_service;
 pub mod ontology_mutation_service;
 pub mod github_pr_service;
+pub mod write_back_service;
+pub mod vault_upload_service;
 pub mod briefing_service;
+pub mod digest_service;
+pub mod duplicate_detection_service;
 pub mod nostr_bead_publisher;
+pub mod webhook_service;
+pub mod public_graph_service;
+pub mod camera_recording_service;
+pub mod layout_history_service;
+pub mod diff_journal_service;
+pub mod tag_color_service;
+pub mod xr_anchor_service;
+pub mod audio_cue_service;
+pub mod json_file;
+pub mod settings_history_service;
+pub mod language_detection;
+pub mod graph_stats_chat;
+pub mod graph_tools;
+pub mod chat_agent;
+pub mod highlight_service;
+pub mod hnsw_index;
+pub mod embedding_sync_service;
+pub mod cost_service;
+pub mod visit_service;
+pub mod lint_service;
+pub mod layout_refresh_service;
 pub mod nostr_bridge;
 // PRD-008 §5.3 — Schnorr identity verifier for the XR presence handshake
 pub mod nostr_identity_verifier;
@@ -12,7 +12,7 @@
 use crate::adapters::whelk_inference_engine::WhelkInferenceEngine;
 use visionclaw_domain::ports::inference_engine::InferenceEngine;
 use visionclaw_domain::ports::ontology_repository::{OwlAxiom, AxiomType, OntologyRepository};
-use crate::services::file_service::MARKDOWN_DIR;
+use crate::services::file_service::markdown_dir;
 use crate::services::github_pr_service::GitHubPRService;
 use crate::types::ontology_tools::*;
 use chrono::Utc;
@@ -95,7 +95,7 @@ impl OntologyMutationService {
         // 6. Determine file path (per-user namespace)
         let file_path = format!(
             "{}/{}/{}.md",
-            MARKDOWN_DIR,
+            markdown_dir(),
             proposal.domain,
             term_id.to_lowercase().replace('-', "_")
         );
@@ -237,7 +237,7 @@ impl OntologyMutationService {
             let term_id = existing.term_id.as_deref().unwrap_or("unknown");
             format!(
                 "{}/{}/{}.md",
-                MARKDOWN_DIR,
+                markdown_dir(),
                 domain,
                 term_id.to_lowercase().replace('-', "_")
             )
@@ -0,0 +1,96 @@
+//! AsciiDoc (`.adoc`, `.asciidoc`) documents.
+//!
+//! - title: the level-0 heading (`= Title`)
+//! - links: cross references to other documents, `xref:other.adoc[]` and
+//!   `<<other.adoc#,Text>>`; references to anchors in the same document are
+//!   ignored
+//! - tags: `:tags: a, b`
+//! - properties: every other `:name: value` attribute entry
+//! - published with `:public: true`
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::content_parser::{file_stem, ContentParser, ParsedDocument};
+
+static XREF: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"xref:([^\[\s]+)\[").expect("Invalid regex pattern"));
+static SHORT_XREF: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"<<([^>,]+)(?:,[^>]*)?>>").expect("Invalid regex pattern"));
+
+pub struct AsciiDocParser;
+
+/// Page named by a cross reference, or `None` for an in-document anchor.
+fn xref_target(raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    let path = raw.split('#').next().unwrap_or(raw);
+    let is_document = path.ends_with(".adoc") || path.ends_with(".asciidoc") || raw.contains('#');
+    if path.is_empty() || !is_document {
+        return None;
+    }
+    Some(file_stem(path).to_string())
+}
+
+impl ContentParser for AsciiDocParser {
+    fn name(&self) -> &'static str {
+        "asciidoc"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["adoc", "asciidoc"]
+    }
+
+    fn parse(&self, content: &str, _file_name: &str) -> Result<ParsedDocument, String> {
+        let mut doc = ParsedDocument::default();
+        for line in content.lines() {
+            if let Some(title) = line.strip_prefix("= ") {
+                if doc.title.is_empty() {
+                    doc.title = title.trim().to_string();
+                }
+                continue;
+            }
+            let Some((name, value)) = line.strip_prefix(':').and_then(|rest| rest.split_once(':'))
+            else {
+                continue;
+            };
+            if name.is_empty() || name.starts_with('!') || name.contains(char::is_whitespace) {
+                continue;
+            }
+            let name = name.to_ascii_lowercase();
+            let value = value.trim();
+            match name.as_str() {
+                "tags" => value.split(',').for_each(|tag| doc.add_tag(tag)),
+                "public" => doc.public = value.eq_ignore_ascii_case("true"),
+                _ if !value.is_empty() => {
+                    doc.properties.insert(name, value.to_string());
+                }
+                _ => {}
+            }
+        }
+        doc.links = XREF
+            .captures_iter(content)
+            .chain(SHORT_XREF.captures_iter(content))
+            .filter_map(|cap| xref_target(&cap[1]))
+            .collect();
+        Ok(doc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_title_attributes_and_cross_references() {
+        let content = "= Deployment Guide\n:tags: ops, k8s\n:public: true\n:revnumber: 2.1\n\n\
+            See xref:networking.adoc#ports[ports] and <<storage.adoc#,Storage>>.\n\
+            Also <<install-steps>> and <<upgrade,Upgrading>>.\n";
+        let doc = AsciiDocParser.parse(content, "deploy.adoc").unwrap();
+
+        assert_eq!(doc.title, "Deployment Guide");
+        assert!(doc.public);
+        assert_eq!(doc.tags, vec!["ops", "k8s"]);
+        assert_eq!(doc.links, vec!["networking", "storage"]);
+        assert_eq!(doc.properties["revnumber"], "2.1");
+    }
+}
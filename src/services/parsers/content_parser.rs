@@ -0,0 +1,240 @@
+//! Content-type plugins for non-markdown sources.
+//!
+//! Logseq markdown keeps its own ingest path (JSON-LD first, then
+//! [`KnowledgeGraphParser`]). Any other file type reaches the graph through a
+//! [`ContentParser`] registered at startup and chosen by file extension. A
+//! parser only has to reduce a file to a [`ParsedDocument`]; turning that into
+//! a page node and link edges is shared, so plugin pages get the same ids,
+//! positions and edge shape as markdown pages and join them by name.
+
+use log::info;
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+use visionclaw_domain::models::edge::{Edge, EdgeProvenance, ProvenanceSource};
+use visionclaw_domain::models::graph::GraphData;
+use visionclaw_domain::models::metadata::MetadataStore;
+
+use super::asciidoc_parser::AsciiDocParser;
+use super::knowledge_graph_parser::KnowledgeGraphParser;
+use super::notebook_parser::NotebookParser;
+use super::org_mode_parser::OrgModeParser;
+
+/// What a content parser extracts from one file.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedDocument {
+    /// Page name; falls back to the file stem when empty.
+    pub title: String,
+    /// Names of the pages this document links to.
+    pub links: Vec<String>,
+    pub tags: Vec<String>,
+    /// Document-level properties, copied into node metadata.
+    pub properties: HashMap<String, String>,
+    /// Whether the document is published. Unpublished documents are skipped,
+    /// matching `public:: true` for markdown pages.
+    pub public: bool,
+}
+
+impl ParsedDocument {
+    /// Add a tag unless it is empty or already present.
+    pub fn add_tag(&mut self, tag: &str) {
+        let tag = tag.trim().trim_start_matches('#');
+        if !tag.is_empty() && !self.tags.iter().any(|t| t == tag) {
+            self.tags.push(tag.to_string());
+        }
+    }
+}
+
+pub trait ContentParser: Send + Sync {
+    /// Short name recorded on nodes as `content_type`.
+    fn name(&self) -> &'static str;
+
+    /// Lowercase extensions handled, without the dot.
+    fn extensions(&self) -> &'static [&'static str];
+
+    fn parse(&self, content: &str, file_name: &str) -> Result<ParsedDocument, String>;
+}
+
+#[derive(Default)]
+pub struct ContentParserRegistry {
+    parsers: RwLock<Vec<Arc<dyn ContentParser>>>,
+}
+
+static REGISTRY: Lazy<ContentParserRegistry> = Lazy::new(ContentParserRegistry::default);
+
+/// Process-wide parser registry consulted by the GitHub listing and sync.
+pub fn content_parsers() -> &'static ContentParserRegistry {
+    &REGISTRY
+}
+
+/// Register the parsers that ship with the server. Called once at startup.
+pub fn register_builtin_parsers() {
+    let registry = content_parsers();
+    registry.register(Arc::new(OrgModeParser));
+    registry.register(Arc::new(AsciiDocParser));
+    registry.register(Arc::new(NotebookParser));
+}
+
+fn extension(path: &str) -> Option<String> {
+    let file_name = path.rsplit('/').next()?;
+    let (_, ext) = file_name.rsplit_once('.')?;
+    Some(ext.to_ascii_lowercase())
+}
+
+/// File name without its extension.
+pub fn file_stem(file_name: &str) -> &str {
+    let base = file_name.rsplit('/').next().unwrap_or(file_name);
+    base.rsplit_once('.').map(|(stem, _)| stem).unwrap_or(base)
+}
+
+impl ContentParserRegistry {
+    /// Add a parser. A later parser claiming the same extension wins.
+    pub fn register(&self, parser: Arc<dyn ContentParser>) {
+        info!(
+            "Registered content parser '{}' for {:?}",
+            parser.name(),
+            parser.extensions()
+        );
+        self.parsers
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(parser);
+    }
+
+    pub fn for_path(&self, path: &str) -> Option<Arc<dyn ContentParser>> {
+        let ext = extension(path)?;
+        self.parsers
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .rev()
+            .find(|p| p.extensions().contains(&ext.as_str()))
+            .cloned()
+    }
+
+    /// Whether `path` is ingested at all: markdown or a registered type.
+    pub fn is_content_file(&self, path: &str) -> bool {
+        path.ends_with(".md") || self.for_path(path).is_some()
+    }
+
+    pub fn extensions(&self) -> Vec<&'static str> {
+        let mut exts: Vec<&'static str> = self
+            .parsers
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .flat_map(|p| p.extensions().iter().copied())
+            .collect();
+        exts.sort_unstable();
+        exts.dedup();
+        exts
+    }
+}
+
+/// Page node plus one edge per distinct link target, shaped like the
+/// markdown parser's output.
+pub fn document_to_graph(
+    doc: &ParsedDocument,
+    parser: &str,
+    file_name: &str,
+    content_size: usize,
+    kg: &KnowledgeGraphParser,
+) -> GraphData {
+    let page_name = if doc.title.trim().is_empty() {
+        file_stem(file_name).to_string()
+    } else {
+        doc.title.trim().to_string()
+    };
+
+    let mut metadata: HashMap<String, String> = doc
+        .properties
+        .iter()
+        .map(|(k, v)| (k.to_lowercase(), v.clone()))
+        .collect();
+    metadata.insert("type".to_string(), "page".to_string());
+    metadata.insert("source_file".to_string(), file_name.to_string());
+    metadata.insert("public".to_string(), doc.public.to_string());
+    metadata.insert("content_type".to_string(), parser.to_string());
+    metadata.insert("file_size".to_string(), content_size.to_string());
+    if !doc.tags.is_empty() {
+        metadata.insert("tags".to_string(), doc.tags.join(", "));
+    }
+
+    let node = kg.document_node(&page_name, content_size, metadata);
+    let source_id = node.id;
+
+    let mut seen = HashSet::new();
+    let edges: Vec<Edge> = doc
+        .links
+        .iter()
+        .map(|target| kg.page_name_to_id(target.trim()))
+        .filter(|&target_id| target_id != source_id && seen.insert(target_id))
+        .map(|target_id| Edge {
+            id: format!("{}_{}", source_id, target_id),
+            source: source_id,
+            target: target_id,
+            weight: 1.0,
+            edge_type: Some("explicit_link".to_string()),
+            metadata: None,
+            owl_property_iri: None,
+            provenance: Some(EdgeProvenance::from_source(ProvenanceSource::Wikilink, 1.0)),
+        })
+        .collect();
+
+    let mut id_to_metadata = HashMap::new();
+    id_to_metadata.insert(source_id.to_string(), page_name);
+
+    GraphData {
+        nodes: vec![node],
+        edges,
+        metadata: MetadataStore::new(),
+        id_to_metadata,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Txt;
+
+    impl ContentParser for Txt {
+        fn name(&self) -> &'static str {
+            "txt"
+        }
+        fn extensions(&self) -> &'static [&'static str] {
+            &["txt"]
+        }
+        fn parse(&self, content: &str, _file_name: &str) -> Result<ParsedDocument, String> {
+            Ok(ParsedDocument {
+                links: content.lines().map(str::to_string).collect(),
+                public: true,
+                ..Default::default()
+            })
+        }
+    }
+
+    #[test]
+    fn registry_picks_parser_by_extension() {
+        let registry = ContentParserRegistry::default();
+        registry.register(Arc::new(Txt));
+        assert_eq!(registry.for_path("notes/a.TXT").unwrap().name(), "txt");
+        assert!(registry.for_path("notes/a.md").is_none());
+        assert!(registry.is_content_file("notes/a.md"));
+        assert!(!registry.is_content_file("notes/a.png"));
+        assert_eq!(registry.extensions(), vec!["txt"]);
+    }
+
+    #[test]
+    fn document_becomes_page_with_deduplicated_links() {
+        let kg = KnowledgeGraphParser::new();
+        let doc = Txt.parse("Other\nOther\nnotes", "notes.txt").unwrap();
+        let graph = document_to_graph(&doc, "txt", "notes.txt", 17, &kg);
+
+        let page = &graph.nodes[0];
+        assert_eq!(page.metadata_id, "notes");
+        assert_eq!(page.metadata["content_type"], "txt");
+        assert_eq!(graph.edges.len(), 1, "duplicate and self links dropped");
+        assert_eq!(graph.edges[0].target, kg.page_name_to_id("Other"));
+    }
+}
@@ -6,7 +6,7 @@
 //! - Edges (links, relationships)
 //! - Metadata (properties, tags)
 
-use visionclaw_domain::models::edge::Edge;
+use visionclaw_domain::models::edge::{Edge, EdgeProvenance, ProvenanceSource};
 use visionclaw_domain::models::graph::GraphData;
 use visionclaw_domain::models::metadata::MetadataStore;
 use visionclaw_domain::models::node::Node;
@@ -128,6 +128,27 @@ impl KnowledgeGraphParser {
             metadata.insert("source_domain".to_string(), dom.clone());
         }
 
+        self.build_page_node(page_name, content_size, metadata, owl_class_iri)
+    }
+
+    /// Page node for a document parsed by a [`super::content_parser::ContentParser`].
+    /// `metadata` should already carry `type`, `source_file` and `public`.
+    pub fn document_node(
+        &self,
+        page_name: &str,
+        content_size: usize,
+        metadata: HashMap<String, String>,
+    ) -> Node {
+        self.build_page_node(page_name, content_size, metadata, None)
+    }
+
+    fn build_page_node(
+        &self,
+        page_name: &str,
+        content_size: usize,
+        metadata: HashMap<String, String>,
+        owl_class_iri: Option<String>,
+    ) -> Node {
         let id = self.page_name_to_id(page_name);
 
         // Use existing position or generate random (position preservation)
@@ -208,10 +229,11 @@ impl KnowledgeGraphParser {
 
     /// Extract wikilink edges only — no new nodes created.
     /// Returns Edge objects for each [[WikiLink]] found in content.
-    /// Deduplicates by target to avoid multiple edges to the same page.
+    /// Deduplicates by target to avoid multiple edges to the same page; repeat
+    /// links are tallied in the edge's provenance instead.
     fn extract_wikilink_edges(&self, content: &str, source_id: &u32) -> Vec<Edge> {
-        let mut edges = Vec::new();
-        let mut seen_targets = std::collections::HashSet::new();
+        let mut edges: Vec<Edge> = Vec::new();
+        let mut seen_targets: HashMap<u32, usize> = HashMap::new();
 
         let link_pattern = regex::Regex::new(r"\[\[([^\]|]+)(?:\|[^\]]+)?\]\]")
             .expect("Invalid regex pattern");
@@ -221,10 +243,24 @@ impl KnowledgeGraphParser {
                 let target_page = link_match.as_str().trim().to_string();
                 let target_id = self.page_name_to_id(&target_page);
 
-                // Skip self-loops and duplicates
-                if target_id == *source_id || !seen_targets.insert(target_id) {
+                // Skip self-loops; repeats only bump the wikilink count
+                if target_id == *source_id {
+                    continue;
+                }
+                if let Some(&idx) = seen_targets.get(&target_id) {
+                    if let Some(contribution) = edges[idx]
+                        .provenance
+                        .as_mut()
+                        .and_then(|p| p.contributions.first_mut())
+                    {
+                        contribution.count = Some(contribution.count.unwrap_or(1) + 1);
+                    }
                     continue;
                 }
+                seen_targets.insert(target_id, edges.len());
+
+                let mut provenance = EdgeProvenance::default();
+                provenance.add(ProvenanceSource::Wikilink, 1.0, Some(1), None);
 
                 edges.push(Edge {
                     id: format!("{}_{}", source_id, target_id),
@@ -234,6 +270,7 @@ impl KnowledgeGraphParser {
                     edge_type: Some("explicit_link".to_string()),
                     metadata: None,
                     owl_property_iri: None,
+                    provenance: Some(provenance),
                 });
             }
         }
@@ -300,6 +337,7 @@ impl KnowledgeGraphParser {
                     edge_type: Some("link".to_string()),
                     metadata: Some(HashMap::new()),
                     owl_property_iri: None,
+                    provenance: Some(EdgeProvenance::from_source(ProvenanceSource::Wikilink, 1.0)),
                 });
             }
         }
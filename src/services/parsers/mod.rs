@@ -1,6 +1,11 @@
+pub mod asciidoc_parser;
+pub mod content_parser;
 pub mod knowledge_graph_parser;
+pub mod notebook_parser;
 pub mod ontology_parser;
+pub mod org_mode_parser;
 
+pub use content_parser::{content_parsers, ContentParser, ParsedDocument};
 pub use knowledge_graph_parser::KnowledgeGraphParser;
 pub use ontology_parser::OntologyParser;
 
@@ -0,0 +1,134 @@
+//! Jupyter notebooks (`.ipynb`).
+//!
+//! - title: `metadata.title`, else the first `# ` heading in a markdown cell
+//! - links: `[[wikilinks]]` and relative markdown links to other notebooks
+//!   or pages (`[text](other.ipynb)`) in markdown cells; code cells are not
+//!   scanned
+//! - tags: `metadata.tags` plus every cell's `metadata.tags`
+//! - properties: kernel and language names
+//! - published with `"public": true` in the notebook metadata
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::Value;
+
+use super::content_parser::{file_stem, ContentParser, ParsedDocument};
+
+static WIKILINK: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\[\[([^\]|]+)(?:\|[^\]]+)?\]\]").expect("Invalid regex pattern"));
+static MARKDOWN_LINK: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\[[^\]]*\]\(([^)\s]+\.(?:ipynb|md))(?:#[^)]*)?\)").expect("Invalid regex pattern")
+});
+
+pub struct NotebookParser;
+
+/// Cell `source` is either one string or a list of lines.
+fn cell_source(cell: &Value) -> String {
+    match &cell["source"] {
+        Value::String(s) => s.clone(),
+        Value::Array(lines) => lines.iter().filter_map(Value::as_str).collect(),
+        _ => String::new(),
+    }
+}
+
+fn add_tags(doc: &mut ParsedDocument, tags: &Value) {
+    for tag in tags
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+    {
+        doc.add_tag(tag);
+    }
+}
+
+impl ContentParser for NotebookParser {
+    fn name(&self) -> &'static str {
+        "notebook"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["ipynb"]
+    }
+
+    fn parse(&self, content: &str, file_name: &str) -> Result<ParsedDocument, String> {
+        let notebook: Value = serde_json::from_str(content)
+            .map_err(|e| format!("{} is not a valid notebook: {}", file_name, e))?;
+        let metadata = &notebook["metadata"];
+
+        let mut doc = ParsedDocument {
+            title: metadata["title"].as_str().unwrap_or_default().to_string(),
+            public: metadata["public"].as_bool().unwrap_or(false)
+                || metadata["public"].as_str() == Some("true"),
+            ..Default::default()
+        };
+        add_tags(&mut doc, &metadata["tags"]);
+        if let Some(kernel) = metadata["kernelspec"]["name"].as_str() {
+            doc.properties
+                .insert("kernel".to_string(), kernel.to_string());
+        }
+        if let Some(language) = metadata["language_info"]["name"].as_str() {
+            doc.properties
+                .insert("language".to_string(), language.to_string());
+        }
+
+        for cell in notebook["cells"].as_array().into_iter().flatten() {
+            add_tags(&mut doc, &cell["metadata"]["tags"]);
+            if cell["cell_type"].as_str() != Some("markdown") {
+                continue;
+            }
+            let source = cell_source(cell);
+            if doc.title.is_empty() {
+                if let Some(heading) = source.lines().find_map(|l| l.strip_prefix("# ")) {
+                    doc.title = heading.trim().to_string();
+                }
+            }
+            doc.links.extend(
+                WIKILINK
+                    .captures_iter(&source)
+                    .map(|cap| cap[1].trim().to_string()),
+            );
+            doc.links.extend(
+                MARKDOWN_LINK
+                    .captures_iter(&source)
+                    .filter(|cap| !cap[1].contains("://"))
+                    .map(|cap| file_stem(&cap[1]).to_string()),
+            );
+        }
+        Ok(doc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_markdown_cells_and_metadata() {
+        let notebook = serde_json::json!({
+            "metadata": {
+                "public": true,
+                "tags": ["analysis"],
+                "kernelspec": { "name": "python3" }
+            },
+            "cells": [
+                { "cell_type": "markdown", "metadata": {},
+                  "source": ["# Sensor Calibration\n", "Uses [[Camera]] and [lidar](notebooks/lidar.ipynb).\n"] },
+                { "cell_type": "code", "metadata": { "tags": ["slow"] },
+                  "source": "print('[[NotALink]]')" },
+                { "cell_type": "markdown", "metadata": {},
+                  "source": "See [docs](https://example.com/x.md)." }
+            ]
+        });
+        let doc = NotebookParser
+            .parse(&notebook.to_string(), "calibration.ipynb")
+            .unwrap();
+
+        assert_eq!(doc.title, "Sensor Calibration");
+        assert!(doc.public);
+        assert_eq!(doc.tags, vec!["analysis", "slow"]);
+        assert_eq!(doc.links, vec!["Camera", "lidar"]);
+        assert_eq!(doc.properties["kernel"], "python3");
+        assert!(NotebookParser.parse("not json", "x.ipynb").is_err());
+    }
+}
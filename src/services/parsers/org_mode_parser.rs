@@ -0,0 +1,96 @@
+//! Org-mode (`.org`) documents.
+//!
+//! - title: `#+TITLE:`
+//! - links: `[[target]]` and `[[target][description]]`; a `file:other.org`
+//!   target names the other file's page, while URLs, `id:` and in-file
+//!   heading links are ignored
+//! - tags: `#+FILETAGS:` plus trailing heading tags (`* Heading :a:b:`)
+//! - properties: every other `#+KEY: value` keyword
+//! - published with `#+PUBLIC: true`
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::content_parser::{file_stem, ContentParser, ParsedDocument};
+
+static LINK: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\[\[([^\]]+)\](?:\[[^\]]*\])?\]").expect("Invalid regex pattern"));
+static HEADING_TAGS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\*+\s.*\s(:(?:[^\s:]+:)+)\s*$").expect("Invalid regex pattern"));
+
+pub struct OrgModeParser;
+
+fn link_target(raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    if let Some(path) = raw.strip_prefix("file:") {
+        let path = path.split("::").next().unwrap_or(path);
+        return Some(file_stem(path).to_string());
+    }
+    let in_file = raw.starts_with('*') || raw.starts_with('#');
+    if in_file || raw.contains("://") || raw.starts_with("id:") || raw.starts_with("mailto:") {
+        return None;
+    }
+    Some(raw.to_string())
+}
+
+impl ContentParser for OrgModeParser {
+    fn name(&self) -> &'static str {
+        "org"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["org"]
+    }
+
+    fn parse(&self, content: &str, _file_name: &str) -> Result<ParsedDocument, String> {
+        let mut doc = ParsedDocument::default();
+        for line in content.lines() {
+            let line = line.trim();
+            if let Some((key, value)) = line
+                .strip_prefix("#+")
+                .and_then(|rest| rest.split_once(':'))
+            {
+                let key = key.trim().to_ascii_lowercase();
+                let value = value.trim();
+                match key.as_str() {
+                    "title" => doc.title = value.to_string(),
+                    "filetags" => value
+                        .split(|c: char| c == ':' || c.is_whitespace())
+                        .for_each(|tag| doc.add_tag(tag)),
+                    "public" => doc.public = value.eq_ignore_ascii_case("true"),
+                    _ if !value.is_empty() => {
+                        doc.properties.insert(key, value.to_string());
+                    }
+                    _ => {}
+                }
+            } else if let Some(caps) = HEADING_TAGS.captures(line) {
+                caps[1].split(':').for_each(|tag| doc.add_tag(tag));
+            }
+        }
+        doc.links = LINK
+            .captures_iter(content)
+            .filter_map(|cap| link_target(&cap[1]))
+            .collect();
+        Ok(doc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_keywords_links_and_tags() {
+        let content =
+            "#+TITLE: Field Notes\n#+FILETAGS: :research:draft:\n#+PUBLIC: true\n#+AUTHOR: Sam\n\n\
+            * Sensors :hardware:\nSee [[Camera]] and [[file:lidar.org::*Range][lidar]].\n\
+            Ignore [[https://example.com][site]], [[id:1234]] and [[*Sensors]].\n";
+        let doc = OrgModeParser.parse(content, "notes.org").unwrap();
+
+        assert_eq!(doc.title, "Field Notes");
+        assert!(doc.public);
+        assert_eq!(doc.tags, vec!["research", "draft", "hardware"]);
+        assert_eq!(doc.links, vec!["Camera", "lidar"]);
+        assert_eq!(doc.properties["author"], "Sam");
+    }
+}
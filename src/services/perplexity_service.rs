@@ -1,5 +1,7 @@
+use crate::config::dev_config;
 use crate::config::AppFullSettings; 
 use visionclaw_domain::models::metadata::Metadata;
+use crate::services::cost_service::{costs, estimate_tokens, ApiProvider, ApiUsage};
 use crate::services::file_service::ProcessedFile;
 use log::{error, info};
 use reqwest::Client;
@@ -7,17 +9,34 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::error::Error as StdError;
 use std::fs;
-use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use crate::utils::time;
 
-const MARKDOWN_DIR: &str = "/app/data/markdown";
 
 #[derive(Debug, Serialize, Deserialize)]
 struct PerplexityResponse {
     content: String,
     link: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    usage: Option<PerplexityUsage>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PerplexityUsage {
+    prompt_tokens: u64,
+    completion_tokens: u64,
+}
+
+impl PerplexityResponse {
+    /// The token counts Perplexity reports, or an estimate from the text
+    /// when a response carries none.
+    fn usage(&self, prompt: &str) -> ApiUsage {
+        match &self.usage {
+            Some(usage) => ApiUsage::tokens(usage.prompt_tokens, usage.completion_tokens),
+            None => ApiUsage::tokens(estimate_tokens(prompt), estimate_tokens(&self.content)),
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -158,6 +177,10 @@ impl PerplexityService {
         }
 
         let perplexity_response: PerplexityResponse = response.json().await?;
+        costs().record(
+            ApiProvider::Perplexity,
+            perplexity_response.usage(&request.query),
+        );
         Ok(perplexity_response.content)
     }
 
@@ -165,8 +188,8 @@ impl PerplexityService {
         &self,
         file_name: &str,
     ) -> Result<ProcessedFile, Box<dyn StdError + Send + Sync>> {
-        let file_path = format!("{}/{}", MARKDOWN_DIR, file_name);
-        if !Path::new(&file_path).exists() {
+        let file_path = dev_config::storage().markdown_dir().join(file_name);
+        if !file_path.exists() {
             return Err(Box::new(std::io::Error::new(
                 std::io::ErrorKind::NotFound,
                 format!("File not found: {}", file_name),
@@ -222,6 +245,7 @@ impl PerplexityService {
         }
 
         let perplexity_response: PerplexityResponse = response.json().await?;
+        costs().record(ApiProvider::Perplexity, perplexity_response.usage(&content));
 
         
         let metadata = Metadata {
@@ -239,6 +263,8 @@ impl PerplexityService {
             perplexity_link: perplexity_response.link,
             last_perplexity_process: Some(time::now()),
             topic_counts: HashMap::new(),
+            external_links: Vec::new(),
+            language: crate::services::language_detection::detect_language(&perplexity_response.content),
             // Ontology fields (not applicable for Perplexity responses)
             term_id: None,
             preferred_term: None,
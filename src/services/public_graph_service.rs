@@ -0,0 +1,271 @@
+//! Cached, read-only snapshot of the public part of the graph.
+//!
+//! Backs the `/public/graph/*` mirror used by embeddable widgets. Requests only
+//! ever read the last [`PublicGraphSnapshot`]; a background task rebuilds it
+//! every `publicApi.cacheTtlSecs` from `GetGraphData`, so public traffic never
+//! touches the graph actors or the simulation loop directly.
+//!
+//! Only pages flagged `public:: true` are included, and only edges whose two
+//! endpoints are both public.
+
+use actix::Addr;
+use chrono::{DateTime, Utc};
+use log::{debug, warn};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use visionclaw_domain::models::graph::GraphData;
+use visionclaw_domain::models::node::Node;
+
+use crate::actors::graph_service_supervisor::GraphServiceSupervisor;
+use crate::actors::messages::GetGraphData;
+use crate::config::PublicApiSettings;
+
+const TOP_NODES: usize = 20;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicNodeRef {
+    pub id: u32,
+    pub label: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicNode {
+    pub id: u32,
+    pub label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    pub degree: usize,
+    pub neighbours: Vec<PublicNodeRef>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicGraphSummary {
+    pub node_count: usize,
+    pub edge_count: usize,
+    pub node_types: BTreeMap<String, usize>,
+    pub top_nodes: Vec<PublicNodeRef>,
+    pub generated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PublicGraphSnapshot {
+    pub summary: PublicGraphSummary,
+    pub nodes: HashMap<u32, PublicNode>,
+}
+
+fn is_public(node: &Node) -> bool {
+    node.metadata
+        .get("public")
+        .is_some_and(|v| v.eq_ignore_ascii_case("true"))
+}
+
+impl PublicGraphSnapshot {
+    pub fn build(graph: &GraphData, max_neighbours: usize, now: DateTime<Utc>) -> Self {
+        let public: HashMap<u32, &Node> = graph
+            .nodes
+            .iter()
+            .filter(|n| is_public(n))
+            .map(|n| (n.id, n))
+            .collect();
+
+        let mut adjacency: HashMap<u32, Vec<u32>> = HashMap::new();
+        let mut edge_count = 0;
+        for edge in &graph.edges {
+            if edge.source == edge.target
+                || !public.contains_key(&edge.source)
+                || !public.contains_key(&edge.target)
+            {
+                continue;
+            }
+            edge_count += 1;
+            adjacency.entry(edge.source).or_default().push(edge.target);
+            adjacency.entry(edge.target).or_default().push(edge.source);
+        }
+
+        let node_ref = |id: u32| PublicNodeRef {
+            id,
+            label: public.get(&id).map(|n| n.label.clone()).unwrap_or_default(),
+        };
+
+        let mut node_types: BTreeMap<String, usize> = BTreeMap::new();
+        let mut nodes = HashMap::with_capacity(public.len());
+        for (&id, node) in &public {
+            let mut neighbours = adjacency.remove(&id).unwrap_or_default();
+            neighbours.sort_unstable();
+            neighbours.dedup();
+            let degree = neighbours.len();
+            *node_types
+                .entry(node.node_type.clone().unwrap_or_else(|| "page".to_string()))
+                .or_default() += 1;
+            nodes.insert(
+                id,
+                PublicNode {
+                    id,
+                    label: node.label.clone(),
+                    node_type: node.node_type.clone(),
+                    color: node.color.clone(),
+                    degree,
+                    neighbours: neighbours
+                        .into_iter()
+                        .take(max_neighbours)
+                        .map(node_ref)
+                        .collect(),
+                },
+            );
+        }
+
+        let mut ranked: Vec<&PublicNode> = nodes.values().collect();
+        ranked.sort_by(|a, b| b.degree.cmp(&a.degree).then(a.id.cmp(&b.id)));
+        let top_nodes = ranked
+            .into_iter()
+            .take(TOP_NODES)
+            .map(|n| PublicNodeRef {
+                id: n.id,
+                label: n.label.clone(),
+            })
+            .collect();
+
+        Self {
+            summary: PublicGraphSummary {
+                node_count: nodes.len(),
+                edge_count,
+                node_types,
+                top_nodes,
+                generated_at: Some(now),
+            },
+            nodes,
+        }
+    }
+}
+
+pub struct PublicGraphService {
+    settings: PublicApiSettings,
+    graph_service_addr: Addr<GraphServiceSupervisor>,
+    snapshot: RwLock<Arc<PublicGraphSnapshot>>,
+}
+
+impl PublicGraphService {
+    pub fn new(
+        settings: PublicApiSettings,
+        graph_service_addr: Addr<GraphServiceSupervisor>,
+    ) -> Self {
+        Self {
+            settings,
+            graph_service_addr,
+            snapshot: RwLock::new(Arc::new(PublicGraphSnapshot::default())),
+        }
+    }
+
+    pub fn settings(&self) -> &PublicApiSettings {
+        &self.settings
+    }
+
+    /// Last built snapshot; empty until the first refresh completes.
+    pub fn snapshot(&self) -> Arc<PublicGraphSnapshot> {
+        match self.snapshot.read() {
+            Ok(guard) => Arc::clone(&guard),
+            Err(poisoned) => Arc::clone(&poisoned.into_inner()),
+        }
+    }
+
+    /// Rebuild the snapshot every `cache_ttl_secs`.
+    pub fn spawn(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(Duration::from_secs(self.settings.cache_ttl_secs));
+            loop {
+                interval.tick().await;
+                self.refresh().await;
+            }
+        });
+    }
+
+    pub async fn refresh(&self) {
+        let graph = match self.graph_service_addr.send(GetGraphData).await {
+            Ok(Ok(graph)) => graph,
+            Ok(Err(e)) => {
+                warn!("[PublicApi] Snapshot refresh skipped: {}", e);
+                return;
+            }
+            Err(e) => {
+                warn!("[PublicApi] Graph service unreachable: {}", e);
+                return;
+            }
+        };
+        let max_neighbours = self.settings.max_neighbours;
+        let built = tokio::task::spawn_blocking(move || {
+            PublicGraphSnapshot::build(&graph, max_neighbours, Utc::now())
+        })
+        .await;
+        match built {
+            Ok(snapshot) => {
+                debug!(
+                    "[PublicApi] Snapshot rebuilt: {} public nodes, {} edges",
+                    snapshot.summary.node_count, snapshot.summary.edge_count
+                );
+                let snapshot = Arc::new(snapshot);
+                match self.snapshot.write() {
+                    Ok(mut guard) => *guard = snapshot,
+                    Err(poisoned) => *poisoned.into_inner() = snapshot,
+                }
+            }
+            Err(e) => warn!("[PublicApi] Snapshot build failed: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use visionclaw_domain::models::edge::Edge;
+
+    fn node(id: u32, label: &str, public: bool) -> Node {
+        let mut n = Node::new_with_id(label.to_string(), Some(id));
+        n.label = label.to_string();
+        if public {
+            n.metadata.insert("public".to_string(), "true".to_string());
+        }
+        n
+    }
+
+    #[test]
+    fn snapshot_excludes_private_nodes_and_their_edges() {
+        let mut g = GraphData::new();
+        g.nodes = vec![
+            node(1, "A", true),
+            node(2, "B", true),
+            node(3, "Secret", false),
+        ];
+        g.edges = vec![
+            Edge::new(1, 2, 1.0),
+            Edge::new(1, 3, 1.0),
+            Edge::new(2, 1, 1.0),
+        ];
+
+        let snap = PublicGraphSnapshot::build(&g, 10, Utc::now());
+        assert_eq!(snap.summary.node_count, 2);
+        assert_eq!(snap.summary.edge_count, 2);
+        assert!(!snap.nodes.contains_key(&3));
+        let a = &snap.nodes[&1];
+        assert_eq!(a.degree, 1);
+        assert_eq!(a.neighbours[0].label, "B");
+        assert_eq!(snap.summary.top_nodes.len(), 2);
+    }
+
+    #[test]
+    fn neighbours_are_capped() {
+        let mut g = GraphData::new();
+        g.nodes = (1..=5).map(|i| node(i, &format!("N{}", i), true)).collect();
+        g.edges = (2..=5).map(|i| Edge::new(1, i, 1.0)).collect();
+        let snap = PublicGraphSnapshot::build(&g, 2, Utc::now());
+        assert_eq!(snap.nodes[&1].degree, 4);
+        assert_eq!(snap.nodes[&1].neighbours.len(), 2);
+    }
+}
@@ -82,6 +82,37 @@ pub enum ChatResponse {
     Streaming(Pin<Box<dyn Stream<Item = Result<Bytes, actix_web::Error>> + Send + 'static>>),
 }
 
+/// Told the names of the documents an answer cites, once they arrive.
+pub type CitationSink = Box<dyn FnOnce(Vec<String>) + Send + 'static>;
+
+/// Names of the documents a completion's `data` cites, first mention first.
+/// RAGFlow lists them in `reference.doc_aggs` and again per chunk in
+/// `reference.chunks`, as arrays or as maps keyed by id.
+pub fn cited_documents(data: &serde_json::Value) -> Vec<String> {
+    fn entries(value: &serde_json::Value) -> Vec<&serde_json::Value> {
+        match value {
+            serde_json::Value::Array(items) => items.iter().collect(),
+            serde_json::Value::Object(items) => items.values().collect(),
+            _ => Vec::new(),
+        }
+    }
+    let reference = &data["reference"];
+    let mut names: Vec<String> = Vec::new();
+    let aggs = entries(&reference["doc_aggs"]);
+    let chunks = entries(&reference["chunks"]);
+    for entry in aggs.into_iter().chain(chunks) {
+        let name = entry["doc_name"]
+            .as_str()
+            .or_else(|| entry["document_name"].as_str())
+            .map(str::trim)
+            .unwrap_or_default();
+        if !name.is_empty() && !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
 #[derive(Debug, Serialize)]
 struct CompletionRequest {
     question: String,
@@ -231,6 +262,7 @@ impl RAGFlowService {
         _quote: bool,                  
         _doc_ids: Option<Vec<String>>, 
         stream: bool,
+        on_cited: Option<CitationSink>,
     ) -> Result<
         Pin<Box<dyn Stream<Item = Result<String, RAGFlowError>> + Send + 'static>>,
         RAGFlowError,
@@ -268,6 +300,7 @@ impl RAGFlowService {
         let status = response.status();
         info!("Response status: {}", status);
 
+        let mut on_cited = on_cited;
         if status.is_success() {
             if stream {
                 let stream = response.bytes_stream().map(move |chunk_result| {
@@ -287,6 +320,12 @@ impl RAGFlowService {
                                         } else if let Some(answer) =
                                             json_response["data"]["answer"].as_str()
                                         {
+                                            let cited = cited_documents(&json_response["data"]);
+                                            if !cited.is_empty() {
+                                                if let Some(sink) = on_cited.take() {
+                                                    sink(cited);
+                                                }
+                                            }
                                             Ok(answer.to_string())
                                         } else {
                                             Err(RAGFlowError::ParseError(
@@ -316,7 +355,10 @@ impl RAGFlowService {
                 let result: serde_json::Value = response.json().await?;
 
                 if let Some(answer) = result["data"]["answer"].as_str() {
-                    
+                    let cited = cited_documents(&result["data"]);
+                    if let Some(sink) = on_cited.filter(|_| !cited.is_empty()) {
+                        sink(cited);
+                    }
                     let stream = futures::stream::once(futures::future::ok(answer.to_string()));
                     Ok(Box::pin(stream))
                 } else {
@@ -372,6 +414,7 @@ impl RAGFlowService {
         session_id: String,
         message: String,
         stream_preference: bool,
+        on_cited: Option<CitationSink>,
     ) -> Result<ChatResponse, RAGFlowError> {
         info!(
             "Sending chat message to RAGFlow session: {}, stream_preference: {}",
@@ -438,11 +481,17 @@ impl RAGFlowService {
                 .map(|s| s.to_string())
                 .unwrap_or_else(|| session_id.clone());
 
+            let cited = cited_documents(&result["data"]);
+            if let Some(sink) = on_cited.filter(|_| !cited.is_empty()) {
+                sink(cited);
+            }
+
             Ok(ChatResponse::Buffered {
                 answer,
                 session_id: final_session_id,
             })
         } else {
+            let mut on_cited = on_cited;
             // True streaming: transform the upstream SSE byte stream into
             // parsed JSON-line chunks suitable for HttpResponse::streaming().
             let byte_stream = response.bytes_stream().map(move |chunk_result| {
@@ -475,6 +524,12 @@ impl RAGFlowService {
                                         .and_then(|d| d.get("answer"))
                                         .and_then(|a| a.as_str())
                                     {
+                                        let cited = cited_documents(&json_val["data"]);
+                                        if !cited.is_empty() {
+                                            if let Some(sink) = on_cited.take() {
+                                                sink(cited);
+                                            }
+                                        }
                                         out.push_str(answer_chunk);
                                     } else if let Some(answer_chunk) =
                                         json_val.get("answer").and_then(|a| a.as_str())
@@ -507,6 +562,17 @@ impl RAGFlowService {
             Ok(ChatResponse::Streaming(Box::pin(byte_stream)))
         }
     }
+
+    /// The agent's OpenAI-compatible chat-completions URL and the key to call
+    /// it with, for callers that need function calling.
+    pub fn openai_compatible_endpoint(&self) -> (String, String) {
+        let url = format!(
+            "{}/api/v1/agents_openai/{}/chat/completions",
+            self.base_url.trim_end_matches('/'),
+            self.agent_id
+        );
+        (url, self.api_key.clone())
+    }
 } 
 
 impl Clone for RAGFlowService {
@@ -519,3 +585,25 @@ impl Clone for RAGFlowService {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn cited_documents_reads_aggregates_and_chunks() {
+        let data = json!({
+            "answer": "Rust is a language.",
+            "reference": {
+                "chunks": [
+                    {"document_name": "Rust.md"},
+                    {"document_name": "Cargo.md"},
+                ],
+                "doc_aggs": {"d1": {"doc_name": "Rust.md", "count": 2}},
+            },
+        });
+        assert_eq!(cited_documents(&data), vec!["Rust.md", "Cargo.md"]);
+        assert!(cited_documents(&json!({"answer": "No sources."})).is_empty());
+    }
+}
@@ -0,0 +1,406 @@
+//! Settings change history.
+//!
+//! Every write to the physics, rendering and visual settings records who
+//! made it, when, over which channel and exactly which values changed. Each
+//! change is a JSON pointer into the section with the value before and
+//! after; a pointer that was absent on one side has no value there. Objects
+//! are compared key by key, anything else as a whole, so an edited array is
+//! one change.
+//!
+//! Physics and rendering live in `AppFullSettings`, so the settings actor
+//! records them on every `UpdateSettings`, whichever handler sent it (see
+//! [`typed_sections`]). Visual settings are stored on their own and are
+//! recorded by their writer.
+//!
+//! `GET /api/settings/history` lists entries newest first and
+//! `POST /api/settings/history/{id}/revert` puts the values an entry changed
+//! back to what they were before it, leaving everything else as it is now.
+//! A value the entry added is removed again. The revert is itself recorded,
+//! pointing at the entry it undid. The log keeps the last [`MAX_ENTRIES`]
+//! entries in `settings_history.json` in the data directory.
+
+use crate::config::{dev_config, AppFullSettings};
+use crate::services::json_file::JsonFile;
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+/// Entries kept before the oldest are dropped.
+pub const MAX_ENTRIES: usize = 1000;
+
+/// Sections whose changes are recorded and can be reverted.
+pub const SECTIONS: [&str; 3] = ["physics", "rendering", "visual"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeSource {
+    Rest,
+    WebSocket,
+}
+
+/// Who made a change and how.
+#[derive(Debug, Clone)]
+pub struct ChangeOrigin {
+    pub author: String,
+    pub source: ChangeSource,
+    /// Set when the change reverts an earlier entry.
+    pub revert_of: Option<u64>,
+}
+
+impl ChangeOrigin {
+    pub fn new(author: impl Into<String>, source: ChangeSource) -> Self {
+        Self {
+            author: author.into(),
+            source,
+            revert_of: None,
+        }
+    }
+
+    pub fn revert(author: impl Into<String>, entry_id: u64) -> Self {
+        Self {
+            revert_of: Some(entry_id),
+            ..Self::new(author, ChangeSource::Rest)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingChange {
+    /// JSON pointer into the section, e.g. `/springK` or `/glow/intensity`.
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntry {
+    pub id: u64,
+    /// Milliseconds since the Unix epoch.
+    pub timestamp: i64,
+    pub author: String,
+    pub source: ChangeSource,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revert_of: Option<u64>,
+    pub section: String,
+    pub changes: Vec<SettingChange>,
+}
+
+fn escape_pointer_token(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn unescape_pointer_token(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+fn diff_into(prefix: &str, old: Option<&Value>, new: Option<&Value>, out: &mut Vec<SettingChange>) {
+    match (old, new) {
+        (Some(Value::Object(old)), Some(Value::Object(new))) => {
+            let mut keys: Vec<&String> = old.keys().chain(new.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let path = format!("{}/{}", prefix, escape_pointer_token(key));
+                diff_into(&path, old.get(key), new.get(key), out);
+            }
+        }
+        (old, new) if old != new => out.push(SettingChange {
+            path: prefix.to_string(),
+            from: old.cloned(),
+            to: new.cloned(),
+        }),
+        _ => {}
+    }
+}
+
+/// Leaf values that differ between `old` and `new`, ordered by path.
+pub fn diff(old: &Value, new: &Value) -> Vec<SettingChange> {
+    let mut changes = Vec::new();
+    diff_into("", Some(old), Some(new), &mut changes);
+    changes
+}
+
+/// Set `pointer` in `root` to `value`, creating objects along the way. The
+/// root pointer replaces `root`.
+fn set_pointer(root: &mut Value, pointer: &str, value: Value) {
+    let tokens: Vec<String> = pointer
+        .split('/')
+        .skip(1)
+        .map(unescape_pointer_token)
+        .collect();
+    let mut node = root;
+    for token in tokens {
+        if !node.is_object() {
+            *node = Value::Object(Map::new());
+        }
+        node = node
+            .as_object_mut()
+            .expect("just made an object")
+            .entry(token)
+            .or_insert(Value::Null);
+    }
+    *node = value;
+}
+
+/// Remove `pointer` from `root`, if it is there.
+fn remove_pointer(root: &mut Value, pointer: &str) {
+    let Some((parent, last)) = pointer.rsplit_once('/') else {
+        return;
+    };
+    let parent = if parent.is_empty() {
+        Some(root)
+    } else {
+        root.pointer_mut(parent)
+    };
+    if let Some(Value::Object(map)) = parent {
+        map.remove(&unescape_pointer_token(last));
+    }
+}
+
+/// `current` with every value `entry` changed put back to its earlier value,
+/// and every value it added removed.
+pub fn reverted(current: &Value, entry: &HistoryEntry) -> Value {
+    let mut section = current.clone();
+    for change in &entry.changes {
+        match &change.from {
+            Some(from) => set_pointer(&mut section, &change.path, from.clone()),
+            None => remove_pointer(&mut section, &change.path),
+        }
+    }
+    section
+}
+
+/// The recorded sections held in `AppFullSettings`, as JSON.
+pub fn typed_sections(settings: &AppFullSettings) -> [(&'static str, Value); 2] {
+    [
+        (
+            "physics",
+            serde_json::to_value(&settings.visualisation.graphs.logseq.physics).unwrap_or_default(),
+        ),
+        (
+            "rendering",
+            serde_json::to_value(&settings.visualisation.rendering).unwrap_or_default(),
+        ),
+    ]
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct History {
+    next_id: u64,
+    entries: VecDeque<HistoryEntry>,
+}
+
+impl History {
+    /// Record the change from `old` to `new`; `None` when nothing changed.
+    pub fn record(
+        &mut self,
+        origin: &ChangeOrigin,
+        section: &str,
+        old: &Value,
+        new: &Value,
+        timestamp: i64,
+    ) -> Option<HistoryEntry> {
+        let changes = diff(old, new);
+        if changes.is_empty() {
+            return None;
+        }
+        self.next_id += 1;
+        let entry = HistoryEntry {
+            id: self.next_id,
+            timestamp,
+            author: origin.author.clone(),
+            source: origin.source,
+            revert_of: origin.revert_of,
+            section: section.to_string(),
+            changes,
+        };
+        self.entries.push_back(entry.clone());
+        while self.entries.len() > MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        Some(entry)
+    }
+
+    /// Newest first, optionally for one section.
+    pub fn list(&self, section: Option<&str>, limit: usize) -> Vec<HistoryEntry> {
+        self.entries
+            .iter()
+            .rev()
+            .filter(|e| section.is_none_or(|s| e.section == s))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    pub fn get(&self, id: u64) -> Option<HistoryEntry> {
+        self.entries.iter().find(|e| e.id == id).cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Process-wide history persisted to disk.
+pub struct SettingsHistoryService {
+    file: JsonFile,
+    history: RwLock<History>,
+}
+
+impl SettingsHistoryService {
+    pub fn load(path: PathBuf) -> Self {
+        let file = JsonFile::new(path, "settings history");
+        let history: History = file.load();
+        info!("Loaded {} settings history entr(ies)", history.len());
+        Self {
+            file,
+            history: RwLock::new(history),
+        }
+    }
+
+    /// Record a change and persist the log. A failed write is logged, not
+    /// returned: the settings change itself has already happened.
+    pub fn record(
+        &self,
+        origin: &ChangeOrigin,
+        section: &str,
+        old: &Value,
+        new: &Value,
+    ) -> Option<HistoryEntry> {
+        let mut history = self.history.write().unwrap_or_else(|e| e.into_inner());
+        let entry = history.record(
+            origin,
+            section,
+            old,
+            new,
+            chrono::Utc::now().timestamp_millis(),
+        )?;
+        info!(
+            "Settings history #{}: {} changed {} value(s) in {} via {:?}",
+            entry.id,
+            entry.author,
+            entry.changes.len(),
+            entry.section,
+            entry.source
+        );
+        if let Err(e) = self.file.save(&*history) {
+            warn!("Failed to persist settings history: {}", e);
+        }
+        Some(entry)
+    }
+
+    pub fn list(&self, section: Option<&str>, limit: usize) -> (Vec<HistoryEntry>, usize) {
+        let history = self.history.read().unwrap_or_else(|e| e.into_inner());
+        (history.list(section, limit), history.len())
+    }
+
+    pub fn get(&self, id: u64) -> Option<HistoryEntry> {
+        self.history
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(id)
+    }
+
+}
+
+static SERVICE: Lazy<SettingsHistoryService> =
+    Lazy::new(|| SettingsHistoryService::load(dev_config::storage().path("settings_history.json")));
+
+pub fn settings_history() -> &'static SettingsHistoryService {
+    &SERVICE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn records_diffs_and_reverts_them() {
+        let before = json!({"springK": 0.1, "glow": {"intensity": 1.0, "color": "#fff"}, "a/b": 1});
+        let after = json!({"springK": 0.2, "glow": {"intensity": 1.0, "radius": 3}, "a/b": 1});
+
+        let changes = diff(&before, &after);
+        assert_eq!(
+            changes,
+            vec![
+                SettingChange {
+                    path: "/glow/color".to_string(),
+                    from: Some(json!("#fff")),
+                    to: None,
+                },
+                SettingChange {
+                    path: "/glow/radius".to_string(),
+                    from: None,
+                    to: Some(json!(3)),
+                },
+                SettingChange {
+                    path: "/springK".to_string(),
+                    from: Some(json!(0.1)),
+                    to: Some(json!(0.2)),
+                },
+            ]
+        );
+        assert!(diff(&before, &before).is_empty());
+
+        let mut history = History::default();
+        let alice = ChangeOrigin::new("alice", ChangeSource::WebSocket);
+        assert!(history
+            .record(&alice, "visual", &before, &before, 1)
+            .is_none());
+        let entry = history
+            .record(&alice, "visual", &before, &after, 2)
+            .unwrap();
+        assert_eq!((entry.id, entry.source), (1, ChangeSource::WebSocket));
+
+        // Someone else changes springK again; reverting entry 1 only touches
+        // what entry 1 changed.
+        let mut now = after.clone();
+        now["a/b"] = json!(2);
+        let restored = reverted(&now, &entry);
+        assert_eq!(
+            restored,
+            json!({"springK": 0.1, "glow": {"intensity": 1.0, "color": "#fff"}, "a/b": 2})
+        );
+
+        let revert = history
+            .record(
+                &ChangeOrigin::revert("bob", entry.id),
+                "visual",
+                &now,
+                &restored,
+                3,
+            )
+            .unwrap();
+        assert_eq!(revert.revert_of, Some(1));
+        assert_eq!(
+            history
+                .list(None, 10)
+                .iter()
+                .map(|e| e.id)
+                .collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+        assert!(history.list(Some("physics"), 10).is_empty());
+        assert_eq!(history.get(1).unwrap().author, "alice");
+
+        for i in 0..MAX_ENTRIES as i64 {
+            history.record(&alice, "physics", &json!({"k": i}), &json!({"k": i + 1}), i);
+        }
+        assert_eq!(history.len(), MAX_ENTRIES);
+        assert!(history.get(1).is_none());
+    }
+}
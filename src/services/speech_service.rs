@@ -23,6 +23,7 @@ use log::{debug, error, info};
 use tokio::net::TcpStream;
 use url::Url;
 // DEPRECATED: call_task_orchestrate_docker removed - use TaskOrchestratorActor
+use crate::services::cost_service::{costs, ApiProvider, ApiUsage};
 use crate::services::voice_context_manager::VoiceContextManager;
 use crate::services::voice_tag_manager::{TaggedVoiceResponse, VoiceTagManager};
 use chrono;
@@ -279,6 +280,17 @@ impl SpeechService {
                                                 error!("OpenAI Realtime API error: {:?}", event);
                                                 break;
                                             }
+                                            Some("response.done") => {
+                                                let usage = &event["response"]["usage"];
+                                                costs().record(
+                                                    ApiProvider::OpenaiRealtime,
+                                                    ApiUsage::tokens(
+                                                        usage["input_tokens"].as_u64().unwrap_or(0),
+                                                        usage["output_tokens"].as_u64().unwrap_or(0),
+                                                    ),
+                                                );
+                                                break;
+                                            }
                                             Some("response.completed") => break,
                                             _ => {}
                                         }
@@ -351,6 +363,12 @@ impl SpeechService {
                                                     );
                                                     continue;
                                                 }
+                                                costs().record(
+                                                    ApiProvider::OpenaiTts,
+                                                    ApiUsage::characters(
+                                                        text.chars().count() as u64,
+                                                    ),
+                                                );
                                                 response
                                             }
                                             Err(e) => {
@@ -813,6 +831,7 @@ impl SpeechService {
                                     if let Some(api_key) = config.api_key.as_ref() {
                                         let api_url =
                                             "https://api.openai.com/v1/audio/transcriptions";
+                                        let audio_seconds = wav_seconds(&audio_data);
 
                                         let form = reqwest::multipart::Form::new()
                                             .part(
@@ -846,6 +865,10 @@ impl SpeechService {
                                             {
                                                 Ok(response) => {
                                                     if response.status().is_success() {
+                                                        costs().record(
+                                                            ApiProvider::OpenaiTranscription,
+                                                            ApiUsage::audio(audio_seconds),
+                                                        );
                                                         match response
                                                             .json::<serde_json::Value>()
                                                             .await
@@ -1412,3 +1435,19 @@ impl SpeechService {
         }
     }
 }
+
+/// Length of a WAV clip in seconds, from the byte rate in its header. Clips
+/// without one are taken as 16 kHz 16-bit mono.
+fn wav_seconds(audio: &[u8]) -> f64 {
+    const HEADER: usize = 44;
+    let byte_rate = match audio.get(..HEADER) {
+        Some(header) if &header[0..4] == b"RIFF" && &header[8..12] == b"WAVE" => {
+            u32::from_le_bytes([header[28], header[29], header[30], header[31]])
+        }
+        _ => 0,
+    };
+    if byte_rate == 0 {
+        return audio.len() as f64 / 32_000.0;
+    }
+    audio.len().saturating_sub(HEADER) as f64 / byte_rate as f64
+}
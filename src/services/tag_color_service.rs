@@ -0,0 +1,334 @@
+//! Deterministic tag colours.
+//!
+//! A tag's colour comes from hashing its normalised name (trimmed,
+//! lowercased, leading `#` dropped) with FNV-1a into the palette set as
+//! `rendering.tag_palette` in dev_config, so "project" is the same hue in
+//! every session and on every client with no shared state. Palette entries
+//! that are not `#RRGGBB` or `#RRGGBBAA` are dropped. Colours pinned
+//! with `PUT /api/graph/tag-colors/{tag}` take precedence over the hash and
+//! are kept in `tag_colors.json` in the data directory.
+//!
+//! A node takes the colour of its first tag; an edge takes the colour of the
+//! first source tag that the target also carries.
+
+use crate::config::dev_config;
+use crate::services::json_file::JsonFile;
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+/// Used when dev_config's `tag_palette` has no valid colour.
+pub const DEFAULT_TAG_PALETTE: [&str; 12] = [
+    "#4E79A7", "#F28E2B", "#E15759", "#76B7B2", "#59A14F", "#EDC948", "#B07AA1", "#FF9DA7",
+    "#9C755F", "#BAB0AC", "#17BECF", "#BCBD22",
+];
+
+pub fn normalise_tag(tag: &str) -> String {
+    tag.trim().trim_start_matches('#').trim().to_lowercase()
+}
+
+/// 64-bit FNV-1a. `std`'s hasher is not stable across releases.
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Normalised tags from a node's `tags` metadata (`"a, b"`), in order.
+pub fn node_tags(metadata: &HashMap<String, String>) -> Vec<String> {
+    metadata
+        .get("tags")
+        .map(|tags| {
+            tags.split(',')
+                .map(normalise_tag)
+                .filter(|t| !t.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagColors {
+    pub palette: Vec<String>,
+    /// Normalised tag to colour.
+    #[serde(default)]
+    pub pins: BTreeMap<String, String>,
+}
+
+impl TagColors {
+    /// Invalid palette colours and pins are dropped with a warning.
+    pub fn new(palette: Vec<String>, mut pins: BTreeMap<String, String>) -> Self {
+        let palette: Vec<String> = palette
+            .into_iter()
+            .filter(|color| {
+                let valid = crate::config::validate_hex_color(color).is_ok();
+                if !valid {
+                    warn!("Ignoring invalid tag palette colour '{}'", color);
+                }
+                valid
+            })
+            .collect();
+        pins.retain(|tag, color| {
+            let valid = crate::config::validate_hex_color(color).is_ok();
+            if !valid {
+                warn!(
+                    "Ignoring invalid colour '{}' pinned to tag '{}'",
+                    color, tag
+                );
+            }
+            valid
+        });
+        let palette = if palette.is_empty() {
+            DEFAULT_TAG_PALETTE.iter().map(|c| c.to_string()).collect()
+        } else {
+            palette
+        };
+        Self { palette, pins }
+    }
+
+    pub fn color_for(&self, tag: &str) -> Option<&str> {
+        let tag = normalise_tag(tag);
+        if tag.is_empty() {
+            return None;
+        }
+        if let Some(color) = self.pins.get(&tag) {
+            return Some(color);
+        }
+        let index = (fnv1a(&tag) % self.palette.len() as u64) as usize;
+        Some(&self.palette[index])
+    }
+
+    pub fn node_color(&self, metadata: &HashMap<String, String>) -> Option<String> {
+        node_tags(metadata)
+            .first()
+            .and_then(|tag| self.color_for(tag))
+            .map(str::to_string)
+    }
+
+    pub fn edge_color(
+        &self,
+        source: &HashMap<String, String>,
+        target: &HashMap<String, String>,
+    ) -> Option<String> {
+        let target_tags = node_tags(target);
+        node_tags(source)
+            .into_iter()
+            .find(|tag| target_tags.contains(tag))
+            .and_then(|tag| self.color_for(&tag).map(str::to_string))
+    }
+
+    /// Pin `tag` to `color`; returns the normalised tag.
+    pub fn pin(&mut self, tag: &str, color: &str) -> Result<String, String> {
+        let tag = normalise_tag(tag);
+        if tag.is_empty() {
+            return Err("tag must not be empty".to_string());
+        }
+        crate::config::validate_hex_color(color)
+            .map_err(|_| format!("Invalid colour '{}': expected #RRGGBB or #RRGGBBAA", color))?;
+        self.pins.insert(tag.clone(), color.to_uppercase());
+        Ok(tag)
+    }
+
+    /// Remove a pin; returns whether there was one.
+    pub fn unpin(&mut self, tag: &str) -> bool {
+        self.pins.remove(&normalise_tag(tag)).is_some()
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PinFile {
+    #[serde(default)]
+    pins: BTreeMap<String, String>,
+}
+
+/// Process-wide tag colours with pins persisted to disk.
+pub struct TagColorService {
+    file: JsonFile,
+    colors: RwLock<TagColors>,
+}
+
+impl TagColorService {
+    pub fn load(path: PathBuf, palette: Vec<String>) -> Self {
+        let file = JsonFile::new(path, "tag colour pins").pretty();
+        let pins = file.load::<PinFile>().pins;
+        info!("Loaded {} pinned tag colour(s)", pins.len());
+        Self {
+            file,
+            colors: RwLock::new(TagColors::new(palette, pins)),
+        }
+    }
+
+    pub fn snapshot(&self) -> TagColors {
+        self.colors
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    pub fn pin(&self, tag: &str, color: &str) -> Result<String, String> {
+        let mut colors = self.colors.write().unwrap_or_else(|e| e.into_inner());
+        let mut updated = colors.clone();
+        let tag = updated.pin(tag, color)?;
+        self.save(&updated.pins)?;
+        *colors = updated;
+        Ok(tag)
+    }
+
+    pub fn unpin(&self, tag: &str) -> Result<bool, String> {
+        let mut colors = self.colors.write().unwrap_or_else(|e| e.into_inner());
+        let mut updated = colors.clone();
+        if !updated.unpin(tag) {
+            return Ok(false);
+        }
+        self.save(&updated.pins)?;
+        *colors = updated;
+        Ok(true)
+    }
+
+    fn save(&self, pins: &BTreeMap<String, String>) -> Result<(), String> {
+        self.file.save(&PinFile { pins: pins.clone() })
+    }
+}
+
+static SERVICE: Lazy<TagColorService> = Lazy::new(|| {
+    TagColorService::load(
+        dev_config::storage().path("tag_colors.json"),
+        dev_config::rendering().tag_palette.clone(),
+    )
+});
+
+pub fn tag_colors() -> &'static TagColorService {
+    &SERVICE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(list: &str) -> HashMap<String, String> {
+        HashMap::from([("tags".to_string(), list.to_string())])
+    }
+
+    fn default_colors() -> TagColors {
+        TagColors::new(Vec::new(), BTreeMap::new())
+    }
+
+    #[test]
+    fn empty_palette_falls_back_to_the_default() {
+        assert_eq!(default_colors().palette.len(), DEFAULT_TAG_PALETTE.len());
+    }
+
+    #[test]
+    fn invalid_palette_entries_are_dropped() {
+        let colors = TagColors::new(
+            vec![
+                "#112233".into(),
+                "red".into(),
+                "#12345".into(),
+                "#AABBCCDD".into(),
+            ],
+            BTreeMap::new(),
+        );
+        assert_eq!(colors.palette, vec!["#112233", "#AABBCCDD"]);
+    }
+
+    #[test]
+    fn palette_without_a_valid_colour_falls_back_to_the_default() {
+        let colors = TagColors::new(vec!["blue".into(), "".into()], BTreeMap::new());
+        assert_eq!(colors.palette.len(), DEFAULT_TAG_PALETTE.len());
+    }
+
+    #[test]
+    fn invalid_stored_pins_are_dropped() {
+        let pins = BTreeMap::from([
+            ("ops".to_string(), "#00FF00".to_string()),
+            ("draft".to_string(), "green".to_string()),
+        ]);
+        let colors = TagColors::new(Vec::new(), pins);
+        assert_eq!(colors.pins.len(), 1);
+        assert_eq!(colors.color_for("ops"), Some("#00FF00"));
+    }
+
+    #[test]
+    fn hash_assignment_is_stable() {
+        // Fixed hash, so the assignment never changes between releases.
+        assert_eq!(fnv1a("project"), 0xac16_7f3e_17fa_e47e);
+        assert_eq!(default_colors().color_for("project"), Some("#E15759"));
+    }
+
+    #[test]
+    fn tags_are_normalised_before_hashing() {
+        let colors = default_colors();
+        assert_eq!(colors.color_for(" #Project "), colors.color_for("project"));
+        assert_eq!(colors.color_for("  "), None);
+    }
+
+    #[test]
+    fn node_takes_its_first_tag_colour() {
+        let colors = default_colors();
+        assert_eq!(
+            colors.node_color(&tags("Project, ops")).as_deref(),
+            colors.color_for("project")
+        );
+        assert_eq!(colors.node_color(&HashMap::new()), None);
+    }
+
+    #[test]
+    fn edge_takes_the_first_shared_tag_colour() {
+        let colors = default_colors();
+        assert_eq!(
+            colors
+                .edge_color(&tags("draft, ops"), &tags("OPS, project"))
+                .as_deref(),
+            colors.color_for("ops")
+        );
+        assert_eq!(colors.edge_color(&tags("draft"), &tags("ops")), None);
+    }
+
+    #[test]
+    fn pin_rejects_invalid_colours_and_empty_tags() {
+        let mut colors = default_colors();
+        assert!(colors.pin("project", "red").is_err());
+        assert!(colors.pin("project", "#GGGGGG").is_err());
+        assert!(colors.pin(" # ", "#00ff00").is_err());
+        assert!(colors.pins.is_empty());
+    }
+
+    #[test]
+    fn pins_take_precedence_until_removed() {
+        let mut colors = default_colors();
+        let hashed = colors.color_for("project").unwrap().to_string();
+        assert_eq!(colors.pin("#Project", "#00ff00").unwrap(), "project");
+        assert_eq!(colors.color_for("project"), Some("#00FF00"));
+        assert!(colors.unpin("PROJECT"));
+        assert!(!colors.unpin("project"));
+        assert_eq!(colors.color_for("project").unwrap(), hashed);
+    }
+
+    #[test]
+    fn service_persists_pins() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tag_colors.json");
+        let service = TagColorService::load(path.clone(), Vec::new());
+        service.pin("ops", "#00ff00").unwrap();
+        let reloaded = TagColorService::load(path.clone(), Vec::new());
+        assert_eq!(reloaded.snapshot().color_for("ops"), Some("#00FF00"));
+
+        assert!(reloaded.unpin("ops").unwrap());
+        let reloaded = TagColorService::load(path, Vec::new());
+        assert!(reloaded.snapshot().pins.is_empty());
+    }
+
+    #[test]
+    fn corrupt_pin_file_loads_without_pins() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tag_colors.json");
+        std::fs::write(&path, "{not json").unwrap();
+        let service = TagColorService::load(path, Vec::new());
+        assert!(service.snapshot().pins.is_empty());
+    }
+}
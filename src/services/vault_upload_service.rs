@@ -0,0 +1,274 @@
+//! Vault uploads for deployments without GitHub.
+//!
+//! A zip of markdown (and any registered content type) is unpacked into the
+//! local content directory, after which metadata is regenerated and the graph
+//! rebuilt from the extracted files. Every entry path is sanitised: absolute
+//! paths, `..` components, hidden directories and Logseq backup folders are
+//! refused, and size limits are enforced on the archive, on each file and on
+//! the total unpacked size, using the bytes actually read rather than the
+//! sizes the archive declares.
+
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use std::io::{Cursor, Read};
+use std::path::Path;
+
+use crate::services::file_service::markdown_dir;
+use crate::services::github::object_store::git_blob_sha;
+use crate::services::github::types::GitHubFileBasicMetadata;
+use crate::services::parsers::content_parsers;
+
+/// Largest accepted request body; matches `client_max_body_size` on the
+/// nginx upload location so oversized archives get the same answer either way.
+pub const MAX_ARCHIVE_BYTES: usize = 50 * 1024 * 1024;
+const MAX_FILE_BYTES: u64 = 8 * 1024 * 1024;
+const MAX_TOTAL_BYTES: u64 = 512 * 1024 * 1024;
+const MAX_ENTRIES: usize = 50_000;
+
+#[derive(Debug, Clone)]
+pub struct ExtractedFile {
+    /// Sanitised path inside the archive.
+    pub path: String,
+    /// Name the file is stored under; the content directory is flat.
+    pub file_name: String,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedEntry {
+    pub path: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Default)]
+pub struct ExtractedVault {
+    pub files: Vec<ExtractedFile>,
+    pub skipped: Vec<SkippedEntry>,
+}
+
+impl ExtractedVault {
+    fn skip(&mut self, path: &str, reason: &str) {
+        self.skipped.push(SkippedEntry {
+            path: path.to_string(),
+            reason: reason.to_string(),
+        });
+    }
+
+    /// Listing entries for the graph rebuild.
+    pub fn as_sync_input(&self) -> Vec<(GitHubFileBasicMetadata, String)> {
+        self.files
+            .iter()
+            .map(|f| {
+                (
+                    GitHubFileBasicMetadata {
+                        name: f.file_name.clone(),
+                        path: f.path.clone(),
+                        sha: git_blob_sha(&f.content),
+                        size: f.content.len() as u64,
+                        download_url: String::new(),
+                    },
+                    f.content.clone(),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Normalise an archive entry path, or `None` if it must not be extracted.
+fn sanitize_path(raw: &str) -> Option<String> {
+    if raw.contains('\0') || raw.starts_with('/') || raw.starts_with('\\') {
+        return None;
+    }
+    let mut parts = Vec::new();
+    for part in raw.split(['/', '\\']) {
+        match part {
+            "" | "." => continue,
+            ".." => return None,
+            // Drive letters and other scheme-like prefixes.
+            p if p.contains(':') => return None,
+            p if p.starts_with('.') || p == "__MACOSX" => return None,
+            p => parts.push(p),
+        }
+    }
+    if parts.is_empty() {
+        return None;
+    }
+    Some(parts.join("/"))
+}
+
+/// Directories the GitHub listing also leaves out.
+fn is_excluded_path(path: &str) -> bool {
+    path.split('/')
+        .any(|p| matches!(p, "bak" | "logseq" | "journals"))
+}
+
+pub fn extract_vault(archive: &[u8]) -> Result<ExtractedVault, String> {
+    let mut zip = zip::ZipArchive::new(Cursor::new(archive))
+        .map_err(|e| format!("Not a readable zip archive: {}", e))?;
+    if zip.len() > MAX_ENTRIES {
+        return Err(format!(
+            "Archive has {} entries; the limit is {}",
+            zip.len(),
+            MAX_ENTRIES
+        ));
+    }
+
+    let mut vault = ExtractedVault::default();
+    let mut names = HashSet::new();
+    let mut total: u64 = 0;
+    for index in 0..zip.len() {
+        let entry = zip
+            .by_index(index)
+            .map_err(|e| format!("Corrupt archive entry {}: {}", index, e))?;
+        if entry.is_dir() {
+            continue;
+        }
+        let raw = entry.name().to_string();
+        let Some(path) = sanitize_path(&raw) else {
+            vault.skip(&raw, "unsafe or hidden path");
+            continue;
+        };
+        if is_excluded_path(&path) || !content_parsers().is_content_file(&path) {
+            vault.skip(&path, "not a content file");
+            continue;
+        }
+        if entry.size() > MAX_FILE_BYTES {
+            vault.skip(&path, "file too large");
+            continue;
+        }
+
+        let mut bytes = Vec::new();
+        entry
+            .take(MAX_FILE_BYTES + 1)
+            .read_to_end(&mut bytes)
+            .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        if bytes.len() as u64 > MAX_FILE_BYTES {
+            vault.skip(&path, "file too large");
+            continue;
+        }
+        total += bytes.len() as u64;
+        if total > MAX_TOTAL_BYTES {
+            return Err(format!(
+                "Archive unpacks to more than {} MiB",
+                MAX_TOTAL_BYTES / (1024 * 1024)
+            ));
+        }
+        let Ok(content) = String::from_utf8(bytes) else {
+            vault.skip(&path, "not UTF-8 text");
+            continue;
+        };
+
+        let file_name = path.rsplit('/').next().unwrap_or(&path).to_string();
+        if !names.insert(file_name.clone()) {
+            vault.skip(&path, "duplicate file name");
+            continue;
+        }
+        vault.files.push(ExtractedFile {
+            path,
+            file_name,
+            content,
+        });
+    }
+    Ok(vault)
+}
+
+/// Write the extracted files into the content directory. With `replace`,
+/// content files already there are removed first. Returns the number of
+/// files removed.
+pub fn install_vault(vault: &ExtractedVault, replace: bool) -> Result<usize, String> {
+    let dir = Path::new(markdown_dir());
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", markdown_dir(), e))?;
+
+    let mut removed = 0;
+    if replace {
+        let entries =
+            fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", markdown_dir(), e))?;
+        for path in entries.flatten().map(|e| e.path()) {
+            let is_content = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| content_parsers().is_content_file(n));
+            if path.is_file() && is_content {
+                fs::remove_file(&path)
+                    .map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+                removed += 1;
+            }
+        }
+    }
+
+    for file in &vault.files {
+        fs::write(dir.join(&file.file_name), &file.content)
+            .map_err(|e| format!("Failed to write {}: {}", file.file_name, e))?;
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn zip_of(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut buf = Cursor::new(Vec::new());
+        {
+            let mut writer = zip::ZipWriter::new(&mut buf);
+            let options = zip::write::SimpleFileOptions::default();
+            for (name, data) in entries {
+                writer.start_file(*name, options).unwrap();
+                writer.write_all(data).unwrap();
+            }
+            writer.finish().unwrap();
+        }
+        buf.into_inner()
+    }
+
+    #[test]
+    fn sanitize_rejects_escapes_and_hidden_paths() {
+        assert_eq!(
+            sanitize_path("vault/./pages/a.md").as_deref(),
+            Some("vault/pages/a.md")
+        );
+        assert_eq!(sanitize_path("pages\\b.md").as_deref(), Some("pages/b.md"));
+        for bad in [
+            "../etc/passwd.md",
+            "/abs.md",
+            "a/../../b.md",
+            "C:/x.md",
+            ".git/c.md",
+            "__MACOSX/._a.md",
+        ] {
+            assert!(sanitize_path(bad).is_none(), "{} accepted", bad);
+        }
+    }
+
+    #[test]
+    fn extracts_content_files_and_reports_the_rest() {
+        let archive = zip_of(&[
+            ("vault/pages/a.md", b"- [[b]]"),
+            ("vault/pages/b.md", b"public:: true"),
+            ("vault/other/a.md", b"dup"),
+            ("vault/logseq/bak/a.md", b"old"),
+            ("vault/assets/img.png", &[0x89, 0x50]),
+            ("../escape.md", b"x"),
+            ("vault/pages/bin.md", &[0xff, 0xfe]),
+        ]);
+        let vault = extract_vault(&archive).unwrap();
+
+        let names: Vec<&str> = vault.files.iter().map(|f| f.file_name.as_str()).collect();
+        assert_eq!(names, vec!["a.md", "b.md"]);
+        let reasons: Vec<&str> = vault.skipped.iter().map(|s| s.reason.as_str()).collect();
+        assert_eq!(
+            reasons,
+            vec![
+                "duplicate file name",
+                "not a content file",
+                "not a content file",
+                "unsafe or hidden path",
+                "not UTF-8 text",
+            ]
+        );
+        assert!(extract_vault(b"not a zip").is_err());
+    }
+}
@@ -0,0 +1,456 @@
+//! "Since you were last here" summaries.
+//!
+//! The graph's pages and clusters are captured as numbered revisions (a new
+//! one only when something changed), and each signed-in user's last-seen
+//! revision is remembered. When a user authenticates on the socket the
+//! revision they last saw is diffed against the current one with
+//! `digest_service::diff_snapshots`, and the session is sent:
+//!
+//! ```json
+//! {"type": "visitSummary", "revision": 12, "sinceRevision": 9, "since": "2026-03-01T08:00:00Z",
+//!  "created": ["New page"], "edited": ["Old page"], "removed": [], "newClusters": [["A", "B", "C"]],
+//!  "linkChanges": 4, "counts": {"created": 1, "edited": 1, "removed": 0}, "newNodeIds": [42],
+//!  "truncated": false, "highlightId": 7}
+//! ```
+//!
+//! Nothing is sent on a first visit, which only sets the baseline, or when
+//! nothing changed. Lists hold at most [`MAX_LISTED`] pages; `counts` has the
+//! full numbers. Only the last [`MAX_REVISIONS`] revisions are kept; a user
+//! who last saw an older one is diffed against the oldest and `truncated` is
+//! set. New pages are also highlighted for the user unless the authenticate
+//! message has `"highlightChanges": false`. State is kept in `visits.json`
+//! under the data dir, rewritten only when a visit adds a revision or moves
+//! the user's last-seen one.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, RwLock};
+
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use crate::config::dev_config;
+use crate::services::digest_service::{diff_snapshots, DigestSnapshot};
+use crate::services::json_file::JsonFile;
+use crate::utils::binary_protocol::{NodeAnalytics, NODE_ID_MASK};
+use visionclaw_domain::models::graph::GraphData;
+
+/// Revisions kept for diffing.
+pub const MAX_REVISIONS: usize = 30;
+
+/// Pages listed per section of a summary.
+pub const MAX_LISTED: usize = 50;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphRevision {
+    pub revision: u64,
+    pub snapshot: DigestSnapshot,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VisitCounts {
+    pub created: usize,
+    pub edited: usize,
+    pub removed: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VisitSummary {
+    pub revision: u64,
+    pub since_revision: u64,
+    pub since: Option<DateTime<Utc>>,
+    pub created: Vec<String>,
+    pub edited: Vec<String>,
+    pub removed: Vec<String>,
+    pub new_clusters: Vec<Vec<String>>,
+    pub link_changes: usize,
+    pub counts: VisitCounts,
+    /// Nodes of the created pages.
+    pub new_node_ids: Vec<u32>,
+    /// The revision last seen was dropped; this is the change since the
+    /// oldest one kept.
+    pub truncated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlight_id: Option<u64>,
+}
+
+impl VisitSummary {
+    /// The `visitSummary` frame.
+    pub fn message(&self) -> String {
+        let mut message = serde_json::to_value(self).unwrap_or_default();
+        message["type"] = serde_json::json!("visitSummary");
+        message.to_string()
+    }
+}
+
+/// Snapshot of the graph's pages and clusters, as the digest takes them.
+pub fn capture(graph: &GraphData, analytics: &HashMap<u32, NodeAnalytics>) -> DigestSnapshot {
+    let clusters: HashMap<String, u32> = graph
+        .nodes
+        .iter()
+        .filter_map(|node| {
+            analytics
+                .get(&(node.id & NODE_ID_MASK))
+                .filter(|a| a.cluster_id != 0)
+                .map(|a| (node.metadata_id.clone(), a.cluster_id))
+        })
+        .collect();
+    DigestSnapshot::capture(&graph.metadata, &clusters, Utc::now())
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VisitStore {
+    pub revisions: Vec<GraphRevision>,
+    pub last_seen: BTreeMap<String, u64>,
+}
+
+/// What [`VisitStore::visit`] did.
+#[derive(Debug, Default)]
+pub struct Visit {
+    pub summary: Option<VisitSummary>,
+    /// A revision was added or the user's last-seen revision moved, so the
+    /// store needs saving.
+    pub changed: bool,
+}
+
+fn same_content(a: &DigestSnapshot, b: &DigestSnapshot) -> bool {
+    a.pages == b.pages && a.clusters == b.clusters
+}
+
+impl VisitStore {
+    /// The revision for `snapshot`, adding one if the graph changed since the
+    /// latest, and whether it was added.
+    fn revision_for(&mut self, snapshot: DigestSnapshot) -> (u64, bool) {
+        if let Some(latest) = self.revisions.last() {
+            if same_content(&latest.snapshot, &snapshot) {
+                return (latest.revision, false);
+            }
+        }
+        let revision = self.revisions.last().map_or(1, |r| r.revision + 1);
+        self.revisions.push(GraphRevision { revision, snapshot });
+        if self.revisions.len() > MAX_REVISIONS {
+            let excess = self.revisions.len() - MAX_REVISIONS;
+            self.revisions.drain(..excess);
+        }
+        (revision, true)
+    }
+
+    /// Record that `pubkey` has seen the graph as `snapshot`, with what
+    /// changed since their last visit, if anything. `node_of_page` maps page
+    /// names (without `.md`) to node ids.
+    pub fn visit(
+        &mut self,
+        pubkey: &str,
+        snapshot: DigestSnapshot,
+        node_of_page: &HashMap<String, u32>,
+    ) -> Visit {
+        let (revision, added) = self.revision_for(snapshot);
+        let seen = self.last_seen.insert(pubkey.to_string(), revision);
+        Visit {
+            summary: seen.and_then(|seen| self.summary(seen, revision, node_of_page)),
+            changed: added || seen != Some(revision),
+        }
+    }
+
+    /// The change from revision `seen` to `revision`, if any.
+    fn summary(
+        &self,
+        seen: u64,
+        revision: u64,
+        node_of_page: &HashMap<String, u32>,
+    ) -> Option<VisitSummary> {
+        if seen >= revision {
+            return None;
+        }
+        let (previous, truncated) = match self.revisions.iter().find(|r| r.revision == seen) {
+            Some(previous) => (previous, false),
+            None => (self.revisions.first()?, true),
+        };
+        let current = &self.revisions.last()?.snapshot;
+        let report = diff_snapshots(&previous.snapshot, current, Utc::now().date_naive(), "");
+        if report.is_empty() {
+            return None;
+        }
+        let listed = |pages: &[String]| pages.iter().take(MAX_LISTED).cloned().collect();
+        Some(VisitSummary {
+            revision,
+            since_revision: previous.revision,
+            since: report.since,
+            created: listed(&report.created),
+            edited: listed(&report.edited),
+            removed: listed(&report.removed),
+            new_clusters: report.new_clusters.clone(),
+            link_changes: report.link_changes.len(),
+            counts: VisitCounts {
+                created: report.created.len(),
+                edited: report.edited.len(),
+                removed: report.removed.len(),
+            },
+            new_node_ids: report
+                .created
+                .iter()
+                .filter_map(|page| node_of_page.get(page).copied())
+                .collect(),
+            truncated,
+            highlight_id: None,
+        })
+    }
+}
+
+/// Process-wide visit history.
+pub struct VisitService {
+    file: JsonFile,
+    store: RwLock<VisitStore>,
+    /// Changes made to `store`, counted under its lock.
+    changes: AtomicU64,
+    /// The last change written to disk. Saves run after the store lock is
+    /// released, so an older copy must not overwrite a newer one.
+    saved: Mutex<u64>,
+}
+
+impl VisitService {
+    pub fn load(path: PathBuf) -> Self {
+        let file = JsonFile::new(path, "visits");
+        let store: VisitStore = file.load();
+        info!(
+            "Loaded {} graph revision(s) and {} visitor(s)",
+            store.revisions.len(),
+            store.last_seen.len()
+        );
+        Self {
+            file,
+            store: RwLock::new(store),
+            changes: AtomicU64::new(0),
+            saved: Mutex::new(0),
+        }
+    }
+
+    /// See [`VisitStore::visit`]; persists the store if the visit changed it.
+    pub fn visit(
+        &self,
+        pubkey: &str,
+        graph: &GraphData,
+        analytics: &HashMap<u32, NodeAnalytics>,
+    ) -> Option<VisitSummary> {
+        let snapshot = capture(graph, analytics);
+        let node_of_page: HashMap<String, u32> = graph
+            .nodes
+            .iter()
+            .map(|n| (n.metadata_id.trim_end_matches(".md").to_string(), n.id))
+            .collect();
+        let (visit, copy) = {
+            let mut store = self.store.write().unwrap_or_else(|e| e.into_inner());
+            let visit = store.visit(pubkey, snapshot, &node_of_page);
+            let copy = visit.changed.then(|| {
+                (
+                    self.changes.fetch_add(1, Ordering::Relaxed) + 1,
+                    store.clone(),
+                )
+            });
+            (visit, copy)
+        };
+        if let Some((change, store)) = copy {
+            self.save(change, &store);
+        }
+        visit.summary
+    }
+
+    fn save(&self, change: u64, store: &VisitStore) {
+        let mut saved = self.saved.lock().unwrap_or_else(|e| e.into_inner());
+        if *saved >= change {
+            return;
+        }
+        match self.file.save(store) {
+            Ok(()) => *saved = change,
+            Err(e) => warn!("[Visits] {}", e),
+        }
+    }
+}
+
+static SERVICE: Lazy<VisitService> =
+    Lazy::new(|| VisitService::load(dev_config::storage().path("visits.json")));
+
+pub fn visits() -> &'static VisitService {
+    &SERVICE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::digest_service::PageFingerprint;
+
+    fn snapshot(pages: &[(&str, &str)], clusters: Vec<Vec<&str>>) -> DigestSnapshot {
+        DigestSnapshot {
+            taken_at: Some(Utc::now()),
+            pages: pages
+                .iter()
+                .map(|(name, sha1)| {
+                    (
+                        name.to_string(),
+                        PageFingerprint {
+                            sha1: sha1.to_string(),
+                            links: Default::default(),
+                        },
+                    )
+                })
+                .collect(),
+            clusters: clusters
+                .into_iter()
+                .map(|c| c.into_iter().map(str::to_string).collect())
+                .collect(),
+        }
+    }
+
+    fn nodes() -> HashMap<String, u32> {
+        [("c".to_string(), 3), ("d".to_string(), 4)].into()
+    }
+
+    fn first() -> DigestSnapshot {
+        snapshot(&[("a", "1"), ("b", "1")], vec![])
+    }
+
+    fn second() -> DigestSnapshot {
+        snapshot(
+            &[("a", "2"), ("c", "1"), ("d", "1"), ("e", "1")],
+            vec![vec!["a", "c", "d"]],
+        )
+    }
+
+    #[test]
+    fn first_visit_sets_the_baseline() {
+        let mut store = VisitStore::default();
+        let visit = store.visit("alice", first(), &nodes());
+        assert!(visit.summary.is_none());
+        assert!(visit.changed);
+        assert_eq!(store.last_seen["alice"], 1);
+    }
+
+    #[test]
+    fn unchanged_graph_adds_no_revision_and_needs_no_save() {
+        let mut store = VisitStore::default();
+        store.visit("alice", first(), &nodes());
+        let visit = store.visit("alice", first(), &nodes());
+        assert!(visit.summary.is_none());
+        assert!(!visit.changed);
+        assert_eq!(store.revisions.len(), 1);
+    }
+
+    #[test]
+    fn another_users_baseline_needs_a_save() {
+        let mut store = VisitStore::default();
+        store.visit("alice", first(), &nodes());
+        let visit = store.visit("bob", first(), &nodes());
+        assert!(visit.summary.is_none());
+        assert!(visit.changed);
+    }
+
+    #[test]
+    fn diffs_against_the_users_last_revision() {
+        let mut store = VisitStore::default();
+        store.visit("alice", first(), &nodes());
+        assert!(store.visit("bob", second(), &nodes()).summary.is_none());
+
+        let visit = store.visit("alice", second(), &nodes());
+        assert!(visit.changed);
+        let summary = visit.summary.unwrap();
+        assert_eq!((summary.since_revision, summary.revision), (1, 2));
+        assert_eq!(summary.created, vec!["c", "d", "e"]);
+        assert_eq!(summary.edited, vec!["a"]);
+        assert_eq!(summary.removed, vec!["b"]);
+        assert_eq!(summary.new_clusters.len(), 1);
+        assert_eq!(summary.new_node_ids, vec![3, 4]);
+        assert!(!summary.truncated);
+        assert!(store.visit("alice", second(), &nodes()).summary.is_none());
+    }
+
+    #[test]
+    fn summary_message_is_typed() {
+        let mut store = VisitStore::default();
+        store.visit("alice", first(), &nodes());
+        let summary = store.visit("alice", second(), &nodes()).summary.unwrap();
+        let message: serde_json::Value = serde_json::from_str(&summary.message()).unwrap();
+        assert_eq!(message["type"], "visitSummary");
+        assert_eq!(message["counts"]["created"], 3);
+    }
+
+    #[test]
+    fn lists_are_capped_but_counts_are_not() {
+        let mut store = VisitStore::default();
+        store.visit("alice", snapshot(&[], vec![]), &nodes());
+        let names: Vec<String> = (0..MAX_LISTED + 5).map(|i| format!("p{i}")).collect();
+        let pages: Vec<(&str, &str)> = names.iter().map(|n| (n.as_str(), "1")).collect();
+        let summary = store
+            .visit("alice", snapshot(&pages, vec![]), &nodes())
+            .summary
+            .unwrap();
+        assert_eq!(summary.created.len(), MAX_LISTED);
+        assert_eq!(summary.counts.created, MAX_LISTED + 5);
+    }
+
+    #[test]
+    fn keeps_only_the_latest_revisions() {
+        let mut store = VisitStore::default();
+        for i in 0..MAX_REVISIONS + 3 {
+            let sha = i.to_string();
+            store.visit("bob", snapshot(&[("a", &sha)], vec![]), &nodes());
+        }
+        assert_eq!(store.revisions.len(), MAX_REVISIONS);
+        assert_eq!(store.revisions[0].revision, 4);
+    }
+
+    #[test]
+    fn a_dropped_revision_diffs_against_the_oldest_kept() {
+        let mut store = VisitStore::default();
+        store.visit("alice", first(), &nodes());
+        for i in 0..MAX_REVISIONS {
+            let sha = i.to_string();
+            store.visit("bob", snapshot(&[("a", &sha)], vec![]), &nodes());
+        }
+        let summary = store
+            .visit("alice", snapshot(&[("z", "1")], vec![]), &nodes())
+            .summary
+            .unwrap();
+        assert!(summary.truncated);
+        assert_eq!(summary.since_revision, store.revisions[0].revision);
+        assert_eq!(summary.created, vec!["z"]);
+    }
+
+    #[test]
+    fn service_saves_only_visits_that_change_the_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("visits.json");
+        let service = VisitService::load(path.clone());
+        let graph = GraphData::new();
+        let analytics = HashMap::new();
+
+        service.visit("alice", &graph, &analytics);
+        assert!(path.exists());
+
+        std::fs::remove_file(&path).unwrap();
+        service.visit("alice", &graph, &analytics);
+        assert!(!path.exists(), "a repeat visit rewrote the file");
+
+        service.visit("bob", &graph, &analytics);
+        let saved: VisitStore =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved.last_seen.len(), 2);
+    }
+
+    #[test]
+    fn corrupt_file_loads_as_an_empty_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("visits.json");
+        std::fs::write(&path, "{not json").unwrap();
+        let service = VisitService::load(path);
+        let store = service.store.read().unwrap();
+        assert!(store.revisions.is_empty());
+        assert!(store.last_seen.is_empty());
+    }
+}
@@ -0,0 +1,462 @@
+//! Outgoing webhooks for graph events.
+//!
+//! Producers call [`emit`] with a [`GraphEvent`]; the process-global hub fans
+//! it out to the [`WebhookService`] worker, which POSTs a JSON envelope to
+//! every configured endpoint subscribed to that event. Keeping the hub global
+//! mirrors `agent_events::hub` — actors deep in the pipeline (sync, clustering)
+//! can emit without threading a new address through their constructors.
+//!
+//! Each request carries:
+//! - `X-VisionClaw-Event`: the event name (`sync.completed`, …)
+//! - `X-VisionClaw-Delivery`: a unique delivery id (stable across retries)
+//! - `X-VisionClaw-Signature`: `sha256=<hex HMAC of the body>` when the
+//!   endpoint has a secret
+//!
+//! Slack and Discord notifiers (`webhooks.slack` / `webhooks.discord`) ride the
+//! same pipeline with platform-shaped message bodies; see `chat_notifier`.
+//!
+//! Endpoints, secrets and retry limits are read from the settings actor on
+//! every dispatch, so edits to `webhooks.*` take effect without a restart.
+//!
+//! Failed deliveries (network errors, 5xx, 429) are retried with exponential
+//! backoff; every final outcome lands in a bounded delivery log served by
+//! `GET /api/webhooks/deliveries`.
+
+use actix::Addr;
+use chrono::{DateTime, Utc};
+use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, RwLock};
+
+use super::chat_notifier::{self, notifier_accepts, ChatPlatform};
+use crate::actors::messages::GetSettings;
+use crate::actors::optimized_settings_actor::OptimizedSettingsActor;
+use crate::config::{WebhookEndpoint, WebhookSettings};
+
+const HUB_CAPACITY: usize = 512;
+const MAX_LOG_ENTRIES: usize = 500;
+const BASE_BACKOFF_MS: u64 = 500;
+const MAX_BACKOFF_MS: u64 = 30_000;
+
+/// Events that can be delivered to webhook endpoints.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", content = "data")]
+pub enum GraphEvent {
+    #[serde(rename = "sync.completed", rename_all = "camelCase")]
+    SyncCompleted {
+        files_scanned: usize,
+        files_processed: usize,
+        duration_ms: u64,
+        error_count: usize,
+    },
+    #[serde(rename = "node.created", rename_all = "camelCase")]
+    NodeCreated {
+        node_id: u32,
+        metadata_id: String,
+        label: String,
+    },
+    #[serde(rename = "cluster.changed", rename_all = "camelCase")]
+    ClusterChanged {
+        clusters: usize,
+        assigned_nodes: usize,
+        changed_nodes: usize,
+    },
+    #[serde(rename = "error", rename_all = "camelCase")]
+    Error { source: String, message: String },
+    #[serde(rename = "gpu.fallback", rename_all = "camelCase")]
+    GpuFallback { kernel: String, reason: String },
+    #[serde(rename = "digest.published", rename_all = "camelCase")]
+    DigestPublished {
+        date: String,
+        page: String,
+        created: usize,
+        edited: usize,
+        removed: usize,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        summary: Option<String>,
+    },
+}
+
+impl GraphEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            GraphEvent::SyncCompleted { .. } => "sync.completed",
+            GraphEvent::NodeCreated { .. } => "node.created",
+            GraphEvent::ClusterChanged { .. } => "cluster.changed",
+            GraphEvent::Error { .. } => "error",
+            GraphEvent::GpuFallback { .. } => "gpu.fallback",
+            GraphEvent::DigestPublished { .. } => "digest.published",
+        }
+    }
+
+    /// `{"id", "event", "timestamp", "data"}` envelope sent as the POST body.
+    pub fn to_payload(&self, delivery_id: &str, timestamp: DateTime<Utc>) -> serde_json::Value {
+        let mut payload = serde_json::to_value(self).unwrap_or_default();
+        if let Some(obj) = payload.as_object_mut() {
+            obj.insert("id".to_string(), delivery_id.into());
+            obj.insert("timestamp".to_string(), timestamp.to_rfc3339().into());
+        }
+        payload
+    }
+}
+
+static WEBHOOK_HUB: Lazy<broadcast::Sender<GraphEvent>> =
+    Lazy::new(|| broadcast::channel(HUB_CAPACITY).0);
+
+/// Publish a graph event. Cheap and non-blocking; a no-op when no webhook
+/// worker is subscribed.
+pub fn emit(event: GraphEvent) -> usize {
+    WEBHOOK_HUB.send(event).unwrap_or(0)
+}
+
+pub fn subscribe() -> broadcast::Receiver<GraphEvent> {
+    WEBHOOK_HUB.subscribe()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    Delivered,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeliveryRecord {
+    pub delivery_id: String,
+    pub event: String,
+    pub url: String,
+    pub status: DeliveryStatus,
+    pub attempts: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// HMAC-SHA256 (RFC 2104) over `message`.
+pub fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    const BLOCK: usize = 64;
+    let mut block_key = [0u8; BLOCK];
+    if key.len() > BLOCK {
+        block_key[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block_key[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = Sha256::new();
+    inner.update(block_key.map(|b| b ^ 0x36));
+    inner.update(message);
+    let inner_hash = inner.finalize();
+
+    let mut outer = Sha256::new();
+    outer.update(block_key.map(|b| b ^ 0x5c));
+    outer.update(inner_hash);
+    outer.finalize().into()
+}
+
+/// Value of the `X-VisionClaw-Signature` header.
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    format!("sha256={}", hex::encode(hmac_sha256(secret.as_bytes(), body)))
+}
+
+/// Delay before retry `attempt` (1-based): 0.5s, 1s, 2s, … capped at 30s.
+pub fn backoff(attempt: u32) -> Duration {
+    let factor = 1u64 << attempt.saturating_sub(1).min(16);
+    Duration::from_millis((BASE_BACKOFF_MS * factor).min(MAX_BACKOFF_MS))
+}
+
+/// Whether `endpoint` subscribed to `event` (an empty list means everything).
+pub fn accepts(endpoint: &WebhookEndpoint, event: &str) -> bool {
+    endpoint.events.is_empty() || endpoint.events.iter().any(|e| e == event || e == "*")
+}
+
+/// Whether webhooks are switched on and at least one target is configured.
+pub fn is_active(settings: &WebhookSettings) -> bool {
+    settings.enabled
+        && !(settings.endpoints.is_empty() && settings.slack.is_none() && settings.discord.is_none())
+}
+
+/// Retry budget and per-request timeout captured when an event is dispatched.
+#[derive(Debug, Clone, Copy)]
+struct DeliveryPolicy {
+    max_attempts: u32,
+    timeout: Duration,
+}
+
+fn is_retryable(status: reqwest::StatusCode) -> bool {
+    status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+}
+
+pub struct WebhookService {
+    settings_addr: Addr<OptimizedSettingsActor>,
+    client: reqwest::Client,
+    log: RwLock<VecDeque<DeliveryRecord>>,
+}
+
+impl WebhookService {
+    pub fn new(settings_addr: Addr<OptimizedSettingsActor>) -> Self {
+        Self {
+            settings_addr,
+            client: reqwest::Client::new(),
+            log: RwLock::new(VecDeque::new()),
+        }
+    }
+
+    /// Current `webhooks` settings; read on every dispatch so endpoint, secret
+    /// and retry changes apply without a restart.
+    async fn current_settings(&self) -> WebhookSettings {
+        match self.settings_addr.send(GetSettings).await {
+            Ok(Ok(settings)) => settings.webhooks.unwrap_or_default(),
+            Ok(Err(e)) => {
+                warn!("[Webhooks] Failed to read settings: {}", e);
+                WebhookSettings::default()
+            }
+            Err(e) => {
+                warn!("[Webhooks] Settings actor unreachable: {}", e);
+                WebhookSettings::default()
+            }
+        }
+    }
+
+    pub async fn is_active(&self) -> bool {
+        is_active(&self.current_settings().await)
+    }
+
+    /// Subscribe to the hub and deliver events until the process exits. Events
+    /// are dropped while `webhooks.enabled` is off or nothing is configured.
+    pub fn spawn(self: Arc<Self>) {
+        let mut rx = subscribe();
+        tokio::spawn(async move {
+            loop {
+                match rx.recv().await {
+                    Ok(event) => self.dispatch(event).await,
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        warn!("[Webhooks] Dropped {} events under backpressure", n);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+
+    async fn dispatch(self: &Arc<Self>, event: GraphEvent) {
+        let settings = self.current_settings().await;
+        if !is_active(&settings) {
+            return;
+        }
+        let policy = DeliveryPolicy {
+            max_attempts: settings.max_retries + 1,
+            timeout: Duration::from_millis(settings.timeout_ms),
+        };
+        let name = event.name();
+        let timestamp = Utc::now();
+        for endpoint in settings.endpoints.iter().filter(|e| accepts(e, name)) {
+            let delivery_id = uuid::Uuid::new_v4().to_string();
+            let body = event.to_payload(&delivery_id, timestamp).to_string();
+            let signature = endpoint.secret.as_deref().map(|s| sign(s, body.as_bytes()));
+            self.spawn_delivery(endpoint.url.clone(), name, delivery_id, body, signature, policy);
+        }
+
+        let chat_targets = [
+            (ChatPlatform::Slack, settings.slack.as_ref()),
+            (ChatPlatform::Discord, settings.discord.as_ref()),
+        ];
+        for (platform, notifier) in chat_targets {
+            let Some(notifier) = notifier.filter(|n| notifier_accepts(n, name)) else {
+                continue;
+            };
+            let delivery_id = uuid::Uuid::new_v4().to_string();
+            let body = chat_notifier::payload(platform, &event).to_string();
+            self.spawn_delivery(notifier.webhook_url.clone(), name, delivery_id, body, None, policy);
+        }
+    }
+
+    fn spawn_delivery(
+        self: &Arc<Self>,
+        url: String,
+        event: &'static str,
+        delivery_id: String,
+        body: String,
+        signature: Option<String>,
+        policy: DeliveryPolicy,
+    ) {
+        let this = Arc::clone(self);
+        tokio::spawn(async move {
+            let record = this.deliver(&url, event, delivery_id, body, signature, policy).await;
+            this.record(record).await;
+        });
+    }
+
+    async fn deliver(
+        &self,
+        url: &str,
+        event: &str,
+        delivery_id: String,
+        body: String,
+        signature: Option<String>,
+        policy: DeliveryPolicy,
+    ) -> DeliveryRecord {
+        let max_attempts = policy.max_attempts;
+        let mut attempts = 0;
+        let mut response_status = None;
+        let mut error = None;
+
+        while attempts < max_attempts {
+            if attempts > 0 {
+                tokio::time::sleep(backoff(attempts)).await;
+            }
+            attempts += 1;
+
+            let mut request = self
+                .client
+                .post(url)
+                .timeout(policy.timeout)
+                .header("Content-Type", "application/json")
+                .header("X-VisionClaw-Event", event)
+                .header("X-VisionClaw-Delivery", &delivery_id)
+                .body(body.clone());
+            if let Some(sig) = &signature {
+                request = request.header("X-VisionClaw-Signature", sig);
+            }
+
+            match request.send().await {
+                Ok(resp) if resp.status().is_success() => {
+                    debug!("[Webhooks] {} delivered to {}", event, url);
+                    return DeliveryRecord {
+                        delivery_id,
+                        event: event.to_string(),
+                        url: url.to_string(),
+                        status: DeliveryStatus::Delivered,
+                        attempts,
+                        response_status: Some(resp.status().as_u16()),
+                        error: None,
+                        timestamp: Utc::now(),
+                    };
+                }
+                Ok(resp) => {
+                    let status = resp.status();
+                    response_status = Some(status.as_u16());
+                    error = Some(format!("HTTP {}", status));
+                    if !is_retryable(status) {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    response_status = None;
+                    error = Some(e.to_string());
+                }
+            }
+        }
+
+        warn!(
+            "[Webhooks] {} to {} failed after {} attempt(s): {}",
+            event,
+            url,
+            attempts,
+            error.as_deref().unwrap_or("unknown error")
+        );
+        DeliveryRecord {
+            delivery_id,
+            event: event.to_string(),
+            url: url.to_string(),
+            status: DeliveryStatus::Failed,
+            attempts,
+            response_status,
+            error,
+            timestamp: Utc::now(),
+        }
+    }
+
+    async fn record(&self, record: DeliveryRecord) {
+        let mut log = self.log.write().await;
+        log.push_back(record);
+        while log.len() > MAX_LOG_ENTRIES {
+            log.pop_front();
+        }
+    }
+
+    /// Delivery log, newest first.
+    pub async fn deliveries(&self) -> Vec<DeliveryRecord> {
+        self.log.read().await.iter().rev().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hmac_matches_rfc4231_case_2() {
+        let mac = hmac_sha256(b"Jefe", b"what do ya want for nothing?");
+        assert_eq!(
+            hex::encode(mac),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn hmac_hashes_long_keys() {
+        // RFC 4231 case 6: 131-byte key
+        let key = [0xaau8; 131];
+        let mac = hmac_sha256(&key, b"Test Using Larger Than Block-Size Key - Hash Key First");
+        assert_eq!(
+            hex::encode(mac),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[test]
+    fn payload_envelope_shape() {
+        let event = GraphEvent::NodeCreated {
+            node_id: 7,
+            metadata_id: "Page.md".into(),
+            label: "Page".into(),
+        };
+        let payload = event.to_payload("d-1", Utc::now());
+        assert_eq!(payload["event"], "node.created");
+        assert_eq!(payload["id"], "d-1");
+        assert_eq!(payload["data"]["nodeId"], 7);
+        assert!(payload["timestamp"].is_string());
+    }
+
+    #[test]
+    fn endpoint_event_filter() {
+        let mut endpoint = WebhookEndpoint {
+            url: "http://example.invalid".into(),
+            secret: None,
+            events: vec![],
+        };
+        assert!(accepts(&endpoint, "error"));
+        endpoint.events = vec!["sync.completed".into()];
+        assert!(accepts(&endpoint, "sync.completed"));
+        assert!(!accepts(&endpoint, "error"));
+    }
+
+    #[test]
+    fn inactive_without_targets_or_when_disabled() {
+        let mut settings = WebhookSettings::default();
+        assert!(!is_active(&settings));
+        settings.endpoints.push(WebhookEndpoint {
+            url: "http://example.invalid".into(),
+            secret: None,
+            events: vec![],
+        });
+        assert!(!is_active(&settings));
+        settings.enabled = true;
+        assert!(is_active(&settings));
+    }
+
+    #[test]
+    fn backoff_doubles_and_caps() {
+        assert_eq!(backoff(1), Duration::from_millis(500));
+        assert_eq!(backoff(2), Duration::from_millis(1000));
+        assert_eq!(backoff(20), Duration::from_millis(MAX_BACKOFF_MS));
+    }
+}
@@ -0,0 +1,600 @@
+//! Write-back of server-side page edits to the content repository.
+//!
+//! `github.writeMode` picks the route:
+//! - `direct`: each edit is committed straight onto the content branch.
+//! - `pr` (the default): edits queue up and are pushed as one commit per
+//!   flush onto a `page-edits/*` branch with a pull request open against the
+//!   content branch. Later flushes add commits to the same PR until it is
+//!   merged or closed, after which the next flush starts a fresh branch.
+//!
+//! Pending edits survive a failed push and are retried on the next flush.
+//!
+//! Before anything is pushed, each edit's base blob SHA (the version it was
+//! made from) is compared with the file on the content branch. If someone
+//! else changed the file meanwhile, that edit is parked with a three-way diff
+//! and the rest of the queue is pushed without it. Parked edits stay queued,
+//! are re-checked on every flush, and are only overwritten by a forced flush.
+
+use actix::Addr;
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+use crate::actors::messages::GetSettings;
+use crate::actors::optimized_settings_actor::OptimizedSettingsActor;
+use crate::config::{AppFullSettings, WriteMode};
+use crate::services::github::object_store::git_blob_sha;
+use crate::services::github::ContentAPI;
+use crate::services::github_pr_service::{GitHubPRService, PullRequestRef, PullRequestState};
+use crate::utils::line_diff::{three_way, ThreeWayDiff};
+
+/// How often queued PR-mode edits are pushed.
+pub const FLUSH_INTERVAL: Duration = Duration::from_secs(120);
+
+const BRANCH_PREFIX: &str = "page-edits";
+
+pub fn write_mode(settings: &AppFullSettings) -> WriteMode {
+    settings
+        .github
+        .as_ref()
+        .map(|g| g.write_mode)
+        .unwrap_or_default()
+}
+
+/// A page edited locally and waiting to be written back.
+#[derive(Debug, Clone)]
+pub struct PageEdit {
+    /// Path of the page relative to its source directory, e.g.
+    /// `projects/plan.md`.
+    pub file_name: String,
+    pub content: String,
+    pub summary: String,
+    /// Blob SHA of the remote version the edit was made from. `None` skips
+    /// conflict detection for this file.
+    pub base_sha: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct PendingEdit {
+    content: String,
+    summary: String,
+    base_sha: Option<String>,
+    queued_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WriteConflict {
+    pub path: String,
+    pub base_sha: String,
+    /// `None` when the file was deleted remotely.
+    pub remote_sha: Option<String>,
+    pub diff: ThreeWayDiff,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingEditSummary {
+    pub path: String,
+    pub summary: String,
+    pub bytes: usize,
+    pub queued_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase", tag = "outcome")]
+pub enum FlushOutcome {
+    NothingPending,
+    /// `conflicts` lists edits parked instead of pushed.
+    Committed {
+        sha: String,
+        files: usize,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        conflicts: Vec<WriteConflict>,
+    },
+    PullRequest {
+        sha: String,
+        files: usize,
+        branch: String,
+        pull_request: PullRequestRef,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        conflicts: Vec<WriteConflict>,
+    },
+    /// Every queued edit conflicted; nothing was pushed and they stay queued.
+    Conflict { conflicts: Vec<WriteConflict> },
+}
+
+impl FlushOutcome {
+    /// Edits held back because the remote file changed.
+    pub fn conflicts(&self) -> &[WriteConflict] {
+        match self {
+            FlushOutcome::NothingPending => &[],
+            FlushOutcome::Committed { conflicts, .. }
+            | FlushOutcome::PullRequest { conflicts, .. }
+            | FlushOutcome::Conflict { conflicts } => conflicts,
+        }
+    }
+
+    /// Keep only the conflicts on `paths`.
+    fn retain_conflicts(mut self, paths: &[String]) -> Self {
+        if let FlushOutcome::Committed { conflicts, .. }
+        | FlushOutcome::PullRequest { conflicts, .. }
+        | FlushOutcome::Conflict { conflicts } = &mut self
+        {
+            conflicts.retain(|c| paths.contains(&c.path));
+        }
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WriteBackStatus {
+    pub mode: WriteMode,
+    pub pending: Vec<PendingEditSummary>,
+    pub branch: Option<String>,
+    pub pull_request: Option<PullRequestState>,
+    /// Queued paths parked by the last flush because they changed remotely.
+    pub conflicted: Vec<String>,
+    pub last_flush: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
+
+/// Queue depth and last flush, without the pull request lookup of
+/// [`WriteBackService::status`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WriteBackQueue {
+    pub pending: usize,
+    pub conflicted: usize,
+    pub last_flush: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct WriteBackState {
+    pending: BTreeMap<String, PendingEdit>,
+    branch: Option<String>,
+    pull_request: Option<PullRequestRef>,
+    /// Blob SHA of the content last pushed per path, so our own earlier
+    /// commits are not mistaken for someone else's change.
+    pushed: HashMap<String, String>,
+    /// Pending paths that changed remotely; skipped until forced or clean.
+    conflicted: Vec<String>,
+    last_flush: Option<DateTime<Utc>>,
+    last_error: Option<String>,
+}
+
+pub struct WriteBackService {
+    github: Arc<GitHubPRService>,
+    content_api: Arc<ContentAPI>,
+    // Held across the GitHub calls of a flush so flushes never interleave.
+    state: Mutex<WriteBackState>,
+}
+
+/// `file_name` joined onto a source directory.
+fn source_path(base: &str, file_name: &str) -> String {
+    let base = base.trim_matches('/');
+    let file_name = file_name.trim_start_matches('/');
+    if base.is_empty() {
+        file_name.to_string()
+    } else {
+        format!("{}/{}", base, file_name)
+    }
+}
+
+/// The listed path that is exactly `file_name` under one of the source
+/// directories. Same-named pages in other folders never match.
+fn match_repo_path(listed: &[String], base_paths: &[String], file_name: &str) -> Option<String> {
+    let roots: Vec<&str> = if base_paths.is_empty() {
+        vec![""]
+    } else {
+        base_paths.iter().map(String::as_str).collect()
+    };
+    roots
+        .into_iter()
+        .map(|base| source_path(base, file_name))
+        .find(|candidate| listed.iter().any(|path| path == candidate))
+}
+
+fn commit_message(edits: &BTreeMap<String, PendingEdit>) -> String {
+    let mut message = match edits.len() {
+        1 => format!("Update {}", edits.keys().next().map(String::as_str).unwrap_or("")),
+        n => format!("Update {} pages", n),
+    };
+    message.push_str("\n\n");
+    for (path, edit) in edits {
+        message.push_str(&format!("- {}: {}\n", path, edit.summary));
+    }
+    message
+}
+
+impl WriteBackService {
+    pub fn new(github: Arc<GitHubPRService>, content_api: Arc<ContentAPI>) -> Self {
+        Self {
+            github,
+            content_api,
+            state: Mutex::new(WriteBackState::default()),
+        }
+    }
+
+    /// Repository path of a page known locally by its path relative to a
+    /// source directory. Falls back to the first source path when the page
+    /// is not in the repo listing yet.
+    async fn repo_path(&self, file_name: &str) -> String {
+        match self.content_api.list_markdown_files_via_tree().await {
+            Ok(files) => {
+                let listed: Vec<String> = files.into_iter().map(|f| f.path).collect();
+                if let Some(path) = match_repo_path(&listed, self.content_api.base_paths(), file_name) {
+                    return path;
+                }
+            }
+            Err(e) => warn!("[WriteBack] Repo listing unavailable, guessing path for {}: {}", file_name, e),
+        }
+        source_path(self.content_api.base_path(), file_name)
+    }
+
+    /// Record edited pages. Direct mode commits them together right away;
+    /// PR mode leaves them queued for the next flush. Only conflicts on the
+    /// submitted pages are reported.
+    pub async fn submit(
+        &self,
+        edits: Vec<PageEdit>,
+        mode: WriteMode,
+        force: bool,
+    ) -> Result<FlushOutcome, String> {
+        let mut resolved = Vec::with_capacity(edits.len());
+        for edit in edits {
+            resolved.push((self.repo_path(&edit.file_name).await, edit));
+        }
+        let paths: Vec<String> = resolved.iter().map(|(path, _)| path.clone()).collect();
+        {
+            let mut state = self.state.lock().await;
+            for (path, edit) in resolved {
+                // Re-editing a queued page keeps the base of the first edit.
+                let base_sha = match state.pending.get(&path) {
+                    Some(queued) => queued.base_sha.clone(),
+                    None => edit.base_sha,
+                };
+                state.pending.insert(
+                    path,
+                    PendingEdit {
+                        content: edit.content,
+                        summary: edit.summary,
+                        base_sha,
+                        queued_at: Utc::now(),
+                    },
+                );
+            }
+        }
+        match mode {
+            WriteMode::Direct => Ok(self.flush(mode, force).await?.retain_conflicts(&paths)),
+            WriteMode::Pr => Ok(FlushOutcome::NothingPending),
+        }
+    }
+
+    /// Push queued edits. Unless `force` is set, an edit whose file changed
+    /// remotely since its base is parked and the others are pushed without
+    /// it; [`FlushOutcome::Conflict`] means every edit was parked.
+    pub async fn flush(&self, mode: WriteMode, force: bool) -> Result<FlushOutcome, String> {
+        let mut state = self.state.lock().await;
+        if state.pending.is_empty() {
+            return Ok(FlushOutcome::NothingPending);
+        }
+        let conflicts = if force {
+            Vec::new()
+        } else {
+            match self.find_conflicts(&state).await {
+                Ok(conflicts) => conflicts,
+                Err(e) => {
+                    state.last_error = Some(e.clone());
+                    return Err(e);
+                }
+            }
+        };
+        state.conflicted = conflicts.iter().map(|c| c.path.clone()).collect();
+        let conflict_note = (!conflicts.is_empty()).then(|| {
+            format!(
+                "{} file(s) changed remotely: {}",
+                conflicts.len(),
+                state.conflicted.join(", ")
+            )
+        });
+        let batch = unparked(&state.pending, &state.conflicted);
+        if batch.is_empty() {
+            state.last_error = conflict_note;
+            warn!("[WriteBack] Flush held back: {}", state.last_error.as_deref().unwrap_or(""));
+            return Ok(FlushOutcome::Conflict { conflicts });
+        }
+        let result = match mode {
+            WriteMode::Direct => self.commit_direct(&batch, conflicts).await,
+            WriteMode::Pr => self.push_pull_request(&mut state, &batch, conflicts).await,
+        };
+        match &result {
+            Ok(outcome) => {
+                info!("[WriteBack] Flushed {} edit(s): {:?}", batch.len(), outcome);
+                for (path, edit) in &batch {
+                    state.pending.remove(path);
+                    state.pushed.insert(path.clone(), git_blob_sha(&edit.content));
+                }
+                if let Some(note) = &conflict_note {
+                    warn!("[WriteBack] Parked: {}", note);
+                }
+                state.last_flush = Some(Utc::now());
+                state.last_error = conflict_note;
+            }
+            Err(e) => {
+                warn!("[WriteBack] Flush failed, {} edit(s) kept: {}", state.pending.len(), e);
+                state.last_error = Some(e.clone());
+            }
+        }
+        result
+    }
+
+    async fn find_conflicts(&self, state: &WriteBackState) -> Result<Vec<WriteConflict>, String> {
+        let base_branch = self.github.base_branch().to_string();
+        let mut conflicts = Vec::new();
+        for (path, edit) in &state.pending {
+            let Some(base_sha) = &edit.base_sha else {
+                continue;
+            };
+            let remote_sha = self.github.file_sha(path, &base_branch).await?;
+            let pushed = state.pushed.get(path);
+            let unchanged = remote_sha.as_ref() == Some(base_sha)
+                || (pushed.is_some() && remote_sha.as_ref() == pushed);
+            if unchanged {
+                continue;
+            }
+            let theirs = match &remote_sha {
+                Some(sha) => self.github.blob_text(sha).await?,
+                None => String::new(),
+            };
+            if theirs == edit.content {
+                continue;
+            }
+            // Our last push is a closer ancestor than the original base.
+            let base_sha = pushed.unwrap_or(base_sha).clone();
+            let base = self.github.blob_text(&base_sha).await?;
+            conflicts.push(WriteConflict {
+                path: path.clone(),
+                base_sha,
+                remote_sha,
+                diff: three_way(base, edit.content.clone(), theirs),
+            });
+        }
+        Ok(conflicts)
+    }
+
+    async fn commit_direct(
+        &self,
+        edits: &BTreeMap<String, PendingEdit>,
+        conflicts: Vec<WriteConflict>,
+    ) -> Result<FlushOutcome, String> {
+        let base = self.github.base_branch().to_string();
+        let parent = self
+            .github
+            .branch_head(&base)
+            .await?
+            .ok_or_else(|| format!("Content branch '{}' not found", base))?;
+        let files = edit_files(edits);
+        let sha = self.github.commit_files(&parent, &files, &commit_message(edits)).await?;
+        self.github.move_branch(&base, &sha).await?;
+        Ok(FlushOutcome::Committed {
+            sha,
+            files: files.len(),
+            conflicts,
+        })
+    }
+
+    async fn push_pull_request(
+        &self,
+        state: &mut WriteBackState,
+        edits: &BTreeMap<String, PendingEdit>,
+        conflicts: Vec<WriteConflict>,
+    ) -> Result<FlushOutcome, String> {
+        // A merged or closed PR ends the batch; start a new branch.
+        if let Some(pr) = state.pull_request.clone() {
+            match self.github.pull_request_state(pr.number).await {
+                Ok(live) if live.state == "open" => {}
+                Ok(_) => {
+                    state.pull_request = None;
+                    state.branch = None;
+                }
+                Err(e) => warn!("[WriteBack] Could not check PR #{}: {}", pr.number, e),
+            }
+        }
+
+        let branch = state
+            .branch
+            .clone()
+            .unwrap_or_else(|| format!("{}/{}", BRANCH_PREFIX, Utc::now().format("%Y%m%d-%H%M%S")));
+        let parent = match self.github.branch_head(&branch).await? {
+            Some(head) => head,
+            None => {
+                let base = self.github.base_branch().to_string();
+                self.github
+                    .branch_head(&base)
+                    .await?
+                    .ok_or_else(|| format!("Content branch '{}' not found", base))?
+            }
+        };
+        let files = edit_files(edits);
+        let sha = self
+            .github
+            .commit_files(&parent, &files, &commit_message(edits))
+            .await?;
+        self.github.move_branch(&branch, &sha).await?;
+        state.branch = Some(branch.clone());
+
+        let pull_request = match state.pull_request.clone() {
+            Some(pr) => pr,
+            None => {
+                let body = format!(
+                    "Page edits made through VisionClaw.\n\n{}",
+                    commit_message(edits)
+                );
+                let pr = self
+                    .github
+                    .open_pull_request("Page edits from VisionClaw", &body, &branch)
+                    .await?;
+                state.pull_request = Some(pr.clone());
+                pr
+            }
+        };
+        Ok(FlushOutcome::PullRequest {
+            sha,
+            files: files.len(),
+            branch,
+            pull_request,
+            conflicts,
+        })
+    }
+
+    pub async fn status(&self, mode: WriteMode) -> WriteBackStatus {
+        let state = self.state.lock().await;
+        let pull_request = match &state.pull_request {
+            Some(pr) => match self.github.pull_request_state(pr.number).await {
+                Ok(live) => Some(live),
+                Err(e) => {
+                    warn!("[WriteBack] Could not fetch PR #{}: {}", pr.number, e);
+                    Some(PullRequestState {
+                        number: pr.number,
+                        url: pr.url.clone(),
+                        state: "unknown".to_string(),
+                        merged: false,
+                        mergeable: None,
+                    })
+                }
+            },
+            None => None,
+        };
+        WriteBackStatus {
+            mode,
+            pending: state
+                .pending
+                .iter()
+                .map(|(path, edit)| PendingEditSummary {
+                    path: path.clone(),
+                    summary: edit.summary.clone(),
+                    bytes: edit.content.len(),
+                    queued_at: edit.queued_at,
+                })
+                .collect(),
+            branch: state.branch.clone(),
+            pull_request,
+            conflicted: state.conflicted.clone(),
+            last_flush: state.last_flush,
+            last_error: state.last_error.clone(),
+        }
+    }
+
+    /// Waits for any flush in progress to finish.
+    pub async fn queue(&self) -> WriteBackQueue {
+        let state = self.state.lock().await;
+        WriteBackQueue {
+            pending: state.pending.len(),
+            conflicted: state.conflicted.len(),
+            last_flush: state.last_flush,
+            last_error: state.last_error.clone(),
+        }
+    }
+
+    /// Push queued PR-mode edits every [`FLUSH_INTERVAL`].
+    pub fn spawn_flush_loop(self: Arc<Self>, settings_addr: Addr<OptimizedSettingsActor>) {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(FLUSH_INTERVAL);
+            loop {
+                ticker.tick().await;
+                let mode = match settings_addr.send(GetSettings).await {
+                    Ok(Ok(settings)) => write_mode(&settings),
+                    _ => continue,
+                };
+                if mode == WriteMode::Pr {
+                    // Errors are recorded in the status; nothing else to do here.
+                    let _ = self.flush(mode, false).await;
+                }
+            }
+        });
+    }
+}
+
+/// Pending edits that are not parked.
+fn unparked(pending: &BTreeMap<String, PendingEdit>, parked: &[String]) -> BTreeMap<String, PendingEdit> {
+    pending
+        .iter()
+        .filter(|(path, _)| !parked.contains(path))
+        .map(|(path, edit)| (path.clone(), edit.clone()))
+        .collect()
+}
+
+fn edit_files(edits: &BTreeMap<String, PendingEdit>) -> Vec<(String, String)> {
+    edits
+        .iter()
+        .map(|(path, edit)| (path.clone(), edit.content.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit(summary: &str) -> PendingEdit {
+        PendingEdit {
+            content: "x".to_string(),
+            summary: summary.to_string(),
+            base_sha: None,
+            queued_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn commit_message_lists_every_edit() {
+        let mut edits = BTreeMap::new();
+        edits.insert("pages/a.md".to_string(), edit("merged b"));
+        assert!(commit_message(&edits).starts_with("Update pages/a.md\n\n- pages/a.md: merged b"));
+
+        edits.insert("pages/b.md".to_string(), edit("redirect stub"));
+        let message = commit_message(&edits);
+        assert!(message.starts_with("Update 2 pages"));
+        assert!(message.contains("- pages/b.md: redirect stub\n"));
+    }
+
+    #[test]
+    fn parked_edits_are_left_out_of_the_batch() {
+        let mut pending = BTreeMap::new();
+        pending.insert("pages/a.md".to_string(), edit("a"));
+        pending.insert("pages/b.md".to_string(), edit("b"));
+        pending.insert("pages/c.md".to_string(), edit("c"));
+
+        let batch = unparked(&pending, &["pages/b.md".to_string()]);
+        assert_eq!(batch.keys().collect::<Vec<_>>(), vec!["pages/a.md", "pages/c.md"]);
+        assert!(unparked(&pending, &pending.keys().cloned().collect::<Vec<_>>()).is_empty());
+    }
+
+    #[test]
+    fn repo_path_matches_the_full_relative_path() {
+        let listed = vec![
+            "vault/pages/plan.md".to_string(),
+            "vault/pages/archive/plan.md".to_string(),
+            "notes/plan.md".to_string(),
+        ];
+        let bases = vec!["vault/pages".to_string(), "notes".to_string()];
+        assert_eq!(
+            match_repo_path(&listed, &bases, "archive/plan.md").as_deref(),
+            Some("vault/pages/archive/plan.md")
+        );
+        assert_eq!(
+            match_repo_path(&listed, &bases, "plan.md").as_deref(),
+            Some("vault/pages/plan.md")
+        );
+        assert_eq!(match_repo_path(&listed, &bases, "other/plan.md"), None);
+        assert_eq!(
+            match_repo_path(&listed, &[], "notes/plan.md").as_deref(),
+            Some("notes/plan.md")
+        );
+    }
+}
@@ -0,0 +1,420 @@
+//! Room anchors for XR clients.
+//!
+//! An anchor is the transform from a user's physical room to the graph
+//! origin, so a headset can put the graph back in the same spot next session.
+//! Anchors are stored per user and device in `xr_anchors.json` in the data
+//! directory. A device with no anchor of its own gets the user's most
+//! recently saved one, flagged as `inherited`, so a second headset in the
+//! same room starts from the first one's placement.
+
+use crate::config::dev_config;
+use crate::services::json_file::JsonFile;
+use chrono::{DateTime, Utc};
+use log::info;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+/// Longest accepted device id.
+pub const MAX_DEVICE_ID_LEN: usize = 128;
+
+/// Anchors further than this from the room origin are rejected, in metres.
+const MAX_ANCHOR_DISTANCE: f32 = 1_000.0;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XrAnchor {
+    /// Graph origin in room space, metres.
+    pub position: [f32; 3],
+    /// Orientation as a unit quaternion `[x, y, z, w]`.
+    pub rotation: [f32; 4],
+    #[serde(default = "default_scale")]
+    pub scale: f32,
+    pub updated_at: DateTime<Utc>,
+}
+
+fn default_scale() -> f32 {
+    1.0
+}
+
+/// Body of `PUT /api/xr/anchor`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnchorUpdate {
+    pub device_id: String,
+    pub position: [f32; 3],
+    pub rotation: [f32; 4],
+    #[serde(default = "default_scale")]
+    pub scale: f32,
+}
+
+/// An anchor as returned to a device.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceAnchor {
+    /// Device that saved the anchor.
+    pub device_id: String,
+    /// True when it was saved by another of the user's devices.
+    pub inherited: bool,
+    #[serde(flatten)]
+    pub anchor: XrAnchor,
+}
+
+pub fn validate_device_id(device_id: &str) -> Result<(), String> {
+    if device_id.is_empty() || device_id.len() > MAX_DEVICE_ID_LEN {
+        return Err(format!(
+            "deviceId must be 1 to {} characters",
+            MAX_DEVICE_ID_LEN
+        ));
+    }
+    if !device_id
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'))
+    {
+        return Err("deviceId may only contain letters, digits, '-', '_', '.' and ':'".to_string());
+    }
+    Ok(())
+}
+
+impl AnchorUpdate {
+    /// Check the transform and normalise the quaternion.
+    pub fn into_anchor(self, now: DateTime<Utc>) -> Result<XrAnchor, String> {
+        validate_device_id(&self.device_id)?;
+        if self
+            .position
+            .iter()
+            .any(|v| !v.is_finite() || v.abs() > MAX_ANCHOR_DISTANCE)
+        {
+            return Err(format!(
+                "position must be finite and within {} m of the room origin",
+                MAX_ANCHOR_DISTANCE
+            ));
+        }
+        let norm = self.rotation.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if !norm.is_finite() || norm < 1e-6 {
+            return Err("rotation must be a non-zero quaternion [x, y, z, w]".to_string());
+        }
+        if !self.scale.is_finite() || self.scale <= 0.0 {
+            return Err("scale must be positive".to_string());
+        }
+        Ok(XrAnchor {
+            position: self.position,
+            rotation: self.rotation.map(|v| v / norm),
+            scale: self.scale,
+            updated_at: now,
+        })
+    }
+}
+
+/// User pubkey to device id to anchor.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AnchorBook {
+    #[serde(default)]
+    pub users: BTreeMap<String, BTreeMap<String, XrAnchor>>,
+}
+
+impl AnchorBook {
+    pub fn set(&mut self, user: &str, device_id: &str, anchor: XrAnchor) {
+        self.users
+            .entry(user.to_string())
+            .or_default()
+            .insert(device_id.to_string(), anchor);
+    }
+
+    /// The device's own anchor, else the user's most recently saved one.
+    pub fn for_device(&self, user: &str, device_id: Option<&str>) -> Option<DeviceAnchor> {
+        let devices = self.users.get(user)?;
+        if let Some((id, anchor)) = device_id.and_then(|d| devices.get_key_value(d)) {
+            return Some(DeviceAnchor {
+                device_id: id.clone(),
+                inherited: false,
+                anchor: anchor.clone(),
+            });
+        }
+        devices
+            .iter()
+            .max_by_key(|(_, anchor)| anchor.updated_at)
+            .map(|(id, anchor)| DeviceAnchor {
+                device_id: id.clone(),
+                inherited: true,
+                anchor: anchor.clone(),
+            })
+    }
+
+    /// Remove one device's anchor; returns whether there was one.
+    pub fn remove(&mut self, user: &str, device_id: &str) -> bool {
+        let Some(devices) = self.users.get_mut(user) else {
+            return false;
+        };
+        let removed = devices.remove(device_id).is_some();
+        if devices.is_empty() {
+            self.users.remove(user);
+        }
+        removed
+    }
+}
+
+/// Process-wide anchor book persisted to disk.
+pub struct XrAnchorService {
+    file: JsonFile,
+    book: RwLock<AnchorBook>,
+}
+
+impl XrAnchorService {
+    pub fn load(path: PathBuf) -> Self {
+        let file = JsonFile::new(path, "XR anchors").pretty();
+        let book: AnchorBook = file.load();
+        info!("Loaded XR anchors for {} user(s)", book.users.len());
+        Self {
+            file,
+            book: RwLock::new(book),
+        }
+    }
+
+    pub fn get(&self, user: &str, device_id: Option<&str>) -> Option<DeviceAnchor> {
+        self.book
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .for_device(user, device_id)
+    }
+
+    /// Store a validated anchor; see [`AnchorUpdate::into_anchor`].
+    pub fn put(
+        &self,
+        user: &str,
+        device_id: String,
+        anchor: XrAnchor,
+    ) -> Result<DeviceAnchor, String> {
+        let mut book = self.book.write().unwrap_or_else(|e| e.into_inner());
+        let mut updated = book.clone();
+        updated.set(user, &device_id, anchor.clone());
+        self.save(&updated)?;
+        *book = updated;
+        Ok(DeviceAnchor {
+            device_id,
+            inherited: false,
+            anchor,
+        })
+    }
+
+    pub fn delete(&self, user: &str, device_id: &str) -> Result<bool, String> {
+        let mut book = self.book.write().unwrap_or_else(|e| e.into_inner());
+        let mut updated = book.clone();
+        if !updated.remove(user, device_id) {
+            return Ok(false);
+        }
+        self.save(&updated)?;
+        *book = updated;
+        Ok(true)
+    }
+
+    fn save(&self, book: &AnchorBook) -> Result<(), String> {
+        self.file.save(book)
+    }
+}
+
+static SERVICE: Lazy<XrAnchorService> =
+    Lazy::new(|| XrAnchorService::load(dev_config::storage().path("xr_anchors.json")));
+
+pub fn xr_anchors() -> &'static XrAnchorService {
+    &SERVICE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn t0() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap()
+    }
+
+    fn update(device_id: &str, x: f32) -> AnchorUpdate {
+        AnchorUpdate {
+            device_id: device_id.to_string(),
+            position: [x, 0.0, -1.5],
+            rotation: [0.0, 0.0, 0.0, 2.0],
+            scale: 1.0,
+        }
+    }
+
+    fn anchor(x: f32, hours: i64) -> XrAnchor {
+        update("quest", x)
+            .into_anchor(t0() + chrono::Duration::hours(hours))
+            .unwrap()
+    }
+
+    /// Alice's quest anchor, then her vision anchor an hour later.
+    fn two_device_book() -> AnchorBook {
+        let mut book = AnchorBook::default();
+        book.set("alice", "quest", anchor(1.0, 0));
+        book.set("alice", "vision", anchor(2.0, 1));
+        book
+    }
+
+    #[test]
+    fn into_anchor_normalises_rotation() {
+        let anchor = update("quest", 1.0).into_anchor(t0()).unwrap();
+        assert_eq!(anchor.rotation, [0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(anchor.position, [1.0, 0.0, -1.5]);
+        assert_eq!(anchor.updated_at, t0());
+    }
+
+    #[test]
+    fn missing_scale_defaults_to_one() {
+        let update: AnchorUpdate = serde_json::from_value(serde_json::json!({
+            "deviceId": "quest",
+            "position": [0.0, 0.0, 0.0],
+            "rotation": [0.0, 0.0, 0.0, 1.0],
+        }))
+        .unwrap();
+        assert_eq!(update.scale, 1.0);
+    }
+
+    #[test]
+    fn rejects_non_finite_or_distant_positions() {
+        assert!(update("quest", f32::NAN).into_anchor(t0()).is_err());
+        assert!(update("quest", f32::INFINITY).into_anchor(t0()).is_err());
+        assert!(update("quest", MAX_ANCHOR_DISTANCE + 1.0)
+            .into_anchor(t0())
+            .is_err());
+        assert!(update("quest", -MAX_ANCHOR_DISTANCE)
+            .into_anchor(t0())
+            .is_ok());
+    }
+
+    #[test]
+    fn rejects_zero_or_non_finite_rotation() {
+        let mut zero = update("quest", 0.0);
+        zero.rotation = [0.0; 4];
+        assert!(zero.into_anchor(t0()).is_err());
+
+        let mut nan = update("quest", 0.0);
+        nan.rotation = [0.0, f32::NAN, 0.0, 1.0];
+        assert!(nan.into_anchor(t0()).is_err());
+    }
+
+    #[test]
+    fn rejects_non_positive_scale() {
+        for scale in [0.0, -1.0, f32::NAN] {
+            let mut bad = update("quest", 0.0);
+            bad.scale = scale;
+            assert!(bad.into_anchor(t0()).is_err(), "scale {}", scale);
+        }
+    }
+
+    #[test]
+    fn device_ids_are_short_and_path_safe() {
+        assert!(validate_device_id("quest-3:left_eye.1").is_ok());
+        assert!(validate_device_id("").is_err());
+        assert!(validate_device_id(&"q".repeat(MAX_DEVICE_ID_LEN)).is_ok());
+        assert!(validate_device_id(&"q".repeat(MAX_DEVICE_ID_LEN + 1)).is_err());
+        assert!(validate_device_id("../etc").is_err());
+        assert!(validate_device_id("has space").is_err());
+        assert!(update("../etc", 0.0).into_anchor(t0()).is_err());
+    }
+
+    #[test]
+    fn device_gets_its_own_anchor() {
+        let book = two_device_book();
+        let own = book.for_device("alice", Some("quest")).unwrap();
+        assert!(!own.inherited);
+        assert_eq!(own.device_id, "quest");
+        assert_eq!(own.anchor, anchor(1.0, 0));
+    }
+
+    #[test]
+    fn missing_device_inherits_latest_user_anchor() {
+        let book = two_device_book();
+        let other = book.for_device("alice", Some("phone")).unwrap();
+        assert!(other.inherited);
+        assert_eq!(other.device_id, "vision");
+
+        let any = book.for_device("alice", None).unwrap();
+        assert!(any.inherited);
+        assert_eq!(any.device_id, "vision");
+    }
+
+    #[test]
+    fn unknown_user_has_no_anchor() {
+        let book = two_device_book();
+        assert!(book.for_device("bob", Some("quest")).is_none());
+        assert!(book.for_device("bob", None).is_none());
+        assert!(AnchorBook::default()
+            .for_device("alice", Some("quest"))
+            .is_none());
+    }
+
+    #[test]
+    fn device_anchor_serialises_flat_and_camel_case() {
+        let own = two_device_book()
+            .for_device("alice", Some("quest"))
+            .unwrap();
+        let json = serde_json::to_value(&own).unwrap();
+        assert_eq!(json["deviceId"], "quest");
+        assert_eq!(json["inherited"], false);
+        assert_eq!(json["position"], serde_json::json!([1.0, 0.0, -1.5]));
+        assert!(json.get("updatedAt").is_some());
+        assert!(json.get("anchor").is_none());
+    }
+
+    #[test]
+    fn removing_a_device_falls_back_to_the_remaining_one() {
+        let mut book = two_device_book();
+        assert!(book.remove("alice", "vision"));
+        assert_eq!(book.for_device("alice", None).unwrap().device_id, "quest");
+    }
+
+    #[test]
+    fn removing_the_last_device_drops_the_user() {
+        let mut book = two_device_book();
+        assert!(book.remove("alice", "vision"));
+        assert!(book.remove("alice", "quest"));
+        assert!(book.users.is_empty());
+    }
+
+    #[test]
+    fn removing_a_missing_device_or_user_is_a_no_op() {
+        let mut book = two_device_book();
+        assert!(!book.remove("alice", "phone"));
+        assert!(!book.remove("bob", "quest"));
+        assert_eq!(book.users["alice"].len(), 2);
+    }
+
+    #[test]
+    fn service_persists_puts_and_deletes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("xr_anchors.json");
+        let service = XrAnchorService::load(path.clone());
+        let saved = service
+            .put("alice", "quest".to_string(), anchor(1.0, 0))
+            .unwrap();
+        assert!(!saved.inherited);
+
+        let reloaded = XrAnchorService::load(path.clone());
+        assert_eq!(reloaded.get("alice", Some("quest")).unwrap(), saved);
+
+        assert!(reloaded.delete("alice", "quest").unwrap());
+        assert!(XrAnchorService::load(path).get("alice", None).is_none());
+    }
+
+    #[test]
+    fn service_delete_of_missing_device_writes_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("xr_anchors.json");
+        let service = XrAnchorService::load(path.clone());
+        assert!(!service.delete("alice", "quest").unwrap());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn corrupt_file_loads_as_an_empty_book() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("xr_anchors.json");
+        std::fs::write(&path, "{not json").unwrap();
+        let service = XrAnchorService::load(path);
+        assert!(service.get("alice", Some("quest")).is_none());
+    }
+}
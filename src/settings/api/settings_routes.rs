@@ -14,6 +14,7 @@ use crate::utils::unified_gpu_compute::ComputeMode;
 use crate::settings::models::{ConstraintSettings, NodeFilterSettings, QualityGateSettings, AllSettings};
 use crate::settings::auth_extractor::{AuthenticatedUser, OptionalAuth};
 use crate::adapters::SqliteSettingsRepository;
+use crate::services::settings_history_service::{self, settings_history, ChangeOrigin, ChangeSource};
 /// Placeholder for the per-user filter record. Full SQLite migration in Phase 2.
 // todo!("Phase 2: migrate UserFilter to SqliteSettingsRepository / SQLite schema")
 #[derive(Debug, Default, serde::Serialize, serde::Deserialize, Clone)]
@@ -52,6 +53,31 @@ pub struct ErrorResponse {
     pub error: String,
 }
 
+/// Why a settings write did not go through. The REST handlers turn it into
+/// a response; other callers (history revert, the WebSocket) use the message.
+#[derive(Debug)]
+pub enum SettingsWriteError {
+    /// The request was rejected: bad JSON shape or failed validation.
+    Invalid(String),
+    /// The settings actor or the repository failed.
+    Failed(String),
+}
+
+impl SettingsWriteError {
+    pub fn message(&self) -> &str {
+        match self {
+            Self::Invalid(message) | Self::Failed(message) => message,
+        }
+    }
+
+    pub fn into_response(self) -> HttpResponse {
+        match self {
+            Self::Invalid(error) => HttpResponse::BadRequest().json(ErrorResponse { error }),
+            Self::Failed(error) => HttpResponse::InternalServerError().json(ErrorResponse { error }),
+        }
+    }
+}
+
 // ============================================================================
 // Physics Settings Validation (QE Fix #2 + Fix #5)
 // ============================================================================
@@ -299,7 +325,6 @@ pub async fn get_physics_settings(
 /// PUT /api/settings/physics
 /// Validates input before applying (QE Fix #2 + #5).
 /// Accepts partial JSON updates -- missing fields retain current values from the actor.
-/// Uses single GetSettings call to avoid TOCTOU race (QE Fix #3).
 pub async fn update_physics_settings(
     state: web::Data<AppState>,
     body: web::Json<serde_json::Value>,
@@ -308,29 +333,41 @@ pub async fn update_physics_settings(
 ) -> impl Responder {
     debug!("User {} updating physics settings — request body: {:?}", auth.pubkey, body);
 
+    let origin = ChangeOrigin::new(auth.pubkey.clone(), ChangeSource::Rest);
+    match apply_physics_patch(&state, &settings_repo, body.into_inner(), &origin).await {
+        Ok(physics) => HttpResponse::Ok().json(&physics),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Merge a partial physics patch onto the current settings, validate it,
+/// propagate it to the GPU and SQLite, and record it in the settings history.
+/// Uses single GetSettings call to avoid TOCTOU race (QE Fix #3).
+pub(crate) async fn apply_physics_patch(
+    state: &AppState,
+    settings_repo: &SqliteSettingsRepository,
+    patch: serde_json::Value,
+    origin: &ChangeOrigin,
+) -> Result<PhysicsSettings, SettingsWriteError> {
     // Single GetSettings call -- fetch full settings snapshot once to avoid TOCTOU race
     let mut full_settings = match state.settings_addr.send(GetSettings).await {
         Ok(Ok(settings)) => settings,
         Ok(Err(e)) => {
             error!("Failed to fetch current settings: {}", e);
-            return HttpResponse::InternalServerError().json(ErrorResponse {
-                error: format!("Failed to fetch current settings: {}", e),
-            });
+            return Err(SettingsWriteError::Failed(format!("Failed to fetch current settings: {}", e)));
         }
         Err(e) => {
             error!("Actor mailbox error: {}", e);
-            return HttpResponse::InternalServerError().json(ErrorResponse {
-                error: format!("Actor communication error: {}", e),
-            });
+            return Err(SettingsWriteError::Failed(format!("Actor communication error: {}", e)));
         }
     };
 
     // Merge partial patch onto current physics from the same snapshot
     let current_physics = &full_settings.visualisation.graphs.logseq.physics;
-    let current_json = serde_json::to_value(current_physics).unwrap_or_default();
+    let previous_json = serde_json::to_value(current_physics).unwrap_or_default();
 
     let new_physics = if let (serde_json::Value::Object(mut base), serde_json::Value::Object(patch)) =
-        (current_json, body.into_inner())
+        (previous_json, patch)
     {
         // Normalize incoming keys: map common aliases (snake_case, legacy names)
         // to the canonical camelCase field names used by PhysicsSettings.
@@ -342,9 +379,7 @@ pub async fn update_physics_settings(
             Ok(merged) => merged,
             Err(e) => {
                 warn!("Physics settings merge failed: {}", e);
-                return HttpResponse::BadRequest().json(ErrorResponse {
-                    error: format!("Invalid settings value: {}", e),
-                });
+                return Err(SettingsWriteError::Invalid(format!("Invalid settings value: {}", e)));
             }
         }
     } else {
@@ -354,16 +389,18 @@ pub async fn update_physics_settings(
     // Validate before applying
     if let Err(validation_err) = validate_physics_settings(&new_physics) {
         warn!("Physics settings validation failed: {}", validation_err);
-        return HttpResponse::BadRequest().json(ErrorResponse {
-            error: format!("Validation failed: {}", validation_err),
-        });
+        return Err(SettingsWriteError::Invalid(format!("Validation failed: {}", validation_err)));
     }
 
     // Apply merged physics to the same snapshot and write back atomically
     full_settings.visualisation.graphs.logseq.physics = new_physics.clone();
-    match state.settings_addr.send(UpdateSettings { settings: full_settings }).await {
+    let update = UpdateSettings {
+        settings: full_settings,
+        origin: origin.clone(),
+    };
+    match state.settings_addr.send(update).await {
         Ok(Ok(())) => {
-            info!("Physics settings updated successfully by {}", auth.pubkey);
+            info!("Physics settings updated successfully by {}", origin.author);
 
             // Propagate physics changes to GPU actors so layout actually responds
             let sim_params: crate::models::simulation_params::SimulationParams = (&new_physics).into();
@@ -439,19 +476,15 @@ pub async fn update_physics_settings(
                 }
             }
 
-            HttpResponse::Ok().json(&new_physics)
+            Ok(new_physics)
         }
         Ok(Err(e)) => {
             error!("Failed to update physics settings: {}", e);
-            HttpResponse::InternalServerError().json(ErrorResponse {
-                error: format!("Failed to update physics settings: {}", e),
-            })
+            Err(SettingsWriteError::Failed(format!("Failed to update physics settings: {}", e)))
         }
         Err(e) => {
             error!("Actor mailbox error: {}", e);
-            HttpResponse::InternalServerError().json(ErrorResponse {
-                error: format!("Actor communication error: {}", e),
-            })
+            Err(SettingsWriteError::Failed(format!("Actor communication error: {}", e)))
         }
     }
 }
@@ -590,22 +623,36 @@ pub async fn update_rendering_settings(
 ) -> impl Responder {
     info!("User {} updating rendering settings", auth.pubkey);
 
-    let new_rendering = body.into_inner();
+    let origin = ChangeOrigin::new(auth.pubkey.clone(), ChangeSource::Rest);
+    match apply_rendering_settings(&state, body.into_inner(), &origin).await {
+        Ok(rendering) => HttpResponse::Ok().json(&rendering),
+        Err(e) => e.into_response(),
+    }
+}
 
+/// Validate and apply rendering settings, tell connected clients, and record
+/// the change in the settings history.
+pub(crate) async fn apply_rendering_settings(
+    state: &AppState,
+    new_rendering: RenderingSettings,
+    origin: &ChangeOrigin,
+) -> Result<RenderingSettings, SettingsWriteError> {
     // Validate before applying
     if let Err(validation_err) = validate_rendering_settings(&new_rendering) {
         warn!("Rendering settings validation failed: {}", validation_err);
-        return HttpResponse::BadRequest().json(ErrorResponse {
-            error: format!("Validation failed: {}", validation_err),
-        });
+        return Err(SettingsWriteError::Invalid(format!("Validation failed: {}", validation_err)));
     }
 
     match state.settings_addr.send(GetSettings).await {
         Ok(Ok(mut full_settings)) => {
             full_settings.visualisation.rendering = new_rendering.clone();
-            match state.settings_addr.send(UpdateSettings { settings: full_settings }).await {
+            let update = UpdateSettings {
+                settings: full_settings,
+                origin: origin.clone(),
+            };
+            match state.settings_addr.send(update).await {
                 Ok(Ok(())) => {
-                    info!("Rendering settings updated successfully by {}", auth.pubkey);
+                    info!("Rendering settings updated successfully by {}", origin.author);
 
                     // Propagate rendering changes to connected clients via broadcast
                     // Rendering settings (ambient light, shadows, environment) are applied
@@ -613,7 +660,7 @@ pub async fn update_rendering_settings(
                     let broadcast_payload = serde_json::json!({
                         "type": "settingsUpdated",
                         "category": "rendering",
-                        "updatedBy": auth.pubkey,
+                        "updatedBy": origin.author,
                         "timestamp": chrono::Utc::now().timestamp_millis()
                     });
                     if let Ok(msg_str) = serde_json::to_string(&broadcast_payload) {
@@ -621,33 +668,25 @@ pub async fn update_rendering_settings(
                         info!("Rendering settings change broadcast sent to connected clients");
                     }
 
-                    HttpResponse::Ok().json(&new_rendering)
+                    Ok(new_rendering)
                 }
                 Ok(Err(e)) => {
                     error!("Failed to update rendering settings: {}", e);
-                    HttpResponse::InternalServerError().json(ErrorResponse {
-                        error: format!("Failed to update rendering settings: {}", e),
-                    })
+                    Err(SettingsWriteError::Failed(format!("Failed to update rendering settings: {}", e)))
                 }
                 Err(e) => {
                     error!("Actor mailbox error: {}", e);
-                    HttpResponse::InternalServerError().json(ErrorResponse {
-                        error: format!("Actor communication error: {}", e),
-                    })
+                    Err(SettingsWriteError::Failed(format!("Actor communication error: {}", e)))
                 }
             }
         }
         Ok(Err(e)) => {
             error!("Failed to fetch current settings: {}", e);
-            HttpResponse::InternalServerError().json(ErrorResponse {
-                error: format!("Failed to fetch current settings: {}", e),
-            })
+            Err(SettingsWriteError::Failed(format!("Failed to fetch current settings: {}", e)))
         }
         Err(e) => {
             error!("Actor mailbox error: {}", e);
-            HttpResponse::InternalServerError().json(ErrorResponse {
-                error: format!("Actor communication error: {}", e),
-            })
+            Err(SettingsWriteError::Failed(format!("Actor communication error: {}", e)))
         }
     }
 }
@@ -1022,36 +1061,59 @@ pub async fn update_visual_settings(
 ) -> impl Responder {
     info!("User {} updating visual settings", auth.pubkey);
 
-    let patch = body.into_inner();
+    let origin = ChangeOrigin::new(auth.pubkey.clone(), ChangeSource::Rest);
+    match apply_visual_patch(&settings_repo, body.into_inner(), &origin).await {
+        Ok(visual) => HttpResponse::Ok().json(visual),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Deep merge a visual settings patch into the stored blob, persist it and
+/// record the change in the settings history.
+pub(crate) async fn apply_visual_patch(
+    settings_repo: &SqliteSettingsRepository,
+    patch: serde_json::Value,
+    origin: &ChangeOrigin,
+) -> Result<serde_json::Value, SettingsWriteError> {
     if !patch.is_object() {
-        return HttpResponse::BadRequest().json(ErrorResponse {
-            error: "Visual settings must be a JSON object".to_string(),
-        });
+        return Err(SettingsWriteError::Invalid("Visual settings must be a JSON object".to_string()));
     }
 
     // Load current stored settings as merge base
-    let mut current = match settings_repo.get_setting("visual").await {
-        Ok(Some(SettingValue::Json(json))) if json.is_object() => json,
-        _ => serde_json::json!({}),
-    };
+    let previous = stored_visual(settings_repo).await;
 
     // Deep merge patch into current
+    let mut current = previous.clone();
     deep_merge_json(&mut current, patch);
+    store_visual(settings_repo, &previous, current, origin).await
+}
 
-    // Persist merged result to SQLite
+async fn stored_visual(settings_repo: &SqliteSettingsRepository) -> serde_json::Value {
+    match settings_repo.get_setting("visual").await {
+        Ok(Some(SettingValue::Json(json))) if json.is_object() => json,
+        _ => serde_json::json!({}),
+    }
+}
+
+/// Persist the visual settings blob and record the change from `previous`.
+async fn store_visual(
+    settings_repo: &SqliteSettingsRepository,
+    previous: &serde_json::Value,
+    current: serde_json::Value,
+    origin: &ChangeOrigin,
+) -> Result<serde_json::Value, SettingsWriteError> {
     if let Err(e) = settings_repo.set_setting(
         "visual",
         SettingValue::Json(current.clone()),
         Some("Client visual settings (glow, hologram, graphTypeVisuals, nodes, edges, labels, etc.)"),
     ).await {
         error!("Failed to persist visual settings: {}", e);
-        return HttpResponse::InternalServerError().json(ErrorResponse {
-            error: format!("Failed to persist visual settings: {}", e),
-        });
+        return Err(SettingsWriteError::Failed(format!("Failed to persist visual settings: {}", e)));
     }
 
-    info!("Visual settings updated and persisted for user {}", auth.pubkey);
-    HttpResponse::Ok().json(current)
+    info!("Visual settings updated and persisted for user {}", origin.author);
+    settings_history().record(origin, "visual", previous, &current);
+    Ok(current)
 }
 
 // ============================================================================
@@ -1304,6 +1366,176 @@ pub async fn reset_layout(
     }))
 }
 
+// ============================================================================
+// Settings History Routes
+// ============================================================================
+
+/// Entries returned by GET /api/settings/history when no limit is given.
+const DEFAULT_HISTORY_LIMIT: usize = 100;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsHistoryQuery {
+    pub section: Option<String>,
+    pub limit: Option<usize>,
+}
+
+fn unknown_section(section: &str) -> SettingsWriteError {
+    SettingsWriteError::Invalid(format!(
+        "Unknown settings section '{}'; expected one of {:?}",
+        section,
+        settings_history_service::SECTIONS
+    ))
+}
+
+/// Current values of a history section ("physics", "rendering" or "visual") as JSON.
+pub(crate) async fn current_section(
+    state: &AppState,
+    settings_repo: &SqliteSettingsRepository,
+    section: &str,
+) -> Result<serde_json::Value, SettingsWriteError> {
+    match section {
+        "visual" => Ok(stored_visual(settings_repo).await),
+        "physics" | "rendering" => {
+            let settings = state
+                .settings_addr
+                .send(GetSettings)
+                .await
+                .map_err(|e| SettingsWriteError::Failed(format!("Actor communication error: {}", e)))?
+                .map_err(|e| SettingsWriteError::Failed(format!("Failed to fetch current settings: {}", e)))?;
+            let value = if section == "physics" {
+                serde_json::to_value(&settings.visualisation.graphs.logseq.physics)
+            } else {
+                serde_json::to_value(&settings.visualisation.rendering)
+            };
+            value.map_err(|e| SettingsWriteError::Failed(format!("Failed to serialize {} settings: {}", section, e)))
+        }
+        other => Err(unknown_section(other)),
+    }
+}
+
+/// Apply a partial update to one history section through the same path as its
+/// PUT route. Rendering takes a full struct over REST, so the patch is deep
+/// merged onto the current values first.
+pub(crate) async fn apply_settings_patch(
+    state: &AppState,
+    settings_repo: &SqliteSettingsRepository,
+    section: &str,
+    patch: serde_json::Value,
+    origin: &ChangeOrigin,
+) -> Result<serde_json::Value, SettingsWriteError> {
+    let to_json = |value: serde_json::Result<serde_json::Value>| {
+        value.map_err(|e| SettingsWriteError::Failed(format!("Failed to serialize {} settings: {}", section, e)))
+    };
+    match section {
+        "physics" => to_json(serde_json::to_value(
+            apply_physics_patch(state, settings_repo, patch, origin).await?,
+        )),
+        "rendering" => {
+            let mut merged = current_section(state, settings_repo, "rendering").await?;
+            deep_merge_json(&mut merged, patch);
+            let rendering = serde_json::from_value::<RenderingSettings>(merged)
+                .map_err(|e| SettingsWriteError::Invalid(format!("Invalid settings value: {}", e)))?;
+            to_json(serde_json::to_value(
+                apply_rendering_settings(state, rendering, origin).await?,
+            ))
+        }
+        "visual" => apply_visual_patch(settings_repo, patch, origin).await,
+        other => Err(unknown_section(other)),
+    }
+}
+
+/// Replace one history section with `values` through the same path as its
+/// PUT route. Unlike a patch, a key missing from `values` is unset rather
+/// than kept at its current value.
+pub(crate) async fn replace_settings_section(
+    state: &AppState,
+    settings_repo: &SqliteSettingsRepository,
+    section: &str,
+    values: serde_json::Value,
+    origin: &ChangeOrigin,
+) -> Result<serde_json::Value, SettingsWriteError> {
+    let invalid = |e: serde_json::Error| SettingsWriteError::Invalid(format!("Invalid settings value: {}", e));
+    let to_json = |value: serde_json::Result<serde_json::Value>| {
+        value.map_err(|e| SettingsWriteError::Failed(format!("Failed to serialize {} settings: {}", section, e)))
+    };
+    match section {
+        "physics" => {
+            let physics = serde_json::from_value::<PhysicsSettings>(values).map_err(invalid)?;
+            let patch = to_json(serde_json::to_value(physics))?;
+            to_json(serde_json::to_value(
+                apply_physics_patch(state, settings_repo, patch, origin).await?,
+            ))
+        }
+        "rendering" => {
+            let rendering = serde_json::from_value::<RenderingSettings>(values).map_err(invalid)?;
+            to_json(serde_json::to_value(
+                apply_rendering_settings(state, rendering, origin).await?,
+            ))
+        }
+        "visual" => {
+            if !values.is_object() {
+                return Err(SettingsWriteError::Invalid("Visual settings must be a JSON object".to_string()));
+            }
+            let previous = stored_visual(settings_repo).await;
+            store_visual(settings_repo, &previous, values, origin).await
+        }
+        other => Err(unknown_section(other)),
+    }
+}
+
+/// GET /api/settings/history
+/// Recorded physics, rendering and visual settings changes, newest first.
+/// `?section=physics` narrows to one section; `?limit=` caps the entry count.
+pub async fn get_settings_history(
+    query: web::Query<SettingsHistoryQuery>,
+    _auth: AuthenticatedUser,
+) -> impl Responder {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_HISTORY_LIMIT)
+        .min(settings_history_service::MAX_ENTRIES);
+    let (entries, total) = settings_history().list(query.section.as_deref(), limit);
+    HttpResponse::Ok().json(serde_json::json!({
+        "entries": entries,
+        "total": total,
+    }))
+}
+
+/// POST /api/settings/history/{id}/revert
+/// Puts the values entry `id` changed back to what they were before it.
+/// Other values keep their current state. The revert is recorded as a new
+/// entry with `revertOf` set.
+pub async fn revert_settings_history(
+    state: web::Data<AppState>,
+    settings_repo: web::Data<Arc<SqliteSettingsRepository>>,
+    path: web::Path<u64>,
+    auth: AuthenticatedUser,
+) -> impl Responder {
+    let id = path.into_inner();
+    let Some(entry) = settings_history().get(id) else {
+        return HttpResponse::NotFound().json(ErrorResponse {
+            error: format!("No settings history entry {}", id),
+        });
+    };
+    info!("User {} reverting settings history entry #{} ({})", auth.pubkey, id, entry.section);
+
+    let current = match current_section(&state, &settings_repo, &entry.section).await {
+        Ok(current) => current,
+        Err(e) => return e.into_response(),
+    };
+    let reverted = settings_history_service::reverted(&current, &entry);
+    let origin = ChangeOrigin::revert(auth.pubkey.clone(), id);
+    match replace_settings_section(&state, &settings_repo, &entry.section, reverted, &origin).await {
+        Ok(values) => HttpResponse::Ok().json(serde_json::json!({
+            "reverted": id,
+            "section": entry.section,
+            "values": values,
+        })),
+        Err(e) => e.into_response(),
+    }
+}
+
 // ============================================================================
 // Route Configuration
 // ============================================================================
@@ -1328,7 +1560,9 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
         .route("profiles", web::post().to(save_profile))
         .route("profiles", web::get().to(list_profiles))
         .route("profiles/{id}", web::get().to(load_profile))
-        .route("profiles/{id}", web::delete().to(delete_profile));
+        .route("profiles/{id}", web::delete().to(delete_profile))
+        .route("history", web::get().to(get_settings_history))
+        .route("history/{id}/revert", web::post().to(revert_settings_history));
 
     // User-specific filter settings
     cfg.service(
@@ -538,6 +538,24 @@ pub fn decode_node_data(data: &[u8]) -> Result<Vec<(u32, BinaryNodeData)>, Strin
     }
 }
 
+/// Whether `data` is a V3 or V5 position frame. The leading byte alone is not
+/// enough: multiplexed control frames also start with `0x03`, so the payload
+/// length must be a whole number of node records and a `0x03` frame must not
+/// carry a JSON body.
+pub fn is_position_frame(data: &[u8]) -> bool {
+    match data.first() {
+        Some(&PROTOCOL_V3) => {
+            let payload = &data[1..];
+            if payload.is_empty() || payload.len() % WIRE_V3_ITEM_SIZE != 0 {
+                return false;
+            }
+            payload[0] != b'{' || serde_json::from_slice::<serde_json::Value>(payload).is_err()
+        }
+        Some(5) => data.len() >= 9 && (data.len() - 9) % WIRE_V3_ITEM_SIZE == 0,
+        _ => false,
+    }
+}
+
 // decode_node_data_v1 REMOVED - V1 protocol no longer supported
 
 // decode_node_data_v2 REMOVED — V2 protocol no longer supported (was 36 bytes/node, no analytics)
@@ -734,6 +752,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn position_frames_are_told_apart_from_control_frames() {
+        let node = BinaryNodeData {
+            node_id: 1,
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+            vx: 0.0,
+            vy: 0.0,
+            vz: 0.0,
+        };
+        let v3 = encode_node_data(&[(1, node)]);
+        assert!(is_position_frame(&v3));
+
+        let mut v5 = vec![5u8];
+        v5.extend_from_slice(&7u64.to_le_bytes());
+        v5.extend_from_slice(&v3[1..]);
+        assert!(is_position_frame(&v5));
+
+        let control = MultiplexedMessage::control(&ControlFrame::ack("pause", true, None))
+            .unwrap()
+            .encode();
+        assert_eq!(control[0], MessageType::ControlFrame as u8);
+        assert!(!is_position_frame(&control));
+        assert!(!is_position_frame(&[MessageType::PositionDelta as u8, 1, 2]));
+        assert!(!is_position_frame(&encode_agent_actions(&[])));
+    }
+
     #[test]
     fn test_encode_decode_roundtrip() {
         let nodes = vec![
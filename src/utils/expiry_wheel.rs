@@ -0,0 +1,142 @@
+//! Hashed timing wheel for TTL bookkeeping.
+//!
+//! Keys are bucketed by deadline tick into a fixed ring of slots, so inserting,
+//! cancelling and advancing are all proportional to the number of entries
+//! touched rather than the number tracked. Deadlines longer than one rotation
+//! simply stay in their slot until the wheel comes round to the right tick.
+//! Cancellation is lazy: the authoritative deadline lives in a side map and
+//! stale slot entries are discarded when their slot is next visited.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+pub struct ExpiryWheel<K> {
+    slot_ms: u64,
+    slots: Vec<Vec<(K, u64)>>,
+    deadlines: HashMap<K, u64>,
+    origin: Instant,
+    current_tick: u64,
+}
+
+impl<K: Copy + Eq + Hash + Ord> ExpiryWheel<K> {
+    pub fn new(slot: Duration, slot_count: usize, now: Instant) -> Self {
+        Self {
+            slot_ms: (slot.as_millis() as u64).max(1),
+            slots: vec![Vec::new(); slot_count.max(1)],
+            deadlines: HashMap::new(),
+            origin: now,
+            current_tick: 0,
+        }
+    }
+
+    fn elapsed_ms(&self, at: Instant) -> u64 {
+        at.saturating_duration_since(self.origin).as_millis() as u64
+    }
+
+    pub fn len(&self) -> usize {
+        self.deadlines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.deadlines.is_empty()
+    }
+
+    pub fn contains(&self, key: K) -> bool {
+        self.deadlines.contains_key(&key)
+    }
+
+    /// Schedule `key` to expire at `deadline`, replacing any earlier schedule.
+    /// Deadlines round up to the next tick, so keys never expire early.
+    pub fn insert(&mut self, key: K, deadline: Instant) {
+        let tick = self
+            .elapsed_ms(deadline)
+            .div_ceil(self.slot_ms)
+            .max(self.current_tick + 1);
+        self.deadlines.insert(key, tick);
+        let idx = (tick % self.slots.len() as u64) as usize;
+        self.slots[idx].push((key, tick));
+    }
+
+    pub fn cancel(&mut self, key: K) -> bool {
+        self.deadlines.remove(&key).is_some()
+    }
+
+    /// Advance to `now` and return every key whose deadline has passed, sorted.
+    pub fn advance(&mut self, now: Instant) -> Vec<K> {
+        let target = self.elapsed_ms(now) / self.slot_ms;
+        if target <= self.current_tick {
+            return Vec::new();
+        }
+        // Visiting every slot once covers all ticks, however far behind we are.
+        let steps = (target - self.current_tick).min(self.slots.len() as u64);
+        let mut expired = Vec::new();
+        for step in 1..=steps {
+            let idx = ((self.current_tick + step) % self.slots.len() as u64) as usize;
+            let deadlines = &mut self.deadlines;
+            self.slots[idx].retain(|&(key, tick)| {
+                if deadlines.get(&key) != Some(&tick) {
+                    return false;
+                }
+                if tick <= target {
+                    deadlines.remove(&key);
+                    expired.push(key);
+                    return false;
+                }
+                true
+            });
+        }
+        self.current_tick = target;
+        expired.sort_unstable();
+        expired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wheel(now: Instant) -> ExpiryWheel<u32> {
+        ExpiryWheel::new(Duration::from_millis(100), 8, now)
+    }
+
+    #[test]
+    fn expires_at_deadline_not_before() {
+        let start = Instant::now();
+        let mut w = wheel(start);
+        w.insert(1, start + Duration::from_millis(250));
+        w.insert(2, start + Duration::from_millis(500));
+
+        assert!(w.advance(start + Duration::from_millis(200)).is_empty());
+        assert_eq!(w.advance(start + Duration::from_millis(300)), vec![1]);
+        assert_eq!(w.len(), 1);
+        assert_eq!(w.advance(start + Duration::from_millis(500)), vec![2]);
+        assert!(w.is_empty());
+    }
+
+    #[test]
+    fn deadlines_beyond_one_rotation_survive_wraparound() {
+        let start = Instant::now();
+        let mut w = wheel(start);
+        // 8 slots × 100 ms = 800 ms per rotation.
+        w.insert(7, start + Duration::from_millis(2_000));
+        assert!(w.advance(start + Duration::from_millis(900)).is_empty());
+        assert!(w.advance(start + Duration::from_millis(1_700)).is_empty());
+        assert_eq!(w.advance(start + Duration::from_millis(2_000)), vec![7]);
+    }
+
+    #[test]
+    fn cancel_and_reschedule_are_honoured() {
+        let start = Instant::now();
+        let mut w = wheel(start);
+        w.insert(1, start + Duration::from_millis(100));
+        w.insert(2, start + Duration::from_millis(100));
+        assert!(w.cancel(1));
+        w.insert(2, start + Duration::from_millis(600));
+
+        assert!(w.advance(start + Duration::from_millis(300)).is_empty());
+        // Falling far behind still expires everything that is due.
+        assert_eq!(w.advance(start + Duration::from_secs(10)), vec![2]);
+    }
+}
@@ -8,6 +8,10 @@ use std::env;
 use std::io::{Error, ErrorKind};
 use std::path::Path;
 
+const PTX_FILE: &str = "visionclaw_unified.ptx";
+/// Source tree copy, relative to the working directory.
+const SOURCE_PTX_DIR: &str = "./src/utils/ptx";
+
 pub fn ptx_module_smoke_test() -> String {
     let mut report = String::new();
     report.push_str("==== GPU PTX MODULE SMOKE TEST ====\n");
@@ -141,18 +145,19 @@ pub fn run_gpu_diagnostics() -> String {
     }
 
     
-    let ptx_paths = [
-        "/app/src/utils/ptx/visionclaw_unified.ptx",
-        "./src/utils/ptx/visionclaw_unified.ptx",
-    ];
+    let mut ptx_paths: Vec<std::path::PathBuf> = Vec::new();
+    if let Some(ref dir) = crate::config::dev_config::gpu().ptx_path {
+        ptx_paths.push(dir.join(PTX_FILE));
+    }
+    ptx_paths.push(Path::new(SOURCE_PTX_DIR).join(PTX_FILE));
     report.push_str("\nPTX File Status:\n");
     let mut ptx_found = false;
 
     for path in &ptx_paths {
-        if Path::new(path).exists() {
+        if path.exists() {
             ptx_found = true;
-            report.push_str(&format!("  PTX file found at: {}\n", path));
-            info!("GPU Diagnostic: PTX file found at {}", path);
+            report.push_str(&format!("  PTX file found at: {}\n", path.display()));
+            info!("GPU Diagnostic: PTX file found at {}", path.display());
             
             match std::fs::metadata(path) {
                 Ok(metadata) => {
@@ -165,8 +170,8 @@ pub fn run_gpu_diagnostics() -> String {
                 }
             }
         } else {
-            report.push_str(&format!("  ❌ PTX file NOT found at: {}\n", path));
-            warn!("GPU Diagnostic: PTX file NOT found at {}", path);
+            report.push_str(&format!("  ❌ PTX file NOT found at: {}\n", path.display()));
+            warn!("GPU Diagnostic: PTX file NOT found at {}", path.display());
         }
     }
 
@@ -342,26 +347,32 @@ pub fn fix_cuda_environment() -> Result<(), Error> {
     }
 
     
-    let primary_path = "/app/src/utils/ptx/visionclaw_unified.ptx";
-    let alternative_path = "./src/utils/ptx/visionclaw_unified.ptx";
-
-    if !Path::new(primary_path).exists() {
-        info!("Primary PTX file not found at {}", primary_path);
-
-        if Path::new(alternative_path).exists() {
+    // Only a configured PTX directory needs populating; without one the
+    // loader already searches the source tree copy.
+    let Some(ref ptx_dir) = crate::config::dev_config::gpu().ptx_path else {
+        info!("No gpu.ptx_path configured, leaving PTX lookup to the loader");
+        return Ok(());
+    };
+    let primary_path = ptx_dir.join(PTX_FILE);
+    let alternative_path = Path::new(SOURCE_PTX_DIR).join(PTX_FILE);
+
+    if !primary_path.exists() {
+        info!("Primary PTX file not found at {}", primary_path.display());
+
+        if alternative_path.exists() {
             info!(
                 "Alternative PTX file found at {}, attempting to create symlink",
-                alternative_path
+                alternative_path.display()
             );
 
-            let alt_path_abs = std::fs::canonicalize(alternative_path).map_err(|e| {
+            let alt_path_abs = std::fs::canonicalize(&alternative_path).map_err(|e| {
                 Error::new(
                     ErrorKind::Other,
                     format!("Failed to get canonical path: {}", e),
                 )
             })?;
 
-            let dir_path = Path::new(primary_path)
+            let dir_path = primary_path
                 .parent()
                 .ok_or_else(|| Error::new(ErrorKind::Other, "Invalid PTX path"))?;
 
@@ -375,16 +386,16 @@ pub fn fix_cuda_environment() -> Result<(), Error> {
             }
 
             #[cfg(unix)]
-            std::os::unix::fs::symlink(&alt_path_abs, primary_path).map_err(|e| {
+            std::os::unix::fs::symlink(&alt_path_abs, &primary_path).map_err(|e| {
                 Error::new(ErrorKind::Other, format!("Failed to create symlink: {}", e))
             })?;
 
             #[cfg(not(unix))]
-            std::fs::copy(&alt_path_abs, primary_path).map_err(|e| {
+            std::fs::copy(&alt_path_abs, &primary_path).map_err(|e| {
                 Error::new(ErrorKind::Other, format!("Failed to copy PTX file: {}", e))
             })?;
 
-            info!("Successfully created PTX file at {}", primary_path);
+            info!("Successfully created PTX file at {}", primary_path.display());
         } else {
             return Err(Error::new(
                 ErrorKind::NotFound,
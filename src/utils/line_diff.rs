@@ -0,0 +1,176 @@
+//! Line-level diffs for write-back conflict reports.
+//!
+//! Each side of a three-way comparison is reduced to the hunks that replace
+//! a range of `base` lines. Hunks from the two sides that touch the same base
+//! lines are what a human has to resolve; the rest could merge cleanly.
+
+use serde::Serialize;
+
+/// Above this many LCS cells the changed middle is reported as one hunk.
+const MAX_LCS_CELLS: usize = 4_000_000;
+
+/// Replace `base_len` lines of base starting at `base_start` (zero-based)
+/// with `lines`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Hunk {
+    pub base_start: usize,
+    pub base_len: usize,
+    pub lines: Vec<String>,
+}
+
+impl Hunk {
+    fn touches(&self, other: &Hunk) -> bool {
+        // Pure insertions occupy their insertion point.
+        self.base_start < other.base_start + other.base_len.max(1)
+            && other.base_start < self.base_start + self.base_len.max(1)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreeWayDiff {
+    pub base: String,
+    pub ours: String,
+    pub theirs: String,
+    pub ours_hunks: Vec<Hunk>,
+    pub theirs_hunks: Vec<Hunk>,
+    /// Whether any of our hunks touches one of theirs.
+    pub overlapping: bool,
+}
+
+pub fn hunks(base: &str, other: &str) -> Vec<Hunk> {
+    let a: Vec<&str> = base.lines().collect();
+    let b: Vec<&str> = other.lines().collect();
+    let prefix = a.iter().zip(&b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let a = &a[prefix..a.len() - suffix];
+    let b = &b[prefix..b.len() - suffix];
+    if a.is_empty() && b.is_empty() {
+        return Vec::new();
+    }
+    let (n, m) = (a.len(), b.len());
+    if n * m > MAX_LCS_CELLS {
+        return vec![Hunk {
+            base_start: prefix,
+            base_len: n,
+            lines: b.iter().map(|l| l.to_string()).collect(),
+        }];
+    }
+
+    // lcs[i * (m + 1) + j] = LCS length of a[i..] and b[j..].
+    let width = m + 1;
+    let mut lcs = vec![0u32; (n + 1) * width];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i * width + j] = if a[i] == b[j] {
+                lcs[(i + 1) * width + j + 1] + 1
+            } else {
+                lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut out = Vec::new();
+    let mut open: Option<Hunk> = None;
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && a[i] == b[j] {
+            out.extend(open.take());
+            i += 1;
+            j += 1;
+            continue;
+        }
+        let hunk = open.get_or_insert_with(|| Hunk {
+            base_start: prefix + i,
+            base_len: 0,
+            lines: Vec::new(),
+        });
+        if j < m && (i == n || lcs[i * width + j + 1] >= lcs[(i + 1) * width + j]) {
+            hunk.lines.push(b[j].to_string());
+            j += 1;
+        } else {
+            hunk.base_len += 1;
+            i += 1;
+        }
+    }
+    out.extend(open);
+    out
+}
+
+pub fn three_way(base: String, ours: String, theirs: String) -> ThreeWayDiff {
+    let ours_hunks = hunks(&base, &ours);
+    let theirs_hunks = hunks(&base, &theirs);
+    let overlapping = ours_hunks
+        .iter()
+        .any(|o| theirs_hunks.iter().any(|t| o.touches(t)));
+    ThreeWayDiff {
+        base,
+        ours,
+        theirs,
+        ours_hunks,
+        theirs_hunks,
+        overlapping,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hunks_cover_replace_insert_and_delete() {
+        let base = "a\nb\nc\nd\ne";
+        assert!(hunks(base, base).is_empty());
+
+        let found = hunks(base, "a\nB\nc\nd\nx\ne");
+        assert_eq!(
+            found,
+            vec![
+                Hunk {
+                    base_start: 1,
+                    base_len: 1,
+                    lines: vec!["B".into()]
+                },
+                Hunk {
+                    base_start: 4,
+                    base_len: 0,
+                    lines: vec!["x".into()]
+                },
+            ]
+        );
+
+        assert_eq!(
+            hunks(base, "a\ne"),
+            vec![Hunk {
+                base_start: 1,
+                base_len: 3,
+                lines: vec![]
+            }]
+        );
+    }
+
+    #[test]
+    fn three_way_flags_only_overlapping_edits() {
+        let base = "title\n\nintro\n\nbody\n".to_string();
+        let clean = three_way(
+            base.clone(),
+            "title\n\nintro!\n\nbody\n".into(),
+            "title\n\nintro\n\nbody 2\n".into(),
+        );
+        assert!(!clean.overlapping);
+
+        let clash = three_way(
+            base,
+            "title\n\nours\n\nbody\n".into(),
+            "title\n\ntheirs\n\nbody\n".into(),
+        );
+        assert!(clash.overlapping);
+        assert_eq!(clash.theirs_hunks[0].lines, vec!["theirs".to_string()]);
+    }
+}
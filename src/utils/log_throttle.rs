@@ -0,0 +1,113 @@
+//! Rate-limited logging for per-frame code paths.
+//!
+//! The physics pipeline runs at 60fps; logging every frame (or every Nth
+//! frame, whose wall-clock spacing varies with the frame rate) floods the logs
+//! and costs CPU. [`log_throttled!`](crate::log_throttled) emits at most one
+//! line per call site per interval and reports how many were dropped since.
+//! The interval comes from `system.debug.hotPathLogIntervalSecs`.
+
+use once_cell::sync::Lazy;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use visionclaw_domain::config::system::DEFAULT_HOT_PATH_LOG_INTERVAL_SECS;
+
+static INTERVAL_MS: AtomicU64 =
+    AtomicU64::new(DEFAULT_HOT_PATH_LOG_INTERVAL_SECS as u64 * 1000);
+static EPOCH: Lazy<Instant> = Lazy::new(Instant::now);
+
+/// Set the minimum spacing between lines from one call site. 0 disables
+/// throttling.
+pub fn set_interval_secs(secs: u32) {
+    INTERVAL_MS.store(secs as u64 * 1000, Ordering::Relaxed);
+}
+
+pub fn interval() -> Duration {
+    Duration::from_millis(INTERVAL_MS.load(Ordering::Relaxed))
+}
+
+fn now_ms() -> u64 {
+    EPOCH.elapsed().as_millis() as u64
+}
+
+/// Throttle state for one call site; the macro declares one as a static.
+#[derive(Debug)]
+pub struct LogThrottle {
+    last_ms: AtomicU64,
+    suppressed: AtomicU64,
+}
+
+const NEVER: u64 = u64::MAX;
+
+impl Default for LogThrottle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LogThrottle {
+    pub const fn new() -> Self {
+        Self {
+            last_ms: AtomicU64::new(NEVER),
+            suppressed: AtomicU64::new(0),
+        }
+    }
+
+    /// `Some(suppressed)` if the site may log now, with the number of calls
+    /// dropped since it last did; `None` if this call should be dropped.
+    pub fn ready(&self) -> Option<u64> {
+        self.ready_at(now_ms(), INTERVAL_MS.load(Ordering::Relaxed))
+    }
+
+    fn ready_at(&self, now: u64, interval_ms: u64) -> Option<u64> {
+        let last = self.last_ms.load(Ordering::Relaxed);
+        let due = last == NEVER || now.saturating_sub(last) >= interval_ms;
+        if due
+            && self
+                .last_ms
+                .compare_exchange(last, now, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+        {
+            return Some(self.suppressed.swap(0, Ordering::Relaxed));
+        }
+        self.suppressed.fetch_add(1, Ordering::Relaxed);
+        None
+    }
+}
+
+/// `log!` limited to one line per call site per throttle interval.
+///
+/// ```ignore
+/// log_throttled!(log::Level::Info, "Computing forces (iteration {})", iteration);
+/// ```
+#[macro_export]
+macro_rules! log_throttled {
+    ($lvl:expr, $($arg:tt)+) => {{
+        static SITE: $crate::utils::log_throttle::LogThrottle =
+            $crate::utils::log_throttle::LogThrottle::new();
+        let lvl = $lvl;
+        if ::log::log_enabled!(lvl) {
+            if let Some(suppressed) = SITE.ready() {
+                if suppressed > 0 {
+                    ::log::log!(lvl, "{} [{} similar suppressed]", format_args!($($arg)+), suppressed);
+                } else {
+                    ::log::log!(lvl, $($arg)+);
+                }
+            }
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_line_per_interval_with_suppressed_count() {
+        let site = LogThrottle::new();
+        assert_eq!(site.ready_at(0, 1000), Some(0));
+        assert_eq!(site.ready_at(10, 1000), None);
+        assert_eq!(site.ready_at(999, 1000), None);
+        assert_eq!(site.ready_at(1000, 1000), Some(2));
+        assert_eq!(site.ready_at(1001, 0), Some(0), "zero interval never drops");
+    }
+}
@@ -9,6 +9,7 @@ pub mod audio_processor;
 pub mod binary_protocol;
 pub mod client_message_extractor;
 pub mod edge_data;
+pub mod expiry_wheel;
 pub mod gpu_diagnostics;
 // ADR-090: GPU memory canonical at visionclaw_gpu::memory. The `gpu_memory`
 // alias is preserved so existing `crate::utils::gpu_memory::*` paths in tests
@@ -21,6 +22,8 @@ pub mod response_macros;
 // pub mod hybrid_fault_tolerance;
 // pub mod hybrid_performance_optimizer;
 pub mod json;
+pub mod line_diff;
+pub mod log_throttle;
 // REMOVED: pub mod logging; - Superseded by advanced_logging, archived to archive/legacy_code_2025_11_03/
 // Re-export advanced_logging as 'logging' for backwards compatibility
 pub mod logging {
@@ -40,6 +43,7 @@ pub mod socket_flow_messages;
 pub mod standard_websocket_messages;
 pub mod unified_gpu_compute;
 pub mod time;
+pub mod transliteration;
 pub mod validation;
 pub mod websocket_heartbeat;
 pub mod nip98; // NIP-98 HTTP authentication for Solid Server integration
@@ -0,0 +1,414 @@
+//! Label cleaning and romanization for non-Latin vaults.
+//!
+//! Labels come straight from file names and page titles, so a vault written
+//! in Korean, Japanese, Russian or Arabic produces labels that a viewer who
+//! does not read the script cannot tell apart, and stray control or bidi
+//! override characters can garble how a right-to-left label renders. When a
+//! node is built its label is cleaned here and, if
+//! `rendering.romanize_labels` is on, a Latin rendering is stored alongside
+//! it in `metadata["label_romanized"]` (and `label_direction = "rtl"` for
+//! Hebrew and Arabic script labels). Clients pick the variant they want with
+//! their locale; see `LabelVariant` in `protocol::graph_json`.
+//!
+//! Romanization is table driven and deliberately simple:
+//!
+//! - Hangul follows Revised Romanization, including liaison of a final
+//!   consonant into a following vowel (한국어 → `hangugeo`).
+//! - Kana follows Hepburn, with digraphs, doubled consonants for small tsu
+//!   and `ー` repeating the previous vowel.
+//! - Cyrillic, Greek, Arabic/Persian and Hebrew are transliterated letter
+//!   by letter. Unvowelled Arabic and Hebrew give consonant skeletons.
+//! - Fullwidth forms and CJK punctuation become their ASCII equivalents.
+//!
+//! Han characters have no reading without a dictionary and pass through
+//! unchanged, so a label that is all kanji or hanzi gets no romanized
+//! variant.
+
+use crate::config::dev_config;
+use log::warn;
+use visionclaw_domain::models::node::Node;
+
+const HANGUL_BASE: u32 = 0xAC00;
+const HANGUL_LAST: u32 = 0xD7A3;
+/// Syllables per initial consonant: 21 vowels × 28 finals.
+const HANGUL_PER_INITIAL: u32 = 588;
+/// Index of the silent initial ㅇ.
+const HANGUL_SILENT_INITIAL: u32 = 11;
+
+const HANGUL_INITIALS: [&str; 19] = [
+    "g", "kk", "n", "d", "tt", "r", "m", "b", "pp", "s", "ss", "", "j", "jj", "ch", "k", "t", "p",
+    "h",
+];
+const HANGUL_VOWELS: [&str; 21] = [
+    "a", "ae", "ya", "yae", "eo", "e", "yeo", "ye", "o", "wa", "wae", "oe", "yo", "u", "wo", "we",
+    "wi", "yu", "eu", "ui", "i",
+];
+/// Finals at the end of a word or before a consonant.
+const HANGUL_FINALS: [&str; 28] = [
+    "", "k", "k", "k", "n", "n", "n", "t", "l", "k", "m", "l", "l", "l", "p", "l", "m", "p", "p",
+    "t", "t", "ng", "t", "t", "k", "t", "p", "t",
+];
+/// Finals carried over into a following syllable that starts with ㅇ.
+const HANGUL_FINALS_LIAISON: [&str; 28] = [
+    "", "g", "kk", "ks", "n", "nj", "n", "d", "r", "lg", "lm", "lb", "ls", "lt", "lp", "r", "m",
+    "b", "ps", "s", "ss", "ng", "j", "ch", "k", "t", "p", "",
+];
+
+/// Hiragana U+3041..=U+3096; katakana is the same block shifted by 0x60.
+const KANA: [&str; 86] = [
+    "a", "a", "i", "i", "u", "u", "e", "e", "o", "o", "ka", "ga", "ki", "gi", "ku", "gu", "ke",
+    "ge", "ko", "go", "sa", "za", "shi", "ji", "su", "zu", "se", "ze", "so", "zo", "ta", "da",
+    "chi", "ji", "tsu", "tsu", "zu", "te", "de", "to", "do", "na", "ni", "nu", "ne", "no", "ha",
+    "ba", "pa", "hi", "bi", "pi", "fu", "bu", "pu", "he", "be", "pe", "ho", "bo", "po", "ma", "mi",
+    "mu", "me", "mo", "ya", "ya", "yu", "yu", "yo", "yo", "ra", "ri", "ru", "re", "ro", "wa", "wa",
+    "i", "e", "o", "n", "vu", "ka", "ke",
+];
+const HIRAGANA_FIRST: u32 = 0x3041;
+const KATAKANA_FIRST: u32 = 0x30A1;
+const SMALL_TSU: u32 = 0x3063;
+
+/// Russian letters а..я; uppercase is 0x20 below.
+const CYRILLIC: [&str; 32] = [
+    "a", "b", "v", "g", "d", "e", "zh", "z", "i", "y", "k", "l", "m", "n", "o", "p", "r", "s", "t",
+    "u", "f", "kh", "ts", "ch", "sh", "shch", "", "y", "", "e", "yu", "ya",
+];
+/// Greek α..ω (with final sigma); uppercase is 0x20 below.
+const GREEK: [&str; 25] = [
+    "a", "v", "g", "d", "e", "z", "i", "th", "i", "k", "l", "m", "n", "x", "o", "p", "r", "s", "s",
+    "t", "y", "f", "ch", "ps", "o",
+];
+/// Arabic U+0621..=U+064A.
+const ARABIC: [&str; 42] = [
+    "'", "a", "a", "'", "i", "'", "a", "b", "h", "t", "th", "j", "h", "kh", "d", "dh", "r", "z",
+    "s", "sh", "s", "d", "t", "z", "'", "gh", "", "", "", "", "", "", "f", "q", "k", "l", "m", "n",
+    "h", "w", "a", "y",
+];
+/// Hebrew U+05D0..=U+05EA, final forms included.
+const HEBREW: [&str; 27] = [
+    "", "v", "g", "d", "h", "v", "z", "kh", "t", "y", "kh", "kh", "l", "m", "m", "n", "n", "s", "",
+    "f", "p", "ts", "ts", "k", "r", "sh", "t",
+];
+
+fn hangul_parts(c: char) -> Option<(u32, u32, u32)> {
+    let cp = c as u32;
+    if !(HANGUL_BASE..=HANGUL_LAST).contains(&cp) {
+        return None;
+    }
+    let index = cp - HANGUL_BASE;
+    Some((
+        index / HANGUL_PER_INITIAL,
+        (index % HANGUL_PER_INITIAL) / 28,
+        index % 28,
+    ))
+}
+
+/// Table index of a hiragana or katakana letter.
+fn kana_index(c: char) -> Option<u32> {
+    let cp = c as u32;
+    let len = KANA.len() as u32;
+    if (HIRAGANA_FIRST..HIRAGANA_FIRST + len).contains(&cp) {
+        Some(cp - HIRAGANA_FIRST)
+    } else if (KATAKANA_FIRST..KATAKANA_FIRST + len).contains(&cp) {
+        Some(cp - KATAKANA_FIRST)
+    } else {
+        None
+    }
+}
+
+/// Vowel of a small ゃ/ゅ/ょ (or katakana) that forms a digraph.
+fn small_y_vowel(c: char) -> Option<&'static str> {
+    match kana_index(c)? + HIRAGANA_FIRST {
+        0x3083 => Some("a"),
+        0x3085 => Some("u"),
+        0x3087 => Some("o"),
+        _ => None,
+    }
+}
+
+/// Vowel of a small ぁぃぅぇぉ (or katakana), as in ファ or ティ.
+fn small_vowel(c: char) -> Option<char> {
+    match kana_index(c)? + HIRAGANA_FIRST {
+        0x3041 => Some('a'),
+        0x3043 => Some('i'),
+        0x3045 => Some('u'),
+        0x3047 => Some('e'),
+        0x3049 => Some('o'),
+        _ => None,
+    }
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+/// Letter-by-letter scripts, with whether the source letter was uppercase.
+fn letter(c: char) -> Option<(&'static str, bool)> {
+    let cp = c as u32;
+    let found = match cp {
+        0x0430..=0x044F => (CYRILLIC[(cp - 0x0430) as usize], false),
+        0x0410..=0x042F => (CYRILLIC[(cp - 0x0410) as usize], true),
+        0x0451 => ("yo", false),
+        0x0401 => ("yo", true),
+        // Ukrainian and Belarusian additions
+        0x0454 => ("ye", false),
+        0x0404 => ("ye", true),
+        0x0456 => ("i", false),
+        0x0406 => ("i", true),
+        0x0457 => ("yi", false),
+        0x0407 => ("yi", true),
+        0x0491 => ("g", false),
+        0x0490 => ("g", true),
+        0x045E => ("w", false),
+        0x040E => ("w", true),
+        0x03B1..=0x03C9 => (GREEK[(cp - 0x03B1) as usize], false),
+        0x0391..=0x03A9 if cp != 0x03A2 => (GREEK[(cp - 0x0391) as usize], true),
+        0x03AC => ("a", false),
+        0x03AD => ("e", false),
+        0x03AE | 0x03AF | 0x03CA | 0x0390 => ("i", false),
+        0x03CC | 0x03CE => ("o", false),
+        0x03CD | 0x03CB | 0x03B0 => ("y", false),
+        0x0386 => ("a", true),
+        0x0388 => ("e", true),
+        0x0389 | 0x038A => ("i", true),
+        0x038C | 0x038F => ("o", true),
+        0x038E => ("y", true),
+        0x0621..=0x064A => (ARABIC[(cp - 0x0621) as usize], false),
+        // Short vowel marks; the rest of the harakat are dropped.
+        0x064E => ("a", false),
+        0x064F => ("u", false),
+        0x0650 => ("i", false),
+        0x064B..=0x0652 | 0x0670 => ("", false),
+        0x067E => ("p", false),
+        0x0686 => ("ch", false),
+        0x0698 => ("zh", false),
+        0x06A9 => ("k", false),
+        0x06AF => ("g", false),
+        0x06CC => ("y", false),
+        0x05D0..=0x05EA => (HEBREW[(cp - 0x05D0) as usize], false),
+        // Niqqud and cantillation marks
+        0x0591..=0x05C7 => ("", false),
+        _ => return None,
+    };
+    Some(found)
+}
+
+/// ASCII stand-ins for fullwidth forms, digits and CJK punctuation.
+fn punctuation(c: char) -> Option<char> {
+    let cp = c as u32;
+    let mapped = match cp {
+        0xFF01..=0xFF5E => char::from_u32(cp - 0xFEE0)?,
+        0x3000 | 0x30FB => ' ',
+        0x3001 | 0x060C => ',',
+        0x3002 => '.',
+        0x300C..=0x300F => '"',
+        0x3010 => '[',
+        0x3011 => ']',
+        0x301C => '~',
+        0x061F => '?',
+        0x0660..=0x0669 => char::from_u32('0' as u32 + cp - 0x0660)?,
+        0x06F0..=0x06F9 => char::from_u32('0' as u32 + cp - 0x06F0)?,
+        _ => return None,
+    };
+    Some(mapped)
+}
+
+fn push_capitalised(out: &mut String, text: &str, upper: bool) {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) if upper => {
+            out.extend(first.to_uppercase());
+            out.push_str(chars.as_str());
+        }
+        _ => out.push_str(text),
+    }
+}
+
+/// Latin rendering of `label`, or `None` if romanizing changes nothing.
+pub fn romanize(label: &str) -> Option<String> {
+    if label.is_ascii() {
+        return None;
+    }
+    let chars: Vec<char> = label.chars().collect();
+    let mut out = String::with_capacity(label.len());
+    let mut double_next = false;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        i += 1;
+
+        if let Some((initial, vowel, last)) = hangul_parts(c) {
+            out.push_str(HANGUL_INITIALS[initial as usize]);
+            out.push_str(HANGUL_VOWELS[vowel as usize]);
+            let liaison = chars
+                .get(i)
+                .and_then(|&next| hangul_parts(next))
+                .is_some_and(|(next_initial, _, _)| next_initial == HANGUL_SILENT_INITIAL);
+            out.push_str(if liaison {
+                HANGUL_FINALS_LIAISON[last as usize]
+            } else {
+                HANGUL_FINALS[last as usize]
+            });
+            continue;
+        }
+
+        if let Some(index) = kana_index(c) {
+            if index + HIRAGANA_FIRST == SMALL_TSU {
+                double_next = true;
+                continue;
+            }
+            let mut syllable = KANA[index as usize].to_string();
+            if let Some(&next) = chars.get(i) {
+                if let Some(vowel) = small_y_vowel(next).filter(|_| syllable.ends_with('i')) {
+                    syllable.pop();
+                    // shi/chi/ji + ya → sha/cha/ja; ki + ya → kya
+                    if !matches!(syllable.as_str(), "sh" | "ch" | "j") {
+                        syllable.push('y');
+                    }
+                    syllable.push_str(vowel);
+                    i += 1;
+                } else if let Some(vowel) = small_vowel(next).filter(|_| syllable.len() > 1) {
+                    syllable.pop();
+                    syllable.push(vowel);
+                    i += 1;
+                } else if let Some(vowel) = small_vowel(next).filter(|_| syllable == "u") {
+                    syllable = format!("w{}", vowel);
+                    i += 1;
+                }
+            }
+            if std::mem::take(&mut double_next) {
+                match syllable.chars().next() {
+                    Some('c') => out.push('t'),
+                    Some(first) if !is_vowel(first) => out.push(first),
+                    _ => {}
+                }
+            }
+            out.push_str(&syllable);
+            continue;
+        }
+        double_next = false;
+
+        // Prolonged sound mark ー repeats the previous vowel.
+        if c == '\u{30FC}' {
+            if let Some(last) = out.chars().last().filter(|&v| is_vowel(v)) {
+                out.push(last);
+            }
+            continue;
+        }
+        if let Some((text, upper)) = letter(c) {
+            push_capitalised(&mut out, text, upper);
+            continue;
+        }
+        out.push(punctuation(c).unwrap_or(c));
+    }
+    (out != label).then_some(out)
+}
+
+/// Whether `label` reads right to left: its first letter is Hebrew or Arabic.
+pub fn is_rtl(label: &str) -> bool {
+    label
+        .chars()
+        .find(|c| c.is_alphabetic())
+        .is_some_and(|c| matches!(c as u32, 0x0590..=0x08FF | 0xFB1D..=0xFDFF | 0xFE70..=0xFEFF))
+}
+
+/// Explicit embedding, override and isolate controls. Left in a label they
+/// reorder whatever the client renders after it.
+fn is_bidi_control(c: char) -> bool {
+    matches!(c as u32, 0x202A..=0x202E | 0x2066..=0x2069)
+}
+
+/// Problems that indicate a mangled label: replacement characters left by
+/// lossy UTF-8 decoding, control characters and bidi controls.
+pub fn validate_label(label: &str) -> Result<(), String> {
+    if label.contains('\u{FFFD}') {
+        return Err("contains U+FFFD; the source was not valid UTF-8".to_string());
+    }
+    if let Some(c) = label
+        .chars()
+        .find(|&c| c.is_control() || is_bidi_control(c))
+    {
+        return Err(format!("contains control character U+{:04X}", c as u32));
+    }
+    Ok(())
+}
+
+/// `label` with control and bidi control characters removed; line breaks
+/// and tabs become spaces.
+pub fn clean_label(label: &str) -> String {
+    label
+        .chars()
+        .filter_map(|c| match c {
+            '\t' | '\n' | '\r' => Some(' '),
+            '\u{FEFF}' => None,
+            c if c.is_control() || is_bidi_control(c) => None,
+            c => Some(c),
+        })
+        .collect()
+}
+
+/// Clean `node.label` and, if enabled, record its romanized form and
+/// direction in the node's metadata. Call whenever the label is set.
+pub fn prepare_label(node: &mut Node) {
+    if let Err(reason) = validate_label(&node.label) {
+        warn!(
+            "Label of node {} ({}) {}",
+            node.id, node.metadata_id, reason
+        );
+        node.label = clean_label(&node.label);
+    }
+    // Drop variants left from a previous label.
+    node.metadata.remove("label_romanized");
+    node.metadata.remove("label_direction");
+    if !dev_config::rendering().romanize_labels {
+        return;
+    }
+    if let Some(romanized) = romanize(&node.label) {
+        node.metadata
+            .insert("label_romanized".to_string(), romanized);
+    }
+    if is_rtl(&node.label) {
+        node.metadata
+            .insert("label_direction".to_string(), "rtl".to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn romanizes_supported_scripts() {
+        let cases = [
+            ("한국어", "hangugeo"),
+            ("서울 지하철", "seoul jihacheol"),
+            ("ひらがな", "hiragana"),
+            ("きょうと", "kyouto"),
+            ("マッチャ", "matcha"),
+            ("コーヒー", "koohii"),
+            ("ファイル", "fairu"),
+            ("Москва", "Moskva"),
+            ("Щука", "Shchuka"),
+            ("Львів", "Lviv"),
+            ("Αθήνα", "Athina"),
+            ("سلام", "slam"),
+            ("שלום", "shlvm"),
+            ("ＡＢＣ　１２３", "ABC 123"),
+            ("「東京」", "\"東京\""),
+        ];
+        for (label, expected) in cases {
+            assert_eq!(romanize(label).as_deref(), Some(expected), "{}", label);
+        }
+        assert_eq!(romanize("plain ascii"), None);
+        assert_eq!(romanize("東京"), None, "Han passes through");
+
+        assert!(is_rtl("שלום world"));
+        assert!(is_rtl("123 سلام"));
+        assert!(!is_rtl("hello שלום"));
+
+        assert!(validate_label("Café 東京").is_ok());
+        assert!(validate_label("bad\u{FFFD}").is_err());
+        let spoofed = "abc\u{202E}txt.exe\u{2069}\nline";
+        assert!(validate_label(spoofed).is_err());
+        assert_eq!(clean_label(spoofed), "abctxt.exe line");
+    }
+}
@@ -1,6 +1,7 @@
 //! Asynchronous GPU-to-CPU transfer methods with double buffering.
 
 use super::construction::UnifiedGPUCompute;
+use super::half_storage::StatePrecision;
 use anyhow::Result;
 use cust::memory::CopyDestination;
 
@@ -116,6 +117,11 @@ impl UnifiedGPUCompute {
 
         let target_buffer = !self.current_vel_buffer;
         let event_idx = if target_buffer { 1 } else { 0 };
+        // F16 velocities have no f32 device buffer; they are widened here.
+        let widened = match self.state_precision {
+            StatePrecision::F32 => None,
+            StatePrecision::F16 => Some(self.read_velocity_planes()?),
+        };
 
 
         let (target_x, target_y, target_z) = if target_buffer {
@@ -140,9 +146,18 @@ impl UnifiedGPUCompute {
 
 
 
-        self.vel_in_x.copy_to(target_x)?;
-        self.vel_in_y.copy_to(target_y)?;
-        self.vel_in_z.copy_to(target_z)?;
+        match widened {
+            Some([x, y, z]) => {
+                target_x.copy_from_slice(&x[..target_x.len()]);
+                target_y.copy_from_slice(&y[..target_y.len()]);
+                target_z.copy_from_slice(&z[..target_z.len()]);
+            }
+            None => {
+                self.vel_in_x.copy_to(target_x)?;
+                self.vel_in_y.copy_to(target_y)?;
+                self.vel_in_z.copy_to(target_z)?;
+            }
+        }
 
 
         self.transfer_events[event_idx].record(&self.transfer_stream)?;
@@ -286,6 +301,11 @@ impl UnifiedGPUCompute {
 
         let target_buffer = !self.current_vel_buffer;
         let event_idx = if target_buffer { 1 } else { 0 };
+        // F16 velocities have no f32 device buffer; they are widened here.
+        let widened = match self.state_precision {
+            StatePrecision::F32 => None,
+            StatePrecision::F16 => Some(self.read_velocity_planes()?),
+        };
 
 
         let (target_x, target_y, target_z) = if target_buffer {
@@ -309,9 +329,18 @@ impl UnifiedGPUCompute {
 
 
 
-        self.vel_in_x.copy_to(target_x)?;
-        self.vel_in_y.copy_to(target_y)?;
-        self.vel_in_z.copy_to(target_z)?;
+        match widened {
+            Some([x, y, z]) => {
+                target_x.copy_from_slice(&x[..target_x.len()]);
+                target_y.copy_from_slice(&y[..target_y.len()]);
+                target_z.copy_from_slice(&z[..target_z.len()]);
+            }
+            None => {
+                self.vel_in_x.copy_to(target_x)?;
+                self.vel_in_y.copy_to(target_y)?;
+                self.vel_in_z.copy_to(target_z)?;
+            }
+        }
 
 
         self.transfer_events[event_idx].record(&self.transfer_stream)?;
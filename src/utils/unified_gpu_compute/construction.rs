@@ -1,5 +1,6 @@
 //! Construction and initialization of the `UnifiedGPUCompute` struct.
 
+use super::half_storage::StatePrecision;
 use super::types::{curandState, GPUPerformanceMetrics, AABB};
 use crate::models::constraints::ConstraintData;
 pub use crate::models::simulation_params::SimParams;
@@ -60,6 +61,15 @@ pub struct UnifiedGPUCompute {
     pub vel_out_y: DeviceBuffer<f32>,
     pub vel_out_z: DeviceBuffer<f32>,
 
+    /// How integrated positions/velocities are stored; see `half_storage`.
+    /// In F16 mode velocities live in `vel_half_*`, positions are updated in
+    /// place in `pos_in_*`, and `vel_in_*` and the `*_out_*` set are
+    /// one-element placeholders.
+    pub(crate) state_precision: StatePrecision,
+    pub(crate) vel_half_x: DeviceBuffer<u16>,
+    pub(crate) vel_half_y: DeviceBuffer<u16>,
+    pub(crate) vel_half_z: DeviceBuffer<u16>,
+
 
     pub mass: DeviceBuffer<f32>,
     pub node_graph_id: DeviceBuffer<i32>,
@@ -331,19 +341,28 @@ impl UnifiedGPUCompute {
         let stream = Stream::new(StreamFlags::NON_BLOCKING, None)?;
 
 
+        let state_precision = crate::config::dev_config::cuda().state_precision;
+        let (out_len, half_len) = Self::state_buffer_lengths(state_precision, num_nodes);
+
         let pos_in_x = DeviceBuffer::zeroed(num_nodes)?;
         let pos_in_y = DeviceBuffer::zeroed(num_nodes)?;
         let pos_in_z = DeviceBuffer::zeroed(num_nodes)?;
-        let vel_in_x = DeviceBuffer::zeroed(num_nodes)?;
-        let vel_in_y = DeviceBuffer::zeroed(num_nodes)?;
-        let vel_in_z = DeviceBuffer::zeroed(num_nodes)?;
-
-        let pos_out_x = DeviceBuffer::zeroed(num_nodes)?;
-        let pos_out_y = DeviceBuffer::zeroed(num_nodes)?;
-        let pos_out_z = DeviceBuffer::zeroed(num_nodes)?;
-        let vel_out_x = DeviceBuffer::zeroed(num_nodes)?;
-        let vel_out_y = DeviceBuffer::zeroed(num_nodes)?;
-        let vel_out_z = DeviceBuffer::zeroed(num_nodes)?;
+        let vel_in_x = DeviceBuffer::zeroed(out_len)?;
+        let vel_in_y = DeviceBuffer::zeroed(out_len)?;
+        let vel_in_z = DeviceBuffer::zeroed(out_len)?;
+
+        let pos_out_x = DeviceBuffer::zeroed(out_len)?;
+        let pos_out_y = DeviceBuffer::zeroed(out_len)?;
+        let pos_out_z = DeviceBuffer::zeroed(out_len)?;
+        let vel_out_x = DeviceBuffer::zeroed(out_len)?;
+        let vel_out_y = DeviceBuffer::zeroed(out_len)?;
+        let vel_out_z = DeviceBuffer::zeroed(out_len)?;
+        let vel_half_x = DeviceBuffer::zeroed(half_len)?;
+        let vel_half_y = DeviceBuffer::zeroed(half_len)?;
+        let vel_half_z = DeviceBuffer::zeroed(half_len)?;
+        if state_precision == StatePrecision::F16 {
+            info!("GPU node velocities stored as f16 ({} nodes)", num_nodes);
+        }
 
 
         let mass = DeviceBuffer::from_slice(&vec![1.0f32; num_nodes])?;
@@ -463,6 +482,10 @@ impl UnifiedGPUCompute {
             vel_out_x,
             vel_out_y,
             vel_out_z,
+            state_precision,
+            vel_half_x,
+            vel_half_y,
+            vel_half_z,
             mass,
             node_graph_id,
             class_id,
@@ -643,6 +666,16 @@ impl UnifiedGPUCompute {
             .map_err(|e| anyhow!("Failed to allocate CUB temp storage ({} bytes): {}", total_bytes, e))
     }
 
+    /// Lengths of the f32 `vel_in_*`/`*_out_*` buffers and of each f16
+    /// `vel_half_*` plane for `num_nodes` nodes. The unused side keeps a
+    /// single element. `pos_in_*` always holds `num_nodes`.
+    pub(crate) fn state_buffer_lengths(precision: StatePrecision, num_nodes: usize) -> (usize, usize) {
+        match precision {
+            StatePrecision::F32 => (num_nodes, 1),
+            StatePrecision::F16 => (1, num_nodes),
+        }
+    }
+
     pub(crate) fn calculate_memory_usage(num_nodes: usize, num_edges: usize, max_grid_cells: usize) -> usize {
 
         let node_memory = num_nodes * (12 * 4 + 1 * 4 + 1 * 4);
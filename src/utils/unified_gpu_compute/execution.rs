@@ -1,6 +1,7 @@
 //! Physics simulation execution pipeline (force computation, integration, stability).
 
 use super::construction::UnifiedGPUCompute;
+use super::half_storage::StatePrecision;
 use super::types::{int3, thrust_sort_key_value, AABB};
 use crate::models::simulation_params::{SimParams, ToSimParams};
 use anyhow::{anyhow, Result};
@@ -60,12 +61,30 @@ impl UnifiedGPUCompute {
         })
     }
 
+    /// `vel_half_*` pointers for kernels that read velocities, or nulls when
+    /// velocities are stored as f32.
+    fn vel_half_ptrs(&self) -> (DevicePointer<u16>, DevicePointer<u16>, DevicePointer<u16>) {
+        match self.state_precision {
+            StatePrecision::F32 => (
+                DevicePointer::null(),
+                DevicePointer::null(),
+                DevicePointer::null(),
+            ),
+            StatePrecision::F16 => (
+                self.vel_half_x.as_device_ptr(),
+                self.vel_half_y.as_device_ptr(),
+                self.vel_half_z.as_device_ptr(),
+            ),
+        }
+    }
+
     pub fn execute(&mut self, mut params: SimParams) -> Result<()> {
         // Make CUDA context current for this thread (required when called from spawn_blocking threads)
         // Context::new() on the same device retains the primary context and makes it current
         let _thread_context = Context::new(self.device.clone())
             .map_err(|e| anyhow!("Failed to set CUDA context: {}", e))?;
 
+        let upload_start = std::time::Instant::now();
         params.iteration = self.iteration;
         let block_size = Self::kernel_block_size();
         let grid_size = (self.num_nodes as u32 + block_size - 1) / block_size;
@@ -101,6 +120,11 @@ impl UnifiedGPUCompute {
             ._module
             .get_global(CStr::from_bytes_with_nul(b"c_params\0").expect("static null-terminated byte literal is always valid"))?;
         c_params_global.copy_from(&[params])?;
+        self.performance_metrics.last_step_upload_ms = upload_start.elapsed().as_secs_f32() * 1000.0;
+        self.performance_metrics.last_step_kernel_ms = None;
+
+        let kernels_start = cust::event::Event::new(cust::event::EventFlags::DEFAULT)?;
+        kernels_start.record(&self.stream)?;
 
 
 
@@ -117,6 +141,7 @@ impl UnifiedGPUCompute {
             let ke_kernel = self
                 ._module
                 .get_function("calculate_kinetic_energy_kernel")?;
+            let vel_half = self.vel_half_ptrs();
             // SAFETY: Kernel launch is safe because:
             // 1. All DeviceBuffer pointers (vel_in_*, mass, partial_kinetic_energy, active_node_count)
             //    are valid allocations created during UnifiedGPUCompute::new()
@@ -135,7 +160,10 @@ impl UnifiedGPUCompute {
                         self.partial_kinetic_energy.as_device_ptr(),
                         self.active_node_count.as_device_ptr(),
                         self.num_nodes as i32,
-                        params.min_velocity_threshold
+                        params.min_velocity_threshold,
+                        vel_half.0,
+                        vel_half.1,
+                        vel_half.2
                     )
                 )?;
             }
@@ -556,6 +584,7 @@ impl UnifiedGPUCompute {
             DevicePointer::<f32>::null()
         };
 
+        let vel_half = self.vel_half_ptrs();
         unsafe {
             if params.stability_threshold > 0.0 {
                 // Force pass with stability checking variant
@@ -584,7 +613,10 @@ impl UnifiedGPUCompute {
                     self.num_constraints as i32,
                     self.should_skip_physics.as_device_ptr(),
                     d_node_degrees,
-                    self.spring_scale.as_device_ptr()
+                    self.spring_scale.as_device_ptr(),
+                    vel_half.0,
+                    vel_half.1,
+                    vel_half.2
                 ))?;
             } else {
 
@@ -739,43 +771,73 @@ impl UnifiedGPUCompute {
             }
         }
 
-        let integrate_pass_kernel = self._module.get_function(self.integrate_pass_kernel_name)?;
         let stream = &self.stream;
-        // SAFETY: Integration kernel launch is safe because:
-        // 1. All input buffers (pos_in_*, vel_in_*, force_*, mass) contain data from force pass
-        // 2. All output buffers (pos_out_*, vel_out_*) are valid DeviceBuffers with capacity >= num_nodes
-        // 3. class_id, class_charge, class_mass are ontology metadata buffers loaded at construction
-        // 4. The kernel performs Verlet integration using c_params constants from device memory
-        // 5. After this kernel, swap_buffers() exchanges input/output for next iteration
-        unsafe {
-            launch!(
-                integrate_pass_kernel<<<grid_size as u32, block_size as u32, 0, stream>>>(
-                self.pos_in_x.as_device_ptr(),
-                self.pos_in_y.as_device_ptr(),
-                self.pos_in_z.as_device_ptr(),
-                self.vel_in_x.as_device_ptr(),
-                self.vel_in_y.as_device_ptr(),
-                self.vel_in_z.as_device_ptr(),
-                self.force_x.as_device_ptr(),
-                self.force_y.as_device_ptr(),
-                self.force_z.as_device_ptr(),
-                self.mass.as_device_ptr(),
-                self.pos_out_x.as_device_ptr(),
-                self.pos_out_y.as_device_ptr(),
-                self.pos_out_z.as_device_ptr(),
-                self.vel_out_x.as_device_ptr(),
-                self.vel_out_y.as_device_ptr(),
-                self.vel_out_z.as_device_ptr(),
-                self.num_nodes as i32,
-                // Ontology class metadata
-                self.class_id.as_device_ptr(),
-                self.class_charge.as_device_ptr(),
-                self.class_mass.as_device_ptr(),
-                // FA2 adaptive speed: previous-step forces for swing/traction
-                self.prev_force_x.as_device_ptr(),
-                self.prev_force_y.as_device_ptr(),
-                self.prev_force_z.as_device_ptr()
-            ))?;
+        if self.state_precision == StatePrecision::F32 {
+            let integrate_pass_kernel = self._module.get_function(self.integrate_pass_kernel_name)?;
+            // SAFETY: Integration kernel launch is safe because:
+            // 1. All input buffers (pos_in_*, vel_in_*, force_*, mass) contain data from force pass
+            // 2. All output buffers (pos_out_*, vel_out_*) are valid DeviceBuffers with capacity >= num_nodes
+            // 3. class_id, class_charge, class_mass are ontology metadata buffers loaded at construction
+            // 4. The kernel performs Verlet integration using c_params constants from device memory
+            // 5. After this kernel, swap_buffers() exchanges input/output for next iteration
+            unsafe {
+                launch!(
+                    integrate_pass_kernel<<<grid_size as u32, block_size as u32, 0, stream>>>(
+                    self.pos_in_x.as_device_ptr(),
+                    self.pos_in_y.as_device_ptr(),
+                    self.pos_in_z.as_device_ptr(),
+                    self.vel_in_x.as_device_ptr(),
+                    self.vel_in_y.as_device_ptr(),
+                    self.vel_in_z.as_device_ptr(),
+                    self.force_x.as_device_ptr(),
+                    self.force_y.as_device_ptr(),
+                    self.force_z.as_device_ptr(),
+                    self.mass.as_device_ptr(),
+                    self.pos_out_x.as_device_ptr(),
+                    self.pos_out_y.as_device_ptr(),
+                    self.pos_out_z.as_device_ptr(),
+                    self.vel_out_x.as_device_ptr(),
+                    self.vel_out_y.as_device_ptr(),
+                    self.vel_out_z.as_device_ptr(),
+                    self.num_nodes as i32,
+                    // Ontology class metadata
+                    self.class_id.as_device_ptr(),
+                    self.class_charge.as_device_ptr(),
+                    self.class_mass.as_device_ptr(),
+                    // FA2 adaptive speed: previous-step forces for swing/traction
+                    self.prev_force_x.as_device_ptr(),
+                    self.prev_force_y.as_device_ptr(),
+                    self.prev_force_z.as_device_ptr()
+                ))?;
+            }
+        } else {
+            let integrate_f16_kernel = self._module.get_function("integrate_pass_f16_kernel")?;
+            // SAFETY: Same inputs as integrate_pass_kernel above. pos_in_* and
+            // vel_half_* hold allocated_nodes >= num_nodes elements each, and
+            // every thread reads and writes only its own node, so updating
+            // them in place is race-free.
+            unsafe {
+                launch!(
+                    integrate_f16_kernel<<<grid_size as u32, block_size as u32, 0, stream>>>(
+                    self.pos_in_x.as_device_ptr(),
+                    self.pos_in_y.as_device_ptr(),
+                    self.pos_in_z.as_device_ptr(),
+                    self.vel_half_x.as_device_ptr(),
+                    self.vel_half_y.as_device_ptr(),
+                    self.vel_half_z.as_device_ptr(),
+                    self.force_x.as_device_ptr(),
+                    self.force_y.as_device_ptr(),
+                    self.force_z.as_device_ptr(),
+                    self.mass.as_device_ptr(),
+                    self.num_nodes as i32,
+                    self.class_id.as_device_ptr(),
+                    self.class_charge.as_device_ptr(),
+                    self.class_mass.as_device_ptr(),
+                    self.prev_force_x.as_device_ptr(),
+                    self.prev_force_y.as_device_ptr(),
+                    self.prev_force_z.as_device_ptr()
+                ))?;
+            }
         }
 
 
@@ -795,8 +857,13 @@ impl UnifiedGPUCompute {
             }
             std::thread::yield_now();
         }
+        self.performance_metrics.last_step_kernel_ms =
+            kernels_start.elapsed_time_f32(&completion_event).ok();
 
-        self.swap_buffers();
+        // F16 mode updates the state in place.
+        if self.state_precision == StatePrecision::F32 {
+            self.swap_buffers();
+        }
         self.iteration += 1;
 
 
@@ -903,15 +970,7 @@ impl UnifiedGPUCompute {
         let _thread_context = Context::new(self.device.clone())
             .map_err(|e| anyhow!("Failed to set CUDA context: {}", e))?;
 
-        let mut vel_x = vec![0.0f32; self.allocated_nodes];
-        let mut vel_y = vec![0.0f32; self.allocated_nodes];
-        let mut vel_z = vec![0.0f32; self.allocated_nodes];
-
-
-        safe_copy_from_device(&self.vel_in_x, &mut vel_x, "vel_in_x")?;
-        safe_copy_from_device(&self.vel_in_y, &mut vel_y, "vel_in_y")?;
-        safe_copy_from_device(&self.vel_in_z, &mut vel_z, "vel_in_z")?;
-
+        let [mut vel_x, mut vel_y, mut vel_z] = self.read_velocity_planes()?;
 
         vel_x.truncate(self.num_nodes);
         vel_y.truncate(self.num_nodes);
@@ -931,22 +990,14 @@ impl UnifiedGPUCompute {
         use rand::Rng;
         let mut rng = rand::thread_rng();
         let n = self.num_nodes.min(self.allocated_nodes);
-        let mut vx = vec![0.0f32; self.allocated_nodes];
-        let mut vy = vec![0.0f32; self.allocated_nodes];
-        let mut vz = vec![0.0f32; self.allocated_nodes];
-        safe_copy_from_device(&self.vel_in_x, &mut vx, "vel_in_x")?;
-        safe_copy_from_device(&self.vel_in_y, &mut vy, "vel_in_y")?;
-        safe_copy_from_device(&self.vel_in_z, &mut vz, "vel_in_z")?;
+        let [mut vx, mut vy, mut vz] = self.read_velocity_planes()?;
         let magnitude = factor * 2.0;
         for i in 0..n {
             vx[i] += rng.gen_range(-magnitude..magnitude);
             vy[i] += rng.gen_range(-magnitude..magnitude);
             vz[i] += rng.gen_range(-magnitude..magnitude);
         }
-        safe_copy_to_device(&mut self.vel_in_x, &vx, "vel_in_x")?;
-        safe_copy_to_device(&mut self.vel_in_y, &vy, "vel_in_y")?;
-        safe_copy_to_device(&mut self.vel_in_z, &vz, "vel_in_z")?;
-        Ok(())
+        self.write_velocity_planes(&vx, &vy, &vz)
     }
 
     /// Zero all node velocities on the GPU. Used by the divergence circuit
@@ -956,9 +1007,52 @@ impl UnifiedGPUCompute {
         let _thread_context = Context::new(self.device.clone())
             .map_err(|e| anyhow!("Failed to set CUDA context: {}", e))?;
         let zeros = vec![0.0f32; self.allocated_nodes];
-        safe_copy_to_device(&mut self.vel_in_x, &zeros, "vel_in_x")?;
-        safe_copy_to_device(&mut self.vel_in_y, &zeros, "vel_in_y")?;
-        safe_copy_to_device(&mut self.vel_in_z, &zeros, "vel_in_z")?;
-        Ok(())
+        self.write_velocity_planes(&zeros, &zeros, &zeros)
+    }
+
+    /// Overwrite the position and velocity of individual nodes, given as
+    /// `(index, position, velocity)`, leaving every other node as it is.
+    pub fn set_node_states(&mut self, states: &[(usize, [f32; 3], [f32; 3])]) -> Result<()> {
+        if states.is_empty() {
+            return Ok(());
+        }
+        let (mut x, mut y, mut z) = self.get_node_positions()?;
+        let (mut vx, mut vy, mut vz) = self.get_node_velocities()?;
+        for &(index, position, velocity) in states {
+            if index >= self.num_nodes {
+                return Err(anyhow!(
+                    "Node index {} out of range for {} nodes",
+                    index,
+                    self.num_nodes
+                ));
+            }
+            [x[index], y[index], z[index]] = position;
+            [vx[index], vy[index], vz[index]] = velocity;
+        }
+        self.upload_positions(&x, &y, &z)?;
+        self.upload_velocities(&vx, &vy, &vz)
+    }
+
+    /// Overwrite node velocities on the GPU, e.g. after non-finite values
+    /// were scrubbed from a readback. Slices cover the active nodes only.
+    pub fn upload_velocities(&mut self, x: &[f32], y: &[f32], z: &[f32]) -> Result<()> {
+        if x.len() != self.num_nodes || y.len() != self.num_nodes || z.len() != self.num_nodes {
+            return Err(anyhow!(
+                "Velocity array size mismatch: expected {} nodes, got x:{}, y:{}, z:{}",
+                self.num_nodes,
+                x.len(),
+                y.len(),
+                z.len()
+            ));
+        }
+        let _thread_context = Context::new(self.device.clone())
+            .map_err(|e| anyhow!("Failed to set CUDA context: {}", e))?;
+        let allocated = self.allocated_nodes;
+        let pad = |src: &[f32]| {
+            let mut padded = src.to_vec();
+            padded.resize(allocated, 0.0);
+            padded
+        };
+        self.write_velocity_planes(&pad(x), &pad(y), &pad(z))
     }
 }
@@ -0,0 +1,137 @@
+//! Half-precision storage of node velocities.
+//!
+//! In [`StatePrecision::F16`] mode every kernel still computes in f32, but
+//! velocities are stored only as IEEE binary16 (`vel_half_*`) and positions
+//! in a single f32 buffer that the integration pass updates in place. The
+//! f32 velocity buffers and the `*_out_*` ping-pong set are not allocated,
+//! so node state takes 18 bytes per node instead of 48, and the integration
+//! pass moves 36 bytes per node instead of 48.
+//!
+//! Positions stay f32: the grid, force, clustering and AABB kernels all read
+//! them, and a half position would stop a node whose step is below half the
+//! local spacing (a whole unit between 1024 and 2048). Velocities are
+//! relative quantities and keep 11 significant bits. f32 stays
+//! the default; `tests/f16_state_accuracy_test.rs` runs both modes through
+//! the kernels and compares the layouts.
+//!
+//! The host conversions below round to nearest even like `__float2half_rn`;
+//! they are used where velocities cross the host boundary (uploads,
+//! readbacks, resizes).
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatePrecision {
+    #[default]
+    F32,
+    F16,
+}
+
+impl StatePrecision {
+    /// Device bytes for one node's positions and velocities.
+    pub fn state_bytes_per_node(self) -> usize {
+        match self {
+            // pos/vel in and out, three f32 each
+            StatePrecision::F32 => 12 * 4,
+            // three f32 positions plus three half velocities
+            StatePrecision::F16 => 3 * 4 + 3 * 2,
+        }
+    }
+}
+
+/// `value` as binary16 bits, rounding to nearest even. Out-of-range values
+/// become infinity and NaN stays NaN.
+pub fn f32_to_f16_bits(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exp = ((bits >> 23) & 0xff) as i32;
+    let mant = bits & 0x007f_ffff;
+
+    if exp == 0xff {
+        let nan = if mant != 0 { 0x0200 } else { 0 };
+        return sign | 0x7c00 | nan;
+    }
+    let half_exp = exp - 127 + 15;
+    if half_exp >= 0x1f {
+        return sign | 0x7c00;
+    }
+    if half_exp <= 0 {
+        // Subnormal half: the value in units of 2^-24.
+        let shift = (14 - half_exp) as u32;
+        if shift > 24 {
+            return sign;
+        }
+        let m = mant | 0x0080_0000;
+        let mut h = m >> shift;
+        let rem = m & ((1 << shift) - 1);
+        let halfway = 1 << (shift - 1);
+        if rem > halfway || (rem == halfway && h & 1 == 1) {
+            h += 1;
+        }
+        return sign | h as u16;
+    }
+
+    let mut h = ((half_exp as u32) << 10) | (mant >> 13);
+    let rem = mant & 0x1fff;
+    // A carry out of the mantissa bumps the exponent, up to infinity.
+    if rem > 0x1000 || (rem == 0x1000 && h & 1 == 1) {
+        h += 1;
+    }
+    sign | h as u16
+}
+
+pub fn f16_bits_to_f32(bits: u16) -> f32 {
+    let negative = bits & 0x8000 != 0;
+    let exp = ((bits >> 10) & 0x1f) as u32;
+    let mant = (bits & 0x03ff) as u32;
+    let magnitude = match exp {
+        0 => mant as f32 * (1.0 / 16_777_216.0),
+        0x1f => f32::from_bits(0x7f80_0000 | (mant << 13)),
+        _ => f32::from_bits(((exp + 112) << 23) | (mant << 13)),
+    };
+    if negative {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+/// Round-trip `value` through binary16, as F16 storage does each step.
+pub fn quantize(value: f32) -> f32 {
+    f16_bits_to_f32(f32_to_f16_bits(value))
+}
+
+pub fn narrow(values: &[f32]) -> Vec<u16> {
+    values.iter().copied().map(f32_to_f16_bits).collect()
+}
+
+pub fn widen(bits: &[u16]) -> Vec<f32> {
+    bits.iter().copied().map(f16_bits_to_f32).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_like_float2half_rn() {
+        assert_eq!(f32_to_f16_bits(1.0), 0x3c00);
+        assert_eq!(f32_to_f16_bits(-2.0), 0xc000);
+        assert_eq!(f32_to_f16_bits(65504.0), 0x7bff);
+        assert_eq!(f32_to_f16_bits(65520.0), 0x7c00, "rounds up to infinity");
+        assert_eq!(f32_to_f16_bits(2f32.powi(-24)), 0x0001);
+        assert_eq!(f32_to_f16_bits(2f32.powi(-26)), 0x0000);
+        // 1 + 2^-11 is exactly halfway between two halves: ties to even.
+        assert_eq!(f32_to_f16_bits(1.0 + 2f32.powi(-11)), 0x3c00);
+        assert_eq!(f32_to_f16_bits(1.0 + 3.0 * 2f32.powi(-11)), 0x3c02);
+        assert!(f16_bits_to_f32(f32_to_f16_bits(f32::NAN)).is_nan());
+
+        for bits in [0x0001u16, 0x03ff, 0x0400, 0x3555, 0x7bff, 0xfbff] {
+            assert_eq!(f32_to_f16_bits(f16_bits_to_f32(bits)), bits);
+        }
+        for v in [0.1f32, 3.3, -123.456, 999.9] {
+            assert!((quantize(v) - v).abs() <= v.abs() * 2f32.powi(-11));
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! Memory management, buffer resizing, and data upload/download operations.
 
 use super::construction::UnifiedGPUCompute;
+use super::half_storage::{self, StatePrecision};
 use super::types::ComputeMode;
 use crate::models::constraints::ConstraintData;
 use crate::models::simulation_params::SimParams;
@@ -205,6 +206,22 @@ impl UnifiedGPUCompute {
         Ok(())
     }
 
+    /// Replace the CSR edge weights, keeping row offsets and column indices.
+    /// `weights` must be in the order of the uploaded `col_indices`.
+    pub fn upload_edge_weights(&mut self, weights: &[f32]) -> Result<()> {
+        if weights.len() != self.num_edges {
+            return Err(anyhow!(
+                "Edge weight array size mismatch: expected {} edges, got {}",
+                self.num_edges,
+                weights.len()
+            ));
+        }
+        let mut padded = weights.to_vec();
+        padded.resize(self.edge_weights.len(), 0.0);
+        checked_copy_from(&mut self.edge_weights, &padded, "edge_weights")?;
+        Ok(())
+    }
+
     /// Download the CSR graph structure from GPU device memory.
     /// Returns (row_offsets, col_indices) where row_offsets has length num_nodes+1
     /// and col_indices has length num_edges.
@@ -242,25 +259,89 @@ impl UnifiedGPUCompute {
     }
 
     pub fn download_velocities(&self, x: &mut [f32], y: &mut [f32], z: &mut [f32]) -> Result<()> {
-        if x.len() == self.vel_in_x.len() {
-            checked_copy_to(&self.vel_in_x, x, "vel_in_x")?;
-            checked_copy_to(&self.vel_in_y, y, "vel_in_y")?;
-            checked_copy_to(&self.vel_in_z, z, "vel_in_z")?;
-        } else {
-            let mut full_x = vec![0.0f32; self.vel_in_x.len()];
-            let mut full_y = vec![0.0f32; self.vel_in_y.len()];
-            let mut full_z = vec![0.0f32; self.vel_in_z.len()];
-            checked_copy_to(&self.vel_in_x, &mut full_x, "vel_in_x")?;
-            checked_copy_to(&self.vel_in_y, &mut full_y, "vel_in_y")?;
-            checked_copy_to(&self.vel_in_z, &mut full_z, "vel_in_z")?;
-            let n = x.len().min(full_x.len());
-            x[..n].copy_from_slice(&full_x[..n]);
-            y[..n].copy_from_slice(&full_y[..n]);
-            z[..n].copy_from_slice(&full_z[..n]);
+        let [full_x, full_y, full_z] = self.read_velocity_planes()?;
+        let n = x.len().min(full_x.len());
+        x[..n].copy_from_slice(&full_x[..n]);
+        y[..n].copy_from_slice(&full_y[..n]);
+        z[..n].copy_from_slice(&full_z[..n]);
+        Ok(())
+    }
+
+    /// Every allocated node's velocity as f32 planes, widened from the
+    /// `vel_half_*` planes in F16 mode.
+    pub(crate) fn read_velocity_planes(&self) -> Result<[Vec<f32>; 3]> {
+        match self.state_precision {
+            StatePrecision::F32 => {
+                let mut planes = [
+                    vec![0.0f32; self.vel_in_x.len()],
+                    vec![0.0f32; self.vel_in_y.len()],
+                    vec![0.0f32; self.vel_in_z.len()],
+                ];
+                checked_copy_to(&self.vel_in_x, &mut planes[0], "vel_in_x")?;
+                checked_copy_to(&self.vel_in_y, &mut planes[1], "vel_in_y")?;
+                checked_copy_to(&self.vel_in_z, &mut planes[2], "vel_in_z")?;
+                Ok(planes)
+            }
+            StatePrecision::F16 => {
+                let read = |buf: &DeviceBuffer<u16>, label: &str| -> Result<Vec<f32>> {
+                    let mut bits = vec![0u16; buf.len()];
+                    checked_copy_to(buf, &mut bits, label)?;
+                    Ok(half_storage::widen(&bits))
+                };
+                Ok([
+                    read(&self.vel_half_x, "vel_half_x")?,
+                    read(&self.vel_half_y, "vel_half_y")?,
+                    read(&self.vel_half_z, "vel_half_z")?,
+                ])
+            }
+        }
+    }
+
+    /// Overwrite every allocated node's velocity; each plane must be
+    /// `allocated_nodes` long. Narrowed to f16 in F16 mode.
+    pub(crate) fn write_velocity_planes(&mut self, x: &[f32], y: &[f32], z: &[f32]) -> Result<()> {
+        match self.state_precision {
+            StatePrecision::F32 => {
+                checked_copy_from(&mut self.vel_in_x, x, "vel_in_x")?;
+                checked_copy_from(&mut self.vel_in_y, y, "vel_in_y")?;
+                checked_copy_from(&mut self.vel_in_z, z, "vel_in_z")?;
+            }
+            StatePrecision::F16 => {
+                checked_copy_from(&mut self.vel_half_x, &half_storage::narrow(x), "vel_half_x")?;
+                checked_copy_from(&mut self.vel_half_y, &half_storage::narrow(y), "vel_half_y")?;
+                checked_copy_from(&mut self.vel_half_z, &half_storage::narrow(z), "vel_half_z")?;
+            }
         }
         Ok(())
     }
 
+    /// Switch how node velocities are stored, converting the live state.
+    /// Overrides `cuda.state_precision` for this context.
+    pub fn set_state_precision(&mut self, precision: StatePrecision) -> Result<()> {
+        if precision == self.state_precision {
+            return Ok(());
+        }
+        let [vel_x, vel_y, vel_z] = self.read_velocity_planes()?;
+        let (f32_len, half_len) = Self::state_buffer_lengths(precision, self.allocated_nodes);
+        self.state_precision = precision;
+        self.vel_in_x = DeviceBuffer::zeroed(f32_len)?;
+        self.vel_in_y = DeviceBuffer::zeroed(f32_len)?;
+        self.vel_in_z = DeviceBuffer::zeroed(f32_len)?;
+        self.pos_out_x = DeviceBuffer::zeroed(f32_len)?;
+        self.pos_out_y = DeviceBuffer::zeroed(f32_len)?;
+        self.pos_out_z = DeviceBuffer::zeroed(f32_len)?;
+        self.vel_out_x = DeviceBuffer::zeroed(f32_len)?;
+        self.vel_out_y = DeviceBuffer::zeroed(f32_len)?;
+        self.vel_out_z = DeviceBuffer::zeroed(f32_len)?;
+        self.vel_half_x = DeviceBuffer::zeroed(half_len)?;
+        self.vel_half_y = DeviceBuffer::zeroed(half_len)?;
+        self.vel_half_z = DeviceBuffer::zeroed(half_len)?;
+        self.write_velocity_planes(&vel_x, &vel_y, &vel_z)?;
+        self.update_memory_usage();
+        info!("GPU node velocities now stored as {:?}", precision);
+        Ok(())
+    }
+
     pub fn swap_buffers(&mut self) {
         std::mem::swap(&mut self.pos_in_x, &mut self.pos_out_x);
         std::mem::swap(&mut self.pos_in_y, &mut self.pos_out_y);
@@ -406,17 +487,12 @@ impl UnifiedGPUCompute {
         let mut pos_x_data = vec![0.0f32; copy_size];
         let mut pos_y_data = vec![0.0f32; copy_size];
         let mut pos_z_data = vec![0.0f32; copy_size];
-        let mut vel_x_data = vec![0.0f32; copy_size];
-        let mut vel_y_data = vec![0.0f32; copy_size];
-        let mut vel_z_data = vec![0.0f32; copy_size];
 
 
         checked_copy_to(&self.pos_in_x, &mut pos_x_data, "pos_in_x")?;
         checked_copy_to(&self.pos_in_y, &mut pos_y_data, "pos_in_y")?;
         checked_copy_to(&self.pos_in_z, &mut pos_z_data, "pos_in_z")?;
-        checked_copy_to(&self.vel_in_x, &mut vel_x_data, "vel_in_x")?;
-        checked_copy_to(&self.vel_in_y, &mut vel_y_data, "vel_in_y")?;
-        checked_copy_to(&self.vel_in_z, &mut vel_z_data, "vel_in_z")?;
+        let [mut vel_x_data, mut vel_y_data, mut vel_z_data] = self.read_velocity_planes()?;
 
 
         pos_x_data.resize(actual_new_nodes, 0.0);
@@ -430,16 +506,25 @@ impl UnifiedGPUCompute {
         self.pos_in_x = DeviceBuffer::from_slice(&pos_x_data)?;
         self.pos_in_y = DeviceBuffer::from_slice(&pos_y_data)?;
         self.pos_in_z = DeviceBuffer::from_slice(&pos_z_data)?;
-        self.vel_in_x = DeviceBuffer::from_slice(&vel_x_data)?;
-        self.vel_in_y = DeviceBuffer::from_slice(&vel_y_data)?;
-        self.vel_in_z = DeviceBuffer::from_slice(&vel_z_data)?;
-
-        self.pos_out_x = DeviceBuffer::from_slice(&pos_x_data)?;
-        self.pos_out_y = DeviceBuffer::from_slice(&pos_y_data)?;
-        self.pos_out_z = DeviceBuffer::from_slice(&pos_z_data)?;
-        self.vel_out_x = DeviceBuffer::from_slice(&vel_x_data)?;
-        self.vel_out_y = DeviceBuffer::from_slice(&vel_y_data)?;
-        self.vel_out_z = DeviceBuffer::from_slice(&vel_z_data)?;
+
+        match self.state_precision {
+            StatePrecision::F32 => {
+                self.vel_in_x = DeviceBuffer::from_slice(&vel_x_data)?;
+                self.vel_in_y = DeviceBuffer::from_slice(&vel_y_data)?;
+                self.vel_in_z = DeviceBuffer::from_slice(&vel_z_data)?;
+                self.pos_out_x = DeviceBuffer::from_slice(&pos_x_data)?;
+                self.pos_out_y = DeviceBuffer::from_slice(&pos_y_data)?;
+                self.pos_out_z = DeviceBuffer::from_slice(&pos_z_data)?;
+                self.vel_out_x = DeviceBuffer::from_slice(&vel_x_data)?;
+                self.vel_out_y = DeviceBuffer::from_slice(&vel_y_data)?;
+                self.vel_out_z = DeviceBuffer::from_slice(&vel_z_data)?;
+            }
+            StatePrecision::F16 => {
+                self.vel_half_x = DeviceBuffer::from_slice(&half_storage::narrow(&vel_x_data))?;
+                self.vel_half_y = DeviceBuffer::from_slice(&half_storage::narrow(&vel_y_data))?;
+                self.vel_half_z = DeviceBuffer::from_slice(&half_storage::narrow(&vel_z_data))?;
+            }
+        }
 
 
         self.mass = DeviceBuffer::from_slice(&vec![1.0f32; actual_new_nodes])?;
@@ -111,7 +111,7 @@ impl UnifiedGPUCompute {
 
     pub fn update_memory_usage(&mut self) {
 
-        let node_memory = self.allocated_nodes * std::mem::size_of::<f32>() * 12;
+        let node_memory = self.allocated_nodes * self.state_precision.state_bytes_per_node();
         let edge_memory =
             self.allocated_edges * (std::mem::size_of::<i32>() * 2 + std::mem::size_of::<f32>());
         let grid_memory = self.max_grid_cells * std::mem::size_of::<i32>() * 4;
@@ -139,9 +139,11 @@ mod community;
 mod leiden;
 mod async_transfer;
 mod metrics;
+pub mod half_storage;
 
 // Re-export all public types from types module
 pub use types::{ComputeMode, GPUPerformanceMetrics, curandState};
+pub use half_storage::StatePrecision;
 
 // Re-export the main struct from construction
 pub use construction::UnifiedGPUCompute;
@@ -46,6 +46,12 @@ pub struct GPUPerformanceMetrics {
     pub frames_per_second: f32,
     pub total_simulation_time: f32,
     pub last_frame_time: f32,
+
+    /// Host time spent on per-step uploads in the last `execute`.
+    pub last_step_upload_ms: f32,
+    /// CUDA event time of the last `execute`'s kernels; `None` if the
+    /// stability gate skipped the step.
+    pub last_step_kernel_ms: Option<f32>,
 }
 
 impl Default for GPUPerformanceMetrics {
@@ -68,6 +74,8 @@ impl Default for GPUPerformanceMetrics {
             frames_per_second: 0.0,
             total_simulation_time: 0.0,
             last_frame_time: 0.0,
+            last_step_upload_ms: 0.0,
+            last_step_kernel_ms: None,
         }
     }
 }
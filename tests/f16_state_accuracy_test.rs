@@ -0,0 +1,114 @@
+//! F16 node-state storage against the f32 path, through the real kernels.
+//!
+//! Two contexts get the same graph and starting layout; one stores
+//! velocities as f16 (`StatePrecision::F16`). Both run the same number of
+//! physics steps and the final layouts must agree to within a small
+//! fraction of the layout's extent.
+//!
+//! GPU GATING: needs a CUDA device and the compiled PTX. Run on the GPU CI
+//! runner via `cargo test -- --ignored`; a host without either skips.
+
+use visionclaw_gpu::ptx_loader::{load_ptx_module_sync, PTXModule};
+use visionclaw_server::models::simulation_params::SimulationParams;
+use visionclaw_server::utils::unified_gpu_compute::{StatePrecision, UnifiedGPUCompute};
+
+const NODES: usize = 512;
+const STEPS: usize = 300;
+
+/// Ring with a chord every seventh node, both directions, as CSR.
+fn ring_with_chords() -> (Vec<i32>, Vec<i32>, Vec<f32>) {
+    let mut adj: Vec<Vec<i32>> = vec![Vec::new(); NODES];
+    for i in 0..NODES {
+        let mut link = |j: usize| {
+            adj[i].push(j as i32);
+            adj[j].push(i as i32);
+        };
+        link((i + 1) % NODES);
+        if i % 7 == 0 {
+            link((i + NODES / 3) % NODES);
+        }
+    }
+    let mut offsets = vec![0i32];
+    let mut indices = Vec::new();
+    for a in &adj {
+        indices.extend_from_slice(a);
+        offsets.push(indices.len() as i32);
+    }
+    let weights = vec![1.0f32; indices.len()];
+    (offsets, indices, weights)
+}
+
+/// Golden-angle spiral so no two nodes start on top of each other.
+fn spiral() -> (Vec<f32>, Vec<f32>, Vec<f32>) {
+    let mut x = Vec::with_capacity(NODES);
+    let mut y = Vec::with_capacity(NODES);
+    let mut z = Vec::with_capacity(NODES);
+    for i in 0..NODES {
+        let a = i as f32 * 2.399_963;
+        let r = 20.0 + i as f32 * 0.5;
+        x.push(r * a.cos());
+        y.push(r * a.sin());
+        z.push((i % 11) as f32 * 4.0 - 20.0);
+    }
+    (x, y, z)
+}
+
+fn run(ptx: &str, precision: StatePrecision) -> Option<Vec<[f32; 3]>> {
+    let (offsets, indices, weights) = ring_with_chords();
+    let mut gpu = match UnifiedGPUCompute::new(NODES, indices.len(), ptx) {
+        Ok(gpu) => gpu,
+        Err(e) => {
+            eprintln!("SKIP f16 accuracy: no CUDA device: {e}");
+            return None;
+        }
+    };
+    gpu.set_state_precision(precision).expect("set_state_precision");
+    gpu.upload_edges_csr(&offsets, &indices, &weights)
+        .expect("upload_edges_csr");
+    let (x, y, z) = spiral();
+    gpu.upload_positions(&x, &y, &z).expect("upload_positions");
+
+    let params = SimulationParams::default();
+    for _ in 0..STEPS {
+        gpu.execute_physics_step_with_bypass(&params, true)
+            .expect("physics step");
+    }
+    let (x, y, z) = gpu.get_node_positions().expect("download positions");
+    Some((0..NODES).map(|i| [x[i], y[i], z[i]]).collect())
+}
+
+#[test]
+#[ignore = "needs GPU: real CUDA device + compiled PTX (run with --ignored on GPU CI)"]
+fn f16_storage_tracks_the_f32_layout() {
+    let ptx = match load_ptx_module_sync(PTXModule::VisionflowUnified) {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("SKIP f16 accuracy: unified PTX unavailable: {e}");
+            return;
+        }
+    };
+    let Some(reference) = run(&ptx, StatePrecision::F32) else {
+        return;
+    };
+    let half = run(&ptx, StatePrecision::F16).expect("F32 ran, so F16 must too");
+
+    let extent = reference
+        .iter()
+        .flat_map(|p| p.iter())
+        .fold(0.0f32, |m, v| m.max(v.abs()));
+    assert!(extent > 10.0, "layout collapsed: extent {extent}");
+    assert!(
+        half.iter().flatten().all(|v| v.is_finite()),
+        "f16 layout has non-finite positions"
+    );
+
+    let worst = reference
+        .iter()
+        .zip(&half)
+        .map(|(a, b)| ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt())
+        .fold(0.0f32, f32::max);
+    assert!(
+        worst <= extent * 0.05,
+        "f16 drifted {worst} from f32 over extent {extent}"
+    );
+}